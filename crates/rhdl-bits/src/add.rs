@@ -66,17 +66,30 @@ use std::ops::AddAssign;
 
 use super::BitWidth;
 use super::bits_impl::Bits;
-use super::bits_impl::bits_masked;
 use super::dyn_bits::DynBits;
+use super::limbs::Limbs;
 use super::signed_bits_impl::SignedBits;
-use super::signed_bits_impl::signed_wrapped;
 use super::signed_dyn_bits::SignedDynBits;
 // By default, all add operations are wrapping.
 
-impl_binop!(Add, add, u128::wrapping_add);
-impl_assign_op!(AddAssign, add_assign, u128::wrapping_add);
-impl_signed_binop!(Add, add, i128::wrapping_add);
-impl_assigned_signed_op!(AddAssign, add_assign, i128::wrapping_add);
+impl_binop!(Add, add, u128::wrapping_add, Limbs::wrapping_add);
+impl_assign_op!(
+    AddAssign,
+    add_assign,
+    u128::wrapping_add,
+    Limbs::wrapping_add
+);
+impl_signed_binop!(Add, add, i128::wrapping_add, Limbs::wrapping_add);
+impl_assigned_signed_op!(
+    AddAssign,
+    add_assign,
+    i128::wrapping_add,
+    Limbs::wrapping_add
+);
+impl_wide_binop!(Add, add, Limbs::wrapping_add);
+impl_wide_assign_op!(AddAssign, add_assign, Limbs::wrapping_add);
+impl_wide_signed_binop!(Add, add, Limbs::wrapping_add);
+impl_wide_assigned_signed_op!(AddAssign, add_assign, Limbs::wrapping_add);
 
 #[cfg(test)]
 mod test {
@@ -35,11 +35,13 @@
 //! ```
 use std::ops::{BitAnd, BitAndAssign};
 
-use super::bits_impl::bits_masked;
+use super::limbs::Limbs;
 use super::{BitWidth, bits_impl::Bits, dyn_bits::DynBits};
 
-impl_binop!(BitAnd, bitand, u128::bitand);
-impl_assign_op!(BitAndAssign, bitand_assign, u128::bitand);
+impl_binop!(BitAnd, bitand, u128::bitand, Limbs::bitand);
+impl_assign_op!(BitAndAssign, bitand_assign, u128::bitand, Limbs::bitand);
+impl_wide_binop!(BitAnd, bitand, Limbs::bitand);
+impl_wide_assign_op!(BitAndAssign, bitand_assign, Limbs::bitand);
 
 #[cfg(test)]
 mod test {
@@ -1,6 +1,7 @@
 #![allow(non_camel_case_types)]
 use super::{BitWidth, dyn_bits::DynBits, signed, signed_bits_impl::SignedBits};
 use crate::bitwidth::W;
+use crate::limbs::Limbs;
 use seq_macro::seq;
 /// The [Bits] type is a fixed-sized bit vector.  It is meant to
 /// imitate the behavior of bit vectors in hardware.  Due to the
@@ -11,7 +12,9 @@ use seq_macro::seq;
 /// you cannot perform arbitrary arithmetic on longer bit values in your
 /// hardware designs.  I don't think this is a significant issue, but
 /// the [Bits] design of the `rust-hdl` crate was much slower and harder
-/// to maintain and use.  I think this is a good trade-off.
+/// to maintain and use.  I think this is a good trade-off.  If you
+/// really do need arithmetic on wider values, see
+/// [WideBits](crate::WideBits).
 ///
 /// Note that the [Bits] type implements 2's complement arithmetic.
 /// See <https://en.wikipedia.org/wiki/Two%27s_complement> for more
@@ -188,14 +191,36 @@ where
     pub const fn raw(self) -> u128 {
         self.0
     }
+    /// Extract the raw `u128` behind the [Bits] value.  This
+    /// always succeeds, and is provided for symmetry with
+    /// [WideBits::try_raw](crate::WideBits::try_raw).
+    pub const fn try_raw(self) -> Option<u128> {
+        Some(self.0)
+    }
+    /// Convert the value to [Limbs].
+    pub const fn limbs(self) -> Limbs {
+        Limbs::from_u128(self.0)
+    }
+    /// Build a [Bits] value from [Limbs].
+    ///
+    /// # Panics
+    /// Panics if the value does not fit in `N` bits.
+    pub const fn from_limbs(value: Limbs) -> Self {
+        assert!(value.fits_unsigned(N));
+        Self(value.to_u128())
+    }
+    /// Build a [Bits] value from [Limbs], discarding any bits
+    /// that do not fit in `N` bits.
+    pub const fn from_limbs_masked(value: Limbs) -> Self {
+        bits_masked(value.to_u128())
+    }
     /// Convert the compile-time sized [Bits] to a run-time
     /// tracked [DynBits] value.
     pub const fn dyn_bits(self) -> DynBits {
         DynBits {
-            val: self.raw(),
+            val: self.limbs(),
             bits: N,
         }
-        .wrapped()
     }
     /// Build a (dynamic, stack allocated) vector containing
     /// the bits that make up this value.  This will be slow.
@@ -232,6 +257,96 @@ where
         x ^= x >> 64;
         x & 1 == 1
     }
+    /// Add two values, clamping the result to [Bits::MAX] if the
+    /// sum does not fit.
+    /// Available in synthesizable functions.
+    /// ```
+    /// # use rhdl_bits::alias::*;
+    /// assert_eq!(b8(200).saturating_add(b8(100)), b8(255));
+    /// assert_eq!(b8(20).saturating_add(b8(100)), b8(120));
+    /// ```
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        match self.overflowing_add(rhs) {
+            (_, true) => Self::MAX,
+            (sum, false) => sum,
+        }
+    }
+    /// Subtract two values, clamping the result to zero if the
+    /// difference would be negative.
+    /// Available in synthesizable functions.
+    /// ```
+    /// # use rhdl_bits::alias::*;
+    /// assert_eq!(b8(20).saturating_sub(b8(100)), b8(0));
+    /// assert_eq!(b8(120).saturating_sub(b8(100)), b8(20));
+    /// ```
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        match self.overflowing_sub(rhs) {
+            (_, true) => Self::ZERO,
+            (difference, false) => difference,
+        }
+    }
+    /// Add two values, returning the wrapped sum and a flag that is
+    /// set if the addition overflowed (i.e., the carry out).
+    /// Available in synthesizable functions.
+    /// ```
+    /// # use rhdl_bits::alias::*;
+    /// assert_eq!(b8(200).overflowing_add(b8(100)), (b8(44), true));
+    /// ```
+    pub fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+        let sum = self + rhs;
+        (sum, sum < self)
+    }
+    /// Subtract two values, returning the wrapped difference and a flag
+    /// that is set if the subtraction overflowed (i.e., the borrow out).
+    /// Available in synthesizable functions.
+    /// ```
+    /// # use rhdl_bits::alias::*;
+    /// assert_eq!(b8(20).overflowing_sub(b8(100)), (b8(176), true));
+    /// ```
+    pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+        (self - rhs, self < rhs)
+    }
+    /// Count the number of bits that are set.  The count is returned
+    /// as a value of the same width (which is always wide enough to hold it).
+    /// Available in synthesizable functions.
+    /// ```
+    /// # use rhdl_bits::alias::*;
+    /// assert_eq!(b8(0b1011_0010).count_ones(), b8(4));
+    /// ```
+    pub fn count_ones(self) -> Self {
+        bits(self.raw().count_ones() as u128)
+    }
+    /// Count the number of zero bits above the highest set bit.  The count
+    /// is returned as a value of the same width.
+    /// Available in synthesizable functions.
+    /// ```
+    /// # use rhdl_bits::alias::*;
+    /// assert_eq!(b8(0b0001_0010).leading_zeros(), b8(3));
+    /// assert_eq!(b8(0).leading_zeros(), b8(8));
+    /// ```
+    pub fn leading_zeros(self) -> Self {
+        bits((self.raw().leading_zeros() as usize - (128 - N)) as u128)
+    }
+    /// Count the number of zero bits below the lowest set bit.  The count
+    /// is returned as a value of the same width.
+    /// Available in synthesizable functions.
+    /// ```
+    /// # use rhdl_bits::alias::*;
+    /// assert_eq!(b8(0b0001_0100).trailing_zeros(), b8(2));
+    /// assert_eq!(b8(0).trailing_zeros(), b8(8));
+    /// ```
+    pub fn trailing_zeros(self) -> Self {
+        bits((self.raw().trailing_zeros() as usize).min(N) as u128)
+    }
+    /// Reverse the order of the bits.
+    /// Available in synthesizable functions.
+    /// ```
+    /// # use rhdl_bits::alias::*;
+    /// assert_eq!(b8(0b0001_0110).reverse_bits(), b8(0b0110_1000));
+    /// ```
+    pub fn reverse_bits(self) -> Self {
+        bits(self.raw().reverse_bits() >> (128 - N))
+    }
     /// Shift left by a constant amount, returning a [DynBits] value.
     /// The output size is increased by the shift amount.
     /// Available in synthesizable functions.
@@ -9,6 +9,10 @@
 //! ```
 //! This is a work around for the constraint `where N >= 1 && N <= 128` which is not
 //! currently supported in Rust.
+//!
+//! The [WideBitWidth] marker trait plays the same role for the [WideBits](crate::WideBits)
+//! and [SignedWideBits](crate::SignedWideBits) types, and covers the range
+//! 1..=[MAX_BITS](crate::MAX_BITS).
 
 /// A marker trait used to constrain a const generic parameter to the range `1..=128`
 /// This is used to ensure that the number of bits in a [Bits](crate::Bits) or [SignedBits](crate::SignedBits)
@@ -31,8 +35,24 @@ pub trait BitWidth {}
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct W<const N: usize>;
 
+/// A marker trait used to constrain a const generic parameter to the range `1..=MAX_BITS`
+/// This is used to ensure that the number of bits in a [WideBits](crate::WideBits) or
+/// [SignedWideBits](crate::SignedWideBits) value is in a valid range.
+/// ```
+/// use rhdl_bits::*;
+/// fn takes_wide_bit_widths<const N: usize>(value: WideBits<N>) where W<N> : WideBitWidth {}
+/// ```
+pub trait WideBitWidth {}
+
 seq_macro::seq!(N in 1..=128 {
     #(
         impl BitWidth for W<N> {}
     )*
 });
+
+// Keep the upper bound in sync with `crate::MAX_BITS`
+seq_macro::seq!(N in 1..=512 {
+    #(
+        impl WideBitWidth for W<N> {}
+    )*
+});
@@ -0,0 +1,158 @@
+//! # Division operations via `/` and `/=`
+//!
+//! Division follows the same rules as Rust integer division.  The quotient is
+//! truncated towards zero.  Unlike Rust, dividing by zero does not panic.  Instead, the
+//! quotient is all ones (i.e., the maximum value for [Bits] and -1 for [SignedBits]),
+//! which is also what the generated hardware produces.  Here is a simple example
+//! of dividing 2 8-bit unsigned values:
+//! ```
+//! # use rhdl_bits::*;
+//! # use rhdl_bits::alias::*;
+//! let a: Bits<8> = 200.into();
+//! let b: Bits<8> = 7.into();
+//! let c = a / b; // 28
+//! assert_eq!(c, b8(28));
+//! ```
+//!
+//! We can convert them to [DynBits] and divide them too:
+//! ```
+//! # use rhdl_bits::*;
+//! # use rhdl_bits::alias::*;
+//! # let a: Bits<8> = 200.into();
+//! # let b: Bits<8> = 7.into();
+//! let a = a.dyn_bits();
+//! let b = b.dyn_bits();
+//! let c = a / b; // 28
+//! assert_eq!(c.as_bits::<8>(), b8(28));
+//! ```
+//!
+//! When working with signed values, remember to put parentheses around negative literals:
+//! ```
+//! # use rhdl_bits::*;
+//! # use rhdl_bits::alias::*;
+//! let a : SignedBits<8> = (-100).into();
+//! let b : SignedBits<8> = 7.into();
+//! let c = a / b; // -14
+//! assert_eq!(c, s8(-14));
+//! ```
+//!
+//! Signed division wraps in the one case where it can overflow.  Dividing the
+//! most negative value by `-1` returns the most negative value, just as
+//! [i8::wrapping_div] does:
+//! ```
+//! # use rhdl_bits::*;
+//! # use rhdl_bits::alias::*;
+//! let a : SignedBits<8> = (-128).into();
+//! let c = a / (-1); // -128
+//! assert_eq!(c, s8(-128));
+//! ```
+//!
+//! Note that you cannot mix unsigned and signed types, any more than you can divide `i8` and `u8` in normal Rust.
+//!
+//! You can also use the `/=` operator to divide and assign in place:
+//! ```
+//! # use rhdl_bits::*;
+//! # use rhdl_bits::alias::*;
+//! let mut a: Bits<8> = 200.into();
+//! a /= 10; // a is now 20
+//! assert_eq!(a, b8(20));
+//! ```
+
+use std::ops::Div;
+use std::ops::DivAssign;
+
+use super::BitWidth;
+use super::bits_impl::Bits;
+use super::dyn_bits::DynBits;
+use super::limbs::Limbs;
+use super::signed_bits_impl::SignedBits;
+use super::signed_dyn_bits::SignedDynBits;
+
+// Dividing by zero yields all ones (i.e., -1 for signed values), like the
+// generated hardware and the [Limbs] implementation.
+fn wrapping_div(lhs: u128, rhs: u128) -> u128 {
+    lhs.checked_div(rhs).unwrap_or(u128::MAX)
+}
+
+fn wrapping_div_signed(lhs: i128, rhs: i128) -> i128 {
+    if rhs == 0 { -1 } else { lhs.wrapping_div(rhs) }
+}
+
+impl_binop!(Div, div, wrapping_div, Limbs::wrapping_div);
+impl_assign_op!(DivAssign, div_assign, wrapping_div, Limbs::wrapping_div);
+impl_signed_binop!(Div, div, wrapping_div_signed, Limbs::wrapping_div_signed);
+impl_assigned_signed_op!(
+    DivAssign,
+    div_assign,
+    wrapping_div_signed,
+    Limbs::wrapping_div_signed
+);
+impl_wide_binop!(Div, div, Limbs::wrapping_div);
+impl_wide_assign_op!(DivAssign, div_assign, Limbs::wrapping_div);
+impl_wide_signed_binop!(Div, div, Limbs::wrapping_div_signed);
+impl_wide_assigned_signed_op!(DivAssign, div_assign, Limbs::wrapping_div_signed);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_div() {
+        // Zero is excluded, since the test also divides by the left argument
+        for i in 1..=255 {
+            for j in 1..=255 {
+                test_binop!(/, u128::wrapping_div, i, j);
+            }
+        }
+    }
+
+    #[test]
+    fn test_div_by_zero_is_all_ones() {
+        let a: Bits<8> = 20.into();
+        assert_eq!(a / Bits::<8>::from(0), Bits::<8>::MAX);
+        let a: SignedBits<8> = (-20).into();
+        assert_eq!(a / SignedBits::<8>::from(0), SignedBits::<8>::from(-1));
+        let a: SignedBits<8> = 20.into();
+        assert_eq!(a / SignedBits::<8>::from(0), SignedBits::<8>::from(-1));
+    }
+
+    #[test]
+    fn test_division_matches_built_in_behavior_for_i8() {
+        for i in i8::MIN..=i8::MAX {
+            for j in i8::MIN..=i8::MAX {
+                if j == 0 {
+                    continue;
+                }
+                let i_as_signed = SignedBits::<8>::from(i as i128);
+                let j_as_signed = SignedBits::<8>::from(j as i128);
+                let k_as_signed = i_as_signed / j_as_signed;
+                let k = i8::wrapping_div(i, j);
+                assert_eq!(k_as_signed.raw(), k as i128);
+                let k_as_dyn = i_as_signed.dyn_bits() / j_as_signed.dyn_bits();
+                assert_eq!(k_as_dyn.as_signed_bits::<8>(), k_as_signed);
+            }
+        }
+    }
+
+    #[test]
+    fn test_division_i128() {
+        for i in [i128::MIN, -7, -1, 1, 7, i128::MAX] {
+            for j in [i128::MIN, -7, -1, 1, 7, i128::MAX] {
+                let i_as_signed = SignedBits::<128>::from(i);
+                let j_as_signed = SignedBits::<128>::from(j);
+                let k_as_signed = i_as_signed / j_as_signed;
+                assert_eq!(k_as_signed.raw(), i128::wrapping_div(i, j));
+            }
+        }
+    }
+
+    #[test]
+    fn test_divassign() {
+        let mut x = SignedBits::<8>::from(-100);
+        x /= 3;
+        assert_eq!(x.raw(), -33);
+        let mut y = Bits::<8>::from(100);
+        y /= Bits::<8>::from(3);
+        assert_eq!(y.raw(), 33);
+    }
+}
@@ -17,16 +17,16 @@
 //!
 //! The RHDL compiler will check that the bit conversions are valid.
 //!
-use crate::{bits_impl::bits_masked, bitwidth::W};
+use crate::{bitwidth::W, limbs::Limbs, limbs::MAX_BITS};
 
-use super::{BitWidth, Bits, signed_dyn_bits::SignedDynBits};
+use super::{BitWidth, Bits, WideBitWidth, WideBits, signed_dyn_bits::SignedDynBits};
 
 /// A bit vector whose size is determined at runtime.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DynBits {
     /// The raw value of the bits.  Only the lower `bits` bits are valid.
-    pub(crate) val: u128,
-    /// The number of bits in this value.  Must be in the range 1..=128.
+    pub(crate) val: Limbs,
+    /// The number of bits in this value.  Must be in the range 1..=[MAX_BITS].
     pub(crate) bits: usize,
 }
 
@@ -39,21 +39,21 @@ impl std::fmt::Debug for DynBits {
 impl DynBits {
     pub(crate) const fn masked(self) -> DynBits {
         DynBits {
-            val: self.val & self.mask(),
+            val: self.val.bitand(self.mask()),
             bits: self.bits,
         }
     }
     /// Returns a mask with the lower `bits` bits set to 1.
-    pub const fn mask(self) -> u128 {
-        u128::MAX >> (128 - self.bits)
+    pub const fn mask(self) -> Limbs {
+        Limbs::mask(self.bits)
     }
     /// Zero extend the [DynBits] value to a larger size, returning a [DynBits] value.
     /// The output size is increased by M bits.
     ///
     /// # Panics
-    /// Panics if the resulting size would be greater than 128 bits.
+    /// Panics if the resulting size would be greater than [MAX_BITS] bits.
     pub const fn xext<const M: usize>(self) -> DynBits {
-        assert!((M + self.bits) <= 128);
+        assert!((M + self.bits) <= MAX_BITS);
         DynBits {
             val: self.val,
             bits: M + self.bits,
@@ -67,7 +67,7 @@ impl DynBits {
     pub const fn xshr<const M: usize>(self) -> DynBits {
         assert!(self.bits > M);
         DynBits {
-            val: self.val >> M,
+            val: self.val.wrapping_shr(M as u32),
             bits: self.bits - M,
         }
     }
@@ -75,11 +75,11 @@ impl DynBits {
     /// The output size is increased by the shift amount.
     ///
     /// # Panics
-    /// Panics if the resulting size would be greater than 128 bits.
+    /// Panics if the resulting size would be greater than [MAX_BITS] bits.
     pub const fn xshl<const M: usize>(self) -> DynBits {
-        assert!((M + self.bits) <= 128);
+        assert!((M + self.bits) <= MAX_BITS);
         DynBits {
-            val: self.val << M,
+            val: self.val.wrapping_shl(M as u32),
             bits: self.bits + M,
         }
     }
@@ -88,9 +88,9 @@ impl DynBits {
     /// the value is zero-extended.
     ///
     /// # Panics
-    /// Panics if the new size is zero or greater than 128 bits.
+    /// Panics if the new size is zero or greater than [MAX_BITS] bits.
     pub const fn resize<const M: usize>(self) -> DynBits {
-        assert!(M <= 128);
+        assert!(M <= MAX_BITS);
         assert!(M != 0);
         DynBits {
             val: self.val,
@@ -100,55 +100,58 @@ impl DynBits {
     }
     /// Returns the raw value of the bits, without masking.
     /// Only the lower `bits` bits are valid.
+    ///
+    /// # Panics
+    /// Panics if the value is wider than 128 bits.  Use [DynBits::try_raw]
+    /// or [DynBits::limbs] to access wider values.
     pub const fn raw(self) -> u128 {
+        assert!(
+            self.bits <= 128,
+            "DynBits wider than 128 bits do not fit in a u128"
+        );
+        self.val.to_u128()
+    }
+    /// Returns the value as a `u128`, or `None` if the value
+    /// does not fit in 128 bits.
+    pub const fn try_raw(self) -> Option<u128> {
+        if self.val.fits_unsigned(128) {
+            Some(self.val.to_u128())
+        } else {
+            None
+        }
+    }
+    /// Returns the [Limbs] holding the value.
+    pub const fn limbs(self) -> Limbs {
         self.val
     }
     /// Convert to a [SignedDynBits] value, interpreting the value as a signed integer
     /// in two's complement representation.
     /// The output size is the same as the input size.
     pub const fn as_signed(self) -> SignedDynBits {
-        if self.val & (1 << (self.bits - 1)) != 0 {
-            SignedDynBits {
-                val: self.val as i128 | !self.mask() as i128,
-                bits: self.bits,
-            }
-        } else {
-            SignedDynBits {
-                val: self.val as i128,
-                bits: self.bits,
-            }
+        SignedDynBits {
+            val: self.val.sign_extend(self.bits),
+            bits: self.bits,
         }
     }
     /// Returns true if any bit is set.
     /// Can be called in a synthesizable context
     pub const fn any(self) -> bool {
-        (self.val & self.mask()) != 0
+        !self.val.bitand(self.mask()).is_zero()
     }
     /// Returns true if all bits are set.
     /// Can be called in a synthesizable context
     pub const fn all(self) -> bool {
-        (self.val & self.mask()) == self.mask()
+        self.val.bitand(self.mask()).const_eq(self.mask())
     }
     /// Returns true if the number of set bits is odd.
     /// Can be called in a synthesizable context
     pub const fn xor(self) -> bool {
-        let mut x = self.val & self.mask();
-        x ^= x >> 1;
-        x ^= x >> 2;
-        x ^= x >> 4;
-        x ^= x >> 8;
-        x ^= x >> 16;
-        x ^= x >> 32;
-        x ^= x >> 64;
-        x & 1 == 1
+        self.val.bitand(self.mask()).count_ones() % 2 == 1
     }
     /// Wrap the value to fit in the specified number of bits, returning a [DynBits] value.
     /// The output size is the same as the input size.
     pub const fn wrapped(self) -> DynBits {
-        DynBits {
-            val: self.val & self.mask(),
-            bits: self.bits,
-        }
+        self.masked()
     }
     /// Convert to a [Bits] value of the specified size.
     /// # Panics
@@ -159,7 +162,18 @@ impl DynBits {
         W<N>: BitWidth,
     {
         assert!(self.bits == N);
-        bits_masked(self.val)
+        Bits::from_limbs_masked(self.val)
+    }
+    /// Convert to a [WideBits] value of the specified size.
+    /// # Panics
+    /// Panics if the size does not match the number of bits in this value.
+    /// Can be called in a synthesizable context
+    pub const fn as_wide_bits<const N: usize>(self) -> WideBits<N>
+    where
+        W<N>: WideBitWidth,
+    {
+        assert!(self.bits == N);
+        WideBits::from_limbs_masked(self.val)
     }
     /// The number of bits in this [DynBits] value.
     pub const fn bits(self) -> usize {
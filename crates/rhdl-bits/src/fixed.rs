@@ -0,0 +1,861 @@
+//! Fixed point numbers
+//!
+//! The [Fixed] and [SignedFixed] types represent binary fixed point
+//! numbers.  A `Fixed<I, F>` value has `I` integer bits and `F` fractional
+//! bits, and is stored as an `I + F` bit unsigned integer scaled by `2^-F`.
+//! A `SignedFixed<I, F>` value is the 2's complement equivalent, where the
+//! `I` integer bits include the sign bit.  So a `SignedFixed<4, 4>` covers
+//! the range `-8.0` to `7.9375` in steps of `1/16`.
+//!
+//! ```
+//! # use rhdl_bits::fixed::*;
+//! let a = Fixed::<4, 4>::from_f64(2.75);
+//! assert_eq!(a.raw(), 0x2C);
+//! assert_eq!(a.to_f64(), 2.75);
+//! let b = SignedFixed::<4, 4>::from_f64(-1.5);
+//! assert_eq!(b.to_f64(), -1.5);
+//! ```
+//!
+//! Adding or subtracting two values of the same type wraps, just like [Bits].
+//! To avoid overflow, use the growing operations `xadd`, `xsub` and `xmul`.
+//! As with their [Bits] counterparts, the output size must be given
+//! (usually by a type annotation).  It is checked at compile time.
+//!
+//! ```
+//! # use rhdl_bits::fixed::*;
+//! let a = Fixed::<4, 4>::from_f64(12.5);
+//! let b = Fixed::<2, 4>::from_f64(3.25);
+//! let c: Fixed<5, 4> = a.xadd(b); // max(4, 2) + 1 integer bits
+//! assert_eq!(c.to_f64(), 15.75);
+//! let d: SignedFixed<5, 4> = b.xsub(a); // Unsigned differences are signed
+//! assert_eq!(d.to_f64(), -9.25);
+//! let e: Fixed<6, 8> = a.xmul(b); // Integer and fractional bits both add
+//! assert_eq!(e.to_f64(), 40.625);
+//! ```
+//!
+//! An output of the wrong size is a compile error:
+//!
+//! ```compile_fail
+//! # use rhdl_bits::fixed::*;
+//! let a = Fixed::<2, 2>::from_raw(1);
+//! let b: Fixed<2, 2> = a.xadd(a); // Needs 3 integer bits
+//! ```
+//!
+//! Note that `xadd` and `xsub` require both arguments to have the same number
+//! of fractional bits.
+//!
+//! # Quantization
+//!
+//! Changing the format of a value is done with the [quantize()] function.  It
+//! takes a [Rounding] mode, which controls what happens to fractional bits
+//! that are dropped, and an [Overflow] mode, which controls what happens to
+//! integer bits that are dropped.
+//!
+//! ```
+//! # use rhdl_bits::fixed::*;
+//! let x = SignedFixed::<4, 4>::from_f64(2.5625);
+//! let t: SignedFixed<4, 1> = quantize::<Truncate, Wrap, _, _>(x);
+//! assert_eq!(t.to_f64(), 2.5);
+//! let h: SignedFixed<4, 1> = quantize::<HalfUp, Wrap, _, _>(x);
+//! assert_eq!(h.to_f64(), 2.5);
+//! let y = SignedFixed::<4, 4>::from_f64(7.5);
+//! let w: SignedFixed<3, 0> = quantize::<Convergent, Wrap, _, _>(y);
+//! assert_eq!(w.to_f64(), 0.0); // 8 wraps to 0 in 3 bits
+//! let s: SignedFixed<3, 0> = quantize::<Convergent, Saturate, _, _>(y);
+//! assert_eq!(s.to_f64(), 3.0);
+//! ```
+//!
+//! The [quantize()] function, the growing operations and the conversions
+//! to and from [Bits] ([Fixed::as_bits], [Bits::as_fixed](crate::Bits::as_fixed)
+//! and their signed equivalents) are all available in synthesizable functions.
+#![allow(non_camel_case_types)]
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+
+use crate::bitwidth::W;
+use crate::limbs::{Limbs, MAX_BITS};
+use crate::{BitWidth, Bits, SignedBits};
+
+/// An unsigned fixed point value with `I` integer bits and `F`
+/// fractional bits.  The total width `I + F` must be between
+/// 1 and [MAX_BITS].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Fixed<const I: usize, const F: usize>(Limbs);
+
+/// A signed (2's complement) fixed point value with `I` integer
+/// bits (including the sign bit) and `F` fractional bits.  The
+/// total width `I + F` must be between 1 and [MAX_BITS].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct SignedFixed<const I: usize, const F: usize>(Limbs);
+
+/// The format of a fixed point type, i.e., the number of integer
+/// and fractional bits, and whether it is signed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FixedFormat {
+    /// The number of integer bits (including the sign bit for signed values)
+    pub int: usize,
+    /// The number of fractional bits
+    pub frac: usize,
+    /// True if the values are signed
+    pub signed: bool,
+}
+
+impl FixedFormat {
+    /// The total number of bits in the format.
+    pub const fn width(&self) -> usize {
+        self.int + self.frac
+    }
+}
+
+/// How fractional bits are discarded when a value is quantized
+/// to a format with fewer fractional bits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// Discard the bits (round towards negative infinity)
+    Truncate,
+    /// Round to the nearest value, with ties rounded up (towards positive infinity)
+    HalfUp,
+    /// Round to the nearest value, with ties rounded to the nearest even value
+    Convergent,
+}
+
+/// How integer bits are discarded when a value is quantized
+/// to a format with fewer integer bits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OverflowMode {
+    /// Discard the bits, wrapping values that do not fit
+    Wrap,
+    /// Clamp values that do not fit to the largest (or smallest) representable value
+    Saturate,
+}
+
+/// Marker trait for the rounding mode passed to [quantize()].
+pub trait Rounding: 'static {
+    /// The rounding mode
+    const MODE: RoundingMode;
+}
+
+/// Marker trait for the overflow mode passed to [quantize()].
+pub trait Overflow: 'static {
+    /// The overflow mode
+    const MODE: OverflowMode;
+}
+
+/// Rounding marker for [RoundingMode::Truncate]
+pub struct Truncate;
+/// Rounding marker for [RoundingMode::HalfUp]
+pub struct HalfUp;
+/// Rounding marker for [RoundingMode::Convergent]
+pub struct Convergent;
+/// Overflow marker for [OverflowMode::Wrap]
+pub struct Wrap;
+/// Overflow marker for [OverflowMode::Saturate]
+pub struct Saturate;
+
+impl Rounding for Truncate {
+    const MODE: RoundingMode = RoundingMode::Truncate;
+}
+
+impl Rounding for HalfUp {
+    const MODE: RoundingMode = RoundingMode::HalfUp;
+}
+
+impl Rounding for Convergent {
+    const MODE: RoundingMode = RoundingMode::Convergent;
+}
+
+impl Overflow for Wrap {
+    const MODE: OverflowMode = OverflowMode::Wrap;
+}
+
+impl Overflow for Saturate {
+    const MODE: OverflowMode = OverflowMode::Saturate;
+}
+
+/// Trait implemented by [Fixed] and [SignedFixed] so that
+/// they can be handled generically (e.g., by [quantize()]).
+pub trait FixedPoint: Copy + 'static {
+    /// The format of the type
+    const FORMAT: FixedFormat;
+    /// The backing [Limbs] (zero extended for unsigned values, and
+    /// sign extended for signed values).
+    fn limbs(self) -> Limbs;
+    /// Build a value from the lower bits of the given [Limbs],
+    /// discarding any bits that do not fit.
+    fn from_limbs_wrapped(value: Limbs) -> Self;
+}
+
+const fn check_width(int: usize, frac: usize) -> usize {
+    assert!(
+        int + frac >= 1 && int + frac <= MAX_BITS,
+        "Fixed point values must have between 1 and MAX_BITS bits"
+    );
+    int + frac
+}
+
+// The number of integer bits needed to hold the sum or difference
+// of values with `a` and `b` integer bits.
+const fn grown_width(a: usize, b: usize) -> usize {
+    if a > b { a + 1 } else { b + 1 }
+}
+
+// Compute the real value of the low `width` bits of `value`, where the
+// top bit has a negative weight if the value is signed.
+fn limbs_to_f64(value: Limbs, width: usize, frac: usize, signed: bool) -> f64 {
+    // Negative values are converted via their magnitude, so that the
+    // result does not suffer from cancellation against the sign bit.
+    if signed && value.bit(width - 1) {
+        return -limbs_to_f64(value.wrapping_neg(), width, frac, false);
+    }
+    (0..width)
+        .rev()
+        .filter(|&ndx| value.bit(ndx))
+        .map(|ndx| 2.0_f64.powi(ndx as i32 - frac as i32))
+        .sum()
+}
+
+// Scale `value` by `2^frac` and round it to the nearest integer that fits in
+// the given format, clamping values that are out of range.
+fn limbs_from_f64(value: f64, width: usize, frac: usize, signed: bool) -> Limbs {
+    if value.is_nan() {
+        return Limbs::ZERO;
+    }
+    let value = (value * 2.0_f64.powi(frac as i32)).round();
+    let (min, max) = if signed {
+        (Limbs::mask(width - 1).not(), Limbs::mask(width - 1))
+    } else {
+        (Limbs::ZERO, Limbs::mask(width))
+    };
+    let positive_bits = (if signed { width - 1 } else { width }) as i32;
+    if value >= 2.0_f64.powi(positive_bits) {
+        return max;
+    }
+    if (signed && value < -(2.0_f64.powi(positive_bits))) || (!signed && value < 0.0) {
+        return min;
+    }
+    // Split the (integral) value into its mantissa and exponent
+    let magnitude = value.abs();
+    let raw = magnitude.to_bits();
+    let exponent = ((raw >> 52) & 0x7FF) as i32;
+    if exponent == 0 {
+        return Limbs::ZERO;
+    }
+    let mantissa = Limbs::from_u128(((raw & ((1 << 52) - 1)) | (1 << 52)) as u128);
+    let shift = exponent - 1075;
+    let magnitude = if shift >= 0 {
+        mantissa.wrapping_shl(shift as u32)
+    } else {
+        mantissa.wrapping_shr((-shift) as u32)
+    };
+    if value < 0.0 {
+        magnitude.wrapping_neg()
+    } else {
+        magnitude
+    }
+}
+
+fn requantize(
+    value: Limbs,
+    from: FixedFormat,
+    to: FixedFormat,
+    rounding: RoundingMode,
+    overflow: OverflowMode,
+) -> Limbs {
+    let signed = from.signed;
+    let mut value = value;
+    if from.frac > to.frac {
+        let shift = from.frac - to.frac;
+        let half = Limbs::from_u128(1).wrapping_shl(shift as u32 - 1);
+        let bias = match rounding {
+            RoundingMode::Truncate => Limbs::ZERO,
+            RoundingMode::HalfUp => half,
+            RoundingMode::Convergent => {
+                let lsb = value.wrapping_shr(shift as u32).bitand(Limbs::from_u128(1));
+                half.wrapping_sub(Limbs::from_u128(1)).wrapping_add(lsb)
+            }
+        };
+        value = value.wrapping_add(bias);
+        value = if signed {
+            value.wrapping_shr_signed(shift as u32)
+        } else {
+            value.wrapping_shr(shift as u32)
+        };
+    } else {
+        value = value.wrapping_shl((to.frac - from.frac) as u32);
+    }
+    let width = to.width();
+    if overflow == OverflowMode::Saturate {
+        if signed {
+            let max = Limbs::mask(width - 1);
+            let min = max.not();
+            if value.cmp_signed(max) == Ordering::Greater {
+                value = max;
+            } else if value.cmp_signed(min) == Ordering::Less {
+                value = min;
+            }
+        } else {
+            let max = Limbs::mask(width);
+            if value.cmp_unsigned(max) == Ordering::Greater {
+                value = max;
+            }
+        }
+    }
+    if signed {
+        value.sign_extend(width)
+    } else {
+        value.bitand(Limbs::mask(width))
+    }
+}
+
+/// Quantize a fixed point value into a different format.
+///
+/// Fractional bits that are dropped are rounded using the `R` [Rounding]
+/// mode, and integer bits that are dropped are handled using the `O`
+/// [Overflow] mode.  The input and output types must have the same
+/// signedness.  Available in synthesizable functions, where all four
+/// type parameters must be given explicitly.
+/// ```
+/// # use rhdl_bits::fixed::*;
+/// let x = Fixed::<4, 4>::from_f64(2.5);
+/// let y: Fixed<4, 0> = quantize::<Convergent, Wrap, _, _>(x);
+/// assert_eq!(y.raw(), 2);
+/// let y: Fixed<4, 0> = quantize::<HalfUp, Wrap, _, _>(x);
+/// assert_eq!(y.raw(), 3);
+/// ```
+///
+/// # Panics
+/// Panics if `T` and `S` do not have the same signedness.
+pub fn quantize<R: Rounding, O: Overflow, T: FixedPoint, S: FixedPoint>(x: T) -> S {
+    assert_eq!(
+        T::FORMAT.signed,
+        S::FORMAT.signed,
+        "Quantize cannot change the signedness of a fixed point value"
+    );
+    S::from_limbs_wrapped(requantize(
+        x.limbs(),
+        T::FORMAT,
+        S::FORMAT,
+        R::MODE,
+        O::MODE,
+    ))
+}
+
+/// This struct is needed so that the `quantize` function can be used in synthesizable
+/// contexts.
+#[doc(hidden)]
+pub struct quantize<R, O, T, S> {
+    _marker: PhantomData<(R, O, T, S)>,
+}
+
+impl<const I: usize, const F: usize> Fixed<I, F> {
+    /// The total number of bits in the value.
+    pub const WIDTH: usize = check_width(I, F);
+    /// The value zero.
+    pub const ZERO: Self = Self(Limbs::ZERO);
+    /// The smallest representable value (zero).
+    pub const MIN: Self = Self(Limbs::ZERO);
+    /// The largest representable value.
+    pub const MAX: Self = Self(Limbs::mask(Self::WIDTH));
+    /// Build a value from its raw (scaled) integer representation.
+    /// ```
+    /// # use rhdl_bits::fixed::Fixed;
+    /// const HALF: Fixed<1, 1> = Fixed::from_raw(1);
+    /// assert_eq!(HALF.to_f64(), 0.5);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if the value does not fit in `I + F` bits.
+    pub const fn from_raw(value: u128) -> Self {
+        Self::from_limbs(Limbs::from_u128(value))
+    }
+    /// Return the raw (scaled) integer representation of the value.
+    ///
+    /// # Panics
+    /// Panics if `I + F` is larger than 128.
+    pub const fn raw(self) -> u128 {
+        assert!(
+            Self::WIDTH <= 128,
+            "Fixed wider than 128 bits does not fit in a u128"
+        );
+        self.0.to_u128()
+    }
+    /// Build a value from its raw (scaled) representation as [Limbs].
+    ///
+    /// # Panics
+    /// Panics if the value does not fit in `I + F` bits.
+    pub const fn from_limbs(value: Limbs) -> Self {
+        assert!(value.fits_unsigned(Self::WIDTH));
+        Self(value)
+    }
+    /// Extract the [Limbs] backing the value.
+    pub const fn limbs(self) -> Limbs {
+        self.0
+    }
+    /// Convert a real number into the nearest representable value.
+    /// Values outside the representable range saturate.
+    /// Not available in synthesizable functions.
+    pub fn from_f64(value: f64) -> Self {
+        Self(limbs_from_f64(value, Self::WIDTH, F, false))
+    }
+    /// Convert the value into a real number.
+    /// Not available in synthesizable functions.
+    pub fn to_f64(self) -> f64 {
+        limbs_to_f64(self.0, Self::WIDTH, F, false)
+    }
+    /// Build a (dynamic, stack allocated) vector containing
+    /// the bits that make up this value.
+    /// Not available in synthesizable functions.
+    pub fn to_bools(self) -> Vec<bool> {
+        (0..Self::WIDTH).map(|ndx| self.0.bit(ndx)).collect()
+    }
+    /// Reinterpret the value as a [Bits] value with `N = I + F` bits.
+    /// Available in synthesizable functions.
+    ///
+    /// # Panics
+    /// Panics if `N` is not equal to `I + F`.
+    pub fn as_bits<const N: usize>(self) -> Bits<N>
+    where
+        W<N>: BitWidth,
+    {
+        assert_eq!(N, Self::WIDTH, "Fixed value has {} bits", Self::WIDTH);
+        Bits::from_limbs(self.0)
+    }
+    /// Add two values, producing a result with one more integer
+    /// bit than the larger of the two.  The output must have
+    /// `IO = max(I, I2) + 1` integer bits.
+    /// Available in synthesizable functions.
+    pub fn xadd<const I2: usize, const IO: usize>(self, rhs: Fixed<I2, F>) -> Fixed<IO, F> {
+        const {
+            assert!(
+                IO == grown_width(I, I2),
+                "xadd output must have max(I, I2) + 1 integer bits"
+            )
+        };
+        Fixed::from_limbs(self.0.wrapping_add(rhs.0))
+    }
+    /// Subtract two values, producing a signed result with one
+    /// more integer bit than the larger of the two.  The output must
+    /// have `IO = max(I, I2) + 1` integer bits.
+    /// Available in synthesizable functions.
+    pub fn xsub<const I2: usize, const IO: usize>(self, rhs: Fixed<I2, F>) -> SignedFixed<IO, F> {
+        const {
+            assert!(
+                IO == grown_width(I, I2),
+                "xsub output must have max(I, I2) + 1 integer bits"
+            )
+        };
+        SignedFixed::from_limbs(self.0.wrapping_sub(rhs.0))
+    }
+    /// Multiply two values, producing an exact result.  The output
+    /// must have `IO = I + I2` integer bits and `FO = F + F2` fractional bits.
+    /// Available in synthesizable functions.
+    pub fn xmul<const I2: usize, const F2: usize, const IO: usize, const FO: usize>(
+        self,
+        rhs: Fixed<I2, F2>,
+    ) -> Fixed<IO, FO> {
+        const {
+            assert!(IO == I + I2, "xmul output must have I + I2 integer bits");
+            assert!(FO == F + F2, "xmul output must have F + F2 fractional bits");
+        };
+        Fixed::from_limbs(self.0.wrapping_mul(rhs.0))
+    }
+}
+
+impl<const I: usize, const F: usize> SignedFixed<I, F> {
+    /// The total number of bits in the value.
+    pub const WIDTH: usize = check_width(I, F);
+    /// The value zero.
+    pub const ZERO: Self = Self(Limbs::ZERO);
+    /// The smallest (most negative) representable value.
+    pub const MIN: Self = Self(Limbs::mask(Self::WIDTH - 1).not());
+    /// The largest representable value.
+    pub const MAX: Self = Self(Limbs::mask(Self::WIDTH - 1));
+    /// Build a value from its raw (scaled) integer representation.
+    /// ```
+    /// # use rhdl_bits::fixed::SignedFixed;
+    /// const MINUS_HALF: SignedFixed<1, 1> = SignedFixed::from_raw(-1);
+    /// assert_eq!(MINUS_HALF.to_f64(), -0.5);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if the value does not fit in `I + F` bits.
+    pub const fn from_raw(value: i128) -> Self {
+        Self::from_limbs(Limbs::from_i128(value))
+    }
+    /// Return the raw (scaled) integer representation of the value.
+    ///
+    /// # Panics
+    /// Panics if `I + F` is larger than 128.
+    pub const fn raw(self) -> i128 {
+        assert!(
+            Self::WIDTH <= 128,
+            "SignedFixed wider than 128 bits does not fit in an i128"
+        );
+        self.0.to_i128()
+    }
+    /// Build a value from its raw (scaled) representation as sign
+    /// extended [Limbs].
+    ///
+    /// # Panics
+    /// Panics if the value does not fit in `I + F` bits.
+    pub const fn from_limbs(value: Limbs) -> Self {
+        assert!(value.fits_signed(Self::WIDTH));
+        Self(value)
+    }
+    /// Extract the (sign extended) [Limbs] backing the value.
+    pub const fn limbs(self) -> Limbs {
+        self.0
+    }
+    /// Convert a real number into the nearest representable value.
+    /// Values outside the representable range saturate.
+    /// Not available in synthesizable functions.
+    pub fn from_f64(value: f64) -> Self {
+        Self(limbs_from_f64(value, Self::WIDTH, F, true))
+    }
+    /// Convert the value into a real number.
+    /// Not available in synthesizable functions.
+    pub fn to_f64(self) -> f64 {
+        limbs_to_f64(self.0, Self::WIDTH, F, true)
+    }
+    /// Build a (dynamic, stack allocated) vector containing
+    /// the bits that make up this value.
+    /// Not available in synthesizable functions.
+    pub fn to_bools(self) -> Vec<bool> {
+        (0..Self::WIDTH).map(|ndx| self.0.bit(ndx)).collect()
+    }
+    /// Reinterpret the value as a [SignedBits] value with `N = I + F` bits.
+    /// Available in synthesizable functions.
+    ///
+    /// # Panics
+    /// Panics if `N` is not equal to `I + F`.
+    pub fn as_signed_bits<const N: usize>(self) -> SignedBits<N>
+    where
+        W<N>: BitWidth,
+    {
+        assert_eq!(N, Self::WIDTH, "SignedFixed value has {} bits", Self::WIDTH);
+        SignedBits::from_limbs(self.0)
+    }
+    /// Add two values, producing a result with one more integer
+    /// bit than the larger of the two.  The output must have
+    /// `IO = max(I, I2) + 1` integer bits.
+    /// Available in synthesizable functions.
+    pub fn xadd<const I2: usize, const IO: usize>(
+        self,
+        rhs: SignedFixed<I2, F>,
+    ) -> SignedFixed<IO, F> {
+        const {
+            assert!(
+                IO == grown_width(I, I2),
+                "xadd output must have max(I, I2) + 1 integer bits"
+            )
+        };
+        SignedFixed::from_limbs(self.0.wrapping_add(rhs.0))
+    }
+    /// Subtract two values, producing a result with one more integer
+    /// bit than the larger of the two.  The output must have
+    /// `IO = max(I, I2) + 1` integer bits.
+    /// Available in synthesizable functions.
+    pub fn xsub<const I2: usize, const IO: usize>(
+        self,
+        rhs: SignedFixed<I2, F>,
+    ) -> SignedFixed<IO, F> {
+        const {
+            assert!(
+                IO == grown_width(I, I2),
+                "xsub output must have max(I, I2) + 1 integer bits"
+            )
+        };
+        SignedFixed::from_limbs(self.0.wrapping_sub(rhs.0))
+    }
+    /// Multiply two values, producing an exact result.  The output
+    /// must have `IO = I + I2` integer bits and `FO = F + F2` fractional bits.
+    /// Available in synthesizable functions.
+    pub fn xmul<const I2: usize, const F2: usize, const IO: usize, const FO: usize>(
+        self,
+        rhs: SignedFixed<I2, F2>,
+    ) -> SignedFixed<IO, FO> {
+        const {
+            assert!(IO == I + I2, "xmul output must have I + I2 integer bits");
+            assert!(FO == F + F2, "xmul output must have F + F2 fractional bits");
+        };
+        SignedFixed::from_limbs(self.0.wrapping_mul(rhs.0))
+    }
+}
+
+impl<const N: usize> Bits<N>
+where
+    W<N>: BitWidth,
+{
+    /// Reinterpret the [Bits] value as a [Fixed] value with `I + F = N` bits.
+    /// Available in synthesizable functions.
+    /// ```
+    /// # use rhdl_bits::{alias::*, fixed::Fixed};
+    /// let x: Fixed<4, 4> = b8(0x18).as_fixed();
+    /// assert_eq!(x.to_f64(), 1.5);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if `I + F` is not equal to `N`.
+    pub fn as_fixed<const I: usize, const F: usize>(self) -> Fixed<I, F> {
+        assert_eq!(N, Fixed::<I, F>::WIDTH, "Fixed value has {} bits", I + F);
+        Fixed::from_limbs(self.limbs())
+    }
+}
+
+impl<const N: usize> SignedBits<N>
+where
+    W<N>: BitWidth,
+{
+    /// Reinterpret the [SignedBits] value as a [SignedFixed] value with `I + F = N` bits.
+    /// Available in synthesizable functions.
+    ///
+    /// # Panics
+    /// Panics if `I + F` is not equal to `N`.
+    pub fn as_signed_fixed<const I: usize, const F: usize>(self) -> SignedFixed<I, F> {
+        assert_eq!(
+            N,
+            SignedFixed::<I, F>::WIDTH,
+            "SignedFixed value has {} bits",
+            I + F
+        );
+        SignedFixed::from_limbs(self.limbs())
+    }
+}
+
+impl<const I: usize, const F: usize> FixedPoint for Fixed<I, F> {
+    const FORMAT: FixedFormat = FixedFormat {
+        int: I,
+        frac: F,
+        signed: false,
+    };
+    fn limbs(self) -> Limbs {
+        self.0
+    }
+    fn from_limbs_wrapped(value: Limbs) -> Self {
+        Self(value.bitand(Limbs::mask(Self::WIDTH)))
+    }
+}
+
+impl<const I: usize, const F: usize> FixedPoint for SignedFixed<I, F> {
+    const FORMAT: FixedFormat = FixedFormat {
+        int: I,
+        frac: F,
+        signed: true,
+    };
+    fn limbs(self) -> Limbs {
+        self.0
+    }
+    fn from_limbs_wrapped(value: Limbs) -> Self {
+        Self(value.sign_extend(Self::WIDTH))
+    }
+}
+
+impl<const I: usize, const F: usize> std::fmt::Debug for Fixed<I, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}_fx{}.{}", self.to_f64(), I, F)
+    }
+}
+
+impl<const I: usize, const F: usize> std::fmt::Display for Fixed<I, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.to_f64())
+    }
+}
+
+impl<const I: usize, const F: usize> std::fmt::Debug for SignedFixed<I, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}_sfx{}.{}", self.to_f64(), I, F)
+    }
+}
+
+impl<const I: usize, const F: usize> std::fmt::Display for SignedFixed<I, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.to_f64())
+    }
+}
+
+impl<const I: usize, const F: usize> PartialOrd for Fixed<I, F> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const I: usize, const F: usize> Ord for Fixed<I, F> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp_unsigned(other.0)
+    }
+}
+
+impl<const I: usize, const F: usize> PartialOrd for SignedFixed<I, F> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const I: usize, const F: usize> Ord for SignedFixed<I, F> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp_signed(other.0)
+    }
+}
+
+impl<const I: usize, const F: usize> std::ops::Add for Fixed<I, F> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::from_limbs_wrapped(self.0.wrapping_add(rhs.0))
+    }
+}
+
+impl<const I: usize, const F: usize> std::ops::Sub for Fixed<I, F> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::from_limbs_wrapped(self.0.wrapping_sub(rhs.0))
+    }
+}
+
+impl<const I: usize, const F: usize> std::ops::AddAssign for Fixed<I, F> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const I: usize, const F: usize> std::ops::SubAssign for Fixed<I, F> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const I: usize, const F: usize> std::ops::Add for SignedFixed<I, F> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::from_limbs_wrapped(self.0.wrapping_add(rhs.0))
+    }
+}
+
+impl<const I: usize, const F: usize> std::ops::Sub for SignedFixed<I, F> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::from_limbs_wrapped(self.0.wrapping_sub(rhs.0))
+    }
+}
+
+impl<const I: usize, const F: usize> std::ops::AddAssign for SignedFixed<I, F> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const I: usize, const F: usize> std::ops::SubAssign for SignedFixed<I, F> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const I: usize, const F: usize> std::ops::Neg for SignedFixed<I, F> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::from_limbs_wrapped(self.0.wrapping_neg())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_real_value_round_trip() {
+        for raw in 0..256 {
+            let x = Fixed::<4, 4>::from_raw(raw);
+            assert_eq!(x.to_f64(), raw as f64 / 16.0);
+            assert_eq!(Fixed::<4, 4>::from_f64(x.to_f64()), x);
+        }
+        for raw in -128..128 {
+            let x = SignedFixed::<4, 4>::from_raw(raw);
+            assert_eq!(x.to_f64(), raw as f64 / 16.0);
+            assert_eq!(SignedFixed::<4, 4>::from_f64(x.to_f64()), x);
+        }
+    }
+
+    #[test]
+    fn test_from_f64_saturates() {
+        assert_eq!(Fixed::<4, 4>::from_f64(100.0), Fixed::<4, 4>::MAX);
+        assert_eq!(Fixed::<4, 4>::from_f64(-1.0), Fixed::<4, 4>::MIN);
+        assert_eq!(SignedFixed::<4, 4>::from_f64(8.0), SignedFixed::<4, 4>::MAX);
+        assert_eq!(
+            SignedFixed::<4, 4>::from_f64(-9.0),
+            SignedFixed::<4, 4>::MIN
+        );
+        assert_eq!(SignedFixed::<4, 4>::MIN.to_f64(), -8.0);
+        let wide = SignedFixed::<200, 100>::from_f64(-3.25);
+        assert_eq!(wide.to_f64(), -3.25);
+        assert_eq!(SignedFixed::<200, 100>::from_f64(1e100), SignedFixed::MAX);
+    }
+
+    #[test]
+    fn test_wrapping_arithmetic() {
+        let a = Fixed::<2, 2>::from_f64(3.5);
+        let b = Fixed::<2, 2>::from_f64(0.75);
+        assert_eq!((a + b).to_f64(), 0.25);
+        assert_eq!((b - a).to_f64(), 1.25);
+        let c = SignedFixed::<2, 2>::from_f64(-2.0);
+        assert_eq!(-c, c);
+        assert!(c < SignedFixed::ZERO);
+        assert!(a > b);
+    }
+
+    #[test]
+    fn test_growing_arithmetic_is_exact() {
+        for x in -8..8 {
+            for y in -4..4 {
+                let a = SignedFixed::<2, 2>::from_raw(x);
+                let b = SignedFixed::<1, 2>::from_raw(y);
+                let sum: SignedFixed<3, 2> = a.xadd(b);
+                let diff: SignedFixed<3, 2> = a.xsub(b);
+                let prod: SignedFixed<3, 4> = a.xmul(b);
+                assert_eq!(sum.to_f64(), a.to_f64() + b.to_f64());
+                assert_eq!(diff.to_f64(), a.to_f64() - b.to_f64());
+                assert_eq!(prod.to_f64(), a.to_f64() * b.to_f64());
+            }
+        }
+    }
+
+    #[test]
+    fn test_rounding_modes() {
+        // Reference rounding of a value with 3 fractional bits to 1 fractional bit
+        for raw in -64..64 {
+            let x = SignedFixed::<4, 3>::from_raw(raw);
+            let scaled = x.to_f64() * 2.0;
+            let truncate: SignedFixed<5, 1> = quantize::<Truncate, Wrap, _, _>(x);
+            let half_up: SignedFixed<5, 1> = quantize::<HalfUp, Wrap, _, _>(x);
+            let convergent: SignedFixed<5, 1> = quantize::<Convergent, Wrap, _, _>(x);
+            assert_eq!(truncate.raw() as f64, scaled.floor());
+            assert_eq!(half_up.raw() as f64, (scaled + 0.5).floor());
+            assert_eq!(convergent.raw() as f64, scaled.round_ties_even());
+        }
+    }
+
+    #[test]
+    fn test_saturation() {
+        for raw in 0..256 {
+            let x = Fixed::<4, 4>::from_raw(raw);
+            let y: Fixed<2, 4> = quantize::<Truncate, Saturate, _, _>(x);
+            assert_eq!(y.raw(), raw.min(63));
+            let y: Fixed<2, 4> = quantize::<Truncate, Wrap, _, _>(x);
+            assert_eq!(y.raw(), raw & 63);
+        }
+        for raw in -128..128 {
+            let x = SignedFixed::<4, 4>::from_raw(raw);
+            let y: SignedFixed<3, 5> = quantize::<Truncate, Saturate, _, _>(x);
+            assert_eq!(y.raw(), (raw * 2).clamp(-128, 127));
+        }
+    }
+
+    #[test]
+    fn test_bits_conversions() {
+        let x = crate::bits::<8>(0xA4).as_fixed::<5, 3>();
+        assert_eq!(x.to_f64(), 20.5);
+        assert_eq!(x.as_bits::<8>(), crate::bits::<8>(0xA4));
+        let y = crate::signed::<8>(-3).as_signed_fixed::<6, 2>();
+        assert_eq!(y.to_f64(), -0.75);
+        assert_eq!(y.as_signed_bits::<8>(), crate::signed::<8>(-3));
+    }
+}
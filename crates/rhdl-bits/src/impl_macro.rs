@@ -5,7 +5,7 @@
 // it as needed.
 #[doc(hidden)]
 macro_rules! impl_binop {
-    ($trait: ident, $op: ident, $wrap_op: path) => {
+    ($trait: ident, $op: ident, $raw_op: path, $wrap_op: path) => {
         // First the case of adding a u128 to a Bits<N>
         impl<const N: usize> $trait<u128> for Bits<N>
         where
@@ -14,7 +14,7 @@ macro_rules! impl_binop {
             type Output = Bits<N>;
             fn $op(self, rhs: u128) -> Self::Output {
                 assert!(rhs <= Self::MASK.raw());
-                bits_masked($wrap_op(self.raw(), rhs))
+                $crate::bits_impl::bits_masked($raw_op(self.raw(), rhs))
             }
         }
         // Next the case of adding a Bits<N> to a u128
@@ -25,7 +25,7 @@ macro_rules! impl_binop {
             type Output = Bits<N>;
             fn $op(self, rhs: Bits<N>) -> Self::Output {
                 assert!(self <= Bits::<N>::MASK.raw());
-                bits_masked($wrap_op(self, rhs.raw()))
+                $crate::bits_impl::bits_masked($raw_op(self, rhs.raw()))
             }
         }
         // Adding two Bits<N> together
@@ -35,16 +35,17 @@ macro_rules! impl_binop {
         {
             type Output = Self;
             fn $op(self, rhs: Self) -> Self::Output {
-                bits_masked($wrap_op(self.raw(), rhs.raw()))
+                $crate::bits_impl::bits_masked($raw_op(self.raw(), rhs.raw()))
             }
         }
         // Adding a u128 to a DynBits
         impl $trait<u128> for DynBits {
             type Output = DynBits;
             fn $op(self, rhs: u128) -> Self::Output {
-                assert!(rhs <= self.mask());
+                let rhs = $crate::limbs::Limbs::from_u128(rhs);
+                assert!(rhs.fits_unsigned(self.bits));
                 DynBits {
-                    val: $wrap_op(self.raw(), rhs),
+                    val: $wrap_op(self.limbs(), rhs),
                     bits: self.bits,
                 }
                 .wrapped()
@@ -54,9 +55,10 @@ macro_rules! impl_binop {
         impl $trait<DynBits> for u128 {
             type Output = DynBits;
             fn $op(self, rhs: DynBits) -> Self::Output {
-                assert!(self <= rhs.mask());
+                let lhs = $crate::limbs::Limbs::from_u128(self);
+                assert!(lhs.fits_unsigned(rhs.bits));
                 DynBits {
-                    val: $wrap_op(self, rhs.raw()),
+                    val: $wrap_op(lhs, rhs.limbs()),
                     bits: rhs.bits,
                 }
                 .wrapped()
@@ -68,7 +70,7 @@ macro_rules! impl_binop {
             fn $op(self, rhs: Self) -> Self::Output {
                 assert_eq!(self.bits, rhs.bits);
                 DynBits {
-                    val: $wrap_op(self.raw(), rhs.raw()),
+                    val: $wrap_op(self.limbs(), rhs.limbs()),
                     bits: self.bits,
                 }
                 .wrapped()
@@ -82,7 +84,7 @@ macro_rules! impl_binop {
             type Output = Bits<N>;
             fn $op(self, rhs: Bits<N>) -> Self::Output {
                 assert_eq!(self.bits, { N });
-                bits_masked($wrap_op(self.raw(), rhs.raw()))
+                Bits::from_limbs_masked($wrap_op(self.limbs(), rhs.limbs()))
             }
         }
         // Adding a Bits<N> to a DynBits
@@ -93,14 +95,14 @@ macro_rules! impl_binop {
             type Output = Bits<N>;
             fn $op(self, rhs: DynBits) -> Self::Output {
                 assert_eq!(rhs.bits, { N });
-                bits_masked($wrap_op(self.raw(), rhs.raw()))
+                Bits::from_limbs_masked($wrap_op(self.limbs(), rhs.limbs()))
             }
         }
     };
 }
 
 macro_rules! impl_assign_op {
-    ($trait: ident, $op: ident, $wrap_op: path) => {
+    ($trait: ident, $op: ident, $raw_op: path, $wrap_op: path) => {
         // First the case of adding a u128 to a Bits<N>
         impl<const N: usize> $trait<u128> for Bits<N>
         where
@@ -108,7 +110,7 @@ macro_rules! impl_assign_op {
         {
             fn $op(&mut self, rhs: u128) {
                 assert!(rhs <= Self::MASK.raw());
-                *self = bits_masked($wrap_op(self.raw(), rhs));
+                *self = $crate::bits_impl::bits_masked($raw_op(self.raw(), rhs));
             }
         }
         // Adding two Bits<N> together
@@ -117,14 +119,15 @@ macro_rules! impl_assign_op {
             $crate::bitwidth::W<N>: BitWidth,
         {
             fn $op(&mut self, rhs: Self) {
-                *self = bits_masked($wrap_op(self.raw(), rhs.raw()));
+                *self = $crate::bits_impl::bits_masked($raw_op(self.raw(), rhs.raw()));
             }
         }
         // Adding a u128 to a DynBits
         impl $trait<u128> for DynBits {
             fn $op(&mut self, rhs: u128) {
-                assert!(rhs <= self.mask());
-                self.val = $wrap_op(self.raw(), rhs);
+                let rhs = $crate::limbs::Limbs::from_u128(rhs);
+                assert!(rhs.fits_unsigned(self.bits));
+                self.val = $wrap_op(self.limbs(), rhs);
                 *self = self.wrapped();
             }
         }
@@ -132,7 +135,7 @@ macro_rules! impl_assign_op {
         impl $trait for DynBits {
             fn $op(&mut self, rhs: Self) {
                 assert_eq!(self.bits, rhs.bits);
-                self.val = $wrap_op(self.raw(), rhs.raw());
+                self.val = $wrap_op(self.limbs(), rhs.limbs());
                 *self = self.wrapped();
             }
         }
@@ -143,7 +146,7 @@ macro_rules! impl_assign_op {
         {
             fn $op(&mut self, rhs: Bits<N>) {
                 assert_eq!(self.bits, { N });
-                self.val = $wrap_op(self.raw(), rhs.raw());
+                self.val = $wrap_op(self.limbs(), rhs.limbs());
                 *self = self.wrapped();
             }
         }
@@ -154,7 +157,7 @@ macro_rules! impl_assign_op {
         {
             fn $op(&mut self, rhs: DynBits) {
                 assert_eq!(rhs.bits, { N });
-                *self = bits_masked($wrap_op(self.raw(), rhs.raw()));
+                *self = Bits::from_limbs_masked($wrap_op(self.limbs(), rhs.limbs()));
             }
         }
     };
@@ -162,7 +165,7 @@ macro_rules! impl_assign_op {
 
 // Macro to generate impls for signed values
 macro_rules! impl_signed_binop {
-    ($trait: ident, $op: ident, $wrap_op: path) => {
+    ($trait: ident, $op: ident, $raw_op: path, $wrap_op: path) => {
         // First the case of adding an i128 to a SignedBits<N>
         impl<const N: usize> $trait<i128> for SignedBits<N>
         where
@@ -172,7 +175,7 @@ macro_rules! impl_signed_binop {
             fn $op(self, rhs: i128) -> Self::Output {
                 assert!(rhs <= Self::MAX);
                 assert!(rhs >= Self::MIN);
-                signed_wrapped($wrap_op(self.raw(), rhs))
+                $crate::signed_bits_impl::signed_wrapped($raw_op(self.raw(), rhs))
             }
         }
         // Next the case of adding a SignedBits<N> to an i128
@@ -184,7 +187,7 @@ macro_rules! impl_signed_binop {
             fn $op(self, rhs: SignedBits<N>) -> Self::Output {
                 assert!(self <= SignedBits::<N>::MAX);
                 assert!(self >= SignedBits::<N>::MIN);
-                signed_wrapped($wrap_op(self, rhs.raw()))
+                $crate::signed_bits_impl::signed_wrapped($raw_op(self, rhs.raw()))
             }
         }
         // Adding two SignedBits<N> together
@@ -194,17 +197,17 @@ macro_rules! impl_signed_binop {
         {
             type Output = Self;
             fn $op(self, rhs: Self) -> Self::Output {
-                signed_wrapped($wrap_op(self.raw(), rhs.raw()))
+                $crate::signed_bits_impl::signed_wrapped($raw_op(self.raw(), rhs.raw()))
             }
         }
         // Adding a i128 to a SignedDynBits
         impl $trait<i128> for SignedDynBits {
             type Output = SignedDynBits;
             fn $op(self, rhs: i128) -> Self::Output {
-                assert!(rhs <= self.max_value());
-                assert!(rhs >= self.min_value());
+                let rhs = $crate::limbs::Limbs::from_i128(rhs);
+                assert!(rhs.fits_signed(self.bits));
                 SignedDynBits {
-                    val: $wrap_op(self.raw(), rhs),
+                    val: $wrap_op(self.limbs(), rhs),
                     bits: self.bits,
                 }
                 .wrapped()
@@ -214,10 +217,10 @@ macro_rules! impl_signed_binop {
         impl $trait<SignedDynBits> for i128 {
             type Output = SignedDynBits;
             fn $op(self, rhs: SignedDynBits) -> Self::Output {
-                assert!(self <= rhs.max_value());
-                assert!(self >= rhs.min_value());
+                let lhs = $crate::limbs::Limbs::from_i128(self);
+                assert!(lhs.fits_signed(rhs.bits));
                 SignedDynBits {
-                    val: $wrap_op(self, rhs.raw()),
+                    val: $wrap_op(lhs, rhs.limbs()),
                     bits: rhs.bits,
                 }
                 .wrapped()
@@ -229,7 +232,7 @@ macro_rules! impl_signed_binop {
             fn $op(self, rhs: Self) -> Self::Output {
                 assert_eq!(self.bits, rhs.bits);
                 SignedDynBits {
-                    val: $wrap_op(self.raw(), rhs.raw()),
+                    val: $wrap_op(self.limbs(), rhs.limbs()),
                     bits: self.bits,
                 }
                 .wrapped()
@@ -243,7 +246,7 @@ macro_rules! impl_signed_binop {
             type Output = SignedBits<N>;
             fn $op(self, rhs: SignedBits<N>) -> Self::Output {
                 assert_eq!(self.bits, { N });
-                signed_wrapped($wrap_op(self.raw(), rhs.raw()))
+                SignedBits::from_limbs_wrapped($wrap_op(self.limbs(), rhs.limbs()))
             }
         }
         // Adding a SignedBits<N> to a SignedDynBits
@@ -254,14 +257,14 @@ macro_rules! impl_signed_binop {
             type Output = SignedBits<N>;
             fn $op(self, rhs: SignedDynBits) -> Self::Output {
                 assert_eq!(rhs.bits, { N });
-                signed_wrapped($wrap_op(self.raw(), rhs.raw()))
+                SignedBits::from_limbs_wrapped($wrap_op(self.limbs(), rhs.limbs()))
             }
         }
     };
 }
 
 macro_rules! impl_assigned_signed_op {
-    ($trait: ident, $op: ident, $wrap_op: path) => {
+    ($trait: ident, $op: ident, $raw_op: path, $wrap_op: path) => {
         // First the case of adding an i128 to a SignedBits<N>
         impl<const N: usize> $trait<i128> for SignedBits<N>
         where
@@ -270,7 +273,7 @@ macro_rules! impl_assigned_signed_op {
             fn $op(&mut self, rhs: i128) {
                 assert!(rhs <= Self::MAX);
                 assert!(rhs >= Self::MIN);
-                *self = signed_wrapped($wrap_op(self.raw(), rhs));
+                *self = $crate::signed_bits_impl::signed_wrapped($raw_op(self.raw(), rhs));
             }
         }
         // Adding two SignedBits<N> together
@@ -279,15 +282,15 @@ macro_rules! impl_assigned_signed_op {
             $crate::bitwidth::W<N>: BitWidth,
         {
             fn $op(&mut self, rhs: Self) {
-                *self = signed_wrapped($wrap_op(self.raw(), rhs.raw()));
+                *self = $crate::signed_bits_impl::signed_wrapped($raw_op(self.raw(), rhs.raw()));
             }
         }
         // Adding a i128 to a SignedDynBits
         impl $trait<i128> for SignedDynBits {
             fn $op(&mut self, rhs: i128) {
-                assert!(rhs <= self.max_value());
-                assert!(rhs >= self.min_value());
-                self.val = $wrap_op(self.raw(), rhs);
+                let rhs = $crate::limbs::Limbs::from_i128(rhs);
+                assert!(rhs.fits_signed(self.bits));
+                self.val = $wrap_op(self.limbs(), rhs);
                 *self = self.wrapped();
             }
         }
@@ -295,7 +298,7 @@ macro_rules! impl_assigned_signed_op {
         impl $trait for SignedDynBits {
             fn $op(&mut self, rhs: Self) {
                 assert_eq!(self.bits, rhs.bits);
-                self.val = $wrap_op(self.raw(), rhs.raw());
+                self.val = $wrap_op(self.limbs(), rhs.limbs());
                 *self = self.wrapped();
             }
         }
@@ -306,7 +309,7 @@ macro_rules! impl_assigned_signed_op {
         {
             fn $op(&mut self, rhs: SignedBits<N>) {
                 assert_eq!(self.bits, { N });
-                self.val = $wrap_op(self.raw(), rhs.raw());
+                self.val = $wrap_op(self.limbs(), rhs.limbs());
                 *self = self.wrapped();
             }
         }
@@ -317,7 +320,124 @@ macro_rules! impl_assigned_signed_op {
         {
             fn $op(&mut self, rhs: SignedDynBits) {
                 assert_eq!(rhs.bits, { N });
-                *self = signed_wrapped($wrap_op(self.raw(), rhs.raw()));
+                *self = SignedBits::from_limbs_wrapped($wrap_op(self.limbs(), rhs.limbs()));
+            }
+        }
+    };
+}
+
+// The [WideBits](crate::WideBits) and [SignedWideBits](crate::SignedWideBits)
+// types get the same operators, implemented on their limbs.  Mixing them
+// with dynamic values requires an explicit conversion (e.g., `dyn_bits()`).
+macro_rules! impl_wide_binop {
+    ($trait: ident, $op: ident, $wrap_op: path) => {
+        impl<const N: usize> $trait<u128> for $crate::WideBits<N>
+        where
+            $crate::bitwidth::W<N>: $crate::WideBitWidth,
+        {
+            type Output = $crate::WideBits<N>;
+            fn $op(self, rhs: u128) -> Self::Output {
+                let rhs = $crate::wide_bits_impl::wide_bits::<N>(rhs);
+                $crate::WideBits::from_limbs_masked($wrap_op(self.limbs(), rhs.limbs()))
+            }
+        }
+        impl<const N: usize> $trait<$crate::WideBits<N>> for u128
+        where
+            $crate::bitwidth::W<N>: $crate::WideBitWidth,
+        {
+            type Output = $crate::WideBits<N>;
+            fn $op(self, rhs: $crate::WideBits<N>) -> Self::Output {
+                let lhs = $crate::wide_bits_impl::wide_bits::<N>(self);
+                $crate::WideBits::from_limbs_masked($wrap_op(lhs.limbs(), rhs.limbs()))
+            }
+        }
+        impl<const N: usize> $trait for $crate::WideBits<N>
+        where
+            $crate::bitwidth::W<N>: $crate::WideBitWidth,
+        {
+            type Output = Self;
+            fn $op(self, rhs: Self) -> Self::Output {
+                $crate::WideBits::from_limbs_masked($wrap_op(self.limbs(), rhs.limbs()))
+            }
+        }
+    };
+}
+
+macro_rules! impl_wide_assign_op {
+    ($trait: ident, $op: ident, $wrap_op: path) => {
+        impl<const N: usize> $trait<u128> for $crate::WideBits<N>
+        where
+            $crate::bitwidth::W<N>: $crate::WideBitWidth,
+        {
+            fn $op(&mut self, rhs: u128) {
+                let rhs = $crate::wide_bits_impl::wide_bits::<N>(rhs);
+                *self = $crate::WideBits::from_limbs_masked($wrap_op(self.limbs(), rhs.limbs()));
+            }
+        }
+        impl<const N: usize> $trait for $crate::WideBits<N>
+        where
+            $crate::bitwidth::W<N>: $crate::WideBitWidth,
+        {
+            fn $op(&mut self, rhs: Self) {
+                *self = $crate::WideBits::from_limbs_masked($wrap_op(self.limbs(), rhs.limbs()));
+            }
+        }
+    };
+}
+
+macro_rules! impl_wide_signed_binop {
+    ($trait: ident, $op: ident, $wrap_op: path) => {
+        impl<const N: usize> $trait<i128> for $crate::SignedWideBits<N>
+        where
+            $crate::bitwidth::W<N>: $crate::WideBitWidth,
+        {
+            type Output = $crate::SignedWideBits<N>;
+            fn $op(self, rhs: i128) -> Self::Output {
+                let rhs = $crate::signed_wide_bits_impl::signed_wide::<N>(rhs);
+                $crate::SignedWideBits::from_limbs_wrapped($wrap_op(self.limbs(), rhs.limbs()))
+            }
+        }
+        impl<const N: usize> $trait<$crate::SignedWideBits<N>> for i128
+        where
+            $crate::bitwidth::W<N>: $crate::WideBitWidth,
+        {
+            type Output = $crate::SignedWideBits<N>;
+            fn $op(self, rhs: $crate::SignedWideBits<N>) -> Self::Output {
+                let lhs = $crate::signed_wide_bits_impl::signed_wide::<N>(self);
+                $crate::SignedWideBits::from_limbs_wrapped($wrap_op(lhs.limbs(), rhs.limbs()))
+            }
+        }
+        impl<const N: usize> $trait for $crate::SignedWideBits<N>
+        where
+            $crate::bitwidth::W<N>: $crate::WideBitWidth,
+        {
+            type Output = Self;
+            fn $op(self, rhs: Self) -> Self::Output {
+                $crate::SignedWideBits::from_limbs_wrapped($wrap_op(self.limbs(), rhs.limbs()))
+            }
+        }
+    };
+}
+
+macro_rules! impl_wide_assigned_signed_op {
+    ($trait: ident, $op: ident, $wrap_op: path) => {
+        impl<const N: usize> $trait<i128> for $crate::SignedWideBits<N>
+        where
+            $crate::bitwidth::W<N>: $crate::WideBitWidth,
+        {
+            fn $op(&mut self, rhs: i128) {
+                let rhs = $crate::signed_wide_bits_impl::signed_wide::<N>(rhs);
+                *self =
+                    $crate::SignedWideBits::from_limbs_wrapped($wrap_op(self.limbs(), rhs.limbs()));
+            }
+        }
+        impl<const N: usize> $trait for $crate::SignedWideBits<N>
+        where
+            $crate::bitwidth::W<N>: $crate::WideBitWidth,
+        {
+            fn $op(&mut self, rhs: Self) {
+                *self =
+                    $crate::SignedWideBits::from_limbs_wrapped($wrap_op(self.limbs(), rhs.limbs()));
             }
         }
     };
@@ -336,6 +456,7 @@ macro_rules! test_binop {
             // Check all reasonable combinations.  For each combination,
             // the result should be the same as if we had added the two
             // values together and then masked the result.
+            use $crate::bits_masked;
             assert_eq!(x $op y, bits_masked($wrap(x.raw(), y)));
             assert_eq!(y $op x, bits_masked($wrap(y, x.raw())));
             assert_eq!(x $op z, bits_masked($wrap(x.raw(), z.raw())));
@@ -85,13 +85,31 @@
 //! let bits: b8 = 0b1101_1010_u128.into(); // Short form (not the same as u8)
 //! ```
 //!
-//! Note that the [Bits] type only supports up to 128 bit values.  Larger bit vectors
-//! can easily be constructed using data structures (arrays, structs, enums, tuples).
-//! But arithmetic on them is not supported by default.  You will need to provide your
-//! own arithmetic implementations for these types.  This is a limitation of the
-//! [Bits] type, but it is a limitation that is not likely to be a problem in practice.
-//! Practical hardware limitations can mean that performing arithmetic on very long
-//! bit vectors is likely to be very slow.
+//! Note that the [Bits] type only supports up to 128 bit values, which keeps it as
+//! small and fast as a `u128`.  For wider datapaths (up to [MAX_BITS] bits), use the
+//! [WideBits] and [SignedWideBits] types.  They support the same operators, but store
+//! their value in [Limbs], and so are larger and slower than [Bits] and [SignedBits]:
+//! ```
+//! # use rhdl_bits::{Limbs, WideBits};
+//! let x: WideBits<256> = WideBits::from_limbs(Limbs::ONES.wrapping_shr(256));
+//! let y = x + 1;
+//! assert!(y.limbs().is_zero());
+//! let z: WideBits<256> = WideBits::<256>::from(1) << 200;
+//! assert!(z.limbs().bit(200));
+//! assert_eq!(z.try_raw(), None);
+//! ```
+//! The split into two families of types is deliberate.  Stable Rust cannot pick the
+//! storage of a single type based on `N`, and storing every [Bits] value in [Limbs]
+//! would make all of them as large as the widest one, and would hide the `u128` that
+//! the `Bits::<N>(x)` constructor and patterns rely on.  So you pick [WideBits] when
+//! a value is wider than 128 bits.  Inside RHDL the two families look the same - a
+//! [WideBits] value has the same `Bits(N)` kind (or `Signed(N)` for [SignedWideBits]) as
+//! a [Bits] value would, and can be used in kernels, converted to and from
+//! [DynBits](crate::dyn_bits::DynBits), and synthesized.
+//!
+//! Larger bit vectors can still be constructed using data structures (arrays, structs,
+//! enums, tuples).  Keep in mind that performing arithmetic on very long bit vectors
+//! is likely to be slow in practical hardware.
 //!
 //! # Constructing [SignedBits]
 //! The [SignedBits] type can be constructed in the same way as the [Bits] type.  The
@@ -257,6 +275,24 @@
 //! assert_eq!(x, 0);
 //! ```
 //!
+//! ## Division and Remainder
+//!
+//! Division (`/`) and remainder (`%`) are supported for both [Bits] and [SignedBits],
+//! along with the `/=` and `%=` operators.  They behave like the `wrapping_div` and
+//! `wrapping_rem` methods on the Rust integer types.  The quotient is truncated towards zero.
+//! Dividing by zero does not panic: the quotient is all ones and the remainder is the
+//! dividend, matching the generated hardware.
+//! ```
+//! # use rhdl_bits::alias::*;
+//! let x: s8 = (-7).into();
+//! let y: s8 = 2.into();
+//! assert_eq!(x / y, -3);
+//! assert_eq!(x % y, -1);
+//! ```
+//!
+//! In hardware, a division by a constant power of two is just a shift (or a mask, for the
+//! remainder).  Division by anything else requires a combinatorial divider, which is large and slow.
+//!
 //! ## Bitwise Logical Operators
 //!
 //! All four of the standard Rust logical operators are supported for both [Bits] and [SignedBits].
@@ -392,18 +428,26 @@ pub mod and;
 pub mod bits_impl;
 /// Trait (and type) used to constrain the bit width parameter
 pub mod bitwidth;
+pub mod div;
 pub mod dyn_bits;
+pub mod fixed;
+pub mod limbs;
 pub mod mul;
 pub mod neg;
 pub mod not;
 pub mod or;
+pub mod rem;
 pub mod shl;
 pub mod shr;
 /// Implementation details for the [SignedBits] type
 pub mod signed_bits_impl;
 /// Support for signed bit vectors with run-time defined size
 pub mod signed_dyn_bits;
+/// Implementation details for the [SignedWideBits] type
+pub mod signed_wide_bits_impl;
 pub mod sub;
+/// Implementation details for the [WideBits] type
+pub mod wide_bits_impl;
 pub mod xadd;
 pub mod xmul;
 pub mod xneg;
@@ -450,8 +494,16 @@ pub use bits_impl::Bits;
 pub use bits_impl::bits;
 pub use bits_impl::bits_masked;
 pub use bitwidth::BitWidth;
+pub use bitwidth::WideBitWidth;
+pub use limbs::Limbs;
+pub use limbs::MAX_BITS;
 pub use signed_bits_impl::SignedBits;
 pub use signed_bits_impl::signed;
+pub use signed_wide_bits_impl::SignedWideBits;
+pub use signed_wide_bits_impl::signed_wide;
+pub use wide_bits_impl::WideBits;
+pub use wide_bits_impl::wide_bits;
+pub use wide_bits_impl::wide_bits_masked;
 pub use xadd::XAdd;
 pub use xmul::XMul;
 pub use xneg::XNeg;
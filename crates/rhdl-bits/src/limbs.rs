@@ -0,0 +1,604 @@
+//! Fixed capacity multi-word storage for bit vectors
+//!
+//! The [Bits](crate::Bits) and [SignedBits](crate::SignedBits) types (as well
+//! as their run-time sized counterparts) store their values in a [Limbs] value.
+//! This is a [MAX_BITS] wide integer made of 64 bit words, stored least
+//! significant word first.  All of the arithmetic provided here wraps modulo
+//! `2^MAX_BITS`.  It is up to the bit vector types to mask (for unsigned values)
+//! or sign extend (for signed values) the result to their own width.
+//!
+//! The storage is a fixed size array so that the bit vector types remain [Copy],
+//! and the operations are `const` so that values can still be constructed in
+//! constant contexts.  Values that fit in 128 bits can be moved in and out
+//! using [Limbs::from_u128], [Limbs::from_i128], [Limbs::to_u128] and
+//! [Limbs::to_i128].
+//! ```
+//! # use rhdl_bits::limbs::Limbs;
+//! let a = Limbs::from_u128(u128::MAX);
+//! let b = a.wrapping_add(Limbs::from_u128(1));
+//! assert!(b.bit(128));
+//! assert_eq!(b.wrapping_shr(128).to_u128(), 1);
+//! ```
+use std::cmp::Ordering;
+
+/// The largest number of bits that can be held by a [Bits](crate::Bits)
+/// or [SignedBits](crate::SignedBits) value.
+pub const MAX_BITS: usize = 512;
+
+const WORDS: usize = MAX_BITS / 64;
+
+/// A [MAX_BITS] wide integer stored as little-endian 64 bit words.
+///
+/// The same storage is used for both unsigned and signed values.  Unsigned
+/// values are kept zero extended, and signed values are kept sign extended,
+/// so that the wrapping operations give the 2's complement result in both
+/// cases.  Operations that depend on the interpretation (comparison, division,
+/// right shift) come in unsigned and `_signed` flavors.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Limbs([u64; WORDS]);
+
+impl Limbs {
+    /// The value zero.
+    pub const ZERO: Self = Self([0; WORDS]);
+    /// The value with every bit set.
+    pub const ONES: Self = Self([u64::MAX; WORDS]);
+
+    /// Zero extend a `u128` into a [Limbs] value.
+    pub const fn from_u128(value: u128) -> Self {
+        let mut words = [0; WORDS];
+        words[0] = value as u64;
+        words[1] = (value >> 64) as u64;
+        Self(words)
+    }
+    /// Sign extend an `i128` into a [Limbs] value.
+    pub const fn from_i128(value: i128) -> Self {
+        let fill = if value < 0 { u64::MAX } else { 0 };
+        let mut words = [fill; WORDS];
+        words[0] = value as u64;
+        words[1] = ((value as u128) >> 64) as u64;
+        Self(words)
+    }
+    /// Return the least significant 128 bits as a `u128`.
+    pub const fn to_u128(self) -> u128 {
+        (self.0[0] as u128) | ((self.0[1] as u128) << 64)
+    }
+    /// Return the least significant 128 bits as an `i128`.
+    pub const fn to_i128(self) -> i128 {
+        self.to_u128() as i128
+    }
+    /// Return a value with the lower `bits` bits set to 1.
+    pub const fn mask(bits: usize) -> Self {
+        let mut words = [0; WORDS];
+        let mut i = 0;
+        while i < WORDS {
+            let low = i * 64;
+            words[i] = if bits >= low + 64 {
+                u64::MAX
+            } else if bits > low {
+                u64::MAX >> (64 - (bits - low))
+            } else {
+                0
+            };
+            i += 1;
+        }
+        Self(words)
+    }
+    /// Test if bit `ndx` is set.  Bits past [MAX_BITS] read as zero.
+    pub const fn bit(self, ndx: usize) -> bool {
+        ndx < MAX_BITS && (self.0[ndx / 64] >> (ndx % 64)) & 1 == 1
+    }
+    /// Return a copy of the value with bit `ndx` set to `value`.
+    ///
+    /// # Panics
+    /// Panics if `ndx` is not less than [MAX_BITS].
+    pub const fn with_bit(self, ndx: usize, value: bool) -> Self {
+        assert!(ndx < MAX_BITS);
+        let mut words = self.0;
+        let selector = 1_u64 << (ndx % 64);
+        if value {
+            words[ndx / 64] |= selector;
+        } else {
+            words[ndx / 64] &= !selector;
+        }
+        Self(words)
+    }
+    /// Test if the value is zero.
+    pub const fn is_zero(self) -> bool {
+        let mut i = 0;
+        while i < WORDS {
+            if self.0[i] != 0 {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+    /// Test if the most significant bit is set (i.e., the value is
+    /// negative when interpreted as a signed value).
+    pub const fn is_negative(self) -> bool {
+        self.bit(MAX_BITS - 1)
+    }
+    /// A `const` version of equality.
+    pub const fn const_eq(self, rhs: Self) -> bool {
+        self.bitxor(rhs).is_zero()
+    }
+    /// The number of set bits.
+    pub const fn count_ones(self) -> u32 {
+        let mut count = 0;
+        let mut i = 0;
+        while i < WORDS {
+            count += self.0[i].count_ones();
+            i += 1;
+        }
+        count
+    }
+    /// The number of bits needed to represent the value as an unsigned
+    /// quantity (i.e., the index of the highest set bit plus one).
+    pub const fn significant_bits(self) -> usize {
+        let mut i = WORDS;
+        while i > 0 {
+            i -= 1;
+            if self.0[i] != 0 {
+                return i * 64 + 64 - self.0[i].leading_zeros() as usize;
+            }
+        }
+        0
+    }
+    /// Interpret the value as a shift amount.  Values that do not fit
+    /// in a `u32` saturate to `u32::MAX` (which shifts out every bit).
+    pub const fn shift_amount(self) -> u32 {
+        if self.fits_unsigned(32) {
+            self.0[0] as u32
+        } else {
+            u32::MAX
+        }
+    }
+    /// Test if the value is unchanged when truncated to `bits` bits.
+    pub const fn fits_unsigned(self, bits: usize) -> bool {
+        self.significant_bits() <= bits
+    }
+    /// Test if the value is unchanged when truncated to `bits` bits
+    /// and then sign extended.
+    pub const fn fits_signed(self, bits: usize) -> bool {
+        bits > 0 && self.sign_extend(bits).const_eq(self)
+    }
+    /// Sign extend the value from bit `bits - 1` up through [MAX_BITS].
+    pub const fn sign_extend(self, bits: usize) -> Self {
+        let mask = Self::mask(bits);
+        if bits < MAX_BITS && self.bit(bits - 1) {
+            self.bitor(mask.not())
+        } else {
+            self.bitand(mask)
+        }
+    }
+    /// Bitwise AND.
+    pub const fn bitand(self, rhs: Self) -> Self {
+        let mut words = self.0;
+        let mut i = 0;
+        while i < WORDS {
+            words[i] &= rhs.0[i];
+            i += 1;
+        }
+        Self(words)
+    }
+    /// Bitwise OR.
+    pub const fn bitor(self, rhs: Self) -> Self {
+        let mut words = self.0;
+        let mut i = 0;
+        while i < WORDS {
+            words[i] |= rhs.0[i];
+            i += 1;
+        }
+        Self(words)
+    }
+    /// Bitwise XOR.
+    pub const fn bitxor(self, rhs: Self) -> Self {
+        let mut words = self.0;
+        let mut i = 0;
+        while i < WORDS {
+            words[i] ^= rhs.0[i];
+            i += 1;
+        }
+        Self(words)
+    }
+    /// Bitwise NOT.
+    #[allow(clippy::should_implement_trait)]
+    pub const fn not(self) -> Self {
+        let mut words = self.0;
+        let mut i = 0;
+        while i < WORDS {
+            words[i] = !words[i];
+            i += 1;
+        }
+        Self(words)
+    }
+    /// Wrapping addition.
+    pub const fn wrapping_add(self, rhs: Self) -> Self {
+        let mut words = [0; WORDS];
+        let mut carry = 0_u64;
+        let mut i = 0;
+        while i < WORDS {
+            let (sum, c1) = self.0[i].overflowing_add(rhs.0[i]);
+            let (sum, c2) = sum.overflowing_add(carry);
+            words[i] = sum;
+            carry = (c1 | c2) as u64;
+            i += 1;
+        }
+        Self(words)
+    }
+    /// Wrapping subtraction.
+    pub const fn wrapping_sub(self, rhs: Self) -> Self {
+        let mut words = [0; WORDS];
+        let mut borrow = 0_u64;
+        let mut i = 0;
+        while i < WORDS {
+            let (diff, b1) = self.0[i].overflowing_sub(rhs.0[i]);
+            let (diff, b2) = diff.overflowing_sub(borrow);
+            words[i] = diff;
+            borrow = (b1 | b2) as u64;
+            i += 1;
+        }
+        Self(words)
+    }
+    /// Wrapping (2's complement) negation.
+    pub const fn wrapping_neg(self) -> Self {
+        Self::ZERO.wrapping_sub(self)
+    }
+    /// Wrapping multiplication.  Because the result is truncated to
+    /// [MAX_BITS], the same operation serves signed and unsigned values.
+    pub const fn wrapping_mul(self, rhs: Self) -> Self {
+        let mut words = [0; WORDS];
+        let mut i = 0;
+        while i < WORDS {
+            if self.0[i] != 0 {
+                let mut carry = 0_u128;
+                let mut j = 0;
+                while i + j < WORDS {
+                    let t = (self.0[i] as u128) * (rhs.0[j] as u128) + words[i + j] as u128 + carry;
+                    words[i + j] = t as u64;
+                    carry = t >> 64;
+                    j += 1;
+                }
+            }
+            i += 1;
+        }
+        Self(words)
+    }
+    /// Shift left by `shift` bits.  Shifting by [MAX_BITS] or more
+    /// yields zero.
+    pub const fn wrapping_shl(self, shift: u32) -> Self {
+        let shift = shift as usize;
+        if shift >= MAX_BITS {
+            return Self::ZERO;
+        }
+        let (word_shift, bit_shift) = (shift / 64, shift % 64);
+        let mut words = [0; WORDS];
+        let mut i = WORDS;
+        while i > word_shift {
+            i -= 1;
+            let src = i - word_shift;
+            words[i] = self.0[src] << bit_shift;
+            if bit_shift != 0 && src > 0 {
+                words[i] |= self.0[src - 1] >> (64 - bit_shift);
+            }
+        }
+        Self(words)
+    }
+    /// Logical shift right by `shift` bits.  Shifting by [MAX_BITS] or
+    /// more yields zero.
+    pub const fn wrapping_shr(self, shift: u32) -> Self {
+        self.shr_fill(shift, 0)
+    }
+    /// Arithmetic shift right by `shift` bits, replicating the sign bit.
+    pub const fn wrapping_shr_signed(self, shift: u32) -> Self {
+        let fill = if self.is_negative() { u64::MAX } else { 0 };
+        self.shr_fill(shift, fill)
+    }
+    const fn shr_fill(self, shift: u32, fill: u64) -> Self {
+        let shift = shift as usize;
+        if shift >= MAX_BITS {
+            return Self([fill; WORDS]);
+        }
+        let (word_shift, bit_shift) = (shift / 64, shift % 64);
+        let mut words = [fill; WORDS];
+        let mut i = 0;
+        while i + word_shift < WORDS {
+            let src = i + word_shift;
+            let upper = if src + 1 < WORDS {
+                self.0[src + 1]
+            } else {
+                fill
+            };
+            words[i] = self.0[src] >> bit_shift;
+            if bit_shift != 0 {
+                words[i] |= upper << (64 - bit_shift);
+            }
+            i += 1;
+        }
+        Self(words)
+    }
+    /// Unsigned comparison.
+    pub const fn cmp_unsigned(self, rhs: Self) -> Ordering {
+        let mut i = WORDS;
+        while i > 0 {
+            i -= 1;
+            if self.0[i] < rhs.0[i] {
+                return Ordering::Less;
+            }
+            if self.0[i] > rhs.0[i] {
+                return Ordering::Greater;
+            }
+        }
+        Ordering::Equal
+    }
+    /// Signed (2's complement) comparison.
+    pub const fn cmp_signed(self, rhs: Self) -> Ordering {
+        match (self.is_negative(), rhs.is_negative()) {
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            _ => self.cmp_unsigned(rhs),
+        }
+    }
+    /// Unsigned division, returning the quotient and remainder.
+    ///
+    /// Dividing by zero does not panic.  The quotient is all ones and
+    /// the remainder is `self`, matching what the generated hardware does.
+    pub const fn div_rem(self, rhs: Self) -> (Self, Self) {
+        if rhs.is_zero() {
+            return (Self::ONES, self);
+        }
+        if self.fits_unsigned(128) && rhs.fits_unsigned(128) {
+            let (a, b) = (self.to_u128(), rhs.to_u128());
+            return (Self::from_u128(a / b), Self::from_u128(a % b));
+        }
+        let mut quotient = Self::ZERO;
+        let mut remainder = Self::ZERO;
+        let mut ndx = self.significant_bits();
+        while ndx > 0 {
+            ndx -= 1;
+            remainder = remainder.wrapping_shl(1).with_bit(0, self.bit(ndx));
+            if !matches!(remainder.cmp_unsigned(rhs), Ordering::Less) {
+                remainder = remainder.wrapping_sub(rhs);
+                quotient = quotient.with_bit(ndx, true);
+            }
+        }
+        (quotient, remainder)
+    }
+    /// Unsigned division.  Dividing by zero yields all ones.
+    pub const fn wrapping_div(self, rhs: Self) -> Self {
+        self.div_rem(rhs).0
+    }
+    /// Unsigned remainder.  The remainder of a division by zero is `self`.
+    pub const fn wrapping_rem(self, rhs: Self) -> Self {
+        self.div_rem(rhs).1
+    }
+    /// The magnitude of the value when interpreted as a signed value.
+    pub const fn unsigned_abs(self) -> Self {
+        if self.is_negative() {
+            self.wrapping_neg()
+        } else {
+            self
+        }
+    }
+    /// Signed division, truncating towards zero like Rust's integer division.
+    /// Dividing by zero yields all ones (i.e., -1).
+    pub const fn wrapping_div_signed(self, rhs: Self) -> Self {
+        if rhs.is_zero() {
+            return Self::ONES;
+        }
+        let quotient = self.unsigned_abs().wrapping_div(rhs.unsigned_abs());
+        if self.is_negative() != rhs.is_negative() {
+            quotient.wrapping_neg()
+        } else {
+            quotient
+        }
+    }
+    /// Signed remainder.  The result has the sign of `self`, like
+    /// Rust's `%` operator.  The remainder of a division by zero is `self`.
+    pub const fn wrapping_rem_signed(self, rhs: Self) -> Self {
+        let remainder = self.unsigned_abs().wrapping_rem(rhs.unsigned_abs());
+        if self.is_negative() {
+            remainder.wrapping_neg()
+        } else {
+            remainder
+        }
+    }
+    // Divide by a single word, returning the quotient and remainder.
+    fn div_rem_word(self, divisor: u64) -> (Self, u64) {
+        let mut words = [0; WORDS];
+        let mut remainder = 0_u128;
+        let mut i = WORDS;
+        while i > 0 {
+            i -= 1;
+            let current = (remainder << 64) | self.0[i] as u128;
+            words[i] = (current / divisor as u128) as u64;
+            remainder = current % divisor as u128;
+        }
+        (Self(words), remainder as u64)
+    }
+    /// Write the value as an unsigned decimal number.
+    fn write_decimal(self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.fits_unsigned(128) {
+            return write!(f, "{}", self.to_u128());
+        }
+        // Peel off 19 decimal digits at a time (10^19 fits in a u64)
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+        let mut chunks = vec![];
+        let mut value = self;
+        while !value.is_zero() {
+            let (quotient, remainder) = value.div_rem_word(CHUNK);
+            chunks.push(remainder);
+            value = quotient;
+        }
+        let mut chunks = chunks.into_iter().rev();
+        if let Some(first) = chunks.next() {
+            write!(f, "{first}")?;
+        }
+        chunks.try_for_each(|chunk| write!(f, "{chunk:019}"))
+    }
+    /// Write the value with `digits` digits per word, using the
+    /// supplied per-word formatters for the leading and remaining words.
+    fn write_words(
+        self,
+        f: &mut std::fmt::Formatter<'_>,
+        lead: fn(u64, &mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+        rest: fn(u64, &mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+    ) -> std::fmt::Result {
+        let top = self.significant_bits().saturating_sub(1) / 64;
+        lead(self.0[top], f)?;
+        (0..top).rev().try_for_each(|i| rest(self.0[i], f))
+    }
+}
+
+impl PartialOrd for Limbs {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// [Limbs] are ordered as unsigned values.  Use [Limbs::cmp_signed]
+/// for a signed comparison.
+impl Ord for Limbs {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_unsigned(*other)
+    }
+}
+
+impl std::fmt::Debug for Limbs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write_decimal(f)
+    }
+}
+
+/// Formats the value as an unsigned decimal number
+impl std::fmt::Display for Limbs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write_decimal(f)
+    }
+}
+
+impl std::fmt::LowerHex for Limbs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write_words(f, |w, f| write!(f, "{w:x}"), |w, f| write!(f, "{w:016x}"))
+    }
+}
+
+impl std::fmt::UpperHex for Limbs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write_words(f, |w, f| write!(f, "{w:X}"), |w, f| write!(f, "{w:016X}"))
+    }
+}
+
+impl std::fmt::Binary for Limbs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write_words(f, |w, f| write!(f, "{w:b}"), |w, f| write!(f, "{w:064b}"))
+    }
+}
+
+/// Helper to format a sign extended [Limbs] value as a signed decimal number.
+pub(crate) struct SignedDecimal(pub(crate) Limbs);
+
+impl std::fmt::Debug for SignedDecimal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_negative() {
+            write!(f, "-")?;
+        }
+        self.0.unsigned_abs().write_decimal(f)
+    }
+}
+
+impl std::fmt::Display for SignedDecimal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_128() {
+        for x in [0, 1, u128::MAX, 0xDEAD_BEEF_u128 << 64] {
+            assert_eq!(Limbs::from_u128(x).to_u128(), x);
+        }
+        for x in [0, -1, i128::MIN, i128::MAX] {
+            let l = Limbs::from_i128(x);
+            assert_eq!(l.to_i128(), x);
+            assert_eq!(l.is_negative(), x < 0);
+        }
+    }
+
+    #[test]
+    fn test_add_sub_match_u128() {
+        let samples = [0, 1, 0xFFFF_FFFF_FFFF_FFFF, u128::MAX, 1 << 100, 12345];
+        for a in samples {
+            for b in samples {
+                let (la, lb) = (Limbs::from_u128(a), Limbs::from_u128(b));
+                assert_eq!(la.wrapping_add(lb).to_u128(), a.wrapping_add(b));
+                assert_eq!(la.wrapping_sub(lb).to_u128(), a.wrapping_sub(b));
+                assert_eq!(la.wrapping_mul(lb).to_u128(), a.wrapping_mul(b));
+                let (q, r) = a
+                    .checked_div(b)
+                    .zip(a.checked_rem(b))
+                    .unwrap_or((u128::MAX, a));
+                assert_eq!(la.wrapping_div(lb).to_u128(), q);
+                assert_eq!(la.wrapping_rem(lb).to_u128(), r);
+                assert_eq!(la.cmp(&lb), a.cmp(&b));
+            }
+        }
+    }
+
+    #[test]
+    fn test_signed_ops_match_i128() {
+        let samples = [0, 1, -1, 7, -7, i64::MIN as i128, i64::MAX as i128];
+        for a in samples {
+            for b in samples {
+                let (la, lb) = (Limbs::from_i128(a), Limbs::from_i128(b));
+                assert_eq!(la.cmp_signed(lb), a.cmp(&b));
+                let (q, r) = a.checked_div(b).zip(a.checked_rem(b)).unwrap_or((-1, a));
+                assert_eq!(la.wrapping_div_signed(lb).to_i128(), q);
+                assert_eq!(la.wrapping_rem_signed(lb).to_i128(), r);
+            }
+        }
+    }
+
+    #[test]
+    fn test_shifts_cross_words() {
+        let one = Limbs::from_u128(1);
+        for shift in 0..MAX_BITS as u32 {
+            let x = one.wrapping_shl(shift);
+            assert_eq!(x.significant_bits(), shift as usize + 1);
+            assert!(x.wrapping_shr(shift).const_eq(one));
+        }
+        assert!(one.wrapping_shl(MAX_BITS as u32).is_zero());
+        let minus_two = Limbs::from_i128(-2);
+        assert_eq!(minus_two.wrapping_shr_signed(1).to_i128(), -1);
+        assert_eq!(minus_two.wrapping_shr_signed(300).to_i128(), -1);
+    }
+
+    #[test]
+    fn test_mask_and_sign_extend() {
+        assert_eq!(Limbs::mask(8).to_u128(), 0xFF);
+        assert_eq!(Limbs::mask(MAX_BITS).count_ones(), MAX_BITS as u32);
+        assert_eq!(Limbs::mask(200).significant_bits(), 200);
+        let x = Limbs::ZERO.with_bit(199, true).sign_extend(200);
+        assert!(x.is_negative());
+        assert!(x.fits_signed(200));
+        assert!(!x.fits_signed(199));
+    }
+
+    #[test]
+    fn test_format_wide_values() {
+        let x = Limbs::ONES.wrapping_shr((MAX_BITS - 256) as u32);
+        assert_eq!(format!("{x:x}"), "f".repeat(64));
+        assert_eq!(
+            format!("{x}"),
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+        );
+        assert_eq!(format!("{}", Limbs::ZERO), "0");
+        assert_eq!(format!("{:x}", Limbs::from_u128(0xDA)), "da");
+        assert_eq!(format!("{:b}", Limbs::from_u128(0xDA)), "11011010");
+        assert_eq!(format!("{}", SignedDecimal(Limbs::from_i128(-42))), "-42");
+    }
+}
@@ -56,15 +56,16 @@ use std::ops::Mul;
 
 use super::dyn_bits::DynBits;
 use super::signed_dyn_bits::SignedDynBits;
-use super::{BitWidth, Bits, SignedBits, bits_impl::bits_masked, signed_bits_impl::signed_wrapped};
+use super::{BitWidth, Bits, SignedBits, limbs::Limbs};
 
-impl_binop!(Mul, mul, u128::wrapping_mul);
-impl_signed_binop!(Mul, mul, i128::wrapping_mul);
+impl_binop!(Mul, mul, u128::wrapping_mul, Limbs::wrapping_mul);
+impl_signed_binop!(Mul, mul, i128::wrapping_mul, Limbs::wrapping_mul);
+impl_wide_binop!(Mul, mul, Limbs::wrapping_mul);
+impl_wide_signed_binop!(Mul, mul, Limbs::wrapping_mul);
 
 #[cfg(test)]
 mod tests {
     use crate::bits;
-    use crate::bits_impl::bits_masked;
 
     #[test]
     fn test_muls() {
@@ -35,7 +35,9 @@ use crate::bitwidth::W;
 use super::signed_bits_impl::signed_wrapped;
 
 use super::signed_dyn_bits::SignedDynBits;
-use super::{BitWidth, signed_bits_impl::SignedBits};
+use super::{
+    BitWidth, WideBitWidth, signed_bits_impl::SignedBits, signed_wide_bits_impl::SignedWideBits,
+};
 use std::ops::Neg;
 
 impl<const N: usize> Neg for SignedBits<N>
@@ -44,7 +46,17 @@ where
 {
     type Output = SignedBits<N>;
     fn neg(self) -> Self::Output {
-        signed_wrapped(-self.raw())
+        signed_wrapped(self.raw().wrapping_neg())
+    }
+}
+
+impl<const N: usize> Neg for SignedWideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    type Output = SignedWideBits<N>;
+    fn neg(self) -> Self::Output {
+        SignedWideBits::from_limbs_wrapped(self.limbs().wrapping_neg())
     }
 }
 
@@ -52,7 +64,7 @@ impl Neg for SignedDynBits {
     type Output = SignedDynBits;
     fn neg(self) -> Self::Output {
         SignedDynBits {
-            val: -self.val,
+            val: self.val.wrapping_neg(),
             bits: self.bits,
         }
         .wrapped()
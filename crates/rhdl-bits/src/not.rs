@@ -23,7 +23,10 @@ use std::ops::Not;
 
 use crate::bitwidth::W;
 
-use super::{BitWidth, bits_impl::Bits, signed_bits_impl::SignedBits};
+use super::{
+    BitWidth, WideBitWidth, bits_impl::Bits, signed_bits_impl::SignedBits,
+    signed_wide_bits_impl::SignedWideBits, wide_bits_impl::WideBits,
+};
 
 impl<const N: usize> Not for Bits<N>
 where
@@ -45,6 +48,26 @@ where
     }
 }
 
+impl<const N: usize> Not for WideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    type Output = Self;
+    fn not(self) -> Self::Output {
+        Self::from_limbs_masked(self.limbs().not())
+    }
+}
+
+impl<const N: usize> Not for SignedWideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    type Output = Self;
+    fn not(self) -> Self::Output {
+        self.as_unsigned().not().as_signed()
+    }
+}
+
 #[cfg(test)]
 mod test {
 
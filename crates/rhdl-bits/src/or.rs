@@ -38,11 +38,13 @@ use std::ops::BitOrAssign;
 
 use super::BitWidth;
 use super::bits_impl::Bits;
-use super::bits_impl::bits_masked;
 use super::dyn_bits::DynBits;
+use super::limbs::Limbs;
 
-impl_binop!(BitOr, bitor, u128::bitor);
-impl_assign_op!(BitOrAssign, bitor_assign, u128::bitor);
+impl_binop!(BitOr, bitor, u128::bitor, Limbs::bitor);
+impl_assign_op!(BitOrAssign, bitor_assign, u128::bitor, Limbs::bitor);
+impl_wide_binop!(BitOr, bitor, Limbs::bitor);
+impl_wide_assign_op!(BitOrAssign, bitor_assign, Limbs::bitor);
 
 #[cfg(test)]
 mod test {
@@ -0,0 +1,133 @@
+//! # Remainder operations via `%` and `%=`
+//!
+//! The remainder follows the same rules as Rust integer remainder.  The result
+//! has the same sign as the dividend.  Unlike Rust, taking the remainder by zero does
+//! not panic.  The result is the dividend, which is also what the generated hardware
+//! produces.  Here is a simple example using 2 8-bit unsigned values:
+//! ```
+//! # use rhdl_bits::*;
+//! # use rhdl_bits::alias::*;
+//! let a: Bits<8> = 200.into();
+//! let b: Bits<8> = 7.into();
+//! let c = a % b; // 4
+//! assert_eq!(c, b8(4));
+//! ```
+//!
+//! We can convert them to [DynBits] and take the remainder too:
+//! ```
+//! # use rhdl_bits::*;
+//! # use rhdl_bits::alias::*;
+//! # let a: Bits<8> = 200.into();
+//! # let b: Bits<8> = 7.into();
+//! let a = a.dyn_bits();
+//! let b = b.dyn_bits();
+//! let c = a % b; // 4
+//! assert_eq!(c.as_bits::<8>(), b8(4));
+//! ```
+//!
+//! When working with signed values, remember to put parentheses around negative literals:
+//! ```
+//! # use rhdl_bits::*;
+//! # use rhdl_bits::alias::*;
+//! let a : SignedBits<8> = (-100).into();
+//! let b : SignedBits<8> = 7.into();
+//! let c = a % b; // -2
+//! assert_eq!(c, s8(-2));
+//! ```
+//!
+//! Note that you cannot mix unsigned and signed types, any more than you can take the
+//! remainder of `i8` and `u8` in normal Rust.
+//!
+//! You can also use the `%=` operator to take the remainder and assign in place:
+//! ```
+//! # use rhdl_bits::*;
+//! # use rhdl_bits::alias::*;
+//! let mut a: Bits<8> = 200.into();
+//! a %= 7; // a is now 4
+//! assert_eq!(a, b8(4));
+//! ```
+
+use std::ops::Rem;
+use std::ops::RemAssign;
+
+use super::BitWidth;
+use super::bits_impl::Bits;
+use super::dyn_bits::DynBits;
+use super::limbs::Limbs;
+use super::signed_bits_impl::SignedBits;
+use super::signed_dyn_bits::SignedDynBits;
+
+// The remainder of a division by zero is the dividend, like the generated
+// hardware and the [Limbs] implementation.
+fn wrapping_rem(lhs: u128, rhs: u128) -> u128 {
+    lhs.checked_rem(rhs).unwrap_or(lhs)
+}
+
+fn wrapping_rem_signed(lhs: i128, rhs: i128) -> i128 {
+    if rhs == 0 { lhs } else { lhs.wrapping_rem(rhs) }
+}
+
+impl_binop!(Rem, rem, wrapping_rem, Limbs::wrapping_rem);
+impl_assign_op!(RemAssign, rem_assign, wrapping_rem, Limbs::wrapping_rem);
+impl_signed_binop!(Rem, rem, wrapping_rem_signed, Limbs::wrapping_rem_signed);
+impl_assigned_signed_op!(
+    RemAssign,
+    rem_assign,
+    wrapping_rem_signed,
+    Limbs::wrapping_rem_signed
+);
+impl_wide_binop!(Rem, rem, Limbs::wrapping_rem);
+impl_wide_assign_op!(RemAssign, rem_assign, Limbs::wrapping_rem);
+impl_wide_signed_binop!(Rem, rem, Limbs::wrapping_rem_signed);
+impl_wide_assigned_signed_op!(RemAssign, rem_assign, Limbs::wrapping_rem_signed);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rem() {
+        // Zero is excluded, since the test also divides by the left argument
+        for i in 1..=255 {
+            for j in 1..=255 {
+                test_binop!(%, u128::wrapping_rem, i, j);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rem_by_zero_is_dividend() {
+        let a: Bits<8> = 20.into();
+        assert_eq!(a % Bits::<8>::from(0), a);
+        let a: SignedBits<8> = (-20).into();
+        assert_eq!(a % SignedBits::<8>::from(0), a);
+    }
+
+    #[test]
+    fn test_remainder_matches_built_in_behavior_for_i8() {
+        for i in i8::MIN..=i8::MAX {
+            for j in i8::MIN..=i8::MAX {
+                if j == 0 {
+                    continue;
+                }
+                let i_as_signed = SignedBits::<8>::from(i as i128);
+                let j_as_signed = SignedBits::<8>::from(j as i128);
+                let k_as_signed = i_as_signed % j_as_signed;
+                let k = i8::wrapping_rem(i, j);
+                assert_eq!(k_as_signed.raw(), k as i128);
+                let k_as_dyn = i_as_signed.dyn_bits() % j_as_signed.dyn_bits();
+                assert_eq!(k_as_dyn.as_signed_bits::<8>(), k_as_signed);
+            }
+        }
+    }
+
+    #[test]
+    fn test_remassign() {
+        let mut x = SignedBits::<8>::from(-100);
+        x %= 3;
+        assert_eq!(x.raw(), -1);
+        let mut y = Bits::<8>::from(100);
+        y %= Bits::<8>::from(3);
+        assert_eq!(y.raw(), 1);
+    }
+}
@@ -102,17 +102,23 @@ use super::bits_impl::bits_masked;
 use super::dyn_bits::DynBits;
 use super::signed_bits_impl::SignedBits;
 use super::signed_dyn_bits::SignedDynBits;
+use super::{SignedWideBits, WideBitWidth, WideBits};
 
 // Note! When reviewing this code remember that wrapping is not the same
 // as rotate.
 
+// Shifting by the width of a u128 or more clears all of the bits.
+const fn shl_u128(value: u128, amount: u128) -> u128 {
+    if amount >= 128 { 0 } else { value << amount }
+}
+
 impl<const N: usize> Shl<u128> for Bits<N>
 where
     W<N>: BitWidth,
 {
     type Output = Self;
     fn shl(self, rhs: u128) -> Self::Output {
-        bits_masked(self.raw().wrapping_shl(rhs as u32))
+        bits_masked(shl_u128(self.raw(), rhs))
     }
 }
 
@@ -122,7 +128,7 @@ where
 {
     type Output = Bits<N>;
     fn shl(self, rhs: Bits<N>) -> Self::Output {
-        bits_masked(self.wrapping_shl(rhs.raw() as u32))
+        bits_masked(shl_u128(self, rhs.raw()))
     }
 }
 
@@ -133,7 +139,7 @@ where
 {
     type Output = Self;
     fn shl(self, rhs: Bits<M>) -> Self::Output {
-        bits_masked(u128::wrapping_shl(self.raw(), rhs.raw() as u32))
+        bits_masked(shl_u128(self.raw(), rhs.raw()))
     }
 }
 
@@ -143,9 +149,9 @@ where
 {
     type Output = DynBits;
     fn shl(self, rhs: Bits<N>) -> Self::Output {
-        assert!(rhs.raw() < self.bits as u128);
+        assert!((rhs.limbs().shift_amount() as usize) < self.bits);
         DynBits {
-            val: self.raw().wrapping_shl(rhs.raw() as u32),
+            val: self.limbs().wrapping_shl(rhs.limbs().shift_amount()),
             bits: self.bits,
         }
         .wrapped()
@@ -158,17 +164,17 @@ where
 {
     type Output = Bits<N>;
     fn shl(self, rhs: DynBits) -> Self::Output {
-        assert!(rhs.raw() <= { N } as u128);
-        bits_masked(self.raw().wrapping_shl(rhs.raw() as u32))
+        assert!((rhs.limbs().shift_amount() as usize) <= N);
+        Bits::from_limbs_masked(self.limbs().wrapping_shl(rhs.limbs().shift_amount()))
     }
 }
 
 impl Shl<DynBits> for DynBits {
     type Output = DynBits;
     fn shl(self, rhs: DynBits) -> Self::Output {
-        assert!(rhs.raw() < self.bits as u128);
+        assert!((rhs.limbs().shift_amount() as usize) < self.bits);
         DynBits {
-            val: self.raw().wrapping_shl(rhs.raw() as u32),
+            val: self.limbs().wrapping_shl(rhs.limbs().shift_amount()),
             bits: self.bits,
         }
         .wrapped()
@@ -180,7 +186,9 @@ impl Shl<u128> for DynBits {
     fn shl(self, rhs: u128) -> Self::Output {
         assert!(rhs <= self.bits as u128);
         DynBits {
-            val: self.raw().wrapping_shl(rhs as u32),
+            val: self
+                .limbs()
+                .wrapping_shl(u32::try_from(rhs).unwrap_or(u32::MAX)),
             bits: self.bits,
         }
         .wrapped()
@@ -233,7 +241,7 @@ where
 {
     type Output = Self;
     fn shl(self, rhs: DynBits) -> Self::Output {
-        assert!(rhs.raw() < { N } as u128);
+        assert!((rhs.limbs().shift_amount() as usize) < N);
         self.as_unsigned().shl(rhs).as_signed()
     }
 }
@@ -263,9 +271,9 @@ where
 {
     type Output = Self;
     fn shl(self, rhs: Bits<N>) -> Self::Output {
-        assert!(rhs.raw() <= self.bits as u128);
+        assert!((rhs.limbs().shift_amount() as usize) <= self.bits);
         SignedDynBits {
-            val: self.raw().wrapping_shl(rhs.raw() as u32),
+            val: self.limbs().wrapping_shl(rhs.limbs().shift_amount()),
             bits: self.bits,
         }
         .wrapped()
@@ -275,9 +283,9 @@ where
 impl Shl<DynBits> for SignedDynBits {
     type Output = SignedDynBits;
     fn shl(self, rhs: DynBits) -> Self::Output {
-        assert!(rhs.raw() <= self.bits as u128);
+        assert!((rhs.limbs().shift_amount() as usize) <= self.bits);
         SignedDynBits {
-            val: self.raw().wrapping_shl(rhs.raw() as u32),
+            val: self.limbs().wrapping_shl(rhs.limbs().shift_amount()),
             bits: self.bits,
         }
         .wrapped()
@@ -289,13 +297,99 @@ impl Shl<u128> for SignedDynBits {
     fn shl(self, rhs: u128) -> Self::Output {
         assert!(rhs <= self.bits as u128);
         SignedDynBits {
-            val: self.raw().wrapping_shl(rhs as u32),
+            val: self
+                .limbs()
+                .wrapping_shl(u32::try_from(rhs).unwrap_or(u32::MAX)),
             bits: self.bits,
         }
         .wrapped()
     }
 }
 
+impl<const N: usize> Shl<u128> for WideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    type Output = Self;
+    fn shl(self, rhs: u128) -> Self::Output {
+        WideBits::from_limbs_masked(
+            self.limbs()
+                .wrapping_shl(u32::try_from(rhs).unwrap_or(u32::MAX)),
+        )
+    }
+}
+
+impl<const N: usize, const M: usize> Shl<Bits<M>> for WideBits<N>
+where
+    W<N>: WideBitWidth,
+    W<M>: BitWidth,
+{
+    type Output = Self;
+    fn shl(self, rhs: Bits<M>) -> Self::Output {
+        self << rhs.raw()
+    }
+}
+
+impl<const N: usize, const M: usize> Shl<WideBits<M>> for WideBits<N>
+where
+    W<N>: WideBitWidth,
+    W<M>: WideBitWidth,
+{
+    type Output = Self;
+    fn shl(self, rhs: WideBits<M>) -> Self::Output {
+        WideBits::from_limbs_masked(self.limbs().wrapping_shl(rhs.limbs().shift_amount()))
+    }
+}
+
+impl<const N: usize> ShlAssign<u128> for WideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    fn shl_assign(&mut self, rhs: u128) {
+        *self = *self << rhs;
+    }
+}
+
+impl<const N: usize, const M: usize> ShlAssign<Bits<M>> for WideBits<N>
+where
+    W<N>: WideBitWidth,
+    W<M>: BitWidth,
+{
+    fn shl_assign(&mut self, rhs: Bits<M>) {
+        *self = *self << rhs;
+    }
+}
+
+impl<const N: usize> Shl<u128> for SignedWideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    type Output = Self;
+    fn shl(self, rhs: u128) -> Self::Output {
+        (self.as_unsigned() << rhs).as_signed()
+    }
+}
+
+impl<const N: usize, const M: usize> Shl<Bits<M>> for SignedWideBits<N>
+where
+    W<N>: WideBitWidth,
+    W<M>: BitWidth,
+{
+    type Output = Self;
+    fn shl(self, rhs: Bits<M>) -> Self::Output {
+        (self.as_unsigned() << rhs).as_signed()
+    }
+}
+
+impl<const N: usize> ShlAssign<u128> for SignedWideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    fn shl_assign(&mut self, rhs: u128) {
+        *self = *self << rhs;
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -105,6 +105,17 @@ use super::bits_impl::bits_masked;
 use super::dyn_bits::DynBits;
 use super::signed;
 use super::signed_bits_impl::SignedBits;
+use super::{SignedWideBits, WideBitWidth, WideBits};
+
+// Shifting by the width of a u128 or more clears all of the bits.
+const fn shr_u128(value: u128, amount: u128) -> u128 {
+    if amount >= 128 { 0 } else { value >> amount }
+}
+
+// Shifting by the width of an i128 or more leaves only copies of the sign bit.
+const fn shr_i128(value: i128, amount: u128) -> i128 {
+    value >> if amount >= 128 { 127 } else { amount }
+}
 
 impl<const N: usize> Shr<u128> for Bits<N>
 where
@@ -112,7 +123,7 @@ where
 {
     type Output = Self;
     fn shr(self, rhs: u128) -> Self::Output {
-        bits_masked(self.raw().wrapping_shr(rhs as u32))
+        bits_masked(shr_u128(self.raw(), rhs))
     }
 }
 
@@ -122,7 +133,7 @@ where
 {
     type Output = Bits<N>;
     fn shr(self, rhs: Bits<N>) -> Self::Output {
-        bits_masked(self.wrapping_shr(rhs.raw() as u32))
+        bits_masked(shr_u128(self, rhs.raw()))
     }
 }
 
@@ -133,7 +144,7 @@ where
 {
     type Output = Self;
     fn shr(self, rhs: Bits<M>) -> Self::Output {
-        bits_masked(u128::wrapping_shr(self.raw(), rhs.raw() as u32))
+        bits_masked(shr_u128(self.raw(), rhs.raw()))
     }
 }
 
@@ -143,9 +154,9 @@ where
 {
     type Output = DynBits;
     fn shr(self, rhs: Bits<N>) -> Self::Output {
-        assert!(rhs.raw() < self.bits as u128);
+        assert!((rhs.limbs().shift_amount() as usize) < self.bits);
         DynBits {
-            val: self.raw().wrapping_shr(rhs.raw() as u32),
+            val: self.limbs().wrapping_shr(rhs.limbs().shift_amount()),
             bits: self.bits,
         }
         .wrapped()
@@ -158,17 +169,17 @@ where
 {
     type Output = Bits<N>;
     fn shr(self, rhs: DynBits) -> Self::Output {
-        assert!(rhs.raw() < N as u128);
-        bits_masked(self.raw().wrapping_shr(rhs.raw() as u32))
+        assert!((rhs.limbs().shift_amount() as usize) < N);
+        Bits::from_limbs_masked(self.limbs().wrapping_shr(rhs.limbs().shift_amount()))
     }
 }
 
 impl Shr<DynBits> for DynBits {
     type Output = DynBits;
     fn shr(self, rhs: DynBits) -> Self::Output {
-        assert!(rhs.raw() < self.bits as u128);
+        assert!((rhs.limbs().shift_amount() as usize) < self.bits);
         DynBits {
-            val: self.raw().wrapping_shr(rhs.raw() as u32),
+            val: self.limbs().wrapping_shr(rhs.limbs().shift_amount()),
             bits: self.bits,
         }
         .wrapped()
@@ -180,7 +191,9 @@ impl Shr<u128> for DynBits {
     fn shr(self, rhs: u128) -> Self::Output {
         assert!(rhs <= self.bits as u128);
         DynBits {
-            val: self.raw().wrapping_shr(rhs as u32),
+            val: self
+                .limbs()
+                .wrapping_shr(u32::try_from(rhs).unwrap_or(u32::MAX)),
             bits: self.bits,
         }
         .wrapped()
@@ -213,7 +226,7 @@ where
 {
     type Output = Self;
     fn shr(self, rhs: Bits<M>) -> Self::Output {
-        signed(i128::wrapping_shr(self.raw(), rhs.raw() as u32))
+        signed(shr_i128(self.raw(), rhs.raw()))
     }
 }
 
@@ -223,7 +236,7 @@ where
 {
     type Output = Self;
     fn shr(self, rhs: u128) -> Self::Output {
-        signed(self.raw().wrapping_shr(rhs as u32))
+        signed(shr_i128(self.raw(), rhs))
     }
 }
 
@@ -252,8 +265,8 @@ where
 {
     type Output = Self;
     fn shr(self, rhs: DynBits) -> Self::Output {
-        assert!(rhs.raw() < N as u128);
-        signed(i128::wrapping_shr(self.raw(), rhs.raw() as u32))
+        assert!((rhs.limbs().shift_amount() as usize) < N);
+        SignedBits::from_limbs(self.limbs().wrapping_shr_signed(rhs.limbs().shift_amount()))
     }
 }
 
@@ -263,9 +276,9 @@ where
 {
     type Output = Self;
     fn shr(self, rhs: Bits<N>) -> Self::Output {
-        assert!(rhs.raw() <= self.bits as u128);
+        assert!((rhs.limbs().shift_amount() as usize) <= self.bits);
         SignedDynBits {
-            val: self.raw().wrapping_shr(rhs.raw() as u32),
+            val: self.limbs().wrapping_shr_signed(rhs.limbs().shift_amount()),
             bits: self.bits,
         }
         .wrapped()
@@ -275,9 +288,9 @@ where
 impl Shr<DynBits> for SignedDynBits {
     type Output = SignedDynBits;
     fn shr(self, rhs: DynBits) -> Self::Output {
-        assert!(rhs.raw() <= self.bits as u128);
+        assert!((rhs.limbs().shift_amount() as usize) <= self.bits);
         SignedDynBits {
-            val: self.raw().wrapping_shr(rhs.raw() as u32),
+            val: self.limbs().wrapping_shr_signed(rhs.limbs().shift_amount()),
             bits: self.bits,
         }
         .wrapped()
@@ -289,13 +302,102 @@ impl Shr<u128> for SignedDynBits {
     fn shr(self, rhs: u128) -> Self::Output {
         assert!(rhs <= self.bits as u128);
         SignedDynBits {
-            val: self.raw().wrapping_shr(rhs as u32),
+            val: self
+                .limbs()
+                .wrapping_shr_signed(u32::try_from(rhs).unwrap_or(u32::MAX)),
             bits: self.bits,
         }
         .wrapped()
     }
 }
 
+impl<const N: usize> Shr<u128> for WideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    type Output = Self;
+    fn shr(self, rhs: u128) -> Self::Output {
+        WideBits::from_limbs_masked(
+            self.limbs()
+                .wrapping_shr(u32::try_from(rhs).unwrap_or(u32::MAX)),
+        )
+    }
+}
+
+impl<const N: usize, const M: usize> Shr<Bits<M>> for WideBits<N>
+where
+    W<N>: WideBitWidth,
+    W<M>: BitWidth,
+{
+    type Output = Self;
+    fn shr(self, rhs: Bits<M>) -> Self::Output {
+        self >> rhs.raw()
+    }
+}
+
+impl<const N: usize, const M: usize> Shr<WideBits<M>> for WideBits<N>
+where
+    W<N>: WideBitWidth,
+    W<M>: WideBitWidth,
+{
+    type Output = Self;
+    fn shr(self, rhs: WideBits<M>) -> Self::Output {
+        WideBits::from_limbs_masked(self.limbs().wrapping_shr(rhs.limbs().shift_amount()))
+    }
+}
+
+impl<const N: usize> ShrAssign<u128> for WideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    fn shr_assign(&mut self, rhs: u128) {
+        *self = *self >> rhs;
+    }
+}
+
+impl<const N: usize, const M: usize> ShrAssign<Bits<M>> for WideBits<N>
+where
+    W<N>: WideBitWidth,
+    W<M>: BitWidth,
+{
+    fn shr_assign(&mut self, rhs: Bits<M>) {
+        *self = *self >> rhs;
+    }
+}
+
+impl<const N: usize> Shr<u128> for SignedWideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    type Output = Self;
+    fn shr(self, rhs: u128) -> Self::Output {
+        SignedWideBits::from_limbs(
+            self.limbs()
+                .wrapping_shr_signed(u32::try_from(rhs).unwrap_or(u32::MAX)),
+        )
+    }
+}
+
+impl<const N: usize, const M: usize> Shr<Bits<M>> for SignedWideBits<N>
+where
+    W<N>: WideBitWidth,
+    W<M>: BitWidth,
+{
+    type Output = Self;
+    fn shr(self, rhs: Bits<M>) -> Self::Output {
+        self >> rhs.raw()
+    }
+}
+
+impl<const N: usize> ShrAssign<u128> for SignedWideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    fn shr_assign(&mut self, rhs: u128) {
+        *self = *self >> rhs;
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
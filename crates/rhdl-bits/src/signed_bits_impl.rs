@@ -1,6 +1,7 @@
 #![allow(non_camel_case_types)]
 use super::{BitWidth, Bits, bits, bits_impl::bits_masked, signed_dyn_bits::SignedDynBits};
 use crate::bitwidth::W;
+use crate::limbs::Limbs;
 use seq_macro::seq;
 
 /// The [SignedBits] type is a fixed-size bit vector.  It is
@@ -11,7 +12,7 @@ use seq_macro::seq;
 /// tuples and structs.  The only real limitation of the [SignedBits]
 /// type being 128 bits is that you cannot perform arbitrary arithmetic
 /// on longer bit values in your hardware designs.  Even that limitation
-/// can be worked around.
+/// can be worked around (see [SignedWideBits](crate::SignedWideBits)).
 ///
 /// Signed arithmetic is performed using 2's complement arithmetic.
 /// See <https://en.wikipedia.org/wiki/Two%27s_complement> for more
@@ -201,11 +202,34 @@ where
     pub const fn raw(self) -> i128 {
         self.0
     }
+    /// Extract the raw signed `i128` backing this SignedBits
+    /// value.  This always succeeds, and is provided for symmetry
+    /// with [SignedWideBits::try_raw](crate::SignedWideBits::try_raw).
+    pub const fn try_raw(self) -> Option<i128> {
+        Some(self.0)
+    }
+    /// Convert the value to (sign extended) [Limbs].
+    pub const fn limbs(self) -> Limbs {
+        Limbs::from_i128(self.0)
+    }
+    /// Build a [SignedBits] value from sign extended [Limbs].
+    ///
+    /// # Panics
+    /// Panics if the value does not fit in `N` bits.
+    pub const fn from_limbs(value: Limbs) -> Self {
+        assert!(value.fits_signed(N));
+        Self(value.to_i128())
+    }
+    /// Build a [SignedBits] value from the lower `N` bits
+    /// of the given [Limbs], sign extending from bit `N-1`.
+    pub const fn from_limbs_wrapped(value: Limbs) -> Self {
+        signed_wrapped(value.to_u128() as i128)
+    }
     /// Convert the compile time sized [SignedBits] value
     /// to a run-time traced [SignedDynBits] value.
     pub const fn dyn_bits(self) -> SignedDynBits {
         SignedDynBits {
-            val: self.raw(),
+            val: self.limbs(),
             bits: { N },
         }
     }
@@ -239,6 +263,85 @@ where
         x ^= x >> 64;
         x & 1 == 1
     }
+    /// Add two values, clamping the result to [SignedBits::MAX] or
+    /// [SignedBits::MIN] if the sum does not fit.
+    /// Can be called in a synthesizable context
+    /// ```
+    /// # use rhdl_bits::alias::*;
+    /// assert_eq!(s8(100).saturating_add(s8(100)), s8(127));
+    /// assert_eq!(s8(-100).saturating_add(s8(-100)), s8(-128));
+    /// ```
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        match self.overflowing_add(rhs) {
+            (_, true) if self.is_negative() => Self::MIN,
+            (_, true) => Self::MAX,
+            (sum, false) => sum,
+        }
+    }
+    /// Subtract two values, clamping the result to [SignedBits::MAX] or
+    /// [SignedBits::MIN] if the difference does not fit.
+    /// Can be called in a synthesizable context
+    /// ```
+    /// # use rhdl_bits::alias::*;
+    /// assert_eq!(s8(-100).saturating_sub(s8(100)), s8(-128));
+    /// assert_eq!(s8(100).saturating_sub(s8(-100)), s8(127));
+    /// ```
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        match self.overflowing_sub(rhs) {
+            (_, true) if self.is_negative() => Self::MIN,
+            (_, true) => Self::MAX,
+            (difference, false) => difference,
+        }
+    }
+    /// Add two values, returning the wrapped sum and a flag that
+    /// is set if the addition overflowed.
+    /// Can be called in a synthesizable context
+    /// ```
+    /// # use rhdl_bits::alias::*;
+    /// assert_eq!(s8(100).overflowing_add(s8(100)), (s8(-56), true));
+    /// ```
+    pub fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+        let sum = self + rhs;
+        let overflow =
+            self.is_negative() == rhs.is_negative() && sum.is_negative() != self.is_negative();
+        (sum, overflow)
+    }
+    /// Subtract two values, returning the wrapped difference and a flag
+    /// that is set if the subtraction overflowed.
+    /// Can be called in a synthesizable context
+    /// ```
+    /// # use rhdl_bits::alias::*;
+    /// assert_eq!(s8(-100).overflowing_sub(s8(100)), (s8(56), true));
+    /// ```
+    pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+        let difference = self - rhs;
+        let overflow = self.is_negative() != rhs.is_negative()
+            && difference.is_negative() != self.is_negative();
+        (difference, overflow)
+    }
+    /// Count the number of bits that are set (including the sign bit).
+    /// The count is returned as an unsigned value of the same width.
+    /// Can be called in a synthesizable context
+    pub fn count_ones(self) -> Bits<N> {
+        self.as_unsigned().count_ones()
+    }
+    /// Count the number of zero bits above the highest set bit.
+    /// The count is returned as an unsigned value of the same width.
+    /// Can be called in a synthesizable context
+    pub fn leading_zeros(self) -> Bits<N> {
+        self.as_unsigned().leading_zeros()
+    }
+    /// Count the number of zero bits below the lowest set bit.
+    /// The count is returned as an unsigned value of the same width.
+    /// Can be called in a synthesizable context
+    pub fn trailing_zeros(self) -> Bits<N> {
+        self.as_unsigned().trailing_zeros()
+    }
+    /// Reverse the order of the bits.
+    /// Can be called in a synthesizable context
+    pub fn reverse_bits(self) -> Self {
+        self.as_unsigned().reverse_bits().as_signed()
+    }
     /// Resize the [SignedBits] value to a different number of bits.
     /// If the new size is larger than the current size, then sign
     /// extension is performed.  If the new size is smaller than the
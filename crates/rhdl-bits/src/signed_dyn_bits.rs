@@ -14,35 +14,69 @@
 //! assert_eq!(c, s8(-50));
 //! ```
 use crate::bitwidth::W;
-use crate::signed_bits_impl::signed_wrapped;
+use crate::limbs::{Limbs, MAX_BITS, SignedDecimal};
 
-use super::{BitWidth, SignedBits, dyn_bits::DynBits};
+use super::{BitWidth, SignedBits, SignedWideBits, WideBitWidth, dyn_bits::DynBits};
 
 /// A signed bit vector whose size is determined at runtime.
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct SignedDynBits {
-    /// The raw value of the bits.  Represented as 2s complement
-    /// integer.  Only the lower `bits` bits are valid.
-    pub(crate) val: i128,
-    /// The number of bits in this value.  Must be in the range 1..=128.
+    /// The raw value of the bits.  Represented as a sign extended
+    /// 2s complement integer.  Only the lower `bits` bits are valid.
+    pub(crate) val: Limbs,
+    /// The number of bits in this value.  Must be in the range 1..=[MAX_BITS].
     pub(crate) bits: usize,
 }
 
 impl std::fmt::Debug for SignedDynBits {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{val}_ds{bits}", bits = self.bits, val = self.val)
+        write!(
+            f,
+            "{val}_ds{bits}",
+            bits = self.bits,
+            val = SignedDecimal(self.val)
+        )
+    }
+}
+
+impl PartialOrd for SignedDynBits {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Values are compared as signed integers first, and then by size.
+impl Ord for SignedDynBits {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.val
+            .cmp_signed(other.val)
+            .then(self.bits.cmp(&other.bits))
     }
 }
 
 impl SignedDynBits {
     /// The largest positive value that can be represented
     /// with this number of bits.
+    ///
+    /// # Panics
+    /// Panics if the value is wider than 128 bits.
     pub const fn max_value(self) -> i128 {
+        assert!(
+            self.bits <= 128,
+            "SignedDynBits wider than 128 bits do not fit in an i128"
+        );
         i128::MAX >> (128 - self.bits)
     }
     /// The smallest (most negative) value that can be represented
     /// with this number of bits.
+    ///
+    /// # Panics
+    /// Panics if the value is wider than 128 bits.
     pub const fn min_value(self) -> i128 {
+        assert!(
+            self.bits <= 128,
+            "SignedDynBits wider than 128 bits do not fit in an i128"
+        );
         i128::MIN >> (128 - self.bits)
     }
     /// Convert to an unsigned [DynBits] value.
@@ -50,45 +84,58 @@ impl SignedDynBits {
     /// The value is reinterpreted as an unsigned value.
     pub const fn as_unsigned(self) -> DynBits {
         DynBits {
-            val: self.val as u128,
+            val: self.val,
             bits: self.bits,
         }
         .masked()
     }
     /// Extract the raw i128 value.
+    ///
+    /// # Panics
+    /// Panics if the value is wider than 128 bits.  Use [SignedDynBits::try_raw]
+    /// or [SignedDynBits::limbs] to access wider values.
     pub const fn raw(self) -> i128 {
+        assert!(
+            self.bits <= 128,
+            "SignedDynBits wider than 128 bits do not fit in an i128"
+        );
+        self.val.to_i128()
+    }
+    /// Returns the value as an `i128`, or `None` if the value
+    /// does not fit in 128 bits.
+    pub const fn try_raw(self) -> Option<i128> {
+        if self.val.fits_signed(128) {
+            Some(self.val.to_i128())
+        } else {
+            None
+        }
+    }
+    /// Returns the (sign extended) [Limbs] holding the value.
+    pub const fn limbs(self) -> Limbs {
         self.val
     }
     /// Returns true if any bit is set.
     /// Can be called in a synthesizable context
     pub const fn any(self) -> bool {
-        self.val != 0
+        !self.val.is_zero()
     }
     /// Returns true if all bits are set.
     /// Can be called in a synthesizable context
     pub const fn all(self) -> bool {
-        self.val == -1
+        self.val.const_eq(Limbs::ONES)
     }
     /// Returns true if the number of set bits is odd.
     /// Can be called in a synthesizable context
     pub const fn xor(self) -> bool {
-        let mut x = self.val as u128;
-        x ^= x >> 1;
-        x ^= x >> 2;
-        x ^= x >> 4;
-        x ^= x >> 8;
-        x ^= x >> 16;
-        x ^= x >> 32;
-        x ^= x >> 64;
-        x & 1 == 1
+        self.as_unsigned().xor()
     }
     /// Sign extend the value by the given number of bits, returning a [SignedDynBits] value.
     /// The output size is the input size plus the extension amount.
     ///
     /// # Panics
-    /// Panics if the output size would be greater than 128 bits.
+    /// Panics if the output size would be greater than [MAX_BITS] bits.
     pub const fn xext<const M: usize>(self) -> SignedDynBits {
-        assert!(self.bits + M <= 128);
+        assert!(self.bits + M <= MAX_BITS);
         SignedDynBits {
             val: self.val,
             bits: M + self.bits,
@@ -101,18 +148,18 @@ impl SignedDynBits {
     pub const fn xshr<const M: usize>(self) -> SignedDynBits {
         assert!(self.bits > M);
         SignedDynBits {
-            val: self.val >> M,
+            val: self.val.wrapping_shr_signed(M as u32),
             bits: self.bits - M,
         }
     }
     /// Shift left by a constant amount, returning a [SignedDynBits] value.
     /// The output size is the input size plus the shift amount.
     /// # Panics
-    /// Panics if the output size would be greater than 128 bits.
+    /// Panics if the output size would be greater than [MAX_BITS] bits.
     pub const fn xshl<const M: usize>(self) -> SignedDynBits {
-        assert!(self.bits + M <= 128);
+        assert!(self.bits + M <= MAX_BITS);
         SignedDynBits {
-            val: self.val << M,
+            val: self.val.wrapping_shl(M as u32),
             bits: self.bits + M,
         }
     }
@@ -145,7 +192,17 @@ impl SignedDynBits {
         W<N>: BitWidth,
     {
         assert!(N == self.bits);
-        signed_wrapped(self.val)
+        SignedBits::from_limbs_wrapped(self.val)
+    }
+    /// Convert to a [SignedWideBits] value of the given size.
+    /// # Panics
+    /// Panics if the size does not match the current size.
+    pub const fn as_signed_wide_bits<const N: usize>(self) -> SignedWideBits<N>
+    where
+        W<N>: WideBitWidth,
+    {
+        assert!(N == self.bits);
+        SignedWideBits::from_limbs_wrapped(self.val)
     }
     /// Returns the number of bits in this value.
     pub const fn bits(self) -> usize {
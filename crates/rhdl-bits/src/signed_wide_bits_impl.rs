@@ -0,0 +1,430 @@
+#![allow(non_camel_case_types)]
+use super::{WideBitWidth, WideBits, signed_dyn_bits::SignedDynBits};
+use crate::bitwidth::W;
+use crate::limbs::{Limbs, SignedDecimal};
+
+/// The [SignedWideBits] type is the signed counterpart of [WideBits],
+/// and can be up to [MAX_BITS](crate::MAX_BITS) bits long.  Like [WideBits],
+/// the value is stored in (sign extended) [Limbs], so prefer
+/// [SignedBits](crate::SignedBits) for anything that fits in 128 bits.
+/// Values are moved in and out through their [Limbs] (see
+/// [SignedWideBits::limbs] and [SignedWideBits::from_limbs]), or as an
+/// `i128` when they fit (see [SignedWideBits::try_raw]).
+///
+/// Comparisons are performed using signed arithmetic, and the right
+/// shift operator sign extends the value, just like [SignedBits](crate::SignedBits).
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct SignedWideBits<const N: usize>(pub(crate) Limbs)
+where
+    W<N>: WideBitWidth;
+
+impl<const N: usize> std::fmt::Debug for SignedWideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_tuple("SignedWideBits")
+            .field(&SignedDecimal(self.0))
+            .finish()
+    }
+}
+
+impl<const N: usize> PartialOrd for SignedWideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for SignedWideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.cmp_signed(other.0)
+    }
+}
+
+impl<const N: usize> std::fmt::Display for SignedWideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.is_negative() {
+            write!(f, "-{}'sd{}", { N }, self.0.unsigned_abs())
+        } else {
+            write!(f, "{}'sd{}", { N }, self.0)
+        }
+    }
+}
+
+impl<const N: usize> std::fmt::LowerHex for SignedWideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.is_negative() {
+            write!(f, "-{}'sh{:x}", { N }, self.0.unsigned_abs())
+        } else {
+            write!(f, "{}'sh{:x}", { N }, self.0)
+        }
+    }
+}
+
+impl<const N: usize> std::fmt::UpperHex for SignedWideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.is_negative() {
+            write!(f, "-{}'sH{:X}", { N }, self.0.unsigned_abs())
+        } else {
+            write!(f, "{}'sH{:X}", { N }, self.0)
+        }
+    }
+}
+
+impl<const N: usize> std::fmt::Binary for SignedWideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.is_negative() {
+            write!(f, "-{}'sb{:b}", { N }, self.0.unsigned_abs())
+        } else {
+            write!(f, "{}'sb{:b}", { N }, self.0)
+        }
+    }
+}
+
+/// Helper function for creating a signed wide bits value
+/// from a constant.
+/// ```
+/// # use rhdl_bits::{SignedWideBits, signed_wide};
+/// let value : SignedWideBits<256> = signed_wide(-86);
+/// assert_eq!(value.try_raw(), Some(-86));
+/// ```
+/// Because the function is `const`, you can use it a constant
+/// context:
+/// ```
+/// # use rhdl_bits::{SignedWideBits, signed_wide};
+/// const VALUE : SignedWideBits<256> = signed_wide(-86);
+/// assert_eq!(VALUE.try_raw(), Some(-86));
+/// ```
+pub const fn signed_wide<const N: usize>(val: i128) -> SignedWideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    SignedWideBits::from_limbs(Limbs::from_i128(val))
+}
+
+/// Like `signed_wide()`, but wraps the value to fit in the specified
+/// number of bits.
+pub const fn signed_wide_wrapped<const N: usize>(val: i128) -> SignedWideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    SignedWideBits::from_limbs_wrapped(Limbs::from_i128(val))
+}
+
+/// This struct is needed so that the `signed_wide` function can be used in synthesizable
+/// contexts.
+#[doc(hidden)]
+pub struct signed_wide<const N: usize>
+where
+    W<N>: WideBitWidth, {}
+
+impl<const N: usize> SignedWideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    /// The largest positive value that can be represented
+    /// by this sized [SignedWideBits] value.
+    pub const MAX: Self = Self(Limbs::mask(N - 1));
+    /// The smallest negative value that can be represented
+    /// by this sized [SignedWideBits] value.
+    pub const MIN: Self = Self(Limbs::mask(N - 1).not());
+    /// The zero value for this sized [SignedWideBits] value.
+    pub const ZERO: Self = Self(Limbs::ZERO);
+    /// Test if the value is negative.
+    /// ```
+    /// # use rhdl_bits::{SignedWideBits, signed_wide};
+    /// assert!(signed_wide::<8>(-1).is_negative());
+    /// assert!(!signed_wide::<8>(0).is_negative());
+    /// assert!(!signed_wide::<8>(1).is_negative());
+    /// ```
+    pub const fn is_negative(&self) -> bool {
+        self.0.is_negative()
+    }
+    /// Test if the value is positive or zero.
+    /// ```
+    /// # use rhdl_bits::signed_wide;
+    /// assert!(!signed_wide::<8>(-1).is_non_negative());
+    /// assert!(signed_wide::<8>(0).is_non_negative());
+    /// assert!(signed_wide::<8>(1).is_non_negative());
+    /// ```
+    pub const fn is_non_negative(&self) -> bool {
+        !self.0.is_negative()
+    }
+    /// Reinterpret the [SignedWideBits] value as an unsigned
+    /// [WideBits] value.  This is useful for performing
+    /// bit manipulations on the value that may or may not
+    /// preserve the 2's complement nature of the value.
+    /// ```
+    /// # use rhdl_bits::{WideBits, SignedWideBits, signed_wide};
+    /// let x = signed_wide::<8>(-14); // In binary: 1111_0010
+    /// let y : WideBits<8> = x.as_unsigned();
+    /// assert_eq!(y, 0b1111_0010);
+    /// ```
+    pub const fn as_unsigned(self) -> WideBits<N> {
+        WideBits::from_limbs_masked(self.0)
+    }
+    /// Extract the value as an `i128`, or `None` if it does not fit
+    /// in 128 bits.
+    pub const fn try_raw(self) -> Option<i128> {
+        if self.0.fits_signed(128) {
+            Some(self.0.to_i128())
+        } else {
+            None
+        }
+    }
+    /// Extract the (sign extended) [Limbs] backing this
+    /// [SignedWideBits] value.
+    pub const fn limbs(self) -> Limbs {
+        self.0
+    }
+    /// Build a [SignedWideBits] value from sign extended [Limbs].
+    ///
+    /// # Panics
+    /// Panics if the value does not fit in `N` bits.
+    pub const fn from_limbs(value: Limbs) -> Self {
+        assert!(value.fits_signed(N));
+        Self(value)
+    }
+    /// Build a [SignedWideBits] value from the lower `N` bits
+    /// of the given [Limbs], sign extending from bit `N-1`.
+    pub const fn from_limbs_wrapped(value: Limbs) -> Self {
+        Self(value.sign_extend(N))
+    }
+    /// Convert the compile time sized [SignedWideBits] value
+    /// to a run-time traced [SignedDynBits] value.
+    pub const fn dyn_bits(self) -> SignedDynBits {
+        SignedDynBits {
+            val: self.0,
+            bits: { N },
+        }
+    }
+    /// Build a (dynamic, stack allocated) vector
+    /// containing the bits that make up this value.
+    /// This will be slow.  Not available in synthesizable
+    /// contexts.
+    pub fn to_bools(self) -> Vec<bool> {
+        self.as_unsigned().to_bools()
+    }
+    /// Returns true if any bit is set.
+    /// Can be called in a synthesizable context
+    pub const fn any(self) -> bool {
+        !self.0.is_zero()
+    }
+    /// Returns true if all bits are set.
+    /// Can be called in a synthesizable context
+    pub const fn all(self) -> bool {
+        self.0.const_eq(Limbs::ONES)
+    }
+    /// Returns true if the number of set bits is odd.
+    /// Can be called in a synthesizable context
+    pub const fn xor(self) -> bool {
+        self.as_unsigned().0.count_ones() % 2 == 1
+    }
+    /// Add two values, clamping the result to [SignedWideBits::MAX] or
+    /// [SignedWideBits::MIN] if the sum does not fit.
+    /// Can be called in a synthesizable context
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        match self.overflowing_add(rhs) {
+            (_, true) if self.is_negative() => Self::MIN,
+            (_, true) => Self::MAX,
+            (sum, false) => sum,
+        }
+    }
+    /// Subtract two values, clamping the result to [SignedWideBits::MAX] or
+    /// [SignedWideBits::MIN] if the difference does not fit.
+    /// Can be called in a synthesizable context
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        match self.overflowing_sub(rhs) {
+            (_, true) if self.is_negative() => Self::MIN,
+            (_, true) => Self::MAX,
+            (difference, false) => difference,
+        }
+    }
+    /// Add two values, returning the wrapped sum and a flag that
+    /// is set if the addition overflowed.
+    /// Can be called in a synthesizable context
+    pub fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+        let sum = self + rhs;
+        let overflow =
+            self.is_negative() == rhs.is_negative() && sum.is_negative() != self.is_negative();
+        (sum, overflow)
+    }
+    /// Subtract two values, returning the wrapped difference and a flag
+    /// that is set if the subtraction overflowed.
+    /// Can be called in a synthesizable context
+    pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+        let difference = self - rhs;
+        let overflow = self.is_negative() != rhs.is_negative()
+            && difference.is_negative() != self.is_negative();
+        (difference, overflow)
+    }
+    /// Count the number of bits that are set (including the sign bit).
+    /// The count is returned as an unsigned value of the same width.
+    /// Can be called in a synthesizable context
+    pub fn count_ones(self) -> WideBits<N> {
+        self.as_unsigned().count_ones()
+    }
+    /// Count the number of zero bits above the highest set bit.
+    /// The count is returned as an unsigned value of the same width.
+    /// Can be called in a synthesizable context
+    pub fn leading_zeros(self) -> WideBits<N> {
+        self.as_unsigned().leading_zeros()
+    }
+    /// Count the number of zero bits below the lowest set bit.
+    /// The count is returned as an unsigned value of the same width.
+    /// Can be called in a synthesizable context
+    pub fn trailing_zeros(self) -> WideBits<N> {
+        self.as_unsigned().trailing_zeros()
+    }
+    /// Reverse the order of the bits.
+    /// Can be called in a synthesizable context
+    pub fn reverse_bits(self) -> Self {
+        self.as_unsigned().reverse_bits().as_signed()
+    }
+    /// Resize the [SignedWideBits] value to a different number of bits.
+    /// If the new size is larger than the current size, then sign
+    /// extension is performed.  If the new size is smaller than the
+    /// current size, then the value is truncated to fit in the
+    /// smaller size.
+    pub const fn resize<const M: usize>(self) -> SignedWideBits<M>
+    where
+        W<M>: WideBitWidth,
+    {
+        SignedWideBits::from_limbs_wrapped(self.0)
+    }
+    /// Shift left by a constant amount, returning a [SignedDynBits] value.
+    /// The output size is the input size plus the shift amount.
+    /// Available in synthesizable contexts.
+    pub fn xshl<const M: usize>(self) -> SignedDynBits {
+        self.dyn_bits().xshl::<M>()
+    }
+    /// Shift right by a constant amount, returning a [SignedDynBits] value.
+    /// The output size is the input size minus the shift amount.
+    pub fn xshr<const M: usize>(self) -> SignedDynBits {
+        self.dyn_bits().xshr::<M>()
+    }
+    /// Sign extend the value by the given number of bits, returning a [SignedDynBits] value.
+    /// The output size is the input size plus the extension amount.
+    /// Available in synthesizable contexts.
+    pub fn xext<const M: usize>(self) -> SignedDynBits {
+        self.dyn_bits().xext::<M>()
+    }
+}
+
+impl<const N: usize> Default for SignedWideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<const N: usize> PartialEq<i128> for SignedWideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    fn eq(&self, other: &i128) -> bool {
+        *self == signed_wide::<N>(*other)
+    }
+}
+
+impl<const N: usize> PartialEq<SignedWideBits<N>> for i128
+where
+    W<N>: WideBitWidth,
+{
+    fn eq(&self, other: &SignedWideBits<N>) -> bool {
+        signed_wide::<N>(*self) == *other
+    }
+}
+
+impl<const N: usize> PartialOrd<i128> for SignedWideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    fn partial_cmp(&self, other: &i128) -> Option<std::cmp::Ordering> {
+        self.partial_cmp(&signed_wide::<N>(*other))
+    }
+}
+
+impl<const N: usize> PartialOrd<SignedWideBits<N>> for i128
+where
+    W<N>: WideBitWidth,
+{
+    fn partial_cmp(&self, other: &SignedWideBits<N>) -> Option<std::cmp::Ordering> {
+        signed_wide::<N>(*self).partial_cmp(other)
+    }
+}
+
+// Provide conversion from a `i128` to a [SignedWideBits] value.
+// This will panic if you try to convert a value that
+// is larger than the [SignedWideBits] value can hold.
+impl<const N: usize> From<i128> for SignedWideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    fn from(value: i128) -> Self {
+        signed_wide(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_wide_signed_arithmetic() {
+        let one = SignedWideBits::<256>::from(1);
+        let minus_one = SignedWideBits::<256>::from(-1);
+        assert!(minus_one.all());
+        assert_eq!(minus_one + one, SignedWideBits::<256>::ZERO);
+        assert_eq!(-one, minus_one);
+        let big = one << 200;
+        assert!(big > one);
+        assert!(-big < minus_one);
+        assert_eq!((-big) >> 200, minus_one);
+        assert_eq!((-big) / big, minus_one);
+        assert_eq!(SignedWideBits::<256>::MAX + one, SignedWideBits::<256>::MIN);
+        assert_eq!((-big).resize::<300>().resize::<256>(), -big);
+        assert_eq!((-big).try_raw(), None);
+        assert_eq!(minus_one.try_raw(), Some(-1));
+    }
+
+    #[test]
+    fn test_wide_signed_saturation() {
+        let max = SignedWideBits::<256>::MAX;
+        let min = SignedWideBits::<256>::MIN;
+        let one = SignedWideBits::<256>::from(1);
+        assert_eq!(max.saturating_add(one), max);
+        assert_eq!(min.saturating_sub(one), min);
+        assert_eq!(max.overflowing_add(one), (min, true));
+    }
+
+    #[test]
+    fn test_wide_signed_format() {
+        let x = -(SignedWideBits::<256>::from(1) << 128);
+        assert_eq!(format!("{x:x}"), "-256'sh100000000000000000000000000000000");
+        assert_eq!(
+            format!("{x:?}"),
+            "SignedWideBits(-340282366920938463463374607431768211456)"
+        );
+    }
+}
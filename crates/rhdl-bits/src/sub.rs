@@ -64,16 +64,29 @@ use std::ops::SubAssign;
 
 use super::BitWidth;
 use super::bits_impl::Bits;
-use super::bits_impl::bits_masked;
 use super::dyn_bits::DynBits;
+use super::limbs::Limbs;
 use super::signed_bits_impl::SignedBits;
-use super::signed_bits_impl::signed_wrapped;
 use super::signed_dyn_bits::SignedDynBits;
 
-impl_binop!(Sub, sub, u128::wrapping_sub);
-impl_assign_op!(SubAssign, sub_assign, u128::wrapping_sub);
-impl_signed_binop!(Sub, sub, i128::wrapping_sub);
-impl_assigned_signed_op!(SubAssign, sub_assign, i128::wrapping_sub);
+impl_binop!(Sub, sub, u128::wrapping_sub, Limbs::wrapping_sub);
+impl_assign_op!(
+    SubAssign,
+    sub_assign,
+    u128::wrapping_sub,
+    Limbs::wrapping_sub
+);
+impl_signed_binop!(Sub, sub, i128::wrapping_sub, Limbs::wrapping_sub);
+impl_assigned_signed_op!(
+    SubAssign,
+    sub_assign,
+    i128::wrapping_sub,
+    Limbs::wrapping_sub
+);
+impl_wide_binop!(Sub, sub, Limbs::wrapping_sub);
+impl_wide_assign_op!(SubAssign, sub_assign, Limbs::wrapping_sub);
+impl_wide_signed_binop!(Sub, sub, Limbs::wrapping_sub);
+impl_wide_assigned_signed_op!(SubAssign, sub_assign, Limbs::wrapping_sub);
 
 #[cfg(test)]
 mod test {
@@ -0,0 +1,469 @@
+#![allow(non_camel_case_types)]
+use super::{WideBitWidth, dyn_bits::DynBits, signed_wide_bits_impl::SignedWideBits};
+use crate::bitwidth::W;
+use crate::limbs::Limbs;
+/// The [WideBits] type is a fixed-sized bit vector, like [Bits](crate::Bits),
+/// that can be up to [MAX_BITS](crate::MAX_BITS) bits long.  The value is
+/// stored in [Limbs], so a [WideBits] value is always as large as [Limbs]
+/// regardless of `N`, and arithmetic on it is slower than on [Bits](crate::Bits).
+/// Prefer [Bits](crate::Bits) for anything that fits in 128 bits, and use
+/// [WideBits] for wide datapaths (e.g., 256 or 512 bit buses).
+///
+/// Values are moved in and out through their [Limbs] (see [WideBits::limbs]
+/// and [WideBits::from_limbs]), or as a `u128` when they fit (see
+/// [WideBits::try_raw]).  All of the operators work for any width.
+///
+/// Like [Bits](crate::Bits), [WideBits] implements 2's complement arithmetic,
+/// and is treated as an unsigned value for the purposes of comparisons.  If you
+/// need signed comparisons, you will need the [SignedWideBits] type.
+///
+#[derive(Clone, Copy, PartialEq, PartialOrd, Ord, Eq)]
+pub struct WideBits<const N: usize>(pub(crate) Limbs)
+where
+    W<N>: WideBitWidth;
+
+impl<const N: usize> std::fmt::Debug for WideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}_b{}", self.0, N)
+    }
+}
+
+impl<const N: usize> std::fmt::Display for WideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}'d{}", N, self.0)
+    }
+}
+
+impl<const N: usize> std::fmt::LowerHex for WideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}'h{:x}", N, self.0)
+    }
+}
+
+impl<const N: usize> std::fmt::UpperHex for WideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}'H{:X}", N, self.0)
+    }
+}
+
+impl<const N: usize> std::fmt::Binary for WideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}'b{:b}", N, self.0)
+    }
+}
+
+/// Helper function for creating a wide bits value from
+/// a constant.
+/// ```
+/// # use rhdl_bits::{WideBits, wide_bits};
+/// let value : WideBits<8> = wide_bits(0b1010_1010);
+/// assert_eq!(value, 0b1010_1010);
+/// ```
+/// Because the function is `const`, you can use it a constant
+/// context:
+/// ```
+/// # use rhdl_bits::{WideBits, wide_bits};
+/// const VALUE : WideBits<8> = wide_bits(0b1010_1010);
+/// ```
+pub const fn wide_bits<const N: usize>(value: u128) -> WideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    WideBits::from_limbs(Limbs::from_u128(value))
+}
+
+/// Helper function for creating a wide bits value from
+/// a constant, masking off any excess bits.
+/// ```
+/// # use rhdl_bits::{WideBits, wide_bits_masked};
+/// let value : WideBits<8> = wide_bits_masked(0b1_1010_1010);
+/// assert_eq!(value, 0b1010_1010);
+/// ```
+/// Because the function is `const`, you can use it a constant context:
+/// ```
+/// # use rhdl_bits::{WideBits, wide_bits_masked};
+/// const VALUE : WideBits<8> = wide_bits_masked(0b1_1010_1010);
+/// assert_eq!(VALUE, 0b1010_1010);
+/// ```
+pub const fn wide_bits_masked<const N: usize>(value: u128) -> WideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    WideBits::from_limbs_masked(Limbs::from_u128(value))
+}
+
+/// This struct is needed so that the `wide_bits` function can be used in synthesizable
+/// contexts.
+#[doc(hidden)]
+pub struct wide_bits<const N: usize>
+where
+    W<N>: WideBitWidth, {}
+
+impl<const N: usize> WideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    /// Defines a constant WideBits value with all bits set to 1.
+    pub const MASK: Self = Self::mask();
+    /// Defines a constant WideBits value set to the maximum storable value.
+    pub const MAX: Self = Self::mask();
+    /// Defines a constant WideBits value set to zero.
+    pub const ZERO: Self = Self(Limbs::ZERO);
+    /// The number of bits in this [WideBits] value.
+    pub const fn len(&self) -> usize {
+        N
+    }
+    /// Return true if the [WideBits] value has zero bits.
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+    /// Return a [WideBits] value with all bits set to 1.
+    /// ```
+    /// # use rhdl_bits::{WideBits};
+    /// let bits = WideBits::<8>::mask();
+    /// assert_eq!(bits, 0xFF);
+    /// ```
+    pub const fn mask() -> Self {
+        Self(Limbs::mask(N))
+    }
+    /// Resize the [WideBits] value to a different size.
+    /// If the new size is smaller, the value is truncated.
+    /// If the new size is larger, the value is zero-extended.
+    /// ```
+    /// # use rhdl_bits::{WideBits, wide_bits};
+    /// let bits: WideBits<8> = wide_bits(0b1101_1010);
+    /// let new_bits: WideBits<4> = bits.resize();
+    /// assert_eq!(new_bits, 0b1010);
+    /// let new_bits: WideBits<16> = bits.resize();
+    /// assert_eq!(new_bits, 0b0000_0000_1101_1010);
+    /// ```
+    pub const fn resize<const M: usize>(self) -> WideBits<M>
+    where
+        W<M>: WideBitWidth,
+    {
+        WideBits::from_limbs_masked(self.0)
+    }
+    /// Reinterpret the [WideBits] value as a [SignedWideBits] value.
+    pub const fn as_signed(self) -> SignedWideBits<N> {
+        SignedWideBits::from_limbs_wrapped(self.0)
+    }
+    /// Extract the value as a `u128`, or `None` if it does not fit
+    /// in 128 bits.
+    /// ```
+    /// # use rhdl_bits::WideBits;
+    /// assert_eq!(WideBits::<256>::from(42).try_raw(), Some(42));
+    /// assert_eq!((WideBits::<256>::from(1) << 200).try_raw(), None);
+    /// ```
+    pub const fn try_raw(self) -> Option<u128> {
+        if self.0.fits_unsigned(128) {
+            Some(self.0.to_u128())
+        } else {
+            None
+        }
+    }
+    /// Extract the [Limbs] backing the [WideBits] value.
+    pub const fn limbs(self) -> Limbs {
+        self.0
+    }
+    /// Build a [WideBits] value from [Limbs].
+    ///
+    /// # Panics
+    /// Panics if the value does not fit in `N` bits.
+    pub const fn from_limbs(value: Limbs) -> Self {
+        assert!(value.fits_unsigned(N));
+        Self(value)
+    }
+    /// Build a [WideBits] value from [Limbs], discarding any bits
+    /// that do not fit in `N` bits.
+    pub const fn from_limbs_masked(value: Limbs) -> Self {
+        Self(value.bitand(Limbs::mask(N)))
+    }
+    /// Convert the compile-time sized [WideBits] to a run-time
+    /// tracked [DynBits] value.
+    pub const fn dyn_bits(self) -> DynBits {
+        DynBits {
+            val: self.0,
+            bits: N,
+        }
+    }
+    /// Build a (dynamic, stack allocated) vector containing
+    /// the bits that make up this value.  This will be slow.
+    /// Not available in synthesizable functions.
+    pub fn to_bools(self) -> Vec<bool> {
+        (0..N).map(|ndx| self.0.bit(ndx)).collect()
+    }
+    /// Return true if any bit is set.
+    /// Available in synthesizable functions.
+    pub fn any(self) -> bool {
+        !self.0.is_zero()
+    }
+    /// Return true if all bits are set.
+    /// Available in synthesizable functions.
+    pub fn all(self) -> bool {
+        self.0 == Self::mask().0
+    }
+    /// Return true if an odd number of bits are set.
+    /// Available in synthesizable functions.
+    pub fn xor(self) -> bool {
+        self.0.count_ones() % 2 == 1
+    }
+    /// Add two values, clamping the result to [WideBits::MAX] if the
+    /// sum does not fit.
+    /// Available in synthesizable functions.
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        match self.overflowing_add(rhs) {
+            (_, true) => Self::MAX,
+            (sum, false) => sum,
+        }
+    }
+    /// Subtract two values, clamping the result to zero if the
+    /// difference would be negative.
+    /// Available in synthesizable functions.
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        match self.overflowing_sub(rhs) {
+            (_, true) => Self::ZERO,
+            (difference, false) => difference,
+        }
+    }
+    /// Add two values, returning the wrapped sum and a flag that is
+    /// set if the addition overflowed (i.e., the carry out).
+    /// Available in synthesizable functions.
+    pub fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+        let sum = self + rhs;
+        (sum, sum < self)
+    }
+    /// Subtract two values, returning the wrapped difference and a flag
+    /// that is set if the subtraction overflowed (i.e., the borrow out).
+    /// Available in synthesizable functions.
+    pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+        (self - rhs, self < rhs)
+    }
+    /// Count the number of bits that are set.  The count is returned
+    /// as a value of the same width (which is always wide enough to hold it).
+    /// Available in synthesizable functions.
+    pub fn count_ones(self) -> Self {
+        Self::from_limbs(Limbs::from_u128(self.0.count_ones() as u128))
+    }
+    /// Count the number of zero bits above the highest set bit.  The count
+    /// is returned as a value of the same width.
+    /// Available in synthesizable functions.
+    pub fn leading_zeros(self) -> Self {
+        let count = (0..N).rev().take_while(|&ndx| !self.0.bit(ndx)).count();
+        Self::from_limbs(Limbs::from_u128(count as u128))
+    }
+    /// Count the number of zero bits below the lowest set bit.  The count
+    /// is returned as a value of the same width.
+    /// Available in synthesizable functions.
+    pub fn trailing_zeros(self) -> Self {
+        let count = (0..N).take_while(|&ndx| !self.0.bit(ndx)).count();
+        Self::from_limbs(Limbs::from_u128(count as u128))
+    }
+    /// Reverse the order of the bits.
+    /// Available in synthesizable functions.
+    pub fn reverse_bits(self) -> Self {
+        Self((0..N).fold(Limbs::ZERO, |acc, ndx| {
+            acc.with_bit(N - 1 - ndx, self.0.bit(ndx))
+        }))
+    }
+    /// Shift left by a constant amount, returning a [DynBits] value.
+    /// The output size is increased by the shift amount.
+    /// Available in synthesizable functions.
+    pub fn xshl<const M: usize>(self) -> DynBits {
+        self.dyn_bits().xshl::<M>()
+    }
+    /// Shift right by a constant amount, returning a [DynBits] value.
+    /// The output size is decreased by the shift amount.
+    /// Available in synthesizable functions.
+    pub fn xshr<const M: usize>(self) -> DynBits {
+        self.dyn_bits().xshr::<M>()
+    }
+    /// Pad the [WideBits] value to a larger size, returning a [DynBits] value.
+    /// The output size is increased by M bits.
+    /// Available in synthesizable functions.
+    pub fn xext<const M: usize>(self) -> DynBits {
+        self.dyn_bits().xext::<M>()
+    }
+}
+
+/// The default value for a [WideBits] value is 0.
+impl<const N: usize> Default for WideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+/// Provide conversion from a `u128` to a [WideBits] value.
+/// This will panic if you try to convert a value that
+/// is larger than the [WideBits] value can hold.
+impl<const N: usize> From<u128> for WideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    fn from(value: u128) -> Self {
+        wide_bits(value)
+    }
+}
+
+impl<const N: usize> PartialEq<WideBits<N>> for u128
+where
+    W<N>: WideBitWidth,
+{
+    fn eq(&self, other: &WideBits<N>) -> bool {
+        wide_bits::<N>(*self) == *other
+    }
+}
+
+impl<const N: usize> PartialEq<u128> for WideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    fn eq(&self, other: &u128) -> bool {
+        *self == wide_bits::<N>(*other)
+    }
+}
+
+impl<const N: usize> PartialOrd<WideBits<N>> for u128
+where
+    W<N>: WideBitWidth,
+{
+    fn partial_cmp(&self, other: &WideBits<N>) -> Option<std::cmp::Ordering> {
+        wide_bits::<N>(*self).partial_cmp(other)
+    }
+}
+
+impl<const N: usize> PartialOrd<u128> for WideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    fn partial_cmp(&self, other: &u128) -> Option<std::cmp::Ordering> {
+        self.partial_cmp(&wide_bits::<N>(*other))
+    }
+}
+
+impl<const N: usize> WideBits<N>
+where
+    W<N>: WideBitWidth,
+{
+    // Indices too large for a usize map to usize::MAX, so that
+    // indexing fails with the usual out of bounds panic.
+    fn as_index(self) -> usize {
+        if self.0.fits_unsigned(usize::BITS as usize) {
+            self.0.to_u128() as usize
+        } else {
+            usize::MAX
+        }
+    }
+}
+
+impl<T, const N: usize, const M: usize> std::ops::Index<WideBits<N>> for [T; M]
+where
+    W<N>: WideBitWidth,
+{
+    type Output = T;
+    fn index(&self, index: WideBits<N>) -> &Self::Output {
+        &self[index.as_index()]
+    }
+}
+
+impl<T, const N: usize, const M: usize> std::ops::IndexMut<WideBits<N>> for [T; M]
+where
+    W<N>: WideBitWidth,
+{
+    fn index_mut(&mut self, index: WideBits<N>) -> &mut Self::Output {
+        &mut self[index.as_index()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::XAdd;
+
+    #[test]
+    fn test_mask() {
+        let bits = WideBits::<128>::mask();
+        assert_eq!(bits.try_raw(), Some(u128::MAX));
+        let bits = WideBits::<200>::mask();
+        assert_eq!(bits.try_raw(), None);
+        assert_eq!(bits.limbs().count_ones(), 200);
+    }
+
+    #[test]
+    fn test_size_is_independent_of_width() {
+        assert_eq!(
+            std::mem::size_of::<WideBits<8>>(),
+            std::mem::size_of::<WideBits<512>>()
+        );
+    }
+
+    #[test]
+    fn test_wide_arithmetic() {
+        let one = WideBits::<256>::from(1);
+        let max = WideBits::<256>::MAX;
+        assert!(max.all());
+        assert_eq!(max + one, WideBits::<256>::ZERO);
+        assert_eq!(WideBits::<256>::ZERO - one, max);
+        let big = one << 200;
+        assert!(big.limbs().bit(200));
+        assert_eq!(big >> 200, one);
+        assert_eq!((big * WideBits::<256>::from(3)) / big, 3);
+        assert_eq!((big + 5) % big, 5);
+        assert!(big > WideBits::<256>::from(u128::MAX));
+        let x: WideBits<300> = big.resize();
+        assert_eq!(x.resize::<256>(), big);
+        assert_eq!(x.resize::<8>(), 0);
+    }
+
+    #[test]
+    fn test_wide_intrinsics() {
+        let big = WideBits::<256>::from(1) << 200;
+        assert_eq!(big.leading_zeros(), 55);
+        assert_eq!(big.trailing_zeros(), 200);
+        assert_eq!(big.count_ones(), 1);
+        assert_eq!(big.reverse_bits(), WideBits::<256>::from(1) << 55);
+        assert_eq!(WideBits::<256>::MAX.saturating_add(big), WideBits::MAX);
+        assert_eq!(big.saturating_sub(big + 1), WideBits::ZERO);
+    }
+
+    #[test]
+    fn test_wide_format() {
+        let x = WideBits::<256>::from(1) << 128;
+        assert_eq!(format!("{x:x}"), "256'h100000000000000000000000000000000");
+        assert_eq!(
+            format!("{x}"),
+            "256'd340282366920938463463374607431768211456"
+        );
+        assert_eq!(
+            format!("{x:?}"),
+            "340282366920938463463374607431768211456_b256"
+        );
+    }
+
+    #[test]
+    fn test_wide_dyn_bits() {
+        let x = WideBits::<256>::from(1) << 255;
+        let y = x.dyn_bits().xadd(x.dyn_bits());
+        assert_eq!(y.bits(), 257);
+        assert!(y.limbs().bit(256));
+        assert_eq!(x.dyn_bits().as_wide_bits::<256>(), x);
+    }
+}
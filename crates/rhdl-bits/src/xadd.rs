@@ -66,8 +66,12 @@
 //!
 //! Note that you cannot mix signed and unsigned types, any more than you can add `i8` and `u8` in normal Rust.
 
-use super::{BitWidth, Bits, SignedBits, dyn_bits::DynBits, signed_dyn_bits::SignedDynBits};
+use super::{
+    BitWidth, Bits, SignedBits, SignedWideBits, WideBitWidth, WideBits, dyn_bits::DynBits,
+    signed_dyn_bits::SignedDynBits,
+};
 use crate::bitwidth::W;
+use crate::limbs::MAX_BITS;
 
 /// Extended addition trait.  Represents a bit-preserving addition
 /// of two values (either signed or unsigned) where the output
@@ -87,7 +91,7 @@ where
 {
     type Output = DynBits;
     fn xadd(self, rhs: Bits<M>) -> Self::Output {
-        assert!(N.max(M) < 128);
+        assert!(N.max(M) < MAX_BITS);
         self.dyn_bits().xadd(rhs)
     }
 }
@@ -98,9 +102,9 @@ where
 {
     type Output = DynBits;
     fn xadd(self, rhs: DynBits) -> Self::Output {
-        assert!(rhs.bits.max(N) < 128);
+        assert!(rhs.bits.max(N) < MAX_BITS);
         DynBits {
-            val: self.raw().wrapping_add(rhs.val),
+            val: self.limbs().wrapping_add(rhs.val),
             bits: N.max(rhs.bits) + 1,
         }
         .wrapped()
@@ -113,9 +117,9 @@ where
 {
     type Output = DynBits;
     fn xadd(self, rhs: Bits<N>) -> Self::Output {
-        assert!(self.bits.max(N) < 128);
+        assert!(self.bits.max(N) < MAX_BITS);
         DynBits {
-            val: self.val.wrapping_add(rhs.raw()),
+            val: self.val.wrapping_add(rhs.limbs()),
             bits: self.bits.max(N) + 1,
         }
         .wrapped()
@@ -125,7 +129,7 @@ where
 impl XAdd<DynBits> for DynBits {
     type Output = DynBits;
     fn xadd(self, rhs: DynBits) -> Self::Output {
-        assert!(self.bits.max(rhs.bits) < 128);
+        assert!(self.bits.max(rhs.bits) < MAX_BITS);
         DynBits {
             val: self.val.wrapping_add(rhs.val),
             bits: self.bits.max(rhs.bits) + 1,
@@ -151,9 +155,9 @@ where
 {
     type Output = SignedDynBits;
     fn xadd(self, rhs: SignedDynBits) -> Self::Output {
-        assert!(rhs.bits.max(N) < 128);
+        assert!(rhs.bits.max(N) < MAX_BITS);
         SignedDynBits {
-            val: self.raw().wrapping_add(rhs.val),
+            val: self.limbs().wrapping_add(rhs.val),
             bits: N.max(rhs.bits) + 1,
         }
         .wrapped()
@@ -166,9 +170,9 @@ where
 {
     type Output = SignedDynBits;
     fn xadd(self, rhs: SignedBits<N>) -> Self::Output {
-        assert!(self.bits.max(N) < 128);
+        assert!(self.bits.max(N) < MAX_BITS);
         SignedDynBits {
-            val: self.val.wrapping_add(rhs.raw()),
+            val: self.val.wrapping_add(rhs.limbs()),
             bits: self.bits.max(N) + 1,
         }
         .wrapped()
@@ -178,7 +182,7 @@ where
 impl XAdd<SignedDynBits> for SignedDynBits {
     type Output = SignedDynBits;
     fn xadd(self, rhs: SignedDynBits) -> Self::Output {
-        assert!(self.bits.max(rhs.bits) < 128);
+        assert!(self.bits.max(rhs.bits) < MAX_BITS);
         SignedDynBits {
             val: self.val.wrapping_add(rhs.val),
             bits: self.bits.max(rhs.bits) + 1,
@@ -187,6 +191,28 @@ impl XAdd<SignedDynBits> for SignedDynBits {
     }
 }
 
+impl<const N: usize, const M: usize> XAdd<WideBits<M>> for WideBits<N>
+where
+    W<N>: WideBitWidth,
+    W<M>: WideBitWidth,
+{
+    type Output = DynBits;
+    fn xadd(self, rhs: WideBits<M>) -> Self::Output {
+        self.dyn_bits().xadd(rhs.dyn_bits())
+    }
+}
+
+impl<const N: usize, const M: usize> XAdd<SignedWideBits<M>> for SignedWideBits<N>
+where
+    W<N>: WideBitWidth,
+    W<M>: WideBitWidth,
+{
+    type Output = SignedDynBits;
+    fn xadd(self, rhs: SignedWideBits<M>) -> Self::Output {
+        self.dyn_bits().xadd(rhs.dyn_bits())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
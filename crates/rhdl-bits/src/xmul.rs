@@ -62,16 +62,21 @@
 //! assert_eq!(c, s12(-150).dyn_bits()); // c is a SignedDynBits with 12 bits
 //! ```
 //!
-//! Note that the maximum supported bit width for [Bits], [SignedBits], [DynBits] and [SignedDynBits] is 128 bits.
-//! Therefore, the sum of the sizes of the two inputs to the `xmul` method must not exceed 128 bits.
+//! Note that the maximum supported bit width for [Bits], [SignedBits], [DynBits] and [SignedDynBits] is
+//! [MAX_BITS](crate::MAX_BITS) bits. Therefore, the sum of the sizes of the two inputs to the `xmul`
+//! method must not exceed [MAX_BITS](crate::MAX_BITS) bits.
 //! Attempting to multiply two values whose combined bit width exceeds this limit will result in a panic at runtime.
 //!
 //! Also, note that while multipliers are generally synthesizable, exactly how they are implemented can vary
 //! greatly between synthesis tools and target technologies. Therefore, it's advisable to consult the documentation
 //! for your specific toolchain and target architecture to understand the implications of using extended multiplication.
 //!
-use super::{BitWidth, Bits, SignedBits, dyn_bits::DynBits, signed_dyn_bits::SignedDynBits};
+use super::{
+    BitWidth, Bits, SignedBits, SignedWideBits, WideBitWidth, WideBits, dyn_bits::DynBits,
+    signed_dyn_bits::SignedDynBits,
+};
 use crate::bitwidth::W;
+use crate::limbs::MAX_BITS;
 
 /// Extended multiplication trait.  Represents a bit-preserving multiplication
 /// of two values (either signed or unsigned) where the output
@@ -101,9 +106,9 @@ where
 {
     type Output = DynBits;
     fn xmul(self, rhs: Bits<N>) -> Self::Output {
-        assert!(self.bits + N <= 128);
+        assert!(self.bits + N <= MAX_BITS);
         DynBits {
-            val: self.val.wrapping_mul(rhs.raw()),
+            val: self.val.wrapping_mul(rhs.limbs()),
             bits: self.bits + N,
         }
         .wrapped()
@@ -116,9 +121,9 @@ where
 {
     type Output = DynBits;
     fn xmul(self, rhs: DynBits) -> Self::Output {
-        assert!(N + rhs.bits <= 128);
+        assert!(N + rhs.bits <= MAX_BITS);
         DynBits {
-            val: self.raw().wrapping_mul(rhs.val),
+            val: self.limbs().wrapping_mul(rhs.val),
             bits: N + rhs.bits,
         }
         .wrapped()
@@ -128,7 +133,7 @@ where
 impl XMul<DynBits> for DynBits {
     type Output = DynBits;
     fn xmul(self, rhs: DynBits) -> Self::Output {
-        assert!(self.bits + rhs.bits <= 128);
+        assert!(self.bits + rhs.bits <= MAX_BITS);
         DynBits {
             val: self.val.wrapping_mul(rhs.val),
             bits: self.bits + rhs.bits,
@@ -154,9 +159,9 @@ where
 {
     type Output = SignedDynBits;
     fn xmul(self, rhs: SignedBits<N>) -> Self::Output {
-        assert!(self.bits + N <= 128);
+        assert!(self.bits + N <= MAX_BITS);
         SignedDynBits {
-            val: self.val.wrapping_mul(rhs.raw()),
+            val: self.val.wrapping_mul(rhs.limbs()),
             bits: self.bits + N,
         }
         .wrapped()
@@ -169,9 +174,9 @@ where
 {
     type Output = SignedDynBits;
     fn xmul(self, rhs: SignedDynBits) -> Self::Output {
-        assert!(N + rhs.bits <= 128);
+        assert!(N + rhs.bits <= MAX_BITS);
         SignedDynBits {
-            val: self.raw().wrapping_mul(rhs.val),
+            val: self.limbs().wrapping_mul(rhs.val),
             bits: N + rhs.bits,
         }
         .wrapped()
@@ -181,7 +186,7 @@ where
 impl XMul<SignedDynBits> for SignedDynBits {
     type Output = SignedDynBits;
     fn xmul(self, rhs: SignedDynBits) -> Self::Output {
-        assert!(self.bits + rhs.bits <= 128);
+        assert!(self.bits + rhs.bits <= MAX_BITS);
         SignedDynBits {
             val: self.val.wrapping_mul(rhs.val),
             bits: self.bits + rhs.bits,
@@ -190,6 +195,28 @@ impl XMul<SignedDynBits> for SignedDynBits {
     }
 }
 
+impl<const N: usize, const M: usize> XMul<WideBits<M>> for WideBits<N>
+where
+    W<N>: WideBitWidth,
+    W<M>: WideBitWidth,
+{
+    type Output = DynBits;
+    fn xmul(self, rhs: WideBits<M>) -> Self::Output {
+        self.dyn_bits().xmul(rhs.dyn_bits())
+    }
+}
+
+impl<const N: usize, const M: usize> XMul<SignedWideBits<M>> for SignedWideBits<N>
+where
+    W<N>: WideBitWidth,
+    W<M>: WideBitWidth,
+{
+    type Output = SignedDynBits;
+    fn xmul(self, rhs: SignedWideBits<M>) -> Self::Output {
+        self.dyn_bits().xmul(rhs.dyn_bits())
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
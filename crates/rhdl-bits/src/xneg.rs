@@ -43,7 +43,7 @@
 //! ```
 //!
 use super::{BitWidth, Bits, SignedBits, dyn_bits::DynBits};
-use crate::{W, signed_dyn_bits::SignedDynBits};
+use crate::{W, limbs::MAX_BITS, signed_dyn_bits::SignedDynBits};
 
 /// Extended negation trait.  Represents a bit-preserving negation
 /// of a signed value where the output size is one bit larger
@@ -62,8 +62,8 @@ where
 {
     type Output = SignedDynBits;
     fn xneg(self) -> Self::Output {
-        assert!(N < 128);
-        let val = (self.raw() as i128).wrapping_neg();
+        assert!(N < MAX_BITS);
+        let val = self.limbs().wrapping_neg();
         SignedDynBits { val, bits: N + 1 }
     }
 }
@@ -71,8 +71,8 @@ where
 impl XNeg for DynBits {
     type Output = SignedDynBits;
     fn xneg(self) -> Self::Output {
-        assert!(self.bits < 128);
-        let val = (self.val as i128).wrapping_neg();
+        assert!(self.bits < MAX_BITS);
+        let val = self.val.wrapping_neg();
         SignedDynBits {
             val,
             bits: self.bits + 1,
@@ -93,7 +93,7 @@ where
 impl XNeg for SignedDynBits {
     type Output = SignedDynBits;
     fn xneg(self) -> Self::Output {
-        assert!(self.bits < 128);
+        assert!(self.bits < MAX_BITS);
         SignedDynBits {
             val: self.val.wrapping_neg(),
             bits: self.bits + 1,
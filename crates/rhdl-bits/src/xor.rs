@@ -38,11 +38,13 @@ use std::ops::BitXorAssign;
 
 use super::BitWidth;
 use super::bits_impl::Bits;
-use super::bits_impl::bits_masked;
 use super::dyn_bits::DynBits;
+use super::limbs::Limbs;
 
-impl_binop!(BitXor, bitxor, u128::bitxor);
-impl_assign_op!(BitXorAssign, bitxor_assign, u128::bitxor);
+impl_binop!(BitXor, bitxor, u128::bitxor, Limbs::bitxor);
+impl_assign_op!(BitXorAssign, bitxor_assign, u128::bitxor, Limbs::bitxor);
+impl_wide_binop!(BitXor, bitxor, Limbs::bitxor);
+impl_wide_assign_op!(BitXorAssign, bitxor_assign, Limbs::bitxor);
 
 #[cfg(test)]
 mod test {
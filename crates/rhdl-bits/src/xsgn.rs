@@ -28,7 +28,7 @@
 //! ```
 //!
 use super::{BitWidth, Bits, dyn_bits::DynBits};
-use crate::{W, signed_dyn_bits::SignedDynBits};
+use crate::{W, limbs::MAX_BITS, signed_dyn_bits::SignedDynBits};
 
 /// Promote an unsigned value to a signed value with all bits
 /// preserved.  The output size is one bit larger than the input size.
@@ -47,7 +47,7 @@ where
     type Output = SignedDynBits;
 
     fn xsgn(self) -> Self::Output {
-        assert!(N < 128);
+        assert!(N < MAX_BITS);
         self.dyn_bits().xsgn()
     }
 }
@@ -56,9 +56,9 @@ impl XSgn for DynBits {
     type Output = SignedDynBits;
 
     fn xsgn(self) -> Self::Output {
-        assert!(self.bits < 128);
+        assert!(self.bits < MAX_BITS);
         SignedDynBits {
-            val: self.val as i128,
+            val: self.val,
             bits: self.bits + 1,
         }
         .wrapped()
@@ -42,8 +42,12 @@
 //! assert_eq!(c, s9(50).dyn_bits()); // c is a SignedDynBits with 9 bits
 //! ```
 //!
-use super::{BitWidth, Bits, SignedBits, dyn_bits::DynBits, signed_dyn_bits::SignedDynBits};
+use super::{
+    BitWidth, Bits, SignedBits, SignedWideBits, WideBitWidth, WideBits, dyn_bits::DynBits,
+    signed_dyn_bits::SignedDynBits,
+};
 use crate::bitwidth::W;
+use crate::limbs::MAX_BITS;
 
 /// Extended subtraction trait.  Represents a bit-preserving subtraction
 /// of two values (either signed or unsigned) where the output
@@ -89,9 +93,9 @@ where
 {
     type Output = SignedDynBits;
     fn xsub(self, rhs: Bits<N>) -> Self::Output {
-        assert!(self.bits.max(N) < 128);
-        let a = self.val as i128;
-        let b = rhs.raw() as i128;
+        assert!(self.bits.max(N) < MAX_BITS);
+        let a = self.val;
+        let b = rhs.limbs();
         SignedDynBits {
             val: a.wrapping_sub(b),
             bits: self.bits.max(N) + 1,
@@ -106,9 +110,9 @@ where
 {
     type Output = SignedDynBits;
     fn xsub(self, rhs: DynBits) -> Self::Output {
-        assert!(N.max(rhs.bits) < 128);
-        let a = self.raw() as i128;
-        let b = rhs.val as i128;
+        assert!(N.max(rhs.bits) < MAX_BITS);
+        let a = self.limbs();
+        let b = rhs.val;
         SignedDynBits {
             val: a.wrapping_sub(b),
             bits: N.max(rhs.bits) + 1,
@@ -120,9 +124,9 @@ where
 impl XSub<DynBits> for DynBits {
     type Output = SignedDynBits;
     fn xsub(self, rhs: DynBits) -> Self::Output {
-        assert!(self.bits.max(rhs.bits) < 128);
+        assert!(self.bits.max(rhs.bits) < MAX_BITS);
         SignedDynBits {
-            val: (self.val as i128).wrapping_sub(rhs.val as i128),
+            val: self.val.wrapping_sub(rhs.val),
             bits: self.bits.max(rhs.bits) + 1,
         }
         .wrapped()
@@ -168,9 +172,9 @@ where
 {
     type Output = SignedDynBits;
     fn xsub(self, rhs: SignedDynBits) -> Self::Output {
-        assert!(N.max(rhs.bits) < 128);
+        assert!(N.max(rhs.bits) < MAX_BITS);
         SignedDynBits {
-            val: self.raw().wrapping_sub(rhs.val),
+            val: self.limbs().wrapping_sub(rhs.val),
             bits: N.max(rhs.bits) + 1,
         }
         .wrapped()
@@ -183,9 +187,9 @@ where
 {
     type Output = SignedDynBits;
     fn xsub(self, rhs: SignedBits<N>) -> Self::Output {
-        assert!(self.bits.max(N) < 128);
+        assert!(self.bits.max(N) < MAX_BITS);
         SignedDynBits {
-            val: self.val.wrapping_sub(rhs.raw()),
+            val: self.val.wrapping_sub(rhs.limbs()),
             bits: self.bits.max(N) + 1,
         }
         .wrapped()
@@ -195,7 +199,7 @@ where
 impl XSub<SignedDynBits> for SignedDynBits {
     type Output = SignedDynBits;
     fn xsub(self, rhs: SignedDynBits) -> Self::Output {
-        assert!(self.bits.max(rhs.bits) < 128);
+        assert!(self.bits.max(rhs.bits) < MAX_BITS);
         SignedDynBits {
             val: self.val.wrapping_sub(rhs.val),
             bits: self.bits.max(rhs.bits) + 1,
@@ -204,6 +208,28 @@ impl XSub<SignedDynBits> for SignedDynBits {
     }
 }
 
+impl<const N: usize, const M: usize> XSub<WideBits<M>> for WideBits<N>
+where
+    W<N>: WideBitWidth,
+    W<M>: WideBitWidth,
+{
+    type Output = SignedDynBits;
+    fn xsub(self, rhs: WideBits<M>) -> Self::Output {
+        self.dyn_bits().xsub(rhs.dyn_bits())
+    }
+}
+
+impl<const N: usize, const M: usize> XSub<SignedWideBits<M>> for SignedWideBits<N>
+where
+    W<N>: WideBitWidth,
+    W<M>: WideBitWidth,
+{
+    type Output = SignedDynBits;
+    fn xsub(self, rhs: SignedWideBits<M>) -> Self::Output {
+        self.dyn_bits().xsub(rhs.dyn_bits())
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
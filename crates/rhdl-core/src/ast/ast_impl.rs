@@ -367,6 +367,10 @@ pub enum BinOp {
     Sub,
     /// Multiplication
     Mul,
+    /// Division
+    Div,
+    /// Remainder
+    Rem,
     /// Logical AND
     And,
     /// Logical OR
@@ -399,6 +403,10 @@ pub enum BinOp {
     SubAssign,
     /// Multiplication Assignment
     MulAssign,
+    /// Division Assignment
+    DivAssign,
+    /// Remainder Assignment
+    RemAssign,
     /// Bitwise XOR Assignment
     BitXorAssign,
     /// Bitwise AND Assignment
@@ -422,6 +430,8 @@ impl BinOp {
             BinOp::AddAssign
                 | BinOp::SubAssign
                 | BinOp::MulAssign
+                | BinOp::DivAssign
+                | BinOp::RemAssign
                 | BinOp::BitXorAssign
                 | BinOp::BitAndAssign
                 | BinOp::BitOrAssign
@@ -444,6 +454,7 @@ pub enum UnOp {
 pub struct Arm {
     pub id: NodeId,
     pub kind: ArmKind,
+    pub guard: Option<Box<Expr>>,
     pub body: Box<Expr>,
 }
 
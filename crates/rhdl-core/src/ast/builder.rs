@@ -188,6 +188,19 @@ pub fn arm(id: NodeId, kind: ArmKind, body: Expr) -> Arm {
     Arm {
         id,
         kind,
+        guard: None,
+        body: Box::new(body),
+    }
+}
+
+/// Build an arm with the given kind, guard and body
+///
+/// `kind if guard => body`
+pub fn guarded_arm(id: NodeId, kind: ArmKind, guard: Expr, body: Expr) -> Arm {
+    Arm {
+        id,
+        kind,
+        guard: Some(Box::new(guard)),
         body: Box::new(body),
     }
 }
@@ -67,6 +67,7 @@ use crate::{
     bitx::BitX,
     circuit::{
         descriptor::{AsyncKind, Descriptor, SyncKind},
+        hdl::dedup::deduplicate_modules,
         scoped_name::ScopedName,
     },
     digital_fn::NoCircuitKernel,
@@ -75,6 +76,7 @@ use crate::{
 };
 
 use quote::{format_ident, quote};
+use rhdl_trace_type as rtt;
 use rhdl_vlog::{self as vlog, maybe_port_wire};
 use syn::parse_quote;
 
@@ -138,6 +140,19 @@ impl<I: Digital, D: Domain> Digital for AdapterInput<I, D> {
             .into(),
         )
     }
+    fn static_trace_type() -> rtt::TraceType {
+        rtt::make_struct(
+            "AdapterInput",
+            [
+                rtt::make_field(
+                    "clock_reset",
+                    <Signal<ClockReset, D> as Digital>::static_trace_type(),
+                ),
+                rtt::make_field("input", <Signal<I, D> as Digital>::static_trace_type()),
+            ]
+            .into(),
+        )
+    }
     fn bin(self) -> Box<[BitX]> {
         let mut out = vec![];
         out.extend(self.clock_reset.bin());
@@ -200,19 +215,38 @@ impl<C: Synchronous, D: Domain> Circuit for Adapter<C, D> {
         let inner = self
             .circuit
             .descriptor(scoped_name.with("inner"))
-            .map(|inner| Descriptor::<AsyncKind> {
-                name: scoped_name.with("inner"),
-                input_kind: inner.input_kind,
-                output_kind: inner.output_kind,
-                d_kind: inner.d_kind,
-                q_kind: inner.q_kind,
-                kernel: inner.kernel,
-                hdl: inner.hdl,
-                netlist: inner.netlist,
-                _phantom: std::marker::PhantomData,
-            });
+            .map(as_async);
         std::iter::once(inner)
     }
+
+    fn descendants(
+        &self,
+        scoped_name: &ScopedName,
+    ) -> impl Iterator<Item = Result<Descriptor<AsyncKind>, RHDLError>> {
+        let inner_scope = scoped_name.with("inner");
+        let inner = self
+            .circuit
+            .descendants(&inner_scope)
+            .map(|descriptor| descriptor.map(as_async))
+            .collect::<Vec<_>>();
+        self.children(scoped_name).chain(inner)
+    }
+}
+
+// Present the descriptor of a synchronous circuit inside the adapter
+// as part of the asynchronous hierarchy.
+fn as_async<K>(descriptor: Descriptor<K>) -> Descriptor<AsyncKind> {
+    Descriptor::<AsyncKind> {
+        name: descriptor.name,
+        input_kind: descriptor.input_kind,
+        output_kind: descriptor.output_kind,
+        d_kind: descriptor.d_kind,
+        q_kind: descriptor.q_kind,
+        kernel: descriptor.kernel,
+        hdl: descriptor.hdl,
+        netlist: descriptor.netlist,
+        _phantom: std::marker::PhantomData,
+    }
 }
 
 impl<C: Synchronous, D: Domain> DigitalFn for Adapter<C, D> {}
@@ -246,7 +280,7 @@ impl<C: Synchronous, D: Domain> Adapter<C, D> {
         };
         Ok(HDLDescriptor {
             name: name.to_string(),
-            modules: module_list,
+            modules: deduplicate_modules(module_list),
         })
     }
     fn netlist(
@@ -32,6 +32,7 @@ use crate::{
     Circuit, CircuitDQ, CircuitIO, Digital, HDLDescriptor, Kind, RHDLError,
     circuit::{
         descriptor::{AsyncKind, Descriptor},
+        hdl::dedup::deduplicate_modules,
         scoped_name::ScopedName,
     },
     digital_fn::NoCircuitKernel,
@@ -99,6 +100,18 @@ impl<T: Circuit, const N: usize> Circuit for [T; N] {
     ) -> impl Iterator<Item = Result<Descriptor<AsyncKind>, RHDLError>> {
         (0..N).map(move |i| self[i].descriptor(scoped_name.with(format!("c{i}"))))
     }
+
+    fn descendants(
+        &self,
+        scoped_name: &ScopedName,
+    ) -> impl Iterator<Item = Result<Descriptor<AsyncKind>, RHDLError>> {
+        (0..N).flat_map(move |i| {
+            let scope = scoped_name.with(format!("c{i}"));
+            std::iter::once(self[i].descriptor(scope.clone()))
+                .chain(self[i].descendants(&scope))
+                .collect::<Vec<_>>()
+        })
+    }
 }
 
 fn hdl<T: Circuit, const N: usize>(
@@ -140,7 +153,7 @@ fn hdl<T: Circuit, const N: usize>(
     };
     Ok(HDLDescriptor {
         name: name.into(),
-        modules,
+        modules: deduplicate_modules(modules),
     })
 }
 
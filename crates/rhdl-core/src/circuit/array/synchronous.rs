@@ -36,7 +36,9 @@
 use crate::{
     ClockReset, Digital, HDLDescriptor, Kind, RHDLError, Synchronous, SynchronousDQ, SynchronousIO,
     circuit::{
+        clock_enable::{ClockEnableInput, clock_enable_binding, clock_enable_port},
         descriptor::{Descriptor, SyncKind},
+        hdl::dedup::deduplicate_modules,
         scoped_name::ScopedName,
     },
     digital_fn::NoSynchronousKernel,
@@ -113,6 +115,18 @@ impl<T: Synchronous, const N: usize> Synchronous for [T; N] {
     ) -> impl Iterator<Item = Result<Descriptor<SyncKind>, RHDLError>> {
         (0..N).map(move |i| self[i].descriptor(parent_scope.with(format!("c{i}"))))
     }
+
+    fn descendants(
+        &self,
+        parent_scope: &ScopedName,
+    ) -> impl Iterator<Item = Result<Descriptor<SyncKind>, RHDLError>> {
+        (0..N).flat_map(move |i| {
+            let scope = parent_scope.with(format!("c{i}"));
+            std::iter::once(self[i].descriptor(scope.clone()))
+                .chain(self[i].descendants(&scope))
+                .collect::<Vec<_>>()
+        })
+    }
 }
 
 fn hdl<T: Synchronous, const N: usize>(
@@ -123,10 +137,15 @@ fn hdl<T: Synchronous, const N: usize>(
     let module_ident = format_ident!("{module_name}");
     let i_kind = <[T; N] as SynchronousIO>::I::static_kind();
     let o_kind = <[T; N] as SynchronousIO>::O::static_kind();
+    let child_hdl = children
+        .iter()
+        .map(|child| child.hdl())
+        .collect::<Result<Vec<_>, RHDLError>>()?;
     let ports = [
         vlog::maybe_port_wire(vlog::Direction::Input, 2, "clock_reset"),
         vlog::maybe_port_wire(vlog::Direction::Input, i_kind.bits(), "i"),
         vlog::maybe_port_wire(vlog::Direction::Output, o_kind.bits(), "o"),
+        clock_enable_port(&child_hdl),
     ];
     let ports = ports.iter().flatten();
     let mut child_hdls = vec![];
@@ -145,6 +164,7 @@ fn hdl<T: Synchronous, const N: usize>(
             Some(parse_quote! {.clock_reset(clock_reset)}),
             input_binding,
             output_binding,
+            clock_enable_binding(child.hdl()?).map(|ce| quote! {#ce}),
         ];
         let component_name = format_ident!("{}", child.name.to_string());
         let instance_name = format_ident!("c{ndx}");
@@ -160,7 +180,7 @@ fn hdl<T: Synchronous, const N: usize>(
     };
     Ok(HDLDescriptor {
         name: name.into(),
-        modules,
+        modules: deduplicate_modules(modules),
     })
 }
 
@@ -175,6 +195,7 @@ fn netlist<T: Synchronous, const N: usize>(
     let tcr = builder.add_input(cr_kind);
     let ti = builder.add_input(input_kind);
     let to = builder.allocate_outputs(output_kind);
+    let mut tce = ClockEnableInput::default();
     for (i, child_descriptor) in children.iter().enumerate() {
         let child_path = Path::default().index(i);
         let (output_bit_range, _) = bit_range(output_kind, &child_path)?;
@@ -185,6 +206,7 @@ fn netlist<T: Synchronous, const N: usize>(
         for (&t, c) in tcr.iter().zip(&child_inputs[0]) {
             builder.copy_from_to(t, offset(c.into()));
         }
+        tce.connect(&mut builder, child_netlist, &offset);
         for (&t, c) in ti[input_bit_range].iter().zip(&child_inputs[1]) {
             builder.copy_from_to(t, offset(c.into()));
         }
@@ -0,0 +1,167 @@
+//! Clock Domain Crossing Checks for Circuits
+//!
+//! RHDL tracks the clock domain of each [Signal](crate::Signal) in the type
+//! system, and the kernel compiler rejects logic that mixes signals from different
+//! domains.  But signals still have to move between domains somewhere, and that
+//! happens inside of black boxes, which the compiler cannot see into.  This module
+//! provides design rules that check the flattened netlist of an asynchronous circuit
+//! to make sure that every crossing goes through a recognised synchronizer.
+//!
+//! A black box is marked as a synchronizer with
+//! [Descriptor::with_black_box_synchronizer](crate::Descriptor::with_black_box_synchronizer).
+//! The rules are:
+//!
+//! - [UnsynchronizedCrossings] flags signals that reach a register, black box or
+//!   output in a different domain without going through a synchronizer.
+//! - [MultiBitSynchronizers] flags buses whose bits cross through separate single bit
+//!   synchronizers (unless they are marked as carrying a gray coded bus).
+//! - [ReconvergentSynchronizers] flags logic that combines the outputs of separate
+//!   single bit synchronizers.
+//!
+//! They are part of the default [DesignRules](super::drc::DesignRules) registry, and
+//! do nothing for synchronous circuits (which have only one domain).  See
+//! [crate::ntl::cdc] for details of the analysis.  The clock domain that each register
+//! in a design belongs to can be listed with [register_domains].
+use miette::Severity;
+
+use crate::{
+    Circuit, RHDLError,
+    circuit::{
+        drc::{Design, Rule, Violation},
+        scoped_name::ScopedName,
+    },
+    ntl::{cdc::ClockDomains, graph::GraphMode},
+};
+
+pub use crate::ntl::cdc::{Domains, Synchronizer};
+
+// The clock domain analysis of the design, if it is asynchronous
+fn clock_domains<'a>(design: &Design<'a>) -> Option<ClockDomains<'a>> {
+    matches!(design.mode, GraphMode::Asynchronous)
+        .then(|| ClockDomains::new(design.netlist, design.scopes[0].input_kind))
+}
+
+/// Flags signals that move between clock domains without a synchronizer.
+#[derive(Debug, Clone, Copy)]
+pub struct UnsynchronizedCrossings;
+
+impl Rule for UnsynchronizedCrossings {
+    fn name(&self) -> &'static str {
+        "unsynchronized_crossings"
+    }
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn help(&self) -> &'static str {
+        "Signals must move between clock domains through a synchronizer, like a Sync1Bit, a CrossCounter or an AsyncFIFO"
+    }
+    fn check(&self, design: &Design) -> Vec<Violation> {
+        let Some(domains) = clock_domains(design) else {
+            return vec![];
+        };
+        domains
+            .crossings(design.scopes[0].output_kind)
+            .into_iter()
+            .map(|crossing| {
+                Violation::new(
+                    self,
+                    design.netlist,
+                    crossing.target.clone(),
+                    format!(
+                        "signals from the {} domain reach {} (in the {} domain) without a synchronizer",
+                        crossing.from, crossing.target, crossing.domain
+                    ),
+                    crossing.locs,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Flags buses that cross clock domains one bit at a time through
+/// separate single bit synchronizers.
+#[derive(Debug, Clone, Copy)]
+pub struct MultiBitSynchronizers;
+
+impl Rule for MultiBitSynchronizers {
+    fn name(&self) -> &'static str {
+        "multi_bit_synchronizers"
+    }
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn help(&self) -> &'static str {
+        "The bits of a bus may be synchronized on different clock cycles.  Gray code the bus (like a CrossCounter), or use an AsyncFIFO"
+    }
+    fn check(&self, design: &Design) -> Vec<Violation> {
+        let Some(domains) = clock_domains(design) else {
+            return vec![];
+        };
+        domains
+            .bus_crossings()
+            .into_iter()
+            .map(|bus| {
+                Violation::new(
+                    self,
+                    design.netlist,
+                    bus.source.clone(),
+                    format!(
+                        "{} bits of {} cross domains through separate single bit synchronizers ({})",
+                        bus.synchronizers.len(),
+                        bus.source,
+                        bus.synchronizers.join(", ")
+                    ),
+                    bus.locs,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Flags logic that combines the outputs of separate single bit synchronizers.
+#[derive(Debug, Clone, Copy)]
+pub struct ReconvergentSynchronizers;
+
+impl Rule for ReconvergentSynchronizers {
+    fn name(&self) -> &'static str {
+        "reconvergent_synchronizers"
+    }
+    fn help(&self) -> &'static str {
+        "Separate synchronizers may settle on different clock cycles, so the combined value can be one that never existed in the source domain"
+    }
+    fn check(&self, design: &Design) -> Vec<Violation> {
+        let Some(domains) = clock_domains(design) else {
+            return vec![];
+        };
+        domains
+            .reconvergences()
+            .into_iter()
+            .map(|merge| {
+                Violation::new(
+                    self,
+                    design.netlist,
+                    design.kernel_name(merge.loc),
+                    format!(
+                        "the outputs of {} are combined",
+                        merge.synchronizers.join(", ")
+                    ),
+                    merge.loc,
+                )
+            })
+            .collect()
+    }
+}
+
+/// List the registers (synchronous black boxes) in an asynchronous circuit,
+/// with the clock domain that each one belongs to.
+///
+/// The registers are named by their flattened scoped names.  A register whose
+/// clock domain cannot be determined has no domains.
+pub fn register_domains<T: Circuit>(uut: &T) -> Result<Vec<(String, Domains)>, RHDLError> {
+    let descriptor = uut.descriptor(ScopedName::top())?;
+    let domains = ClockDomains::new(descriptor.netlist()?, descriptor.input_kind);
+    Ok(domains
+        .registers()
+        .map(|(name, domain)| (name.to_string(), domain))
+        .collect())
+}
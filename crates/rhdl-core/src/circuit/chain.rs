@@ -22,7 +22,9 @@ use quote::{format_ident, quote};
 use rhdl_vlog::declaration;
 use syn::parse_quote;
 
+use crate::circuit::clock_enable::{ClockEnableInput, clock_enable_binding, clock_enable_port};
 use crate::circuit::descriptor::{Descriptor, SyncKind};
+use crate::circuit::hdl::dedup::deduplicate_modules;
 use crate::circuit::scoped_name::ScopedName;
 use crate::{
     ClockReset, Digital, HDLDescriptor, Kind, Synchronous, SynchronousDQ, SynchronousIO,
@@ -106,6 +108,20 @@ where
         std::iter::once(self.a.descriptor(parent_scope.with("a")))
             .chain(std::iter::once(self.b.descriptor(parent_scope.with("b"))))
     }
+
+    fn descendants(
+        &self,
+        parent_scope: &ScopedName,
+    ) -> impl Iterator<Item = Result<Descriptor<SyncKind>, RHDLError>> {
+        let a_scope = parent_scope.with("a");
+        let b_scope = parent_scope.with("b");
+        std::iter::once(self.a.descriptor(a_scope.clone()))
+            .chain(self.a.descendants(&a_scope))
+            .chain(std::iter::once(self.b.descriptor(b_scope.clone())))
+            .chain(self.b.descendants(&b_scope))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
 }
 
 impl<A: Synchronous, B: Synchronous, P: Digital> Chain<A, B>
@@ -126,7 +142,9 @@ where
                 <B as SynchronousIO>::O::bits(),
                 "o",
             ),
+            clock_enable_port(&[a_descriptor.hdl()?, b_descriptor.hdl()?]),
         ];
+        let ports = ports.iter().flatten();
         let input_kind = <A as SynchronousIO>::I::static_kind();
         let pipe_kind = <A as SynchronousIO>::O::static_kind();
         let pipe = declaration(
@@ -146,18 +164,20 @@ where
         let b_hdl = b_descriptor.hdl()?;
         let a_modules = &a_hdl.modules;
         let b_modules = &b_hdl.modules;
+        let a_ce = clock_enable_binding(a_hdl).map(|ce| quote! {#ce,});
+        let b_ce = clock_enable_binding(b_hdl).map(|ce| quote! {#ce,});
         let module_list: vlog::ModuleList = parse_quote! {
             module #module_ident(input wire [1:0] clock_reset, #(#ports),*);
                 #pipe
-                #a_ident a(.clock_reset(clock_reset), .o(pipe), #a_input_binding);
-                #b_ident b(.clock_reset(clock_reset), .i(pipe), .o(o));
+                #a_ident a(.clock_reset(clock_reset), #a_ce .o(pipe), #a_input_binding);
+                #b_ident b(.clock_reset(clock_reset), #b_ce .i(pipe), .o(o));
             endmodule
             #a_modules
             #b_modules
         };
         Ok(HDLDescriptor {
             name: name.into(),
-            modules: module_list,
+            modules: deduplicate_modules(module_list),
         })
     }
 
@@ -180,6 +200,9 @@ where
         let b_netlist = b_descriptor.netlist()?;
         let a_offset = builder.import(a_netlist);
         let b_offset = builder.import(b_netlist);
+        let mut top_ce = ClockEnableInput::default();
+        top_ce.connect(&mut builder, a_netlist, &a_offset);
+        top_ce.connect(&mut builder, b_netlist, &b_offset);
         // Connect the clock and reset to the A and B netlists.
         for ((tcr, acr), bcr) in top_cr
             .iter()
@@ -265,4 +265,15 @@ pub trait Circuit: 'static + CircuitIO + Sized {
     ) -> impl Iterator<Item = Result<Descriptor<AsyncKind>, RHDLError>> {
         std::iter::empty()
     }
+
+    /// Iterate over all of the circuits below this one in the hierarchy,
+    /// with each child followed by its own descendants.  Circuits that
+    /// override [children](Circuit::children) should override this too,
+    /// or only their children are visited.
+    fn descendants(
+        &self,
+        parent_scope: &ScopedName,
+    ) -> impl Iterator<Item = Result<Descriptor<AsyncKind>, RHDLError>> {
+        self.children(parent_scope)
+    }
 }
@@ -0,0 +1,310 @@
+//! Clock enables for synchronous circuits
+//!
+//! A [ClockEnable] wraps a synchronous circuit, so that it only advances on
+//! the clock edges where its enable input is `true`.  This allows a subsystem to
+//! run at a fraction of the clock rate, without threading an explicit enable
+//! signal through every kernel and every register by hand.
+#![doc = badascii_doc::badascii!(r"
+      ++ClockEnable+-------------+
+      |                          |
+      |   enable   +-------+     |
+(bool,|   +------->| ce    |     |
+  I)  +   |        |       |     +  O
++-----+---+ I      |   C   +------------>
+      +   +------->|       |     +
+      |            +-------+     |
+ cr   |                ^         |
++----------------------+         |
+      +--------------------------+
+")]
+//!
+//! The input of the wrapped circuit is the pair `(enable, input)`.  The enable
+//! gates the state updates of all of the registers inside (flip flops, block
+//! RAMs, and the things built from them, like FIFOs).  In simulation, the
+//! registers hold their state on the clock edges where the enable was low.  In the
+//! generated Verilog, each of the modules that contains registers gets an extra
+//! `ce` input port, and the registers are updated with an `if (ce)` inside their
+//! `always` block, which maps onto the clock enable pins of FPGA flip flops and
+//! block RAMs.  The reset is not gated by the enable.
+//!
+//! Registers query the enable that is in effect with [clock_enable], when they are
+//! simulated, and when their descriptors are built.  A [ClockEnable] inside another
+//! one is enabled only when both enables are `true`.
+use std::cell::Cell;
+
+use quote::format_ident;
+use syn::parse_quote;
+
+use crate::{
+    ClockReset, Digital, HDLDescriptor, Kind, RHDLError, Synchronous, SynchronousDQ, SynchronousIO,
+    circuit::{
+        descriptor::{Descriptor, SyncKind},
+        hdl::dedup::deduplicate_modules,
+        scoped_name::ScopedName,
+    },
+    common::symtab::RegisterId,
+    digital_fn::NoSynchronousKernel,
+    ntl::{
+        self,
+        builder::Builder,
+        spec::{Wire, WireKind},
+    },
+    trace, trace_pop_path, trace_push_path,
+    types::path::{Path, bit_range},
+};
+use rhdl_vlog::{self as vlog, maybe_port_wire};
+
+thread_local! {
+    static CLOCK_ENABLE: Cell<Option<bool>> = const { Cell::new(None) };
+}
+
+/// The clock enable in effect for the circuit being simulated (or described).
+///
+/// This is `None` if the circuit is not inside a [ClockEnable].  Otherwise, it is
+/// the current value of the enable (when simulating), or `Some(true)` (when building
+/// descriptors).  Registers that support a clock enable should add a `ce` port to
+/// their Verilog module if it is `Some`.
+pub fn clock_enable() -> Option<bool> {
+    CLOCK_ENABLE.with(|enable| enable.get())
+}
+
+struct EnableGuard(Option<bool>);
+
+impl Drop for EnableGuard {
+    fn drop(&mut self) {
+        CLOCK_ENABLE.with(|enable| enable.set(self.0));
+    }
+}
+
+// Run the closure with the given clock enable (combined with
+// that of any enclosing circuit) in effect
+fn with_clock_enable<R>(enable: bool, f: impl FnOnce() -> R) -> R {
+    let previous = clock_enable();
+    CLOCK_ENABLE.with(|cell| cell.set(Some(enable && previous.unwrap_or(true))));
+    let _guard = EnableGuard(previous);
+    f()
+}
+
+/// Returns true if the top module of the HDL has a `ce` port
+pub(crate) fn has_clock_enable(hdl: &HDLDescriptor) -> bool {
+    hdl.modules
+        .modules
+        .first()
+        .is_some_and(|module| module.args.iter().any(|port| port.decl.name == "ce"))
+}
+
+/// The `ce` port of a module, if any of its children need one
+pub(crate) fn clock_enable_port(children: &[&HDLDescriptor]) -> Option<vlog::Port> {
+    children
+        .iter()
+        .any(|child| has_clock_enable(child))
+        .then(|| maybe_port_wire(vlog::Direction::Input, 1, "ce"))
+        .flatten()
+}
+
+/// The connection of the `ce` port of a child, if it has one
+pub(crate) fn clock_enable_binding(child: &HDLDescriptor) -> Option<vlog::stmt::Connection> {
+    has_clock_enable(child).then(|| parse_quote! { .ce(ce) })
+}
+
+/// The clock enable input of a netlist under construction.
+///
+/// Netlists of circuits with a clock enable have it as a third
+/// (single bit) input, after the clock/reset and the input.  The
+/// input is added the first time a child that needs it is linked in,
+/// so it must be created after the other inputs have been added.
+#[derive(Default)]
+pub(crate) struct ClockEnableInput(Option<RegisterId<WireKind>>);
+
+impl ClockEnableInput {
+    /// Connect the clock enable of a child netlist (if it has one)
+    pub(crate) fn connect(
+        &mut self,
+        builder: &mut Builder,
+        child: &ntl::Object,
+        offset: impl Fn(Wire) -> Wire,
+    ) {
+        let Some(&child_ce) = child.inputs.get(2).and_then(|ce| ce.first()) else {
+            return;
+        };
+        let ce = *self
+            .0
+            .get_or_insert_with(|| builder.add_input(bool::static_kind())[0]);
+        builder.copy_from_to(ce, offset(child_ce.into()));
+    }
+}
+
+/// Gate the state updates of a synchronous circuit with an enable.
+///
+/// The input of the circuit is `(enable, input)`, where `input` is
+/// passed to the wrapped circuit.
+#[derive(Clone)]
+pub struct ClockEnable<C> {
+    inner: C,
+}
+
+impl<C> ClockEnable<C> {
+    /// Create a new [ClockEnable] wrapping the given circuit
+    #[must_use]
+    pub fn new(inner: C) -> Self {
+        Self { inner }
+    }
+}
+
+impl<C: Synchronous> SynchronousIO for ClockEnable<C> {
+    type I = (bool, C::I);
+    type O = C::O;
+    type Kernel = NoSynchronousKernel<ClockReset, Self::I, (), (Self::O, ())>;
+}
+
+impl<C: Synchronous> SynchronousDQ for ClockEnable<C> {
+    type D = ();
+    type Q = ();
+}
+
+impl<C: Synchronous> Synchronous for ClockEnable<C> {
+    type S = C::S;
+
+    fn init(&self) -> Self::S {
+        self.inner.init()
+    }
+
+    fn sim(&self, clock_reset: ClockReset, input: Self::I, state: &mut Self::S) -> Self::O {
+        trace_push_path("clock_enable");
+        let (enable, input) = input;
+        trace("enable", &enable);
+        let output = with_clock_enable(enable, || self.inner.sim(clock_reset, input, state));
+        trace_pop_path();
+        output
+    }
+
+    fn descriptor(&self, scoped_name: ScopedName) -> Result<Descriptor<SyncKind>, RHDLError> {
+        // The enable is combined with that of an enclosing circuit
+        let nested = clock_enable().is_some();
+        let inner = with_clock_enable(true, || self.inner.descriptor(scoped_name.with("inner")))?;
+        let name = scoped_name.to_string();
+        Ok(Descriptor::<SyncKind> {
+            name: scoped_name,
+            input_kind: <Self as SynchronousIO>::I::static_kind(),
+            output_kind: <Self as SynchronousIO>::O::static_kind(),
+            d_kind: Kind::Empty,
+            q_kind: Kind::Empty,
+            kernel: None,
+            netlist: Some(self.netlist(&name, nested, &inner)?),
+            hdl: Some(self.hdl(&name, nested, &inner)?),
+            _phantom: std::marker::PhantomData,
+        })
+    }
+
+    fn children(
+        &self,
+        parent_scope: &ScopedName,
+    ) -> impl Iterator<Item = Result<Descriptor<SyncKind>, RHDLError>> {
+        std::iter::once(with_clock_enable(true, || {
+            self.inner.descriptor(parent_scope.with("inner"))
+        }))
+    }
+
+    fn descendants(
+        &self,
+        parent_scope: &ScopedName,
+    ) -> impl Iterator<Item = Result<Descriptor<SyncKind>, RHDLError>> {
+        let scope = parent_scope.with("inner");
+        with_clock_enable(true, || {
+            std::iter::once(self.inner.descriptor(scope.clone()))
+                .chain(self.inner.descendants(&scope))
+                .collect::<Vec<_>>()
+        })
+        .into_iter()
+    }
+}
+
+impl<C: Synchronous> ClockEnable<C> {
+    fn hdl(
+        &self,
+        name: &str,
+        nested: bool,
+        inner: &Descriptor<SyncKind>,
+    ) -> Result<HDLDescriptor, RHDLError> {
+        let i_kind = <Self as SynchronousIO>::I::static_kind();
+        let (enable_range, _) = bit_range(i_kind, &Path::default().tuple_index(0))?;
+        let (input_range, _) = bit_range(i_kind, &Path::default().tuple_index(1))?;
+        let enable_index = syn::Index::from(enable_range.start);
+        let ports = [
+            maybe_port_wire(vlog::Direction::Input, 2, "clock_reset"),
+            maybe_port_wire(vlog::Direction::Input, i_kind.bits(), "i"),
+            maybe_port_wire(
+                vlog::Direction::Output,
+                <Self as SynchronousIO>::O::bits(),
+                "o",
+            ),
+            nested
+                .then(|| maybe_port_wire(vlog::Direction::Input, 1, "ce"))
+                .flatten(),
+        ];
+        let ports = ports.iter().flatten();
+        let inner_hdl = inner.hdl()?;
+        let enable: vlog::stmt::Connection = if nested {
+            parse_quote! { .ce(i[#enable_index] & ce) }
+        } else {
+            parse_quote! { .ce(i[#enable_index]) }
+        };
+        let input_range: vlog::BitRange = input_range.clone().into();
+        let bindings: [Option<vlog::stmt::Connection>; 4] = [
+            Some(parse_quote! { .clock_reset(clock_reset) }),
+            (C::I::bits() != 0).then(|| parse_quote! { .i(i[#input_range]) }),
+            Some(parse_quote! { .o(o) }),
+            has_clock_enable(inner_hdl).then_some(enable),
+        ];
+        let bindings = bindings.iter().flatten();
+        let module_ident = format_ident!("{name}");
+        let inner_ident = format_ident!("{}", inner.name.to_string());
+        let inner_modules = &inner_hdl.modules;
+        let modules: vlog::ModuleList = parse_quote! {
+            module #module_ident(#(#ports),*);
+                #inner_ident inner(#(#bindings),*);
+            endmodule
+            #inner_modules
+        };
+        Ok(HDLDescriptor {
+            name: name.into(),
+            modules: deduplicate_modules(modules),
+        })
+    }
+
+    fn netlist(
+        &self,
+        name: &str,
+        nested: bool,
+        inner: &Descriptor<SyncKind>,
+    ) -> Result<ntl::Object, RHDLError> {
+        let i_kind = <Self as SynchronousIO>::I::static_kind();
+        let (enable_range, _) = bit_range(i_kind, &Path::default().tuple_index(0))?;
+        let (input_range, _) = bit_range(i_kind, &Path::default().tuple_index(1))?;
+        let mut builder = Builder::new(name);
+        let top_cr = builder.add_input(ClockReset::static_kind());
+        let top_i = builder.add_input(i_kind);
+        let top_ce = nested.then(|| builder.add_input(bool::static_kind())[0]);
+        let top_o = builder.allocate_outputs(<Self as SynchronousIO>::O::static_kind());
+        let inner_netlist = inner.netlist()?;
+        let offset = builder.import(inner_netlist);
+        for (&top, bit) in top_cr.iter().zip(&inner_netlist.inputs[0]) {
+            builder.copy_from_to(top, offset(bit.into()));
+        }
+        for (&top, bit) in top_i[input_range].iter().zip(&inner_netlist.inputs[1]) {
+            builder.copy_from_to(top, offset(bit.into()));
+        }
+        for (&top, bit) in top_o.iter().zip(&inner_netlist.outputs) {
+            builder.copy_from_to(offset(*bit), top);
+        }
+        if let Some(&inner_ce) = inner_netlist.inputs.get(2).and_then(|ce| ce.first()) {
+            let enable = top_i[enable_range.start];
+            let enable = match top_ce {
+                Some(ce) => builder.and(enable, ce),
+                None => enable,
+            };
+            builder.copy_from_to(enable, offset(inner_ce.into()));
+        }
+        builder.build(ntl::builder::BuilderMode::Synchronous)
+    }
+}
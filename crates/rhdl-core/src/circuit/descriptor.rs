@@ -8,6 +8,7 @@
 //! It includes information about the input and output kinds,
 //! the internal feedback types, the compiled kernel object,
 //! and optionally the netlist and HDL description of the circuit.
+//! A VHDL description can be generated from the netlist on demand.
 //!
 //! It also provides a way to iterate over the subcircuits of a
 //! circuit, allowing for a run time iteration over the (heterogeneous)
@@ -23,7 +24,19 @@
 //! kinds of circuits.
 use std::marker::PhantomData;
 
-use crate::{HDLDescriptor, Kind, RHDLError, circuit::scoped_name::ScopedName, ntl, rtl};
+use crate::{
+    HDLDescriptor, Kind, RHDLError, VHDLDescriptor,
+    circuit::scoped_name::ScopedName,
+    ntl::{
+        self,
+        cdc::Synchronizer,
+        resources::BlackBoxResources,
+        smt::{BlackBoxSmt, BlackBoxSmtModel},
+        vhdl::build_vhdl,
+        vm::{BlackBoxModel, BlackBoxSim},
+    },
+    rtl,
+};
 
 /// Marker type for asynchronous circuits.
 pub struct AsyncKind;
@@ -68,20 +81,137 @@ impl<T> Descriptor<T> {
             name: self.name.to_string(),
         })
     }
+    /// Attach a VHDL implementation to the black box netlist of this descriptor.
+    ///
+    /// Call this after `with_netlist_black_box` so that the VHDL generator
+    /// can emit the core, since it cannot be derived from the Verilog.
+    pub fn with_black_box_vhdl(mut self, vhdl: VHDLDescriptor) -> Result<Self, RHDLError> {
+        let netlist = self
+            .netlist
+            .as_mut()
+            .ok_or(RHDLError::NetlistNotAvailable {
+                name: self.name.to_string(),
+            })?;
+        for black_box in &mut netlist.black_boxes {
+            black_box.vhdl = Some(vhdl.clone());
+        }
+        Ok(self)
+    }
+    /// Attach a formal model to the black box netlist of this descriptor.
+    ///
+    /// Call this after `with_netlist_black_box` so that the netlist can be
+    /// exported to SMT-LIB2 for formal verification.
+    pub fn with_black_box_smt(
+        mut self,
+        model: impl BlackBoxSmt + 'static,
+    ) -> Result<Self, RHDLError> {
+        let netlist = self
+            .netlist
+            .as_mut()
+            .ok_or(RHDLError::NetlistNotAvailable {
+                name: self.name.to_string(),
+            })?;
+        let model = BlackBoxSmtModel::new(model);
+        for black_box in &mut netlist.black_boxes {
+            black_box.smt = Some(model.clone());
+        }
+        Ok(self)
+    }
+    /// Declare the resources (flip flops and memory) used by the black box
+    /// netlist of this descriptor.
+    ///
+    /// Call this after `with_netlist_black_box` so that the resource estimator
+    /// can account for the contents of the black box.
+    pub fn with_black_box_resources(
+        mut self,
+        resources: BlackBoxResources,
+    ) -> Result<Self, RHDLError> {
+        let netlist = self
+            .netlist
+            .as_mut()
+            .ok_or(RHDLError::NetlistNotAvailable {
+                name: self.name.to_string(),
+            })?;
+        for black_box in &mut netlist.black_boxes {
+            black_box.resources = Some(resources);
+        }
+        Ok(self)
+    }
+    /// Mark the black box netlist of this descriptor as a recognised
+    /// clock domain crossing.
+    ///
+    /// Call this after `with_netlist_black_box` so that the clock domain
+    /// crossing checks accept signals that cross domains through the black box.
+    pub fn with_black_box_synchronizer(
+        mut self,
+        synchronizer: Synchronizer,
+    ) -> Result<Self, RHDLError> {
+        let netlist = self
+            .netlist
+            .as_mut()
+            .ok_or(RHDLError::NetlistNotAvailable {
+                name: self.name.to_string(),
+            })?;
+        for black_box in &mut netlist.black_boxes {
+            black_box.synchronizer = Some(synchronizer);
+        }
+        Ok(self)
+    }
 }
 
 impl Descriptor<AsyncKind> {
+    /// Generate a VHDL-2008 description of the circuit from its netlist.
+    ///
+    /// The top level entity has the same ports (`i` and `o`) as the Verilog module.
+    pub fn vhdl(&self) -> Result<VHDLDescriptor, RHDLError> {
+        build_vhdl(&self.name.to_string(), self.netlist()?, &["i"], "o")
+    }
     /// Create a black box (asynchronous) netlist for this descriptor.
     pub fn with_netlist_black_box(mut self) -> Result<Descriptor<AsyncKind>, RHDLError> {
         self.netlist = Some(ntl::builder::circuit_black_box(&self)?);
         Ok(self)
     }
+    /// Create a black box (asynchronous) netlist for this descriptor, with
+    /// a behavioral model that allows the netlist to be simulated.
+    pub fn with_netlist_black_box_model(
+        mut self,
+        model: impl BlackBoxSim + 'static,
+    ) -> Result<Descriptor<AsyncKind>, RHDLError> {
+        self.netlist = Some(ntl::builder::circuit_black_box_with_model(
+            &self,
+            Some(BlackBoxModel::new(model)),
+        )?);
+        Ok(self)
+    }
 }
 
 impl Descriptor<SyncKind> {
+    /// Generate a VHDL-2008 description of the circuit from its netlist.
+    ///
+    /// The top level entity has the same ports (`clock_reset`, `i` and `o`) as the Verilog module.
+    pub fn vhdl(&self) -> Result<VHDLDescriptor, RHDLError> {
+        build_vhdl(
+            &self.name.to_string(),
+            self.netlist()?,
+            &["clock_reset", "i"],
+            "o",
+        )
+    }
     /// Create a black box (synchronous) netlist for this descriptor.
     pub fn with_netlist_black_box(mut self) -> Result<Descriptor<SyncKind>, RHDLError> {
         self.netlist = Some(ntl::builder::synchronous_black_box(&self)?);
         Ok(self)
     }
+    /// Create a black box (synchronous) netlist for this descriptor, with
+    /// a behavioral model that allows the netlist to be simulated.
+    pub fn with_netlist_black_box_model(
+        mut self,
+        model: impl BlackBoxSim + 'static,
+    ) -> Result<Descriptor<SyncKind>, RHDLError> {
+        self.netlist = Some(ntl::builder::synchronous_black_box_with_model(
+            &self,
+            Some(BlackBoxModel::new(model)),
+        )?);
+        Ok(self)
+    }
 }
@@ -1,27 +1,62 @@
 //! Design Rule Checking for Circuits
 //!
-//! Eventually this module will contain various design rule checking functions to apply to RHDL circuits.
-//! For now, it contains a single check for combinatorial paths in synchronous circuits.
+//! This module contains design rule checks (DRC) that can be applied to RHDL circuits.
+//! Each check is a [Rule] that inspects the netlists of a design and reports
+//! [Violation]s, which are miette diagnostics that point to the kernel source
+//! responsible for the problem (where it is known).  The rules are collected into
+//! a [DesignRules] registry, which assigns each rule a severity, and holds a
+//! per-rule allow-list of the places where a violation is expected.
 //!
+//! The built in rules are:
+//!
+//! - [UnusedInputs] flags input bits of a circuit that are never used.
+//! - [ConstantOutputs] flags output bits of a circuit that are driven by constants.
+//! - [RegistersNeverReset] flags registers (and other synchronous black boxes)
+//!   whose reset is tied to a constant.
+//! - [WideCase] flags case statements (`match` expressions) with too many arms.
+//! - [WideMultiplier] flags multipliers that are wider than a threshold.
+//! - [CombinationalDepth] flags designs whose logic depth exceeds a threshold.
+//!
+//! The registry also includes the clock domain crossing rules from [super::cdc]
+//! ([UnsynchronizedCrossings], [MultiBitSynchronizers] and [ReconvergentSynchronizers]),
+//! which only apply to asynchronous circuits.
+//!
+//! The registry is run over a design with [DesignRules::check_synchronous] or
+//! [DesignRules::check_circuit].  Rules that look at the interface of a circuit
+//! are applied to the top level circuit and every circuit inside it, and report
+//! the scoped name of the circuit.  Rules that look at the logic are applied to
+//! the flattened netlist of the whole design, and report the name of the kernel
+//! that contains the offending logic.  These names are also the keys of the allow-lists.
+//!
+//! There is also a stand alone check for combinatorial paths in synchronous circuits.
 //! A combinatorial path is a path from an input to an output that does not terminate on any flip flops or
 //! other black box components.  Some specifications discourage or even forbid combinatorial paths, as they can
-//! lead to timing issues.  This module provides a function to check for such paths and report them.
+//! lead to timing issues.  The [no_combinatorial_paths] function checks for such paths and reports them.
 //!
 //! See the [book] for an example of how to use it.
 use crate::{
-    Synchronous,
-    ast::SourcePool,
+    Circuit, ClockReset, Descriptor, Digital, Kind, RHDLError, Synchronous,
+    ast::{SourceLocation, SourcePool},
     circuit::scoped_name::ScopedName,
+    common::symtab::RegisterId,
     ntl::{
+        Object,
         graph::{GraphMode, WriteSource, make_net_graph},
-        spec::Wire,
+        object::BlackBoxMode,
+        spec::{OpCode, VectorOp, Wire, WireKind},
+        timing::{UnitDelay, critical_path},
+        visit::visit_wires,
     },
+    rtl,
+    types::path::{Path, bit_range},
 };
-use miette::{Diagnostic, SourceSpan};
+use miette::{Diagnostic, Severity, SourceSpan};
 use petgraph::algo::DfsSpace;
-use std::collections::hash_map::RandomState;
+use std::collections::{HashMap, HashSet, hash_map::RandomState};
 use thiserror::Error;
 
+pub use super::cdc::{MultiBitSynchronizers, ReconvergentSynchronizers, UnsynchronizedCrossings};
+
 /// Diagnostic for combinatorial paths in synchronous circuits.
 #[derive(Debug, Error)]
 #[error("RHDL Combinatorial Path")]
@@ -111,3 +146,631 @@ pub fn no_combinatorial_paths<T: Synchronous>(uut: &T) -> miette::Result<()> {
     }
     Ok(())
 }
+
+/// A design rule violation.
+///
+/// The severity of the violation is set by the [DesignRules] that found it.
+#[derive(Debug, Error)]
+#[error("RHDL Design Rule {rule} ({location}): {message}")]
+pub struct Violation {
+    /// The name of the rule that was violated
+    pub rule: &'static str,
+    /// Where the violation was found (a scoped name or a kernel name)
+    pub location: String,
+    /// A description of the violation
+    pub message: String,
+    /// How serious the violation is
+    pub severity: Severity,
+    help: &'static str,
+    src: SourcePool,
+    elements: Vec<SourceSpan>,
+}
+
+impl Violation {
+    /// Create a violation of the given rule, pointing to the source of the given
+    /// locations in the netlist.
+    pub fn new<R: Rule + ?Sized>(
+        rule: &R,
+        object: &Object,
+        location: impl Into<String>,
+        message: impl Into<String>,
+        locs: impl IntoIterator<Item = SourceLocation>,
+    ) -> Self {
+        let mut elements: Vec<SourceSpan> = vec![];
+        for span in locs
+            .into_iter()
+            .map(|loc| SourceSpan::from(object.code.span(loc)))
+        {
+            if !elements.contains(&span) {
+                elements.push(span);
+            }
+        }
+        Self {
+            rule: rule.name(),
+            location: location.into(),
+            message: message.into(),
+            severity: rule.severity(),
+            help: rule.help(),
+            src: object.code.source(),
+            elements,
+        }
+    }
+}
+
+impl Diagnostic for Violation {
+    fn severity(&self) -> Option<Severity> {
+        Some(self.severity)
+    }
+    fn source_code(&self) -> Option<&dyn miette::SourceCode> {
+        Some(&self.src)
+    }
+    fn help<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+        Some(Box::new(self.help))
+    }
+    fn labels<'a>(
+        &'a self,
+    ) -> Option<Box<dyn std::iter::Iterator<Item = miette::LabeledSpan> + 'a>> {
+        Some(Box::new(self.elements.iter().map(|span| {
+            miette::LabeledSpan::new_primary_with_span(None, *span)
+        })))
+    }
+}
+
+/// A circuit in the design being checked.
+pub struct Scope<'a> {
+    /// The scoped name of the circuit
+    pub name: ScopedName,
+    /// The netlist of the circuit (including its children)
+    pub netlist: &'a Object,
+    /// The kernel of the circuit, if it has one
+    pub kernel: Option<&'a rtl::Object>,
+    /// The kind of the input of the circuit
+    pub input_kind: Kind,
+    /// The kind of the output of the circuit
+    pub output_kind: Kind,
+}
+
+impl Scope<'_> {
+    // The source location of the kernel for this circuit, if it is known
+    fn kernel_loc(&self) -> Option<SourceLocation> {
+        let kernel = self.kernel?;
+        self.netlist
+            .code
+            .sources
+            .contains_key(&kernel.fn_id)
+            .then(|| self.netlist.code.fallback(kernel.fn_id))
+    }
+}
+
+/// The design being checked.
+pub struct Design<'a> {
+    /// Whether the design is synchronous (so that the first input of each
+    /// netlist is the clock and reset) or asynchronous
+    pub mode: GraphMode,
+    /// The flattened netlist of the whole design
+    pub netlist: &'a Object,
+    /// The top level circuit, followed by each of its descendants
+    pub scopes: Vec<Scope<'a>>,
+}
+
+impl Design<'_> {
+    /// The name of the kernel that contains the given location, if it is known.
+    pub fn kernel_name(&self, loc: Option<SourceLocation>) -> String {
+        loc.and_then(|loc| self.netlist.code.sources.get(&loc.func))
+            .map(|src| src.name.clone())
+            .unwrap_or_else(|| self.scopes[0].name.to_string())
+    }
+}
+
+/// A design rule.
+pub trait Rule {
+    /// The name of the rule, which is used to configure it in a [DesignRules] registry.
+    fn name(&self) -> &'static str;
+    /// The default severity of violations of this rule.
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+    /// Advice on how to fix a violation of this rule.
+    fn help(&self) -> &'static str;
+    /// Check the design, and return any violations found.
+    fn check(&self, design: &Design) -> Vec<Violation>;
+}
+
+// Describe a set of bit indices as a list of ranges
+fn bit_list(bits: &[usize]) -> String {
+    let mut ranges: Vec<(usize, usize)> = vec![];
+    for &bit in bits {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == bit => *end = bit,
+            _ => ranges.push((bit, bit)),
+        }
+    }
+    ranges
+        .into_iter()
+        .map(|(start, end)| {
+            if start == end {
+                format!("{start}")
+            } else {
+                format!("{start}..={end}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// Map the left hand side of each assignment in the netlist to its right hand side
+fn assignments(netlist: &Object) -> HashMap<RegisterId<WireKind>, Wire> {
+    netlist
+        .ops
+        .iter()
+        .filter_map(|lop| match &lop.op {
+            OpCode::Assign(assign) => Some((assign.lhs.reg()?, assign.rhs)),
+            _ => None,
+        })
+        .collect()
+}
+
+// Follow a chain of assignments back to the wire that drives the given one
+fn driver(assigns: &HashMap<RegisterId<WireKind>, Wire>, mut wire: Wire) -> Wire {
+    for _ in 0..=assigns.len() {
+        match wire.reg().and_then(|reg| assigns.get(&reg)) {
+            Some(rhs) => wire = *rhs,
+            None => break,
+        }
+    }
+    wire
+}
+
+/// Flags input bits of a circuit that are never used.
+///
+/// The clock and reset of a synchronous circuit are not checked, since
+/// they are implicitly connected to every circuit.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UnusedInputs;
+
+impl Rule for UnusedInputs {
+    fn name(&self) -> &'static str {
+        "unused_inputs"
+    }
+    fn help(&self) -> &'static str {
+        "Remove the unused inputs, or use a narrower type for them"
+    }
+    fn check(&self, design: &Design) -> Vec<Violation> {
+        let skip = match design.mode {
+            GraphMode::Synchronous => 1,
+            GraphMode::Asynchronous => 0,
+        };
+        design
+            .scopes
+            .iter()
+            .filter_map(|scope| {
+                let netlist = scope.netlist;
+                let mut read = HashSet::new();
+                for lop in &netlist.ops {
+                    visit_wires(&lop.op, |sense, wire| {
+                        if sense.is_read() {
+                            read.extend(wire.reg());
+                        }
+                    });
+                }
+                read.extend(netlist.outputs.iter().flat_map(|w| w.reg()));
+                let unused = netlist
+                    .inputs
+                    .iter()
+                    .skip(skip)
+                    .flatten()
+                    .enumerate()
+                    .filter(|(_, reg)| !read.contains(*reg))
+                    .map(|(bit, _)| bit)
+                    .collect::<Vec<_>>();
+                (!unused.is_empty()).then(|| {
+                    Violation::new(
+                        self,
+                        netlist,
+                        scope.name.to_string(),
+                        format!("input bits {} are never used", bit_list(&unused)),
+                        scope.kernel_loc(),
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+/// Flags output bits of a circuit that are driven by constants.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConstantOutputs;
+
+impl Rule for ConstantOutputs {
+    fn name(&self) -> &'static str {
+        "constant_outputs"
+    }
+    fn severity(&self) -> Severity {
+        Severity::Advice
+    }
+    fn help(&self) -> &'static str {
+        "Outputs that are always constant can be removed, or replaced with a narrower type"
+    }
+    fn check(&self, design: &Design) -> Vec<Violation> {
+        design
+            .scopes
+            .iter()
+            .filter_map(|scope| {
+                let netlist = scope.netlist;
+                let assigns = assignments(netlist);
+                let constant = netlist
+                    .outputs
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, wire)| driver(&assigns, **wire).is_lit())
+                    .map(|(bit, _)| bit)
+                    .collect::<Vec<_>>();
+                (!constant.is_empty()).then(|| {
+                    Violation::new(
+                        self,
+                        netlist,
+                        scope.name.to_string(),
+                        format!("output bits {} are constant", bit_list(&constant)),
+                        scope.kernel_loc(),
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+/// Flags registers (and other synchronous black boxes) whose reset is tied to a constant.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RegistersNeverReset;
+
+impl Rule for RegistersNeverReset {
+    fn name(&self) -> &'static str {
+        "registers_never_reset"
+    }
+    fn help(&self) -> &'static str {
+        "Connect the reset of the register to a reset signal, so that it starts in a known state"
+    }
+    fn check(&self, design: &Design) -> Vec<Violation> {
+        let netlist = design.netlist;
+        let assigns = assignments(netlist);
+        let Ok((reset_bit, _)) =
+            bit_range(ClockReset::static_kind(), &Path::default().field("reset"))
+        else {
+            return vec![];
+        };
+        netlist
+            .ops
+            .iter()
+            .filter_map(|lop| {
+                let OpCode::BlackBox(black_box) = &lop.op else {
+                    return None;
+                };
+                let core = &netlist.black_boxes[black_box.code.raw()];
+                if core.mode != BlackBoxMode::Synchronous {
+                    return None;
+                }
+                let reset = black_box.arg.first()?.get(reset_bit.start)?;
+                let value = netlist.bitx(driver(&assigns, *reset))?;
+                Some(Violation::new(
+                    self,
+                    netlist,
+                    core.code.name.clone(),
+                    format!("the reset of {} is tied to {value:?}", core.code.name),
+                    lop.loc,
+                ))
+            })
+            .collect()
+    }
+}
+
+/// Flags case statements (`match` expressions) with more than `max_arms` arms.
+///
+/// Wide multiplexers are slow and expensive.  A lookup table in a ROM may
+/// be a better choice.
+#[derive(Debug, Clone, Copy)]
+pub struct WideCase {
+    /// The largest number of arms allowed
+    pub max_arms: usize,
+}
+
+impl Default for WideCase {
+    fn default() -> Self {
+        Self { max_arms: 32 }
+    }
+}
+
+impl Rule for WideCase {
+    fn name(&self) -> &'static str {
+        "wide_case"
+    }
+    fn help(&self) -> &'static str {
+        "Wide multiplexers are slow and expensive.  Consider splitting the match, or using a ROM"
+    }
+    fn check(&self, design: &Design) -> Vec<Violation> {
+        // The case statement is repeated for each bit of the result, so
+        // only report each source location once
+        let mut seen = HashSet::new();
+        design
+            .netlist
+            .ops
+            .iter()
+            .filter_map(|lop| {
+                let OpCode::Case(case) = &lop.op else {
+                    return None;
+                };
+                let arms = case.entries.len();
+                (arms > self.max_arms && seen.insert(lop.loc)).then(|| {
+                    Violation::new(
+                        self,
+                        design.netlist,
+                        design.kernel_name(lop.loc),
+                        format!(
+                            "case statement has {arms} arms (the limit is {})",
+                            self.max_arms
+                        ),
+                        lop.loc,
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+/// Flags multipliers whose arguments are wider than `max_width` bits.
+#[derive(Debug, Clone, Copy)]
+pub struct WideMultiplier {
+    /// The widest multiplier argument allowed
+    pub max_width: usize,
+}
+
+impl Default for WideMultiplier {
+    /// The default matches the width of a typical FPGA multiplier block.
+    fn default() -> Self {
+        Self { max_width: 18 }
+    }
+}
+
+impl Rule for WideMultiplier {
+    fn name(&self) -> &'static str {
+        "wide_multiplier"
+    }
+    fn help(&self) -> &'static str {
+        "Wide multipliers need several multiplier blocks.  Consider reducing the width, or pipelining the product"
+    }
+    fn check(&self, design: &Design) -> Vec<Violation> {
+        design
+            .netlist
+            .ops
+            .iter()
+            .filter_map(|lop| {
+                let OpCode::Vector(vector) = &lop.op else {
+                    return None;
+                };
+                let width = vector.arg1.len().max(vector.arg2.len());
+                (vector.op == VectorOp::Mul && width > self.max_width).then(|| {
+                    Violation::new(
+                        self,
+                        design.netlist,
+                        design.kernel_name(lop.loc),
+                        format!(
+                            "multiplier is {width} bits wide (the limit is {})",
+                            self.max_width
+                        ),
+                        lop.loc,
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+/// Flags designs whose longest combinational path has more than
+/// `max_depth` levels of logic (as measured by [UnitDelay]).
+#[derive(Debug, Clone, Copy)]
+pub struct CombinationalDepth {
+    /// The deepest path allowed
+    pub max_depth: usize,
+}
+
+impl Default for CombinationalDepth {
+    fn default() -> Self {
+        Self { max_depth: 64 }
+    }
+}
+
+impl Rule for CombinationalDepth {
+    fn name(&self) -> &'static str {
+        "combinational_depth"
+    }
+    fn help(&self) -> &'static str {
+        "This is the longest combinational path in the design.  Consider adding a pipeline stage along it"
+    }
+    fn check(&self, design: &Design) -> Vec<Violation> {
+        let netlist = design.netlist;
+        let Ok(Some(path)) = critical_path(netlist, design.mode, &UnitDelay) else {
+            return vec![];
+        };
+        if path.delay <= self.max_depth as f64 {
+            return vec![];
+        }
+        let locs = path
+            .ops
+            .iter()
+            .flat_map(|&ndx| netlist.ops[ndx].loc)
+            .collect::<Vec<_>>();
+        vec![Violation::new(
+            self,
+            netlist,
+            design.kernel_name(locs.first().copied()),
+            format!(
+                "combinational path is {} levels deep (the limit is {})",
+                path.delay, self.max_depth
+            ),
+            locs,
+        )]
+    }
+}
+
+/// A registry of design rules, with their severities and allow-lists.
+///
+/// The default registry contains all of the built in rules, with their
+/// default thresholds and severities.
+pub struct DesignRules {
+    rules: Vec<Box<dyn Rule>>,
+    severities: HashMap<&'static str, Severity>,
+    allowed: HashMap<String, HashSet<String>>,
+}
+
+impl Default for DesignRules {
+    fn default() -> Self {
+        Self::empty()
+            .with_rule(UnusedInputs)
+            .with_rule(ConstantOutputs)
+            .with_rule(RegistersNeverReset)
+            .with_rule(WideCase::default())
+            .with_rule(WideMultiplier::default())
+            .with_rule(CombinationalDepth::default())
+            .with_rule(UnsynchronizedCrossings)
+            .with_rule(MultiBitSynchronizers)
+            .with_rule(ReconvergentSynchronizers)
+    }
+}
+
+impl DesignRules {
+    /// A registry with no rules in it.
+    pub fn empty() -> Self {
+        Self {
+            rules: vec![],
+            severities: HashMap::default(),
+            allowed: HashMap::default(),
+        }
+    }
+    /// Add a rule to the registry.  Any existing rule with the
+    /// same name is replaced.
+    pub fn with_rule(mut self, rule: impl Rule + 'static) -> Self {
+        self.rules.retain(|r| r.name() != rule.name());
+        self.rules.push(Box::new(rule));
+        self
+    }
+    /// Remove the named rule from the registry.
+    pub fn without_rule(mut self, rule: &str) -> Self {
+        self.rules.retain(|r| r.name() != rule);
+        self
+    }
+    /// Override the severity of the named rule.
+    pub fn with_severity(mut self, rule: &str, severity: Severity) -> Self {
+        if let Some(rule) = self.rules.iter().find(|r| r.name() == rule) {
+            self.severities.insert(rule.name(), severity);
+        }
+        self
+    }
+    /// Allow violations of the named rule at the given location, which is the
+    /// scoped name of a circuit (like `top_counter`), or the name of a kernel.
+    pub fn allow(mut self, rule: &str, location: impl Into<String>) -> Self {
+        self.allowed
+            .entry(rule.into())
+            .or_default()
+            .insert(location.into());
+        self
+    }
+    fn check<K>(
+        &self,
+        mode: GraphMode,
+        descriptor: &Descriptor<K>,
+        descendants: &[Descriptor<K>],
+    ) -> Result<DesignRuleReport, RHDLError> {
+        let scopes = std::iter::once(descriptor)
+            .chain(descendants)
+            .map(|descriptor| {
+                Ok(Scope {
+                    name: descriptor.name.clone(),
+                    netlist: descriptor.netlist()?,
+                    kernel: descriptor.kernel.as_ref(),
+                    input_kind: descriptor.input_kind,
+                    output_kind: descriptor.output_kind,
+                })
+            })
+            .collect::<Result<Vec<_>, RHDLError>>()?;
+        let design = Design {
+            mode,
+            netlist: descriptor.netlist()?,
+            scopes,
+        };
+        let violations = self
+            .rules
+            .iter()
+            .flat_map(|rule| {
+                let allowed = self.allowed.get(rule.name());
+                let severity = self.severities.get(rule.name()).copied();
+                rule.check(&design)
+                    .into_iter()
+                    .filter(move |v| !allowed.is_some_and(|allowed| allowed.contains(&v.location)))
+                    .map(move |mut v| {
+                        v.severity = severity.unwrap_or(v.severity);
+                        v
+                    })
+            })
+            .collect();
+        Ok(DesignRuleReport { violations })
+    }
+    /// Check a synchronous circuit (and all of the circuits inside it) against the rules in the registry.
+    pub fn check_synchronous<T: Synchronous>(
+        &self,
+        uut: &T,
+    ) -> Result<DesignRuleReport, RHDLError> {
+        let top = ScopedName::top();
+        let descriptor = uut.descriptor(top.clone())?;
+        let descendants = uut.descendants(&top).collect::<Result<Vec<_>, _>>()?;
+        self.check(GraphMode::Synchronous, &descriptor, &descendants)
+    }
+    /// Check an asynchronous circuit (and all of the circuits inside it) against the rules in the registry.
+    pub fn check_circuit<T: Circuit>(&self, uut: &T) -> Result<DesignRuleReport, RHDLError> {
+        let top = ScopedName::top();
+        let descriptor = uut.descriptor(top.clone())?;
+        let descendants = uut.descendants(&top).collect::<Result<Vec<_>, _>>()?;
+        self.check(GraphMode::Asynchronous, &descriptor, &descendants)
+    }
+}
+
+/// The violations found by a [DesignRules] check.
+///
+/// The report is itself a diagnostic, with each of the violations as a
+/// related diagnostic.  It is an error if any of the violations are errors.
+#[derive(Debug, Error)]
+#[error("RHDL Design Rule Check found {} violations", .violations.len())]
+pub struct DesignRuleReport {
+    /// The violations found
+    pub violations: Vec<Violation>,
+}
+
+impl DesignRuleReport {
+    /// Returns true if any of the violations are errors.
+    pub fn has_errors(&self) -> bool {
+        self.violations
+            .iter()
+            .any(|v| v.severity == Severity::Error)
+    }
+    /// The violations of the named rule.
+    pub fn violations_of<'a>(&'a self, rule: &'a str) -> impl Iterator<Item = &'a Violation> {
+        self.violations.iter().filter(move |v| v.rule == rule)
+    }
+    /// Convert the report into an error if any of the violations are errors.
+    pub fn into_result(self) -> miette::Result<()> {
+        if self.has_errors() {
+            Err(miette::Report::new(self))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Diagnostic for DesignRuleReport {
+    fn severity(&self) -> Option<Severity> {
+        self.violations.iter().map(|v| v.severity).max()
+    }
+    fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item = &'a dyn Diagnostic> + 'a>> {
+        Some(Box::new(
+            self.violations.iter().map(|v| v as &dyn Diagnostic),
+        ))
+    }
+}
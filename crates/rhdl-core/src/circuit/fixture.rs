@@ -131,7 +131,8 @@ b   |   +--->|     +--+-> sum
 //! ```
 use super::circuit_impl::Circuit;
 use crate::{
-    CircuitIO, Digital, Kind, RHDLError,
+    CircuitIO, Digital, Kind, RHDLError, VHDLDescriptor,
+    hdl::vhdl,
     types::path::{Path, bit_range, leaf_paths},
 };
 use miette::Diagnostic;
@@ -198,6 +199,12 @@ pub enum ExportError {
         /// The kind of the signal
         kind: Kind,
     },
+    /// A driver used in a VHDL export does not provide VHDL
+    #[error("Driver with ports {ports:?} has no VHDL implementation")]
+    DriverVHDLNotAvailable {
+        /// The ports of the driver
+        ports: Vec<String>,
+    },
     /// The circuit cannot be exported as a fixture, due to some BSP specific issue.
     #[error("BSP Error {0}")]
     Custom(anyhow::Error),
@@ -236,6 +243,16 @@ pub enum MountPoint {
     Output(std::ops::Range<usize>),
 }
 
+impl MountPoint {
+    /// The slice of the circuit input or output for this mount point, as a VHDL expression.
+    pub fn vhdl(&self) -> String {
+        match self {
+            MountPoint::Input(range) => format!("inner_input({})", vhdl::range(range)),
+            MountPoint::Output(range) => format!("inner_output({})", vhdl::range(range)),
+        }
+    }
+}
+
 impl ToTokens for MountPoint {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         tokens.extend(match self {
@@ -295,10 +312,16 @@ pub struct Driver<T> {
     ///
     /// These will be the top level ports on the fixture.
     ports: Vec<vlog::Port>,
+    /// The ports for this driver, as VHDL port declarations.
+    vhdl_ports: Vec<String>,
     /// The HDL for this driver.
     ///
     /// This should be a fragment of Verilog that implements the driver.
     pub hdl: vlog::ItemList,
+    /// The VHDL for this driver, if it supports VHDL export.
+    ///
+    /// This should be a list of VHDL concurrent statements that implement the driver.
+    pub vhdl: Option<String>,
     /// The constraints for this driver.
     ///
     /// This should be whatever text needs to generated to supply constraints for this driver.
@@ -311,7 +334,9 @@ impl<T> Default for Driver<T> {
             marker: std::marker::PhantomData,
             mounts: Default::default(),
             ports: Default::default(),
+            vhdl_ports: Default::default(),
             hdl: vlog::ItemList::default(),
+            vhdl: None,
             constraints: Default::default(),
         }
     }
@@ -326,6 +351,8 @@ impl<T: CircuitIO> Driver<T> {
             vlog::Direction::Input,
             vlog::wire_decl(name, vlog::unsigned_width(width)),
         ));
+        self.vhdl_ports
+            .extend(vhdl::port(name, vhdl::Direction::In, width));
     }
     /// Add an output port to this driver.
     ///
@@ -335,6 +362,8 @@ impl<T: CircuitIO> Driver<T> {
             vlog::Direction::Output,
             vlog::wire_decl(name, vlog::unsigned_width(width)),
         ));
+        self.vhdl_ports
+            .extend(vhdl::port(name, vhdl::Direction::Out, width));
     }
     /// Add an inout port to this driver.
     ///
@@ -344,6 +373,8 @@ impl<T: CircuitIO> Driver<T> {
             vlog::Direction::Inout,
             vlog::wire_decl(name, vlog::unsigned_width(width)),
         ));
+        self.vhdl_ports
+            .extend(vhdl::port(name, vhdl::Direction::InOut, width));
     }
     /// Connect this driver's input to an inner input path on the circuit.
     pub fn write_to_inner_input(&mut self, path: &Path) -> Result<MountPoint, RHDLError> {
@@ -373,6 +404,7 @@ pub fn passthrough_output_driver<T: Circuit>(
     let mut driver = Driver::default();
     driver.output_port(name, bits.len());
     let output = driver.read_from_inner_output(path)?;
+    driver.vhdl = Some(format!("{name} <= {};", output.vhdl()));
     let name = format_ident!("{}", name);
     driver.hdl = parse_quote!(assign #name = #output;);
     Ok(driver)
@@ -390,6 +422,7 @@ pub fn passthrough_input_driver<T: Circuit>(
     let mut driver = Driver::default();
     driver.input_port(name, bits.len());
     let input = driver.write_to_inner_input(path)?;
+    driver.vhdl = Some(format!("{} <= {name};", input.vhdl()));
     let name = format_ident!("{}", name);
     driver.hdl = parse_quote!(assign #input = #name;);
     Ok(driver)
@@ -412,6 +445,11 @@ pub fn constant_driver<T: Circuit, S: Digital>(
     }
     let mut driver = Driver::<T>::default();
     let input = driver.write_to_inner_input(path)?;
+    driver.vhdl = Some(format!(
+        "{} <= {};",
+        input.vhdl(),
+        vhdl::vector_literal(&val.bin())
+    ));
     let lit: vlog::LitVerilog = val.typed_bits().into();
     driver.hdl = parse_quote!(assign #input = #lit;);
     Ok(driver)
@@ -459,7 +497,7 @@ pub struct Fixture<T> {
     circuit: T,
 }
 
-fn build_coverage_error(kind: Kind, coverage: &[bool]) -> String {
+pub(crate) fn build_coverage_error(kind: Kind, coverage: &[bool]) -> String {
     let paths = leaf_paths(&kind, Path::default());
     let mut details = String::new();
     for path in paths {
@@ -521,8 +559,7 @@ impl<T: Circuit> Fixture<T> {
     pub fn module(&self) -> Result<vlog::ModuleList, RHDLError> {
         let ports = self.drivers.iter().flat_map(|t| t.ports.iter());
         // Declare the mount points for the circuit
-        let i_kind = <<T as CircuitIO>::I as Digital>::static_kind();
-        let inputs_len = i_kind.bits();
+        let inputs_len = <<T as CircuitIO>::I as Digital>::static_kind().bits();
         let outputs_len = <<T as CircuitIO>::O as Digital>::static_kind().bits();
         let declarations = [
             vlog::maybe_decl_wire(inputs_len, "inner_input"),
@@ -530,7 +567,81 @@ impl<T: Circuit> Fixture<T> {
         ]
         .into_iter()
         .flatten();
-        let mut i_cover = vec![false; inputs_len];
+        self.check_input_coverage()?;
+        let driver_items = self.drivers.iter().flat_map(|x| &x.hdl.items);
+        // Instantiate the thing
+        let desc = self.circuit.descriptor("inner".into())?;
+        let hdl = desc.hdl()?;
+        let verilog = &hdl.modules;
+        let name_ident = format_ident!("{}", self.name);
+        let inner_ident = format_ident!("{}", hdl.name);
+        let module: vlog::ModuleList = parse_quote! {
+            module #name_ident (#(#ports),*);
+                #( #declarations ;)*
+                #( #driver_items ;)*
+                #inner_ident inner_inst (
+                    .i(inner_input),
+                    .o(inner_output)
+                );
+            endmodule
+            #verilog
+        };
+        Ok(module)
+    }
+    /// Generate the VHDL-2008 description of this fixture.
+    ///
+    /// The top level entity combines the VHDL from all the drivers, and
+    /// instantiates the entity generated for the circuit.  Every driver that
+    /// provides Verilog must also provide VHDL.
+    pub fn vhdl(&self) -> Result<VHDLDescriptor, RHDLError> {
+        let ports = self
+            .drivers
+            .iter()
+            .flat_map(|t| t.vhdl_ports.iter().cloned())
+            .collect::<Vec<_>>();
+        let inputs_len = <<T as CircuitIO>::I as Digital>::static_kind().bits();
+        let outputs_len = <<T as CircuitIO>::O as Digital>::static_kind().bits();
+        self.check_input_coverage()?;
+        let mut declarations = vec![];
+        let mut connections = vec![];
+        if inputs_len != 0 {
+            declarations.push(format!("signal inner_input : {};", vhdl::slv(inputs_len)));
+            connections.push("i => inner_input");
+        }
+        if outputs_len != 0 {
+            declarations.push(format!("signal inner_output : {};", vhdl::slv(outputs_len)));
+            connections.push("o => inner_output");
+        }
+        let mut body = vec![];
+        for driver in &self.drivers {
+            match &driver.vhdl {
+                Some(vhdl) => body.push(vhdl.clone()),
+                None if driver.hdl.items.is_empty() => {}
+                None => {
+                    return Err(ExportError::DriverVHDLNotAvailable {
+                        ports: driver.vhdl_ports.clone(),
+                    }
+                    .into());
+                }
+            }
+        }
+        // Instantiate the thing
+        let desc = self.circuit.descriptor("inner".into())?;
+        let inner = desc.vhdl()?;
+        body.push(format!(
+            "inner_inst : entity work.{} port map (\n    {}\n);",
+            inner.name,
+            connections.join(",\n    ")
+        ));
+        let text = vhdl::entity(&self.name, &ports, &declarations, &body);
+        let mut top = VHDLDescriptor::new(&self.name, text);
+        top.extend(&inner);
+        Ok(top)
+    }
+    // Check that every bit of the circuit input is driven by exactly one driver
+    fn check_input_coverage(&self) -> Result<(), RHDLError> {
+        let i_kind = <<T as CircuitIO>::I as Digital>::static_kind();
+        let mut i_cover = vec![false; i_kind.bits()];
         self.drivers
             .iter()
             .flat_map(|x| x.mounts.iter())
@@ -551,25 +662,7 @@ impl<T: Circuit> Fixture<T> {
             let coverage = build_coverage_error(i_kind, &i_cover);
             return Err(ExportError::InputsNotCovered(coverage).into());
         }
-        let driver_items = self.drivers.iter().flat_map(|x| &x.hdl.items);
-        // Instantiate the thing
-        let desc = self.circuit.descriptor("inner".into())?;
-        let hdl = desc.hdl()?;
-        let verilog = &hdl.modules;
-        let name_ident = format_ident!("{}", self.name);
-        let inner_ident = format_ident!("{}", hdl.name);
-        let module: vlog::ModuleList = parse_quote! {
-            module #name_ident (#(#ports),*);
-                #( #declarations ;)*
-                #( #driver_items ;)*
-                #inner_ident inner_inst (
-                    .i(inner_input),
-                    .o(inner_output)
-                );
-            endmodule
-            #verilog
-        };
-        Ok(module)
+        Ok(())
     }
     /// Generate the constraints for this fixture.
     ///
@@ -0,0 +1,515 @@
+//! Wrap cores written in Verilog as RHDL circuits
+//!
+//! Sometimes the logic you need already exists as a Verilog module, be it
+//! a vendor primitive, a piece of legacy IP, or a core that was generated
+//! by some other tool.  The [ForeignBuilder] takes the definition of such a
+//! module (as a [vlog::ModuleDef]) and a map from its ports to fields of the
+//! input and output types of an RHDL circuit, and produces either a
+//! [ForeignCircuit] (which implements [Circuit]) or a [ForeignSynchronous]
+//! (which implements [Synchronous]).
+//!
+//! The resulting circuit is a black box as far as RHDL is concerned.  Its
+//! HDL consists of a thin wrapper module with the usual RHDL ports (`i`, `o`
+//! and, for synchronous circuits, `clock_reset`) that instantiates the
+//! foreign module.  The foreign module is renamed to `<scope>_<name>` so that
+//! multiple instances of the same core do not collide.
+//!
+//! The width of each port is checked against the [Kind] of the field it is
+//! mapped to, every input port of the module must be driven, and every bit of
+//! the output type must be driven by exactly one output port.
+//!
+//! Because RHDL cannot simulate Verilog, you can optionally attach a behavioral
+//! model in Rust, which will be used by `sim`.  Without a model, the output
+//! of the circuit is [Digital::dont_care].  Alternately, a synchronous core can
+//! be simulated by the Verilog itself by wrapping it in a [Cosim](crate::sim::cosim::Cosim).
+//!
+//! For example, to wrap a Verilog adder as an asynchronous circuit:
+//!
+//! ```rust,ignore
+//! let module: vlog::ModuleDef = syn::parse_quote! {
+//!     module adder(input wire [3:0] a, input wire [3:0] b, output wire [3:0] y);
+//!         assign y = a + b;
+//!     endmodule
+//! };
+//! let i = <Signal<(b4, b4), Red>>::dont_care();
+//! let o = <Signal<b4, Red>>::dont_care();
+//! let adder = ForeignBuilder::<Signal<(b4, b4), Red>, Signal<b4, Red>>::new(module)
+//!     .input("a", path!(i.val().0))
+//!     .input("b", path!(i.val().1))
+//!     .output("y", path!(o.val()))
+//!     .circuit()?
+//!     .with_model((), |i, _| signal(i.val().0 + i.val().1));
+//! ```
+use std::rc::Rc;
+
+use crate::{
+    Circuit, CircuitDQ, CircuitIO, ClockReset, Digital, Kind, RHDLError, Synchronous,
+    SynchronousDQ, SynchronousIO, Timed,
+    circuit::{
+        descriptor::{AsyncKind, Descriptor, SyncKind},
+        fixture::build_coverage_error,
+        scoped_name::ScopedName,
+    },
+    types::{
+        digital_fn::{NoCircuitKernel, NoSynchronousKernel},
+        path::{Path, bit_range},
+    },
+};
+use miette::Diagnostic;
+use quote::format_ident;
+use rhdl_vlog as vlog;
+use thiserror::Error;
+
+/// Errors that can occur when wrapping a foreign module
+#[derive(Error, Debug, Diagnostic)]
+pub enum ForeignError {
+    /// The port map refers to a port that the module does not have
+    #[error("Module {module} has no port named {port}")]
+    UnknownPort {
+        /// The name of the module
+        module: String,
+        /// The name of the port
+        port: String,
+    },
+    /// A port was bound more than once
+    #[error("Port {port} is bound more than once")]
+    DuplicateBinding {
+        /// The name of the port
+        port: String,
+    },
+    /// A port was bound in the wrong direction
+    #[error("Port {port} is not an {expected} port")]
+    DirectionMismatch {
+        /// The name of the port
+        port: String,
+        /// The direction the binding requires
+        expected: &'static str,
+    },
+    /// The width of a port does not match the field it is bound to
+    #[error("Port {port} is {port_width} bits wide, but {path:?} is {field_width} bits wide")]
+    WidthMismatch {
+        /// The name of the port
+        port: String,
+        /// The width of the port
+        port_width: usize,
+        /// The path of the field
+        path: Path,
+        /// The width of the field
+        field_width: usize,
+    },
+    /// An input port of the module is not driven
+    #[error("Input port {port} is not bound")]
+    UnboundInput {
+        /// The name of the port
+        port: String,
+    },
+    /// Bidirectional ports cannot be wrapped
+    #[error("Port {port} is an inout port, which is not supported")]
+    InoutPort {
+        /// The name of the port
+        port: String,
+    },
+    /// Some bits of the output are not driven by the module
+    #[error("Outputs are not covered by the foreign module:\n{0}")]
+    OutputsNotCovered(String),
+    /// Some bits of the output are driven by more than one port
+    #[error("Output bits {0:?} are driven by more than one port")]
+    OutputsMultiplyDriven(std::ops::Range<usize>),
+    /// Clock and reset bindings require a synchronous circuit
+    #[error("Port {port} is bound to the clock or reset, which requires a synchronous circuit")]
+    ClockResetNotAvailable {
+        /// The name of the port
+        port: String,
+    },
+}
+
+#[derive(Clone, Debug)]
+enum Binding {
+    Input(Path),
+    Output(Path),
+    Clock,
+    Reset,
+}
+
+// A validated connection between a port of the foreign module and
+// a range of bits in one of the wrapper's ports.
+#[derive(Clone, Debug)]
+struct Connection {
+    port: String,
+    source: &'static str,
+    bits: std::ops::Range<usize>,
+}
+
+/// Builder for wrapping a Verilog module as an RHDL circuit
+///
+/// The type parameters `I` and `O` are the input and output types of
+/// the resulting circuit.  Ports of the module are mapped to fields of
+/// these types using [Path]s, typically created with the `path!` macro.
+#[derive(Clone)]
+pub struct ForeignBuilder<I, O> {
+    module: vlog::ModuleDef,
+    bindings: Vec<(String, Binding)>,
+    marker: std::marker::PhantomData<(I, O)>,
+}
+
+impl<I: Digital, O: Digital> ForeignBuilder<I, O> {
+    /// Start wrapping the given module.
+    pub fn new(module: vlog::ModuleDef) -> Self {
+        Self {
+            module,
+            bindings: vec![],
+            marker: std::marker::PhantomData,
+        }
+    }
+    /// Drive the input port `port` of the module from the field of the circuit input at `path`.
+    pub fn input(mut self, port: &str, path: Path) -> Self {
+        self.bindings.push((port.into(), Binding::Input(path)));
+        self
+    }
+    /// Drive the field of the circuit output at `path` from the output port `port` of the module.
+    pub fn output(mut self, port: &str, path: Path) -> Self {
+        self.bindings.push((port.into(), Binding::Output(path)));
+        self
+    }
+    /// Drive the input port `port` of the module from the clock.  Synchronous circuits only.
+    pub fn clock(mut self, port: &str) -> Self {
+        self.bindings.push((port.into(), Binding::Clock));
+        self
+    }
+    /// Drive the input port `port` of the module from the reset.  Synchronous circuits only.
+    pub fn reset(mut self, port: &str) -> Self {
+        self.bindings.push((port.into(), Binding::Reset));
+        self
+    }
+    fn validate(&self, synchronous: bool) -> Result<Vec<Connection>, RHDLError> {
+        let module = &self.module;
+        let i_kind = I::static_kind();
+        let o_kind = O::static_kind();
+        let cr_kind = ClockReset::static_kind();
+        let mut connections: Vec<Connection> = vec![];
+        for (name, binding) in &self.bindings {
+            let Some(port) = module.args.iter().find(|p| &p.decl.name == name) else {
+                return Err(ForeignError::UnknownPort {
+                    module: module.name.clone(),
+                    port: name.clone(),
+                }
+                .into());
+            };
+            if connections.iter().any(|c| &c.port == name) {
+                return Err(ForeignError::DuplicateBinding { port: name.clone() }.into());
+            }
+            let (source, kind, path, expected) = match binding {
+                Binding::Input(path) => ("i", i_kind, path.clone(), "input"),
+                Binding::Output(path) => ("o", o_kind, path.clone(), "output"),
+                Binding::Clock | Binding::Reset if !synchronous => {
+                    return Err(ForeignError::ClockResetNotAvailable { port: name.clone() }.into());
+                }
+                Binding::Clock => (
+                    "clock_reset",
+                    cr_kind,
+                    Path::default().field("clock"),
+                    "input",
+                ),
+                Binding::Reset => (
+                    "clock_reset",
+                    cr_kind,
+                    Path::default().field("reset"),
+                    "input",
+                ),
+            };
+            let direction_ok = match port.direction {
+                vlog::Direction::Input => expected == "input",
+                vlog::Direction::Output => expected == "output",
+                vlog::Direction::Inout => {
+                    return Err(ForeignError::InoutPort { port: name.clone() }.into());
+                }
+            };
+            if !direction_ok {
+                return Err(ForeignError::DirectionMismatch {
+                    port: name.clone(),
+                    expected,
+                }
+                .into());
+            }
+            let (bits, _) = bit_range(kind, &path)?;
+            if bits.len() != port.width() {
+                return Err(ForeignError::WidthMismatch {
+                    port: name.clone(),
+                    port_width: port.width(),
+                    path,
+                    field_width: bits.len(),
+                }
+                .into());
+            }
+            connections.push(Connection {
+                port: name.clone(),
+                source,
+                bits,
+            });
+        }
+        for port in &module.args {
+            match port.direction {
+                vlog::Direction::Inout => {
+                    return Err(ForeignError::InoutPort {
+                        port: port.decl.name.clone(),
+                    }
+                    .into());
+                }
+                vlog::Direction::Input if !connections.iter().any(|c| c.port == port.decl.name) => {
+                    return Err(ForeignError::UnboundInput {
+                        port: port.decl.name.clone(),
+                    }
+                    .into());
+                }
+                _ => {}
+            }
+        }
+        let mut o_cover = vec![false; o_kind.bits()];
+        for connection in connections.iter().filter(|c| c.source == "o") {
+            if connection.bits.clone().any(|bit| o_cover[bit]) {
+                return Err(ForeignError::OutputsMultiplyDriven(connection.bits.clone()).into());
+            }
+            o_cover[connection.bits.clone()].fill(true);
+        }
+        if o_cover.iter().any(|b| !b) {
+            let coverage = build_coverage_error(o_kind, &o_cover);
+            return Err(ForeignError::OutputsNotCovered(coverage).into());
+        }
+        Ok(connections)
+    }
+    /// Build an asynchronous [Circuit] from the module.  The module may not
+    /// bind the clock or reset, since a [Circuit] has none.
+    pub fn circuit(self) -> Result<ForeignCircuit<I, O>, RHDLError>
+    where
+        I: Timed,
+        O: Timed,
+    {
+        let connections = self.validate(false)?;
+        Ok(ForeignCircuit {
+            module: self.module,
+            connections,
+            init: (),
+            model: None,
+        })
+    }
+    /// Build a [Synchronous] circuit from the module.
+    pub fn synchronous(self) -> Result<ForeignSynchronous<I, O>, RHDLError> {
+        let connections = self.validate(true)?;
+        Ok(ForeignSynchronous {
+            module: self.module,
+            connections,
+            init: (),
+            model: None,
+        })
+    }
+}
+
+// Build the wrapper module that instantiates the foreign module, followed by
+// the (renamed) foreign module itself.
+fn build_hdl(
+    name: &str,
+    module: &vlog::ModuleDef,
+    connections: &[Connection],
+    ports: &[Option<vlog::Port>],
+) -> Result<crate::HDLDescriptor, RHDLError> {
+    let mut inner = module.clone();
+    inner.name = format!("{name}_{}", module.name);
+    let inner_name = format_ident!("{}", inner.name);
+    let module_name = format_ident!("{name}");
+    let ports = ports.iter().flatten();
+    let bindings = connections
+        .iter()
+        .flat_map(|c| vlog::maybe_connect(&c.port, c.source, c.bits.clone()));
+    let modules: vlog::ModuleList = vlog::parse_quote_miette! {
+        module #module_name(#(#ports),*);
+            #inner_name inner(
+                #(#bindings),*
+            );
+        endmodule
+        #inner
+    }?;
+    Ok(crate::HDLDescriptor {
+        name: name.into(),
+        modules,
+    })
+}
+
+type AsyncModel<I, O, S> = Rc<dyn Fn(I, &mut S) -> O>;
+
+/// A Verilog module wrapped as a [Circuit]
+///
+/// Created with [ForeignBuilder::circuit].  The type parameter `S` is the
+/// state of the behavioral model, if any.
+pub struct ForeignCircuit<I, O, S = ()> {
+    module: vlog::ModuleDef,
+    connections: Vec<Connection>,
+    init: S,
+    model: Option<AsyncModel<I, O, S>>,
+}
+
+impl<I, O, S: Clone> Clone for ForeignCircuit<I, O, S> {
+    fn clone(&self) -> Self {
+        Self {
+            module: self.module.clone(),
+            connections: self.connections.clone(),
+            init: self.init.clone(),
+            model: self.model.clone(),
+        }
+    }
+}
+
+impl<I: Timed, O: Timed, S> ForeignCircuit<I, O, S> {
+    /// Attach a behavioral model, used when simulating the circuit.  The model
+    /// is called with the input and the model state (which starts at `init`),
+    /// and should return the output of the module.
+    pub fn with_model<T: Clone + PartialEq + 'static>(
+        self,
+        init: T,
+        model: impl Fn(I, &mut T) -> O + 'static,
+    ) -> ForeignCircuit<I, O, T> {
+        ForeignCircuit {
+            module: self.module,
+            connections: self.connections,
+            init,
+            model: Some(Rc::new(model)),
+        }
+    }
+}
+
+impl<I: Timed, O: Timed, S: 'static> CircuitDQ for ForeignCircuit<I, O, S> {
+    type D = ();
+    type Q = ();
+}
+
+impl<I: Timed, O: Timed, S: 'static> CircuitIO for ForeignCircuit<I, O, S> {
+    type I = I;
+    type O = O;
+    type Kernel = NoCircuitKernel<I, (), (O, ())>;
+}
+
+impl<I: Timed, O: Timed, S: Clone + PartialEq + 'static> Circuit for ForeignCircuit<I, O, S> {
+    type S = S;
+
+    fn init(&self) -> Self::S {
+        self.init.clone()
+    }
+
+    fn sim(&self, input: Self::I, state: &mut Self::S) -> Self::O {
+        match &self.model {
+            Some(model) => model(input, state),
+            None => O::dont_care(),
+        }
+    }
+
+    fn descriptor(&self, scoped_name: ScopedName) -> Result<Descriptor<AsyncKind>, RHDLError> {
+        let name = scoped_name.to_string();
+        let ports = [
+            vlog::maybe_port_wire(vlog::Direction::Input, I::bits(), "i"),
+            vlog::maybe_port_wire(vlog::Direction::Output, O::bits(), "o"),
+        ];
+        Descriptor::<AsyncKind> {
+            name: scoped_name,
+            input_kind: I::static_kind(),
+            output_kind: O::static_kind(),
+            d_kind: Kind::Empty,
+            q_kind: Kind::Empty,
+            kernel: None,
+            hdl: Some(build_hdl(&name, &self.module, &self.connections, &ports)?),
+            netlist: None,
+            _phantom: std::marker::PhantomData,
+        }
+        .with_netlist_black_box()
+    }
+}
+
+type SyncModel<I, O, S> = Rc<dyn Fn(ClockReset, I, &mut S) -> O>;
+
+/// A Verilog module wrapped as a [Synchronous] circuit
+///
+/// Created with [ForeignBuilder::synchronous].  The type parameter `S` is the
+/// state of the behavioral model, if any.
+pub struct ForeignSynchronous<I, O, S = ()> {
+    module: vlog::ModuleDef,
+    connections: Vec<Connection>,
+    init: S,
+    model: Option<SyncModel<I, O, S>>,
+}
+
+impl<I, O, S: Clone> Clone for ForeignSynchronous<I, O, S> {
+    fn clone(&self) -> Self {
+        Self {
+            module: self.module.clone(),
+            connections: self.connections.clone(),
+            init: self.init.clone(),
+            model: self.model.clone(),
+        }
+    }
+}
+
+impl<I: Digital, O: Digital, S> ForeignSynchronous<I, O, S> {
+    /// Attach a behavioral model, used when simulating the circuit.  The model
+    /// is called with the clock and reset, the input and the model state (which
+    /// starts at `init`), and should return the output of the module.  Like any
+    /// other [Synchronous] circuit, it is called on both edges of the clock.
+    pub fn with_model<T: Clone + PartialEq + 'static>(
+        self,
+        init: T,
+        model: impl Fn(ClockReset, I, &mut T) -> O + 'static,
+    ) -> ForeignSynchronous<I, O, T> {
+        ForeignSynchronous {
+            module: self.module,
+            connections: self.connections,
+            init,
+            model: Some(Rc::new(model)),
+        }
+    }
+}
+
+impl<I: Digital, O: Digital, S: 'static> SynchronousDQ for ForeignSynchronous<I, O, S> {
+    type D = ();
+    type Q = ();
+}
+
+impl<I: Digital, O: Digital, S: 'static> SynchronousIO for ForeignSynchronous<I, O, S> {
+    type I = I;
+    type O = O;
+    type Kernel = NoSynchronousKernel<ClockReset, I, (), (O, ())>;
+}
+
+impl<I: Digital, O: Digital, S: Clone + PartialEq + 'static> Synchronous
+    for ForeignSynchronous<I, O, S>
+{
+    type S = S;
+
+    fn init(&self) -> Self::S {
+        self.init.clone()
+    }
+
+    fn sim(&self, clock_reset: ClockReset, input: Self::I, state: &mut Self::S) -> Self::O {
+        match &self.model {
+            Some(model) => model(clock_reset, input, state),
+            None => O::dont_care(),
+        }
+    }
+
+    fn descriptor(&self, scoped_name: ScopedName) -> Result<Descriptor<SyncKind>, RHDLError> {
+        let name = scoped_name.to_string();
+        let ports = [
+            vlog::maybe_port_wire(vlog::Direction::Input, 2, "clock_reset"),
+            vlog::maybe_port_wire(vlog::Direction::Input, I::bits(), "i"),
+            vlog::maybe_port_wire(vlog::Direction::Output, O::bits(), "o"),
+        ];
+        Descriptor::<SyncKind> {
+            name: scoped_name,
+            input_kind: I::static_kind(),
+            output_kind: O::static_kind(),
+            d_kind: Kind::Empty,
+            q_kind: Kind::Empty,
+            kernel: None,
+            hdl: Some(build_hdl(&name, &self.module, &self.connections, &ports)?),
+            netlist: None,
+            _phantom: std::marker::PhantomData,
+        }
+        .with_netlist_black_box()
+    }
+}
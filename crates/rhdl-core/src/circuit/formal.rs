@@ -0,0 +1,213 @@
+//! Formal verification of circuits and kernels
+//!
+//! These functions build on the SMT-LIB2 export in [crate::ntl::smt] to
+//! check properties of [Synchronous] circuits and kernels with an external
+//! solver.  When a check fails, the counterexample is returned in terms of
+//! the inputs of the design, so it can be replayed in simulation or turned
+//! into a Verilog testbench.
+//!
+//! Every black box in the design must carry a formal model (see
+//! [Descriptor::with_black_box_smt](crate::Descriptor::with_black_box_smt)).
+use crate::{
+    BitX, ClockReset, CompilationMode, Digital, DigitalFn, KernelFnKind, Kind, RHDLError,
+    Synchronous, SynchronousIO, TypedBits,
+    ast::ast_impl::PatKind,
+    clock::clock,
+    clock_reset, compile_design,
+    compiler::optimize_ntl,
+    ntl::{
+        from_rtl::build_ntl_from_rtl,
+        smt::{self, Bmc, Solver, Trace},
+    },
+    sim::{run::synchronous::RunSynchronousExt, testbench::synchronous::SynchronousTestBench},
+    timed_sample,
+    types::{
+        path::{Path, bit_range},
+        reset::reset,
+    },
+};
+
+/// A sequence of inputs for a [Synchronous] circuit that causes a check to fail.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Counterexample {
+    /// The first cycle in which the check fails.
+    pub cycle: usize,
+    /// The value of the reset in each cycle.
+    pub reset: Vec<bool>,
+    /// The input bits in each cycle.
+    pub inputs: Vec<TypedBits>,
+}
+
+impl Counterexample {
+    fn new<T: SynchronousIO>(trace: Trace) -> Result<Self, RHDLError> {
+        let (reset_bit, _) = bit_range(ClockReset::static_kind(), &Path::default().field("reset"))?;
+        // Bits the solver left unconstrained can take any value
+        let known = |bits: &[BitX]| {
+            bits.iter()
+                .map(|&b| if b == BitX::X { BitX::Zero } else { b })
+                .collect::<Vec<_>>()
+        };
+        Ok(Self {
+            cycle: trace.cycle,
+            reset: trace
+                .inputs
+                .iter()
+                .map(|args| args[0][reset_bit.start] == BitX::One)
+                .collect(),
+            inputs: trace
+                .inputs
+                .iter()
+                .map(|args| TypedBits::new(known(&args[1]), T::I::static_kind()))
+                .collect(),
+        })
+    }
+    /// Replay the counterexample on the given circuit to get a testbench.
+    ///
+    /// An RHDL value cannot in general be reconstructed from its bits, so
+    /// `decode` is used to convert the input bits of each cycle into an input.
+    /// Each cycle of the counterexample takes one clock period of 100 time
+    /// units, with the clock rising at the half way point.
+    pub fn test_bench<T>(
+        &self,
+        uut: &T,
+        decode: impl Fn(&TypedBits) -> T::I,
+    ) -> SynchronousTestBench<T::I, T::O>
+    where
+        T: Synchronous,
+    {
+        let samples = self
+            .reset
+            .iter()
+            .zip(&self.inputs)
+            .enumerate()
+            .flat_map(|(cycle, (&rst, input))| {
+                let time = cycle as u64 * 100;
+                let input = decode(input);
+                [
+                    timed_sample(time, (clock_reset(clock(false), reset(rst)), input)),
+                    timed_sample(time + 50, (clock_reset(clock(true), reset(rst)), input)),
+                ]
+            })
+            .collect::<Vec<_>>();
+        uut.run(samples).collect()
+    }
+}
+
+/// Check that two [Synchronous] circuits produce the same outputs for the
+/// first `depth` clock cycles, for any sequence of inputs and resets.
+///
+/// Returns `None` if the circuits agree, and a [Counterexample] if not.
+pub fn check_synchronous_equivalence<A, B>(
+    a: &A,
+    b: &B,
+    depth: usize,
+    solver: &Solver,
+) -> Result<Option<Counterexample>, RHDLError>
+where
+    A: Synchronous,
+    B: Synchronous<I = A::I, O = A::O>,
+{
+    let desc_a = a.descriptor("a".into())?;
+    let desc_b = b.descriptor("b".into())?;
+    let bmc = Bmc::synchronous(A::I::BITS, depth)?;
+    smt::check_equivalence(bmc, desc_a.netlist()?, desc_b.netlist()?, solver)?
+        .map(Counterexample::new::<A>)
+        .transpose()
+}
+
+/// Check that a property of the outputs of a [Synchronous] circuit holds for
+/// the first `depth` clock cycles, for any sequence of inputs and resets.
+///
+/// The property is given the SMT-LIB2 terms for the bits of the output in a
+/// cycle, and returns a term that must be true.  For example, to check that
+/// a single bit output is never set, use `|o| format!("(not {})", o[0])`.
+/// Use [bit_range] to find the bits of a particular field of the output.
+pub fn check_synchronous_property<T>(
+    uut: &T,
+    depth: usize,
+    solver: &Solver,
+    property: impl Fn(&[String]) -> String,
+) -> Result<Option<Counterexample>, RHDLError>
+where
+    T: Synchronous,
+{
+    let desc = uut.descriptor("uut".into())?;
+    let bmc = Bmc::synchronous(T::I::BITS, depth)?;
+    smt::check_property(bmc, desc.netlist()?, solver, property)?
+        .map(Counterexample::new::<T>)
+        .transpose()
+}
+
+// The argument and return kinds of a kernel, as declared in its signature
+fn signature<K: DigitalFn>() -> Result<(Vec<Kind>, Kind), RHDLError> {
+    let Some(KernelFnKind::AstKernel(kernel)) = K::kernel_fn() else {
+        return Err(RHDLError::KernelSignatureError(format!(
+            "{} is not a kernel function",
+            std::any::type_name::<K>()
+        )));
+    };
+    let args = kernel
+        .inputs
+        .iter()
+        .map(|pat| match &pat.kind {
+            PatKind::Type(ty) => Ok(ty.kind),
+            _ => Err(RHDLError::KernelSignatureError(format!(
+                "Kernel {} has an argument without a type",
+                kernel.name
+            ))),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((args, kernel.ret))
+}
+
+/// Check that two kernels compute the same function.
+///
+/// This is useful to prove that a refactored kernel matches the previous
+/// version.  Both kernels are compiled in the given mode, and must take
+/// the same arguments and return the same kind.  Returns `None` if the
+/// kernels are equivalent, and the arguments for which they differ if not.
+///
+/// Each `X` literal in a kernel is a single unknown value, however often it
+/// is read.  The unknown values of the two kernels are independent, so any
+/// output bits that depend on them (like the payload of a `None`) must agree
+/// for every choice of those values.
+pub fn check_kernel_equivalence<K1: DigitalFn, K2: DigitalFn>(
+    mode: CompilationMode,
+    solver: &Solver,
+) -> Result<Option<Vec<TypedBits>>, RHDLError> {
+    let (kinds, ret_a) = signature::<K1>()?;
+    let (kinds_b, ret_b) = signature::<K2>()?;
+    if kinds.len() != kinds_b.len() {
+        return Err(RHDLError::KernelSignatureError(
+            "Kernels take a different number of arguments".into(),
+        ));
+    }
+    if let Some((ndx, (a, b))) = kinds
+        .iter()
+        .zip(&kinds_b)
+        .enumerate()
+        .find(|(_, (a, b))| a != b)
+    {
+        return Err(RHDLError::KernelSignatureError(format!(
+            "Kernels differ in the kind of argument {ndx}: {a:?} vs {b:?}"
+        )));
+    }
+    if ret_a != ret_b {
+        return Err(RHDLError::KernelSignatureError(format!(
+            "Kernels differ in their return kind: {ret_a:?} vs {ret_b:?}"
+        )));
+    }
+    let rtl_a = compile_design::<K1>(mode)?;
+    let rtl_b = compile_design::<K2>(mode)?;
+    let widths = kinds.iter().map(|kind| kind.bits()).collect::<Vec<_>>();
+    let ntl_a = optimize_ntl(build_ntl_from_rtl(&rtl_a))?;
+    let ntl_b = optimize_ntl(build_ntl_from_rtl(&rtl_b))?;
+    let trace = smt::check_equivalence(Bmc::new(&widths, 1), &ntl_a, &ntl_b, solver)?;
+    Ok(trace.map(|trace| {
+        trace.inputs[0]
+            .iter()
+            .zip(&kinds)
+            .map(|(bits, &kind)| TypedBits::new(bits.clone(), kind))
+            .collect()
+    }))
+}
@@ -1,3 +1,4 @@
 //! Wrappers for functions as circuits.
 pub mod asynchronous;
+pub mod pipelined;
 pub mod synchronous;
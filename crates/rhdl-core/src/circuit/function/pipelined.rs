@@ -0,0 +1,209 @@
+//! Utility wrapper to convert a pure function into a pipelined synchronous [Circuit](crate::Circuit).
+//!
+//! This module provides the [Pipelined] struct, which wraps a pure function that is synthesizable
+//! (marked with `#[digital]`) into a synchronous circuit, like [Func](super::synchronous::Func) does.
+//! The difference is that the logic of the function is split into `N` stages, separated by
+//! registers, so that a long combinational kernel can run at a higher clock rate.  The output
+//! is the result of the function applied to the input (and reset) from `N` clock cycles earlier.
+#![doc = badascii_doc::badascii!(r"
+      ++Pipelined+-----------------+       
+      |                            |       
+      |   +----------+   +-----+   |       
+  I   +   | Func     |   | N   |   +  O    
++-------->| fn(I)->O +-->| reg +---------->
+      +   +----------+   +-----+   +       
+      |                     ^      |       
+ cr   +--------------------+|+-----+       
++---------------------------+              
+")]
+//!
+//! The stages are balanced using a [DelayModel], which defaults to [WidthDelay].
+//! See [crate::ntl::pipeline] for details of how the netlist is split.
+//!
+//! The pipeline registers are not reset, so the outputs of the circuit for the first `N`
+//! clock cycles are not meaningful.  Inside a [ClockEnable](crate::circuit::clock_enable::ClockEnable),
+//! the pipeline only advances on the enabled clock edges.  When checking a [Pipelined] circuit with a
+//! [SynchronousTestBench](crate::sim::testbench::synchronous::SynchronousTestBench),
+//! skip the test cases in those cycles.
+use std::sync::Arc;
+
+use quote::format_ident;
+use syn::parse_quote;
+
+use crate::{
+    ClockReset, CompilationMode, Digital, DigitalFn, HDLDescriptor, Kind, RHDLError, Reset,
+    Synchronous, SynchronousDQ, SynchronousIO,
+    circuit::{
+        clock_enable::clock_enable,
+        descriptor::{Descriptor, SyncKind},
+        reset_style::build_kernel_netlist,
+        scoped_name::ScopedName,
+    },
+    compile_design,
+    compiler::optimize_ntl,
+    digital_fn::{DigitalFn2, NoSynchronousKernel},
+    ntl::{
+        hdl::build_hdl,
+        pipeline::pipeline,
+        timing::{DelayModel, WidthDelay},
+    },
+    rtl::Object,
+    trace, trace_pop_path, trace_push_path,
+};
+
+use rhdl_vlog::{self as vlog, maybe_port_wire};
+
+/// Wrap a pure (synthesizable) function into a synchronous [Circuit](crate::Circuit)
+/// that is pipelined into `N` stages.
+///
+/// The function signature must be `fn(ClockReset, I) -> O`,
+/// and must be marked with `#[digital]`.
+#[derive(Clone)]
+pub struct Pipelined<I: Digital, O: Digital, const N: usize> {
+    kernel: Object,
+    update: fn(ClockReset, I) -> O,
+    model: Arc<dyn DelayModel + Send + Sync>,
+}
+
+impl<I: Digital, O: Digital, const N: usize> SynchronousIO for Pipelined<I, O, N> {
+    type I = I;
+    type O = O;
+    type Kernel = NoSynchronousKernel<ClockReset, I, (), (O, ())>;
+}
+
+impl<I: Digital, O: Digital, const N: usize> SynchronousDQ for Pipelined<I, O, N> {
+    type D = ();
+    type Q = ();
+}
+
+impl<I: Digital, O: Digital, const N: usize> Pipelined<I, O, N> {
+    /// Create a new [Pipelined] circuit wrapping the given pure function type `T`.
+    /// The function type `T` must be marked with `#[digital]`
+    /// and must accept a `ClockReset` and an `I` argument and return an `O`.
+    pub fn try_new<T>() -> Result<Self, RHDLError>
+    where
+        T: DigitalFn,
+        T: DigitalFn2<A0 = ClockReset, A1 = I, O = O>,
+    {
+        let kernel = compile_design::<T>(CompilationMode::Synchronous)?;
+        let update = T::func();
+        Ok(Self {
+            kernel,
+            update,
+            model: Arc::new(WidthDelay),
+        })
+    }
+    /// Use the given delay model to balance the stages of the pipeline.
+    pub fn with_delay_model(self, model: impl DelayModel + Send + Sync + 'static) -> Self {
+        Self {
+            model: Arc::new(model),
+            ..self
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[doc(hidden)]
+pub struct S<I: Digital, const N: usize> {
+    clock: bool,
+    enable: bool,
+    next: (Reset, I),
+    stages: [(Reset, I); N],
+}
+
+impl<I: Digital, O: Digital, const N: usize> Synchronous for Pipelined<I, O, N> {
+    type S = S<I, N>;
+
+    fn init(&self) -> Self::S {
+        S {
+            clock: false,
+            enable: false,
+            next: (Reset::dont_care(), I::dont_care()),
+            stages: [(Reset::dont_care(), I::dont_care()); N],
+        }
+    }
+
+    fn sim(&self, clock_reset: ClockReset, input: Self::I, state: &mut Self::S) -> Self::O {
+        trace_push_path("pipelined");
+        trace("input", &input);
+        let clock = clock_reset.clock.raw();
+        if !clock {
+            state.next = (clock_reset.reset, input);
+            state.enable = clock_enable().unwrap_or(true);
+        }
+        if clock && !state.clock && state.enable && N > 0 {
+            state.stages.rotate_right(1);
+            state.stages[0] = state.next;
+        }
+        state.clock = clock;
+        // The clock is not delayed, but the reset travels with the input
+        let (reset, input) = state
+            .stages
+            .last()
+            .copied()
+            .unwrap_or((clock_reset.reset, input));
+        let clock_reset = ClockReset {
+            clock: clock_reset.clock,
+            reset,
+        };
+        let output = (self.update)(clock_reset, input);
+        trace("output", &output);
+        trace_pop_path();
+        output
+    }
+
+    fn descriptor(&self, scoped_name: ScopedName) -> Result<Descriptor<SyncKind>, RHDLError> {
+        let module_name = scoped_name.to_string();
+        let module_ident = format_ident!("{}", module_name);
+        let netlist = optimize_ntl(build_kernel_netlist(&self.kernel))?;
+        let netlist = pipeline(
+            &netlist,
+            N,
+            self.model.as_ref(),
+            &module_name,
+            clock_enable().is_some(),
+        )?;
+        let has_clock_enable = netlist.inputs.len() > 2;
+        // The netlist is emitted as its own module, which is wrapped
+        // to give it the usual port names
+        let stages = build_hdl(&format!("{module_name}_stages"), &netlist)?;
+        let stages_ident = format_ident!("{}", stages.name);
+        let stages_modules = &stages.modules.modules;
+        let ports = [
+            maybe_port_wire(vlog::Direction::Input, 2, "clock_reset"),
+            maybe_port_wire(vlog::Direction::Input, Self::I::bits(), "i"),
+            maybe_port_wire(vlog::Direction::Output, Self::O::bits(), "o"),
+            has_clock_enable
+                .then(|| maybe_port_wire(vlog::Direction::Input, 1, "ce"))
+                .flatten(),
+        ];
+        let ports = ports.iter().flatten();
+        let connections: [Option<vlog::stmt::Connection>; 4] = [
+            Some(parse_quote! { .arg_0(clock_reset) }),
+            (Self::I::bits() != 0).then(|| parse_quote! { .arg_1(i) }),
+            has_clock_enable.then(|| parse_quote! { .arg_2(ce) }),
+            Some(parse_quote! { .out(o) }),
+        ];
+        let connections = connections.iter().flatten();
+        let module: vlog::ModuleList = parse_quote! {
+            module #module_ident(#(#ports),*);
+                #stages_ident stages(#(#connections),*);
+            endmodule
+            #(#stages_modules)*
+        };
+        Ok(Descriptor {
+            name: scoped_name,
+            input_kind: Self::I::static_kind(),
+            output_kind: Self::O::static_kind(),
+            d_kind: Kind::Empty,
+            q_kind: Kind::Empty,
+            kernel: Some(self.kernel.clone()),
+            netlist: Some(netlist),
+            hdl: Some(HDLDescriptor {
+                name: module_name,
+                modules: module,
+            }),
+            _phantom: std::marker::PhantomData,
+        })
+    }
+}
@@ -17,7 +17,7 @@
 //!
 //! For an example of how to use it, see the book.
 //!     
-use quote::format_ident;
+use quote::{format_ident, quote};
 use syn::parse_quote;
 
 use crate::{
@@ -25,11 +25,11 @@ use crate::{
     SynchronousDQ, SynchronousIO,
     circuit::{
         descriptor::{Descriptor, SyncKind},
+        reset_style::{build_kernel_netlist, kernel_clock_reset},
         scoped_name::ScopedName,
     },
     compile_design,
     digital_fn::{DigitalFn2, NoSynchronousKernel},
-    ntl::from_rtl::build_ntl_from_rtl,
     rtl::Object,
     trace, trace_pop_path, trace_push_path,
 };
@@ -97,8 +97,8 @@ impl<I: Digital, O: Digital> Synchronous for Func<I, O> {
         let function_def = self.kernel.as_vlog()?;
         let func_name = format_ident!("{}", function_def.name);
         // Call the verilog function with (clock_reset, i, q), if they exist.
-        let clock_reset = Some(format_ident!("clock_reset"));
-        let i_bind = (Self::I::bits() != 0).then(|| format_ident!("i"));
+        let clock_reset = Some(kernel_clock_reset());
+        let i_bind = (Self::I::bits() != 0).then(|| quote! { i });
         let fn_args = [clock_reset, i_bind];
         let module: vlog::ModuleDef = parse_quote! {
             module #module_ident(#(#ports),*);
@@ -113,7 +113,7 @@ impl<I: Digital, O: Digital> Synchronous for Func<I, O> {
             d_kind: Kind::Empty,
             q_kind: Kind::Empty,
             kernel: Some(self.kernel.clone()),
-            netlist: Some(build_ntl_from_rtl(&self.kernel)),
+            netlist: Some(build_kernel_netlist(&self.kernel)),
             hdl: Some(HDLDescriptor {
                 name: module_name,
                 modules: module.into(),
@@ -11,6 +11,7 @@ use crate::{
     Circuit, CircuitDQ, CircuitIO, CompilationMode, HDLDescriptor, Kind, RHDLError,
     circuit::{
         descriptor::{AsyncKind, Descriptor},
+        hdl::dedup::deduplicate_modules,
         scoped_name::ScopedName,
     },
     compile_design,
@@ -91,7 +92,7 @@ fn build_circuit_hdl<C: Circuit>(
     }?;
     Ok(HDLDescriptor {
         name: local_name,
-        modules,
+        modules: deduplicate_modules(modules),
     })
 }
 
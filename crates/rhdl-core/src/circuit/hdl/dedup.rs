@@ -0,0 +1,186 @@
+//! Structural deduplication of generated Verilog modules
+//!
+//! Every circuit names its Verilog module after its [ScopedName](crate::circuit::scoped_name::ScopedName),
+//! so a design with eight identical `DFF<b8>` children would otherwise contain eight
+//! copies of the same module, differing only in their names.  The HDL builders for
+//! circuits with children pass the assembled module list through [deduplicate_modules],
+//! which keeps the first of each set of identical modules and points the instances of
+//! the others at it.
+//!
+//! Two modules are identical if they have the same ports and body (which includes the
+//! Verilog function compiled from the kernel of the circuit), and their instances refer
+//! to identical modules.  Instances of modules that are not in the list (like foreign
+//! modules) are compared by name.
+//!
+//! The hierarchy of the deduplicated modules (which instance is implemented by
+//! which module) can be listed with [HDLDescriptor::instances](crate::HDLDescriptor::instances).
+use std::{
+    collections::HashMap,
+    hash::{Hash, Hasher},
+};
+
+use fnv::FnvHasher;
+use rhdl_vlog::{
+    self as vlog, ItemKind,
+    stmt::{Instance, Stmt, StmtKind},
+};
+
+pub(crate) fn instances(module: &vlog::ModuleDef) -> impl Iterator<Item = &Instance> {
+    module.items.iter().filter_map(|item| match &item.kind {
+        ItemKind::Statement(Stmt {
+            kind: StmtKind::Instance(instance),
+        }) => Some(instance),
+        _ => None,
+    })
+}
+
+fn instances_mut(module: &mut vlog::ModuleDef) -> impl Iterator<Item = &mut Instance> {
+    module
+        .items
+        .iter_mut()
+        .filter_map(|item| match &mut item.kind {
+            ItemKind::Statement(Stmt {
+                kind: StmtKind::Instance(instance),
+            }) => Some(instance),
+            _ => None,
+        })
+}
+
+struct Classes<'a> {
+    modules: HashMap<&'a str, &'a vlog::ModuleDef>,
+    classes: HashMap<&'a str, usize>,
+    // The canonical bodies seen so far, bucketed by their hash
+    buckets: HashMap<u64, Vec<(vlog::ModuleDef, usize)>>,
+    count: usize,
+    hash: fn(&vlog::ModuleDef) -> u64,
+}
+
+impl<'a> Classes<'a> {
+    // The class of identical modules that the module belongs to.  The
+    // canonical body of a module does not depend on its name, or the names
+    // of the modules it instantiates.  The hash of the body only picks a
+    // bucket - modules are merged if their bodies are equal.
+    fn class(&mut self, name: &'a str) -> usize {
+        if let Some(&class) = self.classes.get(name) {
+            return class;
+        }
+        let module = self.modules[name];
+        let mut canonical = module.clone();
+        canonical.name.clear();
+        for (instance, original) in instances_mut(&mut canonical).zip(instances(module)) {
+            if self.modules.contains_key(original.module.as_str()) {
+                instance.module = format!("{}", self.class(&original.module));
+            }
+        }
+        let bucket = self.buckets.entry((self.hash)(&canonical)).or_default();
+        let class = match bucket.iter().find(|(body, _)| *body == canonical) {
+            Some((_, class)) => *class,
+            None => {
+                let class = self.count;
+                self.count += 1;
+                bucket.push((canonical, class));
+                class
+            }
+        };
+        self.classes.insert(name, class);
+        class
+    }
+}
+
+/// Remove duplicate modules from a list of modules.
+///
+/// The first module with a given structure is kept, and the instances of the
+/// later copies are renamed to refer to it.  The first module in the list
+/// (the top of the hierarchy) always keeps its name.
+pub fn deduplicate_modules(modules: vlog::ModuleList) -> vlog::ModuleList {
+    deduplicate_modules_by(modules, |module| {
+        let mut hasher = FnvHasher::default();
+        module.hash(&mut hasher);
+        hasher.finish()
+    })
+}
+
+fn deduplicate_modules_by(
+    modules: vlog::ModuleList,
+    hash: fn(&vlog::ModuleDef) -> u64,
+) -> vlog::ModuleList {
+    let mut classes = Classes {
+        modules: HashMap::new(),
+        classes: HashMap::new(),
+        buckets: HashMap::new(),
+        count: 0,
+        hash,
+    };
+    for module in &modules.modules {
+        classes
+            .modules
+            .entry(module.name.as_str())
+            .or_insert(module);
+    }
+    let mut representative = HashMap::new();
+    let renames = modules
+        .modules
+        .iter()
+        .map(|module| {
+            let class = classes.class(&module.name);
+            let keep = representative
+                .entry(class)
+                .or_insert_with(|| module.name.clone());
+            (module.name.clone(), keep.clone())
+        })
+        .collect::<HashMap<_, _>>();
+    let modules = modules
+        .modules
+        .into_iter()
+        .filter(|module| renames[&module.name] == module.name)
+        .map(|mut module| {
+            for instance in instances_mut(&mut module) {
+                if let Some(name) = renames.get(&instance.module) {
+                    instance.module = name.clone();
+                }
+            }
+            module
+        })
+        .collect();
+    vlog::ModuleList { modules }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::*;
+
+    #[test]
+    fn test_colliding_hashes_do_not_merge_different_modules() {
+        let modules: vlog::ModuleList = parse_quote! {
+            module top(input wire [3:0] i, output wire [11:0] o);
+               left c0(.i(i), .o(o[3:0]));
+               right c1(.i(i), .o(o[7:4]));
+               left_copy c2(.i(i), .o(o[11:8]));
+            endmodule
+            module left(input wire [3:0] i, output wire [3:0] o);
+               assign o = i;
+            endmodule
+            module right(input wire [3:0] i, output wire [3:0] o);
+               assign o = ~i;
+            endmodule
+            module left_copy(input wire [3:0] i, output wire [3:0] o);
+               assign o = i;
+            endmodule
+        };
+        // Every module lands in the same bucket, so only comparing
+        // the bodies keeps `left` and `right` apart
+        let modules = deduplicate_modules_by(modules, |_| 0);
+        let names = modules
+            .modules
+            .iter()
+            .map(|module| module.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["top", "left", "right"]);
+        let targets = instances(&modules.modules[0])
+            .map(|instance| instance.module.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(targets, ["left", "right", "left"]);
+    }
+}
@@ -1,3 +1,4 @@
 //! Support for translation of RHDL circuits to HDL (e.g., Verilog) code
 pub mod asynchronous;
+pub mod dedup;
 pub mod synchronous;
@@ -11,12 +11,13 @@
 use crate::{
     CompilationMode, HDLDescriptor, Kind, RHDLError, Synchronous, SynchronousDQ, SynchronousIO,
     circuit::{
+        clock_enable::{ClockEnableInput, clock_enable_binding, clock_enable_port},
         descriptor::{Descriptor, SyncKind},
+        hdl::dedup::deduplicate_modules,
+        reset_style::{build_kernel_netlist, kernel_clock_reset},
         scoped_name::ScopedName,
     },
-    compile_design,
-    ntl::{self, from_rtl::build_ntl_from_rtl},
-    rtl,
+    compile_design, ntl, rtl,
     types::{
         digital::Digital,
         path::{Path, bit_range},
@@ -37,10 +38,16 @@ fn build_synchronous_hdl<C: Synchronous>(
     let d_kind = <C as SynchronousDQ>::D::static_kind();
     let q_kind = <C as SynchronousDQ>::Q::static_kind();
     let outputs = circuit_output.bits();
+    let instantiated = children
+        .iter()
+        .filter(|child| !child.output_kind.is_empty())
+        .map(|child| child.hdl())
+        .collect::<Result<Vec<_>, RHDLError>>()?;
     let ports = [
         vlog::maybe_port_wire(vlog::Direction::Input, 2, "clock_reset"),
         vlog::maybe_port_wire(vlog::Direction::Input, circuit_input.bits(), "i"),
         vlog::maybe_port_wire(vlog::Direction::Output, circuit_output.bits(), "o"),
+        clock_enable_port(&instantiated),
     ];
     let ports = ports.iter().flatten();
     let declarations = [
@@ -65,6 +72,7 @@ fn build_synchronous_hdl<C: Synchronous>(
             Some(parse_quote! {.clock_reset(clock_reset)}),
             input_binding,
             output_binding,
+            clock_enable_binding(child_desc.hdl()?),
         ];
         let bindings = bindings.iter().flatten();
         let component_name = format_ident!("{}", child_desc.name.to_string());
@@ -77,9 +85,9 @@ fn build_synchronous_hdl<C: Synchronous>(
     }
     let kernel = kernel.as_vlog()?;
     // Call the verilog function with (clock_reset, i, q), if they exist.
-    let i_bind = (circuit_input.bits() != 0).then(|| format_ident!("i"));
-    let q_bind = (q_kind.bits() != 0).then(|| format_ident!("q"));
-    let args = [Some(format_ident!("clock_reset")), i_bind, q_bind];
+    let i_bind = (circuit_input.bits() != 0).then(|| quote! { i });
+    let q_bind = (q_kind.bits() != 0).then(|| quote! { q });
+    let args = [Some(kernel_clock_reset()), i_bind, q_bind];
     let args = args.iter().flatten();
     let kernel_name = format_ident!("{}", kernel.name);
     let module_ident = format_ident!("{local_name}");
@@ -101,7 +109,7 @@ fn build_synchronous_hdl<C: Synchronous>(
     }?;
     Ok(HDLDescriptor {
         name: local_name,
-        modules,
+        modules: deduplicate_modules(modules),
     })
 }
 
@@ -112,7 +120,7 @@ fn build_synchronous_netlist<C: Synchronous>(
 ) -> Result<ntl::Object, RHDLError> {
     let name = scoped_name.to_string();
     // Construct the netlist for the update function
-    let update_netlist = build_ntl_from_rtl(kernel);
+    let update_netlist = build_kernel_netlist(kernel);
     // Create a manual builder for the top level netlist
     let mut builder = ntl::builder::Builder::new(&name);
     // This is the kind of output of the update kernel - it must be equal to
@@ -128,6 +136,7 @@ fn build_synchronous_netlist<C: Synchronous>(
     let top_cr = builder.add_input(crate::ClockReset::static_kind());
     let top_i = builder.add_input(input_kind);
     let top_o = builder.allocate_outputs(output_kind);
+    let mut top_ce = ClockEnableInput::default();
     // Link in the update code.
     let update_register_offset = builder.import(&update_netlist);
     // Link the ClockReset signal from the top down into the update code.
@@ -176,6 +185,8 @@ fn build_synchronous_netlist<C: Synchronous>(
         for (&top_cr, &child_cr) in top_cr.iter().zip(&netlist.inputs[0]) {
             builder.copy_from_to(top_cr, child_offset(child_cr.into()));
         }
+        // And its clock enable, if it has one
+        top_ce.connect(&mut builder, netlist, &child_offset);
         // Connect the child's input registers to the given bits of the D register
         for (&d_bit, &child_i) in d_vec[output_bit_range.clone()]
             .iter()
@@ -8,6 +8,8 @@
 //! You typically don't create `HDLDescriptor` instances directly.
 use rhdl_vlog;
 
+use crate::circuit::hdl::dedup::instances;
+
 /// A hardware description of a circuit.
 ///
 /// This struct captures the HDL representation of a circuit,
@@ -19,3 +21,26 @@ pub struct HDLDescriptor {
     /// The list of modules that make up this circuit.
     pub modules: rhdl_vlog::ModuleList,
 }
+
+impl HDLDescriptor {
+    /// List the instances in the hierarchy of this circuit, with the
+    /// module that implements each one.
+    ///
+    /// Instances are named by their hierarchical Verilog path (like `top.c0.c1`),
+    /// starting from the top module.  Since identical modules are only emitted
+    /// once, many instances can share a module.
+    pub fn instances(&self) -> Vec<(String, String)> {
+        let mut result = vec![];
+        self.collect_instances(&self.name, &self.name, &mut result);
+        result
+    }
+    fn collect_instances(&self, path: &str, module: &str, result: &mut Vec<(String, String)>) {
+        result.push((path.to_string(), module.to_string()));
+        if let Some(module) = self.modules.modules.iter().find(|m| m.name == module) {
+            for instance in instances(module) {
+                let path = format!("{path}.{}", instance.instance);
+                self.collect_instances(&path, &instance.module, result);
+            }
+        }
+    }
+}
@@ -10,14 +10,22 @@
 //! and chain circuits.
 pub mod adapter;
 pub mod array;
+pub mod cdc;
 pub mod chain;
 pub mod circuit_impl;
+pub mod clock_enable;
 pub mod descriptor;
 pub mod drc;
 pub mod fixture;
+pub mod formal;
+pub mod foreign;
 pub mod function;
 pub mod hdl;
 pub mod hdl_descriptor;
 pub mod phantom;
+pub mod reset_style;
+pub mod resources;
 pub mod scoped_name;
 pub mod synchronous;
+pub mod timing;
+pub mod vhdl_descriptor;
@@ -0,0 +1,246 @@
+//! Reset styles for synchronous circuits
+//!
+//! By default, the registers of a [Synchronous] circuit have a synchronous,
+//! active high reset.  The reset is sampled on the rising edge of the clock,
+//! like any other input, and the register is reset if it is `true`.  Some flows
+//! (ASICs, and some FPGA families) prefer a reset that is asynchronous (it takes
+//! effect as soon as it is asserted, without waiting for the clock), or one that is
+//! active low (like [ResetN](crate::ResetN)), or both.
+//!
+//! The [ResetStyled] wrapper applies a [ResetStyle] to a circuit and all of its
+//! descendants.  The reset line of the wrapped circuit has the polarity given by
+//! the style, all the way down the hierarchy of the generated Verilog.  Kernels
+//! still see an active high reset in their `ClockReset` argument, so the same
+//! kernel code works with any style.  When the reset is active low, the kernel
+//! is passed an inverted copy of it.
+//!
+//! Registers (like the `DFF` in `rhdl-fpga`) query the style that is in effect with
+//! [reset_style] when they are simulated, and when their descriptors are built.  The
+//! style applies to the whole design, so the wrapper is meant to go around the top
+//! level circuit.  A [ResetStyled] circuit inside another one passes its
+//! input straight through, and keeps the style of the outer one.
+use std::cell::Cell;
+
+use quote::quote;
+
+use crate::{
+    ClockReset, Digital, RHDLError, Synchronous, SynchronousDQ, SynchronousIO,
+    circuit::{
+        descriptor::{Descriptor, SyncKind},
+        scoped_name::ScopedName,
+    },
+    digital_fn::NoSynchronousKernel,
+    ntl::{
+        self,
+        from_rtl::build_ntl_from_rtl,
+        object::LocatedOpCode,
+        spec::{Not, OpCode, Wire},
+    },
+    rtl,
+    types::{
+        path::{Path, bit_range},
+        reset::reset,
+    },
+};
+
+/// The style of the reset used by the registers of a design.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ResetStyle {
+    /// The reset takes effect as soon as it is asserted, instead of
+    /// on the next rising edge of the clock
+    pub asynchronous: bool,
+    /// The reset is asserted when the reset line is low
+    pub active_low: bool,
+}
+
+impl ResetStyle {
+    /// A synchronous, active high reset (the default)
+    #[must_use]
+    pub fn synchronous() -> Self {
+        Self::default()
+    }
+    /// An asynchronous, active high reset
+    #[must_use]
+    pub fn asynchronous() -> Self {
+        Self {
+            asynchronous: true,
+            active_low: false,
+        }
+    }
+    /// Make the reset active low
+    #[must_use]
+    pub fn active_low(self) -> Self {
+        Self {
+            active_low: true,
+            ..self
+        }
+    }
+    /// Returns true if the given level of the reset line asserts the reset
+    #[must_use]
+    pub fn is_asserted(self, level: bool) -> bool {
+        level != self.active_low
+    }
+}
+
+thread_local! {
+    static RESET_STYLE: Cell<Option<ResetStyle>> = const { Cell::new(None) };
+}
+
+/// The reset style in effect for the circuit being simulated (or described).
+///
+/// This is the style of the enclosing [ResetStyled] circuit, or the default
+/// style if there is none.
+pub fn reset_style() -> ResetStyle {
+    RESET_STYLE.with(|style| style.get()).unwrap_or_default()
+}
+
+struct StyleGuard(Option<ResetStyle>);
+
+impl Drop for StyleGuard {
+    fn drop(&mut self) {
+        RESET_STYLE.with(|style| style.set(self.0));
+    }
+}
+
+// Run the closure with the given reset style in effect, unless
+// one has been set by an enclosing circuit already
+fn with_reset_style<R>(style: ResetStyle, f: impl FnOnce() -> R) -> R {
+    let previous = RESET_STYLE.with(|cell| cell.get());
+    if previous.is_some() {
+        return f();
+    }
+    RESET_STYLE.with(|cell| cell.set(Some(style)));
+    let _guard = StyleGuard(previous);
+    f()
+}
+
+fn reset_bit() -> usize {
+    bit_range(ClockReset::static_kind(), &Path::default().field("reset"))
+        .expect("ClockReset has a reset field")
+        .0
+        .start
+}
+
+/// The Verilog expression for the `ClockReset` argument of a kernel
+/// called from a module with a `clock_reset` port.
+///
+/// With an active low reset, the reset bit is inverted, so that kernels
+/// always see an active high reset.
+pub(crate) fn kernel_clock_reset() -> proc_macro2::TokenStream {
+    if !reset_style().active_low {
+        return quote! { clock_reset };
+    }
+    let bits = (0..ClockReset::bits()).rev().map(|bit| {
+        let index = syn::Index::from(bit);
+        if bit == reset_bit() {
+            quote! { ~clock_reset[#index] }
+        } else {
+            quote! { clock_reset[#index] }
+        }
+    });
+    quote! { {#(#bits),*} }
+}
+
+/// Build the netlist for the kernel of a synchronous circuit.
+///
+/// With an active low reset, the reset input of the netlist is inverted
+/// before it reaches the logic of the kernel.
+pub(crate) fn build_kernel_netlist(kernel: &rtl::Object) -> ntl::Object {
+    let mut netlist = build_ntl_from_rtl(kernel);
+    if !reset_style().active_low {
+        return netlist;
+    }
+    let Some(&asserted) = netlist.inputs.first().and_then(|cr| cr.get(reset_bit())) else {
+        return netlist;
+    };
+    let details = netlist.symtab[Wire::Register(asserted)].clone();
+    let line = netlist.symtab.reg((), details);
+    netlist.inputs[0][reset_bit()] = line.reg().expect("a new register");
+    netlist.ops.insert(
+        0,
+        LocatedOpCode {
+            op: OpCode::Not(Not {
+                lhs: Wire::Register(asserted),
+                arg: line,
+            }),
+            loc: None,
+        },
+    );
+    netlist
+}
+
+/// Apply a [ResetStyle] to a synchronous circuit and its descendants.
+///
+/// The reset input of the wrapped circuit uses the polarity of the style.
+/// The wrapper adds no hierarchy of its own: the descriptor is that of the
+/// inner circuit, generated with the style in effect.
+#[derive(Clone)]
+pub struct ResetStyled<C> {
+    inner: C,
+    style: ResetStyle,
+}
+
+impl<C> ResetStyled<C> {
+    /// Wrap the given circuit, so that it uses the given reset style
+    #[must_use]
+    pub fn new(inner: C, style: ResetStyle) -> Self {
+        Self { inner, style }
+    }
+}
+
+impl<C: Synchronous> SynchronousIO for ResetStyled<C> {
+    type I = C::I;
+    type O = C::O;
+    type Kernel = NoSynchronousKernel<ClockReset, C::I, (), (C::O, ())>;
+}
+
+impl<C: Synchronous> SynchronousDQ for ResetStyled<C> {
+    type D = ();
+    type Q = ();
+}
+
+impl<C: Synchronous> Synchronous for ResetStyled<C> {
+    type S = C::S;
+
+    fn init(&self) -> Self::S {
+        self.inner.init()
+    }
+
+    fn sim(&self, clock_reset: ClockReset, input: Self::I, state: &mut Self::S) -> Self::O {
+        // The circuits inside see an active high reset.  If this wrapper
+        // is nested, the outer one has converted the reset already.
+        let clock_reset = if RESET_STYLE.with(|cell| cell.get()).is_some() {
+            clock_reset
+        } else {
+            ClockReset {
+                clock: clock_reset.clock,
+                reset: reset(self.style.is_asserted(clock_reset.reset.raw())),
+            }
+        };
+        with_reset_style(self.style, || self.inner.sim(clock_reset, input, state))
+    }
+
+    fn descriptor(&self, scoped_name: ScopedName) -> Result<Descriptor<SyncKind>, RHDLError> {
+        with_reset_style(self.style, || self.inner.descriptor(scoped_name))
+    }
+
+    fn children(
+        &self,
+        parent_scope: &ScopedName,
+    ) -> impl Iterator<Item = Result<Descriptor<SyncKind>, RHDLError>> {
+        with_reset_style(self.style, || {
+            self.inner.children(parent_scope).collect::<Vec<_>>()
+        })
+        .into_iter()
+    }
+
+    fn descendants(
+        &self,
+        parent_scope: &ScopedName,
+    ) -> impl Iterator<Item = Result<Descriptor<SyncKind>, RHDLError>> {
+        with_reset_style(self.style, || {
+            self.inner.descendants(parent_scope).collect::<Vec<_>>()
+        })
+        .into_iter()
+    }
+}
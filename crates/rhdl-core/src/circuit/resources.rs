@@ -0,0 +1,155 @@
+//! Resource Estimation for Circuits
+//!
+//! This module provides a quick estimate of the resources (flip flops, LUTs,
+//! multiplexers, adders, multipliers and black box instances like RAMs)
+//! needed by a synchronous circuit, without running a vendor toolchain.
+//! The estimate is computed from the optimized netlist of the circuit,
+//! using the assumptions in a [LutModel].  See [crate::ntl::resources]
+//! for details of how the netlist is mapped.  The netlist is optimized at the
+//! current [OptimizationLevel], which can be changed for the estimate with
+//! [with_optimization_level].
+//!
+//! The estimate is broken down by the [ScopedName] of the circuit and of
+//! every circuit inside of it, with each child followed by its own children.
+//! Each row counts the resources of that circuit and everything inside of it.
+//! Because the netlist of a circuit is optimized after its children are linked
+//! into it, the resources of the children may not add up to the resources of
+//! their parent.  Black box instances are listed
+//! individually, under their full (flattened) scoped names.
+//!
+//! The [ResourceReport] can be printed as a table, or serialized as JSON
+//! with [ResourceReport::to_json], so that utilization can be tracked
+//! between commits.
+//!
+//! [OptimizationLevel]: crate::compiler::OptimizationLevel
+//! [with_optimization_level]: crate::compiler::with_optimization_level
+use serde::Serialize;
+
+use crate::{RHDLError, Synchronous, circuit::scoped_name::ScopedName, ntl::resources::estimate};
+
+pub use crate::ntl::resources::{BlackBoxInstance, BlackBoxResources, LutModel, Resources};
+
+/// The resources used by a circuit in the design hierarchy.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ScopeResources {
+    /// The (flattened) scoped name of the circuit
+    pub name: String,
+    /// The resources used by the circuit, including its children
+    pub resources: Resources,
+}
+
+/// An estimate of the resources used by a circuit and the circuits inside it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ResourceReport {
+    /// The LUT model used for the estimate
+    pub model: LutModel,
+    /// The resources used by the top level circuit, followed by each of its descendants
+    pub scopes: Vec<ScopeResources>,
+}
+
+impl ResourceReport {
+    /// The resources used by the whole design.
+    pub fn total(&self) -> &Resources {
+        &self.scopes[0].resources
+    }
+    /// Serialize the report as (pretty printed) JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("resource reports are always serializable")
+    }
+}
+
+impl std::fmt::Display for ResourceReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let width = self
+            .scopes
+            .iter()
+            .map(|scope| scope.name.len())
+            .chain(
+                self.total()
+                    .black_boxes
+                    .iter()
+                    .map(|instance| instance.name.len()),
+            )
+            .max()
+            .unwrap_or_default()
+            .max(8);
+        writeln!(
+            f,
+            "{:<width$} {:>8} {:>8} {:>8} {:>8} {:>10} {:>8} {:>10} {:>10}",
+            "scope",
+            "FFs",
+            "LUTs",
+            "muxes",
+            "adders",
+            "adder bits",
+            "mults",
+            "mult bits",
+            "mem bits"
+        )?;
+        for scope in &self.scopes {
+            let r = &scope.resources;
+            writeln!(
+                f,
+                "{:<width$} {:>8} {:>8} {:>8} {:>8} {:>10} {:>8} {:>10} {:>10}",
+                scope.name,
+                r.flip_flops,
+                r.luts,
+                r.muxes,
+                r.adders.values().sum::<usize>(),
+                r.adder_bits(),
+                r.multipliers.values().sum::<usize>(),
+                r.multiplier_bits(),
+                r.memory_bits
+            )?;
+        }
+        let black_boxes = &self.total().black_boxes;
+        if black_boxes.is_empty() {
+            return Ok(());
+        }
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:<width$} {:>8} {:>8} {:>8} {:>10}",
+            "black box", "in", "out", "FFs", "mem bits"
+        )?;
+        for instance in black_boxes {
+            let (flip_flops, memory_bits) = match instance.resources {
+                Some(declared) => (
+                    declared.flip_flops.to_string(),
+                    declared.memory_bits.to_string(),
+                ),
+                None => ("?".into(), "?".into()),
+            };
+            writeln!(
+                f,
+                "{:<width$} {:>8} {:>8} {:>8} {:>10}",
+                instance.name, instance.input_bits, instance.output_bits, flip_flops, memory_bits
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Estimate the resources used by the given synchronous circuit, using the supplied LUT model.
+pub fn estimate_resources<T: Synchronous>(
+    uut: &T,
+    model: &LutModel,
+) -> Result<ResourceReport, RHDLError> {
+    let top = ScopedName::top();
+    let descriptor = uut.descriptor(top.clone())?;
+    let mut scopes = vec![ScopeResources {
+        name: descriptor.name.to_string(),
+        resources: estimate(descriptor.netlist()?, model),
+    }];
+    for descendant in uut.descendants(&top) {
+        let descendant = descendant?;
+        scopes.push(ScopeResources {
+            name: descendant.name.to_string(),
+            resources: estimate(descendant.netlist()?, model),
+        });
+    }
+    Ok(ResourceReport {
+        model: *model,
+        scopes,
+    })
+}
@@ -287,4 +287,15 @@ pub trait Synchronous: 'static + Sized + SynchronousIO {
     ) -> impl Iterator<Item = Result<Descriptor<SyncKind>, RHDLError>> {
         std::iter::empty()
     }
+
+    /// Iterate over all of the circuits below this one in the hierarchy,
+    /// with each child followed by its own descendants.  Circuits that
+    /// override [children](Synchronous::children) should override this too,
+    /// or only their children are visited.
+    fn descendants(
+        &self,
+        parent_scope: &ScopedName,
+    ) -> impl Iterator<Item = Result<Descriptor<SyncKind>, RHDLError>> {
+        self.children(parent_scope)
+    }
 }
@@ -0,0 +1,130 @@
+//! Static Timing Estimation for Circuits
+//!
+//! This module provides a longest path timing estimator for synchronous circuits.
+//! It works on the optimized netlist of the circuit, and does not require a vendor
+//! toolchain.  Each opcode in the netlist is assigned a delay by a [DelayModel],
+//! and the critical path is the path with the largest total delay between the
+//! inputs or flip flops of the circuit and its outputs or flip flops.
+//!
+//! The two built in models are [UnitDelay], which measures the logic depth of
+//! the design, and [WidthDelay], which accounts for the width of arithmetic
+//! operations.  Any closure of the form `Fn(&OpCode) -> f64` can also be used.
+//!
+//! The critical path is reported as a miette diagnostic that points to the
+//! kernel source for each element on the path.  To gate a design on a logic
+//! depth budget, use [max_logic_delay].
+//!
+//! The netlist is optimized at the current [OptimizationLevel].  To see how the
+//! design does with the more aggressive optimizations, run the estimate inside
+//! [with_optimization_level].
+//!
+//! [OptimizationLevel]: crate::compiler::OptimizationLevel
+//! [with_optimization_level]: crate::compiler::with_optimization_level
+use crate::{
+    RHDLError, Synchronous,
+    ast::SourcePool,
+    circuit::scoped_name::ScopedName,
+    ntl::{graph::GraphMode, timing::TimingPoint},
+};
+use miette::{Diagnostic, SourceSpan};
+use thiserror::Error;
+
+pub use crate::ntl::timing::{DelayModel, TimingPath, UnitDelay, WidthDelay};
+
+/// Diagnostic describing the critical path through a synchronous circuit.
+#[derive(Debug, Error)]
+#[error("RHDL Critical Path (delay {delay}, start {start:?}, end {end:?})")]
+pub struct CriticalPath {
+    src: SourcePool,
+    elements: Vec<SourceSpan>,
+    /// The total delay along the path
+    pub delay: f64,
+    /// Where the path starts
+    pub start: TimingPoint,
+    /// Where the path ends
+    pub end: TimingPoint,
+    /// The delay budget for the path, if any
+    pub budget: Option<f64>,
+}
+
+impl Diagnostic for CriticalPath {
+    fn severity(&self) -> Option<miette::Severity> {
+        match self.budget {
+            Some(budget) if self.delay > budget => Some(miette::Severity::Error),
+            _ => Some(miette::Severity::Advice),
+        }
+    }
+    fn source_code(&self) -> Option<&dyn miette::SourceCode> {
+        Some(&self.src)
+    }
+    fn help<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+        match self.budget {
+            Some(budget) if self.delay > budget => Some(Box::new(format!(
+                "This path exceeds the delay budget of {budget}.  Consider adding a pipeline stage along it."
+            ))),
+            _ => Some(Box::new(
+                "This is the longest combinatorial path in the design",
+            )),
+        }
+    }
+    fn labels<'a>(
+        &'a self,
+    ) -> Option<Box<dyn std::iter::Iterator<Item = miette::LabeledSpan> + 'a>> {
+        Some(Box::new(self.elements.iter().map(|span| {
+            miette::LabeledSpan::new_primary_with_span(None, *span)
+        })))
+    }
+}
+
+/// Find the critical path through the given synchronous circuit, using the supplied delay model.
+///
+/// Returns `None` if the circuit has no combinatorial paths at all.
+pub fn critical_path<T: Synchronous>(
+    uut: &T,
+    model: &impl DelayModel,
+) -> Result<Option<CriticalPath>, RHDLError> {
+    let descriptor = uut.descriptor(ScopedName::top())?;
+    let ntl = descriptor.netlist()?;
+    let code = &ntl.code;
+    let Some(path) = crate::ntl::timing::critical_path(ntl, GraphMode::Synchronous, model)? else {
+        return Ok(None);
+    };
+    let mut elements: Vec<SourceSpan> = vec![];
+    for span in path
+        .ops
+        .iter()
+        .flat_map(|&ndx| ntl.ops[ndx].loc)
+        .map(|loc| SourceSpan::from(code.span(loc)))
+    {
+        if !elements.contains(&span) {
+            elements.push(span);
+        }
+    }
+    Ok(Some(CriticalPath {
+        src: code.source(),
+        elements,
+        delay: path.delay,
+        start: path.start,
+        end: path.end,
+        budget: None,
+    }))
+}
+
+/// Check that the critical path through the given synchronous circuit is no longer than `budget`.
+///
+/// If the critical path exceeds the budget, it returns an error with a diagnostic that
+/// includes the source locations of the elements along the path.
+pub fn max_logic_delay<T: Synchronous>(
+    uut: &T,
+    model: &impl DelayModel,
+    budget: f64,
+) -> miette::Result<()> {
+    let Some(mut path) = critical_path(uut, model)? else {
+        return Ok(());
+    };
+    path.budget = Some(budget);
+    if path.delay > budget {
+        return Err(miette::Report::new(path));
+    }
+    Ok(())
+}
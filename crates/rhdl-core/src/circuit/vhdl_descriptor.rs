@@ -0,0 +1,77 @@
+//! A runtime VHDL description of a [Circuit](crate::Circuit) or [Synchronous](crate::Synchronous) circuit.
+//!
+//! This is the VHDL counterpart of [HDLDescriptor](crate::HDLDescriptor).  Unlike the
+//! Verilog output, which is built as an AST, VHDL is carried as text.  Each design unit
+//! is a VHDL-2008 entity/architecture pair, and a descriptor collects the entity for
+//! the circuit itself along with the entities of any black box cores it instantiates.
+//!
+//! You typically don't create `VHDLDescriptor` instances directly.  Use the `vhdl`
+//! method on a [Descriptor](crate::Descriptor) instead.
+
+use crate::RHDLError;
+
+/// A single VHDL-2008 design unit (an entity and its architecture).
+#[derive(Clone, Hash, Debug, PartialEq)]
+pub struct VHDLEntity {
+    /// The name of the entity.
+    pub name: String,
+    /// The VHDL text, including the library and use clauses.
+    pub text: String,
+}
+
+/// A VHDL description of a circuit.
+#[derive(Clone, Hash, Debug, Default)]
+pub struct VHDLDescriptor {
+    /// The name of the top level entity.
+    pub name: String,
+    /// The entities that make up this circuit.  The top level entity is listed first.
+    pub entities: Vec<VHDLEntity>,
+}
+
+impl VHDLDescriptor {
+    /// Create a descriptor that consists of a single entity.
+    pub fn new(name: &str, text: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            entities: vec![VHDLEntity {
+                name: name.into(),
+                text: text.into(),
+            }],
+        }
+    }
+    /// Add the entities of another descriptor, skipping those that are already present.
+    pub fn extend(&mut self, other: &VHDLDescriptor) {
+        for entity in &other.entities {
+            if !self.entities.iter().any(|e| e.name == entity.name) {
+                self.entities.push(entity.clone());
+            }
+        }
+    }
+    /// Render the descriptor as a single VHDL source file.
+    pub fn as_vhdl(&self) -> String {
+        self.entities
+            .iter()
+            .map(|e| e.text.trim_end())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+            + "\n"
+    }
+    /// Check the descriptor for correctness by analyzing it with GHDL.
+    pub fn checked(&self) -> Result<(), RHDLError> {
+        let d = tempfile::tempdir()?;
+        let d_path = d.path();
+        std::fs::write(d_path.join("top.vhd"), self.as_vhdl())?;
+        let mut cmd = std::process::Command::new("ghdl");
+        cmd.arg("-a")
+            .arg("--std=08")
+            .arg(format!("--workdir={}", d_path.display()))
+            .arg(d_path.join("top.vhd"));
+        let status = cmd
+            .status()
+            .expect("GHDL should be installed and in your PATH.");
+        if !status.success() {
+            return Err(anyhow::anyhow!("Failed to analyze VHDL with {}", status).into());
+        }
+        Ok(())
+    }
+}
@@ -532,6 +532,8 @@ impl<'a> RTLCompiler<'a> {
             AluBinary::Add => rtl_binop(tl::AluBinary::Add),
             AluBinary::Sub => rtl_binop(tl::AluBinary::Sub),
             AluBinary::Mul => rtl_binop(tl::AluBinary::Mul),
+            AluBinary::Div => rtl_binop(tl::AluBinary::Div),
+            AluBinary::Rem => rtl_binop(tl::AluBinary::Rem),
             AluBinary::BitAnd => rtl_binop(tl::AluBinary::BitAnd),
             AluBinary::BitOr => rtl_binop(tl::AluBinary::BitOr),
             AluBinary::BitXor => rtl_binop(tl::AluBinary::BitXor),
@@ -546,8 +548,189 @@ impl<'a> RTLCompiler<'a> {
             AluBinary::XAdd => self.make_xadd_or_xmul(lhs, arg1, arg2, loc, tl::AluBinary::Add),
             AluBinary::XSub => self.make_xsub(lhs, arg1, arg2, loc),
             AluBinary::XMul => self.make_xadd_or_xmul(lhs, arg1, arg2, loc, tl::AluBinary::Mul),
+            AluBinary::SaturatingAdd => self.make_saturating(lhs, arg1, arg2, false, loc),
+            AluBinary::SaturatingSub => self.make_saturating(lhs, arg1, arg2, true, loc),
+            AluBinary::OverflowingAdd => self.make_overflowing(lhs, arg1, arg2, false, loc),
+            AluBinary::OverflowingSub => self.make_overflowing(lhs, arg1, arg2, true, loc),
         }
     }
+    // Allocate a register of the given kind, and cast the argument into it
+    fn cast_into(
+        &mut self,
+        arg: Operand,
+        kind: Kind,
+        cast: CastKind,
+        loc: SourceLocation,
+    ) -> Operand {
+        if self.operand_bit_width(arg) == kind.bits() && cast == CastKind::Resize {
+            return arg;
+        }
+        let lhs = self.reg(kind, loc);
+        self.lop(
+            tl::OpCode::Cast(tl::Cast {
+                lhs,
+                arg,
+                len: kind.bits(),
+                kind: cast,
+            }),
+            loc,
+        );
+        lhs
+    }
+    fn binary_into(
+        &mut self,
+        op: tl::AluBinary,
+        arg1: Operand,
+        arg2: Operand,
+        kind: Kind,
+        loc: SourceLocation,
+    ) -> Operand {
+        let lhs = self.reg(kind, loc);
+        self.lop(
+            tl::OpCode::Binary(tl::Binary {
+                lhs,
+                op,
+                arg1,
+                arg2,
+            }),
+            loc,
+        );
+        lhs
+    }
+    fn select_into(
+        &mut self,
+        cond: Operand,
+        true_value: Operand,
+        false_value: Operand,
+        kind: Kind,
+        loc: SourceLocation,
+    ) -> Operand {
+        let lhs = self.reg(kind, loc);
+        self.lop(
+            tl::OpCode::Select(tl::Select {
+                lhs,
+                cond,
+                true_value,
+                false_value,
+            }),
+            loc,
+        );
+        lhs
+    }
+    // Extract a single bit from the argument as a boolean
+    fn bit_of(&mut self, arg: Operand, ndx: usize, loc: SourceLocation) -> Operand {
+        let lhs = self.reg(Kind::make_bool(), loc);
+        self.lop(
+            tl::OpCode::Index(tl::Index {
+                lhs,
+                arg,
+                bit_range: ndx..ndx + 1,
+                path: Path::default(),
+            }),
+            loc,
+        );
+        lhs
+    }
+    // A literal of the given kind, with the bits set by `bit`
+    fn lit_from_fn(
+        &mut self,
+        kind: Kind,
+        bit: impl Fn(usize) -> bool,
+        loc: SourceLocation,
+    ) -> Operand {
+        let bits = (0..kind.bits()).map(|ndx| bit(ndx).into()).collect();
+        self.lit(TypedBits::new(bits, kind), loc)
+    }
+    // Add (or subtract) the arguments with one extra bit of headroom, and
+    // return the wrapped result, the overflow flag and the sign of the exact result.
+    fn make_extended_add_sub(
+        &mut self,
+        arg1: Operand,
+        arg2: Operand,
+        sub: bool,
+        loc: SourceLocation,
+    ) -> (Operand, Operand, Operand) {
+        let len = self.operand_bit_width(arg1);
+        let signed = self.operand_is_signed(arg1);
+        let kind = |len| {
+            if signed {
+                Kind::Signed(len)
+            } else {
+                Kind::Bits(len)
+            }
+        };
+        let arg1 = self.cast_into(arg1, kind(len + 1), CastKind::Resize, loc);
+        let arg2 = self.cast_into(arg2, kind(len + 1), CastKind::Resize, loc);
+        let op = if sub {
+            tl::AluBinary::Sub
+        } else {
+            tl::AluBinary::Add
+        };
+        let exact = self.binary_into(op, arg1, arg2, kind(len + 1), loc);
+        let wrapped = self.cast_into(exact, kind(len), CastKind::Resize, loc);
+        let top = self.bit_of(exact, len, loc);
+        // For unsigned values, the top bit is the carry (or borrow).  For signed
+        // values, the result overflowed if the top two bits differ.
+        let overflow = if signed {
+            let sign = self.bit_of(exact, len - 1, loc);
+            self.binary_into(tl::AluBinary::BitXor, top, sign, Kind::make_bool(), loc)
+        } else {
+            top
+        };
+        (wrapped, overflow, top)
+    }
+    fn make_saturating(
+        &mut self,
+        lhs: Operand,
+        arg1: Operand,
+        arg2: Operand,
+        sub: bool,
+        loc: SourceLocation,
+    ) -> Result<()> {
+        let len = self.operand_bit_width(arg1);
+        let kind = if self.operand_is_signed(arg1) {
+            Kind::Signed(len)
+        } else {
+            Kind::Bits(len)
+        };
+        let (wrapped, overflow, top) = self.make_extended_add_sub(arg1, arg2, sub, loc);
+        let limit = if kind.is_signed() {
+            // The sign of the exact result determines which way to clamp
+            let max = self.lit_from_fn(kind, |ndx| ndx + 1 < len, loc);
+            let min = self.lit_from_fn(kind, |ndx| ndx + 1 == len, loc);
+            self.select_into(top, min, max, kind, loc)
+        } else {
+            self.lit_from_fn(kind, |_| !sub, loc)
+        };
+        self.lop(
+            tl::OpCode::Select(tl::Select {
+                lhs,
+                cond: overflow,
+                true_value: limit,
+                false_value: wrapped,
+            }),
+            loc,
+        );
+        Ok(())
+    }
+    fn make_overflowing(
+        &mut self,
+        lhs: Operand,
+        arg1: Operand,
+        arg2: Operand,
+        sub: bool,
+        loc: SourceLocation,
+    ) -> Result<()> {
+        let (wrapped, overflow, _) = self.make_extended_add_sub(arg1, arg2, sub, loc);
+        self.lop(
+            tl::OpCode::Concat(tl::Concat {
+                lhs,
+                args: vec![wrapped, overflow],
+            }),
+            loc,
+        );
+        Ok(())
+    }
     fn make_case_argument(
         &mut self,
         case_argument: &hf::CaseArgument,
@@ -1047,6 +1230,97 @@ impl<'a> RTLCompiler<'a> {
             loc,
         );
     }
+    fn make_count_ones(&mut self, lhs: Operand, arg: Operand, loc: SourceLocation) {
+        // Sum the bits with a tree of adders, that are only as wide as
+        // needed to hold the count of the bits below them.
+        let len = self.operand_bit_width(arg);
+        let mut level = (0..len)
+            .map(|ndx| (self.bit_of(arg, ndx, loc), 1))
+            .collect::<Vec<_>>();
+        while level.len() > 1 {
+            level = level
+                .chunks(2)
+                .map(|pair| match *pair {
+                    [(a, a_len), (b, b_len)] => {
+                        let sum_len = (a_len.max(b_len) + 1).min(len);
+                        let kind = Kind::Bits(sum_len);
+                        let a = self.cast_into(a, kind, CastKind::Resize, loc);
+                        let b = self.cast_into(b, kind, CastKind::Resize, loc);
+                        (
+                            self.binary_into(tl::AluBinary::Add, a, b, kind, loc),
+                            sum_len,
+                        )
+                    }
+                    _ => pair[0],
+                })
+                .collect();
+        }
+        self.lop(
+            tl::OpCode::Cast(tl::Cast {
+                lhs,
+                arg: level[0].0,
+                len,
+                kind: CastKind::Resize,
+            }),
+            loc,
+        );
+    }
+    // Build a priority encoder that counts the zeros at one end of the
+    // given range of bits.  Returns a flag that is set if all the bits
+    // are zero, and the count.
+    fn make_zero_count(
+        &mut self,
+        arg: Operand,
+        range: std::ops::Range<usize>,
+        leading: bool,
+        loc: SourceLocation,
+    ) -> (Operand, Operand) {
+        if range.len() == 1 {
+            let bit = self.bit_of(arg, range.start, loc);
+            let zero = self.reg(Kind::make_bool(), loc);
+            self.lop(
+                tl::OpCode::Unary(tl::Unary {
+                    lhs: zero,
+                    op: tl::AluUnary::Not,
+                    arg1: bit,
+                }),
+                loc,
+            );
+            return (zero, zero);
+        }
+        // Split the range so that the lower half is a power of two
+        let mid = range.start + range.len().next_power_of_two() / 2;
+        let (lo_zero, lo_count) = self.make_zero_count(arg, range.start..mid, leading, loc);
+        let (hi_zero, hi_count) = self.make_zero_count(arg, mid..range.end, leading, loc);
+        // The half that is scanned first determines the count, unless it is all zeros
+        let (first_zero, first_count, first_len, second_count) = if leading {
+            (hi_zero, hi_count, range.end - mid, lo_count)
+        } else {
+            (lo_zero, lo_count, mid - range.start, hi_count)
+        };
+        let kind = Kind::Bits((usize::BITS - range.len().leading_zeros()) as usize);
+        let first_count = self.cast_into(first_count, kind, CastKind::Resize, loc);
+        let second_count = self.cast_into(second_count, kind, CastKind::Resize, loc);
+        let offset = self.lit_from_fn(kind, |ndx| first_len & (1 << ndx) != 0, loc);
+        let through = self.binary_into(tl::AluBinary::Add, second_count, offset, kind, loc);
+        let count = self.select_into(first_zero, through, first_count, kind, loc);
+        let zero = self.binary_into(
+            tl::AluBinary::BitAnd,
+            lo_zero,
+            hi_zero,
+            Kind::make_bool(),
+            loc,
+        );
+        (zero, count)
+    }
+    fn make_reverse_bits(&mut self, lhs: Operand, arg: Operand, loc: SourceLocation) {
+        let len = self.operand_bit_width(arg);
+        let args = (0..len)
+            .rev()
+            .map(|ndx| self.bit_of(arg, ndx, loc))
+            .collect();
+        self.lop(tl::OpCode::Concat(tl::Concat { lhs, args }), loc);
+    }
     fn make_unary(&mut self, unary: &hf::Unary, loc: SourceLocation) -> Result<()> {
         let hf::Unary { lhs, op, arg1 } = *unary;
         if self.object.kind(lhs).is_empty() {
@@ -1080,6 +1354,22 @@ impl<'a> RTLCompiler<'a> {
             hf::AluUnary::XShr(cnt) => self.make_xshr(lhs, arg1, cnt, loc),
             hf::AluUnary::XNeg => self.make_xneg(lhs, arg1, loc),
             hf::AluUnary::XSgn => self.make_xsgn(lhs, arg1, loc),
+            hf::AluUnary::CountOnes => self.make_count_ones(lhs, arg1, loc),
+            hf::AluUnary::LeadingZeros | hf::AluUnary::TrailingZeros => {
+                let len = self.operand_bit_width(arg1);
+                let leading = op == hf::AluUnary::LeadingZeros;
+                let (_, count) = self.make_zero_count(arg1, 0..len, leading, loc);
+                self.lop(
+                    tl::OpCode::Cast(tl::Cast {
+                        lhs,
+                        arg: count,
+                        len,
+                        kind: CastKind::Resize,
+                    }),
+                    loc,
+                );
+            }
+            hf::AluUnary::ReverseBits => self.make_reverse_bits(lhs, arg1, loc),
         };
         Ok(())
     }
@@ -9,6 +9,7 @@
 // KernelFn (ret)
 
 use log::debug;
+use rhdl_bits::fixed::{FixedFormat, OverflowMode, RoundingMode};
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 use std::collections::HashMap;
@@ -77,6 +78,8 @@ fn binop_to_alu(op: BinOp) -> AluBinary {
         BinOp::Add | BinOp::AddAssign => AluBinary::Add,
         BinOp::Sub | BinOp::SubAssign => AluBinary::Sub,
         BinOp::Mul | BinOp::MulAssign => AluBinary::Mul,
+        BinOp::Div | BinOp::DivAssign => AluBinary::Div,
+        BinOp::Rem | BinOp::RemAssign => AluBinary::Rem,
         BinOp::BitXor | BinOp::BitXorAssign => AluBinary::BitXor,
         BinOp::And | BinOp::BitAnd | BinOp::BitAndAssign => AluBinary::BitAnd,
         BinOp::Or | BinOp::BitOr | BinOp::BitOrAssign => AluBinary::BitOr,
@@ -532,13 +535,17 @@ impl<'a> MirContext<'a> {
         self.op(op_array(lhs, elements), id);
         Ok(lhs)
     }
-    fn arm(&mut self, target: Slot, lhs: Slot, arm: &Arm) -> Result<CaseArgument> {
+    // Compile a match arm.  Returns the case argument for the arm, and the
+    // result of evaluating the arm guard (if any).
+    fn arm(&mut self, target: Slot, lhs: Slot, arm: &Arm) -> Result<(CaseArgument, Option<Slot>)> {
         match &arm.kind {
             ArmKind::Wild => {
+                let guard = self.arm_guard(arm)?;
                 self.wrap_expr_in_block(lhs, &arm.body)?;
-                Ok(CaseArgument::Wild)
+                Ok((CaseArgument::Wild, guard))
             }
             ArmKind::Constant(constant) => {
+                let guard = self.arm_guard(arm)?;
                 self.wrap_expr_in_block(lhs, &arm.body)?;
                 let value = self.lit(arm.id, constant.value.clone());
                 let disc = self.reg(arm.id);
@@ -550,7 +557,7 @@ impl<'a> MirContext<'a> {
                     ),
                     arm.id,
                 );
-                Ok(CaseArgument::Slot(disc))
+                Ok((CaseArgument::Slot(disc), guard))
             }
             ArmKind::Enum(arm_enum) => {
                 log::trace!("arm enum {:?}", arm_enum);
@@ -576,13 +583,71 @@ impl<'a> MirContext<'a> {
                 let payload = self.reg(arm_enum.pat.id);
                 self.op(op_index(payload, target, path), arm_enum.pat.id);
                 self.initialize_local(&arm_enum.pat, payload)?;
+                // The guard can refer to the bindings of the pattern
+                let guard = self.arm_guard(arm)?;
                 let result = self.expr(&arm.body)?;
                 self.op(op_assign(lhs, result), arm_enum.pat.id);
                 self.end_scope();
-                Ok(CaseArgument::Slot(discriminant_slot))
+                Ok((CaseArgument::Slot(discriminant_slot), guard))
             }
         }
     }
+    fn arm_guard(&mut self, arm: &Arm) -> Result<Option<Slot>> {
+        arm.guard.as_ref().map(|guard| self.expr(guard)).transpose()
+    }
+    // Compute the condition under which an arm is taken.  This is the
+    // test of the discriminant against the arm, qualified by the guard.
+    // A `None` result means the arm is always taken.
+    fn arm_condition(
+        &mut self,
+        id: NodeId,
+        discriminant: Slot,
+        argument: &CaseArgument,
+        guard: Option<Slot>,
+    ) -> Option<Slot> {
+        let test = match argument {
+            CaseArgument::Slot(slot) => {
+                let test = self.reg(id);
+                self.op(op_binary(AluBinary::Eq, test, discriminant, *slot), id);
+                Some(test)
+            }
+            CaseArgument::Wild => None,
+        };
+        match (test, guard) {
+            (Some(test), Some(guard)) => {
+                let cond = self.reg(id);
+                self.op(op_binary(AluBinary::BitAnd, cond, test, guard), id);
+                Some(cond)
+            }
+            (test, guard) => test.or(guard),
+        }
+    }
+    // Build a priority select chain, so that `lhs` takes the value associated
+    // with the first condition that is true.  The last value is the fallback,
+    // and its condition is ignored, since the match is known to be exhaustive.
+    fn priority_select(
+        &mut self,
+        id: NodeId,
+        lhs: Slot,
+        conditions: &[Option<Slot>],
+        values: &[Slot],
+    ) {
+        let Some((&last, rest)) = values.split_last() else {
+            return;
+        };
+        let mut result = last;
+        for (cond, &value) in conditions.iter().zip(rest).rev() {
+            result = match cond {
+                Some(cond) => {
+                    let select = self.reg(id);
+                    self.op(op_select(select, *cond, value, result), id);
+                    select
+                }
+                None => value,
+            };
+        }
+        self.op(op_assign(lhs, result), id);
+    }
     fn assign(&mut self, id: NodeId, assign: &ExprAssign) -> Result<Slot> {
         let rhs = self.expr(&assign.rhs)?;
         let (rebind, path) = self.expr_lhs(&assign.lhs)?;
@@ -615,6 +680,8 @@ impl<'a> MirContext<'a> {
             BinOp::AddAssign => AluBinary::Add,
             BinOp::SubAssign => AluBinary::Sub,
             BinOp::MulAssign => AluBinary::Mul,
+            BinOp::DivAssign => AluBinary::Div,
+            BinOp::RemAssign => AluBinary::Rem,
             BinOp::BitXorAssign => AluBinary::BitXor,
             BinOp::BitAndAssign => AluBinary::BitAnd,
             BinOp::BitOrAssign => AluBinary::BitOr,
@@ -667,6 +734,8 @@ impl<'a> MirContext<'a> {
             BinOp::AddAssign
                 | BinOp::SubAssign
                 | BinOp::MulAssign
+                | BinOp::DivAssign
+                | BinOp::RemAssign
                 | BinOp::BitXorAssign
                 | BinOp::BitAndAssign
                 | BinOp::ShlAssign
@@ -845,9 +914,139 @@ impl<'a> MirContext<'a> {
                     _ => self.op(op_wrap(lhs, args[0], *wrap_op), id),
                 };
             }
+            KernelFnKind::Quantize {
+                from,
+                to,
+                rounding,
+                overflow,
+            } => self.quantize(id, lhs, args[0], *from, *to, *rounding, *overflow),
         }
         Ok(lhs)
     }
+    // Lower a fixed point quantization into shifts, adds and (for
+    // saturation) a pair of compare and selects.
+    #[allow(clippy::too_many_arguments)]
+    fn quantize(
+        &mut self,
+        id: NodeId,
+        lhs: Slot,
+        arg: Slot,
+        from: FixedFormat,
+        to: FixedFormat,
+        rounding: RoundingMode,
+        overflow: OverflowMode,
+    ) {
+        let kind = |len: usize| {
+            if from.signed {
+                Kind::make_signed(len)
+            } else {
+                Kind::make_bits(len)
+            }
+        };
+        // A literal of the given width, with the bits set by `bit`
+        let literal = |len: usize, bit: &dyn Fn(usize) -> bool| {
+            let bits = (0..len).map(|ndx| bit(ndx).into()).collect();
+            TypedBits::new(bits, kind(len))
+        };
+        let width = from.width();
+        self.bind_slot_to_type(arg, kind(width));
+        self.bind_slot_to_type(lhs, kind(to.width()));
+        let (value, value_width) = if from.frac > to.frac {
+            let shift = from.frac - to.frac;
+            // Extend by one bit so that adding the rounding bias cannot overflow
+            let extended = self.reg(id);
+            self.bind_slot_to_type(extended, kind(width + 1));
+            self.op(op_resize(extended, arg, width + 1), id);
+            let rounded = match rounding {
+                RoundingMode::Truncate => extended,
+                RoundingMode::HalfUp | RoundingMode::Convergent => {
+                    let bias = if rounding == RoundingMode::HalfUp {
+                        let half = literal(width + 1, &|ndx| ndx == shift - 1);
+                        self.literal_tb(id, &half)
+                    } else {
+                        // Ties round to even, so the bias is one less than half,
+                        // plus the LSB of the truncated value
+                        let almost_half = literal(width + 1, &|ndx| ndx < shift - 1);
+                        let almost_half = self.literal_tb(id, &almost_half);
+                        let truncated = self.reg(id);
+                        self.bind_slot_to_type(truncated, kind(width + 1 - shift));
+                        self.op(op_unary(AluUnary::XShr(shift), truncated, extended), id);
+                        let lsb_bits = if from.signed {
+                            let unsigned = self.reg(id);
+                            self.bind_slot_to_type(unsigned, Kind::make_bits(width + 1 - shift));
+                            self.op(op_unary(AluUnary::Unsigned, unsigned, truncated), id);
+                            unsigned
+                        } else {
+                            truncated
+                        };
+                        let lsb = self.reg(id);
+                        self.bind_slot_to_type(lsb, Kind::make_bits(1));
+                        self.op(op_resize(lsb, lsb_bits, 1), id);
+                        let lsb_extended = self.reg(id);
+                        self.bind_slot_to_type(lsb_extended, Kind::make_bits(width + 1));
+                        self.op(op_resize(lsb_extended, lsb, width + 1), id);
+                        let lsb = if from.signed {
+                            let signed = self.reg(id);
+                            self.bind_slot_to_type(signed, kind(width + 1));
+                            self.op(op_unary(AluUnary::Signed, signed, lsb_extended), id);
+                            signed
+                        } else {
+                            lsb_extended
+                        };
+                        let bias = self.reg(id);
+                        self.bind_slot_to_type(bias, kind(width + 1));
+                        self.op(op_binary(AluBinary::Add, bias, almost_half, lsb), id);
+                        bias
+                    };
+                    let rounded = self.reg(id);
+                    self.bind_slot_to_type(rounded, kind(width + 1));
+                    self.op(op_binary(AluBinary::Add, rounded, extended, bias), id);
+                    rounded
+                }
+            };
+            let shifted = self.reg(id);
+            self.bind_slot_to_type(shifted, kind(width + 1 - shift));
+            self.op(op_unary(AluUnary::XShr(shift), shifted, rounded), id);
+            (shifted, width + 1 - shift)
+        } else if from.frac < to.frac {
+            let shift = to.frac - from.frac;
+            let shifted = self.reg(id);
+            self.bind_slot_to_type(shifted, kind(width + shift));
+            self.op(op_unary(AluUnary::XShl(shift), shifted, arg), id);
+            (shifted, width + shift)
+        } else {
+            (arg, width)
+        };
+        let value = if overflow == OverflowMode::Saturate && to.width() < value_width {
+            let (max, min) = if from.signed {
+                let top = to.width() - 1;
+                (
+                    literal(value_width, &|ndx| ndx < top),
+                    literal(value_width, &|ndx| ndx >= top),
+                )
+            } else {
+                (
+                    literal(value_width, &|ndx| ndx < to.width()),
+                    literal(value_width, &|_| false),
+                )
+            };
+            let mut clamped = value;
+            for (limit, cmp) in [(max, AluBinary::Gt), (min, AluBinary::Lt)] {
+                let limit = self.literal_tb(id, &limit);
+                let out_of_range = self.reg(id);
+                self.bind_slot_to_type(out_of_range, Kind::make_bool());
+                self.op(op_binary(cmp, out_of_range, value, limit), id);
+                let next = self.reg(id);
+                self.bind_slot_to_type(next, kind(value_width));
+                self.op(op_select(next, out_of_range, limit, clamped), id);
+                clamped = next;
+            }
+            clamped
+        } else {
+            value
+        };
+        self.op(op_resize(lhs, value, to.width()), id);
+    }
 
     fn expr_list(&mut self, exprs: &[Expr]) -> Result<Vec<Slot>> {
         exprs.iter().map(|expr| self.expr(expr)).collect()
@@ -1021,6 +1220,7 @@ impl<'a> MirContext<'a> {
         let active_arm = Arm {
             id,
             kind: if_let_expr.kind.clone(),
+            guard: None,
             body: Box::new(Expr {
                 id: if_let_expr.then_block.id,
                 kind: ExprKind::Block(ExprBlock {
@@ -1031,6 +1231,7 @@ impl<'a> MirContext<'a> {
         let else_arm = Arm {
             id,
             kind: ArmKind::Wild,
+            guard: None,
             body: if_let_expr
                 .else_branch
                 .clone()
@@ -1134,16 +1335,33 @@ impl<'a> MirContext<'a> {
         // Need to handle local rebindings in the bodies of the arms.
         let locals_prior_to_match = self.locals().clone();
         let mut arguments = vec![];
+        let mut guards = vec![];
         let mut arm_locals = vec![];
         let mut arm_lhs = vec![];
         for arm in &match_expr.arms {
             self.set_locals(&locals_prior_to_match, id)?;
             let lhs = self.reg(id);
-            let disc = self.arm(target, lhs, arm)?;
+            let (disc, guard) = self.arm(target, lhs, arm)?;
             arm_lhs.push(lhs);
             arguments.push(disc);
+            guards.push(guard);
             arm_locals.push(self.locals().clone());
         }
+        // If any of the arms are guarded, then the match cannot be expressed
+        // as a case on the discriminant.  Instead, the arms are tested in order,
+        // and the result is selected from the first arm whose pattern and guard
+        // both match.
+        let conditions = if guards.iter().any(Option::is_some) {
+            Some(
+                arguments
+                    .iter()
+                    .zip(guards)
+                    .map(|(argument, guard)| self.arm_condition(id, discriminant, argument, guard))
+                    .collect::<Vec<_>>(),
+            )
+        } else {
+            None
+        };
         self.set_locals(&locals_prior_to_match, id)?;
         let mut rebound_locals = BTreeSet::new();
         for branch_locals in &arm_locals {
@@ -1169,16 +1387,25 @@ impl<'a> MirContext<'a> {
                     })
                 })
                 .collect::<Result<Vec<_>>>()?;
-            let cases = arguments
-                .iter()
-                .cloned()
-                .zip(arm_bindings.into_iter().cloned())
-                .collect::<Vec<_>>();
             let new_binding = rebind.to;
-            self.op(op_case(new_binding, discriminant, cases), id);
+            if let Some(conditions) = &conditions {
+                let values = arm_bindings.into_iter().cloned().collect::<Vec<_>>();
+                self.priority_select(id, new_binding, conditions, &values);
+            } else {
+                let cases = arguments
+                    .iter()
+                    .cloned()
+                    .zip(arm_bindings.into_iter().cloned())
+                    .collect::<Vec<_>>();
+                self.op(op_case(new_binding, discriminant, cases), id);
+            }
+        }
+        if let Some(conditions) = &conditions {
+            self.priority_select(id, lhs, conditions, &arm_lhs);
+        } else {
+            let match_expr_table = arguments.iter().cloned().zip(arm_lhs).collect::<Vec<_>>();
+            self.op(op_case(lhs, discriminant, match_expr_table), id);
         }
-        let match_expr_table = arguments.iter().cloned().zip(arm_lhs).collect::<Vec<_>>();
-        self.op(op_case(lhs, discriminant, match_expr_table), id);
         Ok(lhs)
     }
     fn resize(&mut self, id: NodeId, cast: &ExprMethodCall) -> Result<Slot> {
@@ -1221,7 +1448,18 @@ impl<'a> MirContext<'a> {
         let arg = self.expr(&method_call.receiver)?;
         // These are all no-ops as far as RHDL is concerned.
         // They exist to satisfy the Rust type system
-        if ["raw", "dyn_bits", "as_bits", "as_signed_bits"].contains(&method_call.method) {
+        if [
+            "raw",
+            "dyn_bits",
+            "as_bits",
+            "as_signed_bits",
+            "as_wide_bits",
+            "as_signed_wide_bits",
+            "as_fixed",
+            "as_signed_fixed",
+        ]
+        .contains(&method_call.method)
+        {
             self.op(op_assign(lhs, arg), id);
             return Ok(lhs);
         }
@@ -1229,6 +1467,10 @@ impl<'a> MirContext<'a> {
             "xadd" => Some(AluBinary::XAdd),
             "xsub" => Some(AluBinary::XSub),
             "xmul" => Some(AluBinary::XMul),
+            "saturating_add" => Some(AluBinary::SaturatingAdd),
+            "saturating_sub" => Some(AluBinary::SaturatingSub),
+            "overflowing_add" => Some(AluBinary::OverflowingAdd),
+            "overflowing_sub" => Some(AluBinary::OverflowingSub),
             _ => None,
         } {
             let rhs = self.expr(&method_call.args[0])?;
@@ -1243,6 +1485,10 @@ impl<'a> MirContext<'a> {
             "as_signed" => AluUnary::Signed,
             "xneg" => AluUnary::XNeg,
             "xsgn" => AluUnary::XSgn,
+            "count_ones" => AluUnary::CountOnes,
+            "leading_zeros" => AluUnary::LeadingZeros,
+            "trailing_zeros" => AluUnary::TrailingZeros,
+            "reverse_bits" => AluUnary::ReverseBits,
             // The `val` method is a special case used to strip the clocking context
             // from a signal.
             "val" => AluUnary::Val,
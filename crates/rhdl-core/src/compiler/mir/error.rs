@@ -51,7 +51,7 @@ pub enum TypeCheck {
     #[error("Cannot determine the sign of this value")]
     ExpectedSignFlag,
     #[error(
-        "Expression causes an overflow in bit widths (currently a maximum of 128 bits is supported)"
+        "Expression causes an overflow in bit widths (currently a maximum of 512 bits is supported)"
     )]
     BitWidthOverflow,
 }
@@ -14,7 +14,7 @@ use crate::{
     ast::ast_impl::{ExprLit, WrapOp},
     compiler::mir::{
         error::{RHDLSyntaxError, RHDLTypeCheckError, Syntax},
-        ty::SignFlag,
+        ty::{Const, SignFlag},
     },
     error::RHDLError,
     rhif::{
@@ -96,7 +96,7 @@ type Result<T> = std::result::Result<T, RHDLError>;
 
 */
 
-const MAX_BIT_SIZE: usize = 128;
+const MAX_BIT_SIZE: usize = rhdl_bits::MAX_BITS;
 
 impl<'a> MirTypeInference<'a> {
     fn new(mir: &'a Mir) -> Self {
@@ -307,6 +307,14 @@ impl<'a> MirTypeInference<'a> {
                 let lhs_ty = self.ctx.ty_with_sign_and_len(loc, a1_sign, len);
                 self.unify(loc, op.lhs, lhs_ty)?;
             }
+            AluUnary::CountOnes | AluUnary::LeadingZeros | AluUnary::TrailingZeros => {
+                // The count is unsigned, and has the same width as the argument
+                let Some(a1_len) = self.ctx.project_bit_length(a1) else {
+                    return Ok(());
+                };
+                let lhs_ty = self.ctx.ty_bits(loc, a1_len);
+                self.unify(loc, op.lhs, lhs_ty)?;
+            }
             AluUnary::XNeg | AluUnary::XSgn => {
                 let Some(a1_len) = self.ctx.project_bit_length(a1) else {
                     return Ok(());
@@ -330,9 +338,21 @@ impl<'a> MirTypeInference<'a> {
             | AluBinary::BitOr
             | AluBinary::BitXor
             | AluBinary::Mul
-            | AluBinary::Sub => {
+            | AluBinary::Div
+            | AluBinary::Rem
+            | AluBinary::Sub
+            | AluBinary::SaturatingAdd
+            | AluBinary::SaturatingSub => {
                 self.enforce_data_types_binary(loc, op.lhs, op.arg1, op.arg2)?;
             }
+            AluBinary::OverflowingAdd | AluBinary::OverflowingSub => {
+                // The result is a tuple of the wrapped value and the overflow flag
+                let value = self.ctx.ty_var(loc);
+                self.enforce_data_types_binary(loc, value, op.arg1, op.arg2)?;
+                let flag = self.ctx.ty_bool(loc);
+                let lhs_ty = self.ctx.ty_tuple(loc, vec![value, flag]);
+                self.unify(loc, op.lhs, lhs_ty)?;
+            }
             AluBinary::XAdd => {
                 self.try_xadd_xmul(loc, op.lhs, op.arg1, op.arg2, |a, b| a.max(b) + 1)?;
             }
@@ -852,7 +872,7 @@ impl<'a> MirTypeInference<'a> {
                     let lhs = self.slot_ty(unary.lhs);
                     let arg1 = self.slot_ty(unary.arg1);
                     match unary.op {
-                        AluUnary::Not => {
+                        AluUnary::Not | AluUnary::ReverseBits => {
                             self.unify(loc, lhs, arg1)?;
                         }
                         AluUnary::Neg => {
@@ -877,7 +897,10 @@ impl<'a> MirTypeInference<'a> {
                         | AluUnary::XShl(_)
                         | AluUnary::XShr(_)
                         | AluUnary::XNeg
-                        | AluUnary::XSgn => {
+                        | AluUnary::XSgn
+                        | AluUnary::CountOnes
+                        | AluUnary::LeadingZeros
+                        | AluUnary::TrailingZeros => {
                             self.type_ops.push(TypeOperation {
                                 loc: op.loc,
                                 kind: TypeOperationKind::UnaryOp(TypeUnaryOp {
@@ -1036,6 +1059,19 @@ pub fn infer(mir: Mir) -> Result<Object> {
     debug!("Recheck delayed inference rules");
     infer.try_type_ops(5, &type_ops)?;
 
+    debug!("Try to name Option and Result types from their payloads");
+    if !infer.all_slots_resolved() {
+        for lop in mir.ops.iter() {
+            if let OpCode::Wrap(wrap) = &lop.op {
+                let ty = infer.slot_ty(wrap.lhs);
+                if let Some((name, full_name)) = infer.ctx.ty_option_or_result_name(ty) {
+                    let full_name = infer.ctx.ty_const(lop.loc, Const::String(full_name.into()));
+                    infer.unify(lop.loc, name, full_name)?;
+                }
+            }
+        }
+    }
+
     if let Some(ty) = infer.unresolved_slot_typeid() {
         debug!("=================================");
         debug!("Inference failed");
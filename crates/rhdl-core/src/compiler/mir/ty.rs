@@ -494,6 +494,37 @@ impl UnifyContext {
         )
     }
 
+    /// The enums built by `Some(x)`, `None`, `Ok(x)` and `Err(e)` only learn
+    /// their name when unified with a declared type.  If that never happens,
+    /// returns the (unresolved) name of the enum, and a name built from the
+    /// payload types in the style of the [Option] and [Result] kinds.
+    pub fn ty_option_or_result_name(&mut self, ty: TypeId) -> Option<(TypeId, String)> {
+        let ty = self.apply(ty);
+        let TypeKind::App(AppType::Enum(enumerate)) = ty.kind.as_ref() else {
+            return None;
+        };
+        let name = enumerate.name;
+        if !self.is_unresolved(name) {
+            return None;
+        }
+        let tags = enumerate
+            .variants
+            .iter()
+            .map(|(tag, _)| tag.name.as_str())
+            .collect::<Vec<_>>();
+        let mut payload = |variant: &str| {
+            let ty = self.ty_variant(ty, variant).ok()?;
+            let ty = self.ty_index(ty, 0).ok()?;
+            self.into_kind(ty).ok().map(|kind| kind.get_name())
+        };
+        let full_name = match tags.as_slice() {
+            ["None", "Some"] => format!("Option::<{}>", payload("Some")?),
+            ["Err", "Ok"] => format!("Result::<{}, {}>", payload("Ok")?, payload("Err")?),
+            _ => return None,
+        };
+        Some((name, full_name))
+    }
+
     pub fn ty_with_sign_and_len(
         &mut self,
         loc: SourceLocation,
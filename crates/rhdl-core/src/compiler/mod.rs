@@ -9,4 +9,6 @@ mod stage3;
 pub use stage1::CompilationMode;
 mod lower_rhif_to_rtl;
 mod ntl_passes;
-pub use stage3::optimize_ntl;
+pub use stage3::{
+    OptimizationLevel, optimize_ntl, optimize_ntl_with_level, with_optimization_level,
+};
@@ -0,0 +1,20 @@
+use crate::{
+    RHDLError,
+    ntl::{Object, aig::optimize_netlist},
+};
+
+use super::pass::Pass;
+
+#[derive(Default, Debug, Clone)]
+pub struct AigOptimize {}
+
+// The single bit logic is optimized as an and-inverter graph.  The
+// result is cleaned up by another round of the simplification passes.
+impl Pass for AigOptimize {
+    fn description() -> &'static str {
+        "And-inverter graph optimization"
+    }
+    fn run(input: Object) -> Result<Object, RHDLError> {
+        Ok(optimize_netlist(input))
+    }
+}
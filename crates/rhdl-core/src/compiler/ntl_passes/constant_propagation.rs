@@ -106,6 +106,8 @@ fn compute_vector(
                 VectorOp::Add => AluBinary::Add,
                 VectorOp::Sub => AluBinary::Sub,
                 VectorOp::Mul => AluBinary::Mul,
+                VectorOp::Div => AluBinary::Div,
+                VectorOp::Rem => AluBinary::Rem,
                 VectorOp::Eq => AluBinary::Eq,
                 VectorOp::Ne => AluBinary::Ne,
                 VectorOp::Lt => AluBinary::Lt,
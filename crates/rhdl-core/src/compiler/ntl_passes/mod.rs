@@ -1,3 +1,4 @@
+pub mod aig_optimize;
 pub mod check_for_undriven;
 pub mod constant_propagation;
 pub mod constant_reg_elimination;
@@ -12,6 +13,7 @@ pub mod remove_extra_registers;
 pub mod reorder_instructions;
 pub mod single_write;
 pub mod symbol_table_is_complete;
+pub mod value_numbering;
 /*
 pub mod check_for_unconnected_clock_reset;
 pub mod check_for_undriven;
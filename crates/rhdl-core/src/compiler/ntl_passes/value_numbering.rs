@@ -0,0 +1,203 @@
+use std::collections::{HashMap, hash_map::Entry};
+
+use crate::{
+    RHDLError,
+    common::symtab::RegisterId,
+    compiler::ntl_passes::pass::Pass,
+    ntl::{
+        object::{LocatedOpCode, Object},
+        spec::{
+            Assign, Binary, BinaryOp, Case, CaseEntry, Not, OpCode, Select, Unary, UnaryOp, Vector,
+            VectorOp, Wire, WireKind,
+        },
+        visit::visit_wires,
+    },
+};
+
+// The value computed by a pure opcode.  Two opcodes that produce
+// the same value are redundant, and only one needs to be kept.
+#[derive(PartialEq, Eq, Hash)]
+enum Value {
+    Binary(BinaryOp, Wire, Wire),
+    Not(Wire),
+    Select(Wire, Wire, Wire),
+    Case(Vec<Wire>, Vec<(CaseEntry, Wire)>),
+    Vector(VectorOp, Vec<Wire>, Vec<Wire>, bool),
+    Unary(UnaryOp, Vec<Wire>),
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct ValueNumbering {}
+
+impl Pass for ValueNumbering {
+    fn description() -> &'static str {
+        "Value numbering of pure opcodes"
+    }
+    fn run(mut input: Object) -> Result<Object, RHDLError> {
+        // Only wires that are driven exactly once are safe to number
+        let mut writes: HashMap<RegisterId<WireKind>, usize> = HashMap::default();
+        for lop in &input.ops {
+            visit_wires(&lop.op, |sense, wire| {
+                if let (true, Some(reg)) = (sense.is_write(), wire.reg()) {
+                    *writes.entry(reg).or_default() += 1;
+                }
+            });
+        }
+        let single_driver = |op: &OpCode| {
+            let mut ok = true;
+            visit_wires(op, |_sense, wire| {
+                if let Some(reg) = wire.reg() {
+                    ok &= writes.get(&reg).copied().unwrap_or_default() <= 1;
+                }
+            });
+            ok
+        };
+        let mut values: HashMap<Value, Vec<Wire>> = HashMap::default();
+        let mut ops = Vec::with_capacity(input.ops.len());
+        for lop in std::mem::take(&mut input.ops) {
+            if !single_driver(&lop.op) {
+                ops.push(lop);
+                continue;
+            }
+            let (lhs, value) = match &lop.op {
+                OpCode::Binary(Binary {
+                    op,
+                    lhs,
+                    arg1,
+                    arg2,
+                }) => {
+                    // All of the binary ops are commutative
+                    let (arg1, arg2) = if arg2 < arg1 {
+                        (*arg2, *arg1)
+                    } else {
+                        (*arg1, *arg2)
+                    };
+                    (vec![*lhs], Value::Binary(*op, arg1, arg2))
+                }
+                OpCode::Not(Not { lhs, arg }) => (vec![*lhs], Value::Not(*arg)),
+                OpCode::Select(Select {
+                    lhs,
+                    selector,
+                    true_case,
+                    false_case,
+                }) => (
+                    vec![*lhs],
+                    Value::Select(*selector, *true_case, *false_case),
+                ),
+                OpCode::Case(Case {
+                    lhs,
+                    discriminant,
+                    entries,
+                }) => (
+                    vec![*lhs],
+                    Value::Case(discriminant.clone(), entries.clone()),
+                ),
+                OpCode::Vector(Vector {
+                    op,
+                    lhs,
+                    arg1,
+                    arg2,
+                    signed,
+                }) => {
+                    let commutative = matches!(
+                        op,
+                        VectorOp::Add | VectorOp::Mul | VectorOp::Eq | VectorOp::Ne
+                    );
+                    let (arg1, arg2) = if commutative && arg2 < arg1 {
+                        (arg2.clone(), arg1.clone())
+                    } else {
+                        (arg1.clone(), arg2.clone())
+                    };
+                    (lhs.clone(), Value::Vector(*op, arg1, arg2, *signed))
+                }
+                OpCode::Unary(Unary { op, lhs, arg }) => {
+                    (lhs.clone(), Value::Unary(*op, arg.clone()))
+                }
+                OpCode::Noop | OpCode::Assign(_) | OpCode::BlackBox(_) => {
+                    ops.push(lop);
+                    continue;
+                }
+            };
+            match values.entry(value) {
+                Entry::Occupied(entry) => {
+                    // Replace the recomputation with copies, which will be
+                    // collapsed by the register removal pass.
+                    ops.extend(
+                        lhs.into_iter()
+                            .zip(entry.get())
+                            .map(|(lhs, rhs)| LocatedOpCode {
+                                op: OpCode::Assign(Assign { lhs, rhs: *rhs }),
+                                loc: lop.loc,
+                            }),
+                    );
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(lhs);
+                    ops.push(lop);
+                }
+            }
+        }
+        input.ops = ops;
+        Ok(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Kind, ntl::object::WireDetails};
+
+    use super::*;
+
+    fn count_xors(object: &Object) -> usize {
+        object
+            .ops
+            .iter()
+            .filter(|lop| {
+                matches!(
+                    lop.op,
+                    OpCode::Binary(Binary {
+                        op: BinaryOp::Xor,
+                        ..
+                    })
+                )
+            })
+            .count()
+    }
+
+    #[test]
+    fn test_redundant_ops_are_replaced_with_copies() -> Result<(), RHDLError> {
+        let mut object = Object::default();
+        let mut wire = |bit| {
+            object.symtab.reg(
+                (),
+                WireDetails {
+                    source_details: None,
+                    kind: Kind::make_bits(4),
+                    bit,
+                },
+            )
+        };
+        let (a, b, c, d) = (wire(0), wire(1), wire(2), wire(3));
+        // c <- a ^ b, d <- b ^ a
+        for (lhs, arg1, arg2) in [(c, a, b), (d, b, a)] {
+            object.ops.push(LocatedOpCode {
+                op: OpCode::Binary(Binary {
+                    op: BinaryOp::Xor,
+                    lhs,
+                    arg1,
+                    arg2,
+                }),
+                loc: None,
+            });
+        }
+        object.outputs = vec![c, d];
+        assert_eq!(count_xors(&object), 2);
+        let object = ValueNumbering::run(object)?;
+        assert_eq!(count_xors(&object), 1);
+        assert!(matches!(
+            object.ops[1].op,
+            OpCode::Assign(Assign { lhs, rhs }) if lhs == d && rhs == c
+        ));
+        Ok(())
+    }
+}
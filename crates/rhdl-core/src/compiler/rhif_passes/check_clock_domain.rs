@@ -11,7 +11,7 @@ use crate::{
     error::RHDLError,
     rhif::{
         Object,
-        spec::{AluUnary, CaseArgument, OpCode, Slot},
+        spec::{AluBinary, AluUnary, CaseArgument, OpCode, Slot},
     },
     types::path::{Path, PathElement},
 };
@@ -354,7 +354,14 @@ impl ClockDomainContext<'_> {
             }
             match op {
                 OpCode::Binary(binary) => {
-                    if !binary.op.is_comparison() {
+                    // Comparisons and overflowing ops do not return a value of the
+                    // same type as their arguments, so the domain is projected onto the result.
+                    if !binary.op.is_comparison()
+                        && !matches!(
+                            binary.op,
+                            AluBinary::OverflowingAdd | AluBinary::OverflowingSub
+                        )
+                    {
                         self.unify_clocks(
                             &[binary.arg1, binary.arg2, binary.lhs],
                             loc,
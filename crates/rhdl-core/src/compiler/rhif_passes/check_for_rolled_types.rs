@@ -57,6 +57,8 @@ impl Pass for CheckForRolledTypesPass {
                         AluBinary::Add
                         | AluBinary::Sub
                         | AluBinary::Mul
+                        | AluBinary::Div
+                        | AluBinary::Rem
                         | AluBinary::BitAnd
                         | AluBinary::BitOr
                         | AluBinary::BitXor,
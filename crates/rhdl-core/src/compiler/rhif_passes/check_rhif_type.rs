@@ -143,7 +143,11 @@ fn check_type_correctness(obj: &Object) -> Result<(), RHDLError> {
                     | AluBinary::BitAnd
                     | AluBinary::BitOr
                     | AluBinary::Mul
-                    | AluBinary::BitXor,
+                    | AluBinary::Div
+                    | AluBinary::Rem
+                    | AluBinary::BitXor
+                    | AluBinary::SaturatingAdd
+                    | AluBinary::SaturatingSub,
                 lhs,
                 arg1,
                 arg2,
@@ -151,6 +155,19 @@ fn check_type_correctness(obj: &Object) -> Result<(), RHDLError> {
                 eq_kinds(slot_type(lhs), slot_type(arg1), loc)?;
                 eq_kinds(slot_type(lhs), slot_type(arg2), loc)?;
             }
+            OpCode::Binary(Binary {
+                op: AluBinary::OverflowingAdd | AluBinary::OverflowingSub,
+                lhs,
+                arg1,
+                arg2,
+            }) => {
+                eq_kinds(slot_type(arg1), slot_type(arg2), loc)?;
+                eq_kinds(
+                    slot_type(lhs),
+                    Kind::make_tuple([slot_type(arg1), Kind::make_bool()].into()),
+                    loc,
+                )?;
+            }
             OpCode::Binary(Binary {
                 op: AluBinary::XAdd,
                 lhs,
@@ -220,12 +237,19 @@ fn check_type_correctness(obj: &Object) -> Result<(), RHDLError> {
             // them for their own types.  So we need to check that they are only
             // applied to base types.
             OpCode::Unary(Unary {
-                op: AluUnary::Not | AluUnary::Neg | AluUnary::Val,
+                op: AluUnary::Not | AluUnary::Neg | AluUnary::Val | AluUnary::ReverseBits,
                 lhs,
                 arg1,
             }) => {
                 eq_kinds(slot_type(lhs), slot_type(arg1), loc)?;
             }
+            OpCode::Unary(Unary {
+                op: AluUnary::CountOnes | AluUnary::LeadingZeros | AluUnary::TrailingZeros,
+                lhs,
+                arg1,
+            }) => {
+                eq_kinds(slot_type(lhs), Kind::make_bits(slot_type(arg1).bits()), loc)?;
+            }
             OpCode::Unary(Unary {
                 op: AluUnary::All | AluUnary::Any | AluUnary::Xor,
                 lhs,
@@ -0,0 +1,152 @@
+use crate::{
+    BitX, Kind, RHDLError, TypedBits,
+    ast::SourceLocation,
+    rtl::{
+        Object,
+        object::LocatedOpCode,
+        spec::{AluBinary, Binary, OpCode, Operand},
+    },
+};
+
+use super::pass::Pass;
+
+#[derive(Default, Debug, Clone)]
+pub struct LowerDivideToShift {}
+
+// Build a literal of the given kind with the value 2^k - 1 (or its complement)
+fn mask(kind: Kind, k: usize, invert: bool) -> TypedBits {
+    let bits = (0..kind.bits())
+        .map(|i| {
+            if (i < k) ^ invert {
+                BitX::One
+            } else {
+                BitX::Zero
+            }
+        })
+        .collect();
+    TypedBits::new(bits, kind)
+}
+
+// Build an unsigned literal holding a shift amount
+fn shift_amount(k: usize) -> TypedBits {
+    let len = (usize::BITS - k.leading_zeros()).max(1) as usize;
+    let bits = (0..len)
+        .map(|i| {
+            if (k >> i) & 1 == 1 {
+                BitX::One
+            } else {
+                BitX::Zero
+            }
+        })
+        .collect();
+    TypedBits::new(bits, Kind::Bits(len))
+}
+
+// If the literal is a positive power of two, return the exponent
+fn power_of_two(literal: &TypedBits) -> Option<usize> {
+    if literal.bits().contains(&BitX::X) || literal.num_ones() != 1 {
+        return None;
+    }
+    let k = literal.trailing_zeros();
+    // A signed literal with only the sign bit set is negative
+    if literal.kind().is_signed() && k + 1 == literal.len() {
+        return None;
+    }
+    Some(k)
+}
+
+impl LowerDivideToShift {
+    fn push(
+        input: &mut Object,
+        op: AluBinary,
+        lhs: Operand,
+        arg1: Operand,
+        arg2: Operand,
+        loc: SourceLocation,
+    ) {
+        input.ops.push(LocatedOpCode {
+            op: OpCode::Binary(Binary {
+                op,
+                lhs,
+                arg1,
+                arg2,
+            }),
+            loc,
+        });
+    }
+    fn lower_unsigned(input: &mut Object, binary: &Binary, k: usize, loc: SourceLocation) {
+        let details = input.symtab[binary.arg2].clone();
+        let kind = input.kind(binary.lhs);
+        if binary.op == AluBinary::Div {
+            // x / 2^k = x >> k
+            let shift = input.symtab.lit(shift_amount(k), details);
+            Self::push(input, AluBinary::Shr, binary.lhs, binary.arg1, shift, loc);
+        } else {
+            // x % 2^k = x & (2^k - 1)
+            let mask = input.symtab.lit(mask(kind, k, false), details);
+            Self::push(input, AluBinary::BitAnd, binary.lhs, binary.arg1, mask, loc);
+        }
+    }
+    fn lower_signed(input: &mut Object, binary: &Binary, k: usize, loc: SourceLocation) {
+        // Signed division truncates towards zero, while an arithmetic shift
+        // rounds towards negative infinity.  So negative dividends are biased
+        // by 2^k - 1 before shifting:
+        //   sign = x >> (N - 1)
+        //   sum = x + (sign & (2^k - 1))
+        //   x / 2^k = sum >> k
+        //   x % 2^k = x - (sum & !(2^k - 1))
+        let details = input.symtab[binary.arg2].clone();
+        let kind = input.kind(binary.lhs);
+        let sign_shift = input
+            .symtab
+            .lit(shift_amount(kind.bits() - 1), details.clone());
+        let low_mask = input.symtab.lit(mask(kind, k, false), details.clone());
+        let sign = input.symtab.reg(kind, details.clone());
+        let bias = input.symtab.reg(kind, details.clone());
+        let sum = input.symtab.reg(kind, details.clone());
+        Self::push(input, AluBinary::Shr, sign, binary.arg1, sign_shift, loc);
+        Self::push(input, AluBinary::BitAnd, bias, sign, low_mask, loc);
+        Self::push(input, AluBinary::Add, sum, binary.arg1, bias, loc);
+        if binary.op == AluBinary::Div {
+            let shift = input.symtab.lit(shift_amount(k), details);
+            Self::push(input, AluBinary::Shr, binary.lhs, sum, shift, loc);
+        } else {
+            let high_mask = input.symtab.lit(mask(kind, k, true), details.clone());
+            let truncated = input.symtab.reg(kind, details);
+            Self::push(input, AluBinary::BitAnd, truncated, sum, high_mask, loc);
+            Self::push(
+                input,
+                AluBinary::Sub,
+                binary.lhs,
+                binary.arg1,
+                truncated,
+                loc,
+            );
+        }
+    }
+}
+
+impl Pass for LowerDivideToShift {
+    fn run(mut input: Object) -> Result<Object, RHDLError> {
+        let ops = std::mem::take(&mut input.ops);
+        for lop in ops {
+            if let OpCode::Binary(binary) = &lop.op
+                && matches!(binary.op, AluBinary::Div | AluBinary::Rem)
+                && let Operand::Literal(lit) = binary.arg2
+                && let Some(k) = power_of_two(&input.symtab[&lit])
+            {
+                if input.kind(binary.lhs).is_signed() {
+                    Self::lower_signed(&mut input, binary, k, lop.loc);
+                } else {
+                    Self::lower_unsigned(&mut input, binary, k, lop.loc);
+                }
+                continue;
+            }
+            input.ops.push(lop);
+        }
+        Ok(input)
+    }
+    fn description() -> &'static str {
+        "Lower divide and remainder by 2^N to shifts and masks"
+    }
+}
@@ -1,6 +1,7 @@
 pub(crate) mod check_no_zero_resize;
 pub(crate) mod constant_propagation;
 pub(crate) mod dead_code_elimination;
+pub(crate) mod lower_divide_to_shift;
 pub(crate) mod lower_empty_splice_to_copy;
 pub(crate) mod lower_index_all_to_copy;
 pub(crate) mod lower_multiply_to_shift;
@@ -15,3 +16,4 @@ pub(crate) mod remove_extra_registers;
 pub(crate) mod remove_unused_operands;
 pub(crate) mod strip_empty_args_from_concat;
 pub(crate) mod symbol_table_is_complete;
+pub(crate) mod value_numbering;
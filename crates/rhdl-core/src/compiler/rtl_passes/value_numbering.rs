@@ -0,0 +1,181 @@
+use std::{
+    collections::{HashMap, hash_map::Entry},
+    ops::Range,
+};
+
+use crate::{
+    Kind, RHDLError, TypedBits,
+    common::symtab::{LiteralId, RegisterId, Symbol},
+    rtl::{
+        Object,
+        spec::{
+            AluBinary, AluUnary, Assign, Binary, Case, CaseArgument, Cast, CastKind, Concat, Index,
+            OpCode, Operand, OperandKind, Select, Splice, Unary,
+        },
+        visit::visit_operands,
+    },
+};
+
+use super::pass::Pass;
+
+// The value computed by a pure opcode.  Two opcodes that produce the
+// same value (and write to the same kind of register) are redundant.
+#[derive(PartialEq, Eq, Hash)]
+enum Value {
+    Binary(AluBinary, Operand, Operand),
+    Unary(AluUnary, Operand),
+    Select(Operand, Operand, Operand),
+    Case(Operand, Vec<(CaseArgument, Operand)>),
+    Cast(Operand, usize, CastKind),
+    Concat(Vec<Operand>),
+    Index(Operand, Range<usize>),
+    Splice(Operand, Range<usize>, Operand),
+}
+
+fn is_commutative(op: AluBinary) -> bool {
+    matches!(
+        op,
+        AluBinary::Add
+            | AluBinary::Mul
+            | AluBinary::BitXor
+            | AluBinary::BitAnd
+            | AluBinary::BitOr
+            | AluBinary::Eq
+            | AluBinary::Ne
+    )
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct ValueNumberingPass {}
+
+impl Pass for ValueNumberingPass {
+    fn run(mut input: Object) -> Result<Object, RHDLError> {
+        // Literals with the same value are interchangeable, so map each
+        // literal to the first literal that holds its value.
+        let mut first_lit: HashMap<&TypedBits, LiteralId<OperandKind>> = HashMap::default();
+        let lit_map: HashMap<LiteralId<OperandKind>, LiteralId<OperandKind>> = input
+            .symtab
+            .iter_lit()
+            .map(|(lid, (value, _))| (lid, *first_lit.entry(value).or_insert(lid)))
+            .collect();
+        let canon = |op: Operand| match op {
+            Symbol::Literal(lid) => Symbol::Literal(lit_map[&lid]),
+            Symbol::Register(_) => op,
+        };
+        // Only registers that are written exactly once are safe to number
+        let mut writes: HashMap<RegisterId<OperandKind>, usize> = HashMap::default();
+        for lop in &input.ops {
+            visit_operands(&lop.op, |sense, op| {
+                if let (true, Some(reg)) = (sense.is_write(), op.reg()) {
+                    *writes.entry(reg).or_default() += 1;
+                }
+            });
+        }
+        let single_assignment = |op: &OpCode| {
+            let mut ok = true;
+            visit_operands(op, |_sense, op| {
+                if let Some(reg) = op.reg() {
+                    ok &= writes.get(&reg).copied().unwrap_or_default() <= 1;
+                }
+            });
+            ok
+        };
+        let mut values: HashMap<(Value, Kind), Operand> = HashMap::default();
+        for lop in input.ops.iter_mut() {
+            if !single_assignment(&lop.op) {
+                continue;
+            }
+            let (lhs, value) = match &lop.op {
+                OpCode::Binary(Binary {
+                    op,
+                    lhs,
+                    arg1,
+                    arg2,
+                }) => {
+                    let (mut arg1, mut arg2) = (canon(*arg1), canon(*arg2));
+                    if is_commutative(*op) && arg2 < arg1 {
+                        std::mem::swap(&mut arg1, &mut arg2);
+                    }
+                    (*lhs, Value::Binary(*op, arg1, arg2))
+                }
+                OpCode::Unary(Unary { op, lhs, arg1 }) => (*lhs, Value::Unary(*op, canon(*arg1))),
+                OpCode::Select(Select {
+                    lhs,
+                    cond,
+                    true_value,
+                    false_value,
+                }) => (
+                    *lhs,
+                    Value::Select(canon(*cond), canon(*true_value), canon(*false_value)),
+                ),
+                OpCode::Case(Case {
+                    lhs,
+                    discriminant,
+                    table,
+                }) => {
+                    let table = table
+                        .iter()
+                        .map(|(arg, value)| {
+                            let arg = match arg {
+                                CaseArgument::Literal(lit) => CaseArgument::Literal(lit_map[lit]),
+                                CaseArgument::Wild => CaseArgument::Wild,
+                            };
+                            (arg, canon(*value))
+                        })
+                        .collect();
+                    (*lhs, Value::Case(canon(*discriminant), table))
+                }
+                OpCode::Cast(Cast {
+                    lhs,
+                    arg,
+                    len,
+                    kind,
+                }) => (*lhs, Value::Cast(canon(*arg), *len, *kind)),
+                OpCode::Concat(Concat { lhs, args }) => (
+                    *lhs,
+                    Value::Concat(args.iter().copied().map(canon).collect()),
+                ),
+                OpCode::Index(Index {
+                    lhs,
+                    arg,
+                    bit_range,
+                    path: _,
+                }) => (*lhs, Value::Index(canon(*arg), bit_range.clone())),
+                OpCode::Splice(Splice {
+                    lhs,
+                    orig,
+                    bit_range,
+                    value,
+                    path: _,
+                }) => (
+                    *lhs,
+                    Value::Splice(canon(*orig), bit_range.clone(), canon(*value)),
+                ),
+                OpCode::Noop | OpCode::Assign(_) => continue,
+            };
+            // The kind of the result is part of the key, since the same bits
+            // can be interpreted differently (e.g., signed vs unsigned).
+            let kind = match lhs {
+                Symbol::Literal(lid) => input.symtab[lid].kind(),
+                Symbol::Register(rid) => input.symtab[rid],
+            };
+            match values.entry((value, kind)) {
+                Entry::Occupied(entry) => {
+                    // Replace the recomputation with a copy.  The copy will
+                    // be collapsed by the register removal pass.
+                    lop.op = OpCode::Assign(Assign {
+                        lhs,
+                        rhs: *entry.get(),
+                    });
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(lhs);
+                }
+            }
+        }
+        Ok(input)
+    }
+    fn description() -> &'static str {
+        "Value numbering of pure opcodes"
+    }
+}
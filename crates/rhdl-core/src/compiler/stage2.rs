@@ -5,6 +5,7 @@ use crate::{
         rtl_passes::{
             check_no_zero_resize::CheckNoZeroResize, constant_propagation::ConstantPropagationPass,
             dead_code_elimination::DeadCodeEliminationPass,
+            lower_divide_to_shift::LowerDivideToShift,
             lower_empty_splice_to_copy::LowerEmptySpliceToCopy,
             lower_index_all_to_copy::LowerIndexAllToCopy,
             lower_multiply_to_shift::LowerMultiplyToShift,
@@ -17,7 +18,7 @@ use crate::{
             remove_extra_registers::RemoveExtraRegistersPass,
             remove_unused_operands::RemoveUnusedOperandsPass,
             strip_empty_args_from_concat::StripEmptyArgsFromConcat,
-            symbol_table_is_complete::SymbolTableIsComplete,
+            symbol_table_is_complete::SymbolTableIsComplete, value_numbering::ValueNumberingPass,
         },
     },
     rtl,
@@ -46,10 +47,12 @@ pub(crate) fn compile(object: &crate::rhif::Object) -> Result<rtl::Object> {
         rtl = wrap_pass::<LowerIndexAllToCopy>(rtl)?;
         rtl = wrap_pass::<RemoveEmptyFunctionArguments>(rtl)?;
         rtl = wrap_pass::<LowerMultiplyToShift>(rtl)?;
+        rtl = wrap_pass::<LowerDivideToShift>(rtl)?;
         rtl = wrap_pass::<LowerShiftByConstant>(rtl)?;
         rtl = wrap_pass::<LowerShiftsByZeroToCopy>(rtl)?;
         rtl = wrap_pass::<LowerNotEqualZeroToAny>(rtl)?;
         rtl = wrap_pass::<ConstantPropagationPass>(rtl)?;
+        rtl = wrap_pass::<ValueNumberingPass>(rtl)?;
         let new_hash = rtl.hash_value();
         if new_hash == hash {
             break;
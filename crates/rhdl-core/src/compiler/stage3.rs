@@ -1,10 +1,13 @@
+use std::cell::Cell;
+
 use log::debug;
 
 use crate::{
     RHDLError,
     {
         compiler::ntl_passes::{
-            check_for_undriven::CheckForUndriven, constant_propagation::ConstantPropagationPass,
+            aig_optimize::AigOptimize, check_for_undriven::CheckForUndriven,
+            constant_propagation::ConstantPropagationPass,
             constant_reg_elimination::ConstantRegisterElimination,
             dead_code_elimination::DeadCodeElimination, lower_any_all::LowerAnyAll,
             lower_bitwise_op_with_constant::LowerBitwiseOpWithConstant, lower_case::LowerCase,
@@ -12,7 +15,7 @@ use crate::{
             remove_extra_literals::RemoveExtraLiteralsPass,
             remove_extra_registers::RemoveExtraRegistersPass,
             reorder_instructions::ReorderInstructions, single_write::SingleRegisterWrite,
-            symbol_table_is_complete::SymbolTableIsComplete,
+            symbol_table_is_complete::SymbolTableIsComplete, value_numbering::ValueNumbering,
         },
         ntl::Object,
     },
@@ -23,15 +26,15 @@ fn wrap_pass<P: Pass>(obj: Object) -> Result<Object, RHDLError> {
     P::run(obj)
 }
 
-pub fn optimize_ntl(mut input: Object) -> Result<Object, RHDLError> {
+// Run the simplification passes until the netlist stops changing
+fn simplify(mut input: Object) -> Result<Object, RHDLError> {
     let mut hash = input.hash_value();
-    input = wrap_pass::<SymbolTableIsComplete>(input)?;
-    input = wrap_pass::<CheckForUndriven>(input)?;
     loop {
         input = wrap_pass::<RemoveExtraLiteralsPass>(input)?;
         input = wrap_pass::<ConstantRegisterElimination>(input)?;
         input = wrap_pass::<LowerCase>(input)?;
         input = wrap_pass::<LowerSelects>(input)?;
+        input = wrap_pass::<ValueNumbering>(input)?;
         input = wrap_pass::<RemoveExtraRegistersPass>(input)?;
         input = wrap_pass::<ConstantPropagationPass>(input)?;
         input = wrap_pass::<LowerBitwiseOpWithConstant>(input)?;
@@ -43,6 +46,70 @@ pub fn optimize_ntl(mut input: Object) -> Result<Object, RHDLError> {
         }
         hash = new_hash;
     }
+    Ok(input)
+}
+
+/// How aggressively [optimize_ntl_with_level] rewrites the netlist.
+///
+/// The netlists of circuits (and so the timing and resource estimates
+/// made from them) are optimized at the level set by
+/// [with_optimization_level].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OptimizationLevel {
+    /// Only rewrites that propagate `X` values exactly as the
+    /// unoptimized netlist would.
+    #[default]
+    XPreserving,
+    /// Also run the and-inverter graph optimization.  This can shrink
+    /// the logic considerably, but it treats every wire as a `0` or a `1`,
+    /// so expressions like `a & !a` become `0` even when `a` is `X`.
+    Aggressive,
+}
+
+thread_local! {
+    static OPTIMIZATION_LEVEL: Cell<OptimizationLevel> =
+        const { Cell::new(OptimizationLevel::XPreserving) };
+}
+
+struct LevelGuard(OptimizationLevel);
+
+impl Drop for LevelGuard {
+    fn drop(&mut self) {
+        OPTIMIZATION_LEVEL.with(|level| level.set(self.0));
+    }
+}
+
+/// Run the closure with every netlist built inside of it (like the netlist
+/// of a circuit descriptor) optimized at the given level.
+///
+/// ```rust,ignore
+/// let report = with_optimization_level(OptimizationLevel::Aggressive, || {
+///     resources::estimate_resources(&uut, &resources::LutModel::default())
+/// })?;
+/// ```
+pub fn with_optimization_level<R>(level: OptimizationLevel, f: impl FnOnce() -> R) -> R {
+    let previous = OPTIMIZATION_LEVEL.with(|cell| cell.replace(level));
+    let _guard = LevelGuard(previous);
+    f()
+}
+
+/// Optimize the netlist at the level set by [with_optimization_level],
+/// which is [OptimizationLevel::XPreserving] unless it has been changed.
+pub fn optimize_ntl(input: Object) -> Result<Object, RHDLError> {
+    optimize_ntl_with_level(input, OPTIMIZATION_LEVEL.with(|cell| cell.get()))
+}
+
+pub fn optimize_ntl_with_level(
+    mut input: Object,
+    level: OptimizationLevel,
+) -> Result<Object, RHDLError> {
+    input = wrap_pass::<SymbolTableIsComplete>(input)?;
+    input = wrap_pass::<CheckForUndriven>(input)?;
+    input = simplify(input)?;
+    if level == OptimizationLevel::Aggressive {
+        input = wrap_pass::<AigOptimize>(input)?;
+        input = simplify(input)?;
+    }
     input = wrap_pass::<SymbolTableIsComplete>(input)?;
     input = wrap_pass::<SingleRegisterWrite>(input)?;
     input = wrap_pass::<ReorderInstructions>(input)?;
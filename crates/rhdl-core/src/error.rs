@@ -51,6 +51,20 @@ pub enum RHDLError {
     CannotConvertKernelFunctionToVerilogDescriptor { value: Box<KernelFnKind> },
     #[error("Verilog Verification Error in RTL: Expected {expected:?} got {actual:?}")]
     VerilogVerificationErrorRTL { expected: String, actual: String },
+    #[error("Netlist Verification Error at time {time}: Expected {expected:?} got {actual:?}")]
+    NetListVerificationError {
+        time: u64,
+        expected: TypedBits,
+        actual: TypedBits,
+    },
+    #[error("Co-simulation mismatch in cycle {cycle} at time {time}:\n{diff}")]
+    CosimMismatch {
+        cycle: u64,
+        time: u64,
+        expected: TypedBits,
+        actual: TypedBits,
+        diff: String,
+    },
     #[error("Verilog verification error: {0}")]
     VerilogVerificationErrorString(String),
     #[error("Testbench Construction Error: {0}")]
@@ -61,6 +75,8 @@ pub enum RHDLError {
     SynError(#[from] syn::Error),
     #[error("Top module export error: {0}")]
     ExportError(#[from] crate::circuit::fixture::ExportError),
+    #[error("Foreign module error: {0}")]
+    ForeignError(#[from] crate::circuit::foreign::ForeignError),
     #[error("This module is not synthesizable")]
     NotSynthesizable,
     #[error("Netlist Error")]
@@ -78,6 +94,12 @@ pub enum RHDLError {
     HDLNotAvailable { name: String },
     #[error("Netlist not available for circuit {name}")]
     NetlistNotAvailable { name: String },
+    #[error("VHDL not available for black box {name}")]
+    VHDLNotAvailable { name: String },
+    #[error("SMT solver error: {0}")]
+    SmtSolverError(String),
+    #[error("Kernel signature error: {0}")]
+    KernelSignatureError(String),
 }
 
 pub fn rhdl_error<T>(error: T) -> RHDLError
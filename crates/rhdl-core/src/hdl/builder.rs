@@ -169,6 +169,8 @@ impl TranslationContext<'_> {
             tl::AluBinary::Add => vlog::kw_ops::BinaryOp::Plus,
             tl::AluBinary::Sub => vlog::kw_ops::BinaryOp::Minus,
             tl::AluBinary::Mul => vlog::kw_ops::BinaryOp::Mul,
+            tl::AluBinary::Div => vlog::kw_ops::BinaryOp::Div,
+            tl::AluBinary::Rem => vlog::kw_ops::BinaryOp::Mod,
             tl::AluBinary::BitXor => vlog::kw_ops::BinaryOp::Xor,
             tl::AluBinary::BitAnd => vlog::kw_ops::BinaryOp::And,
             tl::AluBinary::BitOr => vlog::kw_ops::BinaryOp::Or,
@@ -182,7 +184,28 @@ impl TranslationContext<'_> {
             tl::AluBinary::Gt => vlog::kw_ops::BinaryOp::Gt,
         };
         log::trace!("Translating binary op: {lhs} = {arg1} {op:?} {arg2}");
-        self.add_stmt(parse_quote! { #lhs = #arg1 #op #arg2 });
+        // Verilog yields X when dividing by zero.  Instead, the quotient is all
+        // ones and the remainder is the dividend, as in rhdl-bits and the VM.
+        match binary.op {
+            tl::AluBinary::Div => {
+                // The all ones value must be signed too, or Verilog would
+                // carry out the division in an unsigned context.
+                let arg_kind = self.rtl.kind(binary.arg1);
+                let width = syn::Index::from(arg_kind.bits());
+                let ones: vlog::Expr = if arg_kind.is_signed() {
+                    parse_quote! { $signed({#width{1'b1}}) }
+                } else {
+                    parse_quote! { {#width{1'b1}} }
+                };
+                self.add_stmt(parse_quote! {
+                    #lhs = (#arg2 == 0) ? #ones : #arg1 #op #arg2
+                });
+            }
+            tl::AluBinary::Rem => {
+                self.add_stmt(parse_quote! { #lhs = (#arg2 == 0) ? #arg1 : #arg1 #op #arg2 });
+            }
+            _ => self.add_stmt(parse_quote! { #lhs = #arg1 #op #arg2 }),
+        }
         Ok(())
     }
     fn translate_case(&mut self, case: &tl::Case) -> Result<()> {
@@ -243,6 +243,8 @@ fn binop(ast: AluBinary) -> &'static str {
         AluBinary::Add => "+",
         AluBinary::Sub => "-",
         AluBinary::Mul => "*",
+        AluBinary::Div => "/",
+        AluBinary::Rem => "%",
         AluBinary::BitAnd => "&",
         AluBinary::BitOr => "|",
         AluBinary::BitXor => "^",
@@ -1,3 +1,4 @@
 //pub mod ast;
 pub mod builder;
 //pub mod formatter;
+pub mod vhdl;
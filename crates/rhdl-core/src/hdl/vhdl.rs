@@ -0,0 +1,134 @@
+//! Helpers for writing VHDL-2008 text
+//!
+//! RHDL carries VHDL as text rather than as an AST.  These helpers take care
+//! of the details that are easy to get wrong by hand, like the ordering of bits
+//! in vector literals and the form of an entity declaration.  They are used by
+//! the netlist VHDL generator, and by black box cores that provide their own VHDL.
+use std::ops::Range;
+
+use crate::bitx::BitX;
+
+/// The direction of a port on an entity
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Direction {
+    /// An input port
+    In,
+    /// An output port
+    Out,
+    /// A bidirectional port
+    InOut,
+}
+
+/// A VHDL `std_logic` literal for a single bit.
+pub fn bit_literal(bit: BitX) -> &'static str {
+    match bit {
+        BitX::Zero => "'0'",
+        BitX::One => "'1'",
+        BitX::X => "'X'",
+    }
+}
+
+/// A VHDL `std_logic_vector` literal.  The bits are given LSB first, as they
+/// are everywhere else in RHDL, and written MSB first.
+pub fn vector_literal(bits: &[BitX]) -> String {
+    let body = bits
+        .iter()
+        .rev()
+        .map(|b| match b {
+            BitX::Zero => '0',
+            BitX::One => '1',
+            BitX::X => 'X',
+        })
+        .collect::<String>();
+    format!("\"{body}\"")
+}
+
+/// The index range of a vector slice, e.g., `7 downto 4` for the bits `4..8`.
+pub fn range(bits: &Range<usize>) -> String {
+    format!("{} downto {}", bits.end - 1, bits.start)
+}
+
+/// The type of a vector of the given width, e.g., `std_logic_vector(7 downto 0)`.
+pub fn slv(width: usize) -> String {
+    format!("std_logic_vector({})", range(&(0..width)))
+}
+
+/// A port declaration.  Returns `None` if the port has no bits, since VHDL
+/// does not allow empty ports.
+pub fn port(name: &str, direction: Direction, width: usize) -> Option<String> {
+    (width != 0).then(|| {
+        let direction = match direction {
+            Direction::In => "in",
+            Direction::Out => "out",
+            Direction::InOut => "inout",
+        };
+        format!("{name} : {direction} {}", slv(width))
+    })
+}
+
+/// Build a complete entity and architecture.  The `declarations` are placed in
+/// the declarative part of the architecture, and the `body` in its statement part.
+/// Each should be a complete line, including any trailing semicolon.
+pub fn entity(name: &str, ports: &[String], declarations: &[String], body: &[String]) -> String {
+    let mut text = String::new();
+    text.push_str("library ieee;\n");
+    text.push_str("use ieee.std_logic_1164.all;\n");
+    text.push_str("use ieee.numeric_std.all;\n\n");
+    text.push_str(&format!("entity {name} is\n"));
+    if !ports.is_empty() {
+        text.push_str("    port (\n");
+        text.push_str(&format!("        {}\n", ports.join(";\n        ")));
+        text.push_str("    );\n");
+    }
+    text.push_str(&format!("end entity {name};\n\n"));
+    text.push_str(&format!("architecture rtl of {name} is\n"));
+    for decl in declarations {
+        text.push_str(&format!("    {decl}\n"));
+    }
+    text.push_str("begin\n");
+    for stmt in body {
+        for line in stmt.lines() {
+            text.push_str(&format!("    {line}\n"));
+        }
+    }
+    text.push_str("end architecture rtl;\n");
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vector_literal_is_msb_first() {
+        let bits = [BitX::One, BitX::Zero, BitX::X, BitX::Zero];
+        assert_eq!(vector_literal(&bits), "\"0X01\"");
+    }
+
+    #[test]
+    fn test_entity_layout() {
+        let ports = [port("i", Direction::In, 4), port("o", Direction::Out, 4)]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        let text = entity("top", &ports, &[], &["o <= not i;".into()]);
+        let expect = expect_test::expect![[r#"
+            library ieee;
+            use ieee.std_logic_1164.all;
+            use ieee.numeric_std.all;
+
+            entity top is
+                port (
+                    i : in std_logic_vector(3 downto 0);
+                    o : out std_logic_vector(3 downto 0)
+                );
+            end entity top;
+
+            architecture rtl of top is
+            begin
+                o <= not i;
+            end architecture rtl;
+        "#]];
+        expect.assert_eq(&text);
+    }
+}
@@ -12,6 +12,7 @@ pub use circuit::hdl_descriptor::HDLDescriptor;
 pub use circuit::synchronous::Synchronous;
 pub use circuit::synchronous::SynchronousDQ;
 pub use circuit::synchronous::SynchronousIO;
+pub use circuit::vhdl_descriptor::VHDLDescriptor;
 pub use types::clock::Clock;
 pub use types::digital::Digital;
 pub use types::digital_fn::DigitalFn;
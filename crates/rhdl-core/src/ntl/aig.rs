@@ -0,0 +1,1143 @@
+//! And-inverter graph optimization of netlists
+//!
+//! A netlist [Object] is already bit blasted, but its single bit logic (the
+//! [Binary], [Not] and [Select] opcodes) keeps the shape that the
+//! RTL lowering gave it.  This module converts that logic into an
+//! and-inverter graph (AIG), in which every node is a two input AND gate,
+//! and inversions live on the edges between nodes.  Nodes are structurally
+//! hashed as they are created, so identical logic is only represented once.
+//!
+//! The graph is then optimized with a script in the style of ABC's
+//! `balance; rewrite; refactor`:
+//!
+//! - [Aig::balance] rebuilds trees of AND gates as balanced trees, to reduce
+//!   the depth of the logic.
+//! - [Aig::rewrite] looks at the logic feeding each node through small
+//!   (4 input) cuts, and replaces it with a cheaper implementation of the
+//!   same function, if one can be found.
+//! - [Aig::refactor] does the same with larger (6 input) cuts.
+//!
+//! In both cases, the replacement is built from a factored irredundant sum of
+//! products of the truth table of the cut, and is only accepted if it needs
+//! fewer nodes than the logic it frees up.
+//!
+//! Finally, the graph is converted back into netlist opcodes, recovering
+//! OR, XOR and multiplexer gates where the graph contains them.  Case
+//! statements, vector operations, reductions and black boxes are left alone,
+//! and their inputs and outputs form the boundary of the graph.  Literal `X` bits are also
+//! treated as opaque inputs, so that unknown values are not resolved by the
+//! optimizer.
+//!
+//! The rewrites are only exact for `0` and `1` values.  An `X` arriving at an
+//! input can be resolved by them (`a & !a` is `0` even when `a` is `X`), which
+//! is why the optimizer only runs at [OptimizationLevel::Aggressive].
+//!
+//! [OptimizationLevel::Aggressive]: crate::compiler::OptimizationLevel::Aggressive
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+};
+
+use crate::{
+    BitX, Kind,
+    common::symtab::RegisterId,
+    ntl::{
+        object::{LocatedOpCode, Object, WireDetails},
+        spec::{Assign, Binary, BinaryOp, Not, OpCode, Select, Wire, WireKind},
+        visit::visit_wires,
+    },
+};
+
+/// A reference to a node in an [Aig], which may invert the output of the node.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct AigLit(u32);
+
+impl AigLit {
+    /// The constant false literal.
+    pub const FALSE: AigLit = AigLit(0);
+    /// The constant true literal.
+    pub const TRUE: AigLit = AigLit(1);
+    fn new(node: usize, inverted: bool) -> Self {
+        Self(((node as u32) << 1) | inverted as u32)
+    }
+    /// The index of the node this literal refers to.
+    pub fn node(self) -> usize {
+        (self.0 >> 1) as usize
+    }
+    /// Returns true if the literal inverts the output of its node.
+    pub fn is_inverted(self) -> bool {
+        self.0 & 1 == 1
+    }
+    fn regular(self) -> Self {
+        Self(self.0 & !1)
+    }
+    fn invert_if(self, invert: bool) -> Self {
+        Self(self.0 ^ invert as u32)
+    }
+}
+
+impl std::ops::Not for AigLit {
+    type Output = AigLit;
+    fn not(self) -> AigLit {
+        Self(self.0 ^ 1)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Node {
+    Const,
+    Input(Wire),
+    And(AigLit, AigLit),
+}
+
+/// An and-inverter graph, whose inputs and outputs are netlist wires.
+#[derive(Clone, Debug)]
+pub struct Aig {
+    nodes: Vec<Node>,
+    levels: Vec<u32>,
+    strash: HashMap<(AigLit, AigLit), AigLit>,
+    inputs: HashMap<Wire, AigLit>,
+    outputs: Vec<(Wire, AigLit)>,
+    // The indices of the netlist opcodes (if any) that this graph replaces
+    absorbed: Vec<usize>,
+}
+
+impl Default for Aig {
+    fn default() -> Self {
+        Self {
+            nodes: vec![Node::Const],
+            levels: vec![0],
+            strash: HashMap::default(),
+            inputs: HashMap::default(),
+            outputs: vec![],
+            absorbed: vec![],
+        }
+    }
+}
+
+// The truth tables of the (up to 6) variables of a cut
+const VARS: [u64; 6] = [
+    0xAAAA_AAAA_AAAA_AAAA,
+    0xCCCC_CCCC_CCCC_CCCC,
+    0xF0F0_F0F0_F0F0_F0F0,
+    0xFF00_FF00_FF00_FF00,
+    0xFFFF_0000_FFFF_0000,
+    0xFFFF_FFFF_0000_0000,
+];
+
+fn cofactor0(table: u64, var: usize) -> u64 {
+    let lo = table & !VARS[var];
+    lo | (lo << (1 << var))
+}
+
+fn cofactor1(table: u64, var: usize) -> u64 {
+    let hi = table & VARS[var];
+    hi | (hi >> (1 << var))
+}
+
+// A product term over the variables of a cut.  Bit `i` of `mask` is set if
+// variable `i` appears in the term, and bit `i` of `phase` gives its polarity.
+#[derive(Copy, Clone, Debug)]
+struct Cube {
+    mask: u8,
+    phase: u8,
+}
+
+// Compute an irredundant sum of products for a function that covers `on`,
+// and is covered by `upper`, using the Minato-Morreale algorithm.  Returns
+// the cubes and the function they implement.
+fn isop(on: u64, upper: u64, num_vars: usize) -> (Vec<Cube>, u64) {
+    if on == 0 {
+        return (vec![], 0);
+    }
+    if upper == !0 {
+        return (vec![Cube { mask: 0, phase: 0 }], !0);
+    }
+    let Some(var) = (0..num_vars).rev().find(|&var| {
+        cofactor0(on, var) != cofactor1(on, var) || cofactor0(upper, var) != cofactor1(upper, var)
+    }) else {
+        // Both bounds are constant, and on != 0, so upper == !0.
+        return (vec![Cube { mask: 0, phase: 0 }], !0);
+    };
+    let (on0, on1) = (cofactor0(on, var), cofactor1(on, var));
+    let (upper0, upper1) = (cofactor0(upper, var), cofactor1(upper, var));
+    let (cubes0, f0) = isop(on0 & !upper1, upper0, var);
+    let (cubes1, f1) = isop(on1 & !upper0, upper1, var);
+    let rest = (on0 & !f0) | (on1 & !f1);
+    let (cubes2, f2) = isop(rest, upper0 & upper1, var);
+    let func = (f0 & !VARS[var]) | (f1 & VARS[var]) | f2;
+    let bit = 1 << var;
+    let cubes = cubes0
+        .into_iter()
+        .map(|cube| Cube {
+            mask: cube.mask | bit,
+            phase: cube.phase,
+        })
+        .chain(cubes1.into_iter().map(|cube| Cube {
+            mask: cube.mask | bit,
+            phase: cube.phase | bit,
+        }))
+        .chain(cubes2)
+        .collect();
+    (cubes, func)
+}
+
+fn merge_cuts(a: &[usize], b: &[usize], max_size: usize) -> Option<Vec<usize>> {
+    let mut cut = Vec::with_capacity(max_size);
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        let next = match (a.get(i), b.get(j)) {
+            (Some(&x), Some(&y)) if x == y => {
+                i += 1;
+                j += 1;
+                x
+            }
+            (Some(&x), Some(&y)) if x < y => {
+                i += 1;
+                x
+            }
+            (Some(&x), None) => {
+                i += 1;
+                x
+            }
+            (_, Some(&y)) => {
+                j += 1;
+                y
+            }
+            (None, None) => unreachable!(),
+        };
+        if cut.len() == max_size {
+            return None;
+        }
+        cut.push(next);
+    }
+    Some(cut)
+}
+
+fn mapped(map: &[AigLit], lit: AigLit) -> AigLit {
+    map[lit.node()].invert_if(lit.is_inverted())
+}
+
+impl Aig {
+    /// The literal for the given wire, which is added as an input
+    /// to the graph if needed.
+    pub fn input(&mut self, wire: Wire) -> AigLit {
+        if let Some(lit) = self.inputs.get(&wire) {
+            return *lit;
+        }
+        let lit = self.push(Node::Input(wire), 0);
+        self.inputs.insert(wire, lit);
+        lit
+    }
+    /// Drive the given wire with a literal from the graph.
+    pub fn output(&mut self, wire: Wire, lit: AigLit) {
+        self.outputs.push((wire, lit));
+    }
+    fn push(&mut self, node: Node, level: u32) -> AigLit {
+        self.nodes.push(node);
+        self.levels.push(level);
+        AigLit::new(self.nodes.len() - 1, false)
+    }
+    fn level(&self, lit: AigLit) -> u32 {
+        self.levels[lit.node()]
+    }
+    /// The AND of two literals.  Trivial cases are simplified, and
+    /// an existing node is reused if one computes the same function.
+    pub fn and(&mut self, a: AigLit, b: AigLit) -> AigLit {
+        let (a, b) = if a <= b { (a, b) } else { (b, a) };
+        if a == AigLit::FALSE || a == !b {
+            return AigLit::FALSE;
+        }
+        if a == AigLit::TRUE || a == b {
+            return b;
+        }
+        if let Some(lit) = self.strash.get(&(a, b)) {
+            return *lit;
+        }
+        let level = 1 + self.level(a).max(self.level(b));
+        let lit = self.push(Node::And(a, b), level);
+        self.strash.insert((a, b), lit);
+        lit
+    }
+    /// The OR of two literals.
+    pub fn or(&mut self, a: AigLit, b: AigLit) -> AigLit {
+        !self.and(!a, !b)
+    }
+    /// The XOR of two literals.
+    pub fn xor(&mut self, a: AigLit, b: AigLit) -> AigLit {
+        let both = self.and(a, b);
+        let neither = self.and(!a, !b);
+        self.and(!both, !neither)
+    }
+    /// Select `on_true` if `sel` is true, and `on_false` otherwise.
+    pub fn mux(&mut self, sel: AigLit, on_true: AigLit, on_false: AigLit) -> AigLit {
+        let on_true = self.and(sel, on_true);
+        let on_false = self.and(!sel, on_false);
+        self.or(on_true, on_false)
+    }
+    // The AND of a set of literals, built as a tree that combines
+    // the shallowest literals first.
+    fn and_all(&mut self, mut lits: Vec<AigLit>) -> AigLit {
+        lits.sort();
+        lits.dedup();
+        // A literal and its inverse are adjacent after sorting
+        if lits.windows(2).any(|pair| pair[0] == !pair[1]) {
+            return AigLit::FALSE;
+        }
+        let mut heap: BinaryHeap<Reverse<(u32, AigLit)>> = lits
+            .into_iter()
+            .map(|lit| Reverse((self.level(lit), lit)))
+            .collect();
+        loop {
+            let Some(Reverse((_, a))) = heap.pop() else {
+                return AigLit::TRUE;
+            };
+            let Some(Reverse((_, b))) = heap.pop() else {
+                return a;
+            };
+            let c = self.and(a, b);
+            heap.push(Reverse((self.level(c), c)));
+        }
+    }
+    // Mark the nodes that contribute to an output.  Fanins always
+    // have lower indices than the nodes that use them, so a single
+    // backwards sweep is enough.
+    fn live(&self) -> Vec<bool> {
+        let mut live = vec![false; self.nodes.len()];
+        for (_, lit) in &self.outputs {
+            live[lit.node()] = true;
+        }
+        for ndx in (0..self.nodes.len()).rev() {
+            if let (true, Node::And(a, b)) = (live[ndx], self.nodes[ndx]) {
+                live[a.node()] = true;
+                live[b.node()] = true;
+            }
+        }
+        live
+    }
+    // Count the number of references to each live node
+    fn fanouts(&self, live: &[bool]) -> Vec<u32> {
+        let mut refs = vec![0; self.nodes.len()];
+        for (_, lit) in &self.outputs {
+            refs[lit.node()] += 1;
+        }
+        for (ndx, node) in self.nodes.iter().enumerate() {
+            if let (true, Node::And(a, b)) = (live[ndx], node) {
+                refs[a.node()] += 1;
+                refs[b.node()] += 1;
+            }
+        }
+        refs
+    }
+    /// The number of AND nodes needed to compute the outputs.
+    pub fn and_count(&self) -> usize {
+        self.live()
+            .into_iter()
+            .zip(&self.nodes)
+            .filter(|(live, node)| *live && matches!(node, Node::And(..)))
+            .count()
+    }
+    /// The largest number of AND nodes between an input and an output.
+    pub fn depth(&self) -> u32 {
+        self.outputs
+            .iter()
+            .map(|(_, lit)| self.level(*lit))
+            .max()
+            .unwrap_or_default()
+    }
+    /// Evaluate the outputs of the graph, given the values of its inputs.
+    pub fn eval(&self, input: impl Fn(Wire) -> bool) -> Vec<bool> {
+        let mut values = vec![false; self.nodes.len()];
+        for (ndx, node) in self.nodes.iter().enumerate() {
+            values[ndx] = match *node {
+                Node::Const => false,
+                Node::Input(wire) => input(wire),
+                Node::And(a, b) => {
+                    (values[a.node()] ^ a.is_inverted()) && (values[b.node()] ^ b.is_inverted())
+                }
+            }
+        }
+        self.outputs
+            .iter()
+            .map(|(_, lit)| values[lit.node()] ^ lit.is_inverted())
+            .collect()
+    }
+    // An empty graph that replaces the same netlist opcodes as this one
+    fn empty_like(&self) -> Aig {
+        Aig {
+            absorbed: self.absorbed.clone(),
+            ..Default::default()
+        }
+    }
+    // Copy the live nodes of the graph into a new graph, using `build`
+    // to construct each of the AND nodes from the mapped nodes.
+    fn rebuild(&self, mut build: impl FnMut(&mut Aig, usize, &[AigLit]) -> AigLit) -> Aig {
+        let live = self.live();
+        let mut aig = self.empty_like();
+        let mut map = vec![AigLit::FALSE; self.nodes.len()];
+        for ndx in 0..self.nodes.len() {
+            if !live[ndx] {
+                continue;
+            }
+            map[ndx] = match self.nodes[ndx] {
+                Node::Const => AigLit::FALSE,
+                Node::Input(wire) => aig.input(wire),
+                Node::And(..) => build(&mut aig, ndx, &map),
+            };
+        }
+        aig.outputs = self
+            .outputs
+            .iter()
+            .map(|&(wire, lit)| (wire, mapped(&map, lit)))
+            .collect();
+        aig
+    }
+    // Remove any nodes that do not contribute to an output
+    fn cleanup(&self) -> Aig {
+        self.rebuild(|aig, ndx, map| {
+            let Node::And(a, b) = self.nodes[ndx] else {
+                unreachable!()
+            };
+            aig.and(mapped(map, a), mapped(map, b))
+        })
+    }
+    /// Rebuild each tree of AND gates as a balanced tree, to reduce
+    /// the depth of the graph.  Trees are only collected through nodes
+    /// with a single fanout, so that no logic is duplicated.
+    pub fn balance(&self) -> Aig {
+        let refs = self.fanouts(&self.live());
+        self.rebuild(|aig, ndx, map| {
+            let mut leaves = vec![];
+            let mut stack = vec![AigLit::new(ndx, false)];
+            while let Some(lit) = stack.pop() {
+                match self.nodes[lit.node()] {
+                    Node::And(a, b)
+                        if lit.node() == ndx || (!lit.is_inverted() && refs[lit.node()] == 1) =>
+                    {
+                        stack.push(a);
+                        stack.push(b);
+                    }
+                    _ => leaves.push(mapped(map, lit)),
+                }
+            }
+            aig.and_all(leaves)
+        })
+        .cleanup()
+    }
+    /// Replace the logic feeding each node through a cut of up to 4 inputs
+    /// with a smaller implementation, if one exists.
+    pub fn rewrite(&self) -> Aig {
+        self.resynthesize(4, 8)
+    }
+    /// Replace the logic feeding each node through a cut of up to 6 inputs
+    /// with a smaller implementation, if one exists.
+    pub fn refactor(&self) -> Aig {
+        self.resynthesize(6, 4)
+    }
+    /// Run the `balance; rewrite; refactor; balance` script, keeping the
+    /// result of each step only if it improves the graph.  The number of
+    /// nodes is improved first, and the depth second.
+    pub fn optimize(&self) -> Aig {
+        let mut best = self.cleanup();
+        let script: [fn(&Aig) -> Aig; 4] =
+            [Aig::balance, Aig::rewrite, Aig::refactor, Aig::balance];
+        for step in script {
+            let next = step(&best);
+            if (next.and_count(), next.depth()) < (best.and_count(), best.depth()) {
+                best = next;
+            }
+        }
+        best
+    }
+    // Enumerate up to `limit` cuts of at most `max_size` leaves for each live
+    // node, plus the trivial cut that contains only the node itself.
+    fn enumerate_cuts(&self, max_size: usize, limit: usize, live: &[bool]) -> Vec<Vec<Vec<usize>>> {
+        let mut cuts: Vec<Vec<Vec<usize>>> = vec![vec![]; self.nodes.len()];
+        for ndx in 0..self.nodes.len() {
+            if !live[ndx] {
+                continue;
+            }
+            let mut node_cuts: Vec<Vec<usize>> = vec![];
+            if let Node::And(a, b) = self.nodes[ndx] {
+                for cut_a in &cuts[a.node()] {
+                    for cut_b in &cuts[b.node()] {
+                        if let Some(cut) = merge_cuts(cut_a, cut_b, max_size)
+                            && !node_cuts.contains(&cut)
+                        {
+                            node_cuts.push(cut);
+                        }
+                    }
+                }
+                node_cuts.sort_by_key(|cut| cut.len());
+                node_cuts.truncate(limit);
+            }
+            node_cuts.push(vec![ndx]);
+            cuts[ndx] = node_cuts;
+        }
+        cuts
+    }
+    // The truth table of a node in terms of the leaves of one of its cuts
+    fn truth_table(&self, ndx: usize, memo: &mut HashMap<usize, u64>) -> u64 {
+        if let Some(table) = memo.get(&ndx) {
+            return *table;
+        }
+        let table = match self.nodes[ndx] {
+            Node::And(a, b) => {
+                let a_table =
+                    self.truth_table(a.node(), memo) ^ if a.is_inverted() { !0 } else { 0 };
+                let b_table =
+                    self.truth_table(b.node(), memo) ^ if b.is_inverted() { !0 } else { 0 };
+                a_table & b_table
+            }
+            Node::Const => 0,
+            Node::Input(_) => unreachable!("cut does not separate the node from the inputs"),
+        };
+        memo.insert(ndx, table);
+        table
+    }
+    // The nodes that would no longer be needed if `ndx` were computed
+    // directly from the leaves of the cut (the maximum fanout free cone).
+    fn mffc(&self, ndx: usize, cut: &[usize], refs: &mut [u32]) -> Vec<usize> {
+        let mut members = vec![];
+        let mut stack = vec![ndx];
+        while let Some(node) = stack.pop() {
+            if cut.contains(&node) {
+                continue;
+            }
+            let Node::And(a, b) = self.nodes[node] else {
+                continue;
+            };
+            members.push(node);
+            for fanin in [a.node(), b.node()] {
+                refs[fanin] -= 1;
+                if refs[fanin] == 0 {
+                    stack.push(fanin);
+                }
+            }
+        }
+        for &node in &members {
+            if let Node::And(a, b) = self.nodes[node] {
+                refs[a.node()] += 1;
+                refs[b.node()] += 1;
+            }
+        }
+        members
+    }
+    // Build a sum of products in factored form.  The literal that appears in
+    // the most cubes is factored out, until no literal is shared.
+    fn build_sop(&mut self, cubes: &[Cube], leaves: &[AigLit]) -> AigLit {
+        if cubes.is_empty() {
+            return AigLit::FALSE;
+        }
+        if cubes.iter().any(|cube| cube.mask == 0) {
+            return AigLit::TRUE;
+        }
+        let cube_lit = |var: usize, phase: u8| leaves[var].invert_if(phase & (1 << var) == 0);
+        let mut best: Option<(usize, usize, bool)> = None;
+        for var in 0..leaves.len() {
+            for phase in [false, true] {
+                let count = cubes
+                    .iter()
+                    .filter(|cube| {
+                        cube.mask & (1 << var) != 0 && (cube.phase & (1 << var) != 0) == phase
+                    })
+                    .count();
+                if count > 1 && best.is_none_or(|(best_count, ..)| count > best_count) {
+                    best = Some((count, var, phase));
+                }
+            }
+        }
+        let Some((_, var, phase)) = best else {
+            let terms = cubes
+                .iter()
+                .map(|cube| {
+                    let lits = (0..leaves.len())
+                        .filter(|var| cube.mask & (1 << var) != 0)
+                        .map(|var| cube_lit(var, cube.phase))
+                        .collect();
+                    !self.and_all(lits)
+                })
+                .collect();
+            return !self.and_all(terms);
+        };
+        let bit = 1 << var;
+        let (with, without): (Vec<Cube>, Vec<Cube>) = cubes
+            .iter()
+            .partition(|cube| cube.mask & bit != 0 && (cube.phase & bit != 0) == phase);
+        let quotient = with
+            .into_iter()
+            .map(|cube| Cube {
+                mask: cube.mask & !bit,
+                phase: cube.phase & !bit,
+            })
+            .collect::<Vec<_>>();
+        let lit = leaves[var].invert_if(!phase);
+        let quotient = self.build_sop(&quotient, leaves);
+        let product = self.and(lit, quotient);
+        let remainder = self.build_sop(&without, leaves);
+        self.or(product, remainder)
+    }
+    fn synthesize(&mut self, table: u64, leaves: &[AigLit]) -> AigLit {
+        let (cubes, _) = isop(table, table, leaves.len());
+        self.build_sop(&cubes, leaves)
+    }
+    // Remove all nodes from `mark` onwards
+    fn rollback(&mut self, mark: usize) {
+        for node in self.nodes.drain(mark..) {
+            if let Node::And(a, b) = node {
+                self.strash.remove(&(a, b));
+            }
+        }
+        self.levels.truncate(mark);
+    }
+    // The number of nodes that `lit` needs, that would not otherwise be
+    // kept alive.  These are the nodes created after `mark`, and the
+    // nodes in the `dying` set.
+    fn cost(&self, lit: AigLit, mark: usize, dying: &HashSet<usize>, leaves: &[AigLit]) -> usize {
+        let mut visited = HashSet::new();
+        let mut stack = vec![lit.node()];
+        let mut cost = 0;
+        while let Some(node) = stack.pop() {
+            if !visited.insert(node) || leaves.iter().any(|leaf| leaf.node() == node) {
+                continue;
+            }
+            let Node::And(a, b) = self.nodes[node] else {
+                continue;
+            };
+            if node >= mark || dying.contains(&node) {
+                cost += 1;
+                stack.push(a.node());
+                stack.push(b.node());
+            }
+        }
+        cost
+    }
+    fn resynthesize(&self, max_size: usize, limit: usize) -> Aig {
+        let live = self.live();
+        let mut refs = self.fanouts(&live);
+        let cuts = self.enumerate_cuts(max_size, limit, &live);
+        let aig = self.rebuild(|aig, ndx, map| {
+            let Node::And(a, b) = self.nodes[ndx] else {
+                unreachable!()
+            };
+            let mut best: Option<(usize, u64, &[usize])> = None;
+            for cut in &cuts[ndx] {
+                if cut.as_slice() == [ndx] {
+                    continue;
+                }
+                let freed = self.mffc(ndx, cut, &mut refs);
+                // The nodes in the new graph for the freed nodes.  The node
+                // itself has not been built yet.
+                let dying = freed
+                    .iter()
+                    .filter(|&&node| node != ndx)
+                    .map(|&node| map[node].node())
+                    .collect::<HashSet<_>>();
+                let leaves = cut.iter().map(|&leaf| map[leaf]).collect::<Vec<_>>();
+                let mut memo = cut.iter().copied().zip(VARS).collect();
+                let table = self.truth_table(ndx, &mut memo);
+                for table in [table, !table] {
+                    let mark = aig.nodes.len();
+                    let lit = aig.synthesize(table, &leaves);
+                    let cost = aig.cost(lit, mark, &dying, &leaves);
+                    aig.rollback(mark);
+                    if cost < freed.len() && best.is_none_or(|(best_cost, ..)| cost < best_cost) {
+                        best = Some((cost, table, cut));
+                    }
+                }
+            }
+            match best {
+                Some((_, table, cut)) => {
+                    let leaves = cut.iter().map(|&leaf| map[leaf]).collect::<Vec<_>>();
+                    let mut memo = cut.iter().copied().zip(VARS).collect();
+                    let inverted = self.truth_table(ndx, &mut memo) != table;
+                    aig.synthesize(table, &leaves).invert_if(inverted)
+                }
+                None => aig.and(mapped(map, a), mapped(map, b)),
+            }
+        });
+        aig.cleanup()
+    }
+}
+
+// Returns true for the opcodes that need a gate (or multiplexer)
+fn is_logic(op: &OpCode) -> bool {
+    matches!(op, OpCode::Binary(_) | OpCode::Not(_) | OpCode::Select(_))
+}
+
+fn logic_lhs(op: &OpCode) -> Option<Wire> {
+    match op {
+        OpCode::Assign(Assign { lhs, .. })
+        | OpCode::Binary(Binary { lhs, .. })
+        | OpCode::Not(Not { lhs, .. })
+        | OpCode::Select(Select { lhs, .. }) => Some(*lhs),
+        _ => None,
+    }
+}
+
+// The gate used to compute a literal when converting back to a netlist
+enum Gate {
+    Wire(Wire),
+    Const(bool),
+    And(AigLit, AigLit),
+    Or(AigLit, AigLit),
+    Xor(AigLit, AigLit),
+    Select(AigLit, AigLit, AigLit),
+    Not(AigLit),
+}
+
+impl Gate {
+    fn args(&self) -> Vec<AigLit> {
+        match *self {
+            Gate::Wire(_) | Gate::Const(_) => vec![],
+            Gate::And(a, b) | Gate::Or(a, b) | Gate::Xor(a, b) => vec![a, b],
+            Gate::Select(s, t, f) => vec![s, t, f],
+            Gate::Not(a) => vec![a],
+        }
+    }
+}
+
+impl Aig {
+    /// Build a graph from the single bit logic in a netlist.  The outputs
+    /// of the graph are the wires that are needed by the rest of the netlist.
+    /// Returns `None` if the logic contains a combinatorial loop.
+    pub fn from_netlist(object: &Object) -> Option<Aig> {
+        // Only wires with a single driver can be absorbed into the graph
+        let mut writes: HashMap<RegisterId<WireKind>, usize> = HashMap::default();
+        for lop in &object.ops {
+            visit_wires(&lop.op, |sense, wire| {
+                if let (true, Some(reg)) = (sense.is_write(), wire.reg()) {
+                    *writes.entry(reg).or_default() += 1;
+                }
+            });
+        }
+        let mut driver: HashMap<RegisterId<WireKind>, usize> = HashMap::default();
+        let mut absorbed = vec![false; object.ops.len()];
+        for (ndx, lop) in object.ops.iter().enumerate() {
+            if let Some(reg) = logic_lhs(&lop.op).and_then(Wire::reg)
+                && writes[&reg] == 1
+            {
+                driver.insert(reg, ndx);
+                absorbed[ndx] = true;
+            }
+        }
+        // The wires written by the graph that are read elsewhere
+        let mut roots = vec![];
+        let mut seen = HashSet::new();
+        let mut add_root = |wire: &Wire| {
+            if let Some(reg) = wire.reg()
+                && driver.contains_key(&reg)
+                && seen.insert(reg)
+            {
+                roots.push(*wire);
+            }
+        };
+        for (lop, _) in object
+            .ops
+            .iter()
+            .zip(&absorbed)
+            .filter(|(_, absorbed)| !**absorbed)
+        {
+            visit_wires(&lop.op, |sense, wire| {
+                if sense.is_read() {
+                    add_root(wire);
+                }
+            });
+        }
+        object.outputs.iter().for_each(&mut add_root);
+        let mut aig = Aig {
+            absorbed: (0..object.ops.len()).filter(|&ndx| absorbed[ndx]).collect(),
+            ..Default::default()
+        };
+        let mut lits = HashMap::default();
+        for root in roots {
+            let lit = aig.convert(object, &driver, root, &mut lits)?;
+            aig.output(root, lit);
+        }
+        Some(aig)
+    }
+    fn wire_lit(
+        &mut self,
+        object: &Object,
+        lits: &HashMap<RegisterId<WireKind>, AigLit>,
+        wire: Wire,
+    ) -> AigLit {
+        match (wire.reg(), object.bitx(wire)) {
+            (Some(reg), _) => lits[&reg],
+            (None, Some(BitX::Zero)) => AigLit::FALSE,
+            (None, Some(BitX::One)) => AigLit::TRUE,
+            _ => self.input(wire),
+        }
+    }
+    // Convert the logic that drives a wire into the graph.  The logic is
+    // walked with an explicit stack, as the chains can be long.
+    fn convert(
+        &mut self,
+        object: &Object,
+        driver: &HashMap<RegisterId<WireKind>, usize>,
+        wire: Wire,
+        lits: &mut HashMap<RegisterId<WireKind>, AigLit>,
+    ) -> Option<AigLit> {
+        let mut in_progress = HashSet::new();
+        let mut stack = vec![(wire, false)];
+        while let Some((wire, ready)) = stack.pop() {
+            let Some(reg) = wire.reg() else {
+                continue;
+            };
+            if lits.contains_key(&reg) {
+                continue;
+            }
+            let Some(&ndx) = driver.get(&reg) else {
+                let lit = self.input(wire);
+                lits.insert(reg, lit);
+                continue;
+            };
+            let op = &object.ops[ndx].op;
+            if !ready {
+                if !in_progress.insert(reg) {
+                    return None;
+                }
+                stack.push((wire, true));
+                visit_wires(op, |sense, arg| {
+                    if sense.is_read() {
+                        stack.push((*arg, false));
+                    }
+                });
+                continue;
+            }
+            let lit = match op {
+                OpCode::Assign(Assign { rhs, .. }) => self.wire_lit(object, lits, *rhs),
+                OpCode::Binary(Binary { op, arg1, arg2, .. }) => {
+                    let arg1 = self.wire_lit(object, lits, *arg1);
+                    let arg2 = self.wire_lit(object, lits, *arg2);
+                    match op {
+                        BinaryOp::And => self.and(arg1, arg2),
+                        BinaryOp::Or => self.or(arg1, arg2),
+                        BinaryOp::Xor => self.xor(arg1, arg2),
+                    }
+                }
+                OpCode::Not(Not { arg, .. }) => !self.wire_lit(object, lits, *arg),
+                OpCode::Select(Select {
+                    selector,
+                    true_case,
+                    false_case,
+                    ..
+                }) => {
+                    let selector = self.wire_lit(object, lits, *selector);
+                    let true_case = self.wire_lit(object, lits, *true_case);
+                    let false_case = self.wire_lit(object, lits, *false_case);
+                    self.mux(selector, true_case, false_case)
+                }
+                _ => unreachable!("only logic opcodes are absorbed"),
+            };
+            in_progress.remove(&reg);
+            lits.insert(reg, lit);
+        }
+        Some(lits[&wire.reg()?])
+    }
+    // If the node computes the XOR of two literals, return them
+    fn xor_inputs(&self, node: usize) -> Option<(AigLit, AigLit)> {
+        let Node::And(a, b) = self.nodes[node] else {
+            return None;
+        };
+        if !a.is_inverted() || !b.is_inverted() {
+            return None;
+        }
+        let (Node::And(p, q), Node::And(r, s)) = (self.nodes[a.node()], self.nodes[b.node()])
+        else {
+            return None;
+        };
+        ((r == !p && s == !q) || (r == !q && s == !p)).then_some((p, q))
+    }
+    // If `x | y` is a multiplexer, return the selector and the two choices
+    fn mux_inputs(&self, x: AigLit, y: AigLit) -> Option<(AigLit, AigLit, AigLit)> {
+        let (Node::And(x0, x1), Node::And(y0, y1)) = (self.nodes[x.node()], self.nodes[y.node()])
+        else {
+            return None;
+        };
+        for (sel, on_true) in [(x0, x1), (x1, x0)] {
+            for (not_sel, on_false) in [(y0, y1), (y1, y0)] {
+                if not_sel == !sel {
+                    return Some(if sel.is_inverted() {
+                        (!sel, on_false, on_true)
+                    } else {
+                        (sel, on_true, on_false)
+                    });
+                }
+            }
+        }
+        None
+    }
+    fn gate(&self, lit: AigLit) -> Gate {
+        match self.nodes[lit.node()] {
+            Node::Const => Gate::Const(lit.is_inverted()),
+            Node::Input(wire) if !lit.is_inverted() => Gate::Wire(wire),
+            Node::Input(_) => Gate::Not(!lit),
+            Node::And(a, b) => {
+                if let Some((p, q)) = self.xor_inputs(lit.node()) {
+                    let invert = lit.is_inverted() ^ p.is_inverted() ^ q.is_inverted();
+                    return Gate::Xor(p.regular(), q.regular().invert_if(invert));
+                }
+                match (lit.is_inverted(), a.is_inverted() && b.is_inverted()) {
+                    (true, true) => match self.mux_inputs(!a, !b) {
+                        Some((sel, on_true, on_false)) => Gate::Select(sel, on_true, on_false),
+                        None => Gate::Or(!a, !b),
+                    },
+                    (true, false) => Gate::Not(!lit),
+                    // An AND of two inverted literals is cheaper as a NOR
+                    (false, true) => Gate::Not(!lit),
+                    (false, false) => Gate::And(a, b),
+                }
+            }
+        }
+    }
+    /// Replace the logic in the netlist that the graph was built from
+    /// with the logic in the graph.
+    pub fn to_netlist(&self, object: &Object) -> Object {
+        let mut object = object.clone();
+        let absorbed = self.absorbed.iter().copied().collect::<HashSet<_>>();
+        let mut ops = std::mem::take(&mut object.ops)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(ndx, lop)| (!absorbed.contains(&ndx)).then_some(lop))
+            .collect::<Vec<_>>();
+        let mut wires: HashMap<AigLit, Wire> = HashMap::default();
+        let new_wire = |object: &mut Object| {
+            object.symtab.reg(
+                (),
+                WireDetails {
+                    source_details: None,
+                    kind: Kind::make_bool(),
+                    bit: 0,
+                },
+            )
+        };
+        let push =
+            |ops: &mut Vec<LocatedOpCode>, op: OpCode| ops.push(LocatedOpCode { op, loc: None });
+        for &(output, root) in &self.outputs {
+            let mut stack = vec![(root, false)];
+            while let Some((lit, ready)) = stack.pop() {
+                if wires.contains_key(&lit) {
+                    continue;
+                }
+                let gate = self.gate(lit);
+                if !ready {
+                    stack.push((lit, true));
+                    stack.extend(gate.args().into_iter().map(|arg| (arg, false)));
+                    continue;
+                }
+                let wire = match gate {
+                    Gate::Wire(wire) => wire,
+                    Gate::Const(value) => {
+                        let value = BitX::from(value);
+                        let existing = object
+                            .symtab
+                            .iter_lit()
+                            .find(|(_, (bit, _))| *bit == value)
+                            .map(|(lid, _)| lid);
+                        match existing {
+                            Some(lid) => Wire::Literal(lid),
+                            None => object.symtab.lit(
+                                value,
+                                WireDetails {
+                                    source_details: None,
+                                    kind: Kind::make_bool(),
+                                    bit: 0,
+                                },
+                            ),
+                        }
+                    }
+                    Gate::And(a, b) | Gate::Or(a, b) | Gate::Xor(a, b) => {
+                        let op = match gate {
+                            Gate::And(..) => BinaryOp::And,
+                            Gate::Or(..) => BinaryOp::Or,
+                            _ => BinaryOp::Xor,
+                        };
+                        let lhs = new_wire(&mut object);
+                        push(
+                            &mut ops,
+                            OpCode::Binary(Binary {
+                                op,
+                                lhs,
+                                arg1: wires[&a],
+                                arg2: wires[&b],
+                            }),
+                        );
+                        lhs
+                    }
+                    Gate::Select(sel, on_true, on_false) => {
+                        let lhs = new_wire(&mut object);
+                        push(
+                            &mut ops,
+                            OpCode::Select(Select {
+                                lhs,
+                                selector: wires[&sel],
+                                true_case: wires[&on_true],
+                                false_case: wires[&on_false],
+                            }),
+                        );
+                        lhs
+                    }
+                    Gate::Not(arg) => {
+                        let lhs = new_wire(&mut object);
+                        push(
+                            &mut ops,
+                            OpCode::Not(Not {
+                                lhs,
+                                arg: wires[&arg],
+                            }),
+                        );
+                        lhs
+                    }
+                };
+                wires.insert(lit, wire);
+            }
+            push(
+                &mut ops,
+                OpCode::Assign(Assign {
+                    lhs: output,
+                    rhs: wires[&root],
+                }),
+            );
+        }
+        object.ops = ops;
+        object
+    }
+}
+
+/// Optimize the single bit logic of a netlist as an and-inverter graph.
+/// The optimized logic is only used if it needs fewer gates than the
+/// original, otherwise the netlist is returned unchanged.
+pub fn optimize_netlist(object: Object) -> Object {
+    let Some(aig) = Aig::from_netlist(&object) else {
+        return object;
+    };
+    let optimized = aig.optimize().to_netlist(&object);
+    let cost = |object: &Object| object.ops.iter().filter(|lop| is_logic(&lop.op)).count();
+    if cost(&optimized) < cost(&object) {
+        optimized
+    } else {
+        object
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_inputs(count: usize) -> (Aig, Vec<Wire>, Vec<AigLit>) {
+        let mut object = Object::default();
+        let wires = (0..count)
+            .map(|bit| {
+                object.symtab.reg(
+                    (),
+                    WireDetails {
+                        source_details: None,
+                        kind: Kind::make_bits(count),
+                        bit,
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+        let mut aig = Aig::default();
+        let lits = wires.iter().map(|wire| aig.input(*wire)).collect();
+        (aig, wires, lits)
+    }
+
+    // Check that two graphs compute the same outputs for all input values
+    fn assert_equivalent(a: &Aig, b: &Aig, wires: &[Wire]) {
+        for value in 0..(1 << wires.len()) {
+            let input = |wire: Wire| {
+                let ndx = wires.iter().position(|w| *w == wire).unwrap();
+                value & (1 << ndx) != 0
+            };
+            assert_eq!(a.eval(input), b.eval(input), "mismatch for input {value:b}");
+        }
+    }
+
+    #[test]
+    fn test_structural_hashing() {
+        let (mut aig, _, lits) = make_inputs(2);
+        let x = aig.and(lits[0], lits[1]);
+        let y = aig.and(lits[1], lits[0]);
+        assert_eq!(x, y);
+        assert_eq!(aig.and(lits[0], !lits[0]), AigLit::FALSE);
+        assert_eq!(aig.and(lits[0], AigLit::TRUE), lits[0]);
+    }
+
+    #[test]
+    fn test_redundant_logic_is_removed() {
+        let (mut aig, wires, lits) = make_inputs(2);
+        let (a, b) = (lits[0], lits[1]);
+        let p = aig.and(a, b);
+        let q = aig.and(a, !b);
+        let y = aig.or(p, q);
+        aig.output(wires[0], y);
+        assert_eq!(aig.and_count(), 3);
+        let opt = aig.optimize();
+        assert_eq!(opt.and_count(), 0);
+        assert_equivalent(&aig, &opt, &wires);
+    }
+
+    #[test]
+    fn test_and_chain_is_balanced() {
+        let (mut aig, wires, lits) = make_inputs(8);
+        let y = lits[1..]
+            .iter()
+            .fold(lits[0], |acc, lit| aig.and(acc, *lit));
+        aig.output(wires[0], y);
+        assert_eq!(aig.depth(), 7);
+        let opt = aig.optimize();
+        assert_eq!(opt.depth(), 3);
+        assert_eq!(opt.and_count(), 7);
+        assert_equivalent(&aig, &opt, &wires);
+    }
+
+    #[test]
+    fn test_rewrite_finds_smaller_logic() {
+        // A multiplexer whose inputs are the same function of
+        // the other inputs, written out as a sum of minterms.
+        let (mut aig, wires, lits) = make_inputs(4);
+        let mut terms = vec![];
+        for value in 0..16 {
+            if (value & 1 != 0) ^ (value & 2 != 0) {
+                let minterm = lits
+                    .iter()
+                    .enumerate()
+                    .map(|(ndx, lit)| lit.invert_if(value & (1 << ndx) == 0))
+                    .collect();
+                terms.push(aig.and_all(minterm));
+            }
+        }
+        let y = terms
+            .into_iter()
+            .fold(AigLit::FALSE, |acc, term| aig.or(acc, term));
+        aig.output(wires[0], y);
+        let before = aig.and_count();
+        let opt = aig.optimize();
+        assert!(opt.and_count() <= 3, "{} -> {}", before, opt.and_count());
+        assert_equivalent(&aig, &opt, &wires);
+    }
+
+    #[test]
+    fn test_isop_covers_function() {
+        for table in [0x8000_u64, 0x6996, 0xCAFE, 0x0001, 0xFFFF] {
+            let table = table | (table << 16);
+            let table = table | (table << 32);
+            let (cubes, func) = isop(table, table, 4);
+            assert_eq!(func, table);
+            let cover = cubes.iter().fold(0, |acc, cube| {
+                acc | (0..4)
+                    .filter(|var| cube.mask & (1 << var) != 0)
+                    .fold(!0, |acc, var| {
+                        acc & if cube.phase & (1 << var) != 0 {
+                            VARS[var]
+                        } else {
+                            !VARS[var]
+                        }
+                    })
+            });
+            assert_eq!(cover, table);
+        }
+    }
+}
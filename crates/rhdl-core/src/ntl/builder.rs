@@ -1,8 +1,10 @@
 use crate::ast::spanned_source::SpannedSourceSet;
+use crate::circuit::clock_enable::has_clock_enable;
 use crate::circuit::descriptor::{AsyncKind, SyncKind};
 use crate::common::symtab::RegisterId;
 use crate::ntl::object::WireDetails;
-use crate::ntl::spec::{self, Assign, BlackBoxId, WireKind};
+use crate::ntl::spec::{self, Assign, Binary, BinaryOp, BlackBoxId, WireKind};
+use crate::ntl::vm::BlackBoxModel;
 use crate::types::digital::Digital;
 use crate::{ClockReset, Descriptor, Kind, RHDLError};
 use crate::{
@@ -83,6 +85,28 @@ impl Builder {
         self.add_code(&other.code);
         self.object.import(other)
     }
+    pub fn and(
+        &mut self,
+        arg1: RegisterId<WireKind>,
+        arg2: RegisterId<WireKind>,
+    ) -> RegisterId<WireKind> {
+        let wd = WireDetails {
+            source_details: None,
+            kind: Kind::make_bits(1),
+            bit: 0,
+        };
+        let lhs = self.object.symtab.reg((), wd);
+        self.object.ops.push(LocatedOpCode {
+            op: OpCode::Binary(Binary {
+                op: BinaryOp::And,
+                lhs,
+                arg1: arg1.into(),
+                arg2: arg2.into(),
+            }),
+            loc: None,
+        });
+        lhs.reg().expect("a new register")
+    }
     pub fn copy_from_to<T: Into<Wire>, S: Into<Wire>>(&mut self, rhs: T, lhs: S) {
         self.object.ops.push(LocatedOpCode {
             op: OpCode::Assign(Assign {
@@ -95,6 +119,13 @@ impl Builder {
 }
 
 pub fn circuit_black_box(descriptor: &Descriptor<AsyncKind>) -> Result<Object, RHDLError> {
+    circuit_black_box_with_model(descriptor, None)
+}
+
+pub fn circuit_black_box_with_model(
+    descriptor: &Descriptor<AsyncKind>,
+    model: Option<BlackBoxModel>,
+) -> Result<Object, RHDLError> {
     let flat_name = descriptor.name.to_string();
     let mut builder = Builder::new(&flat_name);
     let hdl = descriptor.hdl()?;
@@ -103,6 +134,13 @@ pub fn circuit_black_box(descriptor: &Descriptor<AsyncKind>) -> Result<Object, R
     builder.object.black_boxes.push(BlackBox {
         code: hdl.clone(),
         mode: BlackBoxMode::Asynchronous,
+        model,
+        vhdl: None,
+        smt: None,
+        resources: None,
+        input_kind: descriptor.input_kind,
+        output_kind: descriptor.output_kind,
+        synchronizer: None,
     });
     let arg0 = arg0.into_iter().map(Wire::Register).collect();
     let lhs = out.iter().copied().map(Wire::Register).collect();
@@ -118,24 +156,41 @@ pub fn circuit_black_box(descriptor: &Descriptor<AsyncKind>) -> Result<Object, R
 }
 
 pub fn synchronous_black_box(descriptor: &Descriptor<SyncKind>) -> Result<Object, RHDLError> {
+    synchronous_black_box_with_model(descriptor, None)
+}
+
+pub fn synchronous_black_box_with_model(
+    descriptor: &Descriptor<SyncKind>,
+    model: Option<BlackBoxModel>,
+) -> Result<Object, RHDLError> {
     let flat_name = descriptor.name.to_string();
     let mut builder = Builder::new(&flat_name);
     let hdl = descriptor.hdl()?;
     // This is the Clock/Reset input
     let arg0 = builder.add_input(ClockReset::static_kind());
     let arg1 = builder.add_input(descriptor.input_kind);
+    // And the clock enable, if the black box has one
+    let ce = has_clock_enable(hdl).then(|| builder.add_input(bool::static_kind()));
     let out = builder.allocate_outputs(descriptor.output_kind);
     builder.object.black_boxes.push(BlackBox {
         code: hdl.clone(),
         mode: BlackBoxMode::Synchronous,
+        model,
+        vhdl: None,
+        smt: None,
+        resources: None,
+        input_kind: descriptor.input_kind,
+        output_kind: descriptor.output_kind,
+        synchronizer: None,
     });
     let arg0 = arg0.into_iter().map(Wire::Register).collect();
     let arg1 = arg1.into_iter().map(Wire::Register).collect();
+    let ce = ce.map(|ce| ce.into_iter().map(Wire::Register).collect());
     let lhs = out.iter().copied().map(Wire::Register).collect();
     builder.object.ops.push(LocatedOpCode {
         op: OpCode::BlackBox(spec::BlackBox {
             lhs,
-            arg: vec![arg0, arg1],
+            arg: [Some(arg0), Some(arg1), ce].into_iter().flatten().collect(),
             code: BlackBoxId::new(0),
         }),
         loc: None,
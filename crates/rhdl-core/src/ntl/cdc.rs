@@ -0,0 +1,662 @@
+//! Clock domain crossing analysis for netlists
+//!
+//! This module works out which clock domain each wire in the netlist of an
+//! asynchronous circuit belongs to, and finds the places where signals move
+//! between domains unsafely.
+//!
+//! The domains of the inputs and outputs of the netlist (and of each
+//! asynchronous black box in it) come from the [Color]s of the [Signal](crate::Signal)s
+//! in their kinds.  A synchronous black box (like a flip flop) is a register
+//! that belongs to the domain of its clock, as do its outputs.  Every other wire
+//! belongs to the domains of the wires it is computed from.
+//!
+//! Signals may only move between domains through black boxes that are marked
+//! as a recognised [Synchronizer].  The analysis finds
+//!
+//! - [Crossing]s, where a signal reaches a register, black box or output in a
+//!   different domain without going through a synchronizer, or where a black box
+//!   that is not a synchronizer connects different domains.
+//! - [BusCrossing]s, where several bits of the same bus cross through separate
+//!   single bit synchronizers.  Each bit may be sampled on a different clock
+//!   cycle, so the bus can be seen in a state that it never had.  Gray coded buses,
+//!   where only one bit changes at a time, are the exception.
+//! - [Reconvergence]s, where the outputs of separate single bit synchronizers
+//!   are combined by the same logic, which has the same problem.
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use crate::{
+    ClockReset, Digital, Kind,
+    ast::SourceLocation,
+    common::symtab::RegisterId,
+    ntl::{
+        object::{BlackBoxMode, Object},
+        spec::{OpCode, Wire, WireKind},
+        visit::visit_wires,
+    },
+    types::{
+        domain::Color,
+        path::{Path, bit_range},
+    },
+};
+
+/// The kinds of recognised clock domain crossings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Synchronizer {
+    /// A single bit synchronizer, like a pair of flip flops
+    /// clocked in the destination domain.
+    SingleBit,
+    /// A single bit synchronizer that carries one bit of a gray coded bus.
+    /// The other bits of the bus may cross through other synchronizers,
+    /// and their outputs may be combined.
+    GrayCoded,
+    /// A memory with read and write ports in different domains.  The circuit
+    /// that uses it is responsible for synchronizing the addresses, as
+    /// an asynchronous FIFO does.
+    DualClockMemory,
+}
+
+const COLORS: [Color; 7] = [
+    Color::Red,
+    Color::Orange,
+    Color::Yellow,
+    Color::Green,
+    Color::Blue,
+    Color::Indigo,
+    Color::Violet,
+];
+
+fn color_name(color: Color) -> &'static str {
+    match color {
+        Color::Red => "Red",
+        Color::Orange => "Orange",
+        Color::Yellow => "Yellow",
+        Color::Green => "Green",
+        Color::Blue => "Blue",
+        Color::Indigo => "Indigo",
+        Color::Violet => "Violet",
+    }
+}
+
+/// A set of clock domains.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Domains(u8);
+
+impl Domains {
+    /// The set containing only the given domain.
+    pub fn single(color: Color) -> Self {
+        Self(1 << color as u8)
+    }
+    /// Returns true if the set contains no domains.
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+    /// Returns true if the set contains the given domain.
+    pub fn contains(self, color: Color) -> bool {
+        self.0 & Self::single(color).0 != 0
+    }
+    /// The domains in either set.
+    pub fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+    /// The domains in this set that are not in the other one.
+    pub fn without(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+    /// The domain in the set, if there is exactly one.
+    pub fn only(self) -> Option<Color> {
+        let mut colors = self.iter();
+        match (colors.next(), colors.next()) {
+            (Some(color), None) => Some(color),
+            _ => None,
+        }
+    }
+    /// Iterate over the domains in the set.
+    pub fn iter(self) -> impl Iterator<Item = Color> {
+        COLORS
+            .into_iter()
+            .filter(move |color| self.contains(*color))
+    }
+}
+
+impl std::fmt::Display for Domains {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names = self.iter().map(color_name).collect::<Vec<_>>();
+        match names.as_slice() {
+            [] => write!(f, "no"),
+            [name] => write!(f, "{name}"),
+            [rest @ .., last] => write!(f, "{} and {last}", rest.join(", ")),
+        }
+    }
+}
+
+// The signals in a kind, and the signal (if any) that each bit belongs to
+#[derive(Default)]
+struct SignalLayout {
+    names: Vec<String>,
+    colors: Vec<Color>,
+    bits: Vec<Option<usize>>,
+}
+
+impl SignalLayout {
+    fn new(kind: Kind, name: &str) -> Self {
+        let mut layout = Self::default();
+        layout.add(kind, name.into());
+        layout
+    }
+    fn add(&mut self, kind: Kind, name: String) {
+        match kind {
+            Kind::Signal(inner, color) => {
+                let signal = self.names.len();
+                self.names.push(name);
+                self.colors.push(color);
+                self.bits
+                    .extend(std::iter::repeat_n(Some(signal), inner.bits()));
+            }
+            Kind::Array(array) => {
+                for ndx in 0..array.size {
+                    self.add(*array.base, format!("{name}[{ndx}]"));
+                }
+            }
+            Kind::Tuple(tuple) => {
+                for (ndx, element) in tuple.elements.iter().enumerate() {
+                    self.add(*element, format!("{name}.{ndx}"));
+                }
+            }
+            Kind::Struct(strukt) => {
+                for field in strukt.fields.iter() {
+                    self.add(field.kind, format!("{name}.{}", field.name));
+                }
+            }
+            // Signals inside of enums are not supported, so the bits of
+            // anything else do not belong to a domain
+            _ => self.bits.extend(std::iter::repeat_n(None, kind.bits())),
+        }
+    }
+    fn domains(&self) -> impl Iterator<Item = Domains> + '_ {
+        self.bits.iter().map(|bit| {
+            bit.map(|signal| Domains::single(self.colors[signal]))
+                .unwrap_or_default()
+        })
+    }
+    fn all(&self) -> Domains {
+        self.domains().fold(Domains::default(), Domains::union)
+    }
+}
+
+/// Where a bus that crosses domains comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Origin {
+    /// A signal in the input of the netlist, numbered in the
+    /// order they appear in the input kind
+    Input(usize),
+    /// The output of the black box with the given opcode index
+    BlackBox(usize),
+}
+
+/// A signal that moves between clock domains without a synchronizer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Crossing {
+    /// The name of the register, black box or netlist that the signal reaches
+    pub target: String,
+    /// The domains of the target
+    pub domain: Domains,
+    /// The domains that the signal comes from
+    pub from: Domains,
+    /// The source locations of the logic that carries the signal
+    pub locs: Vec<SourceLocation>,
+}
+
+/// Several bits of a bus that cross domains through separate single bit synchronizers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BusCrossing {
+    /// The name of the bus (a black box, or an input signal)
+    pub source: String,
+    /// The names of the synchronizers
+    pub synchronizers: Vec<String>,
+    /// The source locations of the logic between the bus and the synchronizers
+    pub locs: Vec<SourceLocation>,
+}
+
+/// Logic that combines the outputs of separate single bit synchronizers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reconvergence {
+    /// The names of the synchronizers
+    pub synchronizers: Vec<String>,
+    /// The source location of the logic that combines them
+    pub loc: Option<SourceLocation>,
+}
+
+/// The clock domains of the wires in the netlist of an asynchronous circuit.
+pub struct ClockDomains<'a> {
+    object: &'a Object,
+    inputs: SignalLayout,
+    domains: HashMap<RegisterId<WireKind>, Domains>,
+    // The opcode that writes each register
+    writers: HashMap<RegisterId<WireKind>, usize>,
+    // The input signal that each input register belongs to
+    input_signals: HashMap<RegisterId<WireKind>, usize>,
+    clock_bit: usize,
+    reset_bit: usize,
+}
+
+fn clock_reset_bit(field: &str) -> usize {
+    bit_range(ClockReset::static_kind(), &Path::default().field(field))
+        .expect("ClockReset has clock and reset fields")
+        .0
+        .start
+}
+
+fn read_wires(op: &OpCode) -> Vec<Wire> {
+    let mut wires = vec![];
+    visit_wires(op, |sense, wire| {
+        if sense.is_read() {
+            wires.push(*wire);
+        }
+    });
+    wires
+}
+
+fn written_regs(op: &OpCode) -> Vec<RegisterId<WireKind>> {
+    let mut regs = vec![];
+    visit_wires(op, |sense, wire| {
+        if !sense.is_read() {
+            regs.extend(wire.reg());
+        }
+    });
+    regs
+}
+
+impl<'a> ClockDomains<'a> {
+    /// Work out the clock domains of the given netlist, which has a single
+    /// input of the given kind.
+    pub fn new(object: &'a Object, input_kind: Kind) -> Self {
+        let inputs = SignalLayout::new(input_kind, "i");
+        let mut domains = HashMap::new();
+        let mut input_signals = HashMap::new();
+        for (reg, bit) in object.inputs.iter().flatten().zip(&inputs.bits) {
+            if let Some(signal) = bit {
+                domains.insert(*reg, Domains::single(inputs.colors[*signal]));
+                input_signals.insert(*reg, *signal);
+            }
+        }
+        let writers = object
+            .ops
+            .iter()
+            .enumerate()
+            .flat_map(|(ndx, lop)| written_regs(&lop.op).into_iter().map(move |reg| (reg, ndx)))
+            .collect();
+        let mut analysis = Self {
+            object,
+            inputs,
+            domains,
+            writers,
+            input_signals,
+            clock_bit: clock_reset_bit("clock"),
+            reset_bit: clock_reset_bit("reset"),
+        };
+        analysis.propagate();
+        analysis
+    }
+    // Propagate the domains through the netlist until nothing changes.  The
+    // domains only ever grow, so this terminates.
+    fn propagate(&mut self) {
+        loop {
+            let mut changed = false;
+            for lop in &self.object.ops {
+                let updates = match &lop.op {
+                    OpCode::BlackBox(bb) => {
+                        let core = &self.object.black_boxes[bb.code.raw()];
+                        match core.mode {
+                            // The outputs of a register belong to the domain of its clock
+                            BlackBoxMode::Synchronous => {
+                                let clock = bb
+                                    .arg
+                                    .first()
+                                    .and_then(|cr| cr.get(self.clock_bit))
+                                    .map(|clock| self.wire(*clock))
+                                    .unwrap_or_default();
+                                bb.lhs.iter().map(|lhs| (*lhs, clock)).collect::<Vec<_>>()
+                            }
+                            // The outputs of other black boxes belong to their declared
+                            // domains.  If there is none, assume they depend on all of the inputs.
+                            BlackBoxMode::Asynchronous => {
+                                let all = bb
+                                    .arg
+                                    .iter()
+                                    .flatten()
+                                    .fold(Domains::default(), |d, w| d.union(self.wire(*w)));
+                                let declared = SignalLayout::new(core.output_kind, "o");
+                                bb.lhs
+                                    .iter()
+                                    .zip(declared.domains())
+                                    .map(|(lhs, d)| (*lhs, if d.is_empty() { all } else { d }))
+                                    .collect()
+                            }
+                        }
+                    }
+                    op => {
+                        let d = read_wires(op)
+                            .into_iter()
+                            .fold(Domains::default(), |d, w| d.union(self.wire(w)));
+                        written_regs(op)
+                            .into_iter()
+                            .map(|reg| (Wire::Register(reg), d))
+                            .collect()
+                    }
+                };
+                for (reg, d) in updates
+                    .into_iter()
+                    .filter_map(|(wire, d)| Some((wire.reg()?, d)))
+                {
+                    let entry = self.domains.entry(reg).or_default();
+                    let next = entry.union(d);
+                    if next != *entry {
+                        *entry = next;
+                        changed = true;
+                    }
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+    }
+    /// The domains that the given wire belongs to.  Literals do not belong to any domain.
+    pub fn wire(&self, wire: Wire) -> Domains {
+        wire.reg()
+            .and_then(|reg| self.domains.get(&reg))
+            .copied()
+            .unwrap_or_default()
+    }
+    /// The registers (synchronous black boxes) in the netlist, with the domains of their clocks.
+    pub fn registers(&self) -> impl Iterator<Item = (&str, Domains)> + '_ {
+        self.object.ops.iter().filter_map(|lop| {
+            let OpCode::BlackBox(bb) = &lop.op else {
+                return None;
+            };
+            let core = &self.object.black_boxes[bb.code.raw()];
+            (core.mode == BlackBoxMode::Synchronous).then(|| {
+                let clock = bb
+                    .arg
+                    .first()
+                    .and_then(|cr| cr.get(self.clock_bit))
+                    .map(|clock| self.wire(*clock))
+                    .unwrap_or_default();
+                (core.code.name.as_str(), clock)
+            })
+        })
+    }
+    // Walk back from the given wires through the logic that carries
+    // signals from the `from` domains, and collect its source locations.
+    fn trace(&self, wires: &[Wire], from: Domains) -> Vec<SourceLocation> {
+        let mut locs = vec![];
+        let mut visited = HashSet::new();
+        let mut pending = wires.to_vec();
+        while let Some(wire) = pending.pop() {
+            if self.wire(wire).without(from) == self.wire(wire) {
+                continue;
+            }
+            let Some(&ndx) = wire.reg().and_then(|reg| self.writers.get(&reg)) else {
+                continue;
+            };
+            let lop = &self.object.ops[ndx];
+            if matches!(lop.op, OpCode::BlackBox(_)) || !visited.insert(ndx) {
+                continue;
+            }
+            if let Some(loc) = lop.loc
+                && !locs.contains(&loc)
+            {
+                locs.push(loc);
+            }
+            pending.extend(read_wires(&lop.op));
+        }
+        locs
+    }
+    /// Find the signals that move between domains without a synchronizer.
+    ///
+    /// The outputs of the netlist are checked against the domains in `output_kind`.
+    pub fn crossings(&self, output_kind: Kind) -> Vec<Crossing> {
+        let mut crossings = vec![];
+        let check = |target: &str, ports: Vec<(Wire, Domains)>| {
+            let mut domain = Domains::default();
+            let mut from = Domains::default();
+            let mut wires = vec![];
+            for (wire, declared) in ports {
+                let foreign = self.wire(wire).without(declared);
+                if !foreign.is_empty() {
+                    domain = domain.union(declared);
+                    from = from.union(foreign);
+                    wires.push(wire);
+                }
+            }
+            (!from.is_empty()).then(|| Crossing {
+                target: target.into(),
+                domain,
+                from,
+                locs: self.trace(&wires, from),
+            })
+        };
+        for lop in &self.object.ops {
+            let OpCode::BlackBox(bb) = &lop.op else {
+                continue;
+            };
+            let core = &self.object.black_boxes[bb.code.raw()];
+            let name = &core.code.name;
+            match core.mode {
+                BlackBoxMode::Synchronous => {
+                    let Some(cr) = bb.arg.first() else {
+                        continue;
+                    };
+                    let clock = cr
+                        .get(self.clock_bit)
+                        .map(|w| self.wire(*w))
+                        .unwrap_or_default();
+                    // Registers with no known clock domain are not checked
+                    if clock.only().is_none() {
+                        continue;
+                    }
+                    // The data inputs and the reset must be in the domain of the clock
+                    let ports = cr
+                        .get(self.reset_bit)
+                        .into_iter()
+                        .chain(bb.arg.iter().skip(1).flatten())
+                        .map(|wire| (*wire, clock))
+                        .collect();
+                    crossings.extend(check(name, ports));
+                }
+                BlackBoxMode::Asynchronous => {
+                    let inputs = SignalLayout::new(core.input_kind, "i");
+                    let outputs = SignalLayout::new(core.output_kind, "o");
+                    // Each input must be in the domain that the black box declares for it
+                    let ports = bb
+                        .arg
+                        .iter()
+                        .flatten()
+                        .zip(inputs.domains())
+                        .filter(|(_, declared)| !declared.is_empty())
+                        .map(|(wire, declared)| (*wire, declared))
+                        .collect();
+                    crossings.extend(check(name, ports));
+                    // A black box that connects different domains must be a synchronizer
+                    let all = inputs.all().union(outputs.all());
+                    if core.synchronizer.is_none() && all.only().is_none() && !all.is_empty() {
+                        let from = inputs.all().without(outputs.all());
+                        crossings.push(Crossing {
+                            target: name.clone(),
+                            domain: outputs.all(),
+                            from: if from.is_empty() { inputs.all() } else { from },
+                            locs: lop.loc.into_iter().collect(),
+                        });
+                    }
+                }
+            }
+        }
+        let declared = SignalLayout::new(output_kind, "o");
+        let ports = self
+            .object
+            .outputs
+            .iter()
+            .zip(declared.domains())
+            .filter(|(_, declared)| !declared.is_empty())
+            .map(|(wire, declared)| (*wire, declared))
+            .collect();
+        crossings.extend(check(&self.object.name, ports));
+        crossings
+    }
+    // The single bit synchronizers in the netlist, by opcode index
+    fn single_bit_synchronizers(&self) -> impl Iterator<Item = (usize, &str)> + '_ {
+        self.object.ops.iter().enumerate().filter_map(|(ndx, lop)| {
+            let OpCode::BlackBox(bb) = &lop.op else {
+                return None;
+            };
+            let core = &self.object.black_boxes[bb.code.raw()];
+            (core.synchronizer == Some(Synchronizer::SingleBit))
+                .then_some((ndx, core.code.name.as_str()))
+        })
+    }
+    fn name_of(&self, origin: Origin) -> String {
+        match origin {
+            Origin::Input(signal) => self.inputs.names[signal].clone(),
+            Origin::BlackBox(ndx) => {
+                let OpCode::BlackBox(bb) = &self.object.ops[ndx].op else {
+                    unreachable!("origins are black boxes");
+                };
+                self.object.black_boxes[bb.code.raw()].code.name.clone()
+            }
+        }
+    }
+    /// Find the buses that cross domains through several single bit synchronizers.
+    pub fn bus_crossings(&self) -> Vec<BusCrossing> {
+        let mut buses: BTreeMap<Origin, (Vec<usize>, Vec<SourceLocation>)> = BTreeMap::new();
+        for (ndx, _) in self.single_bit_synchronizers() {
+            let OpCode::BlackBox(bb) = &self.object.ops[ndx].op else {
+                continue;
+            };
+            let core = &self.object.black_boxes[bb.code.raw()];
+            let destination = SignalLayout::new(core.output_kind, "o").all();
+            // The data inputs are the ones that come from another domain
+            let mut pending = bb
+                .arg
+                .iter()
+                .flatten()
+                .zip(SignalLayout::new(core.input_kind, "i").domains())
+                .filter(|(_, declared)| !declared.without(destination).is_empty())
+                .map(|(wire, _)| *wire)
+                .collect::<Vec<_>>();
+            let mut origins = BTreeSet::new();
+            let mut locs = vec![];
+            let mut visited = HashSet::new();
+            while let Some(wire) = pending.pop() {
+                let Some(reg) = wire.reg() else {
+                    continue;
+                };
+                if let Some(signal) = self.input_signals.get(&reg) {
+                    origins.insert(Origin::Input(*signal));
+                    continue;
+                }
+                let Some(&writer) = self.writers.get(&reg) else {
+                    continue;
+                };
+                let lop = &self.object.ops[writer];
+                if matches!(lop.op, OpCode::BlackBox(_)) {
+                    origins.insert(Origin::BlackBox(writer));
+                } else if visited.insert(writer) {
+                    locs.extend(lop.loc);
+                    pending.extend(read_wires(&lop.op));
+                }
+            }
+            for origin in origins {
+                let bus = buses.entry(origin).or_default();
+                bus.0.push(ndx);
+                bus.1.extend(locs.iter().copied());
+            }
+        }
+        buses
+            .into_iter()
+            .filter(|(_, (synchronizers, _))| synchronizers.len() > 1)
+            .map(|(origin, (synchronizers, mut locs))| {
+                let mut seen = HashSet::new();
+                locs.retain(|loc| seen.insert(*loc));
+                BusCrossing {
+                    source: self.name_of(origin),
+                    synchronizers: synchronizers
+                        .into_iter()
+                        .map(|ndx| self.name_of(Origin::BlackBox(ndx)))
+                        .collect(),
+                    locs,
+                }
+            })
+            .collect()
+    }
+    /// Find the logic that combines the outputs of separate single bit synchronizers.
+    ///
+    /// Only combinational logic is considered; the search stops at registers and
+    /// other black boxes.
+    pub fn reconvergences(&self) -> Vec<Reconvergence> {
+        let mut names = BTreeMap::new();
+        let mut reach: HashMap<RegisterId<WireKind>, BTreeSet<usize>> = HashMap::new();
+        for (ndx, name) in self.single_bit_synchronizers() {
+            names.insert(ndx, name);
+            let OpCode::BlackBox(bb) = &self.object.ops[ndx].op else {
+                continue;
+            };
+            for reg in bb.lhs.iter().flat_map(|lhs| lhs.reg()) {
+                reach.entry(reg).or_default().insert(ndx);
+            }
+        }
+        if names.len() < 2 {
+            return vec![];
+        }
+        let reached = |reach: &HashMap<RegisterId<WireKind>, BTreeSet<usize>>, wire: Wire| {
+            wire.reg()
+                .and_then(|reg| reach.get(&reg))
+                .cloned()
+                .unwrap_or_default()
+        };
+        loop {
+            let mut changed = false;
+            for lop in &self.object.ops {
+                if matches!(lop.op, OpCode::BlackBox(_)) {
+                    continue;
+                }
+                let synchronizers = read_wires(&lop.op)
+                    .into_iter()
+                    .flat_map(|wire| reached(&reach, wire))
+                    .collect::<BTreeSet<_>>();
+                if synchronizers.is_empty() {
+                    continue;
+                }
+                for reg in written_regs(&lop.op) {
+                    let entry = reach.entry(reg).or_default();
+                    let before = entry.len();
+                    entry.extend(synchronizers.iter().copied());
+                    changed |= entry.len() != before;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+        // A merge point is an opcode that combines synchronizers that none of its
+        // arguments combine on their own.  Report each combination once.
+        let mut seen = HashSet::new();
+        self.object
+            .ops
+            .iter()
+            .filter(|lop| !matches!(lop.op, OpCode::BlackBox(_)))
+            .filter_map(|lop| {
+                let args = read_wires(&lop.op)
+                    .into_iter()
+                    .map(|wire| reached(&reach, wire))
+                    .collect::<Vec<_>>();
+                let all = args.iter().flatten().copied().collect::<BTreeSet<_>>();
+                (all.len() > 1 && !args.contains(&all) && seen.insert(all.clone())).then(|| {
+                    Reconvergence {
+                        synchronizers: all.iter().map(|ndx| names[ndx].to_string()).collect(),
+                        loc: lop.loc,
+                    }
+                })
+            })
+            .collect()
+    }
+}
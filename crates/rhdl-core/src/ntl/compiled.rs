@@ -0,0 +1,700 @@
+//! Compiled netlist simulator
+//!
+//! The [NetListVM](crate::ntl::vm::NetListVM) interprets the netlist [Object] as it
+//! stands, which is convenient for checking a design, but slow for long regression
+//! runs.  The [CompiledNetList] instead translates the netlist once into a flat
+//! program, in which every wire has been resolved to a slot in a packed state.  The
+//! state holds two bit planes (the value, and whether the bit is unknown), 64 wires
+//! to a word, so that the wires of a vector can be read and written a word at a time.
+//!
+//! Vector operations on up to 128 known bits are evaluated with native integer
+//! arithmetic.  Wider operands, or operands with unknown bits, fall back to the
+//! same three-valued logic as the [NetListVM](crate::ntl::vm::NetListVM), so the
+//! two simulators produce identical results.  Black boxes are run with their
+//! [BlackBoxModel], and the program is repeated until
+//! their outputs settle, just as in the interpreter.
+use crate::{
+    BitX, MAX_ITERS, RHDLError,
+    ast::SourceLocation,
+    common::slot_vec::SlotKey,
+    ntl::{
+        error::NetListICE,
+        object::Object,
+        spec::{
+            Assign, Binary, BinaryOp, Case, CaseEntry, Not, OpCode, Select, Unary, UnaryOp, Vector,
+            VectorOp, Wire,
+        },
+        vm::{BlackBoxModel, raise_ice, unary, vector},
+    },
+};
+
+type Result<T> = std::result::Result<T, RHDLError>;
+
+// The bits of a netlist vector, as slots in the state.  If the slots
+// are consecutive, `start` holds the first one, and the vector can be
+// moved a word at a time.
+#[derive(Clone, Debug)]
+struct Operand {
+    slots: Box<[usize]>,
+    start: Option<usize>,
+}
+
+impl Operand {
+    fn new(slots: Box<[usize]>) -> Self {
+        let start = slots
+            .first()
+            .copied()
+            .filter(|&first| slots.iter().enumerate().all(|(ndx, &s)| s == first + ndx));
+        Self { slots, start }
+    }
+    fn len(&self) -> usize {
+        self.slots.len()
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Pattern {
+    // A literal of up to 128 bits, as its value and unknown planes
+    Packed(u128, u128),
+    Wide(Box<[BitX]>),
+    WildCard,
+}
+
+#[derive(Clone, Debug)]
+enum Instr {
+    Copy {
+        lhs: usize,
+        arg: usize,
+    },
+    Binary {
+        op: BinaryOp,
+        lhs: usize,
+        arg1: usize,
+        arg2: usize,
+    },
+    Not {
+        lhs: usize,
+        arg: usize,
+    },
+    Select {
+        lhs: usize,
+        selector: usize,
+        true_case: usize,
+        false_case: usize,
+    },
+    Case {
+        lhs: usize,
+        discriminant: Operand,
+        entries: Box<[(Pattern, usize)]>,
+    },
+    Vector {
+        op: VectorOp,
+        signed: bool,
+        lhs: Operand,
+        arg1: Operand,
+        arg2: Operand,
+        loc: Option<SourceLocation>,
+    },
+    Unary {
+        op: UnaryOp,
+        lhs: Operand,
+        arg: Operand,
+        loc: Option<SourceLocation>,
+    },
+    BlackBox {
+        id: usize,
+        lhs: Operand,
+        args: Box<[Operand]>,
+    },
+}
+
+fn mask(len: usize) -> u128 {
+    if len >= 128 {
+        u128::MAX
+    } else {
+        (1 << len) - 1
+    }
+}
+
+fn sign_extend(x: u128, len: usize) -> i128 {
+    ((x << (128 - len)) as i128) >> (128 - len)
+}
+
+// The packed state of the netlist.  An unknown bit always has a zero
+// in the value plane.
+#[derive(Clone, Debug)]
+struct Planes {
+    value: Vec<u64>,
+    unknown: Vec<u64>,
+}
+
+impl Planes {
+    fn new(len: usize) -> Self {
+        let words = len.div_ceil(64);
+        Self {
+            value: vec![0; words],
+            unknown: vec![0; words],
+        }
+    }
+    fn get(&self, slot: usize) -> (u64, u64) {
+        let (word, bit) = (slot / 64, slot % 64);
+        (
+            (self.value[word] >> bit) & 1,
+            (self.unknown[word] >> bit) & 1,
+        )
+    }
+    fn set(&mut self, slot: usize, value: u64, unknown: u64) {
+        let (word, bit) = (slot / 64, slot % 64);
+        self.value[word] = (self.value[word] & !(1 << bit)) | (value << bit);
+        self.unknown[word] = (self.unknown[word] & !(1 << bit)) | (unknown << bit);
+    }
+    fn get_bitx(&self, slot: usize) -> BitX {
+        match self.get(slot) {
+            (_, 1) => BitX::X,
+            (1, _) => BitX::One,
+            _ => BitX::Zero,
+        }
+    }
+    fn set_bitx(&mut self, slot: usize, bit: BitX) {
+        match bit {
+            BitX::Zero => self.set(slot, 0, 0),
+            BitX::One => self.set(slot, 1, 0),
+            BitX::X => self.set(slot, 0, 1),
+        }
+    }
+    fn extract(plane: &[u64], start: usize, len: usize) -> u128 {
+        let mut result = 0;
+        let mut done = 0;
+        while done < len {
+            let pos = start + done;
+            let bit = pos % 64;
+            let take = (64 - bit).min(len - done);
+            let chunk = (plane[pos / 64] >> bit) as u128 & mask(take);
+            result |= chunk << done;
+            done += take;
+        }
+        result
+    }
+    fn deposit(plane: &mut [u64], start: usize, len: usize, bits: u128) {
+        let mut done = 0;
+        while done < len {
+            let pos = start + done;
+            let bit = pos % 64;
+            let take = (64 - bit).min(len - done);
+            let chunk_mask = (mask(take) as u64) << bit;
+            let chunk = ((bits >> done) as u64) << bit;
+            let word = &mut plane[pos / 64];
+            *word = (*word & !chunk_mask) | (chunk & chunk_mask);
+            done += take;
+        }
+    }
+    // Read an operand of at most 128 bits
+    fn read(&self, operand: &Operand) -> (u128, u128) {
+        if let Some(start) = operand.start {
+            let len = operand.len();
+            return (
+                Self::extract(&self.value, start, len),
+                Self::extract(&self.unknown, start, len),
+            );
+        }
+        operand
+            .slots
+            .iter()
+            .enumerate()
+            .fold((0, 0), |(value, unknown), (ndx, &slot)| {
+                let (v, u) = self.get(slot);
+                (value | ((v as u128) << ndx), unknown | ((u as u128) << ndx))
+            })
+    }
+    // Write (the low bits of) a value to an operand of at most 128 bits
+    fn write(&mut self, operand: &Operand, value: u128, unknown: u128) {
+        if let Some(start) = operand.start {
+            let len = operand.len();
+            Self::deposit(&mut self.value, start, len, value & !unknown);
+            Self::deposit(&mut self.unknown, start, len, unknown);
+            return;
+        }
+        for (ndx, &slot) in operand.slots.iter().enumerate() {
+            let u = ((unknown >> ndx) & 1) as u64;
+            let v = ((value >> ndx) & 1) as u64 & !u;
+            self.set(slot, v, u);
+        }
+    }
+    fn read_bitx(&self, operand: &Operand, bits: &mut Vec<BitX>) {
+        bits.clear();
+        bits.extend(operand.slots.iter().map(|&slot| self.get_bitx(slot)));
+    }
+    fn write_bitx(&mut self, operand: &Operand, bits: &[BitX]) {
+        for (&slot, &bit) in operand.slots.iter().zip(bits) {
+            self.set_bitx(slot, bit);
+        }
+    }
+}
+
+// Evaluate a vector operation on known operands of up to 128 bits.  Returns
+// `None` if the operation must be left to the three-valued implementation.
+fn fast_vector(
+    op: VectorOp,
+    signed: bool,
+    a: u128,
+    alen: usize,
+    b: u128,
+    blen: usize,
+) -> Option<(u128, usize)> {
+    if alen == 0 || alen > 128 || blen > 128 {
+        return None;
+    }
+    let shift = matches!(op, VectorOp::Shl | VectorOp::Shr);
+    if !shift && alen != blen {
+        return None;
+    }
+    let compare = |f: fn(std::cmp::Ordering) -> bool| {
+        let ordering = if signed {
+            sign_extend(a, alen).cmp(&sign_extend(b, alen))
+        } else {
+            a.cmp(&b)
+        };
+        Some((f(ordering) as u128, 1))
+    };
+    match op {
+        VectorOp::Add => Some((a.wrapping_add(b) & mask(alen), alen)),
+        VectorOp::Sub => Some((a.wrapping_sub(b) & mask(alen), alen)),
+        VectorOp::Mul => Some((a.wrapping_mul(b) & mask(alen), alen)),
+        VectorOp::Eq => Some(((a == b) as u128, 1)),
+        VectorOp::Ne => Some(((a != b) as u128, 1)),
+        VectorOp::Lt => compare(|o| o.is_lt()),
+        VectorOp::Le => compare(|o| o.is_le()),
+        VectorOp::Gt => compare(|o| o.is_gt()),
+        VectorOp::Ge => compare(|o| o.is_ge()),
+        VectorOp::Shl if b < alen as u128 => Some(((a << b) & mask(alen), alen)),
+        VectorOp::Shr if b < alen as u128 => {
+            let result = if signed {
+                (sign_extend(a, alen) >> b) as u128
+            } else {
+                a >> b
+            };
+            Some((result & mask(alen), alen))
+        }
+        _ => None,
+    }
+}
+
+fn fast_unary(op: UnaryOp, a: u128, len: usize) -> Option<(u128, usize)> {
+    if len > 128 {
+        return None;
+    }
+    match op {
+        UnaryOp::All => Some(((a == mask(len)) as u128, 1)),
+        UnaryOp::Any => Some(((a != 0) as u128, 1)),
+        UnaryOp::Xor => Some(((a.count_ones() & 1) as u128, 1)),
+        UnaryOp::Neg => Some(((!a).wrapping_add(1) & mask(len), len)),
+    }
+}
+
+/// A netlist compiled into a program over a packed state.
+///
+/// The [CompiledNetList] has the same interface as the
+/// [NetListVM](crate::ntl::vm::NetListVM), and gives the same results,
+/// but is considerably faster on large designs.
+#[derive(Clone)]
+pub struct CompiledNetList {
+    object: Object,
+    program: Vec<Instr>,
+    inputs: Vec<Operand>,
+    outputs: Operand,
+    planes: Planes,
+    models: Vec<BlackBoxModel>,
+    states: Vec<Vec<BitX>>,
+    snapshot: Vec<Vec<BitX>>,
+    // Scratch space for the arguments of the black boxes and vector
+    // operations that are too wide for the native path
+    args: Vec<Vec<BitX>>,
+    wide: (Vec<BitX>, Vec<BitX>),
+}
+
+struct Compiler<'a> {
+    object: &'a Object,
+    registers: usize,
+}
+
+impl Compiler<'_> {
+    fn slot(&self, wire: Wire) -> usize {
+        match wire {
+            Wire::Register(rid) => rid.index(),
+            Wire::Literal(lid) => self.registers + lid.index(),
+        }
+    }
+    fn lhs(&self, wire: Wire, loc: Option<SourceLocation>) -> Result<usize> {
+        match wire {
+            Wire::Register(rid) => Ok(rid.index()),
+            Wire::Literal(_) => Err(raise_ice(
+                self.object,
+                NetListICE::ExpectedRegisterNotConstant,
+                loc,
+            )),
+        }
+    }
+    fn operand(&self, wires: &[Wire]) -> Operand {
+        Operand::new(wires.iter().map(|&wire| self.slot(wire)).collect())
+    }
+    fn lhs_operand(&self, wires: &[Wire], loc: Option<SourceLocation>) -> Result<Operand> {
+        let slots = wires
+            .iter()
+            .map(|&wire| self.lhs(wire, loc))
+            .collect::<Result<_>>()?;
+        Ok(Operand::new(slots))
+    }
+    fn pattern(entry: &CaseEntry) -> Pattern {
+        match entry {
+            CaseEntry::WildCard => Pattern::WildCard,
+            CaseEntry::Literal(lit) if lit.bits().len() <= 128 => {
+                let (value, unknown) =
+                    lit.bits()
+                        .iter()
+                        .enumerate()
+                        .fold((0, 0), |(value, unknown), (ndx, bit)| match bit {
+                            BitX::Zero => (value, unknown),
+                            BitX::One => (value | (1 << ndx), unknown),
+                            BitX::X => (value, unknown | (1 << ndx)),
+                        });
+                Pattern::Packed(value, unknown)
+            }
+            CaseEntry::Literal(lit) => Pattern::Wide(lit.bits().into()),
+        }
+    }
+    fn instr(&self, op: &OpCode, loc: Option<SourceLocation>) -> Result<Option<Instr>> {
+        Ok(Some(match op {
+            OpCode::Noop => return Ok(None),
+            OpCode::Assign(Assign { lhs, rhs }) => Instr::Copy {
+                lhs: self.lhs(*lhs, loc)?,
+                arg: self.slot(*rhs),
+            },
+            OpCode::Binary(Binary {
+                op,
+                lhs,
+                arg1,
+                arg2,
+            }) => Instr::Binary {
+                op: *op,
+                lhs: self.lhs(*lhs, loc)?,
+                arg1: self.slot(*arg1),
+                arg2: self.slot(*arg2),
+            },
+            OpCode::Not(Not { lhs, arg }) => Instr::Not {
+                lhs: self.lhs(*lhs, loc)?,
+                arg: self.slot(*arg),
+            },
+            OpCode::Select(Select {
+                lhs,
+                selector,
+                true_case,
+                false_case,
+            }) => Instr::Select {
+                lhs: self.lhs(*lhs, loc)?,
+                selector: self.slot(*selector),
+                true_case: self.slot(*true_case),
+                false_case: self.slot(*false_case),
+            },
+            OpCode::Case(Case {
+                lhs,
+                discriminant,
+                entries,
+            }) => Instr::Case {
+                lhs: self.lhs(*lhs, loc)?,
+                discriminant: self.operand(discriminant),
+                entries: entries
+                    .iter()
+                    .map(|(entry, wire)| (Self::pattern(entry), self.slot(*wire)))
+                    .collect(),
+            },
+            OpCode::Vector(Vector {
+                op,
+                lhs,
+                arg1,
+                arg2,
+                signed,
+            }) => Instr::Vector {
+                op: *op,
+                signed: *signed,
+                lhs: self.lhs_operand(lhs, loc)?,
+                arg1: self.operand(arg1),
+                arg2: self.operand(arg2),
+                loc,
+            },
+            OpCode::Unary(Unary { op, lhs, arg }) => Instr::Unary {
+                op: *op,
+                lhs: self.lhs_operand(lhs, loc)?,
+                arg: self.operand(arg),
+                loc,
+            },
+            OpCode::BlackBox(black_box) => Instr::BlackBox {
+                id: black_box.code.raw(),
+                lhs: self.lhs_operand(&black_box.lhs, loc)?,
+                args: black_box.arg.iter().map(|arg| self.operand(arg)).collect(),
+            },
+        }))
+    }
+}
+
+impl CompiledNetList {
+    /// Compile the given netlist.  Fails if the netlist contains a black
+    /// box with no behavioral model.
+    pub fn new(object: &Object) -> Result<Self> {
+        let models = object
+            .black_boxes
+            .iter()
+            .map(|bb| {
+                bb.model.clone().ok_or_else(|| {
+                    raise_ice(
+                        object,
+                        NetListICE::BlackBoxHasNoModel {
+                            name: bb.code.name.clone(),
+                        },
+                        None,
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let registers = object.symtab.reg_vec().len();
+        let compiler = Compiler { object, registers };
+        let program = object
+            .ops
+            .iter()
+            .filter_map(|lop| compiler.instr(&lop.op, lop.loc).transpose())
+            .collect::<Result<Vec<_>>>()?;
+        let inputs = object
+            .inputs
+            .iter()
+            .map(|input| Operand::new(input.iter().map(|reg| reg.index()).collect()))
+            .collect();
+        let outputs = compiler.operand(&object.outputs);
+        let literals = object.symtab.lit_vec();
+        let mut planes = Planes::new(registers + literals.len());
+        for slot in 0..registers {
+            planes.set_bitx(slot, BitX::X);
+        }
+        for (ndx, (bit, _)) in literals.iter().enumerate() {
+            planes.set_bitx(registers + ndx, *bit);
+        }
+        let states = models
+            .iter()
+            .map(|model| model.0.init())
+            .collect::<Vec<_>>();
+        Ok(Self {
+            object: object.clone(),
+            program,
+            inputs,
+            outputs,
+            planes,
+            models,
+            snapshot: states.clone(),
+            states,
+            args: vec![],
+            wide: Default::default(),
+        })
+    }
+    /// The netlist being simulated.
+    pub fn object(&self) -> &Object {
+        &self.object
+    }
+    /// Evaluate the netlist with the given arguments, and return
+    /// the output bits.  The state of any black boxes is updated.
+    pub fn step(&mut self, args: &[&[BitX]]) -> Result<Vec<BitX>> {
+        if args.len() != self.inputs.len() {
+            return Err(raise_ice(
+                &self.object,
+                NetListICE::ArgumentCountMismatch {
+                    expected: self.inputs.len(),
+                    found: args.len(),
+                },
+                None,
+            ));
+        }
+        for (ndx, (input, arg)) in self.inputs.iter().zip(args).enumerate() {
+            if input.len() != arg.len() {
+                return Err(raise_ice(
+                    &self.object,
+                    NetListICE::ArgumentWidthMismatch {
+                        ndx,
+                        expected: input.len(),
+                        found: arg.len(),
+                    },
+                    None,
+                ));
+            }
+            self.planes.write_bitx(input, arg);
+        }
+        // As in the interpreter, black boxes are evaluated from the state
+        // they had at the start of the step, until their outputs settle
+        self.snapshot.clone_from(&self.states);
+        for _ in 0..MAX_ITERS {
+            if self.run()? {
+                let mut output = vec![];
+                self.planes.read_bitx(&self.outputs, &mut output);
+                return Ok(output);
+            }
+        }
+        Err(raise_ice(
+            &self.object,
+            NetListICE::SimulationDidNotConverge,
+            None,
+        ))
+    }
+    // Run the program once, and return true if the outputs of
+    // the black boxes did not change
+    fn run(&mut self) -> Result<bool> {
+        let Self {
+            object,
+            program,
+            planes,
+            models,
+            states,
+            snapshot,
+            args,
+            wide,
+            ..
+        } = self;
+        let mut settled = true;
+        for instr in program.iter() {
+            match instr {
+                Instr::Copy { lhs, arg } => {
+                    let (v, u) = planes.get(*arg);
+                    planes.set(*lhs, v, u);
+                }
+                Instr::Binary {
+                    op,
+                    lhs,
+                    arg1,
+                    arg2,
+                } => {
+                    let (av, au) = planes.get(*arg1);
+                    let (bv, bu) = planes.get(*arg2);
+                    let (v, u) = match op {
+                        BinaryOp::And => {
+                            let zero = (!av & !au) | (!bv & !bu);
+                            (av & bv, (au | bu) & !zero & 1)
+                        }
+                        BinaryOp::Or => {
+                            let v = av | bv;
+                            (v, (au | bu) & !v & 1)
+                        }
+                        BinaryOp::Xor => {
+                            let u = au | bu;
+                            ((av ^ bv) & !u & 1, u)
+                        }
+                    };
+                    planes.set(*lhs, v, u);
+                }
+                Instr::Not { lhs, arg } => {
+                    let (v, u) = planes.get(*arg);
+                    planes.set(*lhs, !v & !u & 1, u);
+                }
+                Instr::Select {
+                    lhs,
+                    selector,
+                    true_case,
+                    false_case,
+                } => {
+                    let (v, u) = match planes.get(*selector) {
+                        (_, 1) => (0, 1),
+                        (1, _) => planes.get(*true_case),
+                        _ => planes.get(*false_case),
+                    };
+                    planes.set(*lhs, v, u);
+                }
+                Instr::Case {
+                    lhs,
+                    discriminant,
+                    entries,
+                } => {
+                    let packed = (discriminant.len() <= 128).then(|| planes.read(discriminant));
+                    if packed.is_none() {
+                        planes.read_bitx(discriminant, &mut wide.0);
+                    }
+                    let (v, u) = entries
+                        .iter()
+                        .find(|(pattern, _)| match (pattern, packed) {
+                            (Pattern::WildCard, _) => true,
+                            (Pattern::Packed(value, unknown), Some(packed)) => {
+                                packed == (*value, *unknown)
+                            }
+                            (Pattern::Wide(bits), None) => bits[..] == wide.0[..],
+                            _ => false,
+                        })
+                        .map(|(_, slot)| planes.get(*slot))
+                        .unwrap_or((0, 1));
+                    planes.set(*lhs, v, u);
+                }
+                Instr::Vector {
+                    op,
+                    signed,
+                    lhs,
+                    arg1,
+                    arg2,
+                    loc,
+                } => {
+                    if arg1.len() <= 128 && arg2.len() <= 128 {
+                        let (a, au) = planes.read(arg1);
+                        let (b, bu) = planes.read(arg2);
+                        if let Some((result, _)) = (au == 0 && bu == 0)
+                            .then(|| fast_vector(*op, *signed, a, arg1.len(), b, arg2.len()))
+                            .flatten()
+                            .filter(|&(_, len)| len == lhs.len())
+                        {
+                            planes.write(lhs, result, 0);
+                            continue;
+                        }
+                    }
+                    planes.read_bitx(arg1, &mut wide.0);
+                    planes.read_bitx(arg2, &mut wide.1);
+                    let result = vector(
+                        *op,
+                        *signed,
+                        std::mem::take(&mut wide.0),
+                        std::mem::take(&mut wide.1),
+                    )
+                    .map_err(|err| {
+                        raise_ice(object, NetListICE::VectorOperatorError(Box::new(err)), *loc)
+                    })?;
+                    planes.write_bitx(lhs, &result);
+                }
+                Instr::Unary { op, lhs, arg, loc } => {
+                    if arg.len() <= 128 {
+                        let (a, au) = planes.read(arg);
+                        if let Some((result, _)) = (au == 0)
+                            .then(|| fast_unary(*op, a, arg.len()))
+                            .flatten()
+                            .filter(|&(_, len)| len == lhs.len())
+                        {
+                            planes.write(lhs, result, 0);
+                            continue;
+                        }
+                    }
+                    planes.read_bitx(arg, &mut wide.0);
+                    let result = unary(*op, std::mem::take(&mut wide.0)).map_err(|err| {
+                        raise_ice(object, NetListICE::VectorOperatorError(Box::new(err)), *loc)
+                    })?;
+                    planes.write_bitx(lhs, &result);
+                }
+                Instr::BlackBox {
+                    id,
+                    lhs,
+                    args: operands,
+                } => {
+                    args.resize_with(operands.len(), Vec::new);
+                    for (arg, operand) in args.iter_mut().zip(operands.iter()) {
+                        planes.read_bitx(operand, arg);
+                    }
+                    let state = &mut states[*id];
+                    state.clone_from(&snapshot[*id]);
+                    let result = models[*id].0.sim(&args[..operands.len()], state);
+                    for (&slot, bit) in lhs.slots.iter().zip(result) {
+                        settled &= planes.get_bitx(slot) == bit;
+                        planes.set_bitx(slot, bit);
+                    }
+                }
+            }
+        }
+        Ok(settled)
+    }
+}
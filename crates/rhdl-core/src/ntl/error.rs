@@ -16,6 +16,31 @@ pub enum NetListICE {
     LogicLoop,
     #[error("Net list contains an undriven node")]
     UndrivenNetlistNode,
+    #[error("Black box {name} has no simulation model")]
+    #[diagnostic(help(
+        "The netlist simulator can only run black boxes that provide a behavioral model.  Use `with_netlist_black_box_model` when building the descriptor."
+    ))]
+    BlackBoxHasNoModel { name: String },
+    #[error("Black box {name} has no formal model")]
+    #[diagnostic(help(
+        "Netlists can only be exported to SMT-LIB2 if every black box provides a formal model.  Use `with_black_box_smt` when building the descriptor."
+    ))]
+    BlackBoxHasNoFormalModel { name: String },
+    #[error("Netlist simulator expected {expected} arguments but found {found}")]
+    ArgumentCountMismatch { expected: usize, found: usize },
+    #[error("Netlist simulator expected argument {ndx} to have {expected} bits but found {found}")]
+    ArgumentWidthMismatch {
+        ndx: usize,
+        expected: usize,
+        found: usize,
+    },
+    #[error("Netlist simulator encountered an error on a vector operation")]
+    VectorOperatorError(Box<crate::RHDLError>),
+    #[error("Netlist simulation did not converge")]
+    #[diagnostic(help(
+        "The outputs of the black boxes in this netlist did not settle.  This usually indicates a combinatorial loop through a black box."
+    ))]
+    SimulationDidNotConverge,
 }
 
 #[derive(Debug, Error)]
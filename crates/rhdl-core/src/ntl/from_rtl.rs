@@ -336,6 +336,8 @@ fn classify_binary(op: tl::AluBinary) -> BinOpClass {
         tl::AluBinary::Sub => BinOpClass::Vector(bt::VectorOp::Sub),
         tl::AluBinary::Eq => BinOpClass::Vector(bt::VectorOp::Eq),
         tl::AluBinary::Mul => BinOpClass::Vector(bt::VectorOp::Mul),
+        tl::AluBinary::Div => BinOpClass::Vector(bt::VectorOp::Div),
+        tl::AluBinary::Rem => BinOpClass::Vector(bt::VectorOp::Rem),
         tl::AluBinary::Shl => BinOpClass::Vector(bt::VectorOp::Shl),
         tl::AluBinary::Shr => BinOpClass::Vector(bt::VectorOp::Shr),
         tl::AluBinary::Lt => BinOpClass::Vector(bt::VectorOp::Lt),
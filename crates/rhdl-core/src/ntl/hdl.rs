@@ -164,6 +164,8 @@ impl<'a> NetListHDLBuilder<'a> {
             VectorOp::Add => vlog::BinaryOp::Plus,
             VectorOp::Sub => vlog::BinaryOp::Minus,
             VectorOp::Mul => vlog::BinaryOp::Mul,
+            VectorOp::Div => vlog::BinaryOp::Div,
+            VectorOp::Rem => vlog::BinaryOp::Mod,
             VectorOp::Eq => vlog::BinaryOp::Eq,
             VectorOp::Ne => vlog::BinaryOp::Ne,
             VectorOp::Lt => vlog::BinaryOp::Lt,
@@ -175,6 +177,7 @@ impl<'a> NetListHDLBuilder<'a> {
         };
         let arg1 = self.opex_v(&op.arg1);
         let arg2 = self.opex_v(&op.arg2);
+        let divisor = arg2.clone();
         let arg1 = if op.signed {
             parse_quote! { $signed(#arg1) }
         } else {
@@ -185,7 +188,21 @@ impl<'a> NetListHDLBuilder<'a> {
         } else {
             arg2
         };
-        self.add_stmt(parse_quote! { #target = #arg1 #alu #arg2 });
+        // Division by zero yields all ones, and the remainder is the dividend,
+        // matching rhdl-bits and the simulators (Verilog would give X).
+        let width = syn::Index::from(op.arg1.len());
+        match op.op {
+            VectorOp::Div if op.signed => self.add_stmt(parse_quote! {
+                #target = (#divisor == 0) ? $signed({#width{1'b1}}) : #arg1 #alu #arg2
+            }),
+            VectorOp::Div => self.add_stmt(parse_quote! {
+                #target = (#divisor == 0) ? {#width{1'b1}} : #arg1 #alu #arg2
+            }),
+            VectorOp::Rem => self.add_stmt(parse_quote! {
+                #target = (#divisor == 0) ? #arg1 : #arg1 #alu #arg2
+            }),
+            _ => self.add_stmt(parse_quote! { #target = #arg1 #alu #arg2 }),
+        }
         Ok(())
     }
     fn not_op(
@@ -254,6 +271,11 @@ impl<'a> NetListHDLBuilder<'a> {
                 let i = self.opex_v(&black_box.arg[1]);
                 connections.push(parse_quote! { .clock_reset(#cr) });
                 connections.push(parse_quote! { .i(#i) });
+                // The clock enable is an optional third argument
+                if let Some(ce) = black_box.arg.get(2) {
+                    let ce = self.opex_v(ce);
+                    connections.push(parse_quote! { .ce(#ce) });
+                }
             }
         }
         let core_id = self.temporary_counter;
@@ -1,7 +1,11 @@
+pub mod aig;
 pub mod builder;
+pub mod cdc;
+pub mod compiled;
 pub mod display;
 pub mod from_rtl;
 pub mod object;
+pub mod pipeline;
 pub mod spec;
 pub mod visit;
 pub(crate) use builder::Builder;
@@ -9,3 +13,8 @@ pub use object::Object;
 pub mod error;
 pub mod graph;
 pub mod hdl;
+pub mod resources;
+pub mod smt;
+pub mod timing;
+pub mod vhdl;
+pub mod vm;
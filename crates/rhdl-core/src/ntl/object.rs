@@ -1,11 +1,15 @@
 use crate::{
-    BitX, HDLDescriptor, Kind, RHDLError,
+    BitX, HDLDescriptor, Kind, RHDLError, VHDLDescriptor,
     ast::{SourceLocation, spanned_source::SpannedSourceSet},
     common::symtab::{RegisterId, SymbolTable},
     ntl::{
+        cdc::Synchronizer,
         hdl::build_hdl,
+        resources::BlackBoxResources,
+        smt::BlackBoxSmtModel,
         spec::{OpCode, Wire, WireKind},
         visit::visit_object_wires_mut,
+        vm::BlackBoxModel,
     },
     rhif::object::SourceDetails,
 };
@@ -20,10 +24,35 @@ pub enum BlackBoxMode {
     Asynchronous,
 }
 
-#[derive(Clone, Hash)]
+#[derive(Clone)]
 pub struct BlackBox {
     pub code: HDLDescriptor,
     pub mode: BlackBoxMode,
+    /// Optional behavioral model used by the netlist simulator
+    pub model: Option<BlackBoxModel>,
+    /// Optional VHDL implementation used by the VHDL generator
+    pub vhdl: Option<VHDLDescriptor>,
+    /// Optional formal model used by the SMT-LIB2 exporter
+    pub smt: Option<BlackBoxSmtModel>,
+    /// Optional declaration of the resources used by the black box
+    pub resources: Option<BlackBoxResources>,
+    /// The kind of the input to the black box (not including the
+    /// clock and reset of a synchronous black box)
+    pub input_kind: Kind,
+    /// The kind of the output of the black box
+    pub output_kind: Kind,
+    /// Set if the black box is a recognised clock domain crossing
+    pub synchronizer: Option<Synchronizer>,
+}
+
+// The behavioral and formal models (and the resource and synchronizer declarations) do not
+// contribute to the identity of the black box - only the generated HDL and mode do.
+impl Hash for BlackBox {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.code.hash(state);
+        self.mode.hash(state);
+        self.vhdl.hash(state);
+    }
 }
 
 #[derive(Clone, Hash)]
@@ -0,0 +1,413 @@
+//! Pipelining (register retiming) of netlists
+//!
+//! This module splits the combinational logic of a synchronous netlist
+//! into a number of stages, separated by ranks of registers.  The arrival
+//! time of each opcode is estimated with a [DelayModel] (as the static
+//! timing estimator does), and each opcode is placed in the stage whose
+//! share of the total delay contains the time at which its arguments are
+//! ready.  An opcode is never split, so a slow opcode (like a multiplier)
+//! can make its stage longer than its share.  Whenever a value is
+//! used in a later stage than the one that computes it, it is carried forward
+//! through one register per stage boundary.  The outputs are registered by the
+//! last rank, so a netlist with `N` stages has a latency of `N` clock cycles.
+//!
+//! The reset input is carried through the pipeline like any other input,
+//! so the logic in each stage sees the reset that went along with its data.
+//! The clock input is never registered.  The pipeline registers themselves are
+//! not reset, so the outputs for the first `N` cycles are not meaningful.
+//! They can be given a clock enable, which is added to the netlist as a
+//! third input.
+//!
+//! Each rank of registers is a synchronous black box, with a Verilog and VHDL
+//! implementation and models for the netlist simulator and the formal exporter.
+use std::collections::HashMap;
+
+use quote::{format_ident, quote};
+use rhdl_vlog as vlog;
+use syn::parse_quote;
+
+use crate::{
+    BitX, ClockReset, Digital, HDLDescriptor, Kind, RHDLError, VHDLDescriptor,
+    common::symtab::RegisterId,
+    error::rhdl_error,
+    hdl::vhdl::{self, Direction},
+    ntl::{
+        error::{NetListError, NetListICE},
+        object::{BlackBox, BlackBoxMode, LocatedOpCode, Object, WireDetails},
+        resources::BlackBoxResources,
+        smt::{BlackBoxSmt, BlackBoxSmtModel},
+        spec::{self, BlackBoxId, OpCode, Wire, WireKind},
+        timing::DelayModel,
+        visit::{visit_wires, visit_wires_mut},
+        vm::{BlackBoxModel, BlackBoxSim},
+    },
+    types::path::{Path, bit_range},
+};
+
+fn raise_ice(object: &Object, cause: NetListICE, ndx: Option<usize>) -> RHDLError {
+    rhdl_error(NetListError {
+        cause,
+        src: object.code.source(),
+        elements: ndx
+            .and_then(|ndx| object.ops[ndx].loc)
+            .map(|loc| object.code.span(loc).into())
+            .into_iter()
+            .collect(),
+    })
+}
+
+fn clock_reset_bit(field: &str) -> usize {
+    bit_range(ClockReset::static_kind(), &Path::default().field(field))
+        .expect("ClockReset has clock and reset fields")
+        .0
+        .start
+}
+
+/// Bit level model of a rank of pipeline registers.
+///
+/// The state is laid out as `[clock, current.., next..]`, like that
+/// of a flip flop without a reset.  With a clock enable, the enable
+/// is the third argument.
+struct PipelineRegisterModel {
+    width: usize,
+    clock_index: usize,
+    clock_enable: bool,
+}
+
+impl BlackBoxSim for PipelineRegisterModel {
+    fn init(&self) -> Vec<BitX> {
+        // Like the generated Verilog, the registers start at zero
+        vec![BitX::Zero; 1 + 2 * self.width]
+    }
+
+    fn sim(&self, args: &[Vec<BitX>], state: &mut Vec<BitX>) -> Vec<BitX> {
+        let current = 1..1 + self.width;
+        let next = 1 + self.width..1 + 2 * self.width;
+        let clock = args[0][self.clock_index];
+        if clock == BitX::Zero {
+            if self.clock_enable && args[2][0] != BitX::One {
+                state.copy_within(current.clone(), next.start);
+            } else {
+                state[next.clone()].copy_from_slice(&args[1]);
+            }
+        }
+        if clock == BitX::One && state[0] != BitX::One {
+            state.copy_within(next, current.start);
+        }
+        state[0] = clock;
+        state[current].to_vec()
+    }
+}
+
+impl BlackBoxSmt for PipelineRegisterModel {
+    fn init(&self) -> Vec<BitX> {
+        vec![BitX::Zero; self.width]
+    }
+
+    fn step(&self, state: &[String], args: &[Vec<String>]) -> (Vec<String>, Vec<String>) {
+        let next = if self.clock_enable {
+            let ce = &args[2][0];
+            args[1]
+                .iter()
+                .zip(state)
+                .map(|(d, q)| format!("(ite {ce} {d} {q})"))
+                .collect()
+        } else {
+            args[1].clone()
+        };
+        (state.to_vec(), next)
+    }
+}
+
+fn register_hdl(name: &str, width: usize, clock_enable: bool) -> HDLDescriptor {
+    let module_name = format_ident!("{}", name);
+    let init = vlog::lit_verilog(width as u32, &format!("b{}", "0".repeat(width)));
+    let data_width: vlog::BitRange = (0..width).into();
+    let clock_index = syn::Index::from(clock_reset_bit("clock"));
+    let (ce_port, update) = if clock_enable {
+        (
+            quote! { , input wire ce },
+            quote! { if (ce) begin o <= i; end },
+        )
+    } else {
+        (quote! {}, quote! { o <= i; })
+    };
+    let module: vlog::ModuleDef = parse_quote! {
+        module #module_name(
+            input wire [1:0] clock_reset,
+            input wire [#data_width] i,
+            output reg [#data_width] o
+            #ce_port
+        );
+            wire clock;
+            assign clock = clock_reset[#clock_index];
+            initial begin
+                o = #init;
+            end
+            always @(posedge clock) begin
+                #update
+            end
+        endmodule
+    };
+    HDLDescriptor {
+        name: name.into(),
+        modules: module.into(),
+    }
+}
+
+fn register_vhdl(name: &str, width: usize, clock_enable: bool) -> VHDLDescriptor {
+    let init = vhdl::vector_literal(&vec![BitX::Zero; width]);
+    let ports = [
+        vhdl::port("clock_reset", Direction::In, 2),
+        vhdl::port("i", Direction::In, width),
+        vhdl::port("o", Direction::Out, width),
+        clock_enable
+            .then(|| vhdl::port("ce", Direction::In, 1))
+            .flatten(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+    let declarations = [format!("signal q : {} := {init};", vhdl::slv(width))];
+    let update = if clock_enable {
+        "if ce(0) = '1' then
+            q <= i;
+        end if;"
+    } else {
+        "q <= i;"
+    };
+    let body = [
+        "o <= q;".to_string(),
+        format!(
+            "process (clock_reset({clock})) is
+begin
+    if rising_edge(clock_reset({clock})) then
+        {update}
+    end if;
+end process;",
+            clock = clock_reset_bit("clock"),
+        ),
+    ];
+    VHDLDescriptor::new(name, vhdl::entity(name, &ports, &declarations, &body))
+}
+
+// The opcodes of the netlist, in an order where each opcode comes
+// after the opcodes that compute its arguments
+fn topological_order(
+    object: &Object,
+    writers: &HashMap<RegisterId<WireKind>, usize>,
+) -> Result<Vec<usize>, RHDLError> {
+    let mut dependents = vec![vec![]; object.ops.len()];
+    let mut pending = vec![0; object.ops.len()];
+    for (ndx, lop) in object.ops.iter().enumerate() {
+        visit_wires(&lop.op, |sense, wire| {
+            if let Some(&writer) = wire.reg().and_then(|reg| writers.get(&reg))
+                && sense.is_read()
+            {
+                dependents[writer].push(ndx);
+                pending[ndx] += 1;
+            }
+        });
+    }
+    let mut ready = (0..object.ops.len())
+        .filter(|&ndx| pending[ndx] == 0)
+        .collect::<Vec<_>>();
+    let mut order = vec![];
+    while let Some(ndx) = ready.pop() {
+        order.push(ndx);
+        for &next in &dependents[ndx] {
+            pending[next] -= 1;
+            if pending[next] == 0 {
+                ready.push(next);
+            }
+        }
+    }
+    if order.len() != object.ops.len() {
+        let ndx = (0..object.ops.len()).find(|&ndx| pending[ndx] != 0);
+        return Err(raise_ice(object, NetListICE::LogicLoop, ndx));
+    }
+    Ok(order)
+}
+
+struct Retimer {
+    object: Object,
+    clock: Option<RegisterId<WireKind>>,
+    // The stage in which each register is computed
+    produced: HashMap<RegisterId<WireKind>, usize>,
+    // The copy of a register that is available in a later stage
+    versions: HashMap<(RegisterId<WireKind>, usize), Wire>,
+    // The (input, output) pairs carried by each rank of registers
+    ranks: Vec<Vec<(Wire, Wire)>>,
+}
+
+impl Retimer {
+    // The copy of the wire that is available in the given stage
+    fn version(&mut self, wire: Wire, stage: usize) -> Wire {
+        let Some(reg) = wire.reg() else {
+            return wire;
+        };
+        if Some(reg) == self.clock || stage <= self.produced.get(&reg).copied().unwrap_or(0) {
+            return wire;
+        }
+        if let Some(version) = self.versions.get(&(reg, stage)) {
+            return *version;
+        }
+        let previous = self.version(wire, stage - 1);
+        let details = self.object.symtab[wire].clone();
+        let version = self.object.symtab.reg((), details);
+        self.ranks[stage - 1].push((previous, version));
+        self.versions.insert((reg, stage), version);
+        version
+    }
+}
+
+/// Split the logic of a synchronous netlist into `stages` pipeline stages.
+///
+/// The ranks of registers are named `{name}_stage_{k}`, so `name` should be the
+/// (flattened) scoped name of the circuit that the netlist belongs to.  With no
+/// stages, the netlist is returned unchanged.  If `clock_enable` is set, the
+/// ranks only load on the clock edges where a new (single bit) input, added after
+/// the existing inputs, is high.
+pub fn pipeline(
+    object: &Object,
+    stages: usize,
+    model: &(impl DelayModel + ?Sized),
+    name: &str,
+    clock_enable: bool,
+) -> Result<Object, RHDLError> {
+    if stages == 0 {
+        return Ok(object.clone());
+    }
+    let mut writers = HashMap::new();
+    for (ndx, lop) in object.ops.iter().enumerate() {
+        visit_wires(&lop.op, |sense, wire| {
+            if let Some(reg) = wire.reg()
+                && !sense.is_read()
+            {
+                writers.insert(reg, ndx);
+            }
+        });
+    }
+    // Estimate the time at which the arguments and the result of each opcode are ready
+    let mut ready = vec![0.0; object.ops.len()];
+    let mut arrival = vec![0.0; object.ops.len()];
+    for ndx in topological_order(object, &writers)? {
+        let op = &object.ops[ndx].op;
+        let mut start: f64 = 0.0;
+        visit_wires(op, |sense, wire| {
+            if let Some(&writer) = wire.reg().and_then(|reg| writers.get(&reg))
+                && sense.is_read()
+            {
+                start = start.max(arrival[writer]);
+            }
+        });
+        ready[ndx] = start;
+        arrival[ndx] = start + model.delay(op);
+    }
+    // Each stage takes an equal share of the total delay
+    let total = arrival.iter().copied().fold(0.0, f64::max);
+    let stage = ready
+        .iter()
+        .map(|&time| {
+            if total > 0.0 {
+                ((time * stages as f64 / total).floor() as usize).min(stages - 1)
+            } else {
+                0
+            }
+        })
+        .collect::<Vec<_>>();
+    let mut retimer = Retimer {
+        object: object.clone(),
+        clock: object
+            .inputs
+            .first()
+            .and_then(|cr| cr.get(clock_reset_bit("clock")))
+            .copied(),
+        produced: writers
+            .iter()
+            .map(|(&reg, &ndx)| (reg, stage[ndx]))
+            .collect(),
+        versions: HashMap::new(),
+        ranks: vec![vec![]; stages],
+    };
+    let mut ops = std::mem::take(&mut retimer.object.ops);
+    for (lop, &stage) in ops.iter_mut().zip(&stage) {
+        visit_wires_mut(&mut lop.op, |sense, wire| {
+            if sense.is_read() {
+                *wire = retimer.version(*wire, stage);
+            }
+        });
+    }
+    let outputs = std::mem::take(&mut retimer.object.outputs);
+    let outputs = outputs
+        .into_iter()
+        .map(|wire| retimer.version(wire, stages))
+        .collect();
+    let Retimer {
+        mut object, ranks, ..
+    } = retimer;
+    object.outputs = outputs;
+    let clock_reset = object
+        .inputs
+        .first()
+        .map(|cr| cr.iter().copied().map(Wire::Register).collect::<Vec<_>>())
+        .unwrap_or_default();
+    let ce = clock_enable.then(|| {
+        let details = WireDetails {
+            source_details: None,
+            kind: Kind::make_bits(1),
+            bit: 0,
+        };
+        let ce = object.symtab.reg((), details);
+        object.inputs.push(ce.reg().into_iter().collect());
+        ce
+    });
+    for (rank, registers) in ranks.into_iter().enumerate() {
+        if registers.is_empty() {
+            continue;
+        }
+        let width = registers.len();
+        let model = PipelineRegisterModel {
+            width,
+            clock_index: clock_reset_bit("clock"),
+            clock_enable,
+        };
+        let smt = PipelineRegisterModel {
+            width,
+            clock_index: clock_reset_bit("clock"),
+            clock_enable,
+        };
+        let rank_name = format!("{name}_stage_{rank}");
+        let code = BlackBoxId::new(object.black_boxes.len());
+        object.black_boxes.push(BlackBox {
+            code: register_hdl(&rank_name, width, clock_enable),
+            mode: BlackBoxMode::Synchronous,
+            model: Some(BlackBoxModel::new(model)),
+            vhdl: Some(register_vhdl(&rank_name, width, clock_enable)),
+            smt: Some(BlackBoxSmtModel::new(smt)),
+            resources: Some(BlackBoxResources {
+                flip_flops: width,
+                memory_bits: 0,
+            }),
+            input_kind: Kind::make_bits(width),
+            output_kind: Kind::make_bits(width),
+            synchronizer: None,
+        });
+        let (arg, lhs) = registers.into_iter().unzip();
+        object.ops.push(LocatedOpCode {
+            op: OpCode::BlackBox(spec::BlackBox {
+                lhs,
+                arg: [Some(clock_reset.clone()), Some(arg), ce.map(|ce| vec![ce])]
+                    .into_iter()
+                    .flatten()
+                    .collect(),
+                code,
+            }),
+            loc: None,
+        });
+    }
+    // The registers come first, as linked children do, so that the netlist
+    // simulator sees their outputs before the logic that reads them
+    object.ops.extend(ops);
+    Ok(object)
+}
@@ -0,0 +1,213 @@
+//! Resource estimation for netlists
+//!
+//! This module estimates the resources needed to implement a netlist on an
+//! FPGA, without running a synthesis tool.  The estimate counts flip flops,
+//! look up tables (LUTs), multiplexers, adders and multipliers (by width),
+//! and the black box instances (like RAMs) used by the design.
+//!
+//! The single bit logic is mapped onto LUTs by counting the number of
+//! two input merges it needs.  A `k` input LUT can absorb a tree of `k - 1`
+//! such merges, so the LUT count is the total number of merges divided by
+//! `k - 1`.  Inverters are free, since they are absorbed into the LUTs.
+//! Arithmetic is assumed to use one LUT per bit (with a dedicated carry chain),
+//! and multipliers are assumed to map to dedicated multiplier blocks.  These
+//! assumptions are captured in a [LutModel].
+//!
+//! Flip flops and memory are only known to the black boxes that implement
+//! them.  A black box can declare its resources with [BlackBoxResources]
+//! (see [Descriptor::with_black_box_resources](crate::Descriptor::with_black_box_resources)).
+//! Black boxes that do not are still reported as instances, but do not
+//! contribute to the totals.
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::ntl::{
+    object::Object,
+    spec::{OpCode, UnaryOp, VectorOp},
+};
+
+/// The resources used by a black box, as declared by the circuit that provides it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize)]
+pub struct BlackBoxResources {
+    /// The number of flip flops in the black box
+    pub flip_flops: usize,
+    /// The number of bits of memory in the black box
+    pub memory_bits: usize,
+}
+
+/// The assumptions used to map netlist logic onto look up tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct LutModel {
+    /// The number of inputs to each LUT
+    pub k: usize,
+    /// If true, multipliers are built from LUTs instead of
+    /// dedicated multiplier blocks.  Each bit of the partial
+    /// product array then costs one LUT.
+    pub lut_multipliers: bool,
+}
+
+impl Default for LutModel {
+    fn default() -> Self {
+        Self {
+            k: 6,
+            lut_multipliers: false,
+        }
+    }
+}
+
+impl LutModel {
+    /// A model for a device with `k` input LUTs.
+    pub fn new(k: usize) -> Self {
+        assert!(k >= 2, "LUTs must have at least 2 inputs");
+        Self {
+            k,
+            ..Default::default()
+        }
+    }
+    /// Build multipliers from LUTs, instead of dedicated multiplier blocks.
+    pub fn with_lut_multipliers(self) -> Self {
+        Self {
+            lut_multipliers: true,
+            ..self
+        }
+    }
+}
+
+/// A black box instance in a netlist.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BlackBoxInstance {
+    /// The (flattened) name of the black box
+    pub name: String,
+    /// The number of input bits
+    pub input_bits: usize,
+    /// The number of output bits
+    pub output_bits: usize,
+    /// The resources declared by the black box, if any
+    pub resources: Option<BlackBoxResources>,
+}
+
+/// An estimate of the resources needed to implement a netlist.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Resources {
+    /// The number of flip flops
+    pub flip_flops: usize,
+    /// The number of LUTs
+    pub luts: usize,
+    /// The number of 2:1 multiplexers
+    pub muxes: usize,
+    /// The number of adders (including subtractors and magnitude
+    /// comparators), keyed by width
+    pub adders: BTreeMap<usize, usize>,
+    /// The number of multipliers, keyed by width
+    pub multipliers: BTreeMap<usize, usize>,
+    /// The number of bits of memory
+    pub memory_bits: usize,
+    /// The black box instances
+    pub black_boxes: Vec<BlackBoxInstance>,
+}
+
+impl Resources {
+    /// The total number of adder bits.
+    pub fn adder_bits(&self) -> usize {
+        self.adders.iter().map(|(width, count)| width * count).sum()
+    }
+    /// The total number of multiplier bits.
+    pub fn multiplier_bits(&self) -> usize {
+        self.multipliers
+            .iter()
+            .map(|(width, count)| width * count)
+            .sum()
+    }
+}
+
+fn clog2(width: usize) -> usize {
+    width.max(2).next_power_of_two().trailing_zeros() as usize
+}
+
+/// Estimate the resources needed to implement the netlist, using the given LUT model.
+pub fn estimate(object: &Object, model: &LutModel) -> Resources {
+    let mut resources = Resources::default();
+    // The number of two input merges that the single bit logic needs
+    let mut merges = 0;
+    // The number of LUTs used by arithmetic, one per bit
+    let mut arithmetic = 0;
+    for lop in &object.ops {
+        match &lop.op {
+            OpCode::Noop | OpCode::Assign(_) | OpCode::Not(_) => {}
+            OpCode::Binary(_) => merges += 1,
+            OpCode::Select(_) => {
+                merges += 2;
+                resources.muxes += 1;
+            }
+            OpCode::Case(case) => {
+                let entries = case.entries.len();
+                merges += (case.discriminant.len() + entries).saturating_sub(1);
+                resources.muxes += entries.saturating_sub(1);
+            }
+            OpCode::Unary(unary) => {
+                let width = unary.arg.len();
+                match unary.op {
+                    UnaryOp::All | UnaryOp::Any | UnaryOp::Xor => {
+                        merges += width.saturating_sub(1);
+                    }
+                    UnaryOp::Neg => {
+                        *resources.adders.entry(width).or_default() += 1;
+                        arithmetic += width;
+                    }
+                }
+            }
+            OpCode::Vector(vector) => {
+                let width = vector.arg1.len().max(vector.arg2.len());
+                match vector.op {
+                    VectorOp::Add
+                    | VectorOp::Sub
+                    | VectorOp::Lt
+                    | VectorOp::Le
+                    | VectorOp::Gt
+                    | VectorOp::Ge => {
+                        *resources.adders.entry(width).or_default() += 1;
+                        arithmetic += width;
+                    }
+                    VectorOp::Mul => {
+                        *resources.multipliers.entry(width).or_default() += 1;
+                        if model.lut_multipliers {
+                            arithmetic += vector.arg1.len() * vector.arg2.len();
+                        }
+                    }
+                    // A restoring divider is a chain of `width` subtractions
+                    VectorOp::Div | VectorOp::Rem => {
+                        *resources.adders.entry(width).or_default() += width;
+                        arithmetic += width * width;
+                    }
+                    VectorOp::Eq | VectorOp::Ne => {
+                        merges += (vector.arg1.len() + vector.arg2.len()).saturating_sub(1);
+                    }
+                    // A barrel shifter has a layer of muxes for each bit of the shift
+                    VectorOp::Shl | VectorOp::Shr => {
+                        let layers = vector.arg2.len().min(clog2(vector.arg1.len()));
+                        let muxes = vector.arg1.len() * layers;
+                        resources.muxes += muxes;
+                        merges += 2 * muxes;
+                    }
+                }
+            }
+            OpCode::BlackBox(bb) => {
+                let black_box = &object.black_boxes[bb.code.raw()];
+                let declared = black_box.resources;
+                if let Some(declared) = declared {
+                    resources.flip_flops += declared.flip_flops;
+                    resources.memory_bits += declared.memory_bits;
+                }
+                resources.black_boxes.push(BlackBoxInstance {
+                    name: black_box.code.name.clone(),
+                    input_bits: bb.arg.iter().map(Vec::len).sum(),
+                    output_bits: bb.lhs.len(),
+                    resources: declared,
+                });
+            }
+        }
+    }
+    resources.luts = merges.div_ceil(model.k - 1) + arithmetic;
+    resources
+}
@@ -0,0 +1,754 @@
+//! SMT-LIB2 export of netlists
+//!
+//! Simulation can only show that a design works for the test vectors
+//! it is given.  The tools in this module instead translate a netlist
+//! [Object] into a set of SMT-LIB2 formulas (in the `QF_BV` logic), so that
+//! a solver like `z3` or `bitwuzla` can search for inputs that break a
+//! property, or that make two designs disagree.
+//!
+//! Every wire in the netlist becomes a `Bool` constant, and every operation
+//! becomes an assertion that relates its output wires to its inputs.  The
+//! vector operators are encoded with the corresponding bit-vector operators,
+//! and follow the same rules as the [NetListVM](crate::ntl::vm::NetListVM):
+//! division by zero, a `case` with no matching arm, and literal `X` bits
+//! all produce unconstrained values.  A proof of equivalence thus holds
+//! for every way the unknown bits could be resolved.
+//!
+//! Synchronous designs are handled by bounded model checking.  A [Bmc]
+//! unrolls the netlist for a fixed number of clock cycles, with free inputs
+//! and reset in each cycle.  The state of the design lives in its black boxes,
+//! and so each black box must provide a formal model in the form of a
+//! [BlackBoxSmt] implementation.  The model maps the state at the start of a
+//! clock cycle and the arguments during that cycle to the outputs, and to the
+//! state at the start of the next cycle.
+use std::{collections::HashMap, process::Command, sync::Arc};
+
+use crate::{
+    BitX, ClockReset, Digital, RHDLError,
+    ast::SourceLocation,
+    common::slot_vec::SlotKey,
+    error::rhdl_error,
+    ntl::{
+        error::{NetListError, NetListICE},
+        object::{LocatedOpCode, Object},
+        spec::{
+            Assign, Binary, BinaryOp, Case, CaseEntry, Not, OpCode, Select, Unary, UnaryOp, Vector,
+            VectorOp, Wire,
+        },
+    },
+    types::path::{Path, bit_range},
+};
+
+type Result<T> = std::result::Result<T, RHDLError>;
+
+/// A formal model of a black box, used when exporting netlists to SMT-LIB2.
+///
+/// Terms are SMT-LIB2 expressions of sort `Bool`, one per bit.  As with
+/// [BlackBoxSim](crate::ntl::vm::BlackBoxSim), for synchronous black boxes
+/// `args[0]` holds the clock and reset bits, and `args[1]` holds the input
+/// bits.  The clock is held low for the duration of a cycle, and rises
+/// between cycles.
+pub trait BlackBoxSmt: Send + Sync {
+    /// The state of the black box at the start of the first cycle.
+    /// Unknown bits are left unconstrained.
+    fn init(&self) -> Vec<BitX>;
+    /// Given the state at the start of a cycle and the arguments during the
+    /// cycle, return the outputs during the cycle and the state at the start
+    /// of the next one.
+    fn step(&self, state: &[String], args: &[Vec<String>]) -> (Vec<String>, Vec<String>);
+}
+
+/// A shareable handle to a [BlackBoxSmt] model.
+#[derive(Clone)]
+pub struct BlackBoxSmtModel(Arc<dyn BlackBoxSmt>);
+
+impl BlackBoxSmtModel {
+    /// Wrap a formal model so it can be attached to a black box.
+    pub fn new<T: BlackBoxSmt + 'static>(model: T) -> Self {
+        Self(Arc::new(model))
+    }
+}
+
+impl std::fmt::Debug for BlackBoxSmtModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "BlackBoxSmtModel")
+    }
+}
+
+/// The SMT-LIB2 constant for a known bit
+pub fn smt_bit(bit: bool) -> &'static str {
+    if bit { "true" } else { "false" }
+}
+
+/// An SMT solver that is run as an external process.
+///
+/// The solver is handed a script file, and must print `sat` or `unsat`
+/// followed by the answer to a `get-value` query.
+#[derive(Clone, Debug)]
+pub struct Solver {
+    command: String,
+    args: Vec<String>,
+}
+
+impl Solver {
+    /// A solver run as `command args.. <script>`
+    pub fn new(command: &str, args: &[&str]) -> Self {
+        Self {
+            command: command.into(),
+            args: args.iter().map(|x| x.to_string()).collect(),
+        }
+    }
+    /// The `z3` solver, which must be in your `PATH`.
+    pub fn z3() -> Self {
+        Self::new("z3", &["-smt2"])
+    }
+    /// The `bitwuzla` solver, which must be in your `PATH`.
+    pub fn bitwuzla() -> Self {
+        Self::new("bitwuzla", &[])
+    }
+    fn run(&self, script: &str) -> Result<String> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("problem.smt2");
+        std::fs::write(&path, script)?;
+        let output = Command::new(&self.command)
+            .args(&self.args)
+            .arg(&path)
+            .output()
+            .map_err(|err| {
+                RHDLError::SmtSolverError(format!("Unable to run {}: {err}", self.command))
+            })?;
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+/// A counterexample found by the solver.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trace {
+    /// The first cycle in which the check fails
+    pub cycle: usize,
+    /// The values of the inputs in each cycle, one entry per argument
+    pub inputs: Vec<Vec<Vec<BitX>>>,
+}
+
+fn raise_ice(object: &Object, cause: NetListICE, location: Option<SourceLocation>) -> RHDLError {
+    rhdl_error(NetListError {
+        cause,
+        src: object.code.source(),
+        elements: location
+            .map(|loc| object.code.span(loc).into())
+            .into_iter()
+            .collect(),
+    })
+}
+
+fn all(terms: &[String]) -> String {
+    match terms {
+        [] => "true".into(),
+        [term] => term.clone(),
+        _ => format!("(and {})", terms.join(" ")),
+    }
+}
+
+fn any(terms: &[String]) -> String {
+    match terms {
+        [] => "false".into(),
+        [term] => term.clone(),
+        _ => format!("(or {})", terms.join(" ")),
+    }
+}
+
+// Pack a list of bits (LSB first) into a bit vector term
+fn to_bv(bits: &[String]) -> String {
+    let bits = bits
+        .iter()
+        .rev()
+        .map(|bit| format!("(ite {bit} #b1 #b0)"))
+        .collect::<Vec<_>>();
+    match bits.as_slice() {
+        [bit] => bit.clone(),
+        _ => format!("(concat {})", bits.join(" ")),
+    }
+}
+
+fn extract(bv: &str, bits: usize) -> Vec<String> {
+    (0..bits)
+        .map(|bit| format!("(= ((_ extract {bit} {bit}) {bv}) #b1)"))
+        .collect()
+}
+
+fn resize(term: String, from: usize, to: usize, signed: bool) -> String {
+    if to > from {
+        let op = if signed { "sign_extend" } else { "zero_extend" };
+        format!("((_ {op} {}) {term})", to - from)
+    } else if to < from {
+        format!("((_ extract {} 0) {term})", to - 1)
+    } else {
+        term
+    }
+}
+
+/// A bounded model checking problem under construction.
+///
+/// The problem has a fixed number of cycles, each with its own set of free
+/// inputs.  Netlists are instantiated into the problem, where they share
+/// the inputs, and the outputs of each instance are returned as terms that
+/// can be used to build assertions.
+pub struct Bmc {
+    text: String,
+    fresh: usize,
+    instances: usize,
+    // The unknown value of each `X` literal of the netlist being instantiated
+    undefined: Vec<Option<String>>,
+    inputs: Vec<Vec<Vec<String>>>,
+    checks: Vec<String>,
+}
+
+impl Bmc {
+    /// A problem with the given number of cycles, and arguments
+    /// of the given widths.
+    pub fn new(widths: &[usize], depth: usize) -> Self {
+        let mut bmc = Self {
+            text: "(set-option :produce-models true)\n(set-logic QF_BV)\n".into(),
+            fresh: 0,
+            instances: 0,
+            undefined: vec![],
+            inputs: vec![],
+            checks: vec![],
+        };
+        for cycle in 0..depth {
+            let inputs = widths
+                .iter()
+                .enumerate()
+                .map(|(arg, &width)| {
+                    (0..width)
+                        .map(|bit| bmc.declare(format!("in_c{cycle}_a{arg}_{bit}")))
+                        .collect()
+                })
+                .collect();
+            bmc.inputs.push(inputs);
+        }
+        bmc
+    }
+    /// A problem for a synchronous design with the given number of input
+    /// bits.  Argument 0 is the clock and reset, and the clock is held low.
+    pub fn synchronous(input_bits: usize, depth: usize) -> Result<Self> {
+        let mut bmc = Self::new(&[ClockReset::BITS, input_bits], depth);
+        let (clock, _) = bit_range(ClockReset::static_kind(), &Path::default().field("clock"))?;
+        bmc.fix(0, clock.start, false);
+        Ok(bmc)
+    }
+    /// Fix a bit of an argument to the given value in every cycle.
+    pub fn fix(&mut self, arg: usize, bit: usize, value: bool) {
+        for cycle in 0..self.inputs.len() {
+            let term = format!("(= {} {})", self.inputs[cycle][arg][bit], smt_bit(value));
+            self.assert(&term);
+        }
+    }
+    /// The number of cycles in the problem.
+    pub fn depth(&self) -> usize {
+        self.inputs.len()
+    }
+    /// The input bits of the given cycle, one entry per argument.
+    pub fn inputs(&self, cycle: usize) -> &[Vec<String>] {
+        &self.inputs[cycle]
+    }
+    fn declare(&mut self, name: String) -> String {
+        self.text
+            .push_str(&format!("(declare-const {name} Bool)\n"));
+        name
+    }
+    /// A new unconstrained `Bool` constant.
+    pub fn fresh(&mut self) -> String {
+        self.fresh += 1;
+        self.declare(format!("x{}", self.fresh))
+    }
+    /// Add an assertion to the problem.
+    pub fn assert(&mut self, term: &str) {
+        self.text.push_str(&format!("(assert {term})\n"));
+    }
+    fn assert_eq(&mut self, lhs: &str, rhs: &str) {
+        self.assert(&format!("(= {lhs} {rhs})"));
+    }
+    fn define_bv(&mut self, width: usize, term: String) -> String {
+        self.fresh += 1;
+        let name = format!("v{}", self.fresh);
+        self.text.push_str(&format!(
+            "(define-fun {name} () (_ BitVec {width}) {term})\n"
+        ));
+        name
+    }
+    /// Add the netlist to the problem, and return the terms for its outputs
+    /// in each cycle.  Fails if the netlist has a black box with no formal model,
+    /// or its arguments do not match the inputs of the problem.
+    pub fn instantiate(&mut self, object: &Object) -> Result<Vec<Vec<String>>> {
+        let instance = self.instances;
+        self.instances += 1;
+        let arguments = self.inputs.first().map(|x| x.len()).unwrap_or_default();
+        if object.inputs.len() != arguments {
+            return Err(raise_ice(
+                object,
+                NetListICE::ArgumentCountMismatch {
+                    expected: arguments,
+                    found: object.inputs.len(),
+                },
+                None,
+            ));
+        }
+        let models = object
+            .black_boxes
+            .iter()
+            .map(|bb| {
+                bb.smt.clone().ok_or_else(|| {
+                    raise_ice(
+                        object,
+                        NetListICE::BlackBoxHasNoFormalModel {
+                            name: bb.code.name.clone(),
+                        },
+                        None,
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let mut states = models
+            .iter()
+            .enumerate()
+            .map(|(bb, model)| {
+                model
+                    .0
+                    .init()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(bit, init)| {
+                        let name = self.declare(format!("u{instance}_c0_s{bb}_{bit}"));
+                        match init {
+                            BitX::Zero => self.assert(&format!("(not {name})")),
+                            BitX::One => self.assert(&name),
+                            BitX::X => {}
+                        }
+                        name
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let mut outputs = vec![];
+        for cycle in 0..self.depth() {
+            let prefix = format!("u{instance}_c{cycle}_");
+            let wires = (0..object.symtab.reg_vec().len())
+                .map(|ndx| self.declare(format!("{prefix}w{ndx}")))
+                .collect::<Vec<_>>();
+            // Each `X` literal is a single unknown value, no matter how often it is read
+            self.undefined = object
+                .symtab
+                .lit_vec()
+                .iter()
+                .enumerate()
+                .map(|(ndx, (bit, _))| {
+                    (*bit == BitX::X).then(|| self.declare(format!("{prefix}l{ndx}")))
+                })
+                .collect();
+            for (ndx, (input, arg)) in object
+                .inputs
+                .iter()
+                .zip(self.inputs[cycle].clone())
+                .enumerate()
+            {
+                // Unused arguments have no wires
+                if input.is_empty() {
+                    continue;
+                }
+                if input.len() != arg.len() {
+                    return Err(raise_ice(
+                        object,
+                        NetListICE::ArgumentWidthMismatch {
+                            ndx,
+                            expected: input.len(),
+                            found: arg.len(),
+                        },
+                        None,
+                    ));
+                }
+                for (reg, bit) in input.iter().zip(arg) {
+                    self.assert_eq(&wires[reg.index()], &bit);
+                }
+            }
+            let mut next_states = states.clone();
+            for lop in &object.ops {
+                self.op(object, &wires, &states, &mut next_states, &models, lop)?;
+            }
+            outputs.push(
+                object
+                    .outputs
+                    .iter()
+                    .map(|&wire| self.read(object, &wires, wire))
+                    .collect(),
+            );
+            // Name the state at the start of the next cycle
+            states = next_states
+                .into_iter()
+                .enumerate()
+                .map(|(bb, next)| {
+                    next.into_iter()
+                        .enumerate()
+                        .map(|(bit, term)| {
+                            let name =
+                                self.declare(format!("u{instance}_c{}_s{bb}_{bit}", cycle + 1));
+                            self.assert_eq(&name, &term);
+                            name
+                        })
+                        .collect()
+                })
+                .collect();
+        }
+        Ok(outputs)
+    }
+    fn read(&mut self, object: &Object, wires: &[String], wire: Wire) -> String {
+        match wire {
+            Wire::Register(rid) => wires[rid.index()].clone(),
+            Wire::Literal(lid) => match object.symtab[lid] {
+                BitX::Zero => "false".into(),
+                BitX::One => "true".into(),
+                BitX::X => self.undefined[lid.index()].clone().unwrap_or_default(),
+            },
+        }
+    }
+    fn read_v(&mut self, object: &Object, wires: &[String], operands: &[Wire]) -> Vec<String> {
+        operands
+            .iter()
+            .map(|&wire| self.read(object, wires, wire))
+            .collect()
+    }
+    fn write(
+        &mut self,
+        object: &Object,
+        wires: &[String],
+        wire: Wire,
+        term: &str,
+        location: Option<SourceLocation>,
+    ) -> Result<()> {
+        match wire {
+            Wire::Literal(_) => Err(raise_ice(
+                object,
+                NetListICE::ExpectedRegisterNotConstant,
+                location,
+            )),
+            Wire::Register(rid) => {
+                self.assert_eq(&wires[rid.index()], term);
+                Ok(())
+            }
+        }
+    }
+    fn op(
+        &mut self,
+        object: &Object,
+        wires: &[String],
+        states: &[Vec<String>],
+        next_states: &mut [Vec<String>],
+        models: &[BlackBoxSmtModel],
+        lop: &LocatedOpCode,
+    ) -> Result<()> {
+        let loc = lop.loc;
+        match &lop.op {
+            OpCode::Noop => {}
+            OpCode::Assign(Assign { lhs, rhs }) => {
+                let rhs = self.read(object, wires, *rhs);
+                self.write(object, wires, *lhs, &rhs, loc)?;
+            }
+            OpCode::Binary(Binary {
+                op,
+                lhs,
+                arg1,
+                arg2,
+            }) => {
+                let arg1 = self.read(object, wires, *arg1);
+                let arg2 = self.read(object, wires, *arg2);
+                let op = match op {
+                    BinaryOp::Xor => "xor",
+                    BinaryOp::And => "and",
+                    BinaryOp::Or => "or",
+                };
+                self.write(object, wires, *lhs, &format!("({op} {arg1} {arg2})"), loc)?;
+            }
+            OpCode::Vector(op) => {
+                let arg1 = self.read_v(object, wires, &op.arg1);
+                let arg2 = self.read_v(object, wires, &op.arg2);
+                let result = self.vector(op, arg1, arg2);
+                for (&lhs, bit) in op.lhs.iter().zip(result) {
+                    self.write(object, wires, lhs, &bit, loc)?;
+                }
+            }
+            OpCode::Case(Case {
+                lhs,
+                discriminant,
+                entries,
+            }) => {
+                let discriminant = self.read_v(object, wires, discriminant);
+                // Build the chain of matches from the last entry backwards
+                let mut result = self.fresh();
+                for (entry, wire) in entries.iter().rev() {
+                    let value = self.read(object, wires, *wire);
+                    result = match entry {
+                        CaseEntry::Literal(lit) => {
+                            let matches = lit
+                                .bits()
+                                .iter()
+                                .zip(&discriminant)
+                                .map(|(bit, term)| match bit {
+                                    BitX::One => term.clone(),
+                                    BitX::Zero => format!("(not {term})"),
+                                    BitX::X => "false".into(),
+                                })
+                                .collect::<Vec<_>>();
+                            format!("(ite {} {value} {result})", all(&matches))
+                        }
+                        CaseEntry::WildCard => value,
+                    };
+                }
+                self.write(object, wires, *lhs, &result, loc)?;
+            }
+            OpCode::Select(Select {
+                lhs,
+                selector,
+                true_case,
+                false_case,
+            }) => {
+                let selector = self.read(object, wires, *selector);
+                let true_case = self.read(object, wires, *true_case);
+                let false_case = self.read(object, wires, *false_case);
+                self.write(
+                    object,
+                    wires,
+                    *lhs,
+                    &format!("(ite {selector} {true_case} {false_case})"),
+                    loc,
+                )?;
+            }
+            OpCode::Not(Not { lhs, arg }) => {
+                let arg = self.read(object, wires, *arg);
+                self.write(object, wires, *lhs, &format!("(not {arg})"), loc)?;
+            }
+            OpCode::Unary(Unary { op, lhs, arg }) => {
+                let arg = self.read_v(object, wires, arg);
+                let result = match op {
+                    UnaryOp::All => vec![all(&arg)],
+                    UnaryOp::Any => vec![any(&arg)],
+                    UnaryOp::Xor => vec![match arg.as_slice() {
+                        [] => "false".into(),
+                        [term] => term.clone(),
+                        _ => format!("(xor {})", arg.join(" ")),
+                    }],
+                    UnaryOp::Neg => {
+                        let bv = self.define_bv(arg.len(), format!("(bvneg {})", to_bv(&arg)));
+                        extract(&bv, arg.len())
+                    }
+                };
+                for (&lhs, bit) in lhs.iter().zip(result) {
+                    self.write(object, wires, lhs, &bit, loc)?;
+                }
+            }
+            OpCode::BlackBox(black_box) => {
+                let id = black_box.code.raw();
+                let args = black_box
+                    .arg
+                    .iter()
+                    .map(|arg| self.read_v(object, wires, arg))
+                    .collect::<Vec<_>>();
+                let (outputs, next) = models[id].0.step(&states[id], &args);
+                next_states[id] = next;
+                for (&lhs, bit) in black_box.lhs.iter().zip(outputs) {
+                    self.write(object, wires, lhs, &bit, loc)?;
+                }
+            }
+        }
+        Ok(())
+    }
+    fn vector(&mut self, op: &Vector, arg1: Vec<String>, arg2: Vec<String>) -> Vec<String> {
+        let (w1, w2, wl) = (arg1.len(), arg2.len(), op.lhs.len());
+        let signed = op.signed;
+        let a = to_bv(&arg1);
+        let b = to_bv(&arg2);
+        let width = w1.max(w2);
+        let compare = |name: &str, signed_name: &str| {
+            let name = if signed { signed_name } else { name };
+            format!(
+                "({name} {} {})",
+                resize(to_bv(&arg1), w1, width, signed),
+                resize(to_bv(&arg2), w2, width, signed)
+            )
+        };
+        match op.op {
+            VectorOp::Add | VectorOp::Sub | VectorOp::Mul => {
+                let name = match op.op {
+                    VectorOp::Add => "bvadd",
+                    VectorOp::Sub => "bvsub",
+                    _ => "bvmul",
+                };
+                let term = format!(
+                    "({name} {} {})",
+                    resize(a, w1, wl, signed),
+                    resize(b, w2, wl, signed)
+                );
+                let bv = self.define_bv(wl, term);
+                extract(&bv, wl)
+            }
+            VectorOp::Div | VectorOp::Rem => {
+                let name = match (op.op, signed) {
+                    (VectorOp::Div, false) => "bvudiv",
+                    (VectorOp::Div, true) => "bvsdiv",
+                    (_, false) => "bvurem",
+                    (_, true) => "bvsrem",
+                };
+                let (a, b) = (resize(a, w1, width, signed), resize(b, w2, width, signed));
+                let mut term = format!("({name} {a} {b})");
+                // Division by zero yields all ones, and the remainder is the
+                // dividend.  SMT-LIB agrees, except for a negative signed dividend.
+                if signed && op.op == VectorOp::Div {
+                    term = format!("(ite (= {b} (_ bv0 {width})) (bvnot (_ bv0 {width})) {term})");
+                }
+                let bv = self.define_bv(wl, resize(term, width, wl, signed));
+                extract(&bv, wl)
+            }
+            VectorOp::Eq => vec![compare("=", "=")],
+            VectorOp::Ne => vec![format!("(not {})", compare("=", "="))],
+            VectorOp::Lt => vec![compare("bvult", "bvslt")],
+            VectorOp::Le => vec![compare("bvule", "bvsle")],
+            VectorOp::Gt => vec![compare("bvugt", "bvsgt")],
+            VectorOp::Ge => vec![compare("bvuge", "bvsge")],
+            VectorOp::Shl | VectorOp::Shr => {
+                let name = match (op.op, signed) {
+                    (VectorOp::Shl, _) => "bvshl",
+                    (_, false) => "bvlshr",
+                    (_, true) => "bvashr",
+                };
+                // Shift amounts are always unsigned
+                let term = format!(
+                    "({name} {} {})",
+                    resize(a, w1, width, signed),
+                    resize(b, w2, width, false)
+                );
+                let bv = self.define_bv(w1, resize(term, width, w1, signed));
+                extract(&bv, wl)
+            }
+        }
+    }
+    /// Check the condition in each cycle.  The solver looks for inputs
+    /// that make it false in at least one cycle.
+    pub fn check_each_cycle(&mut self, condition: impl Fn(usize) -> String) {
+        for cycle in 0..self.depth() {
+            let check = format!("ok_c{cycle}");
+            self.text.push_str(&format!(
+                "(define-fun {check} () Bool {})\n",
+                condition(cycle)
+            ));
+            self.checks.push(check);
+        }
+    }
+    /// The complete SMT-LIB2 script for the problem.
+    pub fn script(&self) -> String {
+        let mut text = self.text.clone();
+        text.push_str(&format!(
+            "(assert (not {}))\n(check-sat)\n",
+            all(&self.checks)
+        ));
+        let mut names = self
+            .inputs
+            .iter()
+            .flatten()
+            .flatten()
+            .cloned()
+            .collect::<Vec<_>>();
+        names.extend(self.checks.iter().cloned());
+        if !names.is_empty() {
+            text.push_str(&format!("(get-value ({}))\n", names.join(" ")));
+        }
+        text
+    }
+    /// Run the solver on the problem.  Returns `None` if the checks hold in
+    /// every cycle, and a counterexample otherwise.
+    pub fn solve(&self, solver: &Solver) -> Result<Option<Trace>> {
+        let output = solver.run(&self.script())?;
+        let mut lines = output.lines().map(str::trim).filter(|x| !x.is_empty());
+        match lines.next() {
+            Some("unsat") => return Ok(None),
+            Some("sat") => {}
+            other => {
+                return Err(RHDLError::SmtSolverError(format!(
+                    "Unexpected solver response: {}",
+                    other.unwrap_or_default()
+                )));
+            }
+        }
+        let rest = lines.collect::<Vec<_>>().join(" ").replace(['(', ')'], " ");
+        let tokens = rest.split_whitespace().collect::<Vec<_>>();
+        let values = tokens
+            .chunks(2)
+            .filter_map(|pair| match pair {
+                [name, "true"] => Some((*name, BitX::One)),
+                [name, "false"] => Some((*name, BitX::Zero)),
+                _ => None,
+            })
+            .collect::<HashMap<_, _>>();
+        let value = |name: &String| values.get(name.as_str()).copied().unwrap_or(BitX::X);
+        let inputs = self
+            .inputs
+            .iter()
+            .map(|cycle| {
+                cycle
+                    .iter()
+                    .map(|arg| arg.iter().map(value).collect())
+                    .collect()
+            })
+            .collect();
+        let cycle = self
+            .checks
+            .iter()
+            .position(|check| value(check) == BitX::Zero)
+            .unwrap_or_default();
+        Ok(Some(Trace { cycle, inputs }))
+    }
+}
+
+/// Check that two netlists with the same arguments produce the same outputs
+/// for the first `depth` cycles after initialization.
+///
+/// The `bmc` supplies the inputs, and any constraints on them.  Use
+/// [Bmc::synchronous] for synchronous designs, and [Bmc::new] with a depth
+/// of one for purely combinational ones.
+pub fn check_equivalence(
+    mut bmc: Bmc,
+    a: &Object,
+    b: &Object,
+    solver: &Solver,
+) -> Result<Option<Trace>> {
+    let a = bmc.instantiate(a)?;
+    let b = bmc.instantiate(b)?;
+    if a.first().map(|x| x.len()) != b.first().map(|x| x.len()) {
+        return Err(RHDLError::SmtSolverError(
+            "Netlists have outputs of different widths".into(),
+        ));
+    }
+    bmc.check_each_cycle(|cycle| {
+        let same = a[cycle]
+            .iter()
+            .zip(&b[cycle])
+            .map(|(a, b)| format!("(= {a} {b})"))
+            .collect::<Vec<_>>();
+        all(&same)
+    });
+    bmc.solve(solver)
+}
+
+/// Check that a property of the outputs of a netlist holds for the first
+/// `depth` cycles after initialization.
+///
+/// The property is given the terms for the output bits in a cycle, and
+/// returns an SMT-LIB2 term of sort `Bool` that must be true.
+pub fn check_property(
+    mut bmc: Bmc,
+    object: &Object,
+    solver: &Solver,
+    property: impl Fn(&[String]) -> String,
+) -> Result<Option<Trace>> {
+    let outputs = bmc.instantiate(object)?;
+    bmc.check_each_cycle(|cycle| property(&outputs[cycle]));
+    bmc.solve(solver)
+}
@@ -46,7 +46,7 @@ pub struct Case {
     pub entries: Vec<(CaseEntry, Wire)>,
 }
 
-#[derive(Debug, Clone, Hash, PartialEq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub enum CaseEntry {
     Literal(BitString),
     WildCard,
@@ -68,11 +68,13 @@ pub struct Unary {
     pub arg: Vec<Wire>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum VectorOp {
     Add,
     Sub,
     Mul,
+    Div,
+    Rem,
     Eq,
     Ne,
     Lt,
@@ -83,7 +85,7 @@ pub enum VectorOp {
     Shr,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum UnaryOp {
     All,
     Any,
@@ -105,7 +107,7 @@ pub struct Binary {
     pub arg2: Wire,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum BinaryOp {
     Xor,
     And,
@@ -0,0 +1,239 @@
+//! Static timing estimation for netlists
+//!
+//! This module estimates the longest combinatorial path through a netlist
+//! using the [NetGraph](crate::ntl::graph::NetGraph) of the design.  Each
+//! opcode is assigned a delay by a [DelayModel], and the arrival time of each
+//! opcode is the maximum over its inputs plus its own delay.  Paths start at
+//! the inputs of the netlist or at the outputs of black boxes (like flip flops),
+//! and end at the outputs of the netlist or at the inputs of black boxes.
+//!
+//! The estimate is only a heuristic.  It knows nothing about the target
+//! device, routing or fanout.  But it is fast, and it is good enough to catch
+//! a design whose logic depth has grown beyond some budget.
+use petgraph::{Direction, graph::NodeIndex, visit::EdgeRef};
+
+use crate::{
+    RHDLError,
+    error::rhdl_error,
+    ntl::{
+        error::{NetListError, NetListICE},
+        graph::{GraphMode, WriteSource, make_net_graph},
+        object::Object,
+        spec::{OpCode, VectorOp, Wire},
+    },
+};
+
+/// A model that assigns a delay to each opcode in a netlist.
+///
+/// Any closure of the form `Fn(&OpCode) -> f64` can be used as a delay model.
+pub trait DelayModel {
+    /// The delay through the given opcode.
+    fn delay(&self, op: &OpCode) -> f64;
+}
+
+impl<F: Fn(&OpCode) -> f64> DelayModel for F {
+    fn delay(&self, op: &OpCode) -> f64 {
+        self(op)
+    }
+}
+
+/// A delay model that counts levels of logic.
+///
+/// Every logic operation costs one unit of delay.  Assignments, no-ops and
+/// black boxes are free, so the delay of a path is its logic depth.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UnitDelay;
+
+impl DelayModel for UnitDelay {
+    fn delay(&self, op: &OpCode) -> f64 {
+        match op {
+            OpCode::Noop | OpCode::Assign(_) | OpCode::BlackBox(_) => 0.0,
+            _ => 1.0,
+        }
+    }
+}
+
+/// A delay model that accounts for the width of vector operations.
+///
+/// Single bit operations cost one unit of delay.  Comparisons and reductions
+/// cost `log2(width)` units (a balanced tree), additions and subtractions
+/// cost `width` units (a ripple carry chain), multiplications cost
+/// twice the width of the wider argument, and divisions cost the square
+/// of the width.  Shifts are treated as a barrel
+/// shifter, costing `log2(width)` units.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WidthDelay;
+
+fn log2(width: usize) -> f64 {
+    (width.max(2) as f64).log2().ceil()
+}
+
+impl DelayModel for WidthDelay {
+    fn delay(&self, op: &OpCode) -> f64 {
+        match op {
+            OpCode::Noop | OpCode::Assign(_) | OpCode::BlackBox(_) => 0.0,
+            OpCode::Binary(_) | OpCode::Not(_) | OpCode::Select(_) => 1.0,
+            OpCode::Case(case) => log2(case.entries.len()),
+            OpCode::Unary(unary) => log2(unary.arg.len()),
+            OpCode::Vector(vector) => {
+                let width = vector.arg1.len().max(vector.arg2.len());
+                match vector.op {
+                    VectorOp::Add | VectorOp::Sub => width as f64,
+                    VectorOp::Mul => 2.0 * width as f64,
+                    // A restoring divider is a chain of `width` subtractions
+                    VectorOp::Div | VectorOp::Rem => (width * width) as f64,
+                    VectorOp::Eq
+                    | VectorOp::Ne
+                    | VectorOp::Lt
+                    | VectorOp::Le
+                    | VectorOp::Gt
+                    | VectorOp::Ge
+                    | VectorOp::Shl
+                    | VectorOp::Shr => log2(width),
+                }
+            }
+        }
+    }
+}
+
+/// Where a timing path starts or ends.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimingPoint {
+    /// An input to the netlist
+    Input,
+    /// An output of the netlist
+    Output,
+    /// A black box (like a flip flop), identified by the index
+    /// of its opcode in the netlist.
+    BlackBox(usize),
+}
+
+/// The longest path found through a netlist.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimingPath {
+    /// The total delay along the path
+    pub delay: f64,
+    /// Where the path starts
+    pub start: TimingPoint,
+    /// Where the path ends
+    pub end: TimingPoint,
+    /// The indices of the opcodes along the path, in order
+    pub ops: Vec<usize>,
+}
+
+fn raise_ice(object: &Object, cause: NetListICE, ndx: Option<usize>) -> RHDLError {
+    rhdl_error(NetListError {
+        cause,
+        src: object.code.source(),
+        elements: ndx
+            .and_then(|ndx| object.ops[ndx].loc)
+            .map(|loc| object.code.span(loc).into())
+            .into_iter()
+            .collect(),
+    })
+}
+
+/// Find the longest combinatorial path through the netlist, using the given delay model.
+///
+/// Returns `None` if the netlist has no paths at all (for example, if all of its
+/// outputs are constant).
+pub fn critical_path(
+    object: &Object,
+    mode: GraphMode,
+    model: &impl DelayModel,
+) -> Result<Option<TimingPath>, RHDLError> {
+    let dep = make_net_graph(object, mode);
+    let order = petgraph::algo::toposort(&dep.graph, None).map_err(|cycle| {
+        let ndx = match dep.graph[cycle.node_id()] {
+            WriteSource::OpCode(ndx) => Some(ndx),
+            _ => None,
+        };
+        raise_ice(object, NetListICE::LogicLoop, ndx)
+    })?;
+    // For each node, the arrival time at its output, and the predecessor
+    // on the longest path leading to it.
+    let mut arrival: Vec<Option<(f64, Option<NodeIndex>)>> = vec![None; dep.graph.node_count()];
+    arrival[dep.input_node.index()] = Some((0.0, None));
+    for node in order {
+        let WriteSource::OpCode(ndx) = dep.graph[node] else {
+            continue;
+        };
+        let op = &object.ops[ndx].op;
+        let delay = model.delay(op);
+        let latest = dep
+            .graph
+            .edges_directed(node, Direction::Incoming)
+            .filter_map(|edge| arrival[edge.source().index()].map(|(t, _)| (t, edge.source())))
+            .max_by(|a, b| a.0.total_cmp(&b.0));
+        arrival[node.index()] = match (latest, op) {
+            (Some((t, pred)), _) => Some((t + delay, Some(pred))),
+            // Black boxes start a new path
+            (None, OpCode::BlackBox(_)) => Some((delay, None)),
+            // Opcodes that depend only on constants or clock and reset
+            // are not on any path
+            (None, _) => None,
+        };
+    }
+    let node_of = |reg| match dep.reg_map.get(&reg) {
+        Some(WriteSource::Input) => Some(dep.input_node),
+        Some(WriteSource::OpCode(ndx)) => Some(dep.op_nodes[*ndx]),
+        _ => None,
+    };
+    // Collect the end points of all paths: the outputs of the netlist,
+    // and the arguments of the black boxes.
+    let outputs = object
+        .outputs
+        .iter()
+        .flat_map(|w| w.reg())
+        .map(|reg| (reg, TimingPoint::Output));
+    let black_box_inputs = object
+        .ops
+        .iter()
+        .enumerate()
+        .filter_map(|(ndx, lop)| match &lop.op {
+            OpCode::BlackBox(bb) => Some((ndx, bb)),
+            _ => None,
+        })
+        .flat_map(|(ndx, bb)| {
+            bb.arg
+                .iter()
+                .flatten()
+                .flat_map(|w: &Wire| w.reg())
+                .map(move |reg| (reg, TimingPoint::BlackBox(ndx)))
+        });
+    let Some((delay, end, mut node)) = outputs
+        .chain(black_box_inputs)
+        .filter_map(|(reg, end)| {
+            let node = node_of(reg)?;
+            let (delay, _) = arrival[node.index()]?;
+            Some((delay, end, node))
+        })
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+    else {
+        return Ok(None);
+    };
+    // Walk back along the path to recover the opcodes
+    let mut ops = vec![];
+    let start = loop {
+        match dep.graph[node] {
+            WriteSource::OpCode(ndx) => {
+                if let Some((_, Some(pred))) = arrival[node.index()] {
+                    if !matches!(object.ops[ndx].op, OpCode::BlackBox(_)) {
+                        ops.push(ndx);
+                    }
+                    node = pred;
+                } else {
+                    break TimingPoint::BlackBox(ndx);
+                }
+            }
+            _ => break TimingPoint::Input,
+        }
+    };
+    ops.reverse();
+    Ok(Some(TimingPath {
+        delay,
+        start,
+        end,
+        ops,
+    }))
+}
@@ -0,0 +1,389 @@
+use std::collections::BTreeSet;
+
+use crate::RHDLError;
+use crate::VHDLDescriptor;
+use crate::ast::SourceLocation;
+use crate::error::rhdl_error;
+use crate::hdl::vhdl;
+use crate::ntl::Object;
+use crate::ntl::error::NetListError;
+use crate::ntl::error::NetListICE;
+use crate::ntl::object::BlackBoxMode;
+use crate::ntl::spec;
+use crate::ntl::spec::BlackBox;
+use crate::ntl::spec::CaseEntry;
+use crate::ntl::spec::VectorOp;
+use crate::ntl::spec::Wire;
+use crate::ntl::visit::visit_wires;
+
+// The netlist is in single assignment form (see the `SingleRegisterWrite` pass),
+// so each wire becomes a `std_logic` signal driven by exactly one concurrent
+// statement.  Vector operations go through temporary `std_logic_vector` signals,
+// since VHDL has no way to treat a list of scalar signals as a vector.
+struct NetListVHDLBuilder<'a> {
+    ntl: &'a Object,
+    declarations: Vec<String>,
+    body: Vec<String>,
+    entities: VHDLDescriptor,
+    name: String,
+    temporary_counter: usize,
+}
+
+impl<'a> NetListVHDLBuilder<'a> {
+    fn new(name: &'_ str, ntl: &'a Object) -> Self {
+        Self {
+            ntl,
+            declarations: vec![],
+            body: vec![],
+            entities: VHDLDescriptor::default(),
+            name: name.into(),
+            temporary_counter: 0,
+        }
+    }
+    fn opex(&self, operand: Wire) -> String {
+        match operand {
+            Wire::Literal(lid) => vhdl::bit_literal(self.ntl.symtab[lid]).into(),
+            Wire::Register(rid) => rid.to_string(),
+        }
+    }
+    fn raise_ice(&self, cause: NetListICE, location: Option<SourceLocation>) -> RHDLError {
+        rhdl_error(NetListError {
+            cause,
+            src: self.ntl.code.source(),
+            elements: location
+                .map(|loc| self.ntl.code.span(loc).into())
+                .into_iter()
+                .collect(),
+        })
+    }
+    fn reg(&self, operand: Wire, location: Option<SourceLocation>) -> Result<String, RHDLError> {
+        if let Some(rid) = operand.reg() {
+            Ok(rid.to_string())
+        } else {
+            Err(self.raise_ice(NetListICE::ExpectedRegisterNotConstant, location))
+        }
+    }
+    fn add_stmt(&mut self, stmt: String) {
+        self.body.push(stmt);
+    }
+    // Declare a new temporary vector signal of the given width
+    fn temporary(&mut self, width: usize) -> String {
+        let name = format!("t{}", self.temporary_counter);
+        self.temporary_counter += 1;
+        self.declarations
+            .push(format!("signal {name} : {};", vhdl::slv(width)));
+        name
+    }
+    // Gather a list of wires into a temporary vector
+    fn gather(&mut self, operands: &[Wire]) -> String {
+        let name = self.temporary(operands.len());
+        let elements = operands
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(ndx, &op)| format!("{ndx} => {}", self.opex(op)))
+            .collect::<Vec<_>>();
+        self.add_stmt(format!("{name} <= ({});", elements.join(", ")));
+        name
+    }
+    // Assign the low bits of a vector expression of the given width to a list of wires
+    fn scatter(
+        &mut self,
+        lhs: &[Wire],
+        expr: String,
+        width: usize,
+        location: Option<SourceLocation>,
+    ) -> Result<(), RHDLError> {
+        let name = self.temporary(width);
+        self.add_stmt(format!("{name} <= {expr};"));
+        for (ndx, &wire) in lhs.iter().enumerate() {
+            let target = self.reg(wire, location)?;
+            self.add_stmt(format!("{target} <= {name}({ndx});"));
+        }
+        Ok(())
+    }
+    fn select_op(
+        &mut self,
+        op: &spec::Select,
+        location: Option<SourceLocation>,
+    ) -> Result<(), RHDLError> {
+        let target = self.reg(op.lhs, location)?;
+        let selector = self.opex(op.selector);
+        let true_case = self.opex(op.true_case);
+        let false_case = self.opex(op.false_case);
+        self.add_stmt(format!(
+            "{target} <= {true_case} when {selector} = '1' else {false_case};"
+        ));
+        Ok(())
+    }
+    fn assign_op(
+        &mut self,
+        op: &spec::Assign,
+        location: Option<SourceLocation>,
+    ) -> Result<(), RHDLError> {
+        let target = self.reg(op.lhs, location)?;
+        let rhs = self.opex(op.rhs);
+        self.add_stmt(format!("{target} <= {rhs};"));
+        Ok(())
+    }
+    fn binary_op(
+        &mut self,
+        op: &spec::Binary,
+        location: Option<SourceLocation>,
+    ) -> Result<(), RHDLError> {
+        let target = self.reg(op.lhs, location)?;
+        let alu = match op.op {
+            spec::BinaryOp::Xor => "xor",
+            spec::BinaryOp::And => "and",
+            spec::BinaryOp::Or => "or",
+        };
+        let arg1 = self.opex(op.arg1);
+        let arg2 = self.opex(op.arg2);
+        self.add_stmt(format!("{target} <= {arg1} {alu} {arg2};"));
+        Ok(())
+    }
+    fn vector_op(
+        &mut self,
+        op: &spec::Vector,
+        location: Option<SourceLocation>,
+    ) -> Result<(), RHDLError> {
+        let ty = if op.signed { "signed" } else { "unsigned" };
+        let arg1 = self.gather(&op.arg1);
+        let arg2 = self.gather(&op.arg2);
+        let a = format!("{ty}({arg1})");
+        let b = format!("{ty}({arg2})");
+        // The shift amount is always unsigned, as it is in Verilog
+        let shift = format!("to_integer(unsigned({arg2}))");
+        let compare = match op.op {
+            VectorOp::Eq => Some("="),
+            VectorOp::Ne => Some("/="),
+            VectorOp::Lt => Some("<"),
+            VectorOp::Le => Some("<="),
+            VectorOp::Gt => Some(">"),
+            VectorOp::Ge => Some(">="),
+            _ => None,
+        };
+        if let Some(compare) = compare {
+            let target = self.reg(op.lhs[0], location)?;
+            self.add_stmt(format!("{target} <= '1' when {a} {compare} {b} else '0';"));
+            return Ok(());
+        }
+        // Compute the expression, and the width of the result, which may be wider
+        // than the target (as for multiplication).  Only the low bits are kept.
+        let (expr, width) = match op.op {
+            VectorOp::Add => (format!("{a} + {b}"), op.arg1.len().max(op.arg2.len())),
+            VectorOp::Sub => (format!("{a} - {b}"), op.arg1.len().max(op.arg2.len())),
+            VectorOp::Mul => (format!("{a} * {b}"), op.arg1.len() + op.arg2.len()),
+            VectorOp::Div => (format!("{a} / {b}"), op.arg1.len()),
+            VectorOp::Rem => (format!("{a} rem {b}"), op.arg2.len()),
+            VectorOp::Shl => (format!("shift_left({a}, {shift})"), op.arg1.len()),
+            VectorOp::Shr => (format!("shift_right({a}, {shift})"), op.arg1.len()),
+            _ => unreachable!("comparisons are handled above"),
+        };
+        // Division by zero yields all ones, and the remainder is the dividend,
+        // matching rhdl-bits and the simulators.
+        let value = format!("std_logic_vector({expr})");
+        let value = match op.op {
+            VectorOp::Div => format!("(others => '1') when {b} = 0 else {value}"),
+            VectorOp::Rem => {
+                format!("std_logic_vector(resize({a}, {width})) when {b} = 0 else {value}")
+            }
+            _ => value,
+        };
+        self.scatter(&op.lhs, value, width, location)
+    }
+    fn not_op(
+        &mut self,
+        op: &spec::Not,
+        location: Option<SourceLocation>,
+    ) -> Result<(), RHDLError> {
+        let target = self.reg(op.lhs, location)?;
+        let arg = self.opex(op.arg);
+        self.add_stmt(format!("{target} <= not {arg};"));
+        Ok(())
+    }
+    fn case_op(
+        &mut self,
+        op: &spec::Case,
+        location: Option<SourceLocation>,
+    ) -> Result<(), RHDLError> {
+        let discriminant = self.gather(&op.discriminant);
+        let lhs = self.reg(op.lhs, location)?;
+        let mut table = op
+            .entries
+            .iter()
+            .map(|(entry, operand)| {
+                let value = self.opex(*operand);
+                match entry {
+                    CaseEntry::Literal(lit) => {
+                        format!("{value} when {}", vhdl::vector_literal(lit.bits()))
+                    }
+                    CaseEntry::WildCard => format!("{value} when others"),
+                }
+            })
+            .collect::<Vec<_>>();
+        // VHDL requires the choices to be complete
+        if !op
+            .entries
+            .iter()
+            .any(|(entry, _)| matches!(entry, CaseEntry::WildCard))
+        {
+            table.push("'X' when others".into());
+        }
+        self.add_stmt(format!(
+            "with {discriminant} select {lhs} <=\n    {};",
+            table.join(",\n    ")
+        ));
+        Ok(())
+    }
+    fn unary_op(
+        &mut self,
+        op: &spec::Unary,
+        location: Option<SourceLocation>,
+    ) -> Result<(), RHDLError> {
+        let arg = self.gather(&op.arg);
+        let reduce = match op.op {
+            spec::UnaryOp::All => "and",
+            spec::UnaryOp::Any => "or",
+            spec::UnaryOp::Xor => "xor",
+            spec::UnaryOp::Neg => {
+                return self.scatter(
+                    &op.lhs,
+                    format!("std_logic_vector(-signed({arg}))"),
+                    op.arg.len(),
+                    location,
+                );
+            }
+        };
+        let target = self.reg(op.lhs[0], location)?;
+        self.add_stmt(format!("{target} <= {reduce} {arg};"));
+        Ok(())
+    }
+    fn black_box_op(
+        &mut self,
+        black_box: &BlackBox,
+        location: Option<SourceLocation>,
+    ) -> Result<(), RHDLError> {
+        let ntl = self.ntl;
+        let bb_core = &ntl.black_boxes[black_box.code.raw()];
+        let Some(core_vhdl) = &bb_core.vhdl else {
+            return Err(RHDLError::VHDLNotAvailable {
+                name: bb_core.code.name.clone(),
+            });
+        };
+        self.entities.extend(core_vhdl);
+        let core_name = core_vhdl.name.clone();
+        let mode = bb_core.mode;
+        let port_names: &[&str] = match mode {
+            BlackBoxMode::Asynchronous => &["i"],
+            BlackBoxMode::Synchronous => &["clock_reset", "i", "ce"],
+        };
+        let mut connections = vec![];
+        for (name, arg) in port_names.iter().zip(&black_box.arg) {
+            if !arg.is_empty() {
+                let signal = self.gather(arg);
+                connections.push(format!("{name} => {signal}"));
+            }
+        }
+        let out = self.temporary(black_box.lhs.len());
+        connections.push(format!("o => {out}"));
+        for (ndx, &wire) in black_box.lhs.iter().enumerate() {
+            let target = self.reg(wire, location)?;
+            self.add_stmt(format!("{target} <= {out}({ndx});"));
+        }
+        let instance_name = format!("bb_{}", self.temporary_counter);
+        self.temporary_counter += 1;
+        self.add_stmt(format!(
+            "{instance_name} : entity work.{core_name} port map (\n    {}\n);",
+            connections.join(",\n    ")
+        ));
+        Ok(())
+    }
+    fn op_code(
+        &mut self,
+        op: &spec::OpCode,
+        location: Option<SourceLocation>,
+    ) -> Result<(), RHDLError> {
+        match op {
+            spec::OpCode::Noop => Ok(()),
+            spec::OpCode::Assign(assign) => self.assign_op(assign, location),
+            spec::OpCode::Binary(binary) => self.binary_op(binary, location),
+            spec::OpCode::Vector(vector) => self.vector_op(vector, location),
+            spec::OpCode::Case(case) => self.case_op(case, location),
+            spec::OpCode::Select(select) => self.select_op(select, location),
+            spec::OpCode::Not(not) => self.not_op(not, location),
+            spec::OpCode::BlackBox(black_box) => self.black_box_op(black_box, location),
+            spec::OpCode::Unary(unary) => self.unary_op(unary, location),
+        }
+    }
+    fn build(
+        mut self,
+        input_names: &[&str],
+        output_name: &str,
+    ) -> Result<VHDLDescriptor, RHDLError> {
+        let ports = self
+            .ntl
+            .inputs
+            .iter()
+            .zip(input_names)
+            .flat_map(|(x, name)| vhdl::port(name, vhdl::Direction::In, x.len()))
+            .chain(vhdl::port(
+                output_name,
+                vhdl::Direction::Out,
+                self.ntl.outputs.len(),
+            ))
+            .collect::<Vec<_>>();
+        let mut registers = BTreeSet::default();
+        for lop in &self.ntl.ops {
+            visit_wires(&lop.op, |_sense, op| {
+                if let Some(reg) = op.reg() {
+                    registers.insert(reg);
+                }
+            });
+        }
+        registers.extend(self.ntl.inputs.iter().flatten());
+        let signals = registers
+            .iter()
+            .map(|reg| format!("signal {reg} : std_logic;"))
+            .collect::<Vec<_>>();
+        // Connect the input registers to their entity port names
+        for (arg, name) in self.ntl.inputs.iter().zip(input_names) {
+            for (bit, &reg) in arg.iter().enumerate() {
+                self.add_stmt(format!("{reg} <= {name}({bit});"));
+            }
+        }
+        for lop in &self.ntl.ops {
+            self.op_code(&lop.op, lop.loc)?;
+        }
+        if !self.ntl.outputs.is_empty() {
+            let outputs = self
+                .ntl
+                .outputs
+                .iter()
+                .enumerate()
+                .rev()
+                .map(|(ndx, &wire)| format!("{ndx} => {}", self.opex(wire)))
+                .collect::<Vec<_>>();
+            self.add_stmt(format!("{output_name} <= ({});", outputs.join(", ")));
+        }
+        let declarations = signals
+            .into_iter()
+            .chain(std::mem::take(&mut self.declarations))
+            .collect::<Vec<_>>();
+        let text = vhdl::entity(&self.name, &ports, &declarations, &self.body);
+        let mut descriptor = VHDLDescriptor::new(&self.name, text);
+        descriptor.extend(&self.entities);
+        Ok(descriptor)
+    }
+}
+
+/// Build a VHDL entity from a netlist.  The inputs of the netlist are mapped to
+/// ports with the given names, and the outputs to a single port named `output_name`.
+pub(crate) fn build_vhdl(
+    entity_name: &str,
+    ntl: &Object,
+    input_names: &[&str],
+    output_name: &str,
+) -> Result<VHDLDescriptor, RHDLError> {
+    NetListVHDLBuilder::new(entity_name, ntl).build(input_names, output_name)
+}
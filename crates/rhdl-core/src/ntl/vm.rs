@@ -0,0 +1,349 @@
+//! Netlist simulator
+//!
+//! The [NetListVM] evaluates a netlist [Object] directly, one wire at a time,
+//! using the same three-valued [BitX] logic as the RTL virtual machine.  This
+//! allows the post-optimization netlist of a design to be checked in-process,
+//! without exporting Verilog and running an external simulator.
+//!
+//! Black boxes (like flip flops and RAMs) cannot be simulated from their
+//! HDL description.  Instead, a black box may carry a [BlackBoxModel] which
+//! provides a bit level behavioral model of the core.  Netlists that contain
+//! black boxes without a model cannot be simulated.
+use std::sync::Arc;
+
+use crate::{
+    BitX, Kind, MAX_ITERS, RHDLError, TypedBits,
+    ast::SourceLocation,
+    common::slot_vec::SlotKey,
+    error::rhdl_error,
+    ntl::{
+        error::{NetListError, NetListICE},
+        object::Object,
+        spec::{
+            Assign, Binary, BinaryOp, Case, CaseEntry, Not, OpCode, Select, Unary, UnaryOp,
+            VectorOp, Wire,
+        },
+    },
+    rhif::{runtime_ops::binary, spec::AluBinary},
+};
+
+type Result<T> = std::result::Result<T, RHDLError>;
+
+/// A bit level behavioral model of a black box.
+///
+/// The model is handed the current values of the black box arguments
+/// and is free to update its internal state.  For synchronous black boxes,
+/// `args[0]` holds the clock and reset bits, and `args[1]` holds the input
+/// bits.  For asynchronous black boxes, `args[0]` holds the input bits.
+///
+/// The model may be called several times with the same starting state
+/// while the netlist settles, so `sim` must be a pure function of
+/// the state and arguments.
+pub trait BlackBoxSim: Send + Sync {
+    /// The initial state of the black box.
+    fn init(&self) -> Vec<BitX>;
+    /// Compute the outputs of the black box, updating the state.
+    fn sim(&self, args: &[Vec<BitX>], state: &mut Vec<BitX>) -> Vec<BitX>;
+}
+
+/// A shareable handle to a [BlackBoxSim] model.
+#[derive(Clone)]
+pub struct BlackBoxModel(pub(crate) Arc<dyn BlackBoxSim>);
+
+impl BlackBoxModel {
+    /// Wrap a behavioral model so it can be attached to a black box.
+    pub fn new<T: BlackBoxSim + 'static>(model: T) -> Self {
+        Self(Arc::new(model))
+    }
+}
+
+impl std::fmt::Debug for BlackBoxModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "BlackBoxModel")
+    }
+}
+
+/// A simulator that executes a netlist [Object] directly.
+#[derive(Clone)]
+pub struct NetListVM {
+    object: Object,
+    wires: Vec<BitX>,
+    states: Vec<Vec<BitX>>,
+}
+
+pub(crate) fn raise_ice(
+    object: &Object,
+    cause: NetListICE,
+    location: Option<SourceLocation>,
+) -> RHDLError {
+    rhdl_error(NetListError {
+        cause,
+        src: object.code.source(),
+        elements: location
+            .map(|loc| object.code.span(loc).into())
+            .into_iter()
+            .collect(),
+    })
+}
+
+fn read(object: &Object, wires: &[BitX], wire: Wire) -> BitX {
+    match wire {
+        Wire::Literal(lid) => object.symtab[lid],
+        Wire::Register(rid) => wires[rid.index()],
+    }
+}
+
+fn read_v(object: &Object, wires: &[BitX], operands: &[Wire]) -> Vec<BitX> {
+    operands
+        .iter()
+        .map(|&wire| read(object, wires, wire))
+        .collect()
+}
+
+fn write(
+    object: &Object,
+    wires: &mut [BitX],
+    wire: Wire,
+    value: BitX,
+    location: Option<SourceLocation>,
+) -> Result<()> {
+    match wire {
+        Wire::Literal(_) => Err(raise_ice(
+            object,
+            NetListICE::ExpectedRegisterNotConstant,
+            location,
+        )),
+        Wire::Register(rid) => {
+            wires[rid.index()] = value;
+            Ok(())
+        }
+    }
+}
+
+pub(crate) fn vector(
+    op: VectorOp,
+    signed: bool,
+    arg1: Vec<BitX>,
+    arg2: Vec<BitX>,
+) -> Result<Vec<BitX>> {
+    let kind = |len| {
+        if signed {
+            Kind::make_signed(len)
+        } else {
+            Kind::make_bits(len)
+        }
+    };
+    let (alu, arg2_kind) = match op {
+        VectorOp::Add => (AluBinary::Add, kind(arg2.len())),
+        VectorOp::Sub => (AluBinary::Sub, kind(arg2.len())),
+        VectorOp::Mul => (AluBinary::Mul, kind(arg2.len())),
+        VectorOp::Div => (AluBinary::Div, kind(arg2.len())),
+        VectorOp::Rem => (AluBinary::Rem, kind(arg2.len())),
+        VectorOp::Eq => (AluBinary::Eq, kind(arg2.len())),
+        VectorOp::Ne => (AluBinary::Ne, kind(arg2.len())),
+        VectorOp::Lt => (AluBinary::Lt, kind(arg2.len())),
+        VectorOp::Le => (AluBinary::Le, kind(arg2.len())),
+        VectorOp::Gt => (AluBinary::Gt, kind(arg2.len())),
+        VectorOp::Ge => (AluBinary::Ge, kind(arg2.len())),
+        // Shift amounts are always unsigned
+        VectorOp::Shl => (AluBinary::Shl, Kind::make_bits(arg2.len())),
+        VectorOp::Shr => (AluBinary::Shr, Kind::make_bits(arg2.len())),
+    };
+    let arg1_kind = kind(arg1.len());
+    let arg1 = TypedBits::new(arg1, arg1_kind);
+    let arg2 = TypedBits::new(arg2, arg2_kind);
+    Ok(binary(alu, arg1, arg2)?.bits().to_vec())
+}
+
+pub(crate) fn unary(op: UnaryOp, arg: Vec<BitX>) -> Result<Vec<BitX>> {
+    let len = arg.len();
+    let result = match op {
+        UnaryOp::All => TypedBits::new(arg, Kind::make_bits(len)).all(),
+        UnaryOp::Any => TypedBits::new(arg, Kind::make_bits(len)).any(),
+        UnaryOp::Xor => TypedBits::new(arg, Kind::make_bits(len)).xor(),
+        UnaryOp::Neg => (-TypedBits::new(arg, Kind::make_signed(len)))?,
+    };
+    Ok(result.bits().to_vec())
+}
+
+impl NetListVM {
+    /// Create a new simulator for the given netlist.  Fails if the
+    /// netlist contains a black box with no behavioral model.
+    pub fn new(object: &Object) -> Result<Self> {
+        let states = object
+            .black_boxes
+            .iter()
+            .map(|bb| match &bb.model {
+                Some(model) => Ok(model.0.init()),
+                None => Err(raise_ice(
+                    object,
+                    NetListICE::BlackBoxHasNoModel {
+                        name: bb.code.name.clone(),
+                    },
+                    None,
+                )),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            object: object.clone(),
+            wires: vec![BitX::X; object.symtab.reg_vec().len()],
+            states,
+        })
+    }
+    /// The netlist being simulated.
+    pub fn object(&self) -> &Object {
+        &self.object
+    }
+    /// Evaluate the netlist with the given arguments, and return
+    /// the output bits.  The state of any black boxes is updated.
+    pub fn step(&mut self, args: &[&[BitX]]) -> Result<Vec<BitX>> {
+        let Self {
+            object,
+            wires,
+            states,
+        } = self;
+        if args.len() != object.inputs.len() {
+            return Err(raise_ice(
+                object,
+                NetListICE::ArgumentCountMismatch {
+                    expected: object.inputs.len(),
+                    found: args.len(),
+                },
+                None,
+            ));
+        }
+        for (ndx, (input, arg)) in object.inputs.iter().zip(args).enumerate() {
+            if input.len() != arg.len() {
+                return Err(raise_ice(
+                    object,
+                    NetListICE::ArgumentWidthMismatch {
+                        ndx,
+                        expected: input.len(),
+                        found: arg.len(),
+                    },
+                    None,
+                ));
+            }
+            for (reg, &bit) in input.iter().zip(arg.iter()) {
+                wires[reg.index()] = bit;
+            }
+        }
+        // Black boxes are evaluated from the state they had at the start of
+        // this step.  We then iterate until the outputs of the black boxes
+        // stop changing, just as the derived simulation does for a
+        // hierarchy of circuits.
+        let snapshot = states.clone();
+        for _ in 0..MAX_ITERS {
+            let mut settled = true;
+            for lop in &object.ops {
+                let loc = lop.loc;
+                match &lop.op {
+                    OpCode::Noop => {}
+                    OpCode::Assign(Assign { lhs, rhs }) => {
+                        let rhs = read(object, wires, *rhs);
+                        write(object, wires, *lhs, rhs, loc)?;
+                    }
+                    OpCode::Binary(Binary {
+                        op,
+                        lhs,
+                        arg1,
+                        arg2,
+                    }) => {
+                        let arg1 = read(object, wires, *arg1);
+                        let arg2 = read(object, wires, *arg2);
+                        let result = match op {
+                            BinaryOp::Xor => arg1 ^ arg2,
+                            BinaryOp::And => arg1 & arg2,
+                            BinaryOp::Or => arg1 | arg2,
+                        };
+                        write(object, wires, *lhs, result, loc)?;
+                    }
+                    OpCode::Vector(op) => {
+                        let arg1 = read_v(object, wires, &op.arg1);
+                        let arg2 = read_v(object, wires, &op.arg2);
+                        let result = vector(op.op, op.signed, arg1, arg2).map_err(|err| {
+                            raise_ice(object, NetListICE::VectorOperatorError(Box::new(err)), loc)
+                        })?;
+                        for (&lhs, bit) in op.lhs.iter().zip(result) {
+                            write(object, wires, lhs, bit, loc)?;
+                        }
+                    }
+                    OpCode::Case(Case {
+                        lhs,
+                        discriminant,
+                        entries,
+                    }) => {
+                        let discriminant = read_v(object, wires, discriminant);
+                        let result = entries
+                            .iter()
+                            .find(|(entry, _)| match entry {
+                                CaseEntry::Literal(lit) => lit.bits() == discriminant.as_slice(),
+                                CaseEntry::WildCard => true,
+                            })
+                            .map(|(_, wire)| read(object, wires, *wire))
+                            .unwrap_or(BitX::X);
+                        write(object, wires, *lhs, result, loc)?;
+                    }
+                    OpCode::Select(Select {
+                        lhs,
+                        selector,
+                        true_case,
+                        false_case,
+                    }) => {
+                        let result = match read(object, wires, *selector) {
+                            BitX::One => read(object, wires, *true_case),
+                            BitX::Zero => read(object, wires, *false_case),
+                            BitX::X => BitX::X,
+                        };
+                        write(object, wires, *lhs, result, loc)?;
+                    }
+                    OpCode::Not(Not { lhs, arg }) => {
+                        let arg = read(object, wires, *arg);
+                        write(object, wires, *lhs, !arg, loc)?;
+                    }
+                    OpCode::Unary(Unary { op, lhs, arg }) => {
+                        let arg = read_v(object, wires, arg);
+                        let result = unary(*op, arg).map_err(|err| {
+                            raise_ice(object, NetListICE::VectorOperatorError(Box::new(err)), loc)
+                        })?;
+                        for (&lhs, bit) in lhs.iter().zip(result) {
+                            write(object, wires, lhs, bit, loc)?;
+                        }
+                    }
+                    OpCode::BlackBox(black_box) => {
+                        let id = black_box.code.raw();
+                        let Some(model) = &object.black_boxes[id].model else {
+                            return Err(raise_ice(
+                                object,
+                                NetListICE::BlackBoxHasNoModel {
+                                    name: object.black_boxes[id].code.name.clone(),
+                                },
+                                loc,
+                            ));
+                        };
+                        let args = black_box
+                            .arg
+                            .iter()
+                            .map(|arg| read_v(object, wires, arg))
+                            .collect::<Vec<_>>();
+                        let mut state = snapshot[id].clone();
+                        let result = model.0.sim(&args, &mut state);
+                        states[id] = state;
+                        for (&lhs, bit) in black_box.lhs.iter().zip(result) {
+                            settled &= read(object, wires, lhs) == bit;
+                            write(object, wires, lhs, bit, loc)?;
+                        }
+                    }
+                }
+            }
+            if settled {
+                return Ok(read_v(object, wires, &object.outputs));
+            }
+        }
+        Err(raise_ice(
+            object,
+            NetListICE::SimulationDidNotConverge,
+            None,
+        ))
+    }
+}
@@ -161,6 +161,8 @@ impl std::fmt::Debug for AluBinary {
             AluBinary::Add => write!(f, "+"),
             AluBinary::Sub => write!(f, "-"),
             AluBinary::Mul => write!(f, "*"),
+            AluBinary::Div => write!(f, "/"),
+            AluBinary::Rem => write!(f, "%"),
             AluBinary::BitAnd => write!(f, "&"),
             AluBinary::BitOr => write!(f, "|"),
             AluBinary::BitXor => write!(f, "^"),
@@ -175,6 +177,10 @@ impl std::fmt::Debug for AluBinary {
             AluBinary::XAdd => write!(f, "xadd"),
             AluBinary::XSub => write!(f, "xsub"),
             AluBinary::XMul => write!(f, "xmul"),
+            AluBinary::SaturatingAdd => write!(f, "saturating_add"),
+            AluBinary::SaturatingSub => write!(f, "saturating_sub"),
+            AluBinary::OverflowingAdd => write!(f, "overflowing_add"),
+            AluBinary::OverflowingSub => write!(f, "overflowing_sub"),
         }
     }
 }
@@ -201,6 +207,10 @@ impl std::fmt::Debug for AluUnary {
             }
             AluUnary::XNeg => write!(f, "xneg "),
             AluUnary::XSgn => write!(f, "xsgn "),
+            AluUnary::CountOnes => write!(f, "count_ones "),
+            AluUnary::LeadingZeros => write!(f, "leading_zeros "),
+            AluUnary::TrailingZeros => write!(f, "trailing_zeros "),
+            AluUnary::ReverseBits => write!(f, "reverse_bits "),
         }
     }
 }
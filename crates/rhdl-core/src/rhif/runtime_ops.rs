@@ -3,7 +3,7 @@ use num_bigint::BigInt;
 use crate::bitx::dyn_bit_manip::{from_bigint, from_biguint, to_bigint, to_biguint};
 use crate::error::rhdl_error;
 use crate::types::error::DynamicTypeError;
-use crate::{Digital, Kind, RHDLError, TypedBits};
+use crate::{BitX, Digital, Kind, RHDLError, TypedBits};
 
 use super::spec::{AluBinary, AluUnary};
 
@@ -45,6 +45,53 @@ fn mul(a: TypedBits, b: TypedBits) -> Result<TypedBits, RHDLError> {
     }
 }
 
+// Division by zero yields an undefined result (all X), which matches the
+// behavior of the `/` and `%` operators in Verilog.
+fn div_rem(a: TypedBits, b: TypedBits, rem: bool) -> Result<TypedBits, RHDLError> {
+    if a.kind().is_signed() ^ b.kind().is_signed() {
+        return Err(rhdl_error(
+            DynamicTypeError::BinaryOperationRequiresCompatibleType {
+                lhs: a.kind(),
+                rhs: b.kind(),
+            },
+        ));
+    }
+    let kind = if a.kind().is_signed() {
+        Kind::Signed(a.len())
+    } else {
+        Kind::Bits(a.len())
+    };
+    // Division by zero yields all ones, and the remainder is the dividend.
+    // This matches the rhdl-bits behavior and the generated hardware.
+    if b.is_zero() {
+        let bits = if rem {
+            a.bits().to_vec()
+        } else {
+            vec![BitX::One; a.len()]
+        };
+        return Ok(TypedBits::new(bits, kind));
+    }
+    if a.kind().is_signed() {
+        let a_bi = to_bigint(a.bits()).ok_or_else(|| {
+            rhdl_error(DynamicTypeError::CannotConvertUninitToInt { value: a.clone() })
+        })?;
+        let b_bi = to_bigint(b.bits()).ok_or_else(|| {
+            rhdl_error(DynamicTypeError::CannotConvertUninitToInt { value: b.clone() })
+        })?;
+        let result = if rem { a_bi % b_bi } else { a_bi / b_bi };
+        Ok(TypedBits::new(from_bigint(&result, a.len()).into(), kind))
+    } else {
+        let a_bi = to_biguint(a.bits()).ok_or_else(|| {
+            rhdl_error(DynamicTypeError::CannotConvertUninitToInt { value: a.clone() })
+        })?;
+        let b_bi = to_biguint(b.bits()).ok_or_else(|| {
+            rhdl_error(DynamicTypeError::CannotConvertUninitToInt { value: b.clone() })
+        })?;
+        let result = if rem { a_bi % b_bi } else { a_bi / b_bi };
+        Ok(TypedBits::new(from_biguint(&result, a.len()), kind))
+    }
+}
+
 fn xsub(a: TypedBits, b: TypedBits) -> Result<TypedBits, RHDLError> {
     let size_fn = |a: usize, b| a.max(b) + 1;
     if a.kind().is_signed() ^ b.kind().is_signed() {
@@ -157,6 +204,73 @@ fn xmul(a: TypedBits, b: TypedBits) -> Result<TypedBits, RHDLError> {
     }
 }
 
+// Add or subtract two values of the same kind, returning the wrapped
+// result and a flag that is set if the exact result does not fit.
+fn overflowing(a: TypedBits, b: TypedBits, sub: bool) -> Result<(TypedBits, bool), RHDLError> {
+    if a.kind() != b.kind() {
+        return Err(rhdl_error(
+            DynamicTypeError::BinaryOperationRequiresCompatibleType {
+                lhs: a.kind(),
+                rhs: b.kind(),
+            },
+        ));
+    }
+    let to_int = |x: &TypedBits| {
+        let value = if x.kind().is_signed() {
+            to_bigint(x.bits())
+        } else {
+            to_biguint(x.bits()).map(BigInt::from)
+        };
+        value.ok_or_else(|| {
+            rhdl_error(DynamicTypeError::CannotConvertUninitToInt { value: x.clone() })
+        })
+    };
+    let exact = if sub {
+        to_int(&a)? - to_int(&b)?
+    } else {
+        to_int(&a)? + to_int(&b)?
+    };
+    let wrapped = TypedBits::new(from_bigint(&exact, a.len()).into(), a.kind());
+    let overflow = to_int(&wrapped)? != exact;
+    Ok((wrapped, overflow))
+}
+
+fn saturating(a: TypedBits, b: TypedBits, sub: bool) -> Result<TypedBits, RHDLError> {
+    let kind = a.kind();
+    let len = a.len();
+    let negative = kind.is_signed() && a.bits().last() == Some(&BitX::One);
+    let (wrapped, overflow) = overflowing(a, b, sub)?;
+    if !overflow {
+        return Ok(wrapped);
+    }
+    // Clamp to the largest or smallest value of the kind
+    let bits = match (kind.is_signed(), negative, sub) {
+        (true, false, _) => (0..len).map(|ndx| ndx + 1 < len).collect::<Vec<_>>(),
+        (true, true, _) => (0..len).map(|ndx| ndx + 1 == len).collect(),
+        (false, _, false) => vec![true; len],
+        (false, _, true) => vec![false; len],
+    };
+    Ok(TypedBits::new(
+        bits.into_iter().map(BitX::from).collect(),
+        kind,
+    ))
+}
+
+// Count the bits of the argument with the given function.  The
+// count has the same width as the argument, and is undefined if
+// any of the bits are undefined.
+fn count(arg1: TypedBits, count_fn: impl Fn(&[BitX]) -> usize) -> Result<TypedBits, RHDLError> {
+    let len = arg1.len();
+    if arg1
+        .bits()
+        .iter()
+        .any(|b| !matches!(b, BitX::Zero | BitX::One))
+    {
+        return Ok(TypedBits::new(vec![BitX::X; len], Kind::Bits(len)));
+    }
+    TypedBits::from(count_fn(arg1.bits()) as u64).unsigned_cast(len)
+}
+
 pub fn binary(
     op: crate::rhif::spec::AluBinary,
     arg1: TypedBits,
@@ -177,9 +291,17 @@ pub fn binary(
         AluBinary::Gt => Ok((arg1 > arg2).typed_bits()),
         AluBinary::Ge => Ok((arg1 >= arg2).typed_bits()),
         AluBinary::Mul => mul(arg1, arg2),
+        AluBinary::Div => div_rem(arg1, arg2, false),
+        AluBinary::Rem => div_rem(arg1, arg2, true),
         AluBinary::XAdd => xadd(arg1, arg2),
         AluBinary::XSub => xsub(arg1, arg2),
         AluBinary::XMul => xmul(arg1, arg2),
+        AluBinary::SaturatingAdd => saturating(arg1, arg2, false),
+        AluBinary::SaturatingSub => saturating(arg1, arg2, true),
+        AluBinary::OverflowingAdd | AluBinary::OverflowingSub => {
+            let (wrapped, overflow) = overflowing(arg1, arg2, op == AluBinary::OverflowingSub)?;
+            Ok(tuple(&[wrapped, overflow.typed_bits()]))
+        }
     }
 }
 
@@ -209,6 +331,19 @@ pub fn unary(op: AluUnary, arg1: TypedBits) -> Result<TypedBits, RHDLError> {
             let arg1 = arg1.xext(1)?;
             arg1.as_signed()
         }
+        AluUnary::CountOnes => count(arg1, |bits| {
+            bits.iter().filter(|b| **b == BitX::One).count()
+        }),
+        AluUnary::LeadingZeros => count(arg1, |bits| {
+            bits.iter().rev().take_while(|b| **b == BitX::Zero).count()
+        }),
+        AluUnary::TrailingZeros => count(arg1, |bits| {
+            bits.iter().take_while(|b| **b == BitX::Zero).count()
+        }),
+        AluUnary::ReverseBits => Ok(TypedBits::new(
+            arg1.bits().iter().rev().copied().collect(),
+            arg1.kind(),
+        )),
     }
 }
 
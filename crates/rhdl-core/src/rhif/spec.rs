@@ -185,6 +185,8 @@ pub enum AluBinary {
     Add,
     Sub,
     Mul,
+    Div,
+    Rem,
     BitXor,
     BitAnd,
     BitOr,
@@ -199,6 +201,10 @@ pub enum AluBinary {
     XAdd,
     XSub,
     XMul,
+    SaturatingAdd,
+    SaturatingSub,
+    OverflowingAdd,
+    OverflowingSub,
 }
 
 impl AluBinary {
@@ -234,6 +240,10 @@ pub enum AluUnary {
     XShr(usize),
     XNeg,
     XSgn,
+    CountOnes,
+    LeadingZeros,
+    TrailingZeros,
+    ReverseBits,
 }
 
 #[derive(Hash, Eq, Ord, PartialOrd, PartialEq, Copy, Clone, Default)]
@@ -10,6 +10,8 @@ impl std::fmt::Debug for AluBinary {
             AluBinary::Add => write!(f, "+"),
             AluBinary::Sub => write!(f, "-"),
             AluBinary::Mul => write!(f, "*"),
+            AluBinary::Div => write!(f, "/"),
+            AluBinary::Rem => write!(f, "%"),
             AluBinary::BitAnd => write!(f, "&"),
             AluBinary::BitOr => write!(f, "|"),
             AluBinary::BitXor => write!(f, "^"),
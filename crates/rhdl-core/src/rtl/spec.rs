@@ -37,11 +37,13 @@ impl SymbolKind for OperandKind {
 
 pub type Operand = Symbol<OperandKind>;
 
-#[derive(Clone, Copy, PartialEq, Hash)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub enum AluBinary {
     Add,
     Sub,
     Mul,
+    Div,
+    Rem,
     BitXor,
     BitAnd,
     BitOr,
@@ -61,6 +63,8 @@ impl From<AluBinary> for crate::rhif::spec::AluBinary {
             AluBinary::Add => crate::rhif::spec::AluBinary::Add,
             AluBinary::Sub => crate::rhif::spec::AluBinary::Sub,
             AluBinary::Mul => crate::rhif::spec::AluBinary::Mul,
+            AluBinary::Div => crate::rhif::spec::AluBinary::Div,
+            AluBinary::Rem => crate::rhif::spec::AluBinary::Rem,
             AluBinary::BitXor => crate::rhif::spec::AluBinary::BitXor,
             AluBinary::BitAnd => crate::rhif::spec::AluBinary::BitAnd,
             AluBinary::BitOr => crate::rhif::spec::AluBinary::BitOr,
@@ -84,7 +88,7 @@ pub struct Binary {
     pub arg2: Operand,
 }
 
-#[derive(Clone, Copy, PartialEq, Hash)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub enum AluUnary {
     Neg,
     Not,
@@ -155,7 +159,7 @@ pub struct Assign {
     pub rhs: Operand,
 }
 
-#[derive(Clone, Debug, PartialEq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum CaseArgument {
     Literal(LiteralId<OperandKind>),
     Wild,
@@ -176,7 +180,7 @@ pub struct Cast {
     pub kind: CastKind,
 }
 
-#[derive(Debug, Clone, PartialEq, Copy, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Copy, Hash)]
 pub enum CastKind {
     Signed,
     Unsigned,
@@ -0,0 +1,339 @@
+//! Lock-step co-simulation with Icarus Verilog
+//!
+//! The testbenches generated by [SynchronousTestBench](crate::sim::testbench::synchronous::SynchronousTestBench)
+//! replay a recorded trace in Verilog after the fact, and can only report
+//! that something failed.  The tools in this module instead keep an `iverilog`
+//! simulation running alongside the Rust one.  The generated module is wrapped
+//! in a small driver that reads one input vector per line from `stdin`, applies
+//! it, and writes the resulting output vector to `stdout`.  No VPI modules are
+//! needed, just `iverilog` and `vvp` on the `PATH`.
+//!
+//! There are two ways to use it:
+//!
+//! - [SynchronousTestBench::cosim](crate::sim::testbench::synchronous::SynchronousTestBench::cosim)
+//!   checks a circuit against the Verilog generated for it, step by step, and
+//!   reports the first cycle in which they disagree, along with a field-by-field
+//!   diff of the outputs.
+//! - [Cosim] wraps a [Synchronous] circuit so that its simulation is delegated
+//!   to its Verilog.  This lets an RHDL design instantiate a child that only
+//!   exists in Verilog, like a [ForeignSynchronous](crate::circuit::foreign::ForeignSynchronous)
+//!   without a behavioral model.
+use std::{
+    cell::RefCell,
+    io::{BufRead, BufReader, Write},
+    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+    rc::Rc,
+};
+
+use crate::{
+    BitX, ClockReset, Digital, Kind, RHDLError, Synchronous, SynchronousDQ, SynchronousIO,
+    TypedBits,
+    bitx::{bitx_parse, bitx_string},
+    circuit::{
+        descriptor::{Descriptor, SyncKind},
+        scoped_name::ScopedName,
+    },
+    types::{
+        digital_fn::NoSynchronousKernel,
+        path::{Path, bit_range, leaf_paths},
+    },
+};
+use rhdl_vlog as vlog;
+
+/// A running `vvp` process simulating a synchronous module.
+///
+/// The module must follow the RHDL convention for synchronous circuits,
+/// i.e., its ports are `clock_reset`, the (optional) input and the output.
+/// Each call to [VerilogProcess::step] applies a new set of inputs, advances
+/// the simulation by one time unit, and returns the output.
+pub struct VerilogProcess {
+    child: Child,
+    stdin: Option<ChildStdin>,
+    stdout: BufReader<ChildStdout>,
+    has_input: bool,
+    output_kind: Kind,
+    // The compiled simulation lives here, and must outlive the process
+    _dir: tempfile::TempDir,
+}
+
+fn driver(
+    hdl: &vlog::ModuleList,
+    input_bits: usize,
+    output_kind: Kind,
+) -> Result<String, RHDLError> {
+    let uut = hdl
+        .modules
+        .first()
+        .ok_or_else(|| RHDLError::TestbenchConstructionError("No modules to simulate".into()))?;
+    let has_input = uut.args.len() == 3;
+    if uut.args.len() < 2
+        || !uut.args[0].direction.is_input()
+        || uut.args[0].width() != ClockReset::BITS
+    {
+        return Err(RHDLError::TestbenchConstructionError(
+            "First port must be an input with 2 bits width".into(),
+        ));
+    }
+    if has_input != (input_bits != 0) || (has_input && uut.args[1].width() != input_bits) {
+        return Err(RHDLError::TestbenchConstructionError(
+            "Input port width mismatch".into(),
+        ));
+    }
+    let output_port = uut.args.last().unwrap();
+    if !output_port.direction.is_output() || output_port.width() != output_kind.bits() {
+        return Err(RHDLError::TestbenchConstructionError(format!(
+            "Output port mismatch: width {width} expected width {expected}",
+            width = output_port.width(),
+            expected = output_kind.bits()
+        )));
+    }
+    let (input_decl, input_connection, scan) = if has_input {
+        (
+            format!("    reg [{}:0] i;\n", input_bits - 1),
+            format!(", .{}(i)", uut.args[1].decl.name),
+            "count = $fscanf(32'h8000_0000, \"%b %b\\n\", clock_reset, i);\n            if (count != 2) $finish;",
+        )
+    } else {
+        (
+            String::new(),
+            String::new(),
+            "count = $fscanf(32'h8000_0000, \"%b\\n\", clock_reset);\n            if (count != 1) $finish;",
+        )
+    };
+    Ok(format!(
+        "module cosim_driver;
+    reg [1:0] clock_reset;
+{input_decl}    wire [{o_msb}:0] o;
+    integer count;
+    {name} t(.{cr}(clock_reset){input_connection}, .{out}(o));
+    initial begin
+        while (1) begin
+            {scan}
+            #1;
+            $display(\"%b\", o);
+            $fflush(32'h8000_0001);
+        end
+    end
+endmodule
+{hdl}",
+        o_msb = output_kind.bits() - 1,
+        name = uut.name,
+        cr = uut.args[0].decl.name,
+        out = output_port.decl.name,
+    ))
+}
+
+impl VerilogProcess {
+    /// Compile the given modules with `iverilog` and start the simulation.
+    /// The first module in the list is the one that is simulated.
+    pub fn spawn(
+        hdl: &vlog::ModuleList,
+        input_bits: usize,
+        output_kind: Kind,
+    ) -> Result<Self, RHDLError> {
+        let text = driver(hdl, input_bits, output_kind)?;
+        let dir = tempfile::tempdir()?;
+        std::fs::write(dir.path().join("cosim.v"), text)?;
+        let status = Command::new("iverilog")
+            .arg("-o")
+            .arg(dir.path().join("cosim"))
+            .arg(dir.path().join("cosim.v"))
+            .status()
+            .expect("Icarus Verilog should be installed and in your PATH.");
+        if !status.success() {
+            return Err(anyhow::anyhow!("Failed to compile co-simulation with {}", status).into());
+        }
+        let mut child = Command::new("vvp")
+            .arg(dir.path().join("cosim"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let stdin = child.stdin.take();
+        let stdout = BufReader::new(child.stdout.take().expect("stdout is piped"));
+        Ok(Self {
+            child,
+            stdin,
+            stdout,
+            has_input: input_bits != 0,
+            output_kind,
+            _dir: dir,
+        })
+    }
+    /// Apply the clock, reset and input, and return the output of the module.
+    pub fn step(
+        &mut self,
+        clock_reset: ClockReset,
+        input: &[BitX],
+    ) -> Result<TypedBits, RHDLError> {
+        let mut line = bitx_string(&clock_reset.bin());
+        if self.has_input {
+            line.push(' ');
+            line.push_str(&bitx_string(input));
+        }
+        line.push('\n');
+        let stdin = self.stdin.as_mut().expect("stdin is open until drop");
+        stdin.write_all(line.as_bytes())?;
+        stdin.flush()?;
+        // Skip anything that is not an output vector, like messages from vvp itself
+        loop {
+            let mut reply = String::new();
+            if self.stdout.read_line(&mut reply)? == 0 {
+                return Err(RHDLError::VerilogVerificationErrorString(
+                    "Co-simulation ended unexpectedly".into(),
+                ));
+            }
+            let reply = reply.trim().replace(['z', 'Z'], "x");
+            if reply.len() != self.output_kind.bits() {
+                continue;
+            }
+            if let Some(bits) = bitx_parse(&reply) {
+                return Ok(TypedBits::new(bits.into(), self.output_kind));
+            }
+        }
+    }
+}
+
+impl Drop for VerilogProcess {
+    fn drop(&mut self) {
+        // Closing stdin causes the driver to call $finish
+        self.stdin.take();
+        if self.child.wait().is_err() {
+            let _ = self.child.kill();
+        }
+    }
+}
+
+/// Describe the fields that differ between two values of the same kind,
+/// one per line.
+pub(crate) fn typed_bits_diff(expected: &TypedBits, actual: &TypedBits) -> String {
+    let kind = expected.kind();
+    let mut diff = String::new();
+    for path in leaf_paths(&kind, Path::default()) {
+        let Ok((bits, _)) = bit_range(kind, &path) else {
+            continue;
+        };
+        let (expected_bits, actual_bits) = (&expected.bits()[bits.clone()], &actual.bits()[bits]);
+        if expected_bits == actual_bits {
+            continue;
+        }
+        let describe = |value: &TypedBits, bits: &[BitX]| match value.path(&path) {
+            Ok(sub) => format!("{sub:?}"),
+            Err(_) => bitx_string(bits),
+        };
+        diff.push_str(&format!(
+            "{path:?}: expected {} got {}\n",
+            describe(expected, expected_bits),
+            describe(actual, actual_bits)
+        ));
+    }
+    diff
+}
+
+#[derive(Default)]
+struct CosimInner {
+    process: Option<VerilogProcess>,
+    last: Option<(Box<[BitX]>, TypedBits)>,
+}
+
+#[doc(hidden)]
+#[derive(Clone, Default)]
+pub struct CosimState(Rc<RefCell<CosimInner>>);
+
+// The state of the Verilog simulation is opaque, so two states are
+// only the same if they refer to the same simulation.
+impl PartialEq for CosimState {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Simulate a [Synchronous] circuit by running its Verilog
+///
+/// `Cosim` has the same interface and HDL as the circuit it wraps, but
+/// its `sim` method runs the generated Verilog in `iverilog` rather than
+/// calling the circuit's own `sim`.  The simulation is started the first
+/// time the circuit is simulated, and stopped when the state is dropped.
+///
+/// The Verilog produces bits, and an RHDL value cannot in general be
+/// reconstructed from its bits, so you must also supply a function that
+/// decodes the output.  For outputs made of bit vectors, tuples and arrays,
+/// this can be [Decode::decode].  The decoder is not called if any of the
+/// output bits are unknown, in which case the output is [Digital::dont_care].
+///
+/// [Decode::decode]: crate::sim::run::compiled::Decode::decode
+///
+/// Because `sim` cannot return an error, a failure of the Verilog
+/// simulation causes a panic.
+#[derive(Clone, Debug)]
+pub struct Cosim<T: SynchronousIO> {
+    inner: T,
+    decode: fn(&TypedBits) -> T::O,
+}
+
+impl<T: Synchronous> Cosim<T> {
+    /// Wrap the given circuit, using `decode` to convert the output of the
+    /// Verilog back into an RHDL value.
+    pub fn new(inner: T, decode: fn(&TypedBits) -> T::O) -> Self {
+        Self { inner, decode }
+    }
+    fn spawn(&self) -> Result<VerilogProcess, RHDLError> {
+        let desc = self.inner.descriptor("cosim".into())?;
+        VerilogProcess::spawn(&desc.hdl()?.modules, T::I::BITS, T::O::static_kind())
+    }
+}
+
+impl<T: Synchronous> SynchronousDQ for Cosim<T> {
+    type D = ();
+    type Q = ();
+}
+
+impl<T: Synchronous> SynchronousIO for Cosim<T> {
+    type I = T::I;
+    type O = T::O;
+    type Kernel = NoSynchronousKernel<ClockReset, T::I, (), (T::O, ())>;
+}
+
+impl<T: Synchronous> Synchronous for Cosim<T> {
+    type S = CosimState;
+
+    fn init(&self) -> Self::S {
+        CosimState::default()
+    }
+
+    fn sim(&self, clock_reset: ClockReset, input: Self::I, state: &mut Self::S) -> Self::O {
+        let mut state = state.0.borrow_mut();
+        let stimulus: Box<[BitX]> = clock_reset
+            .bin()
+            .iter()
+            .chain(&input.bin())
+            .copied()
+            .collect();
+        // The parent may call us repeatedly with the same inputs while it settles
+        let output = match &state.last {
+            Some((last, output)) if *last == stimulus => output.clone(),
+            _ => {
+                if state.process.is_none() {
+                    let process = self
+                        .spawn()
+                        .unwrap_or_else(|err| panic!("Failed to start co-simulation: {err}"));
+                    state.process = Some(process);
+                }
+                let process = state.process.as_mut().unwrap();
+                let output = process
+                    .step(clock_reset, &input.bin())
+                    .unwrap_or_else(|err| panic!("Co-simulation failed: {err}"));
+                state.last = Some((stimulus, output.clone()));
+                output
+            }
+        };
+        if output.bits().contains(&BitX::X) {
+            Self::O::dont_care()
+        } else {
+            (self.decode)(&output)
+        }
+    }
+
+    fn descriptor(&self, scoped_name: ScopedName) -> Result<Descriptor<SyncKind>, RHDLError> {
+        self.inner.descriptor(scoped_name)
+    }
+}
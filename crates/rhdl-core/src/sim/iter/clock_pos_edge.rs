@@ -22,7 +22,8 @@ clk               | :             |                | :
 "#
 )]
 //!
-//! The hold time `δ` is one time unit, and the clock period is configurable.
+//! The hold time `δ` is one time unit.  The clock period is configurable, as are
+//! the phase, duty cycle and jitter of the clock.
 use crate::{
     Clock, ClockReset, Digital, TimedSample, clock::clock, clock_reset, sim::ResetOrData,
     timed_sample, types::reset::reset,
@@ -43,6 +44,10 @@ enum State {
 /// reset signals along with the samples.
 ///
 /// Normally you would create this adapter using the `ClockPosEdgeExt` trait.
+/// By default, the clock starts low at time zero, has a 50% duty cycle, and
+/// no jitter.  These can be changed with the [with_phase](ClockPosEdge::with_phase),
+/// [with_duty_cycle](ClockPosEdge::with_duty_cycle) and
+/// [with_jitter](ClockPosEdge::with_jitter) methods.
 pub struct ClockPosEdge<I, S>
 where
     S: Digital,
@@ -52,7 +57,12 @@ where
     state: State,
     time: u64,
     next_time: u64,
-    period: u64,
+    nominal: u64,
+    phase: u64,
+    low: u64,
+    high: u64,
+    jitter: u64,
+    seed: u64,
 }
 
 impl<I, S> ClockPosEdge<I, S>
@@ -67,6 +77,65 @@ where
             }
         }
     }
+    // Advance the nominal time of the next clock edge, and
+    // compute the actual (jittered) time at which it occurs.
+    fn advance(&mut self, delta: u64) {
+        self.nominal += delta;
+        self.next_time = self.nominal;
+        if self.jitter != 0 {
+            // SplitMix64, so that the jitter is deterministic given the seed
+            self.seed = self.seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = self.seed;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^= z >> 31;
+            self.next_time = self.nominal - self.jitter + z % (2 * self.jitter + 1);
+        }
+    }
+    fn check_timing(&self) {
+        assert!(
+            self.high >= 2 * self.jitter + 2 && self.low > 2 * self.jitter,
+            "clock high time ({}) and low time ({}) are too short for a jitter of {}",
+            self.high,
+            self.low,
+            self.jitter
+        );
+    }
+    /// Delay the first rising edge of the clock by `phase` time units.
+    /// The clock is held low until then.
+    pub fn with_phase(mut self, phase: u64) -> Self {
+        self.phase = phase;
+        self
+    }
+    /// Set the fraction of the period (in percent) for which the clock is high.
+    /// The period of the clock is unchanged.
+    pub fn with_duty_cycle(mut self, percent: u64) -> Self {
+        assert!(
+            (1..100).contains(&percent),
+            "duty cycle must be between 1 and 99 percent"
+        );
+        let period = self.low + self.high;
+        assert!(
+            period >= 3,
+            "clock period is too short to set the duty cycle"
+        );
+        self.high = (period * percent / 100).clamp(2, period - 1);
+        self.low = period - self.high;
+        self.check_timing();
+        self
+    }
+    /// Move each clock edge by a pseudo-random amount in the range
+    /// `-jitter..=jitter` time units.  The jitter is deterministic, and
+    /// is determined by the `seed`, so that a failing simulation can
+    /// be reproduced.  The edges of the clock are never reordered, so
+    /// the jitter must be small compared to the high and low times of
+    /// the clock.
+    pub fn with_jitter(mut self, jitter: u64, seed: u64) -> Self {
+        self.jitter = jitter;
+        self.seed = seed;
+        self.check_timing();
+        self
+    }
 }
 
 impl<I, S> Clone for ClockPosEdge<I, S>
@@ -81,7 +150,12 @@ where
             state: self.state.clone(),
             time: self.time,
             next_time: self.next_time,
-            period: self.period,
+            nominal: self.nominal,
+            phase: self.phase,
+            low: self.low,
+            high: self.high,
+            jitter: self.jitter,
+            seed: self.seed,
         }
     }
 }
@@ -117,7 +191,7 @@ where
                 if let Some(data) = self.input.next() {
                     self.sample = data;
                     self.state = State::Hold;
-                    self.next_time = self.time + self.period / 2;
+                    self.advance(self.phase + self.low);
                     Some(self.this_sample(clock(false)))
                 } else {
                     self.state = State::Done;
@@ -127,13 +201,12 @@ where
             State::ClockLow => {
                 self.state = State::Hold;
                 self.time = self.next_time;
-                self.next_time = self.time + self.period / 2;
+                self.advance(self.low);
                 Some(self.this_sample(clock(false)))
             }
             State::Hold => {
                 self.state = State::ClockHigh;
                 self.time = self.next_time;
-                self.next_time += 1;
                 Some(self.this_sample(clock(true)))
             }
             State::ClockHigh => {
@@ -143,14 +216,14 @@ where
                 } else {
                     self.state = State::TailStart;
                 }
-                self.time = self.next_time;
-                self.next_time += self.period / 2 - 1;
+                self.time += 1;
+                self.advance(self.high);
                 Some(self.this_sample(clock(true)))
             }
             State::TailStart => {
                 self.state = State::TailEnd;
                 self.time = self.next_time;
-                self.next_time = self.time + self.period / 2;
+                self.advance(self.low);
                 Some(self.this_sample(clock(false)))
             }
             State::TailEnd => {
@@ -177,7 +250,12 @@ where
         state: State::Init,
         time: 0,
         next_time: 0,
-        period,
+        nominal: 0,
+        phase: 0,
+        low: period / 2,
+        high: period / 2,
+        jitter: 0,
+        seed: 0,
     }
 }
 
@@ -242,4 +320,73 @@ mod tests {
         let v = k.collect::<Vec<_>>();
         assert_eq!(v, expected());
     }
+
+    #[test]
+    fn test_clock_pos_edge_defaults_unchanged() {
+        let k = (0..4)
+            .map(b8)
+            .map(ResetOrData::Data)
+            .clock_pos_edge(10)
+            .with_phase(0)
+            .with_jitter(0, 42);
+        assert_eq!(k.collect::<Vec<_>>(), expected());
+    }
+
+    #[test]
+    fn test_clock_pos_edge_with_phase_and_duty_cycle() {
+        let k = (0..2)
+            .map(b8)
+            .map(ResetOrData::Data)
+            .clock_pos_edge(10)
+            .with_phase(3)
+            .with_duty_cycle(30);
+        let v = k
+            .map(|x| (x.time, x.value.0.clock.raw()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            v,
+            vec![
+                (0, false),
+                (10, true),
+                (11, true),
+                (13, false),
+                (20, true),
+                (21, true),
+                (23, false),
+                (30, false)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_clock_pos_edge_with_jitter() {
+        let stream = || {
+            std::iter::repeat_n(b8(0), 1000)
+                .map(ResetOrData::Data)
+                .clock_pos_edge(20)
+                .with_jitter(3, 0xbeef)
+        };
+        let v = stream().collect::<Vec<_>>();
+        // The jitter is deterministic
+        assert_eq!(v, stream().collect::<Vec<_>>());
+        // Time never goes backwards, and the edges stay within the jitter
+        // of their nominal positions
+        assert!(v.windows(2).all(|w| w[0].time < w[1].time));
+        let rising = v
+            .windows(2)
+            .filter(|w| !w[0].value.0.clock.raw() && w[1].value.0.clock.raw())
+            .map(|w| w[1].time)
+            .collect::<Vec<_>>();
+        assert_eq!(rising.len(), 1000);
+        for (ndx, time) in rising.iter().enumerate() {
+            assert!(time.abs_diff(10 + 20 * ndx as u64) <= 3);
+        }
+        // And the clock is actually jittered
+        assert!(
+            rising
+                .iter()
+                .enumerate()
+                .any(|(ndx, time)| *time != 10 + 20 * ndx as u64)
+        );
+    }
 }
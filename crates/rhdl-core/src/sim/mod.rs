@@ -1,5 +1,6 @@
 //! Built-in Simulator and Testbench support
 #![warn(missing_docs)]
+pub mod cosim;
 pub mod iter;
 pub mod probe;
 pub mod run;
@@ -1,4 +1,4 @@
-//! Asynchronous circuit simulator for dual clocked designs
+//! Asynchronous circuit simulators for multi-clocked designs
 use crate::sim::extension::*;
 use crate::trace::page::{set_trace_page, take_trace_page};
 use crate::trace::session::Session;
@@ -7,12 +7,10 @@ use crate::types::clock_reset::clock_reset;
 use crate::types::reset::reset;
 use crate::types::signal::signal;
 use crate::{Circuit, CircuitIO};
-use crate::{ClockReset, Digital};
+use crate::{ClockReset, Digital, TimedSample};
 use crate::{Domain, Signal, clock::clock};
 
-fn neg_edge<D: Domain>(prev_cr: Signal<ClockReset, D>, curr_cr: Signal<ClockReset, D>) -> bool {
-    let prev_cr = prev_cr.val();
-    let curr_cr = curr_cr.val();
+fn neg_edge(prev_cr: ClockReset, curr_cr: ClockReset) -> bool {
     if curr_cr.reset.any() || prev_cr.reset.any() {
         return false;
     }
@@ -63,10 +61,10 @@ where
             let blue_cr = signal(event.value.1.0);
             let mut input = prev_input;
             injector(red_cr, blue_cr, &mut input);
-            if neg_edge(prev_red_cr, red_cr) {
+            if neg_edge(prev_red_cr.val(), red_cr.val()) {
                 red_fn(prev_output, &mut input);
             }
-            if neg_edge(prev_blue_cr, blue_cr) {
+            if neg_edge(prev_blue_cr.val(), blue_cr.val()) {
                 blue_fn(prev_output, &mut input);
             }
             let page = event.is_traced().then(|| session.page());
@@ -88,3 +86,210 @@ where
         }
     })
 }
+
+/// The clock (and reset) of one domain in a multi-clocked simulation.
+///
+/// The clock starts low at time zero, and rises for the first time after
+/// `phase + period * (100 - duty_cycle) / 100` time units.  Reset is held
+/// for the first `reset` rising edges of the clock.  See [ClockPosEdge] for
+/// the details of the waveform.
+///
+/// [ClockPosEdge]: crate::sim::iter::clock_pos_edge::ClockPosEdge
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClockSpec {
+    period: u64,
+    phase: u64,
+    duty_cycle: u64,
+    jitter: u64,
+    seed: u64,
+    reset: usize,
+}
+
+impl ClockSpec {
+    /// A clock with the given period, no phase offset, a 50% duty cycle,
+    /// no jitter and a reset pulse one cycle long.
+    pub fn new(period: u64) -> Self {
+        Self {
+            period,
+            phase: 0,
+            duty_cycle: 50,
+            jitter: 0,
+            seed: 0,
+            reset: 1,
+        }
+    }
+    /// Delay the first rising edge of the clock by `phase` time units.
+    pub fn with_phase(self, phase: u64) -> Self {
+        Self { phase, ..self }
+    }
+    /// Set the percentage of the period for which the clock is high.
+    pub fn with_duty_cycle(self, duty_cycle: u64) -> Self {
+        Self { duty_cycle, ..self }
+    }
+    /// Move each edge of the clock by up to `jitter` time units, using a
+    /// deterministic sequence determined by `seed`.
+    pub fn with_jitter(self, jitter: u64, seed: u64) -> Self {
+        Self {
+            jitter,
+            seed,
+            ..self
+        }
+    }
+    /// Hold reset for the first `cycles` cycles of the clock.
+    pub fn with_reset(self, cycles: usize) -> Self {
+        Self {
+            reset: cycles,
+            ..self
+        }
+    }
+    /// An endless timed stream of the clock and reset described by this spec.
+    pub fn stream(&self) -> impl Iterator<Item = TimedSample<ClockReset>> + Clone + 'static {
+        let stream = std::iter::repeat(())
+            .with_reset(self.reset)
+            .clock_pos_edge(self.period)
+            .with_phase(self.phase);
+        let stream = if self.duty_cycle == 50 {
+            stream
+        } else {
+            stream.with_duty_cycle(self.duty_cycle)
+        };
+        // Jitter needs room in each half cycle, so only ask for it
+        // when there is some.  This keeps very short periods usable.
+        let stream = if self.jitter == 0 {
+            stream
+        } else {
+            stream.with_jitter(self.jitter, self.seed)
+        };
+        stream.map(|sample| sample.map(|(cr, _)| cr))
+    }
+}
+
+type Injector<'a, T> = Box<dyn FnMut(ClockReset, &mut <T as CircuitIO>::I) + 'a>;
+type EdgeFn<'a, T> = Box<dyn FnMut(<T as CircuitIO>::O, &mut <T as CircuitIO>::I) + 'a>;
+
+/// A clock domain driving a circuit in [run_async].
+///
+/// Each domain has a [ClockSpec] describing its clock, an injector that
+/// places the clock and reset signal into the input of the circuit, and
+/// an (optional) function that is called on the negative edges of the
+/// clock (outside of reset) to update the inputs in that domain, given
+/// the most recent output of the circuit.
+pub struct DomainClock<'a, T: CircuitIO> {
+    spec: ClockSpec,
+    injector: Injector<'a, T>,
+    on_neg_edge: Option<EdgeFn<'a, T>>,
+}
+
+impl<'a, T: CircuitIO> DomainClock<'a, T> {
+    /// Create a new clock domain from the spec, and an injector that
+    /// places the clock and reset signal for domain `D` into the input.
+    pub fn new<D: Domain>(
+        spec: ClockSpec,
+        mut injector: impl FnMut(Signal<ClockReset, D>, &mut <T as CircuitIO>::I) + 'a,
+    ) -> Self {
+        Self {
+            spec,
+            injector: Box::new(move |cr, input| injector(signal(cr), input)),
+            on_neg_edge: None,
+        }
+    }
+    /// Call the supplied function on each negative edge of this domain's clock.
+    pub fn on_neg_edge(
+        self,
+        edge_fn: impl FnMut(<T as CircuitIO>::O, &mut <T as CircuitIO>::I) + 'a,
+    ) -> Self {
+        Self {
+            on_neg_edge: Some(Box::new(edge_fn)),
+            ..self
+        }
+    }
+}
+
+/// Run an asynchronous circuit simulation with an arbitrary
+/// number of clock domains.
+///
+/// The clocks of the domains are generated according to their
+/// [ClockSpec]s, and merged into a single timed stream.  At each
+/// point in time, the injectors of all domains are called (in order),
+/// followed by the negative edge functions of any domains whose clock
+/// just fell.  This generalizes [run_async_red_blue] to domains with
+/// different phases, duty cycles and jitter.  The stream is endless,
+/// so you will normally limit it with `take_while`.
+pub fn run_async<'a, T, const N: usize>(
+    uut: &'a T,
+    domains: [DomainClock<'a, T>; N],
+) -> impl Iterator<Item = TracedSample<<T as CircuitIO>::I, <T as CircuitIO>::O>> + 'a
+where
+    T: Circuit,
+{
+    let mut domains = domains;
+    let mut prev_output = <T as CircuitIO>::O::dont_care();
+    let mut state = uut.init();
+    let mut prev_input = <T as CircuitIO>::I::dont_care();
+    let mut prev_crs = [clock_reset(clock(false), reset(true)); N];
+    // The merged stream ends when either of its inputs does, so the
+    // first clock seeds the merge, rather than an empty stream.
+    let mut clocks = domains.iter().map(|domain| domain.spec.stream());
+    let first: Box<dyn Iterator<Item = TimedSample<[ClockReset; N]>>> = match clocks.next() {
+        Some(clock) => Box::new(clock.map(|sample| {
+            sample.map(|cr| {
+                let mut crs = [ClockReset::dont_care(); N];
+                crs[0] = cr;
+                crs
+            })
+        })),
+        None => Box::new(std::iter::empty()),
+    };
+    let mut sequence = clocks.enumerate().fold(first, |merged, (ndx, clock)| {
+        Box::new(
+            merged.merge_map(clock, move |mut crs: [ClockReset; N], cr| {
+                crs[ndx + 1] = cr;
+                crs
+            }),
+        )
+    });
+    let session = Session::default();
+    std::iter::from_fn(move || {
+        let event = sequence.next()?;
+        let mut input = prev_input;
+        for (domain, cr) in domains.iter_mut().zip(event.value) {
+            (domain.injector)(cr, &mut input);
+        }
+        for (domain, (prev_cr, cr)) in domains.iter_mut().zip(prev_crs.iter().zip(event.value)) {
+            if let Some(edge_fn) = domain
+                .on_neg_edge
+                .as_mut()
+                .filter(|_| neg_edge(*prev_cr, cr))
+            {
+                edge_fn(prev_output, &mut input);
+            }
+        }
+        let page = event.is_traced().then(|| session.page());
+        set_trace_page(page);
+        let output = uut.sim(input, &mut state);
+        prev_input = input;
+        prev_output = output;
+        prev_crs = event.value;
+        let page = take_trace_page();
+        Some(TracedSample {
+            time: event.time,
+            page,
+            input,
+            output,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_clock_periods() {
+        for period in [2, 3] {
+            let samples = ClockSpec::new(period).stream().take(20).collect::<Vec<_>>();
+            assert!(samples.windows(2).all(|w| w[0].time <= w[1].time));
+            assert!(samples.iter().any(|sample| sample.value.clock.raw()));
+        }
+    }
+}
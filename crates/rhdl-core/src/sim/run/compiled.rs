@@ -0,0 +1,268 @@
+//! Extension trait and types to run a synchronous circuit with the compiled netlist simulator.
+//!
+//! The runner takes the same iterator of timed inputs as
+//! [run](crate::sim::run::synchronous::RunSynchronousExt::run), and produces the
+//! same [TracedSample]s, so the output can be sampled, checked, or collected into a
+//! test bench in the usual way.  Instead of calling the `sim` method of the circuit,
+//! it flattens the circuit into its netlist, and executes it with a
+//! [CompiledNetList].  This avoids the overhead of walking the hierarchy of the
+//! circuit (and of tracing) on every half cycle, which adds up over long regression
+//! runs.  No trace pages are recorded.
+//!
+//! The netlist produces bits, and an RHDL value cannot in general be reconstructed
+//! from its bits, so you must also supply a function that decodes the output, as for
+//! a [Cosim](crate::sim::cosim::Cosim).  For outputs made of bit vectors (of any
+//! width), tuples and arrays, [Decode::decode] will do.  The decoder is not called if
+//! any of the output bits are unknown, in which case the output is [Digital::dont_care].
+use rhdl_bits::{
+    BitWidth, Bits, SignedBits, SignedWideBits, WideBitWidth, WideBits,
+    fixed::{Fixed, SignedFixed},
+    limbs::Limbs,
+};
+use seq_macro::seq;
+
+use crate::{
+    BitX, ClockReset, Digital, Kind, RHDLError, Synchronous, SynchronousIO, TimedSample, TypedBits,
+    ntl::compiled::CompiledNetList, trace::trace_sample::TracedSample,
+};
+
+/// Values that can be rebuilt from their bits.
+///
+/// This is implemented for `bool`, the bit vector and fixed point types of
+/// any width, and tuples and arrays of these, so that [Decode::decode] can be
+/// used as the decoder for [run_compiled] or a [Cosim](crate::sim::cosim::Cosim)
+/// when the output is one of them.  Enums and structs need a hand written decoder.
+pub trait Decode: Digital {
+    /// Build a value from its bits, in the order produced by [Digital::bin].
+    fn from_bits(bits: &[BitX]) -> Self;
+    /// Build a value from the output of a simulation.
+    fn decode(bits: &TypedBits) -> Self {
+        Self::from_bits(bits.bits())
+    }
+}
+
+// Collect the bits (least significant first) into limbs.  Unknown bits are
+// read as zero.
+fn limbs(bits: &[BitX]) -> Limbs {
+    bits.iter()
+        .enumerate()
+        .fold(Limbs::ZERO, |acc, (ndx, bit)| {
+            acc.with_bit(ndx, *bit == BitX::One)
+        })
+}
+
+impl Decode for bool {
+    fn from_bits(bits: &[BitX]) -> Self {
+        bits[0] == BitX::One
+    }
+}
+
+impl<const N: usize> Decode for Bits<N>
+where
+    rhdl_bits::W<N>: BitWidth,
+{
+    fn from_bits(bits: &[BitX]) -> Self {
+        Bits::from_limbs_masked(limbs(&bits[..N]))
+    }
+}
+
+impl<const N: usize> Decode for SignedBits<N>
+where
+    rhdl_bits::W<N>: BitWidth,
+{
+    fn from_bits(bits: &[BitX]) -> Self {
+        SignedBits::from_limbs_wrapped(limbs(&bits[..N]))
+    }
+}
+
+impl<const N: usize> Decode for WideBits<N>
+where
+    rhdl_bits::W<N>: WideBitWidth,
+{
+    fn from_bits(bits: &[BitX]) -> Self {
+        WideBits::from_limbs_masked(limbs(&bits[..N]))
+    }
+}
+
+impl<const N: usize> Decode for SignedWideBits<N>
+where
+    rhdl_bits::W<N>: WideBitWidth,
+{
+    fn from_bits(bits: &[BitX]) -> Self {
+        SignedWideBits::from_limbs_wrapped(limbs(&bits[..N]))
+    }
+}
+
+impl<const I: usize, const F: usize> Decode for Fixed<I, F> {
+    fn from_bits(bits: &[BitX]) -> Self {
+        Fixed::from_limbs(limbs(&bits[..I + F]))
+    }
+}
+
+impl<const I: usize, const F: usize> Decode for SignedFixed<I, F> {
+    fn from_bits(bits: &[BitX]) -> Self {
+        SignedFixed::from_limbs(limbs(&bits[..I + F]).sign_extend(I + F))
+    }
+}
+
+impl<T: Decode, const N: usize> Decode for [T; N] {
+    fn from_bits(bits: &[BitX]) -> Self {
+        std::array::from_fn(|ndx| T::from_bits(&bits[ndx * T::BITS..]))
+    }
+}
+
+// Split the first `count` bits off the front of `bits`.
+fn take<'a>(bits: &mut &'a [BitX], count: usize) -> &'a [BitX] {
+    let (head, tail) = bits.split_at(count);
+    *bits = tail;
+    head
+}
+
+// Use the seq! macro to generate an implementation for a tuple of size N
+macro_rules! impl_tuple_for_decode {
+    ($size: expr) => {
+        seq!(N in 0..$size {
+            impl<
+              #(T~N: Decode,)*
+            > Decode for
+            (
+                #(T~N,)*
+            ) {
+                fn from_bits(bits: &[BitX]) -> Self {
+                    let mut rest = bits;
+                    (
+                        #(
+                            T~N::from_bits(take(&mut rest, T~N::BITS)),
+                        )*
+                    )
+                }
+            }
+        });
+    }
+}
+
+impl_tuple_for_decode!(1);
+impl_tuple_for_decode!(2);
+impl_tuple_for_decode!(3);
+impl_tuple_for_decode!(4);
+impl_tuple_for_decode!(5);
+impl_tuple_for_decode!(6);
+impl_tuple_for_decode!(7);
+impl_tuple_for_decode!(8);
+impl_tuple_for_decode!(9);
+impl_tuple_for_decode!(10);
+impl_tuple_for_decode!(11);
+impl_tuple_for_decode!(12);
+
+/// An iterator that runs the compiled netlist of a synchronous circuit given
+/// an iterator of timed inputs.
+///
+/// Because the iterator yields plain samples, a failure of the netlist
+/// simulation (like a black box that does not settle) causes a panic.
+#[must_use = "To run the simulation, you must exhaust the iterator"]
+#[derive(Clone)]
+pub struct RunCompiled<T: SynchronousIO, I> {
+    sim: CompiledNetList,
+    inputs: I,
+    time: u64,
+    output_kind: Kind,
+    decode: fn(&TypedBits) -> T::O,
+}
+
+/// Runs the compiled netlist of a synchronous circuit with the given iterator of timed inputs.
+///
+/// See [RunCompiledExt::run_compiled] for the requirements on `decode`.
+pub fn run_compiled<T, I>(
+    uut: &T,
+    inputs: I,
+    decode: fn(&TypedBits) -> T::O,
+) -> Result<RunCompiled<T, I>, RHDLError>
+where
+    T: Synchronous,
+{
+    let desc = uut.descriptor("top".into())?;
+    Ok(RunCompiled {
+        sim: CompiledNetList::new(desc.netlist()?)?,
+        inputs,
+        time: 0,
+        output_kind: desc.output_kind,
+        decode,
+    })
+}
+
+impl<T, I> RunCompiled<T, I>
+where
+    T: SynchronousIO,
+{
+    /// Evaluate the netlist for the given input, and return the
+    /// (undecoded) output bits.
+    pub fn eval(&mut self, input: (ClockReset, T::I)) -> Result<TypedBits, RHDLError> {
+        let (cr, i) = input;
+        let output = self.sim.step(&[&cr.bin(), &i.bin()])?;
+        Ok(TypedBits::new(output, self.output_kind))
+    }
+}
+
+impl<T, I> Iterator for RunCompiled<T, I>
+where
+    T: SynchronousIO,
+    I: Iterator<Item = TimedSample<(ClockReset, T::I)>>,
+{
+    type Item = TracedSample<(ClockReset, T::I), T::O>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let sample = self.inputs.next()?;
+        assert!(
+            sample.time >= self.time,
+            "input time must be non-decreasing"
+        );
+        self.time = sample.time;
+        let output = self
+            .eval(sample.value)
+            .unwrap_or_else(|err| panic!("Compiled simulation failed: {err}"));
+        let output = if output.bits().contains(&BitX::X) {
+            T::O::dont_care()
+        } else {
+            (self.decode)(&output)
+        };
+        Some(TracedSample {
+            time: sample.time,
+            input: sample.value,
+            output,
+            page: None,
+        })
+    }
+}
+
+/// Extension trait to provide a `run_compiled` method on synchronous circuits.
+pub trait RunCompiledExt<I>: Synchronous + Sized {
+    /// Runs the compiled netlist of the circuit with the given iterator of timed
+    /// inputs, using `decode` to convert the output bits back into an RHDL value.
+    ///
+    /// [Digital] can turn a value into bits, but has no way to build a value from
+    /// them, so the decoder must be written for the output type of the circuit.  If
+    /// the output implements [Decode] (like bit vectors of any width, and tuples and
+    /// arrays of them), pass [Decode::decode].  Outputs with unknown bits are not
+    /// decoded - they are returned as [Digital::dont_care].
+    fn run_compiled(
+        &self,
+        iter: I,
+        decode: fn(&TypedBits) -> Self::O,
+    ) -> Result<RunCompiled<Self, <I as IntoIterator>::IntoIter>, RHDLError>
+    where
+        I: IntoIterator;
+}
+
+impl<T, I> RunCompiledExt<I> for T
+where
+    T: Synchronous,
+    I: IntoIterator<Item = TimedSample<(ClockReset, <T as SynchronousIO>::I)>>,
+{
+    fn run_compiled(
+        &self,
+        iter: I,
+        decode: fn(&TypedBits) -> Self::O,
+    ) -> Result<RunCompiled<Self, <I as IntoIterator>::IntoIter>, RHDLError> {
+        run_compiled(self, iter.into_iter(), decode)
+    }
+}
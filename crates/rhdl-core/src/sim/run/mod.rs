@@ -2,5 +2,7 @@
 #![warn(missing_docs)]
 pub mod async_fn;
 pub mod asynchronous;
+pub mod compiled;
+pub mod netlist;
 pub mod sync_fn;
 pub mod synchronous;
@@ -0,0 +1,165 @@
+//! Extension traits and types to run the netlist of a circuit in simulation.
+//!
+//! These runners take the same iterators of timed inputs as
+//! [run](crate::sim::run::asynchronous::RunExt::run) and
+//! [run](crate::sim::run::synchronous::RunSynchronousExt::run), but instead
+//! of calling the `sim` method of the circuit, they build the optimized
+//! netlist for the circuit and execute it with a [NetListVM].
+use crate::{
+    BitX, Circuit, CircuitIO, ClockReset, Digital, Kind, RHDLError, Synchronous, SynchronousIO,
+    TimedSample, TypedBits, ntl::vm::NetListVM,
+};
+
+/// A sample produced by running a netlist.
+///
+/// Because the netlist works at the level of bits, the output
+/// is reported as [TypedBits] of the output kind of the circuit.
+#[derive(Clone, Debug, PartialEq, Hash)]
+pub struct NetListSample<T: Digital> {
+    /// The time of the sample
+    pub time: u64,
+    /// The input value at this time
+    pub input: T,
+    /// The output of the netlist at this time
+    pub output: TypedBits,
+}
+
+/// An iterator that runs the netlist of a circuit given an iterator of timed inputs.
+#[must_use = "To run the simulation, you must exhaust the iterator"]
+#[derive(Clone)]
+pub struct RunNetList<T: Digital, I> {
+    vm: NetListVM,
+    inputs: I,
+    time: u64,
+    output_kind: Kind,
+    args: fn(T) -> Vec<Box<[BitX]>>,
+}
+
+fn synchronous_args<I: Digital>((cr, i): (ClockReset, I)) -> Vec<Box<[BitX]>> {
+    vec![cr.bin(), i.bin()]
+}
+
+fn asynchronous_args<I: Digital>(i: I) -> Vec<Box<[BitX]>> {
+    vec![i.bin()]
+}
+
+/// Runs the netlist of a synchronous circuit with the given iterator of timed inputs.
+pub fn run_synchronous_netlist<T, I>(
+    uut: &T,
+    inputs: I,
+) -> Result<RunNetList<(ClockReset, <T as SynchronousIO>::I), I>, RHDLError>
+where
+    T: Synchronous,
+{
+    let desc = uut.descriptor("top".into())?;
+    Ok(RunNetList {
+        vm: NetListVM::new(desc.netlist()?)?,
+        inputs,
+        time: 0,
+        output_kind: desc.output_kind,
+        args: synchronous_args::<<T as SynchronousIO>::I>,
+    })
+}
+
+/// Runs the netlist of an asynchronous circuit with the given iterator of timed inputs.
+pub fn run_netlist<T, I>(
+    uut: &T,
+    inputs: I,
+) -> Result<RunNetList<<T as CircuitIO>::I, I>, RHDLError>
+where
+    T: Circuit,
+{
+    let desc = uut.descriptor("top".into())?;
+    Ok(RunNetList {
+        vm: NetListVM::new(desc.netlist()?)?,
+        inputs,
+        time: 0,
+        output_kind: desc.output_kind,
+        args: asynchronous_args::<<T as CircuitIO>::I>,
+    })
+}
+
+impl<T, I> RunNetList<T, I>
+where
+    T: Digital,
+{
+    pub(crate) fn eval(&mut self, input: T) -> Result<TypedBits, RHDLError> {
+        let args = (self.args)(input);
+        let args = args.iter().map(|x| &x[..]).collect::<Vec<_>>();
+        let output = self.vm.step(&args)?;
+        Ok(TypedBits::new(output, self.output_kind))
+    }
+}
+
+impl<T, I> Iterator for RunNetList<T, I>
+where
+    T: Digital,
+    I: Iterator<Item = TimedSample<T>>,
+{
+    type Item = Result<NetListSample<T>, RHDLError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let sample = self.inputs.next()?;
+        assert!(
+            sample.time >= self.time,
+            "input time must be non-decreasing"
+        );
+        self.time = sample.time;
+        Some(self.eval(sample.value).map(|output| NetListSample {
+            time: sample.time,
+            input: sample.value,
+            output,
+        }))
+    }
+}
+
+/// The iterator returned by running the netlist of a synchronous circuit.
+pub type RunSynchronousNetList<T, I> = RunNetList<(ClockReset, <T as SynchronousIO>::I), I>;
+
+/// Extension trait to provide a `run_netlist` method on synchronous circuits.
+pub trait RunSynchronousNetListExt<I>: Synchronous + Sized {
+    /// Runs the netlist of the circuit with the given iterator of timed inputs.
+    fn run_netlist(
+        &self,
+        iter: I,
+    ) -> Result<RunSynchronousNetList<Self, <I as IntoIterator>::IntoIter>, RHDLError>
+    where
+        I: IntoIterator;
+}
+
+impl<T, I> RunSynchronousNetListExt<I> for T
+where
+    T: Synchronous,
+    I: IntoIterator<Item = TimedSample<(ClockReset, <T as SynchronousIO>::I)>>,
+{
+    fn run_netlist(
+        &self,
+        iter: I,
+    ) -> Result<RunSynchronousNetList<Self, <I as IntoIterator>::IntoIter>, RHDLError> {
+        run_synchronous_netlist(self, iter.into_iter())
+    }
+}
+
+/// Extension trait to provide a `run_netlist` method on asynchronous circuits.
+pub trait RunNetListExt<I>: Circuit + Sized {
+    /// Runs the netlist of the circuit with the given iterator of timed inputs.
+    fn run_netlist(
+        &self,
+        iter: I,
+    ) -> Result<RunNetList<<Self as CircuitIO>::I, <I as IntoIterator>::IntoIter>, RHDLError>
+    where
+        I: IntoIterator;
+}
+
+impl<T, I> RunNetListExt<I> for T
+where
+    T: Circuit,
+    I: IntoIterator<Item = TimedSample<<T as CircuitIO>::I>>,
+{
+    fn run_netlist(
+        &self,
+        iter: I,
+    ) -> Result<RunNetList<<Self as CircuitIO>::I, <I as IntoIterator>::IntoIter>, RHDLError> {
+        run_netlist(self, iter.into_iter())
+    }
+}
@@ -5,11 +5,12 @@ use rhdl_vlog::{self as vlog, LitVerilog, maybe_decl_reg, maybe_decl_wire};
 use syn::parse_quote;
 
 use crate::{
-    Circuit, CircuitIO, Digital, RHDLError, TimedSample, sim::test_module::TestModule,
+    Circuit, CircuitIO, Digital, RHDLError, TimedSample,
+    sim::{run::netlist::run_netlist, test_module::TestModule},
     trace::trace_sample::TracedSample,
 };
 
-use super::TestBenchOptions;
+use super::{TestBenchOptions, check_netlist_samples};
 
 /// A test bench for asynchronous circuits
 #[derive(Clone)]
@@ -190,4 +191,19 @@ impl<I: Digital, O: Digital> TestBench<I, O> {
         let module = uut.descriptor("uut".into())?.netlist()?.as_vlog("dut")?;
         self.build_test_module(&module.modules, options)
     }
+    /// Simulate the netlist of the given asynchronous UUT in process, and
+    /// check that it reproduces the outputs recorded in this testbench.
+    pub fn netlist_sim<T>(&self, uut: &T, options: &TestBenchOptions) -> Result<(), RHDLError>
+    where
+        T: Circuit,
+        T: CircuitIO<I = I, O = O>,
+    {
+        let mut run = run_netlist(uut, std::iter::empty::<TimedSample<I>>())?;
+        let samples = self.samples.iter().map(|sample| {
+            let (i, o) = sample.value;
+            let actual = run.eval(i)?;
+            Ok((sample.time, o.typed_bits(), actual))
+        });
+        check_netlist_samples(samples, options)
+    }
 }
@@ -1,4 +1,6 @@
 //! Trace containers that generate Verilog testbenches
+use crate::{RHDLError, TypedBits};
+
 pub mod asynchronous;
 pub mod kernel;
 pub mod synchronous;
@@ -42,3 +44,31 @@ impl Default for TestBenchOptions {
         }
     }
 }
+
+/// Check the outputs of a netlist simulation against the expected
+/// outputs of a testbench.  The samples are checked using the same
+/// rules as the generated Verilog testbenches, i.e., a sample is only
+/// checked if the next sample is more than the hold time after it,
+/// and the first `skip_first_cases` samples are ignored.
+fn check_netlist_samples(
+    samples: impl IntoIterator<Item = Result<(u64, TypedBits, TypedBits), RHDLError>>,
+    options: &TestBenchOptions,
+) -> Result<(), RHDLError> {
+    let mut previous: Option<(u64, TypedBits, TypedBits)> = None;
+    for (test_case_counter, sample) in samples.into_iter().enumerate() {
+        let sample = sample?;
+        if let Some((time, expected, actual)) = previous.take()
+            && sample.0.saturating_sub(time) > options.hold_time
+            && test_case_counter >= options.skip_first_cases
+            && expected != actual
+        {
+            return Err(RHDLError::NetListVerificationError {
+                time,
+                expected,
+                actual,
+            });
+        }
+        previous = Some(sample);
+    }
+    Ok(())
+}
@@ -6,10 +6,15 @@ use syn::parse_quote;
 
 use crate::{
     ClockReset, Digital, RHDLError, Synchronous, SynchronousIO, TimedSample, clock_reset,
-    sim::test_module::TestModule, trace::trace_sample::TracedSample,
+    sim::{
+        cosim::{VerilogProcess, typed_bits_diff},
+        run::netlist::run_synchronous_netlist,
+        test_module::TestModule,
+    },
+    trace::trace_sample::TracedSample,
 };
 
-use super::TestBenchOptions;
+use super::{TestBenchOptions, check_netlist_samples};
 
 /// A test bench for synchronous circuits
 ///
@@ -222,4 +227,61 @@ impl<I: Digital, O: Digital> SynchronousTestBench<I, O> {
         let hdl = crate::ntl::hdl::build_hdl("dut", ntl)?;
         self.build_test_module(&hdl.modules, options)
     }
+    /// Co-simulate the Verilog for the given synchronous UUT in lock-step with
+    /// this testbench.
+    ///
+    /// Unlike [SynchronousTestBench::rtl], which generates a batch testbench,
+    /// this runs the Verilog in `iverilog` one sample at a time, and reports
+    /// the first clock cycle in which the outputs disagree, with a diff of the
+    /// fields that differ.  Samples are checked using the same rules as the
+    /// generated testbenches.
+    pub fn cosim<T>(&self, uut: &T, options: &TestBenchOptions) -> Result<(), RHDLError>
+    where
+        T: Synchronous,
+        T: SynchronousIO<I = I, O = O>,
+    {
+        let desc = uut.descriptor("uut".into())?;
+        let mut process = VerilogProcess::spawn(&desc.hdl()?.modules, I::BITS, O::static_kind())?;
+        let mut cycle = 0;
+        let mut previous_clock = false;
+        for (test_case_counter, window) in self.samples.windows(2).enumerate() {
+            let (sample, next) = (&window[0], &window[1]);
+            let (cr, i, o) = sample.value;
+            if cr.clock.raw() && !previous_clock {
+                cycle += 1;
+            }
+            previous_clock = cr.clock.raw();
+            let actual = process.step(cr, &i.bin())?;
+            let expected = o.typed_bits();
+            if next.time.saturating_sub(sample.time) > options.hold_time
+                && test_case_counter >= options.skip_first_cases
+                && expected != actual
+            {
+                return Err(RHDLError::CosimMismatch {
+                    cycle,
+                    time: sample.time,
+                    diff: typed_bits_diff(&expected, &actual),
+                    expected,
+                    actual,
+                });
+            }
+        }
+        Ok(())
+    }
+    /// Simulate the netlist of the given synchronous UUT in process, and
+    /// check that it reproduces the outputs recorded in this testbench.
+    pub fn netlist_sim<T>(&self, uut: &T, options: &TestBenchOptions) -> Result<(), RHDLError>
+    where
+        T: Synchronous,
+        T: SynchronousIO<I = I, O = O>,
+    {
+        let mut run =
+            run_synchronous_netlist(uut, std::iter::empty::<TimedSample<(ClockReset, I)>>())?;
+        let samples = self.samples.iter().map(|sample| {
+            let (cr, i, o) = sample.value;
+            let actual = run.eval((cr, i))?;
+            Ok((sample.time, o.typed_bits(), actual))
+        });
+        check_netlist_samples(samples, options)
+    }
 }
@@ -105,6 +105,22 @@ pub(crate) fn format_as_label(t: &TypedBits) -> Option<String> {
     (t.len() != 1).then(|| format_as_label_inner(t))?
 }
 
+// Format a fixed point value with `frac` fractional bits as a real number.
+pub(crate) fn format_as_fixed_label(t: &TypedBits, frac: usize) -> Option<String> {
+    let mut val = 0.0_f64;
+    for (ndx, bit) in t.bits().iter().enumerate() {
+        match bit {
+            BitX::One => val += 2.0_f64.powi(ndx as i32 - frac as i32),
+            BitX::Zero => {}
+            _ => return None,
+        }
+    }
+    if t.kind().is_signed() && t.bits().last() == Some(&BitX::One) {
+        val -= 2.0_f64.powi(t.len() as i32 - frac as i32);
+    }
+    Some(format!("{val}"))
+}
+
 // Compute a map indicating which time series in the list
 // is a "parent" of this one.  The parent is defined as
 // a time series with a path that is a prefix of the current
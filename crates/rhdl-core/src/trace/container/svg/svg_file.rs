@@ -2,6 +2,8 @@
 
 use std::sync::{Arc, RwLock};
 
+use rhdl_trace_type::TraceType;
+
 use crate::{
     Digital, Kind, TypedBits,
     trace::{
@@ -12,7 +14,7 @@ use crate::{
                 bucket::bucketize,
                 color::compute_trace_color_from_path,
                 gap,
-                label::rewrite_trace_names_into_tree,
+                label::{format_as_fixed_label, rewrite_trace_names_into_tree},
                 layout::make_svg_document,
                 options::SvgOptions,
                 paths::{pretty_leaf_paths, try_path},
@@ -23,7 +25,7 @@ use crate::{
         trace_sample::TracedSample,
         trace_tree::TraceTree,
     },
-    types::path::Path,
+    types::path::{Path, sub_trace_type},
 };
 
 type TimeAndSample = (u64, TypedBits);
@@ -77,9 +79,15 @@ impl SvgFile {
         tail: u64,
         trace_id: TraceId,
         kind: Kind,
+        trace_type: &TraceType,
         path: &Path,
     ) -> Box<[Region]> {
         let trace_color = compute_trace_color_from_path(kind, path).unwrap_or_default();
+        // Fixed point values are labelled with their real value
+        let frac = match sub_trace_type(trace_type.clone(), path) {
+            Ok(TraceType::Fixed(_, frac) | TraceType::SignedFixed(_, frac)) => Some(frac),
+            _ => None,
+        };
         let sliced = self
             .inner
             .get(&trace_id)
@@ -88,7 +96,13 @@ impl SvgFile {
             .map(|(time, value)| (*time, try_path(value, path)));
         bucketize(tail, sliced, trace_color)
             .iter()
-            .map(|bucket| bucket.into())
+            .map(|bucket| {
+                let region = Region::from(bucket);
+                match frac {
+                    Some(frac) => region.with_tag(format_as_fixed_label(&bucket.data, frac)),
+                    None => region,
+                }
+            })
             .collect()
     }
     fn trace_out(&self, tail: u64, name: &str, trace_id: TraceId, waves: &mut Vec<Waveform>) {
@@ -100,11 +114,12 @@ impl SvgFile {
             return;
         };
         let kind = details.kind;
+        let trace_type = &details.trace_type;
         waves.extend(
             pretty_leaf_paths(kind, Path::default())
                 .into_iter()
                 .map(|path| {
-                    let data = self.build_time_trace(tail, trace_id, kind, &path);
+                    let data = self.build_time_trace(tail, trace_id, kind, trace_type, &path);
                     Waveform {
                         label: format!("{name}{path:?}"),
                         hint: Default::default(),
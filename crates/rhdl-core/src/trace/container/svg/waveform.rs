@@ -43,6 +43,13 @@ impl From<&Bucket> for Region {
     }
 }
 
+impl Region {
+    /// Replace the label shown for the region.
+    pub(crate) fn with_tag(self, tag: Option<String>) -> Self {
+        Self { tag, ..self }
+    }
+}
+
 /// A waveform to be rendered in the SVG output.
 /// Consists of a label for the waveform, a hint (tooltip containing
 /// additional information), and the actual data regions.  Each
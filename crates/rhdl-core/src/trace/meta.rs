@@ -1,5 +1,5 @@
 //! Metadata about a traced value
-use rhdl_trace_type::RTT;
+use rhdl_trace_type::{RTT, TraceType};
 
 use crate::{Kind, trace::trace_tree::TraceTree};
 
@@ -15,6 +15,8 @@ pub struct TraceDetails {
     pub width: usize,
     /// The [Kind](crate::Kind) of this value.
     pub kind: Kind,
+    /// The [TraceType] of this value, used to render it in trace files.
+    pub trace_type: TraceType,
 }
 
 /// The trace details stores an ID for each trace location.  
@@ -53,7 +55,7 @@ impl TraceMetadata {
                         [&["top"], &details.path[..]].concat().join("."),
                         details.key
                     );
-                    (name, details.kind.into())
+                    (name, details.trace_type.clone())
                 })
                 .collect(),
         )
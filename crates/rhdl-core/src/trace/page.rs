@@ -88,6 +88,7 @@ impl TracePage {
                 key: key.as_string().to_string(),
                 width: (T::BITS as u32).max(1) as usize,
                 kind: value.kind(),
+                trace_type: T::static_trace_type(),
             };
             self.details.write().unwrap().insert(trace_id, details);
         }
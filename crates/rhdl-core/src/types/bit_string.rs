@@ -7,7 +7,7 @@ use crate::{
 use rhdl_vlog as vlog;
 
 /// A string of 3-value bits (0, 1, x) that may be signed or unsigned.
-#[derive(Clone, PartialEq, Hash)]
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub enum BitString {
     /// A signed bit string
     Signed(Vec<BitX>),
@@ -1,6 +1,10 @@
 use std::marker::PhantomData;
 
-use rhdl_bits::{BitWidth, Bits, SignedBits};
+use rhdl_bits::{
+    BitWidth, Bits, SignedBits, SignedWideBits, WideBitWidth, WideBits,
+    fixed::{Fixed, SignedFixed},
+};
+use rhdl_trace_type as rtt;
 
 use crate::{
     DiscriminantAlignment, DiscriminantType, Kind, TypedBits,
@@ -60,6 +64,12 @@ pub trait Digital: Copy + PartialEq + Sized + Clone + 'static {
     const BITS: usize;
     /// Returns the [Kind] (run time type descriptor) of the value as a static method
     fn static_kind() -> Kind;
+    /// Returns the trace type used to render the value in trace files.  This is
+    /// derived from the [Kind] unless the type (like a fixed point value) needs
+    /// to be displayed differently from its bits.
+    fn static_trace_type() -> rtt::TraceType {
+        Self::static_kind().into()
+    }
     /// Returns the number of bits needed to represent the value.
     fn bits() -> usize {
         Self::BITS
@@ -113,6 +123,21 @@ impl<T: Digital> Digital for Option<T> {
             },
         )
     }
+    fn static_trace_type() -> rtt::TraceType {
+        rtt::make_enum(
+            &format!("Option::<{}>", std::any::type_name::<T>()),
+            vec![
+                rtt::make_variant("None", rtt::TraceType::Empty, 0),
+                rtt::make_variant("Some", rtt::make_tuple([T::static_trace_type()].into()), 1),
+            ],
+            Kind::make_discriminant_layout(
+                1,
+                DiscriminantAlignment::Msb,
+                DiscriminantType::Unsigned,
+            )
+            .into(),
+        )
+    }
     fn bin(self) -> Box<[BitX]> {
         self.kind().pad(match self {
             Self::None => vec![BitX::Zero],
@@ -160,6 +185,25 @@ impl<O: Digital, E: Digital> Digital for Result<O, E> {
             ),
         )
     }
+    fn static_trace_type() -> rtt::TraceType {
+        rtt::make_enum(
+            &format!(
+                "Result::<{}, {}>",
+                std::any::type_name::<O>(),
+                std::any::type_name::<E>()
+            ),
+            vec![
+                rtt::make_variant("Err", rtt::make_tuple([E::static_trace_type()].into()), 0),
+                rtt::make_variant("Ok", rtt::make_tuple([O::static_trace_type()].into()), 1),
+            ],
+            Kind::make_discriminant_layout(
+                1,
+                DiscriminantAlignment::Msb,
+                DiscriminantType::Unsigned,
+            )
+            .into(),
+        )
+    }
     fn bin(self) -> Box<[BitX]> {
         self.kind().pad(match self {
             Self::Ok(o) => {
@@ -290,6 +334,70 @@ where
     }
 }
 
+impl<const N: usize> Digital for WideBits<N>
+where
+    rhdl_bits::W<N>: WideBitWidth,
+{
+    const BITS: usize = N;
+    fn static_kind() -> Kind {
+        Kind::make_bits(N)
+    }
+    fn bin(self) -> Box<[BitX]> {
+        bitx_vec(&self.to_bools())
+    }
+    fn dont_care() -> Self {
+        Self::default()
+    }
+}
+
+impl<const N: usize> Digital for SignedWideBits<N>
+where
+    rhdl_bits::W<N>: WideBitWidth,
+{
+    const BITS: usize = N;
+    fn static_kind() -> Kind {
+        Kind::make_signed(N)
+    }
+    fn bin(self) -> Box<[BitX]> {
+        bitx_vec(&self.as_unsigned().to_bools())
+    }
+    fn dont_care() -> Self {
+        Self::default()
+    }
+}
+
+impl<const I: usize, const F: usize> Digital for Fixed<I, F> {
+    const BITS: usize = I + F;
+    fn static_kind() -> Kind {
+        Kind::make_bits(I + F)
+    }
+    fn static_trace_type() -> rtt::TraceType {
+        rtt::TraceType::Fixed(I, F)
+    }
+    fn bin(self) -> Box<[BitX]> {
+        bitx_vec(&self.to_bools())
+    }
+    fn dont_care() -> Self {
+        Self::default()
+    }
+}
+
+impl<const I: usize, const F: usize> Digital for SignedFixed<I, F> {
+    const BITS: usize = I + F;
+    fn static_kind() -> Kind {
+        Kind::make_signed(I + F)
+    }
+    fn static_trace_type() -> rtt::TraceType {
+        rtt::TraceType::SignedFixed(I, F)
+    }
+    fn bin(self) -> Box<[BitX]> {
+        bitx_vec(&self.to_bools())
+    }
+    fn dont_care() -> Self {
+        Self::default()
+    }
+}
+
 // Use the seq! macro to generate an implementation for a tuple of size N
 macro_rules! impl_tuple_for_digital {
     ($size: expr) => {
@@ -306,6 +414,11 @@ macro_rules! impl_tuple_for_digital {
                         #(T~N::static_kind(),)*
                         ].into())
                 }
+                fn static_trace_type() -> rtt::TraceType {
+                    rtt::make_tuple(vec![
+                        #(T~N::static_trace_type(),)*
+                        ].into())
+                }
                 fn bin(self) -> Box<[BitX]> {
                     let mut v = Vec::with_capacity(Self::BITS);
                     #(
@@ -367,6 +480,9 @@ impl<T: Digital, const N: usize> Digital for [T; N] {
     fn static_kind() -> Kind {
         Kind::make_array(T::static_kind(), N)
     }
+    fn static_trace_type() -> rtt::TraceType {
+        rtt::make_array(T::static_trace_type(), N)
+    }
     fn bin(self) -> Box<[BitX]> {
         let mut v = Vec::with_capacity(Self::BITS);
         for x in self.iter() {
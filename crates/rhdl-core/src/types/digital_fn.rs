@@ -2,6 +2,7 @@
 #![allow(clippy::type_complexity)]
 
 use rhdl_bits::BitWidth;
+use rhdl_bits::fixed::{FixedPoint, Overflow, Rounding};
 
 pub use crate::{Digital, Kind, kernel::KernelFnKind};
 
@@ -227,6 +228,82 @@ where
     F::describe()
 }
 
+/// A single character of a kernel method name.
+///
+/// The name of a kernel method is spelled out as a tuple of these,
+/// so that `is_full` becomes `(MethodChar<'i'>, MethodChar<'s'>, ..)`.
+/// This tuple is used as the `NAME` parameter of [KernelMethod], which
+/// keeps methods with different names distinct at the type level.
+pub struct MethodChar<const C: char>;
+
+/// A synthesizable method on a [Digital] type.
+///
+/// This trait is implemented by the `#[kernel]` attribute for
+/// each method in an inherent `impl` block.  The `NAME` parameter
+/// spells out the method name (see [MethodChar]), so that a call of the
+/// form `x.name(..)` is resolved as `<X as KernelMethod<name>>`, where
+/// `X` is the type of `x`.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has no `#[kernel]` method with this name",
+    note = "methods called from a kernel must be defined in an `impl` block marked with `#[kernel]`"
+)]
+pub trait KernelMethod<NAME> {
+    /// The signature of the method, with the receiver as the first argument.
+    fn signature() -> DigitalSignature;
+    /// The kernel for the method.
+    fn kernel_fn() -> Option<KernelFnKind>;
+}
+
+/// Marker type used to give each kernel method a unique [std::any::TypeId].
+pub struct MethodMarker<R, NAME>(std::marker::PhantomData<(R, NAME)>);
+
+/// Records the receiver type of a kernel method call.
+///
+/// Values of this type are never constructed.  It only exists
+/// so that the receiver type can be recovered by [resolve_methods].
+pub struct MethodProbe<R, NAME>(std::marker::PhantomData<(R, NAME)>);
+
+/// Record the receiver type of a call to the kernel method `NAME`.
+pub fn probe_method<NAME, R: KernelMethod<NAME>>(_receiver: &R) -> MethodProbe<R, NAME> {
+    MethodProbe(std::marker::PhantomData)
+}
+
+/// A type level list of [MethodProbe]s, built as nested pairs
+/// of the form `(((), p0), p1)`.
+pub trait MethodProbes {
+    /// Append the signature and kernel of each probed method to `out`.
+    fn resolve(out: &mut Vec<(DigitalSignature, Option<KernelFnKind>)>);
+}
+
+impl MethodProbes for () {
+    fn resolve(_out: &mut Vec<(DigitalSignature, Option<KernelFnKind>)>) {}
+}
+
+impl<T, R, NAME> MethodProbes for (T, Option<MethodProbe<R, NAME>>)
+where
+    T: MethodProbes,
+    R: KernelMethod<NAME>,
+{
+    fn resolve(out: &mut Vec<(DigitalSignature, Option<KernelFnKind>)>) {
+        T::resolve(out);
+        out.push((R::signature(), R::kernel_fn()));
+    }
+}
+
+/// Resolve the kernel methods called by a kernel.
+///
+/// The `#[kernel]` attribute generates a closure that mirrors the
+/// body of the kernel, and returns a list of [MethodProbe]s for the
+/// method calls it contains.  The closure is never called.  The types
+/// it returns are enough to find the signature and kernel of each method.
+pub fn resolve_methods<T: MethodProbes>(
+    _shadow: impl FnOnce() -> T,
+) -> Vec<(DigitalSignature, Option<KernelFnKind>)> {
+    let mut out = vec![];
+    T::resolve(&mut out);
+    out
+}
+
 impl<const N: usize> DigitalFn for rhdl_bits::Bits<N>
 where
     rhdl_bits::W<N>: BitWidth,
@@ -262,3 +339,38 @@ where
         Some(KernelFnKind::SignedBitsConstructor(N))
     }
 }
+
+impl<const N: usize> DigitalFn for rhdl_bits::wide_bits<N>
+where
+    rhdl_bits::W<N>: rhdl_bits::WideBitWidth,
+{
+    fn kernel_fn() -> Option<KernelFnKind> {
+        Some(KernelFnKind::BitConstructor(N))
+    }
+}
+
+impl<const N: usize> DigitalFn for rhdl_bits::signed_wide<N>
+where
+    rhdl_bits::W<N>: rhdl_bits::WideBitWidth,
+{
+    fn kernel_fn() -> Option<KernelFnKind> {
+        Some(KernelFnKind::SignedBitsConstructor(N))
+    }
+}
+
+impl<R, O, T, S> DigitalFn for rhdl_bits::fixed::quantize<R, O, T, S>
+where
+    R: Rounding,
+    O: Overflow,
+    T: FixedPoint,
+    S: FixedPoint,
+{
+    fn kernel_fn() -> Option<KernelFnKind> {
+        Some(KernelFnKind::Quantize {
+            from: T::FORMAT,
+            to: S::FORMAT,
+            rounding: R::MODE,
+            overflow: O::MODE,
+        })
+    }
+}
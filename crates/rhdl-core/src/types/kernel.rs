@@ -5,6 +5,8 @@
 //! built in.  For example, when a user kernel calls `bits(n)`, RHDL generates a [Kernel] of kind
 //! [KernelFnKind::BitConstructor] to represent that operation.  These are translated into synthesizable
 //! code later after type inference is completed and the relevant widths and kinds are known.
+use rhdl_bits::fixed::{FixedFormat, OverflowMode, RoundingMode};
+
 use crate::{
     Color, TypedBits,
     ast::ast_impl::{self, WrapOp},
@@ -30,6 +32,17 @@ pub enum KernelFnKind {
     /// A wrap operation for `Result` or `Option`.
     /// E.g., `Ok(x)` or `Some(x)`.
     Wrap(WrapOp),
+    /// A fixed point quantization (e.g., `quantize::<HalfUp, Saturate, T, S>(x)`).
+    Quantize {
+        /// The format of the input value
+        from: FixedFormat,
+        /// The format of the output value
+        to: FixedFormat,
+        /// How dropped fractional bits are rounded
+        rounding: RoundingMode,
+        /// How dropped integer bits are handled
+        overflow: OverflowMode,
+    },
 }
 
 impl std::fmt::Debug for KernelFnKind {
@@ -57,6 +70,12 @@ impl std::fmt::Debug for KernelFnKind {
                 write!(f, "signal constructor {color:?}")
             }
             KernelFnKind::Wrap(op) => write!(f, "wrap {op:?}"),
+            KernelFnKind::Quantize {
+                from,
+                to,
+                rounding,
+                overflow,
+            } => write!(f, "quantize {from:?} -> {to:?} {rounding:?} {overflow:?}"),
         }
     }
 }
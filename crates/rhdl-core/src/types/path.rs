@@ -57,6 +57,13 @@ pub enum PathError {
     EnumPayloadNotFound { name: Intern<String>, kind: Kind },
     #[error("Enum payload not valid for non-enum type {kind:?}")]
     EnumPayloadNotValid { kind: Kind },
+    #[error("Enum variant {name} payload not found for {trace:?}")]
+    EnumPayloadNotFoundTrace {
+        name: Intern<String>,
+        trace: TraceType,
+    },
+    #[error("Enum payload not valid for non-enum type {trace:?}")]
+    EnumPayloadNotValidTrace { trace: TraceType },
     #[error("Enum payload not found for discriminant {disc} in {kind:?}")]
     EnumPayloadByValueNotFound { disc: i64, kind: Kind },
     #[error("Enum payload not valid for non-enum type {kind:?}")]
@@ -407,6 +414,18 @@ pub fn sub_trace_type(trace: TraceType, path: &Path) -> Result<TraceType> {
                     }));
                 }
             },
+            PathElement::EnumPayload(name) => match &trace {
+                TraceType::Enum(enumerate) => {
+                    let Some(variant) = enumerate.variants.iter().find(|v| v.name == **name) else {
+                        return Err(rhdl_error(PathError::EnumPayloadNotFoundTrace {
+                            name: *name,
+                            trace,
+                        }));
+                    };
+                    trace = variant.ty.clone();
+                }
+                _ => return Err(rhdl_error(PathError::EnumPayloadNotValidTrace { trace })),
+            },
             _ => {
                 return Err(rhdl_error(PathError::UnsupportedPathTypeForTrace {
                     path: path.clone(),
@@ -32,7 +32,7 @@ impl Reset {
 ///
 /// This is not a synthesizable function.  It's for testing.
 #[must_use]
-pub fn reset(b: bool) -> Reset {
+pub const fn reset(b: bool) -> Reset {
     Reset(b)
 }
 
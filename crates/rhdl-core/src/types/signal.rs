@@ -1,4 +1,5 @@
 use rhdl_bits::{BitWidth, Bits};
+use rhdl_trace_type as rtt;
 
 use crate::{BitX, Digital, Domain, Kind, Timed};
 
@@ -48,6 +49,9 @@ impl<T: Digital, C: Domain> Digital for Signal<T, C> {
     fn static_kind() -> Kind {
         Kind::make_signal(T::static_kind(), C::color())
     }
+    fn static_trace_type() -> rtt::TraceType {
+        rtt::make_signal(T::static_trace_type(), C::color().into())
+    }
     fn bin(self) -> Box<[BitX]> {
         self.val.bin()
     }
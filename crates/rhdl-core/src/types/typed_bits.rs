@@ -5,10 +5,10 @@ use internment::Intern;
 
 use crate::Color;
 use crate::ast::ast_impl::WrapOp;
-use crate::bitx::dyn_bit_manip::bits_shr_signed;
 use crate::bitx::dyn_bit_manip::{
     bit_neg, bit_not, bits_and, bits_or, bits_shl, bits_shr, bits_xor, full_add, full_sub,
 };
+use crate::bitx::dyn_bit_manip::{bits_shr_signed, to_bigint, to_biguint};
 use crate::bitx::{BitX, bitx_string};
 use crate::error::{RHDLError, rhdl_error};
 use crate::{
@@ -26,7 +26,7 @@ type Result<T> = std::result::Result<T, RHDLError>;
 
 /// A struct that holds a [Kind](crate::types::kind::Kind) and a bit representation
 /// of a value that conforms to that kind.
-#[derive(Clone, PartialEq, Hash)]
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub struct TypedBits {
     bits: Vec<BitX>,
     kind: Kind,
@@ -782,6 +782,14 @@ impl std::cmp::PartialOrd for TypedBits {
         } else {
             self.kind.is_unsigned()
         };
+        // Values too wide for the native integer types are compared as big integers
+        if self.bits.len() > 128 {
+            return if is_unsigned {
+                to_biguint(&self.bits)?.partial_cmp(&to_biguint(&other.bits)?)
+            } else {
+                to_bigint(&self.bits)?.partial_cmp(&to_bigint(&other.bits)?)
+            };
+        }
         if is_unsigned {
             let mut a_as_u128 = 0;
             let mut b_as_u128 = 0;
@@ -927,7 +935,12 @@ fn write_bits(bits: &[BitX], f: &mut std::fmt::Formatter<'_>) -> std::fmt::Resul
             }
         );
     }
-    // We know that the bits array will fit into a u128.
+    if bits.len() > 128 {
+        return match to_biguint(bits) {
+            Some(val) => write!(f, "{:x}_b{}", val, bits.len()),
+            None => write!(f, "{}", bitx_string(bits)),
+        };
+    }
     let val = bits
         .iter()
         .rev()
@@ -947,7 +960,12 @@ fn write_signed(bits: &[BitX], f: &mut std::fmt::Formatter<'_>) -> std::fmt::Res
             }
         );
     }
-    // We know that the bits array will fit into a i128.
+    if bits.len() > 128 {
+        return match to_bigint(bits) {
+            Some(val) => write!(f, "{}_s{}", val, bits.len()),
+            None => write!(f, "{}", bitx_string(bits)),
+        };
+    }
     let bit_len = bits.len();
     let sign_bit = bits.last().cloned().unwrap_or(BitX::Zero);
     let val = std::iter::repeat_n(&sign_bit, 128 - bit_len)
@@ -992,6 +1010,21 @@ mod tests {
         assert_eq!(c, b8(238).typed_bits());
     }
 
+    #[test]
+    fn test_typed_bits_wide_values() {
+        use rhdl_bits::{SignedWideBits, WideBits};
+        let a = (WideBits::<200>::from(1) << 150).typed_bits();
+        let b = WideBits::<200>::from(u128::MAX).typed_bits();
+        assert!(b < a);
+        assert_eq!(format!("{a:?}"), format!("4{}_b200", "0".repeat(37)));
+        let c = (a.clone() + b.clone()).unwrap();
+        assert!(c > a);
+        let d = SignedWideBits::<200>::from(-1).typed_bits();
+        let e = SignedWideBits::<200>::from(1).typed_bits();
+        assert!(d < e);
+        assert_eq!(format!("{d:?}"), "-1_s200");
+    }
+
     #[test]
     #[allow(dead_code)]
     #[allow(clippy::just_underscores_and_digits)]
@@ -18,11 +18,11 @@ use rhdl_fpga::{
 #[rhdl(dq_no_prefix)]
 struct TestFixture {
     req_source: SourceFromFn<b32>,
-    controller: ReadController,
-    endpoint: ReadEndpoint,
+    controller: ReadController<32, 32>,
+    endpoint: ReadEndpoint<32, 32>,
     req_sink: SinkFromFn<b32>,
-    reply_source: SourceFromFn<ReadResult>,
-    reply_sink: SinkFromFn<ReadResult>,
+    reply_source: SourceFromFn<ReadResult<32>>,
+    reply_sink: SinkFromFn<ReadResult<32>>,
 }
 
 impl SynchronousIO for TestFixture {
@@ -56,7 +56,7 @@ fn main() -> Result<(), RHDLError> {
     let rng = XorShift128::default().map(|x| bits(x as u128));
     let address_sink = rng.clone();
     let address = stalling(rng.clone(), 0.23);
-    let reply = rng.clone().map(ReadResult::Ok);
+    let reply = rng.clone().map(Ok);
     let reply_sink = reply.clone();
     let reply = stalling(reply, 0.23);
     let uut = TestFixture {
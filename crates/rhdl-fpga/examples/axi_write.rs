@@ -17,10 +17,10 @@ use rhdl_fpga::{
 #[derive(Clone, Synchronous, SynchronousDQ)]
 #[rhdl(dq_no_prefix)]
 struct TestFixture {
-    req_source: SourceFromFn<WriteCommand>,
-    controller: WriteController,
-    endpoint: WriteEndpoint,
-    req_sink: SinkFromFn<WriteCommand>,
+    req_source: SourceFromFn<WriteCommand<32, 4, 32>>,
+    controller: WriteController<32, 4, 32>,
+    endpoint: WriteEndpoint<32, 4, 32>,
+    req_sink: SinkFromFn<WriteCommand<32, 4, 32>>,
     reply_source: SourceFromFn<WriteResult>,
     reply_sink: SinkFromFn<WriteResult>,
 }
@@ -47,7 +47,7 @@ pub fn kernel(_cr: ClockReset, _i: (), q: Q) -> ((), D) {
     ((), d)
 }
 
-fn write_commands() -> impl Iterator<Item = WriteCommand> {
+fn write_commands() -> impl Iterator<Item = WriteCommand<32, 4, 32>> {
     XorShift128::default().map(|x| {
         let addr = x >> 16;
         let data = x & 0xFFFF;
@@ -0,0 +1,104 @@
+//! Burst address generation
+//!
+//!# Purpose
+//!
+//! Both ends of an AXI4 burst need to compute the address
+//! of each beat from the address command.  The [next_address]
+//! kernel does that for all three burst types:
+//!
+//! - `FIXED` - the address is the same for every beat.
+//! - `INCR` - the address is incremented by the beat size.  The
+//!   first beat may be unaligned, in which case the following
+//!   beats are aligned to the beat size.
+//! - `WRAP` - like `INCR`, but the address wraps at a boundary
+//!   equal to the total size of the burst, which is
+//!   `(len + 1) << size` bytes.  The start address must be
+//!   aligned to the beat size, and the burst length must be 2, 4, 8
+//!   or 16 beats.
+//!
+//! The reserved burst type is treated as `INCR`.
+use rhdl::prelude::*;
+
+use super::types::{burst_codes, BurstKind, BurstLen, BurstSize};
+
+#[kernel]
+/// Compute the address of the next beat of a burst
+///
+/// Given the address of the current beat, and the `len`, `size`
+/// and `burst` fields of the address command, returns the
+/// address of the following beat.
+pub fn next_address<const ADDR: usize>(
+    addr: Bits<ADDR>,
+    len: BurstLen,
+    size: BurstSize,
+    burst: BurstKind,
+) -> Bits<ADDR>
+where
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    let step = bits::<ADDR>(1) << size.resize::<ADDR>();
+    let incr = (addr & !(step - 1)) + step;
+    match burst {
+        burst_codes::FIXED => addr,
+        burst_codes::WRAP => {
+            let span = (len.resize::<ADDR>() + 1) << size.resize::<ADDR>();
+            let mask = span - 1;
+            (addr & !mask) | (incr & mask)
+        }
+        _ => incr,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn walk(start: u128, len: u128, size: u128, burst: BurstKind) -> Vec<u128> {
+        let mut addr = b32(start);
+        (0..=len)
+            .map(|_| {
+                let this = addr.raw();
+                addr = next_address::<32>(addr, bits(len), bits(size), burst);
+                this
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_incr_burst() {
+        assert_eq!(
+            walk(0x100, 3, 2, burst_codes::INCR),
+            vec![0x100, 0x104, 0x108, 0x10c]
+        );
+    }
+
+    #[test]
+    fn test_unaligned_incr_burst_aligns_after_first_beat() {
+        assert_eq!(
+            walk(0x102, 2, 2, burst_codes::INCR),
+            vec![0x102, 0x104, 0x108]
+        );
+    }
+
+    #[test]
+    fn test_fixed_burst() {
+        assert_eq!(
+            walk(0x40, 3, 3, burst_codes::FIXED),
+            vec![0x40, 0x40, 0x40, 0x40]
+        );
+    }
+
+    #[test]
+    fn test_wrap_burst() {
+        // A 4 beat wrapping burst of 4 byte words wraps on a 16 byte boundary
+        assert_eq!(
+            walk(0x38, 3, 2, burst_codes::WRAP),
+            vec![0x38, 0x3c, 0x30, 0x34]
+        );
+        // An 8 beat wrapping burst of 8 byte words wraps on a 64 byte boundary
+        assert_eq!(
+            walk(0x70, 7, 3, burst_codes::WRAP),
+            vec![0x70, 0x78, 0x40, 0x48, 0x50, 0x58, 0x60, 0x68]
+        );
+    }
+}
@@ -0,0 +1,502 @@
+//! AXI4 Protocol Checker
+//!
+//!# Purpose
+//!
+//! The [ProtocolChecker] is a non-synthesizable monitor for
+//! AXI4 busses, intended for use in simulation tests.  It is fed
+//! the MOSI and MISO signals of a bus once per clock cycle (for
+//! example, from the samples of a synchronous simulation), and
+//! reports the first [ProtocolViolation] it sees on each call.
+//!
+//! The checker verifies that:
+//!
+//! - Once `VALID` is asserted on a channel, it stays asserted, and the
+//!   payload stays stable, until `READY` is seen.
+//! - Address commands use a legal burst type, do not exceed the configured
+//!   maximum burst length, use a beat size no wider than the bus,
+//!   respect the length restrictions on `WRAP` and `FIXED` bursts, and
+//!   do not cross a 4KB boundary for `INCR` bursts.
+//! - `WLAST` and `RLAST` are asserted on exactly the last beat of each burst.
+//! - Write responses and read data only carry IDs of outstanding bursts.
+//!
+//! The checker does not attempt to verify data integrity.
+use std::collections::{BTreeMap, VecDeque};
+
+use rhdl::prelude::*;
+
+use crate::axi4lite::types::check_widths;
+
+use super::types::{burst_codes, AddrCommand, ReadMISO, ReadMOSI, WriteBeat, WriteMISO, WriteMOSI};
+
+/// The AXI4 channel on which a violation occurred
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    /// Write address channel
+    AW,
+    /// Write data channel
+    W,
+    /// Write response channel
+    B,
+    /// Read address channel
+    AR,
+    /// Read data channel
+    R,
+}
+
+/// A violation of the AXI4 protocol
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProtocolViolation {
+    /// `VALID` was deasserted before `READY` was seen
+    ValidDropped(Channel),
+    /// The payload changed while `VALID` was asserted and `READY` was not
+    PayloadChanged(Channel),
+    /// The reserved burst type was used
+    ReservedBurst(Channel),
+    /// The burst is longer than the configured maximum
+    BurstTooLong {
+        /// The channel carrying the command
+        channel: Channel,
+        /// The number of beats in the burst
+        beats: usize,
+        /// The maximum number of beats allowed
+        max: usize,
+    },
+    /// The beat size is wider than the data bus
+    SizeTooLarge(Channel),
+    /// A `WRAP` burst was not 2, 4, 8 or 16 beats long, or was unaligned
+    BadWrapBurst(Channel),
+    /// A `FIXED` burst was longer than 16 beats
+    BadFixedBurst(Channel),
+    /// An `INCR` burst crosses a 4KB boundary
+    Crosses4KBoundary(Channel),
+    /// `WLAST` or `RLAST` was not asserted on exactly the last beat
+    LastMismatch {
+        /// The data channel on which the error occurred
+        channel: Channel,
+        /// The number of beats seen when the burst ended
+        beats: usize,
+        /// The number of beats expected
+        expected: usize,
+    },
+    /// A response or read beat arrived with an ID that has no outstanding burst
+    UnexpectedId {
+        /// The response channel on which the error occurred
+        channel: Channel,
+        /// The offending ID
+        id: u128,
+    },
+}
+
+impl std::fmt::Display for ProtocolViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ValidDropped(ch) => write!(f, "{ch:?}: VALID dropped before READY"),
+            Self::PayloadChanged(ch) => write!(f, "{ch:?}: payload changed before READY"),
+            Self::ReservedBurst(ch) => write!(f, "{ch:?}: reserved burst type"),
+            Self::BurstTooLong {
+                channel,
+                beats,
+                max,
+            } => write!(
+                f,
+                "{channel:?}: burst of {beats} beats exceeds maximum of {max}"
+            ),
+            Self::SizeTooLarge(ch) => write!(f, "{ch:?}: beat size wider than the data bus"),
+            Self::BadWrapBurst(ch) => write!(f, "{ch:?}: illegal WRAP burst"),
+            Self::BadFixedBurst(ch) => write!(f, "{ch:?}: FIXED burst longer than 16 beats"),
+            Self::Crosses4KBoundary(ch) => write!(f, "{ch:?}: INCR burst crosses a 4KB boundary"),
+            Self::LastMismatch {
+                channel,
+                beats,
+                expected,
+            } => write!(
+                f,
+                "{channel:?}: burst ended after {beats} beats, expected {expected}"
+            ),
+            Self::UnexpectedId { channel, id } => {
+                write!(f, "{channel:?}: no outstanding burst with ID {id:#x}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ProtocolViolation {}
+
+/// Holds the payload of a channel that was valid
+/// but not accepted on the previous cycle
+#[derive(Default)]
+struct Pending<T> {
+    payload: Option<T>,
+}
+
+impl<T: PartialEq + Copy> Pending<T> {
+    fn check(
+        &mut self,
+        channel: Channel,
+        valid: bool,
+        ready: bool,
+        payload: T,
+    ) -> Result<(), ProtocolViolation> {
+        let result = match self.payload {
+            Some(_) if !valid => Err(ProtocolViolation::ValidDropped(channel)),
+            Some(prev) if prev != payload => Err(ProtocolViolation::PayloadChanged(channel)),
+            _ => Ok(()),
+        };
+        self.payload = (valid && !ready).then_some(payload);
+        result
+    }
+}
+
+/// A non-synthesizable AXI4 protocol checker
+///
+/// Call [ProtocolChecker::check_write] and [ProtocolChecker::check_read]
+/// once per clock cycle with the signals on the bus at the active
+/// clock edge.
+pub struct ProtocolChecker<const ID: usize, const DATA: usize, const STRB: usize, const ADDR: usize>
+where
+    rhdl::bits::W<ID>: BitWidth,
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    max_burst_len: usize,
+    aw: Pending<AddrCommand<ID, ADDR>>,
+    w: Pending<WriteBeat<DATA, STRB>>,
+    b: Pending<(Bits<ID>, Bits<2>)>,
+    ar: Pending<AddrCommand<ID, ADDR>>,
+    r: Pending<(Bits<ID>, Bits<DATA>, Bits<2>, bool)>,
+    // Write bursts whose address has been accepted, but whose data has not (id, beats)
+    write_addrs: VecDeque<(u128, usize)>,
+    // Beat counts of write bursts whose data arrived ahead of the address
+    write_data: VecDeque<usize>,
+    // Beats seen so far in the current write burst
+    write_beats: usize,
+    // Completed write bursts awaiting a response, by ID
+    write_resps: BTreeMap<u128, usize>,
+    // Outstanding read bursts by ID (beats expected, beats seen)
+    reads: BTreeMap<u128, VecDeque<(usize, usize)>>,
+}
+
+impl<const ID: usize, const DATA: usize, const STRB: usize, const ADDR: usize>
+    ProtocolChecker<ID, DATA, STRB, ADDR>
+where
+    rhdl::bits::W<ID>: BitWidth,
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    /// Create a new checker that allows bursts of up to
+    /// `max_burst_len` beats (at most 256).
+    pub fn new(max_burst_len: usize) -> Self {
+        assert!((1..=256).contains(&max_burst_len));
+        check_widths::<DATA, STRB>();
+        Self {
+            max_burst_len,
+            aw: Pending::default(),
+            w: Pending::default(),
+            b: Pending::default(),
+            ar: Pending::default(),
+            r: Pending::default(),
+            write_addrs: VecDeque::new(),
+            write_data: VecDeque::new(),
+            write_beats: 0,
+            write_resps: BTreeMap::new(),
+            reads: BTreeMap::new(),
+        }
+    }
+
+    fn check_command(
+        &self,
+        channel: Channel,
+        cmd: &AddrCommand<ID, ADDR>,
+    ) -> Result<(), ProtocolViolation> {
+        let beats = cmd.len.raw() as usize + 1;
+        let bytes = 1_u128 << cmd.size.raw();
+        let addr = cmd.addr.raw();
+        if cmd.burst == burst_codes::RESERVED {
+            return Err(ProtocolViolation::ReservedBurst(channel));
+        }
+        if beats > self.max_burst_len {
+            return Err(ProtocolViolation::BurstTooLong {
+                channel,
+                beats,
+                max: self.max_burst_len,
+            });
+        }
+        if bytes > DATA as u128 / 8 {
+            return Err(ProtocolViolation::SizeTooLarge(channel));
+        }
+        match cmd.burst {
+            burst_codes::WRAP => {
+                if ![2, 4, 8, 16].contains(&beats) || !addr.is_multiple_of(bytes) {
+                    return Err(ProtocolViolation::BadWrapBurst(channel));
+                }
+            }
+            burst_codes::FIXED => {
+                if beats > 16 {
+                    return Err(ProtocolViolation::BadFixedBurst(channel));
+                }
+            }
+            _ => {
+                let start = addr & !(bytes - 1);
+                let end = start + (beats as u128) * bytes - 1;
+                if start >> 12 != end >> 12 {
+                    return Err(ProtocolViolation::Crosses4KBoundary(channel));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn match_write_burst(&mut self) -> Result<(), ProtocolViolation> {
+        while !self.write_addrs.is_empty() && !self.write_data.is_empty() {
+            let (id, expected) = self.write_addrs.pop_front().unwrap();
+            let beats = self.write_data.pop_front().unwrap();
+            *self.write_resps.entry(id).or_default() += 1;
+            if beats != expected {
+                return Err(ProtocolViolation::LastMismatch {
+                    channel: Channel::W,
+                    beats,
+                    expected,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Check one clock cycle of the write side of the bus
+    pub fn check_write(
+        &mut self,
+        mosi: &WriteMOSI<ID, DATA, STRB, ADDR>,
+        miso: &WriteMISO<ID>,
+    ) -> Result<(), ProtocolViolation> {
+        let cmd = AddrCommand::<ID, ADDR> {
+            id: mosi.awid,
+            addr: mosi.awaddr,
+            len: mosi.awlen,
+            size: mosi.awsize,
+            burst: mosi.awburst,
+        };
+        let beat = WriteBeat::<DATA, STRB> {
+            data: mosi.wdata,
+            strobe: mosi.wstrb,
+            last: mosi.wlast,
+        };
+        self.aw
+            .check(Channel::AW, mosi.awvalid, miso.awready, cmd)?;
+        self.w.check(Channel::W, mosi.wvalid, miso.wready, beat)?;
+        self.b
+            .check(Channel::B, miso.bvalid, mosi.bready, (miso.bid, miso.bresp))?;
+        if mosi.awvalid && miso.awready {
+            self.check_command(Channel::AW, &cmd)?;
+            self.write_addrs
+                .push_back((cmd.id.raw(), cmd.len.raw() as usize + 1));
+        }
+        if mosi.wvalid && miso.wready {
+            self.write_beats += 1;
+            if let Some((_, expected)) = self.write_addrs.front() {
+                if self.write_data.is_empty() && !mosi.wlast && self.write_beats >= *expected {
+                    return Err(ProtocolViolation::LastMismatch {
+                        channel: Channel::W,
+                        beats: self.write_beats,
+                        expected: *expected,
+                    });
+                }
+            }
+            if mosi.wlast {
+                self.write_data.push_back(self.write_beats);
+                self.write_beats = 0;
+            }
+        }
+        self.match_write_burst()?;
+        if miso.bvalid && mosi.bready {
+            let id = miso.bid.raw();
+            match self.write_resps.get_mut(&id) {
+                Some(count) if *count > 0 => *count -= 1,
+                _ => {
+                    return Err(ProtocolViolation::UnexpectedId {
+                        channel: Channel::B,
+                        id,
+                    })
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Check one clock cycle of the read side of the bus
+    pub fn check_read(
+        &mut self,
+        mosi: &ReadMOSI<ID, ADDR>,
+        miso: &ReadMISO<ID, DATA>,
+    ) -> Result<(), ProtocolViolation> {
+        let cmd = AddrCommand::<ID, ADDR> {
+            id: mosi.arid,
+            addr: mosi.araddr,
+            len: mosi.arlen,
+            size: mosi.arsize,
+            burst: mosi.arburst,
+        };
+        self.ar
+            .check(Channel::AR, mosi.arvalid, miso.arready, cmd)?;
+        self.r.check(
+            Channel::R,
+            miso.rvalid,
+            mosi.rready,
+            (miso.rid, miso.rdata, miso.rresp, miso.rlast),
+        )?;
+        if mosi.arvalid && miso.arready {
+            self.check_command(Channel::AR, &cmd)?;
+            self.reads
+                .entry(cmd.id.raw())
+                .or_default()
+                .push_back((cmd.len.raw() as usize + 1, 0));
+        }
+        if miso.rvalid && mosi.rready {
+            let id = miso.rid.raw();
+            let Some((expected, beats)) = self.reads.get_mut(&id).and_then(|q| q.front_mut())
+            else {
+                return Err(ProtocolViolation::UnexpectedId {
+                    channel: Channel::R,
+                    id,
+                });
+            };
+            *beats += 1;
+            let (expected, beats) = (*expected, *beats);
+            if (beats == expected) != miso.rlast {
+                return Err(ProtocolViolation::LastMismatch {
+                    channel: Channel::R,
+                    beats,
+                    expected,
+                });
+            }
+            if beats == expected {
+                self.reads.get_mut(&id).unwrap().pop_front();
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_addr(addr: u128, len: u128, burst: Bits<2>) -> WriteMOSI<4, 32, 4, 32> {
+        WriteMOSI {
+            awid: bits(1),
+            awaddr: bits(addr),
+            awlen: bits(len),
+            awsize: bits(2),
+            awburst: burst,
+            awvalid: true,
+            ..Default::default()
+        }
+    }
+
+    fn write_beat(last: bool) -> WriteMOSI<4, 32, 4, 32> {
+        WriteMOSI {
+            wdata: bits(0xdead_beef),
+            wstrb: bits(0b1111),
+            wlast: last,
+            wvalid: true,
+            ..Default::default()
+        }
+    }
+
+    fn ready() -> WriteMISO<4> {
+        WriteMISO {
+            awready: true,
+            wready: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_early_wlast_is_caught() {
+        let mut checker = ProtocolChecker::<4, 32, 4, 32>::new(16);
+        checker
+            .check_write(&write_addr(0x100, 3, burst_codes::INCR), &ready())
+            .unwrap();
+        checker.check_write(&write_beat(false), &ready()).unwrap();
+        assert_eq!(
+            checker.check_write(&write_beat(true), &ready()),
+            Err(ProtocolViolation::LastMismatch {
+                channel: Channel::W,
+                beats: 2,
+                expected: 4,
+            })
+        );
+    }
+
+    #[test]
+    fn test_missing_wlast_is_caught() {
+        let mut checker = ProtocolChecker::<4, 32, 4, 32>::new(16);
+        checker
+            .check_write(&write_addr(0x100, 1, burst_codes::INCR), &ready())
+            .unwrap();
+        checker.check_write(&write_beat(false), &ready()).unwrap();
+        assert!(matches!(
+            checker.check_write(&write_beat(false), &ready()),
+            Err(ProtocolViolation::LastMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_dropped_valid_is_caught() {
+        let mut checker = ProtocolChecker::<4, 32, 4, 32>::new(16);
+        let stalled = WriteMISO::default();
+        checker
+            .check_write(&write_addr(0x100, 0, burst_codes::INCR), &stalled)
+            .unwrap();
+        assert_eq!(
+            checker.check_write(&WriteMOSI::default(), &stalled),
+            Err(ProtocolViolation::ValidDropped(Channel::AW))
+        );
+    }
+
+    #[test]
+    fn test_illegal_commands_are_caught() {
+        let mut checker = ProtocolChecker::<4, 32, 4, 32>::new(16);
+        assert_eq!(
+            checker.check_write(&write_addr(0xff8, 3, burst_codes::INCR), &ready()),
+            Err(ProtocolViolation::Crosses4KBoundary(Channel::AW))
+        );
+        let mut checker = ProtocolChecker::<4, 32, 4, 32>::new(16);
+        assert_eq!(
+            checker.check_write(&write_addr(0x100, 2, burst_codes::WRAP), &ready()),
+            Err(ProtocolViolation::BadWrapBurst(Channel::AW))
+        );
+        let mut checker = ProtocolChecker::<4, 32, 4, 32>::new(16);
+        assert_eq!(
+            checker.check_write(&write_addr(0x100, 16, burst_codes::INCR), &ready()),
+            Err(ProtocolViolation::BurstTooLong {
+                channel: Channel::AW,
+                beats: 17,
+                max: 16,
+            })
+        );
+    }
+
+    #[test]
+    fn test_unexpected_read_id_is_caught() {
+        let mut checker = ProtocolChecker::<4, 32, 4, 32>::new(16);
+        let mosi = ReadMOSI {
+            rready: true,
+            ..Default::default()
+        };
+        let miso = ReadMISO {
+            rid: bits(3),
+            rvalid: true,
+            rlast: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            checker.check_read(&mosi, &miso),
+            Err(ProtocolViolation::UnexpectedId {
+                channel: Channel::R,
+                id: 3,
+            })
+        );
+    }
+}
@@ -0,0 +1,376 @@
+//! AXI4 subordinate backed by a block RAM
+//!
+//!# Purpose
+//!
+//! This core exposes a [SyncBRAM] as an AXI4 subordinate that
+//! supports `INCR`, `WRAP` and `FIXED` bursts of up to a configurable
+//! number of beats.  Each word of the RAM is `DATA` bits wide and
+//! occupies `DATA/8` bytes of the address space.  The RAM holds
+//! `2^N` words, and addresses wrap modulo the size of the RAM (i.e.,
+//! the upper address bits are ignored), so the core is normally placed
+//! behind an address decoder.
+//!
+//!# Schematic Symbol
+//!
+#![doc = badascii_formal!(r"
+      ++BramEndpoint+-+
+ aw*  |               |
++---->|               |
+ w*   |               |
++---->|     write     |
+ b*   |               |
+<-----+               |
+      |  - - - - - -  |
+ ar*  |               |
++---->|     read      |
+ r*   |               |
+<-----+               |
+      +---------------+
+")]
+//!
+//!# Internal Details
+//!
+//! Every AXI channel is buffered by an [Axi2Rhdl] or [Rhdl2Axi]
+//! shim.  A single state machine serves one burst at a time, giving
+//! priority to writes when both an `AW` and an `AR` command are
+//! waiting.
+//!
+//! - Write beats take two cycles each.  The first reads the current
+//!   contents of the word, and the second merges the strobed bytes
+//!   into it and writes it back.  Once the beat with `WLAST` set has
+//!   been written, a single write response is sent.
+//! - Read bursts take one cycle to prime the RAM, after which one beat
+//!   is sent per clock as long as the `R` channel is ready.
+//!
+//! A burst longer than the configured maximum, or one with the reserved
+//! burst type, is answered with `SLVERR` and does not modify the RAM.
+//! All of the write beats of such a burst are still consumed.  A write
+//! burst whose `WLAST` does not line up with `AWLEN` is answered with
+//! `SLVERR`, and no further beats are written once the mismatch is seen.
+use badascii_doc::badascii_formal;
+use rhdl::prelude::*;
+
+use crate::{
+    axi4::{
+        burst::next_address,
+        types::{
+            burst_codes, response_codes, AddrCommand, ReadBeat, ReadMISO, ReadMOSI, WriteBeat,
+            WriteMISO, WriteMOSI, WriteResponse,
+        },
+    },
+    axi4lite::{
+        stream::{axi_to_rhdl::Axi2Rhdl, rhdl_to_axi::Rhdl2Axi},
+        types::{check_widths, strobe_to_mask},
+    },
+    core::{constant::Constant, dff::DFF, ram::synchronous::SyncBRAM},
+};
+
+#[derive(PartialEq, Debug, Digital, Clone, Copy, Default)]
+#[doc(hidden)]
+pub enum State {
+    #[default]
+    Idle,
+    WriteData,
+    WriteMerge,
+    WriteResp,
+    ReadStart,
+    ReadData,
+}
+
+#[derive(PartialEq, Debug, Digital, Clone, Copy, Default)]
+#[doc(hidden)]
+pub struct Progress {
+    /// Number of beats transferred so far in the burst
+    count: Bits<8>,
+    /// Set if the burst will be answered with SLVERR
+    error: bool,
+}
+
+#[derive(Clone, Synchronous, SynchronousDQ)]
+#[rhdl(dq_no_prefix)]
+/// AXI4 BRAM Endpoint
+///
+/// This core provides `2^N` words of `DATA` bits of
+/// block RAM on an AXI4 bus with `ID` bit transaction IDs,
+/// `STRB = DATA/8` strobe bits and `ADDR` bit addresses.
+pub struct BramEndpoint<
+    const ID: usize,
+    const DATA: usize,
+    const STRB: usize,
+    const ADDR: usize,
+    const N: usize,
+> where
+    rhdl::bits::W<ID>: BitWidth,
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+    rhdl::bits::W<N>: BitWidth,
+{
+    aw_buf: Axi2Rhdl<AddrCommand<ID, ADDR>>,
+    w_buf: Axi2Rhdl<WriteBeat<DATA, STRB>>,
+    b_buf: Rhdl2Axi<WriteResponse<ID>>,
+    ar_buf: Axi2Rhdl<AddrCommand<ID, ADDR>>,
+    r_buf: Rhdl2Axi<ReadBeat<ID, DATA>>,
+    ram: SyncBRAM<Bits<DATA>, N>,
+    state: DFF<State>,
+    cmd: DFF<AddrCommand<ID, ADDR>>,
+    progress: DFF<Progress>,
+    beat: DFF<WriteBeat<DATA, STRB>>,
+    max_len: Constant<Bits<8>>,
+}
+
+impl<const ID: usize, const DATA: usize, const STRB: usize, const ADDR: usize, const N: usize>
+    BramEndpoint<ID, DATA, STRB, ADDR, N>
+where
+    rhdl::bits::W<ID>: BitWidth,
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+    rhdl::bits::W<N>: BitWidth,
+{
+    /// Create a new endpoint that accepts bursts of up
+    /// to `max_burst_len` beats (which must be between
+    /// 1 and 256), and with the given initial contents
+    /// of the RAM.
+    pub fn new(
+        max_burst_len: usize,
+        initial: impl IntoIterator<Item = (Bits<N>, Bits<DATA>)>,
+    ) -> Self {
+        assert!((1..=256).contains(&max_burst_len));
+        check_widths::<DATA, STRB>();
+        Self {
+            aw_buf: Axi2Rhdl::default(),
+            w_buf: Axi2Rhdl::default(),
+            b_buf: Rhdl2Axi::default(),
+            ar_buf: Axi2Rhdl::default(),
+            r_buf: Rhdl2Axi::default(),
+            ram: SyncBRAM::new(initial),
+            state: DFF::default(),
+            cmd: DFF::default(),
+            progress: DFF::default(),
+            beat: DFF::default(),
+            // Stored in the same (len - 1) encoding as AxLEN
+            max_len: Constant::new(bits(max_burst_len as u128 - 1)),
+        }
+    }
+}
+
+impl<const ID: usize, const DATA: usize, const STRB: usize, const ADDR: usize, const N: usize>
+    Default for BramEndpoint<ID, DATA, STRB, ADDR, N>
+where
+    rhdl::bits::W<ID>: BitWidth,
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+    rhdl::bits::W<N>: BitWidth,
+{
+    fn default() -> Self {
+        Self::new(256, std::iter::empty())
+    }
+}
+
+#[derive(PartialEq, Debug, Digital, Clone, Copy)]
+/// Input for the [BramEndpoint] core
+pub struct In<const ID: usize, const DATA: usize, const STRB: usize, const ADDR: usize>
+where
+    rhdl::bits::W<ID>: BitWidth,
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    /// AXI signals from the bus for writing
+    pub write_axi: WriteMOSI<ID, DATA, STRB, ADDR>,
+    /// AXI signals from the bus for reading
+    pub read_axi: ReadMOSI<ID, ADDR>,
+}
+
+#[derive(PartialEq, Debug, Digital, Clone, Copy)]
+/// Output for the [BramEndpoint] core
+pub struct Out<const ID: usize, const DATA: usize>
+where
+    rhdl::bits::W<ID>: BitWidth,
+    rhdl::bits::W<DATA>: BitWidth,
+{
+    /// AXI signals to the bus for writing
+    pub write_axi: WriteMISO<ID>,
+    /// AXI signals to the bus for reading
+    pub read_axi: ReadMISO<ID, DATA>,
+}
+
+impl<const ID: usize, const DATA: usize, const STRB: usize, const ADDR: usize, const N: usize>
+    SynchronousIO for BramEndpoint<ID, DATA, STRB, ADDR, N>
+where
+    rhdl::bits::W<ID>: BitWidth,
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+    rhdl::bits::W<N>: BitWidth,
+{
+    type I = In<ID, DATA, STRB, ADDR>;
+    type O = Out<ID, DATA>;
+    type Kernel = kernel<ID, DATA, STRB, ADDR, N>;
+}
+
+#[kernel]
+#[doc(hidden)]
+pub fn kernel<
+    const ID: usize,
+    const DATA: usize,
+    const STRB: usize,
+    const ADDR: usize,
+    const N: usize,
+>(
+    _cr: ClockReset,
+    i: In<ID, DATA, STRB, ADDR>,
+    q: Q<ID, DATA, STRB, ADDR, N>,
+) -> (Out<ID, DATA>, D<ID, DATA, STRB, ADDR, N>)
+where
+    rhdl::bits::W<ID>: BitWidth,
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+    rhdl::bits::W<N>: BitWidth,
+{
+    let mut d = D::<ID, DATA, STRB, ADDR, N>::dont_care();
+    let mut o = Out::<ID, DATA>::dont_care();
+    // Each word takes DATA/8 bytes, so we need to right shift by 2 or 3
+    let word_shift = bits::<ADDR>(DATA as u128 / 32 + 1);
+    // Wire the AXI busses to the channel buffers
+    d.aw_buf.tdata = AddrCommand::<ID, ADDR> {
+        id: i.write_axi.awid,
+        addr: i.write_axi.awaddr,
+        len: i.write_axi.awlen,
+        size: i.write_axi.awsize,
+        burst: i.write_axi.awburst,
+    };
+    d.aw_buf.tvalid = i.write_axi.awvalid;
+    o.write_axi.awready = q.aw_buf.tready;
+    d.w_buf.tdata = WriteBeat::<DATA, STRB> {
+        data: i.write_axi.wdata,
+        strobe: i.write_axi.wstrb,
+        last: i.write_axi.wlast,
+    };
+    d.w_buf.tvalid = i.write_axi.wvalid;
+    o.write_axi.wready = q.w_buf.tready;
+    d.b_buf.tready = i.write_axi.bready;
+    o.write_axi.bid = q.b_buf.tdata.id;
+    o.write_axi.bresp = q.b_buf.tdata.resp;
+    o.write_axi.bvalid = q.b_buf.tvalid;
+    d.ar_buf.tdata = AddrCommand::<ID, ADDR> {
+        id: i.read_axi.arid,
+        addr: i.read_axi.araddr,
+        len: i.read_axi.arlen,
+        size: i.read_axi.arsize,
+        burst: i.read_axi.arburst,
+    };
+    d.ar_buf.tvalid = i.read_axi.arvalid;
+    o.read_axi.arready = q.ar_buf.tready;
+    d.r_buf.tready = i.read_axi.rready;
+    o.read_axi.rid = q.r_buf.tdata.id;
+    o.read_axi.rdata = q.r_buf.tdata.data;
+    o.read_axi.rresp = q.r_buf.tdata.resp;
+    o.read_axi.rlast = q.r_buf.tdata.last;
+    o.read_axi.rvalid = q.r_buf.tvalid;
+    // Default to holding state, and not transferring anything
+    d.state = q.state;
+    d.cmd = q.cmd;
+    d.progress = q.progress;
+    d.beat = q.beat;
+    d.aw_buf.ready.raw = false;
+    d.w_buf.ready.raw = false;
+    d.ar_buf.ready.raw = false;
+    d.b_buf.data = None;
+    d.r_buf.data = None;
+    let word = (q.cmd.addr >> word_shift).resize::<N>();
+    d.ram.read_addr = word;
+    d.ram.write.addr = word;
+    d.ram.write.value = q.ram;
+    d.ram.write.enable = false;
+    let resp = if q.progress.error {
+        response_codes::SLVERR
+    } else {
+        response_codes::OKAY
+    };
+    match q.state {
+        State::Idle => {
+            if let Some(cmd) = q.aw_buf.data {
+                d.aw_buf.ready.raw = true;
+                d.cmd = cmd;
+                d.progress.count = bits(0);
+                d.progress.error = cmd.len > q.max_len || cmd.burst == burst_codes::RESERVED;
+                d.state = State::WriteData;
+            } else if let Some(cmd) = q.ar_buf.data {
+                d.ar_buf.ready.raw = true;
+                d.cmd = cmd;
+                d.progress.count = bits(0);
+                d.progress.error = cmd.len > q.max_len || cmd.burst == burst_codes::RESERVED;
+                d.state = State::ReadStart;
+            }
+        }
+        State::WriteData => {
+            // Accept a beat, and fetch the word it will be merged into
+            if let Some(beat) = q.w_buf.data {
+                d.w_buf.ready.raw = true;
+                d.beat = beat;
+                d.state = State::WriteMerge;
+            }
+        }
+        State::WriteMerge => {
+            let error = q.progress.error || (q.progress.count == q.cmd.len) != q.beat.last;
+            let mask = strobe_to_mask::<DATA, STRB>(q.beat.strobe);
+            d.ram.write.value = (q.ram & !mask) | (q.beat.data & mask);
+            d.ram.write.enable = !error;
+            d.progress.error = error;
+            d.progress.count = q.progress.count + 1;
+            d.cmd.addr = next_address::<ADDR>(q.cmd.addr, q.cmd.len, q.cmd.size, q.cmd.burst);
+            if q.beat.last {
+                d.state = State::WriteResp;
+            } else {
+                d.state = State::WriteData;
+            }
+        }
+        State::WriteResp => {
+            d.b_buf.data = Some(WriteResponse::<ID> { id: q.cmd.id, resp });
+            if q.b_buf.ready.raw {
+                d.state = State::Idle;
+            }
+        }
+        State::ReadStart => {
+            // The RAM has a single cycle of latency
+            d.state = State::ReadData;
+        }
+        State::ReadData => {
+            let last = q.progress.count == q.cmd.len;
+            d.r_buf.data = Some(ReadBeat::<ID, DATA> {
+                id: q.cmd.id,
+                data: q.ram,
+                resp,
+                last,
+            });
+            if q.r_buf.ready.raw {
+                if last {
+                    d.state = State::Idle;
+                } else {
+                    // Fetch the next word so it is ready on the next cycle
+                    let next = next_address::<ADDR>(q.cmd.addr, q.cmd.len, q.cmd.size, q.cmd.burst);
+                    d.cmd.addr = next;
+                    d.progress.count = q.progress.count + 1;
+                    d.ram.read_addr = (next >> word_shift).resize::<N>();
+                }
+            }
+        }
+    }
+    (o, d)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_combinatorial_paths() -> miette::Result<()> {
+        let uut = BramEndpoint::<4, 32, 4, 32, 8>::default();
+        drc::no_combinatorial_paths(&uut)?;
+        Ok(())
+    }
+}
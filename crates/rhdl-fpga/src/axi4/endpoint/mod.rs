@@ -0,0 +1,2 @@
+//! AXI4 subordinate endpoints
+pub mod bram;
@@ -0,0 +1,6 @@
+//! AXI4 managers
+//!
+//! These cores convert RHDL streams of commands and beats
+//! into AXI4 burst transactions.
+pub mod read;
+pub mod write;
@@ -0,0 +1,153 @@
+//! AXI4 Read Manager
+//!
+//!# Purpose
+//!
+//! This core drives the read side of an AXI4 bus from a pair
+//! of RHDL streams.  One stream carries the [AddrCommand]s (one
+//! per burst), and the other returns the [ReadBeat]s from the bus.
+//! Each command results in `len + 1` beats, the last of which
+//! has its `last` flag set.  Beats from bursts with different
+//! IDs may be interleaved by the subordinate.
+//!
+//!# Schematic Symbol
+//!
+#![doc = badascii_formal!(r"
+               ++ReadManager+++
+  ?AddrCommand |              | ar*
+ +------------>| cmd.data     +--------->
+ <-------------+ cmd.ready    | arready
+               |              |<--------+
+  ?ReadBeat    |              | r*
+ <-------------+ beat.data    |<--------+
+ +------------>| beat.ready   | rready
+               |              +--------->
+               +--------------+
+")]
+//!
+//!# Internal Details
+//!
+//! The `AR` channel is buffered by an [Rhdl2Axi] and the `R`
+//! channel by an [Axi2Rhdl], so that there are no combinatorial
+//! paths between the bus and the streams.
+use badascii_doc::badascii_formal;
+use rhdl::prelude::*;
+
+use crate::{
+    axi4::types::{AddrCommand, ReadBeat, ReadMISO, ReadMOSI},
+    axi4lite::stream::{axi_to_rhdl::Axi2Rhdl, rhdl_to_axi::Rhdl2Axi},
+    stream::Ready,
+};
+
+#[derive(Clone, Synchronous, SynchronousDQ, Default)]
+#[rhdl(dq_no_prefix)]
+/// AXI4 Read Manager
+///
+/// This core sinks a stream of address commands into
+/// AXI4 read bursts, and sources the stream of read beats
+/// that come back.  The core is generic over the `ID`,
+/// `DATA` and `ADDR` widths of the bus.
+pub struct ReadManager<const ID: usize, const DATA: usize, const ADDR: usize>
+where
+    rhdl::bits::W<ID>: BitWidth,
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    cmd_buf: Rhdl2Axi<AddrCommand<ID, ADDR>>,
+    beat_buf: Axi2Rhdl<ReadBeat<ID, DATA>>,
+}
+
+#[derive(PartialEq, Debug, Digital, Clone, Copy)]
+/// Input for the [ReadManager] core
+pub struct In<const ID: usize, const DATA: usize, const ADDR: usize>
+where
+    rhdl::bits::W<ID>: BitWidth,
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    /// AXI signals from the bus
+    pub axi: ReadMISO<ID, DATA>,
+    /// Address command stream
+    pub cmd_data: Option<AddrCommand<ID, ADDR>>,
+    /// Read beat ready signal
+    pub beat_ready: Ready<ReadBeat<ID, DATA>>,
+}
+
+#[derive(PartialEq, Debug, Digital, Clone, Copy)]
+/// Output for the [ReadManager] core
+pub struct Out<const ID: usize, const DATA: usize, const ADDR: usize>
+where
+    rhdl::bits::W<ID>: BitWidth,
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    /// AXI signals to the bus
+    pub axi: ReadMOSI<ID, ADDR>,
+    /// Address command stream ready signal
+    pub cmd_ready: Ready<AddrCommand<ID, ADDR>>,
+    /// Read beat stream
+    pub beat_data: Option<ReadBeat<ID, DATA>>,
+}
+
+impl<const ID: usize, const DATA: usize, const ADDR: usize> SynchronousIO
+    for ReadManager<ID, DATA, ADDR>
+where
+    rhdl::bits::W<ID>: BitWidth,
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    type I = In<ID, DATA, ADDR>;
+    type O = Out<ID, DATA, ADDR>;
+    type Kernel = kernel<ID, DATA, ADDR>;
+}
+
+#[kernel]
+#[doc(hidden)]
+pub fn kernel<const ID: usize, const DATA: usize, const ADDR: usize>(
+    _cr: ClockReset,
+    i: In<ID, DATA, ADDR>,
+    q: Q<ID, DATA, ADDR>,
+) -> (Out<ID, DATA, ADDR>, D<ID, DATA, ADDR>)
+where
+    rhdl::bits::W<ID>: BitWidth,
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    let mut d = D::<ID, DATA, ADDR>::dont_care();
+    let mut o = Out::<ID, DATA, ADDR>::dont_care();
+    // Wire up the stream inputs
+    d.cmd_buf.data = i.cmd_data;
+    d.cmd_buf.tready = i.axi.arready;
+    d.beat_buf.tdata = ReadBeat::<ID, DATA> {
+        id: i.axi.rid,
+        data: i.axi.rdata,
+        resp: i.axi.rresp,
+        last: i.axi.rlast,
+    };
+    d.beat_buf.tvalid = i.axi.rvalid;
+    d.beat_buf.ready = i.beat_ready;
+    // Wire up the stream outputs
+    o.cmd_ready = q.cmd_buf.ready;
+    o.beat_data = q.beat_buf.data;
+    // Wire up the AXI outputs
+    let cmd = q.cmd_buf.tdata;
+    o.axi.arid = cmd.id;
+    o.axi.araddr = cmd.addr;
+    o.axi.arlen = cmd.len;
+    o.axi.arsize = cmd.size;
+    o.axi.arburst = cmd.burst;
+    o.axi.arvalid = q.cmd_buf.tvalid;
+    o.axi.rready = q.beat_buf.tready;
+    (o, d)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_combinatorial_paths() -> miette::Result<()> {
+        let uut = ReadManager::<4, 32, 32>::default();
+        drc::no_combinatorial_paths(&uut)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,199 @@
+//! AXI4 Write Manager
+//!
+//!# Purpose
+//!
+//! This core drives the write side of an AXI4 bus from three
+//! RHDL streams.  One stream carries [AddrCommand]s (one per
+//! burst), one carries the [WriteBeat]s of the bursts, and the
+//! third returns the [WriteResponse]s from the bus.  The manager
+//! does not generate the beats itself, so it is up to the upstream
+//! logic to supply `len + 1` beats for each command, with `last`
+//! set on the final one.  The [crate::axi4::checker] can be used in
+//! tests to verify that this is the case.
+//!
+//!# Schematic Symbol
+//!
+#![doc = badascii_formal!(r"
+               ++WriteManager++
+  ?AddrCommand |              | aw*
+ +------------>| cmd.data     +--------->
+ <-------------+ cmd.ready    | awready
+               |              |<--------+
+  ?WriteBeat   |              | w*
+ +------------>| beat.data    +--------->
+ <-------------+ beat.ready   | wready
+               |              |<--------+
+ ?WriteResponse|              | b*
+ <-------------+ resp.data    |<--------+
+ +------------>| resp.ready   | bready
+               |              +--------->
+               +--------------+
+")]
+//!
+//!# Internal Details
+//!
+//! Each AXI channel is buffered by either an [Rhdl2Axi] (for
+//! the `AW` and `W` channels) or an [Axi2Rhdl] (for the `B`
+//! channel), so that there are no combinatorial paths between
+//! the bus and the streams.  The `AW` and `W` channels are
+//! independent, as the AXI4 specification allows write data to
+//! lead or lag its address.
+use badascii_doc::badascii_formal;
+use rhdl::prelude::*;
+
+use crate::{
+    axi4::types::{AddrCommand, WriteBeat, WriteMISO, WriteMOSI, WriteResponse},
+    axi4lite::{
+        stream::{axi_to_rhdl::Axi2Rhdl, rhdl_to_axi::Rhdl2Axi},
+        types::check_widths,
+    },
+    stream::Ready,
+};
+
+#[derive(Clone, Synchronous, SynchronousDQ)]
+#[rhdl(dq_no_prefix)]
+/// AXI4 Write Manager
+///
+/// This core sinks streams of address commands and write
+/// beats into AXI4 write bursts, and sources the stream
+/// of write responses.  The core is generic over the `ID`,
+/// `DATA` (with `STRB = DATA/8`) and `ADDR` widths of the bus.
+pub struct WriteManager<const ID: usize, const DATA: usize, const STRB: usize, const ADDR: usize>
+where
+    rhdl::bits::W<ID>: BitWidth,
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    cmd_buf: Rhdl2Axi<AddrCommand<ID, ADDR>>,
+    beat_buf: Rhdl2Axi<WriteBeat<DATA, STRB>>,
+    resp_buf: Axi2Rhdl<WriteResponse<ID>>,
+}
+
+impl<const ID: usize, const DATA: usize, const STRB: usize, const ADDR: usize> Default
+    for WriteManager<ID, DATA, STRB, ADDR>
+where
+    rhdl::bits::W<ID>: BitWidth,
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    fn default() -> Self {
+        check_widths::<DATA, STRB>();
+        Self {
+            cmd_buf: Rhdl2Axi::default(),
+            beat_buf: Rhdl2Axi::default(),
+            resp_buf: Axi2Rhdl::default(),
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Digital, Clone, Copy)]
+/// Input for the [WriteManager] core
+pub struct In<const ID: usize, const DATA: usize, const STRB: usize, const ADDR: usize>
+where
+    rhdl::bits::W<ID>: BitWidth,
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    /// AXI signals from the bus
+    pub axi: WriteMISO<ID>,
+    /// Address command stream
+    pub cmd_data: Option<AddrCommand<ID, ADDR>>,
+    /// Write beat stream
+    pub beat_data: Option<WriteBeat<DATA, STRB>>,
+    /// Response ready signal
+    pub resp_ready: Ready<WriteResponse<ID>>,
+}
+
+#[derive(PartialEq, Debug, Digital, Clone, Copy)]
+/// Output for the [WriteManager] core
+pub struct Out<const ID: usize, const DATA: usize, const STRB: usize, const ADDR: usize>
+where
+    rhdl::bits::W<ID>: BitWidth,
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    /// AXI signals to the bus
+    pub axi: WriteMOSI<ID, DATA, STRB, ADDR>,
+    /// Address command stream ready signal
+    pub cmd_ready: Ready<AddrCommand<ID, ADDR>>,
+    /// Write beat stream ready signal
+    pub beat_ready: Ready<WriteBeat<DATA, STRB>>,
+    /// Response data stream
+    pub resp_data: Option<WriteResponse<ID>>,
+}
+
+impl<const ID: usize, const DATA: usize, const STRB: usize, const ADDR: usize> SynchronousIO
+    for WriteManager<ID, DATA, STRB, ADDR>
+where
+    rhdl::bits::W<ID>: BitWidth,
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    type I = In<ID, DATA, STRB, ADDR>;
+    type O = Out<ID, DATA, STRB, ADDR>;
+    type Kernel = kernel<ID, DATA, STRB, ADDR>;
+}
+
+#[kernel]
+#[doc(hidden)]
+pub fn kernel<const ID: usize, const DATA: usize, const STRB: usize, const ADDR: usize>(
+    _cr: ClockReset,
+    i: In<ID, DATA, STRB, ADDR>,
+    q: Q<ID, DATA, STRB, ADDR>,
+) -> (Out<ID, DATA, STRB, ADDR>, D<ID, DATA, STRB, ADDR>)
+where
+    rhdl::bits::W<ID>: BitWidth,
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    let mut d = D::<ID, DATA, STRB, ADDR>::dont_care();
+    let mut o = Out::<ID, DATA, STRB, ADDR>::dont_care();
+    // Wire up the stream inputs
+    d.cmd_buf.data = i.cmd_data;
+    d.cmd_buf.tready = i.axi.awready;
+    d.beat_buf.data = i.beat_data;
+    d.beat_buf.tready = i.axi.wready;
+    d.resp_buf.tdata = WriteResponse::<ID> {
+        id: i.axi.bid,
+        resp: i.axi.bresp,
+    };
+    d.resp_buf.tvalid = i.axi.bvalid;
+    d.resp_buf.ready = i.resp_ready;
+    // Wire up the stream outputs
+    o.cmd_ready = q.cmd_buf.ready;
+    o.beat_ready = q.beat_buf.ready;
+    o.resp_data = q.resp_buf.data;
+    // Wire up the AXI outputs
+    let cmd = q.cmd_buf.tdata;
+    o.axi.awid = cmd.id;
+    o.axi.awaddr = cmd.addr;
+    o.axi.awlen = cmd.len;
+    o.axi.awsize = cmd.size;
+    o.axi.awburst = cmd.burst;
+    o.axi.awvalid = q.cmd_buf.tvalid;
+    let beat = q.beat_buf.tdata;
+    o.axi.wdata = beat.data;
+    o.axi.wstrb = beat.strobe;
+    o.axi.wlast = beat.last;
+    o.axi.wvalid = q.beat_buf.tvalid;
+    o.axi.bready = q.resp_buf.tready;
+    (o, d)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_combinatorial_paths() -> miette::Result<()> {
+        let uut = WriteManager::<4, 32, 4, 32>::default();
+        drc::no_combinatorial_paths(&uut)?;
+        Ok(())
+    }
+}
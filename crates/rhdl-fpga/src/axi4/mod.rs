@@ -0,0 +1,18 @@
+//! IP Cores for implementing full AXI4 (burst) interfaces
+//!
+//! Unlike the [crate::axi4lite] cores, these cores support
+//! multi-beat bursts (`INCR`, `WRAP` and `FIXED`), transaction
+//! IDs and the `WLAST`/`RLAST` framing signals.  The channel
+//! buffering reuses the [Axi2Rhdl] and [Rhdl2Axi] shims from
+//! the AXI4Lite stack, so that every AXI channel is registered
+//! in both directions.
+//!
+//! [Axi2Rhdl]: crate::axi4lite::stream::axi_to_rhdl::Axi2Rhdl
+//! [Rhdl2Axi]: crate::axi4lite::stream::rhdl_to_axi::Rhdl2Axi
+pub mod burst;
+pub mod checker;
+pub mod endpoint;
+pub mod manager;
+#[doc(hidden)]
+pub mod testing;
+pub mod types;
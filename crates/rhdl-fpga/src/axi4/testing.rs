@@ -0,0 +1,206 @@
+//! Test harness for the AXI4 managers and the BRAM endpoint
+//!
+//! The write and read managers are connected to a [BramEndpoint].
+//! The write side is fed with a fixed set of bursts, and once
+//! those have completed, the read side reads the memory back.
+//! The AXI busses are exposed as outputs so that they can be
+//! monitored with a [crate::axi4::checker::ProtocolChecker].
+use rhdl::prelude::*;
+
+use crate::{
+    axi4::{
+        endpoint::bram::BramEndpoint,
+        manager::{read::ReadManager, write::WriteManager},
+        types::{
+            AddrCommand, ReadBeat, ReadMISO, ReadMOSI, WriteBeat, WriteMISO, WriteMOSI,
+            WriteResponse,
+        },
+    },
+    stream::testing::{sink_from_fn::SinkFromFn, source_from_fn::SourceFromFn},
+};
+
+#[derive(Clone, Synchronous, SynchronousDQ)]
+#[rhdl(dq_no_prefix)]
+pub struct TestFixture {
+    cmd_source: SourceFromFn<AddrCommand<4, 32>>,
+    beat_source: SourceFromFn<WriteBeat<32, 4>>,
+    resp_sink: SinkFromFn<WriteResponse<4>>,
+    read_source: SourceFromFn<AddrCommand<4, 32>>,
+    read_sink: SinkFromFn<ReadBeat<4, 32>>,
+    write_manager: WriteManager<4, 32, 4, 32>,
+    read_manager: ReadManager<4, 32, 32>,
+    endpoint: BramEndpoint<4, 32, 4, 32, 8>,
+}
+
+#[derive(PartialEq, Debug, Digital, Clone, Copy)]
+pub struct Busses {
+    pub write_mosi: WriteMOSI<4, 32, 4, 32>,
+    pub write_miso: WriteMISO<4>,
+    pub read_mosi: ReadMOSI<4, 32>,
+    pub read_miso: ReadMISO<4, 32>,
+}
+
+impl SynchronousIO for TestFixture {
+    type I = ();
+    type O = Busses;
+    type Kernel = kernel;
+}
+
+#[kernel]
+pub fn kernel(_cr: ClockReset, _i: (), q: Q) -> (Busses, D) {
+    let mut d = D::dont_care();
+    // Wire the write side streams to the write manager
+    d.write_manager.cmd_data = q.cmd_source;
+    d.cmd_source = q.write_manager.cmd_ready;
+    d.write_manager.beat_data = q.beat_source;
+    d.beat_source = q.write_manager.beat_ready;
+    d.resp_sink = q.write_manager.resp_data;
+    d.write_manager.resp_ready = q.resp_sink;
+    // Wire the read side streams to the read manager
+    d.read_manager.cmd_data = q.read_source;
+    d.read_source = q.read_manager.cmd_ready;
+    d.read_sink = q.read_manager.beat_data;
+    d.read_manager.beat_ready = q.read_sink;
+    // Wire the AXI busses together
+    d.endpoint.write_axi = q.write_manager.axi;
+    d.write_manager.axi = q.endpoint.write_axi;
+    d.endpoint.read_axi = q.read_manager.axi;
+    d.read_manager.axi = q.endpoint.read_axi;
+    let o = Busses {
+        write_mosi: q.write_manager.axi,
+        write_miso: q.endpoint.write_axi,
+        read_mosi: q.read_manager.axi,
+        read_miso: q.endpoint.read_axi,
+    };
+    (o, d)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter::repeat_n;
+
+    use crate::{
+        axi4::{
+            checker::ProtocolChecker,
+            types::{burst_codes, response_codes, BurstKind},
+        },
+        stream::testing::utils::stalling,
+    };
+
+    use super::*;
+
+    fn cmd(id: u128, addr: u128, beats: u128, burst: BurstKind) -> AddrCommand<4, 32> {
+        AddrCommand {
+            id: bits(id),
+            addr: bits(addr),
+            len: bits(beats - 1),
+            size: bits(2),
+            burst,
+        }
+    }
+
+    fn beats(data: &[u128]) -> Vec<WriteBeat<32, 4>> {
+        data.iter()
+            .enumerate()
+            .map(|(ndx, &x)| WriteBeat {
+                data: bits(x),
+                strobe: bits(0b1111),
+                last: ndx == data.len() - 1,
+            })
+            .collect()
+    }
+
+    fn read_beats(id: u128, data: &[u128]) -> Vec<ReadBeat<4, 32>> {
+        data.iter()
+            .enumerate()
+            .map(|(ndx, &x)| ReadBeat {
+                id: bits(id),
+                data: bits(x),
+                resp: response_codes::OKAY,
+                last: ndx == data.len() - 1,
+            })
+            .collect()
+    }
+
+    fn test_fixture() -> TestFixture {
+        let writes = [
+            cmd(1, 0x100, 4, burst_codes::INCR),
+            cmd(2, 0x208, 4, burst_codes::WRAP),
+            cmd(3, 0x300, 3, burst_codes::FIXED),
+        ];
+        let write_data = [
+            beats(&[0x10, 0x11, 0x12, 0x13]),
+            beats(&[0x28, 0x2c, 0x20, 0x24]),
+            beats(&[0x30, 0x31, 0x32]),
+        ]
+        .concat();
+        let responses = [1, 2, 3].map(|id| WriteResponse {
+            id: bits(id),
+            resp: response_codes::OKAY,
+        });
+        let reads = [
+            cmd(4, 0x100, 4, burst_codes::INCR),
+            cmd(5, 0x200, 4, burst_codes::INCR),
+            cmd(6, 0x300, 2, burst_codes::FIXED),
+            cmd(7, 0x20c, 2, burst_codes::WRAP),
+        ];
+        let read_data = [
+            read_beats(4, &[0x10, 0x11, 0x12, 0x13]),
+            read_beats(5, &[0x20, 0x24, 0x28, 0x2c]),
+            read_beats(6, &[0x32, 0x32]),
+            read_beats(7, &[0x2c, 0x28]),
+        ]
+        .concat();
+        // Hold off the reads until the writes have completed
+        let reads = repeat_n(None, 200).chain(stalling(reads.into_iter(), 0.2));
+        TestFixture {
+            cmd_source: SourceFromFn::new(stalling(writes.into_iter(), 0.2)),
+            beat_source: SourceFromFn::new(stalling(write_data.into_iter(), 0.2)),
+            resp_sink: SinkFromFn::new_from_iter(responses.into_iter(), 0.2),
+            read_source: SourceFromFn::new(reads),
+            read_sink: SinkFromFn::new_from_iter(read_data.into_iter(), 0.2),
+            write_manager: WriteManager::default(),
+            read_manager: ReadManager::default(),
+            endpoint: BramEndpoint::new(16, std::iter::empty()),
+        }
+    }
+
+    #[test]
+    fn no_combinatorial_paths() -> miette::Result<()> {
+        let uut = test_fixture();
+        drc::no_combinatorial_paths(&uut)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_bursts_round_trip() -> Result<(), RHDLError> {
+        let uut = test_fixture();
+        let input = repeat_n((), 500).with_reset(1).clock_pos_edge(100);
+        let vcd = uut.run(input).collect::<VcdFile>();
+        vcd.dump_to_file("axi4_bram.vcd")?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_bursts_obey_protocol() -> Result<(), RHDLError> {
+        let uut = test_fixture();
+        let input = repeat_n((), 500).with_reset(1).clock_pos_edge(100);
+        let mut checker = ProtocolChecker::<4, 32, 4, 32>::new(16);
+        let mut beats_read = 0;
+        for sample in uut.run(input).synchronous_sample() {
+            if sample.input.0.reset.any() {
+                continue;
+            }
+            let bus = sample.output;
+            checker
+                .check_write(&bus.write_mosi, &bus.write_miso)
+                .unwrap();
+            checker.check_read(&bus.read_mosi, &bus.read_miso).unwrap();
+            if bus.read_miso.rvalid && bus.read_mosi.rready {
+                beats_read += 1;
+            }
+        }
+        assert_eq!(beats_read, 12);
+        Ok(())
+    }
+}
@@ -0,0 +1,208 @@
+//! Types used by the AXI4 (burst) cores
+//!
+//! The data and strobe types are shared with the AXI4Lite
+//! stack, so the same restrictions apply: `DATA` must be
+//! either 32 or 64 bits wide, and `STRB` must be `DATA/8`.
+//! The `ID` width must be at least one bit.
+use rhdl::prelude::*;
+
+pub use crate::axi4lite::types::{response_codes, AxilStrobe, ResponseKind};
+
+/// The type of burst on the bus.  Use the
+/// constants in [burst_codes] to fill it in.
+pub type BurstKind = Bits<2>;
+
+/// The length of a burst, encoded as the
+/// number of beats minus one (i.e., `AxLEN`).
+pub type BurstLen = Bits<8>;
+
+/// The size of each beat of a burst, encoded
+/// as the log2 of the number of bytes (i.e., `AxSIZE`).
+pub type BurstSize = Bits<3>;
+
+/// Burst type codes
+pub mod burst_codes {
+    use rhdl::prelude::*;
+    /// Every beat of the burst uses the same address
+    pub const FIXED: b2 = bits(0);
+    /// The address increments with each beat of the burst
+    pub const INCR: b2 = bits(1);
+    /// The address increments and wraps at a boundary
+    /// given by the total size of the burst
+    pub const WRAP: b2 = bits(2);
+    /// Reserved - not a legal burst type
+    pub const RESERVED: b2 = bits(3);
+}
+
+#[derive(PartialEq, Debug, Digital, Clone, Copy, Default)]
+/// An address command for a burst
+///
+/// This is the payload of both the write address (`AW`)
+/// and the read address (`AR`) channels.
+pub struct AddrCommand<const ID: usize, const ADDR: usize>
+where
+    rhdl::bits::W<ID>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    /// Transaction ID
+    pub id: Bits<ID>,
+    /// Address of the first beat of the burst
+    pub addr: Bits<ADDR>,
+    /// Number of beats in the burst, minus one
+    pub len: BurstLen,
+    /// log2 of the number of bytes in each beat
+    pub size: BurstSize,
+    /// The burst type (see [burst_codes])
+    pub burst: BurstKind,
+}
+
+#[derive(PartialEq, Debug, Digital, Clone, Copy, Default)]
+/// A single beat of write data (the `W` channel)
+pub struct WriteBeat<const DATA: usize, const STRB: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+{
+    /// The data to write
+    pub data: Bits<DATA>,
+    /// The byte strobe for the data
+    pub strobe: AxilStrobe<STRB>,
+    /// Set on the last beat of the burst
+    pub last: bool,
+}
+
+#[derive(PartialEq, Debug, Digital, Clone, Copy, Default)]
+/// A write response (the `B` channel)
+pub struct WriteResponse<const ID: usize>
+where
+    rhdl::bits::W<ID>: BitWidth,
+{
+    /// The ID of the burst being acknowledged
+    pub id: Bits<ID>,
+    /// The response code (see [response_codes])
+    pub resp: ResponseKind,
+}
+
+#[derive(PartialEq, Debug, Digital, Clone, Copy, Default)]
+/// A single beat of read data (the `R` channel)
+pub struct ReadBeat<const ID: usize, const DATA: usize>
+where
+    rhdl::bits::W<ID>: BitWidth,
+    rhdl::bits::W<DATA>: BitWidth,
+{
+    /// The ID of the burst this beat belongs to
+    pub id: Bits<ID>,
+    /// The data read
+    pub data: Bits<DATA>,
+    /// The response code (see [response_codes])
+    pub resp: ResponseKind,
+    /// Set on the last beat of the burst
+    pub last: bool,
+}
+
+#[derive(PartialEq, Debug, Digital, Clone, Copy, Default)]
+/// MOSI signals for the write interface
+///
+/// These are outputs for the manager and inputs
+/// for the subordinate.
+pub struct WriteMOSI<const ID: usize, const DATA: usize, const STRB: usize, const ADDR: usize>
+where
+    rhdl::bits::W<ID>: BitWidth,
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    /// Write Address ID
+    pub awid: Bits<ID>,
+    /// Write Address
+    pub awaddr: Bits<ADDR>,
+    /// Write burst length
+    pub awlen: BurstLen,
+    /// Write burst size
+    pub awsize: BurstSize,
+    /// Write burst type
+    pub awburst: BurstKind,
+    /// Write Address valid
+    pub awvalid: bool,
+    /// Write Data
+    pub wdata: Bits<DATA>,
+    /// Write byte strobe
+    pub wstrb: AxilStrobe<STRB>,
+    /// Write last beat
+    pub wlast: bool,
+    /// Write Data valid
+    pub wvalid: bool,
+    /// Write Response ready
+    pub bready: bool,
+}
+
+#[derive(PartialEq, Debug, Digital, Clone, Copy, Default)]
+/// MISO signals for the write interface
+///
+/// These are inputs for the manager and outputs
+/// for the subordinate.
+pub struct WriteMISO<const ID: usize>
+where
+    rhdl::bits::W<ID>: BitWidth,
+{
+    /// Write Address ready
+    pub awready: bool,
+    /// Write Data ready
+    pub wready: bool,
+    /// Write Response ID
+    pub bid: Bits<ID>,
+    /// Write Response
+    pub bresp: ResponseKind,
+    /// Write Response valid
+    pub bvalid: bool,
+}
+
+#[derive(PartialEq, Debug, Digital, Clone, Copy, Default)]
+/// MOSI signals for the read interface
+///
+/// These are outputs for the manager and inputs
+/// for the subordinate.
+pub struct ReadMOSI<const ID: usize, const ADDR: usize>
+where
+    rhdl::bits::W<ID>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    /// Read Address ID
+    pub arid: Bits<ID>,
+    /// Read Address
+    pub araddr: Bits<ADDR>,
+    /// Read burst length
+    pub arlen: BurstLen,
+    /// Read burst size
+    pub arsize: BurstSize,
+    /// Read burst type
+    pub arburst: BurstKind,
+    /// Read Address valid
+    pub arvalid: bool,
+    /// Read Data ready
+    pub rready: bool,
+}
+
+#[derive(PartialEq, Debug, Digital, Clone, Copy, Default)]
+/// MISO signals for the read interface
+///
+/// These are inputs for the manager and outputs
+/// for the subordinate.
+pub struct ReadMISO<const ID: usize, const DATA: usize>
+where
+    rhdl::bits::W<ID>: BitWidth,
+    rhdl::bits::W<DATA>: BitWidth,
+{
+    /// Read Address ready
+    pub arready: bool,
+    /// Read Data ID
+    pub rid: Bits<ID>,
+    /// Read Data
+    pub rdata: Bits<DATA>,
+    /// Read Data response
+    pub rresp: ResponseKind,
+    /// Read last beat
+    pub rlast: bool,
+    /// Read Data valid
+    pub rvalid: bool,
+}
@@ -87,14 +87,25 @@ use super::{read::ReadController, write::WriteController};
 
 #[derive(PartialEq, Clone, Copy, Digital)]
 /// Make a blocking read or write request on an AXI bus
-pub enum BlockRequest {
+pub enum BlockRequest<const DATA: usize, const STRB: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     /// Make a blocking write request with the given [WriteCommand]
-    Write(WriteCommand),
+    Write(WriteCommand<DATA, STRB, ADDR>),
     /// Make a blocking read request to the given [AxilAddr]
-    Read(AxilAddr),
+    Read(AxilAddr<ADDR>),
 }
 
-impl Default for BlockRequest {
+impl<const DATA: usize, const STRB: usize, const ADDR: usize> Default
+    for BlockRequest<DATA, STRB, ADDR>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     fn default() -> Self {
         BlockRequest::Write(WriteCommand {
             addr: bits(0),
@@ -108,14 +119,20 @@ impl Default for BlockRequest {
 
 #[derive(PartialEq, Clone, Copy, Digital)]
 /// The response to a [BlockRequest]
-pub enum BlockResponse {
+pub enum BlockResponse<const DATA: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+{
     /// The response to a write [BlockRequest] in the form of a [WriteResult]
     Write(WriteResult),
     /// The response to a read [BlockRequest] in the form of a [ReadResult]
-    Read(ReadResult),
+    Read(ReadResult<DATA>),
 }
 
-impl Default for BlockResponse {
+impl<const DATA: usize> Default for BlockResponse<DATA>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+{
     fn default() -> Self {
         BlockResponse::Write(Err(crate::axi4lite::types::AXI4Error::DECERR))
     }
@@ -136,52 +153,84 @@ pub enum State {
 ///
 /// Executes a sequence of read/write commands provided
 /// at the input one at a time in a blocking fashion, and
-/// sources a stream with the output results.
-pub struct BlockReadWriteController {
-    inbuf: StreamBuffer<BlockRequest>,
-    write_controller: WriteController,
-    read_controller: ReadController,
-    outbuf: StreamBuffer<BlockResponse>,
+/// sources a stream with the output results.  The core is
+/// generic over the `DATA` and `ADDR` widths of the bus,
+/// and the `STRB` width must be `DATA/8`.
+pub struct BlockReadWriteController<const DATA: usize, const STRB: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    inbuf: StreamBuffer<BlockRequest<DATA, STRB, ADDR>>,
+    write_controller: WriteController<DATA, STRB, ADDR>,
+    read_controller: ReadController<DATA, ADDR>,
+    outbuf: StreamBuffer<BlockResponse<DATA>>,
     state: DFF<State>,
 }
 
 #[derive(PartialEq, Clone, Copy, Digital)]
 /// Input for the [BlockReadWriteController]
-pub struct In {
+pub struct In<const DATA: usize, const STRB: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     /// The [BlockRequest] input for the request stream
-    pub request: Option<BlockRequest>,
+    pub request: Option<BlockRequest<DATA, STRB, ADDR>>,
     /// Backpressure/ready signal for the response stream
-    pub resp_ready: Ready<BlockResponse>,
+    pub resp_ready: Ready<BlockResponse<DATA>>,
     /// The input side of the write AXI bus
     pub write_axi: WriteMISO,
     /// The input side of the read AXI bus
-    pub read_axi: ReadMISO,
+    pub read_axi: ReadMISO<DATA>,
 }
 
 #[derive(PartialEq, Clone, Copy, Digital)]
 /// Output for the [BlockReadWriteController]
-pub struct Out {
+pub struct Out<const DATA: usize, const STRB: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     /// The [BlockResponse] stream output
-    pub response: Option<BlockResponse>,
+    pub response: Option<BlockResponse<DATA>>,
     /// Backpressure/ready signal for the request stream
-    pub req_ready: Ready<BlockRequest>,
+    pub req_ready: Ready<BlockRequest<DATA, STRB, ADDR>>,
     /// The output side of the write AXI bus
-    pub write_axi: WriteMOSI,
+    pub write_axi: WriteMOSI<DATA, STRB, ADDR>,
     /// The output side of the read AXI bus
-    pub read_axi: ReadMOSI,
+    pub read_axi: ReadMOSI<ADDR>,
 }
 
-impl SynchronousIO for BlockReadWriteController {
-    type I = In;
-    type O = Out;
-    type Kernel = kernel;
+impl<const DATA: usize, const STRB: usize, const ADDR: usize> SynchronousIO
+    for BlockReadWriteController<DATA, STRB, ADDR>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    type I = In<DATA, STRB, ADDR>;
+    type O = Out<DATA, STRB, ADDR>;
+    type Kernel = kernel<DATA, STRB, ADDR>;
 }
 
 #[kernel]
 #[doc(hidden)]
-pub fn kernel(_cr: ClockReset, i: In, q: Q) -> (Out, D) {
-    let mut d = D::dont_care();
-    let mut o = Out::dont_care();
+pub fn kernel<const DATA: usize, const STRB: usize, const ADDR: usize>(
+    _cr: ClockReset,
+    i: In<DATA, STRB, ADDR>,
+    q: Q<DATA, STRB, ADDR>,
+) -> (Out<DATA, STRB, ADDR>, D<DATA, STRB, ADDR>)
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    let mut d = D::<DATA, STRB, ADDR>::dont_care();
+    let mut o = Out::<DATA, STRB, ADDR>::dont_care();
     d.state = q.state;
     d.inbuf.data = i.request;
     // First check to see if we can capture a response
@@ -195,7 +244,7 @@ pub fn kernel(_cr: ClockReset, i: In, q: Q) -> (Out, D) {
         State::Idle => {}
         State::Reading => {
             if let Some(resp) = q.read_controller.resp_data {
-                d.outbuf.data = Some(BlockResponse::Read(resp));
+                d.outbuf.data = Some(BlockResponse::<DATA>::Read(resp));
                 if can_accept {
                     d.state = State::Idle;
                     will_unload = true;
@@ -204,7 +253,7 @@ pub fn kernel(_cr: ClockReset, i: In, q: Q) -> (Out, D) {
         }
         State::Writing => {
             if let Some(resp) = q.write_controller.resp_data {
-                d.outbuf.data = Some(BlockResponse::Write(resp));
+                d.outbuf.data = Some(BlockResponse::<DATA>::Write(resp));
                 if can_accept {
                     d.state = State::Idle;
                     will_unload = true;
@@ -216,7 +265,7 @@ pub fn kernel(_cr: ClockReset, i: In, q: Q) -> (Out, D) {
     let will_start = q.write_controller.req_ready.raw
         & q.read_controller.req_ready.raw
         & ((q.state == State::Idle) | will_unload)
-        & is_some::<BlockRequest>(q.inbuf.data);
+        & is_some::<BlockRequest<DATA, STRB, ADDR>>(q.inbuf.data);
     // Feed the write and read controllers
     d.write_controller.axi = i.write_axi;
     d.write_controller.req_data = None;
@@ -226,11 +275,11 @@ pub fn kernel(_cr: ClockReset, i: In, q: Q) -> (Out, D) {
     if will_start {
         if let Some(req) = q.inbuf.data {
             match req {
-                BlockRequest::Read(read) => {
+                BlockRequest::<DATA, STRB, ADDR>::Read(read) => {
                     d.state = State::Reading;
                     d.read_controller.req_data = Some(read);
                 }
-                BlockRequest::Write(write) => {
+                BlockRequest::<DATA, STRB, ADDR>::Write(write) => {
                     d.state = State::Writing;
                     d.write_controller.req_data = Some(write);
                 }
@@ -253,7 +302,7 @@ mod tests {
 
     #[test]
     fn no_combinatorial_paths() -> miette::Result<()> {
-        let uut = BlockReadWriteController::default();
+        let uut = BlockReadWriteController::<32, 4, 32>::default();
         let descriptor = uut.descriptor(ScopedName::top())?;
         let hdl = descriptor.hdl()?;
         let module = hdl.modules.pretty();
@@ -122,18 +122,28 @@ use rhdl::prelude::*;
 /// This core sinks a RHDL stream of
 /// addresses into AXI bus read transactions, and
 /// converts the resulting stream of read responses into
-/// a source stream of [ReadResult].
-pub struct ReadController {
-    inbuf: Rhdl2Axi<AxilAddr>,
-    map: Map<ReadResponse, ReadResult>,
-    outbuf: Axi2Rhdl<ReadResponse>,
+/// a source stream of [ReadResult].  The core is generic
+/// over the `DATA` and `ADDR` widths of the bus.
+pub struct ReadController<const DATA: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    inbuf: Rhdl2Axi<AxilAddr<ADDR>>,
+    map: Map<ReadResponse<DATA>, ReadResult<DATA>>,
+    outbuf: Axi2Rhdl<ReadResponse<DATA>>,
 }
 
-impl Default for ReadController {
+impl<const DATA: usize, const ADDR: usize> Default for ReadController<DATA, ADDR>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     fn default() -> Self {
         Self {
             inbuf: Rhdl2Axi::default(),
-            map: Map::try_new::<map_result>().expect("ICE! Compilation of `map_result` failed!"),
+            map: Map::try_new::<map_result<DATA>>()
+                .expect("ICE! Compilation of `map_result` failed!"),
             outbuf: Axi2Rhdl::default(),
         }
     }
@@ -141,53 +151,76 @@ impl Default for ReadController {
 
 #[kernel]
 #[doc(hidden)]
-pub fn map_result(_cr: ClockReset, resp: ReadResponse) -> ReadResult {
+pub fn map_result<const DATA: usize>(_cr: ClockReset, resp: ReadResponse<DATA>) -> ReadResult<DATA>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+{
     match resp.resp {
-        response_codes::OKAY => ReadResult::Ok(resp.data),
-        response_codes::EXOKAY => ReadResult::Ok(resp.data),
-        response_codes::DECERR => ReadResult::Err(AXI4Error::DECERR),
-        response_codes::SLVERR => ReadResult::Err(AXI4Error::SLVERR),
-        _ => ReadResult::Err(AXI4Error::DECERR),
+        response_codes::OKAY => Ok(resp.data),
+        response_codes::EXOKAY => Ok(resp.data),
+        response_codes::DECERR => Err(AXI4Error::DECERR),
+        response_codes::SLVERR => Err(AXI4Error::SLVERR),
+        _ => Err(AXI4Error::DECERR),
     }
 }
 
 #[derive(PartialEq, Debug, Digital, Clone, Copy)]
 /// Input for the [ReadController] core
-pub struct In {
+pub struct In<const DATA: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     /// AXI signals from bus
-    pub axi: ReadMISO,
+    pub axi: ReadMISO<DATA>,
     /// Request data stream
-    pub req_data: Option<AxilAddr>,
+    pub req_data: Option<AxilAddr<ADDR>>,
     /// Response ready signal
-    pub resp_ready: Ready<ReadResult>,
+    pub resp_ready: Ready<ReadResult<DATA>>,
 }
 
 #[derive(PartialEq, Debug, Digital, Clone, Copy)]
 /// Output for the [ReadController] core
-pub struct Out {
+pub struct Out<const DATA: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     /// AXI signals to the bus
-    pub axi: ReadMOSI,
+    pub axi: ReadMOSI<ADDR>,
     /// Request stream ready signal
-    pub req_ready: Ready<AxilAddr>,
+    pub req_ready: Ready<AxilAddr<ADDR>>,
     /// Response data stream
-    pub resp_data: Option<ReadResult>,
+    pub resp_data: Option<ReadResult<DATA>>,
 }
 
-impl SynchronousIO for ReadController {
-    type I = In;
-    type O = Out;
-    type Kernel = kernel;
+impl<const DATA: usize, const ADDR: usize> SynchronousIO for ReadController<DATA, ADDR>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    type I = In<DATA, ADDR>;
+    type O = Out<DATA, ADDR>;
+    type Kernel = kernel<DATA, ADDR>;
 }
 
 #[kernel]
 #[doc(hidden)]
-pub fn kernel(_cr: ClockReset, i: In, q: Q) -> (Out, D) {
-    let mut d = D::dont_care();
+pub fn kernel<const DATA: usize, const ADDR: usize>(
+    _cr: ClockReset,
+    i: In<DATA, ADDR>,
+    q: Q<DATA, ADDR>,
+) -> (Out<DATA, ADDR>, D<DATA, ADDR>)
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    let mut d = D::<DATA, ADDR>::dont_care();
     // Wire up the input buffer inputs
     d.inbuf.data = i.req_data;
     d.inbuf.tready = i.axi.arready;
     // Wire up the output buffer inputs
-    d.outbuf.tdata = ReadResponse {
+    d.outbuf.tdata = ReadResponse::<DATA> {
         resp: i.axi.rresp,
         data: i.axi.rdata,
     };
@@ -197,7 +230,7 @@ pub fn kernel(_cr: ClockReset, i: In, q: Q) -> (Out, D) {
     d.map.data = q.outbuf.data;
     d.map.ready = i.resp_ready;
     // Wire up the axi outputs
-    let mut o = Out::dont_care();
+    let mut o = Out::<DATA, ADDR>::dont_care();
     o.req_ready = q.inbuf.ready;
     o.resp_data = q.map.data;
     o.axi.araddr = q.inbuf.tdata;
@@ -212,14 +245,14 @@ mod tests {
 
     #[test]
     fn no_combinatorial_paths() -> miette::Result<()> {
-        let uut = ReadController::default();
+        let uut = ReadController::<32, 32>::default();
         drc::no_combinatorial_paths(&uut)?;
         Ok(())
     }
 
     #[test]
     fn test_compile() -> miette::Result<()> {
-        compile_design::<map_result>(CompilationMode::Synchronous)?;
+        compile_design::<map_result<32>>(CompilationMode::Synchronous)?;
         Ok(())
     }
 }
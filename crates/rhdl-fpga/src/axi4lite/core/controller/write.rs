@@ -132,8 +132,8 @@ use crate::{
     axi4lite::{
         stream::{axi_to_rhdl::Axi2Rhdl, rhdl_to_axi::Rhdl2Axi},
         types::{
-            response_codes, AXI4Error, AxilAddr, ResponseKind, StrobedData, WriteCommand,
-            WriteMISO, WriteMOSI, WriteResult,
+            check_widths, response_codes, AXI4Error, AxilAddr, ResponseKind, StrobedData,
+            WriteCommand, WriteMISO, WriteMOSI, WriteResult,
         },
     },
     stream::{map::Map, ready, tee::Tee, Ready},
@@ -146,17 +146,31 @@ use crate::{
 /// This core sinks a RHDL stream of [WriteCommand]
 /// structs, and converts them into AXI write transactions
 /// and then converts the resulting responses into a stream
-/// of [WriteResult].
-pub struct WriteController {
-    tee: Tee<AxilAddr, StrobedData>,
-    addr_buf: Rhdl2Axi<AxilAddr>,
-    data_buf: Rhdl2Axi<StrobedData>,
+/// of [WriteResult].  The core is generic over the
+/// `DATA` and `ADDR` widths of the bus, and the
+/// `STRB` width must be `DATA/8`.
+pub struct WriteController<const DATA: usize, const STRB: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    tee: Tee<AxilAddr<ADDR>, StrobedData<DATA, STRB>>,
+    addr_buf: Rhdl2Axi<AxilAddr<ADDR>>,
+    data_buf: Rhdl2Axi<StrobedData<DATA, STRB>>,
     map: Map<ResponseKind, WriteResult>,
     outbuf: Axi2Rhdl<ResponseKind>,
 }
 
-impl Default for WriteController {
+impl<const DATA: usize, const STRB: usize, const ADDR: usize> Default
+    for WriteController<DATA, STRB, ADDR>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     fn default() -> Self {
+        check_widths::<DATA, STRB>();
         Self {
             tee: Tee::default(),
             addr_buf: Rhdl2Axi::default(),
@@ -180,36 +194,61 @@ pub fn map_result(_cr: ClockReset, resp: ResponseKind) -> WriteResult {
 
 #[derive(PartialEq, Debug, Digital, Clone, Copy)]
 /// Input for the [WriteController] core
-pub struct In {
+pub struct In<const DATA: usize, const STRB: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     /// AXI signals from the bus
     pub axi: WriteMISO,
     /// Request data stream
-    pub req_data: Option<WriteCommand>,
+    pub req_data: Option<WriteCommand<DATA, STRB, ADDR>>,
     /// Response ready signal
     pub resp_ready: Ready<WriteResult>,
 }
 
 #[derive(PartialEq, Debug, Digital, Clone, Copy)]
 /// Output from the [WriteController] core
-pub struct Out {
+pub struct Out<const DATA: usize, const STRB: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     /// AXI signals to the bus
-    pub axi: WriteMOSI,
+    pub axi: WriteMOSI<DATA, STRB, ADDR>,
     /// Request stream ready signal
-    pub req_ready: Ready<WriteCommand>,
+    pub req_ready: Ready<WriteCommand<DATA, STRB, ADDR>>,
     /// Response data stream
     pub resp_data: Option<WriteResult>,
 }
 
-impl SynchronousIO for WriteController {
-    type I = In;
-    type O = Out;
-    type Kernel = kernel;
+impl<const DATA: usize, const STRB: usize, const ADDR: usize> SynchronousIO
+    for WriteController<DATA, STRB, ADDR>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    type I = In<DATA, STRB, ADDR>;
+    type O = Out<DATA, STRB, ADDR>;
+    type Kernel = kernel<DATA, STRB, ADDR>;
 }
 
 #[kernel]
 #[doc(hidden)]
-pub fn kernel(_cr: ClockReset, i: In, q: Q) -> (Out, D) {
-    let mut d = D::dont_care();
+pub fn kernel<const DATA: usize, const STRB: usize, const ADDR: usize>(
+    _cr: ClockReset,
+    i: In<DATA, STRB, ADDR>,
+    q: Q<DATA, STRB, ADDR>,
+) -> (Out<DATA, STRB, ADDR>, D<DATA, STRB, ADDR>)
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    let mut d = D::<DATA, STRB, ADDR>::dont_care();
     // Connection 1
     d.tee.data = None;
     if let Some(cmd) = i.req_data {
@@ -223,9 +262,9 @@ pub fn kernel(_cr: ClockReset, i: In, q: Q) -> (Out, D) {
     d.data_buf.data = q.tee.t_data;
     // Connection 5
     d.tee.t_ready = q.data_buf.ready;
-    let mut o = Out::dont_care();
+    let mut o = Out::<DATA, STRB, ADDR>::dont_care();
     // Connection 6
-    o.req_ready = ready::<WriteCommand>(q.tee.ready.raw);
+    o.req_ready = ready::<WriteCommand<DATA, STRB, ADDR>>(q.tee.ready.raw);
     // Connection 7
     o.axi.awaddr = q.addr_buf.tdata;
     // Connection 8
@@ -263,7 +302,7 @@ mod tests {
 
     #[test]
     fn no_combinatorial_paths() -> miette::Result<()> {
-        let uut = WriteController::default();
+        let uut = WriteController::<32, 4, 32>::default();
         drc::no_combinatorial_paths(&uut)?;
         Ok(())
     }
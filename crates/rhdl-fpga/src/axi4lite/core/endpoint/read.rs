@@ -11,7 +11,7 @@
 //!# Schematic Symbol
 //!
 //! Here is the symbol for the core. It provides a source stream
-//! for the addresses (which are `b32`), and sinks a stream of
+//! for the addresses (which are `Bits<ADDR>`), and sinks a stream of
 //! values (which are [ReadResult]).
 //!
 #![doc = badascii_formal!(r"
@@ -110,8 +110,7 @@ use crate::{
     axi4lite::{
         stream::{axi_to_rhdl::Axi2Rhdl, rhdl_to_axi::Rhdl2Axi},
         types::{
-            response_codes, AXI4Error, AxilAddr, AxilData, ReadMISO, ReadMOSI, ReadResponse,
-            ReadResult,
+            response_codes, AXI4Error, AxilAddr, ReadMISO, ReadMOSI, ReadResponse, ReadResult,
         },
     },
     stream::{map::Map, Ready},
@@ -124,18 +123,28 @@ use rhdl::prelude::*;
 ///
 /// This core converts the AXI bus signals into a RHDL stream
 /// source of read addresses, and a RHDL stream sink of
-/// read results.  
-pub struct ReadEndpoint {
-    inbuf: Axi2Rhdl<AxilData>,
-    map: Map<ReadResult, ReadResponse>,
-    outbuf: Rhdl2Axi<ReadResponse>,
+/// read results.  The core is generic over the `DATA` and
+/// `ADDR` widths of the bus.
+pub struct ReadEndpoint<const DATA: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    inbuf: Axi2Rhdl<AxilAddr<ADDR>>,
+    map: Map<ReadResult<DATA>, ReadResponse<DATA>>,
+    outbuf: Rhdl2Axi<ReadResponse<DATA>>,
 }
 
-impl Default for ReadEndpoint {
+impl<const DATA: usize, const ADDR: usize> Default for ReadEndpoint<DATA, ADDR>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     fn default() -> Self {
         Self {
             inbuf: Axi2Rhdl::default(),
-            map: Map::try_new::<map_result>().expect("ICE! Compilation of map_result failed!"),
+            map: Map::try_new::<map_result<DATA>>()
+                .expect("ICE! Compilation of map_result failed!"),
             outbuf: Rhdl2Axi::default(),
         }
     }
@@ -143,18 +152,21 @@ impl Default for ReadEndpoint {
 
 #[kernel]
 #[doc(hidden)]
-pub fn map_result(_cr: ClockReset, res: ReadResult) -> ReadResponse {
+pub fn map_result<const DATA: usize>(_cr: ClockReset, res: ReadResult<DATA>) -> ReadResponse<DATA>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+{
     match res {
-        ReadResult::Ok(data) => ReadResponse {
+        Ok(data) => ReadResponse::<DATA> {
             resp: response_codes::OKAY,
             data,
         },
-        ReadResult::Err(err) => match err {
-            AXI4Error::SLVERR => ReadResponse {
+        Err(err) => match err {
+            AXI4Error::SLVERR => ReadResponse::<DATA> {
                 resp: response_codes::SLVERR,
                 data: bits(0),
             },
-            AXI4Error::DECERR => ReadResponse {
+            AXI4Error::DECERR => ReadResponse::<DATA> {
                 resp: response_codes::DECERR,
                 data: bits(0),
             },
@@ -164,36 +176,56 @@ pub fn map_result(_cr: ClockReset, res: ReadResult) -> ReadResponse {
 
 #[derive(PartialEq, Debug, Digital, Clone, Copy)]
 /// Input for the [AxiReadStreams] core
-pub struct In {
+pub struct In<const DATA: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     /// AXI signals for core
-    pub axi: ReadMOSI,
+    pub axi: ReadMOSI<ADDR>,
     /// Request stream ready signal from core
-    pub req_ready: Ready<AxilAddr>,
+    pub req_ready: Ready<AxilAddr<ADDR>>,
     /// Response data stream from core
-    pub resp_data: Option<ReadResult>,
+    pub resp_data: Option<ReadResult<DATA>>,
 }
 
 #[derive(PartialEq, Debug, Digital, Clone, Copy)]
 /// Output from the [AxiReadStreams] core
-pub struct Out {
+pub struct Out<const DATA: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     /// AXI signals for core
-    pub axi: ReadMISO,
+    pub axi: ReadMISO<DATA>,
     /// Request data to the core
-    pub req_data: Option<AxilAddr>,
+    pub req_data: Option<AxilAddr<ADDR>>,
     /// Response ready signal to core
-    pub resp_ready: Ready<ReadResult>,
+    pub resp_ready: Ready<ReadResult<DATA>>,
 }
 
-impl SynchronousIO for ReadEndpoint {
-    type I = In;
-    type O = Out;
-    type Kernel = kernel; //NoKernel3<ClockReset, In, Q, (Out, D)>;
+impl<const DATA: usize, const ADDR: usize> SynchronousIO for ReadEndpoint<DATA, ADDR>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    type I = In<DATA, ADDR>;
+    type O = Out<DATA, ADDR>;
+    type Kernel = kernel<DATA, ADDR>;
 }
 
 #[kernel]
 #[doc(hidden)]
-pub fn kernel(_cr: ClockReset, i: In, q: Q) -> (Out, D) {
-    let mut d = D::dont_care();
+pub fn kernel<const DATA: usize, const ADDR: usize>(
+    _cr: ClockReset,
+    i: In<DATA, ADDR>,
+    q: Q<DATA, ADDR>,
+) -> (Out<DATA, ADDR>, D<DATA, ADDR>)
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    let mut d = D::<DATA, ADDR>::dont_care();
     // Input buffer inputs
     d.inbuf.tdata = i.axi.araddr;
     d.inbuf.tvalid = i.axi.arvalid;
@@ -205,7 +237,7 @@ pub fn kernel(_cr: ClockReset, i: In, q: Q) -> (Out, D) {
     d.outbuf.tready = i.axi.rready;
     d.outbuf.data = q.map.data;
     // Core outputs
-    let mut o = Out::dont_care();
+    let mut o = Out::<DATA, ADDR>::dont_care();
     o.axi.arready = q.inbuf.tready;
     o.axi.rdata = q.outbuf.tdata.data;
     o.axi.rresp = q.outbuf.tdata.resp;
@@ -221,7 +253,7 @@ mod tests {
 
     #[test]
     fn no_combinatorial_paths() -> miette::Result<()> {
-        let uut = ReadEndpoint::default();
+        let uut = ReadEndpoint::<32, 32>::default();
         drc::no_combinatorial_paths(&uut)?;
         Ok(())
     }
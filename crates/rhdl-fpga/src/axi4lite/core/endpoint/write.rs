@@ -133,8 +133,8 @@ use crate::{
     axi4lite::{
         stream::{axi_to_rhdl::Axi2Rhdl, rhdl_to_axi::Rhdl2Axi},
         types::{
-            response_codes, AXI4Error, AxilAddr, ResponseKind, StrobedData, WriteCommand,
-            WriteMISO, WriteMOSI, WriteResult,
+            check_widths, response_codes, AXI4Error, AxilAddr, ResponseKind, StrobedData,
+            WriteCommand, WriteMISO, WriteMOSI, WriteResult,
         },
     },
     stream::{map::Map, zip::Zip, Ready},
@@ -147,17 +147,31 @@ use crate::{
 /// This core translates an AXI write bus into
 /// a source RHDL stream of [WriteCommand] structs,
 /// and then sinks a stream of [WriteResult] structs
-/// back to the AXI bus.
-pub struct WriteEndpoint {
-    addr_buf: Axi2Rhdl<AxilAddr>,
-    data_buf: Axi2Rhdl<StrobedData>,
-    zip: Zip<AxilAddr, StrobedData>,
+/// back to the AXI bus.  The core is generic over
+/// the `DATA` and `ADDR` widths of the bus, and the
+/// `STRB` width must be `DATA/8`.
+pub struct WriteEndpoint<const DATA: usize, const STRB: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    addr_buf: Axi2Rhdl<AxilAddr<ADDR>>,
+    data_buf: Axi2Rhdl<StrobedData<DATA, STRB>>,
+    zip: Zip<AxilAddr<ADDR>, StrobedData<DATA, STRB>>,
     map: Map<WriteResult, ResponseKind>,
     resp_buf: Rhdl2Axi<ResponseKind>,
 }
 
-impl Default for WriteEndpoint {
+impl<const DATA: usize, const STRB: usize, const ADDR: usize> Default
+    for WriteEndpoint<DATA, STRB, ADDR>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     fn default() -> Self {
+        check_widths::<DATA, STRB>();
         Self {
             addr_buf: Axi2Rhdl::default(),
             data_buf: Axi2Rhdl::default(),
@@ -182,37 +196,62 @@ pub fn map_result(_cr: ClockReset, resp: WriteResult) -> ResponseKind {
 
 #[derive(PartialEq, Debug, Digital, Clone, Copy)]
 /// Input for the [WriteEndpoint] core
-pub struct In {
+pub struct In<const DATA: usize, const STRB: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     /// AXI signals from the bus
-    pub axi: WriteMOSI,
+    pub axi: WriteMOSI<DATA, STRB, ADDR>,
     /// request stream ready signal
-    pub req_ready: Ready<WriteCommand>,
+    pub req_ready: Ready<WriteCommand<DATA, STRB, ADDR>>,
     /// Response data stream
     pub resp_data: Option<WriteResult>,
 }
 
 #[derive(PartialEq, Debug, Digital, Clone, Copy)]
 /// Output from the [WriteEndpoint] core
-pub struct Out {
+pub struct Out<const DATA: usize, const STRB: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     /// AXI signals to the bus
     pub axi: WriteMISO,
     /// Request data stream
-    pub req_data: Option<WriteCommand>,
+    pub req_data: Option<WriteCommand<DATA, STRB, ADDR>>,
     /// Response ready signal
     pub resp_ready: Ready<WriteResult>,
 }
 
-impl SynchronousIO for WriteEndpoint {
-    type I = In;
-    type O = Out;
-    type Kernel = kernel;
+impl<const DATA: usize, const STRB: usize, const ADDR: usize> SynchronousIO
+    for WriteEndpoint<DATA, STRB, ADDR>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    type I = In<DATA, STRB, ADDR>;
+    type O = Out<DATA, STRB, ADDR>;
+    type Kernel = kernel<DATA, STRB, ADDR>;
 }
 
 #[kernel]
 #[doc(hidden)]
-pub fn kernel(_cr: ClockReset, i: In, q: Q) -> (Out, D) {
-    let mut d = D::dont_care();
-    let mut o = Out::dont_care();
+pub fn kernel<const DATA: usize, const STRB: usize, const ADDR: usize>(
+    _cr: ClockReset,
+    i: In<DATA, STRB, ADDR>,
+    q: Q<DATA, STRB, ADDR>,
+) -> (Out<DATA, STRB, ADDR>, D<DATA, STRB, ADDR>)
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    let mut d = D::<DATA, STRB, ADDR>::dont_care();
+    let mut o = Out::<DATA, STRB, ADDR>::dont_care();
     // Connection 1.
     d.addr_buf.tdata = i.axi.awaddr;
     // Connection 2.
@@ -237,7 +276,7 @@ pub fn kernel(_cr: ClockReset, i: In, q: Q) -> (Out, D) {
     // Connection 11
     o.req_data = None;
     if let Some((addr, strobed_data)) = q.zip.data {
-        o.req_data = Some(WriteCommand { addr, strobed_data });
+        o.req_data = Some(WriteCommand::<DATA, STRB, ADDR> { addr, strobed_data });
     }
     // Connection 12
     d.zip.ready.raw = i.req_ready.raw;
@@ -264,7 +303,7 @@ mod tests {
 
     #[test]
     fn no_combinatorial_paths() -> miette::Result<()> {
-        let uut = WriteEndpoint::default();
+        let uut = WriteEndpoint::<32, 4, 32>::default();
         drc::no_combinatorial_paths(&uut)?;
         Ok(())
     }
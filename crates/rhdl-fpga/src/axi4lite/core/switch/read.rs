@@ -59,7 +59,7 @@ pub enum State {
 }
 
 /// The input address along with the port to send it to
-pub type Command = Result<(b4, AxilAddr), AXI4Error>;
+pub type Command<const ADDR: usize> = Result<(b4, AxilAddr<ADDR>), AXI4Error>;
 
 #[derive(Clone, Synchronous, SynchronousDQ)]
 #[rhdl(dq_no_prefix)]
@@ -72,30 +72,40 @@ pub type Command = Result<(b4, AxilAddr), AXI4Error>;
 /// a maximum of 16 controllers.  If you need more
 /// then consider cascading them or writing your own.
 /// The maximum pending number of transactions is
-/// also limited to 255 (which is lot!).
-pub struct ReadSwitch<const N: usize> {
-    endpoint: ReadEndpoint,
-    controllers: [ReadController; N],
+/// also limited to 255 (which is lot!).  The switch
+/// is generic over the `DATA` and `ADDR` widths of the bus.
+pub struct ReadSwitch<const DATA: usize, const ADDR: usize, const N: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    endpoint: ReadEndpoint<DATA, ADDR>,
+    controllers: [ReadController<DATA, ADDR>; N],
     pending_count: DFF<b8>,
     state: DFF<State>,
-    decode: Map<AxilAddr, Command>,
-    limit: Map<Command, Command>,
-    xfer_out: Xfer<ReadResult>,
-    xfer_in: Xfer<Command>,
+    decode: Map<AxilAddr<ADDR>, Command<ADDR>>,
+    limit: Map<Command<ADDR>, Command<ADDR>>,
+    xfer_out: Xfer<ReadResult<DATA>>,
+    xfer_in: Xfer<Command<ADDR>>,
 }
 
-impl<const N: usize> ReadSwitch<N> {
+impl<const DATA: usize, const ADDR: usize, const N: usize> ReadSwitch<DATA, ADDR, N>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     /// Create a new AXI Read Switch with the
     /// provided routing function.
-    pub fn try_new<F: DigitalFn + DigitalFn2<A0 = ClockReset, A1 = AxilAddr, O = Command>>(
-    ) -> Result<Self, RHDLError> {
+    pub fn try_new<
+        F: DigitalFn + DigitalFn2<A0 = ClockReset, A1 = AxilAddr<ADDR>, O = Command<ADDR>>,
+    >() -> Result<Self, RHDLError> {
         Ok(Self {
             endpoint: ReadEndpoint::default(),
             controllers: core::array::from_fn(|_| ReadController::default()),
             pending_count: DFF::default(),
             state: DFF::new(State::Idle),
             decode: Map::try_new::<F>()?,
-            limit: Map::try_new::<range_check<N>>()?,
+            limit: Map::try_new::<range_check<ADDR, N>>()?,
             xfer_out: Xfer::default(),
             xfer_in: Xfer::default(),
         })
@@ -104,10 +114,16 @@ impl<const N: usize> ReadSwitch<N> {
 
 #[kernel]
 #[doc(hidden)]
-pub fn range_check<const N: usize>(_cr: ClockReset, cmd: Command) -> Command {
+pub fn range_check<const ADDR: usize, const N: usize>(
+    _cr: ClockReset,
+    cmd: Command<ADDR>,
+) -> Command<ADDR>
+where
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     match cmd {
         Ok((port, address)) => {
-            if port < bits(N as u128) {
+            if port < b4(N as u128) {
                 Ok((port, address))
             } else {
                 Err(AXI4Error::DECERR)
@@ -119,33 +135,54 @@ pub fn range_check<const N: usize>(_cr: ClockReset, cmd: Command) -> Command {
 
 /// Input for the Read switch
 #[derive(PartialEq, Clone, Copy, Digital)]
-pub struct In<const N: usize> {
+pub struct In<const DATA: usize, const ADDR: usize, const N: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     /// AXI bus connection to the endpoint (subordinate interface)
-    pub endpoint_0: ReadMOSI,
+    pub endpoint_0: ReadMOSI<ADDR>,
     /// AXI bus connection to the controllers (manager interfaces)
-    pub controllers: [ReadMISO; N],
+    pub controllers: [ReadMISO<DATA>; N],
 }
 
 /// Output from the Read Switch
 #[derive(PartialEq, Clone, Copy, Digital)]
-pub struct Out<const N: usize> {
+pub struct Out<const DATA: usize, const ADDR: usize, const N: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     /// AXI bus connection from the endpoint (subordinate interface)
-    pub endpoint_0: ReadMISO,
+    pub endpoint_0: ReadMISO<DATA>,
     /// AXI bus connection from the controllers (manager interfaces)
-    pub controllers: [ReadMOSI; N],
+    pub controllers: [ReadMOSI<ADDR>; N],
 }
 
-impl<const N: usize> SynchronousIO for ReadSwitch<N> {
-    type I = In<N>;
-    type O = Out<N>;
-    type Kernel = kernel<N>;
+impl<const DATA: usize, const ADDR: usize, const N: usize> SynchronousIO
+    for ReadSwitch<DATA, ADDR, N>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    type I = In<DATA, ADDR, N>;
+    type O = Out<DATA, ADDR, N>;
+    type Kernel = kernel<DATA, ADDR, N>;
 }
 
 #[kernel]
 #[doc(hidden)]
-pub fn kernel<const N: usize>(_cr: ClockReset, i: In<N>, q: Q<N>) -> (Out<N>, D<N>) {
-    let mut d = D::<N>::dont_care();
-    let mut o = Out::<N>::dont_care();
+pub fn kernel<const DATA: usize, const ADDR: usize, const N: usize>(
+    _cr: ClockReset,
+    i: In<DATA, ADDR, N>,
+    q: Q<DATA, ADDR, N>,
+) -> (Out<DATA, ADDR, N>, D<DATA, ADDR, N>)
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    let mut d = D::<DATA, ADDR, N>::dont_care();
+    let mut o = Out::<DATA, ADDR, N>::dont_care();
     d.state = q.state;
     // Connect the endpoint AXI busses
     d.endpoint.axi = i.endpoint_0;
@@ -263,21 +300,21 @@ mod tests {
     #[derive(Clone, Synchronous, SynchronousDQ)]
     #[rhdl(dq_no_prefix)]
     pub struct TestFixture {
-        source: SourceFromFn<AxilAddr>,
-        controller: ReadController,
-        switch: ReadSwitch<2>,
-        rom_0: AxiRom<4>,
-        rom_1: AxiRom<4>,
+        source: SourceFromFn<AxilAddr<32>>,
+        controller: ReadController<32, 32>,
+        switch: ReadSwitch<32, 32, 2>,
+        rom_0: AxiRom<32, 32, 4>,
+        rom_1: AxiRom<32, 32, 4>,
     }
 
     impl SynchronousIO for TestFixture {
         type I = ();
-        type O = Option<ReadResult>;
+        type O = Option<ReadResult<32>>;
         type Kernel = kernel;
     }
 
     #[kernel]
-    pub fn kernel(_cr: ClockReset, _i: (), q: Q) -> (Option<ReadResult>, D) {
+    pub fn kernel(_cr: ClockReset, _i: (), q: Q) -> (Option<ReadResult<32>>, D) {
         let mut d = D::dont_care();
         d.controller.req_data = q.source;
         d.source = q.controller.req_ready;
@@ -292,8 +329,8 @@ mod tests {
         (o, d)
     }
 
-    const ROM0_BASE: AxilAddr = bits(0x4_000_000);
-    const ROM1_BASE: AxilAddr = bits(0x6_000_000);
+    const ROM0_BASE: AxilAddr<32> = bits(0x4_000_000);
+    const ROM1_BASE: AxilAddr<32> = bits(0x6_000_000);
     const ROM0_DATA: [b32; 4] = [
         bits(0xDEAD_BEEF),
         bits(0xBABE_FEED),
@@ -309,7 +346,7 @@ mod tests {
 
     // The decode function
     #[kernel]
-    pub fn decode_addr(_cr: ClockReset, req: AxilAddr) -> Command {
+    pub fn decode_addr(_cr: ClockReset, req: AxilAddr<32>) -> Command<32> {
         let rom_0_active = req & ROM0_BASE == ROM0_BASE;
         let rom_1_active = req & ROM1_BASE == ROM1_BASE;
         match (rom_0_active, rom_1_active) {
@@ -328,7 +365,7 @@ mod tests {
         ErrSwitch,
     }
 
-    fn sim(value: TestCase) -> ReadResult {
+    fn sim(value: TestCase) -> ReadResult<32> {
         match value {
             TestCase::Bank0(reg) => Ok(ROM0_DATA[reg.raw() as usize]),
             TestCase::Bank1(reg) => Ok(ROM1_DATA[reg.raw() as usize]),
@@ -336,7 +373,7 @@ mod tests {
         }
     }
 
-    impl From<TestCase> for AxilAddr {
+    impl From<TestCase> for AxilAddr<32> {
         fn from(value: TestCase) -> Self {
             match value {
                 TestCase::Bank0(reg) => ROM0_BASE + (reg.resize::<32>() << 2),
@@ -370,7 +407,7 @@ mod tests {
     #[test]
     #[ignore]
     fn test_no_combinatorial_paths() -> miette::Result<()> {
-        let switch: ReadSwitch<2> = ReadSwitch::try_new::<decode_addr>()?;
+        let switch: ReadSwitch<32, 32, 2> = ReadSwitch::try_new::<decode_addr>()?;
         drc::no_combinatorial_paths(&switch)?;
         Ok(())
     }
@@ -381,7 +418,7 @@ mod tests {
             .map(|x| b32(x as u128))
             .map(TestCase::from);
         let sink = rng.clone().map(sim);
-        let source = stalling(rng.map(AxilAddr::from), 0.1);
+        let source = stalling(rng.map(AxilAddr::<32>::from), 0.1);
         let uut = TestFixture {
             source: SourceFromFn::new(source),
             controller: ReadController::default(),
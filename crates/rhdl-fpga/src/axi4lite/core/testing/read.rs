@@ -27,31 +27,47 @@ use rhdl::prelude::*;
 use crate::{
     axi4lite::{
         core::{controller::read::ReadController, endpoint::read::ReadEndpoint},
-        types::ReadResult,
+        types::{AxilAddr, ReadResult},
     },
     stream::testing::{sink_from_fn::SinkFromFn, source_from_fn::SourceFromFn},
 };
 
 #[derive(Clone, Synchronous, SynchronousDQ)]
 #[rhdl(dq_no_prefix)]
-pub struct TestFixture {
-    req_source: SourceFromFn<b32>,
-    controller: ReadController,
-    endpoint: ReadEndpoint,
-    req_sink: SinkFromFn<b32>,
-    reply_source: SourceFromFn<ReadResult>,
-    reply_sink: SinkFromFn<ReadResult>,
+pub struct TestFixture<const DATA: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    req_source: SourceFromFn<AxilAddr<ADDR>>,
+    controller: ReadController<DATA, ADDR>,
+    endpoint: ReadEndpoint<DATA, ADDR>,
+    req_sink: SinkFromFn<AxilAddr<ADDR>>,
+    reply_source: SourceFromFn<ReadResult<DATA>>,
+    reply_sink: SinkFromFn<ReadResult<DATA>>,
 }
 
-impl SynchronousIO for TestFixture {
+impl<const DATA: usize, const ADDR: usize> SynchronousIO for TestFixture<DATA, ADDR>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     type I = ();
     type O = ();
-    type Kernel = kernel;
+    type Kernel = kernel<DATA, ADDR>;
 }
 
 #[kernel]
-pub fn kernel(_cr: ClockReset, _i: (), q: Q) -> ((), D) {
-    let mut d = D::dont_care();
+pub fn kernel<const DATA: usize, const ADDR: usize>(
+    _cr: ClockReset,
+    _i: (),
+    q: Q<DATA, ADDR>,
+) -> ((), D<DATA, ADDR>)
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    let mut d = D::<DATA, ADDR>::dont_care();
     // Wire the request source to the read controller
     d.controller.req_data = q.req_source;
     d.req_source = q.controller.req_ready;
@@ -78,26 +94,45 @@ mod tests {
 
     use super::*;
 
-    #[test]
-    fn test_controller_endpoint() -> Result<(), RHDLError> {
-        let rng = XorShift128::default().map(|x| bits(x as u128));
-        let address_sink = rng.clone();
-        let address = stalling(rng.clone(), 0.23);
-        let reply = rng.clone().map(ReadResult::Ok);
+    fn test_fixture<const DATA: usize, const ADDR: usize>() -> TestFixture<DATA, ADDR>
+    where
+        rhdl::bits::W<DATA>: BitWidth,
+        rhdl::bits::W<ADDR>: BitWidth,
+    {
+        let rng = XorShift128::default().map(|x| b32(x as u128));
+        let address = rng.clone().map(|x| x.resize::<ADDR>());
+        let address_sink = address.clone();
+        let address = stalling(address, 0.23);
+        let reply = rng.map(|x| Ok(x.resize::<DATA>()));
         let reply_sink = reply.clone();
         let reply = stalling(reply, 0.23);
-        let uut = TestFixture {
+        TestFixture {
             req_source: SourceFromFn::new(address),
             controller: ReadController::default(),
             endpoint: ReadEndpoint::default(),
             req_sink: SinkFromFn::new_from_iter(address_sink, 0.1),
             reply_source: SourceFromFn::new(reply),
             reply_sink: SinkFromFn::new_from_iter(reply_sink, 0.1),
-        };
+        }
+    }
+
+    #[test]
+    fn test_controller_endpoint() -> Result<(), RHDLError> {
+        let uut = test_fixture::<32, 32>();
         let input = repeat_n((), 250);
         let input = input.with_reset(1).clock_pos_edge(100);
         let vcd = uut.run(input).collect::<VcdFile>();
         vcd.dump_to_file("axi_read.vcd")?;
         Ok(())
     }
+
+    #[test]
+    fn test_controller_endpoint_64_bit_data() -> Result<(), RHDLError> {
+        let uut = test_fixture::<64, 16>();
+        let input = repeat_n((), 250);
+        let input = input.with_reset(1).clock_pos_edge(100);
+        let vcd = uut.run(input).collect::<VcdFile>();
+        vcd.dump_to_file("axi_read_64.vcd")?;
+        Ok(())
+    }
 }
@@ -38,24 +38,44 @@ use crate::{
 
 #[derive(Clone, Synchronous, SynchronousDQ)]
 #[rhdl(dq_no_prefix)]
-pub struct TestFixture {
-    req_source: SourceFromFn<WriteCommand>,
-    controller: WriteController,
-    endpoint: WriteEndpoint,
-    req_sink: SinkFromFn<WriteCommand>,
+pub struct TestFixture<const DATA: usize, const STRB: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    req_source: SourceFromFn<WriteCommand<DATA, STRB, ADDR>>,
+    controller: WriteController<DATA, STRB, ADDR>,
+    endpoint: WriteEndpoint<DATA, STRB, ADDR>,
+    req_sink: SinkFromFn<WriteCommand<DATA, STRB, ADDR>>,
     reply_source: SourceFromFn<WriteResult>,
     reply_sink: SinkFromFn<WriteResult>,
 }
 
-impl SynchronousIO for TestFixture {
+impl<const DATA: usize, const STRB: usize, const ADDR: usize> SynchronousIO
+    for TestFixture<DATA, STRB, ADDR>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     type I = ();
     type O = ();
-    type Kernel = kernel;
+    type Kernel = kernel<DATA, STRB, ADDR>;
 }
 
 #[kernel]
-pub fn kernel(_cr: ClockReset, _i: (), q: Q) -> ((), D) {
-    let mut d = D::dont_care();
+pub fn kernel<const DATA: usize, const STRB: usize, const ADDR: usize>(
+    _cr: ClockReset,
+    _i: (),
+    q: Q<DATA, STRB, ADDR>,
+) -> ((), D<DATA, STRB, ADDR>)
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    let mut d = D::<DATA, STRB, ADDR>::dont_care();
     d.controller.req_data = q.req_source; // 1
     d.req_source = q.controller.req_ready; // 2
     d.reply_sink = q.controller.resp_data; // 3
@@ -79,7 +99,7 @@ mod tests {
 
     use super::*;
 
-    fn write_commands() -> impl Iterator<Item = WriteCommand> {
+    fn write_commands() -> impl Iterator<Item = WriteCommand<32, 4, 32>> {
         XorShift128::default().map(|x| {
             let addr = x >> 16;
             let data = x & 0xFFFF;
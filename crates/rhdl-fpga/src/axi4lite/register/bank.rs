@@ -68,65 +68,104 @@ use crate::{
 /// This core provides a bank of dual-ported registers
 /// that has an AXI4Lite bus interface.  The base address
 /// of the registers are provided at construction time.
-pub struct AxiRegBank<const N: usize> {
-    read: ReadEndpoint,
-    write: WriteEndpoint,
-    data: [DFF<AxilData>; N],
-    address_low: Constant<AxilAddr>,
-    address_high: Constant<AxilAddr>,
+/// Each register is `DATA` bits wide, and occupies
+/// `DATA/8` bytes of the address space, with one of
+/// the `STRB` strobe bits per byte.
+pub struct AxiRegBank<const DATA: usize, const STRB: usize, const ADDR: usize, const N: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    read: ReadEndpoint<DATA, ADDR>,
+    write: WriteEndpoint<DATA, STRB, ADDR>,
+    data: [DFF<AxilData<DATA>>; N],
+    address_low: Constant<AxilAddr<ADDR>>,
+    address_high: Constant<AxilAddr<ADDR>>,
 }
 
-impl<const N: usize> AxiRegBank<N> {
+impl<const DATA: usize, const STRB: usize, const ADDR: usize, const N: usize>
+    AxiRegBank<DATA, STRB, ADDR, N>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     /// Create a register with the provided
     /// default value and the given register
     /// Where `address` is the address of the register
     /// and `reset_val` is the reset value of the
     /// register.
-    pub fn new(address: AxilAddr, reset_val: [AxilData; N]) -> Self {
+    pub fn new(address: AxilAddr<ADDR>, reset_val: [AxilData<DATA>; N]) -> Self {
         assert!(N > 0 && N <= 256);
         Self {
             read: ReadEndpoint::default(),
             write: WriteEndpoint::default(),
             data: core::array::from_fn(|i| DFF::new(reset_val[i])),
             address_low: Constant::new(address),
-            address_high: Constant::new(address + bits(N as u128 * 4)),
+            address_high: Constant::new(address + bits(N as u128 * (DATA as u128 / 8))),
         }
     }
 }
 
 #[derive(PartialEq, Debug, Digital, Clone, Copy)]
 /// Input for the [AxiRegBank]
-pub struct In {
+pub struct In<const DATA: usize, const STRB: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     /// AXI signals from the bus for reading
-    pub read_axi: ReadMOSI,
+    pub read_axi: ReadMOSI<ADDR>,
     /// AXI signals from the bus for writing
-    pub write_axi: WriteMOSI,
+    pub write_axi: WriteMOSI<DATA, STRB, ADDR>,
     /// Write data from the client side
-    pub data: Option<(b8, AxilData)>,
+    pub data: Option<(b8, AxilData<DATA>)>,
 }
 
 #[derive(PartialEq, Debug, Digital, Clone, Copy)]
 /// Output for the [AxiRegBank]
-pub struct Out<const N: usize> {
+pub struct Out<const DATA: usize, const N: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+{
     /// AXI signals to the bus for reading
-    pub read_axi: ReadMISO,
+    pub read_axi: ReadMISO<DATA>,
     /// AXI signals to the bus for writing
     pub write_axi: WriteMISO,
     /// Read data from the client side
-    pub data: [AxilData; N],
+    pub data: [AxilData<DATA>; N],
 }
 
-impl<const N: usize> SynchronousIO for AxiRegBank<N> {
-    type I = In;
-    type O = Out<N>;
-    type Kernel = kernel<N>;
+impl<const DATA: usize, const STRB: usize, const ADDR: usize, const N: usize> SynchronousIO
+    for AxiRegBank<DATA, STRB, ADDR, N>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    type I = In<DATA, STRB, ADDR>;
+    type O = Out<DATA, N>;
+    type Kernel = kernel<DATA, STRB, ADDR, N>;
 }
 
 #[kernel]
 #[doc(hidden)]
-pub fn kernel<const N: usize>(_cr: ClockReset, i: In, q: Q<N>) -> (Out<N>, D<N>) {
-    let mut d = D::<N>::dont_care();
-    let mut o = Out::<N>::dont_care();
+pub fn kernel<const DATA: usize, const STRB: usize, const ADDR: usize, const N: usize>(
+    _cr: ClockReset,
+    i: In<DATA, STRB, ADDR>,
+    q: Q<DATA, STRB, ADDR, N>,
+) -> (Out<DATA, N>, D<DATA, STRB, ADDR, N>)
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    let mut d = D::<DATA, STRB, ADDR, N>::dont_care();
+    let mut o = Out::<DATA, N>::dont_care();
+    // Each register takes DATA/8 bytes, so we need to right shift by 2 or 3
+    let word_shift = bits::<ADDR>(DATA as u128 / 32 + 1);
     d.write.axi = i.write_axi;
     o.write_axi = q.write.axi;
     d.read.axi = i.read_axi;
@@ -139,9 +178,8 @@ pub fn kernel<const N: usize>(_cr: ClockReset, i: In, q: Q<N>) -> (Out<N>, D<N>)
             if cmd.addr < q.address_low || cmd.addr > q.address_high {
                 d.write.resp_data = Some(Err(AXI4Error::DECERR));
             } else {
-                // Each register takes 4 bytes, so we need to right shfit by 2
-                let reg_ndx = (cmd.addr - q.address_low) >> 2;
-                let mask = strobe_to_mask(cmd.strobed_data.strobe);
+                let reg_ndx = (cmd.addr - q.address_low) >> word_shift;
+                let mask = strobe_to_mask::<DATA, STRB>(cmd.strobed_data.strobe);
                 d.data[reg_ndx] = (q.data[reg_ndx] & (!mask)) | (cmd.strobed_data.data & mask);
                 d.write.resp_data = Some(Ok(()));
             }
@@ -154,8 +192,7 @@ pub fn kernel<const N: usize>(_cr: ClockReset, i: In, q: Q<N>) -> (Out<N>, D<N>)
             if req < q.address_low || req > q.address_high {
                 d.read.resp_data = Some(Err(AXI4Error::DECERR));
             } else {
-                // Each register takes 4 bytes, so we need to right shift by 2
-                let reg_ndx = (req - q.address_low) >> 2;
+                let reg_ndx = (req - q.address_low) >> word_shift;
                 d.read.resp_data = Some(Ok(q.data[reg_ndx]));
             }
         }
@@ -173,7 +210,7 @@ mod tests {
 
     #[test]
     fn no_combinatorial_paths() -> miette::Result<()> {
-        let uut: AxiRegBank<4> = AxiRegBank::new(bits(0x4_000_000), Default::default());
+        let uut: AxiRegBank<32, 4, 32, 4> = AxiRegBank::new(bits(0x4_000_000), Default::default());
         drc::no_combinatorial_paths(&uut)?;
         Ok(())
     }
@@ -181,7 +218,7 @@ mod tests {
     #[test]
     fn test_compile_times() -> miette::Result<()> {
         let tic = std::time::Instant::now();
-        let uut: AxiRegBank<4> = AxiRegBank::new(bits(0x4_000_000), Default::default());
+        let uut: AxiRegBank<32, 4, 32, 4> = AxiRegBank::new(bits(0x4_000_000), Default::default());
         let _hdl = uut.descriptor("top".into())?;
         let toc = tic.elapsed();
         println!("HDL generation took {toc:?}");
@@ -58,57 +58,87 @@ use crate::{
 /// This core provides a single ROM that has an
 /// AXI4Lite bus interface.  The base (byte) address
 /// of the ROM is provided at construction time.
-pub struct AxiRom<const N: usize> {
-    read: ReadEndpoint,
-    data: [Constant<AxilData>; N],
-    address_low: Constant<AxilAddr>,
-    address_high: Constant<AxilAddr>,
+/// Each word of the ROM is `DATA` bits wide, and
+/// occupies `DATA/8` bytes of the address space.
+pub struct AxiRom<const DATA: usize, const ADDR: usize, const N: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    read: ReadEndpoint<DATA, ADDR>,
+    data: [Constant<AxilData<DATA>>; N],
+    address_low: Constant<AxilAddr<ADDR>>,
+    address_high: Constant<AxilAddr<ADDR>>,
 }
 
-impl<const N: usize> AxiRom<N> {
+impl<const DATA: usize, const ADDR: usize, const N: usize> AxiRom<DATA, ADDR, N>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     /// Create a register with the provided
     /// default value and the given register
     /// Where `address` is the address of the register
     /// and `reset_val` is the reset value of the
     /// register.
-    pub fn new(address: AxilAddr, reset_val: [AxilData; N]) -> Self {
+    pub fn new(address: AxilAddr<ADDR>, reset_val: [AxilData<DATA>; N]) -> Self {
         assert!(N > 0 && N <= 256);
         Self {
             read: ReadEndpoint::default(),
             data: core::array::from_fn(|i| Constant::new(reset_val[i])),
             address_low: Constant::new(address),
-            address_high: Constant::new(address + bits(N as u128 * 4)),
+            address_high: Constant::new(address + bits(N as u128 * (DATA as u128 / 8))),
         }
     }
 }
 
 #[derive(PartialEq, Debug, Digital, Clone, Copy)]
 /// Input for the [AxiRegBank]
-pub struct In {
+pub struct In<const ADDR: usize>
+where
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     /// AXI signals from the bus for reading
-    pub axi: ReadMOSI,
+    pub axi: ReadMOSI<ADDR>,
 }
 
 #[derive(PartialEq, Debug, Digital, Clone, Copy)]
 /// Output for the [AxiRegBank]
-pub struct Out<const N: usize> {
+pub struct Out<const DATA: usize, const N: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+{
     /// AXI signals to the bus for reading
-    pub read_axi: ReadMISO,
+    pub read_axi: ReadMISO<DATA>,
     /// Read data from the client side
-    pub data: [AxilData; N],
+    pub data: [AxilData<DATA>; N],
 }
 
-impl<const N: usize> SynchronousIO for AxiRom<N> {
-    type I = In;
-    type O = Out<N>;
-    type Kernel = kernel<N>;
+impl<const DATA: usize, const ADDR: usize, const N: usize> SynchronousIO for AxiRom<DATA, ADDR, N>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    type I = In<ADDR>;
+    type O = Out<DATA, N>;
+    type Kernel = kernel<DATA, ADDR, N>;
 }
 
 #[kernel]
 #[doc(hidden)]
-pub fn kernel<const N: usize>(_cr: ClockReset, i: In, q: Q<N>) -> (Out<N>, D<N>) {
-    let mut d = D::<N>::dont_care();
-    let mut o = Out::<N>::dont_care();
+pub fn kernel<const DATA: usize, const ADDR: usize, const N: usize>(
+    _cr: ClockReset,
+    i: In<ADDR>,
+    q: Q<DATA, ADDR, N>,
+) -> (Out<DATA, N>, D<DATA, ADDR, N>)
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    let mut d = D::<DATA, ADDR, N>::dont_care();
+    let mut o = Out::<DATA, N>::dont_care();
+    // Each register takes DATA/8 bytes, so we need to right shift by 2 or 3
+    let word_shift = bits::<ADDR>(DATA as u128 / 32 + 1);
     d.read.axi = i.axi;
     o.read_axi = q.read.axi;
     d.read.req_ready.raw = q.read.resp_ready.raw;
@@ -118,8 +148,7 @@ pub fn kernel<const N: usize>(_cr: ClockReset, i: In, q: Q<N>) -> (Out<N>, D<N>)
             if req < q.address_low || req > q.address_high {
                 d.read.resp_data = Some(Err(AXI4Error::DECERR));
             } else {
-                // Each register takes 4 bytes, so we need to right shift by 2
-                let reg_ndx = (req - q.address_low) >> 2;
+                let reg_ndx = (req - q.address_low) >> word_shift;
                 d.read.resp_data = Some(Ok(q.data[reg_ndx]));
             }
         }
@@ -134,7 +163,7 @@ mod tests {
 
     #[test]
     fn no_combinatorial_paths() -> miette::Result<()> {
-        let uut: AxiRom<4> = AxiRom::new(
+        let uut: AxiRom<32, 32, 4> = AxiRom::new(
             bits(0x4_000_000),
             [
                 bits(0xDEADBEEF),
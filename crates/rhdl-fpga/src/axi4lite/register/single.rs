@@ -63,20 +63,32 @@ use crate::{
 /// This core provides a single dual-ported register
 /// that has an AXI4Lite bus interface.  The address
 /// of the register is provided at construction time.
-pub struct AxiRegister {
-    read: ReadEndpoint,
-    write: WriteEndpoint,
-    data: DFF<AxilData>,
-    address: Constant<AxilAddr>,
+/// The register is generic over the `DATA` and `ADDR`
+/// widths of the bus, with `STRB = DATA/8` strobe bits.
+pub struct AxiRegister<const DATA: usize, const STRB: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    read: ReadEndpoint<DATA, ADDR>,
+    write: WriteEndpoint<DATA, STRB, ADDR>,
+    data: DFF<AxilData<DATA>>,
+    address: Constant<AxilAddr<ADDR>>,
 }
 
-impl AxiRegister {
+impl<const DATA: usize, const STRB: usize, const ADDR: usize> AxiRegister<DATA, STRB, ADDR>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     /// Create a register with the provided
     /// default value and the given register
     /// Where `address` is the address of the register
     /// and `reset_val` is the reset value of the
     /// register.
-    pub fn new(address: AxilAddr, reset_val: AxilData) -> Self {
+    pub fn new(address: AxilAddr<ADDR>, reset_val: AxilData<DATA>) -> Self {
         Self {
             read: ReadEndpoint::default(),
             write: WriteEndpoint::default(),
@@ -88,37 +100,60 @@ impl AxiRegister {
 
 #[derive(PartialEq, Debug, Digital, Clone, Copy)]
 /// Input for the [AxiRegister]
-pub struct In {
+pub struct In<const DATA: usize, const STRB: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     /// AXI signals from the bus for reading
-    pub read_axi: ReadMOSI,
+    pub read_axi: ReadMOSI<ADDR>,
     /// AXI signals from the bus for writing
-    pub write_axi: WriteMOSI,
+    pub write_axi: WriteMOSI<DATA, STRB, ADDR>,
     /// Write data from the client side
-    pub data: Option<AxilData>,
+    pub data: Option<AxilData<DATA>>,
 }
 
 #[derive(PartialEq, Debug, Digital, Clone, Copy)]
 /// Output for the [AxiRegister]
-pub struct Out {
+pub struct Out<const DATA: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+{
     /// AXI signals to the bus for reading
-    pub read_axi: ReadMISO,
+    pub read_axi: ReadMISO<DATA>,
     /// AXI signals to the bus for writing
     pub write_axi: WriteMISO,
     /// Read data from the client side
-    pub data: AxilData,
+    pub data: AxilData<DATA>,
 }
 
-impl SynchronousIO for AxiRegister {
-    type I = In;
-    type O = Out;
-    type Kernel = kernel;
+impl<const DATA: usize, const STRB: usize, const ADDR: usize> SynchronousIO
+    for AxiRegister<DATA, STRB, ADDR>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    type I = In<DATA, STRB, ADDR>;
+    type O = Out<DATA>;
+    type Kernel = kernel<DATA, STRB, ADDR>;
 }
 
 #[kernel]
 #[doc(hidden)]
-pub fn kernel(_cr: ClockReset, i: In, q: Q) -> (Out, D) {
-    let mut d = D::dont_care();
-    let mut o = Out::dont_care();
+pub fn kernel<const DATA: usize, const STRB: usize, const ADDR: usize>(
+    _cr: ClockReset,
+    i: In<DATA, STRB, ADDR>,
+    q: Q<DATA, STRB, ADDR>,
+) -> (Out<DATA>, D<DATA, STRB, ADDR>)
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    let mut d = D::<DATA, STRB, ADDR>::dont_care();
+    let mut o = Out::<DATA>::dont_care();
     d.write.axi = i.write_axi;
     o.write_axi = q.write.axi;
     d.read.axi = i.read_axi;
@@ -129,7 +164,7 @@ pub fn kernel(_cr: ClockReset, i: In, q: Q) -> (Out, D) {
     if let Some(cmd) = q.write.req_data {
         if q.write.resp_ready.raw {
             if cmd.addr == q.address {
-                let mask = strobe_to_mask(cmd.strobed_data.strobe);
+                let mask = strobe_to_mask::<DATA, STRB>(cmd.strobed_data.strobe);
                 d.data = (q.data & (!mask)) | (cmd.strobed_data.data & mask);
                 d.write.resp_data = Some(Ok(()));
             } else {
@@ -161,15 +196,14 @@ mod tests {
 
     #[test]
     fn no_combinatorial_paths() -> miette::Result<()> {
-        let uut = AxiRegister::new(bits(0), bits(0));
-        AxiRegister::new(bits(0), bits(0));
+        let uut = AxiRegister::<32, 4, 32>::new(bits(0), bits(0));
         drc::no_combinatorial_paths(&uut)?;
         Ok(())
     }
 
     #[test]
     fn hdl_is_ok() -> miette::Result<()> {
-        let uut = AxiRegister::new(bits(0), bits(0));
+        let uut = AxiRegister::<32, 4, 32>::new(bits(0), bits(0));
         let _ = uut.descriptor("top".into())?.hdl()?;
         Ok(())
     }
@@ -10,12 +10,22 @@ use crate::{
 use rhdl::prelude::*;
 #[derive(Clone, Synchronous, SynchronousDQ)]
 #[rhdl(dq_no_prefix)]
-pub struct Adder {
-    controller: BlockReadWriteController,
-    bank: AxiRegBank<3>,
+pub struct Adder<const DATA: usize, const STRB: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    controller: BlockReadWriteController<DATA, STRB, ADDR>,
+    bank: AxiRegBank<DATA, STRB, ADDR, 3>,
 }
 
-impl Default for Adder {
+impl<const DATA: usize, const STRB: usize, const ADDR: usize> Default for Adder<DATA, STRB, ADDR>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     fn default() -> Self {
         Self {
             controller: BlockReadWriteController::default(),
@@ -25,26 +35,51 @@ impl Default for Adder {
 }
 
 #[derive(PartialEq, Clone, Copy, Digital)]
-pub struct In {
-    pub cmd: Option<BlockRequest>,
+pub struct In<const DATA: usize, const STRB: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    pub cmd: Option<BlockRequest<DATA, STRB, ADDR>>,
 }
 
 #[derive(PartialEq, Clone, Copy, Digital)]
-pub struct Out {
-    pub reply: Option<BlockResponse>,
-    pub cmd_ready: Ready<BlockRequest>,
+pub struct Out<const DATA: usize, const STRB: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    pub reply: Option<BlockResponse<DATA>>,
+    pub cmd_ready: Ready<BlockRequest<DATA, STRB, ADDR>>,
 }
 
-impl SynchronousIO for Adder {
-    type I = In;
-    type O = Out;
-    type Kernel = kernel;
+impl<const DATA: usize, const STRB: usize, const ADDR: usize> SynchronousIO
+    for Adder<DATA, STRB, ADDR>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    type I = In<DATA, STRB, ADDR>;
+    type O = Out<DATA, STRB, ADDR>;
+    type Kernel = kernel<DATA, STRB, ADDR>;
 }
 
 #[kernel]
-pub fn kernel(_cr: ClockReset, i: In, q: Q) -> (Out, D) {
-    let mut d = D::dont_care();
-    let mut o = Out::dont_care();
+pub fn kernel<const DATA: usize, const STRB: usize, const ADDR: usize>(
+    _cr: ClockReset,
+    i: In<DATA, STRB, ADDR>,
+    q: Q<DATA, STRB, ADDR>,
+) -> (Out<DATA, STRB, ADDR>, D<DATA, STRB, ADDR>)
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    let mut d = D::<DATA, STRB, ADDR>::dont_care();
+    let mut o = Out::<DATA, STRB, ADDR>::dont_care();
     d.controller.read_axi = q.bank.read_axi;
     d.controller.write_axi = q.bank.write_axi;
     d.bank.read_axi = q.controller.read_axi;
@@ -69,29 +104,46 @@ mod tests {
     };
 
     use super::*;
-    fn write_cmd(addr: u32, val: u32) -> BlockRequest {
+    fn write_cmd<const DATA: usize, const STRB: usize, const ADDR: usize>(
+        addr: u128,
+        val: u32,
+    ) -> BlockRequest<DATA, STRB, ADDR>
+    where
+        rhdl::bits::W<DATA>: BitWidth,
+        rhdl::bits::W<STRB>: BitWidth,
+        rhdl::bits::W<ADDR>: BitWidth,
+    {
         BlockRequest::Write(WriteCommand {
-            addr: bits(addr as u128),
+            addr: bits(addr),
             strobed_data: StrobedData {
                 data: bits(val as u128),
-                strobe: bits(0b1111),
+                strobe: bits((1 << (DATA / 8)) - 1),
             },
         })
     }
 
-    fn read_cmd() -> BlockRequest {
-        BlockRequest::Read(bits(8))
-    }
-
-    fn test_stream(
+    fn test_stream<const DATA: usize, const STRB: usize, const ADDR: usize>(
         problems: impl Iterator<Item = (u32, u32)>,
-    ) -> impl Iterator<Item = BlockRequest> {
-        problems.flat_map(|p| [write_cmd(0, p.0), write_cmd(4, p.1), read_cmd()])
+    ) -> impl Iterator<Item = BlockRequest<DATA, STRB, ADDR>>
+    where
+        rhdl::bits::W<DATA>: BitWidth,
+        rhdl::bits::W<STRB>: BitWidth,
+        rhdl::bits::W<ADDR>: BitWidth,
+    {
+        // Each register occupies DATA/8 bytes of the address space
+        let stride = DATA as u128 / 8;
+        problems.flat_map(move |p| {
+            [
+                write_cmd(0, p.0),
+                write_cmd(stride, p.1),
+                BlockRequest::Read(bits(2 * stride)),
+            ]
+        })
     }
 
     #[test]
     fn test_synthesizable() -> miette::Result<()> {
-        let uut = Adder::default();
+        let uut = Adder::<32, 4, 32>::default();
         let descriptor = uut.descriptor("top".into())?;
         let _ = descriptor.hdl()?;
         Ok(())
@@ -99,7 +151,7 @@ mod tests {
 
     #[test]
     fn test_adder_trace() -> miette::Result<()> {
-        let uut = Adder::default();
+        let uut = Adder::<32, 4, 32>::default();
         let mut need_reset = true;
         let mut rng = XorShift128::default();
         let problems = (0..10).map(|_| (rng.next().unwrap(), rng.next().unwrap()));
@@ -137,17 +189,22 @@ mod tests {
         Ok(())
     }
 
-    #[test]
-    fn test_bank_works() -> miette::Result<()> {
-        let uut = Adder::default();
+    fn test_bank_works_for<const DATA: usize, const STRB: usize, const ADDR: usize>(
+    ) -> miette::Result<()>
+    where
+        rhdl::bits::W<DATA>: BitWidth,
+        rhdl::bits::W<STRB>: BitWidth,
+        rhdl::bits::W<ADDR>: BitWidth,
+    {
+        let uut = Adder::<DATA, STRB, ADDR>::default();
         let mut need_reset = true;
         let mut rng = XorShift128::default();
         let problems = (0..1000).map(|_| (rng.next().unwrap(), rng.next().unwrap()));
         let mut rng2 = XorShift128::default();
         let mut answers = (0..1000).map(|_| {
-            let a = rng2.next().unwrap();
-            let b = rng2.next().unwrap();
-            a.wrapping_add(b)
+            let a = rng2.next().unwrap() as u128;
+            let b = rng2.next().unwrap() as u128;
+            (a + b) & Bits::<DATA>::mask().raw()
         });
         let mut seq = test_stream(problems);
         let mut tail = 0;
@@ -168,7 +225,7 @@ mod tests {
                 }
                 if let Some(BlockResponse::Read(Ok(data))) = o.reply {
                     let expected = answers.next().unwrap();
-                    assert_eq!(expected, data.raw() as u32);
+                    assert_eq!(expected, data.raw());
                 }
                 Some(rhdl::core::sim::ResetOrData::Data(input))
             },
@@ -177,4 +234,14 @@ mod tests {
         .for_each(drop);
         Ok(())
     }
+
+    #[test]
+    fn test_bank_works() -> miette::Result<()> {
+        test_bank_works_for::<32, 4, 32>()
+    }
+
+    #[test]
+    fn test_bank_works_with_64_bit_data() -> miette::Result<()> {
+        test_bank_works_for::<64, 8, 16>()
+    }
 }
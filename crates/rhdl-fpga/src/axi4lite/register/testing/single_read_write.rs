@@ -12,12 +12,23 @@ use rhdl::prelude::*;
 
 #[derive(Clone, Synchronous, SynchronousDQ)]
 #[rhdl(dq_no_prefix)]
-pub struct TestFixture {
-    controller: BlockReadWriteController,
-    register: AxiRegister,
+pub struct TestFixture<const DATA: usize, const STRB: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    controller: BlockReadWriteController<DATA, STRB, ADDR>,
+    register: AxiRegister<DATA, STRB, ADDR>,
 }
 
-impl Default for TestFixture {
+impl<const DATA: usize, const STRB: usize, const ADDR: usize> Default
+    for TestFixture<DATA, STRB, ADDR>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     fn default() -> Self {
         Self {
             controller: BlockReadWriteController::default(),
@@ -27,26 +38,51 @@ impl Default for TestFixture {
 }
 
 #[derive(PartialEq, Clone, Copy, Digital)]
-pub struct In {
-    pub cmd: Option<BlockRequest>,
+pub struct In<const DATA: usize, const STRB: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    pub cmd: Option<BlockRequest<DATA, STRB, ADDR>>,
 }
 
 #[derive(PartialEq, Clone, Copy, Digital)]
-pub struct Out {
-    pub reply: Option<BlockResponse>,
-    pub cmd_ready: Ready<BlockRequest>,
+pub struct Out<const DATA: usize, const STRB: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    pub reply: Option<BlockResponse<DATA>>,
+    pub cmd_ready: Ready<BlockRequest<DATA, STRB, ADDR>>,
 }
 
-impl SynchronousIO for TestFixture {
-    type I = In;
-    type O = Out;
-    type Kernel = kernel;
+impl<const DATA: usize, const STRB: usize, const ADDR: usize> SynchronousIO
+    for TestFixture<DATA, STRB, ADDR>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    type I = In<DATA, STRB, ADDR>;
+    type O = Out<DATA, STRB, ADDR>;
+    type Kernel = kernel<DATA, STRB, ADDR>;
 }
 
 #[kernel]
-pub fn kernel(_cr: ClockReset, i: In, q: Q) -> (Out, D) {
-    let mut d = D::dont_care();
-    let mut o = Out::dont_care();
+pub fn kernel<const DATA: usize, const STRB: usize, const ADDR: usize>(
+    _cr: ClockReset,
+    i: In<DATA, STRB, ADDR>,
+    q: Q<DATA, STRB, ADDR>,
+) -> (Out<DATA, STRB, ADDR>, D<DATA, STRB, ADDR>)
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    let mut d = D::<DATA, STRB, ADDR>::dont_care();
+    let mut o = Out::<DATA, STRB, ADDR>::dont_care();
     d.controller.read_axi = q.register.read_axi;
     d.controller.write_axi = q.register.write_axi;
     d.register.read_axi = q.controller.read_axi;
@@ -68,7 +104,7 @@ mod tests {
 
     use super::*;
 
-    fn write_cmd(strobe: u8, val: u32) -> BlockRequest {
+    fn write_cmd(strobe: u8, val: u32) -> BlockRequest<32, 4, 32> {
         BlockRequest::Write(WriteCommand {
             addr: bits(0),
             strobed_data: StrobedData {
@@ -78,11 +114,11 @@ mod tests {
         })
     }
 
-    fn read_cmd() -> BlockRequest {
+    fn read_cmd() -> BlockRequest<32, 4, 32> {
         BlockRequest::Read(bits(0))
     }
 
-    fn test_stream() -> impl Iterator<Item = BlockRequest> {
+    fn test_stream() -> impl Iterator<Item = BlockRequest<32, 4, 32>> {
         [
             write_cmd(0b1111, 42),
             read_cmd(),
@@ -107,14 +143,14 @@ mod tests {
 
     #[test]
     fn test_synthesizable() -> miette::Result<()> {
-        let uut = TestFixture::default();
+        let uut = TestFixture::<32, 4, 32>::default();
         let _ = uut.descriptor("top".into())?.hdl()?;
         Ok(())
     }
 
     #[test]
     fn test_register_trace() -> miette::Result<()> {
-        let uut = TestFixture::default();
+        let uut = TestFixture::<32, 4, 32>::default();
         let mut need_reset = true;
         let mut seq = test_stream().fuse();
         let mut tail = 0;
@@ -152,7 +188,7 @@ mod tests {
 
     #[test]
     fn test_register_works() -> miette::Result<()> {
-        let uut = TestFixture::default();
+        let uut = TestFixture::<32, 4, 32>::default();
         let mut need_reset = true;
         let mut seq = test_stream().fuse();
         let mut tail = 0;
@@ -13,24 +13,34 @@ use crate::{
 
 #[derive(Clone, Synchronous, SynchronousDQ)]
 #[rhdl(dq_no_prefix)]
-pub struct Fixture {
-    write_source: SourceFromFn<WriteCommand>,
-    write: WriteController,
+pub struct Fixture<const DATA: usize, const STRB: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    write_source: SourceFromFn<WriteCommand<DATA, STRB, ADDR>>,
+    write: WriteController<DATA, STRB, ADDR>,
     write_sink: SinkFromFn<WriteResult>,
-    reg: AxiRegister,
+    reg: AxiRegister<DATA, STRB, ADDR>,
     xor: XorShift,
     valid: DFF<bool>,
-    prev_value: DFF<b32>,
+    prev_value: DFF<Bits<DATA>>,
 }
 
-impl Default for Fixture {
+impl<const DATA: usize, const STRB: usize, const ADDR: usize> Default for Fixture<DATA, STRB, ADDR>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     fn default() -> Self {
         // get a set of write commands
         let cmd = XorShift128::default().map(|x| WriteCommand {
             addr: bits(0),
             strobed_data: StrobedData {
                 data: bits(x as u128),
-                strobe: bits(0b1111),
+                strobe: bits((1 << (DATA / 8)) - 1),
             },
         });
         let cmd = stalling(cmd, 0.23);
@@ -53,16 +63,31 @@ impl Default for Fixture {
     }
 }
 
-impl SynchronousIO for Fixture {
+impl<const DATA: usize, const STRB: usize, const ADDR: usize> SynchronousIO
+    for Fixture<DATA, STRB, ADDR>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     type I = ();
     type O = ();
-    type Kernel = kernel;
+    type Kernel = kernel<DATA, STRB, ADDR>;
 }
 
 #[kernel]
 #[doc(hidden)]
-pub fn kernel(_cr: ClockReset, _i: (), q: Q) -> ((), D) {
-    let mut d = D::dont_care();
+pub fn kernel<const DATA: usize, const STRB: usize, const ADDR: usize>(
+    _cr: ClockReset,
+    _i: (),
+    q: Q<DATA, STRB, ADDR>,
+) -> ((), D<DATA, STRB, ADDR>)
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
+    let mut d = D::<DATA, STRB, ADDR>::dont_care();
     // Pair the source interfaces
     d.write.req_data = q.write_source;
     d.write_source = q.write.req_ready;
@@ -73,7 +98,7 @@ pub fn kernel(_cr: ClockReset, _i: (), q: Q) -> ((), D) {
     d.reg.write_axi = q.write.axi;
     d.write.axi = q.reg.write_axi;
     // Nothing on the read interface in this test case
-    d.reg.read_axi = ReadMOSI::default();
+    d.reg.read_axi = ReadMOSI::<ADDR>::default();
     // Nothing on the core write interface in this case
     d.reg.data = None;
     d.valid = q.valid;
@@ -83,7 +108,7 @@ pub fn kernel(_cr: ClockReset, _i: (), q: Q) -> ((), D) {
         // Register value has changed
         d.prev_value = q.reg.data;
         // Update the valid flag comparing with the XOR sequence
-        d.valid = q.valid & (q.reg.data == q.xor);
+        d.valid = q.valid & (q.reg.data == q.xor.resize::<DATA>());
         // Advance the XOR generator
         d.xor = true;
     }
@@ -99,7 +124,7 @@ mod tests {
     #[test]
     fn synth_works() -> miette::Result<()> {
         let input = repeat_n((), 100).with_reset(1).clock_pos_edge(100);
-        let uut = Fixture::default();
+        let uut = Fixture::<32, 4, 32>::default();
         let vcd = uut.run(input).collect::<VcdFile>();
         vcd.dump_to_file("thing.vcd").unwrap();
         Ok(())
@@ -29,27 +29,55 @@ pub mod response_codes {
 
 /// AXI Data type
 ///
-/// Although the specification allows for AXI data busses of any width,
-/// we implement the 32-bit width in this module.
-pub type AxilData = Bits<32>;
+/// The specification allows for AXI4-Lite data busses of
+/// either 32 or 64 bits.  The cores in this module are generic
+/// over the `DATA` width, and check that it is one of these.
+pub type AxilData<const DATA: usize> = Bits<DATA>;
 /// AXI Address type
 ///
 /// The specification also allows for various widths of AXI addresses.
-/// Because 32 bits is the most common format, we choose that here.
-pub type AxilAddr = Bits<32>;
+/// The cores in this module are generic over the `ADDR` width.
+pub type AxilAddr<const ADDR: usize> = Bits<ADDR>;
+
 /// AXI Strobe Mask
 ///
 /// This data type includes a bit-per-byte of the [AxilData] type.  Thus
-/// it should have `N` bits where [AxilData] has `2^(8*N)` bits.
-pub type AxilStrobe = Bits<4>;
+/// it has `STRB = DATA/8` bits when [AxilData] has `DATA` bits.  Because
+/// `STRB` cannot be computed from `DATA` in a type, the cores that carry
+/// a strobe take it as a separate parameter, and check it with
+/// [check_widths].
+pub type AxilStrobe<const STRB: usize> = Bits<STRB>;
+
+/// Check the data and strobe widths of an AXI4-Lite bus
+///
+/// This check is evaluated at compile time, and fails the
+/// build unless `DATA` is 32 or 64 bits, and `STRB` has
+/// one bit per byte of `DATA`.  The cores call it from their
+/// constructors.
+pub const fn check_widths<const DATA: usize, const STRB: usize>() {
+    const {
+        assert!(
+            DATA == 32 || DATA == 64,
+            "AXI4-Lite only supports 32 or 64 bit data busses"
+        );
+        assert!(
+            STRB * 8 == DATA,
+            "The strobe must have one bit per byte of data"
+        );
+    }
+}
 
 #[derive(PartialEq, Debug, Digital, Clone, Copy, Default)]
 /// A data word to write, along with a strobe for the bytes
-pub struct StrobedData {
+pub struct StrobedData<const DATA: usize, const STRB: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+{
     /// The data to write
-    pub data: AxilData,
+    pub data: AxilData<DATA>,
     /// The strobe to use
-    pub strobe: AxilStrobe,
+    pub strobe: AxilStrobe<STRB>,
 }
 
 #[derive(PartialEq, Debug, Digital, Clone, Copy)]
@@ -59,14 +87,20 @@ pub struct StrobedData {
 /// returns data and a [ResponseKind].  
 /// Presumably, if the [ResponseKind] indicates
 /// an error, then we should ignore the data lines.
-pub struct ReadResponse {
+pub struct ReadResponse<const DATA: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+{
     /// The response to the transaction
     pub resp: ResponseKind,
     /// The data to return
-    pub data: AxilData,
+    pub data: AxilData<DATA>,
 }
 
-impl Default for ReadResponse {
+impl<const DATA: usize> Default for ReadResponse<DATA>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+{
     fn default() -> Self {
         Self {
             resp: response_codes::OKAY,
@@ -81,31 +115,38 @@ impl Default for ReadResponse {
 /// The address and strobed data are sent on
 /// different channels, but combined in this
 /// data structure.
-pub struct WriteCommand {
+pub struct WriteCommand<const DATA: usize, const STRB: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     /// The address to write to
-    pub addr: AxilAddr,
+    pub addr: AxilAddr<ADDR>,
     /// The data to write along with the strobe
-    pub strobed_data: StrobedData,
+    pub strobed_data: StrobedData<DATA, STRB>,
 }
 
 #[kernel]
 /// Convert a strobe into a mask
 ///
 /// This function simply converts the strobe into
-/// a mask.
-pub fn strobe_to_mask(strobe: Bits<4>) -> Bits<32> {
-    let mut mask = bits(0);
-    if strobe & 1 != 0 {
-        mask |= bits(0xff);
-    }
-    if strobe & 2 != 0 {
-        mask |= bits(0xff00);
-    }
-    if strobe & 4 != 0 {
-        mask |= bits(0xff0000);
-    }
-    if strobe & 8 != 0 {
-        mask |= bits(0xff000000);
+/// a mask, with each bit of the strobe enabling
+/// a byte lane of the data.
+pub fn strobe_to_mask<const DATA: usize, const STRB: usize>(strobe: AxilStrobe<STRB>) -> Bits<DATA>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+{
+    let mut mask = bits::<DATA>(0);
+    let mut lane = bits::<DATA>(0xff);
+    let mut strobe = strobe;
+    for _lane in 0..STRB {
+        if strobe & 1 != 0 {
+            mask |= lane;
+        }
+        strobe >>= 1;
+        lane <<= 8;
     }
     mask
 }
@@ -137,7 +178,7 @@ pub enum ExFlag {
 pub type WriteResult = Result<(), AXI4Error>;
 
 /// The result of a Read operation on the AXI bus
-pub type ReadResult = Result<AxilData, AXI4Error>;
+pub type ReadResult<const DATA: usize> = Result<AxilData<DATA>, AXI4Error>;
 
 #[kernel]
 /// Helper function to recode a [ReadResponse] into a [Result].
@@ -145,7 +186,12 @@ pub type ReadResult = Result<AxilData, AXI4Error>;
 /// Because the [ReadResponse] is a bit cryptic, this function converts
 /// it into a [Result].  Note that the [ResponseKind::OKAY] and [ResponseKind::EXOKAY]
 /// variants are collapsed into a single [Ok] variant.
-pub fn read_response_to_result(resp: ReadResponse) -> Result<AxilData, AXI4Error> {
+pub fn read_response_to_result<const DATA: usize>(
+    resp: ReadResponse<DATA>,
+) -> Result<AxilData<DATA>, AXI4Error>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+{
     match resp.resp {
         response_codes::OKAY => Ok(resp.data),
         response_codes::EXOKAY => Ok(resp.data),
@@ -160,18 +206,23 @@ pub fn read_response_to_result(resp: ReadResponse) -> Result<AxilData, AXI4Error
 /// This is an imperfect conversion, since the [ReadResponse] enum
 /// carries multiple success variants.  For now, we are ignoring that
 /// issue.  
-pub fn result_to_read_response(resp: Result<AxilData, AXI4Error>) -> ReadResponse {
+pub fn result_to_read_response<const DATA: usize>(
+    resp: Result<AxilData<DATA>, AXI4Error>,
+) -> ReadResponse<DATA>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+{
     match resp {
-        Ok(data) => ReadResponse {
+        Ok(data) => ReadResponse::<DATA> {
             resp: response_codes::OKAY,
             data,
         },
         Err(e) => match e {
-            AXI4Error::SLVERR => ReadResponse {
+            AXI4Error::SLVERR => ReadResponse::<DATA> {
                 resp: response_codes::SLVERR,
                 data: bits(0),
             },
-            AXI4Error::DECERR => ReadResponse {
+            AXI4Error::DECERR => ReadResponse::<DATA> {
                 resp: response_codes::DECERR,
                 data: bits(0),
             },
@@ -223,9 +274,12 @@ pub fn write_response_to_result(resp: ResponseKind) -> Result<(), AXI4Error> {
 /// AXI bus that implement the read side of the protocol.
 /// These types are outputs for the master, and inputs
 /// for the slave (hence MOSI).
-pub struct ReadMOSI {
+pub struct ReadMOSI<const ADDR: usize>
+where
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     /// Read Address
-    pub araddr: AxilAddr,
+    pub araddr: AxilAddr<ADDR>,
     /// Read Address valid
     pub arvalid: bool,
     /// Read Data ready
@@ -238,11 +292,14 @@ pub struct ReadMOSI {
 /// These are the protocol, data and error flags used
 /// for the read interface.  This type is an input
 /// for the master, and an output for the slave.
-pub struct ReadMISO {
+pub struct ReadMISO<const DATA: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+{
     /// Read Address ready
     pub arready: bool,
     /// Read Data
-    pub rdata: AxilData,
+    pub rdata: AxilData<DATA>,
     /// Read Data response
     pub rresp: Bits<2>,
     /// Read Data valid
@@ -255,15 +312,20 @@ pub struct ReadMISO {
 /// These are the protocol, data, and address lines
 /// used for the write interface.  This type is an
 /// output for the master, and an input for the slave.
-pub struct WriteMOSI {
+pub struct WriteMOSI<const DATA: usize, const STRB: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     /// Write Address
-    pub awaddr: AxilAddr,
+    pub awaddr: AxilAddr<ADDR>,
     /// Write Address valid
     pub awvalid: bool,
     /// Write Data
-    pub wdata: AxilData,
+    pub wdata: AxilData<DATA>,
     /// Write byte strobe
-    pub wstrb: AxilStrobe,
+    pub wstrb: AxilStrobe<STRB>,
     /// Write Data valid
     pub wvalid: bool,
     /// Write Response ready
@@ -291,20 +353,28 @@ pub struct WriteMISO {
 /// All MOSI signals
 ///
 /// These are the MOSI signals for a master
-pub struct MOSI {
+pub struct MOSI<const DATA: usize, const STRB: usize, const ADDR: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+    rhdl::bits::W<STRB>: BitWidth,
+    rhdl::bits::W<ADDR>: BitWidth,
+{
     /// The MOSI signals for the read bus
-    pub read: ReadMOSI,
+    pub read: ReadMOSI<ADDR>,
     /// The MOSI signals for the write bus
-    pub write: WriteMOSI,
+    pub write: WriteMOSI<DATA, STRB, ADDR>,
 }
 
 #[derive(PartialEq, Debug, Clone, Copy, Digital)]
 /// All MISO signals
 ///
 /// These are the MISO signals for a master
-pub struct MISO {
+pub struct MISO<const DATA: usize>
+where
+    rhdl::bits::W<DATA>: BitWidth,
+{
     /// The MISO signals for the read bus
-    pub read: ReadMISO,
+    pub read: ReadMISO<DATA>,
     /// The MISO signals for the write bus
     pub write: WriteMISO,
 }
@@ -120,7 +120,7 @@ where
     fn default() -> Self {
         Self {
             counter: Adapter::new(dff::DFF::default()),
-            syncs: array_init::array_init(|_| synchronizer::Sync1Bit::default()),
+            syncs: array_init::array_init(|_| synchronizer::Sync1Bit::gray_coded()),
         }
     }
 }
@@ -226,6 +226,30 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_clock_domain_crossings() -> miette::Result<()> {
+        type UC = CrossCounter<Red, Blue, 4>;
+        let report = drc::DesignRules::default().check_circuit(&UC::default())?;
+        assert!(report.violations.is_empty());
+        let registers = rhdl::core::circuit::cdc::register_domains(&UC::default())?;
+        assert_eq!(registers.len(), 1);
+        assert_eq!(registers[0].1.to_string(), "Red");
+        // Without the gray code, the bits of the count may be sampled on different clocks
+        let binary = UC {
+            syncs: array_init::array_init(|_| synchronizer::Sync1Bit::default()),
+            ..UC::default()
+        };
+        let report = drc::DesignRules::default().check_circuit(&binary)?;
+        let buses = report
+            .violations_of("multi_bit_synchronizers")
+            .collect::<Vec<_>>();
+        assert_eq!(buses.len(), 1);
+        assert!(buses[0].message.starts_with("4 bits"));
+        assert!(report.violations_of("reconvergent_synchronizers").count() > 0);
+        assert!(report.into_result().is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_read_counter_is_monotonic() -> miette::Result<()> {
         type UC = CrossCounter<Red, Blue, 8>;
@@ -168,7 +168,10 @@ clk   +----+    +----+    +----+
 
 use quote::format_ident;
 use rhdl::{
-    core::{circuit::descriptor::AsyncKind, ScopedName},
+    core::{
+        circuit::{cdc::Synchronizer, descriptor::AsyncKind, resources::BlackBoxResources},
+        ScopedName,
+    },
     prelude::*,
 };
 use syn::parse_quote;
@@ -177,6 +180,7 @@ use syn::parse_quote;
 /// a single bit from the W domain to the R domain
 #[derive(PartialEq, Debug, Clone, Default)]
 pub struct Sync1Bit<W: Domain, R: Domain> {
+    gray_coded: bool,
     _w: std::marker::PhantomData<W>,
     _r: std::marker::PhantomData<R>,
 }
@@ -260,11 +264,31 @@ impl<W: Domain, R: Domain> Circuit for Sync1Bit<W, R> {
             hdl: Some(self.hdl(&name)?),
             _phantom: std::marker::PhantomData,
         }
-        .with_netlist_black_box()
+        .with_netlist_black_box()?
+        .with_black_box_resources(BlackBoxResources {
+            flip_flops: 2,
+            memory_bits: 0,
+        })?
+        .with_black_box_synchronizer(if self.gray_coded {
+            Synchronizer::GrayCoded
+        } else {
+            Synchronizer::SingleBit
+        })
     }
 }
 
 impl<W: Domain, R: Domain> Sync1Bit<W, R> {
+    /// A synchronizer that carries one bit of a gray coded bus.
+    ///
+    /// The clock domain crossing checks allow the bits of a gray
+    /// coded bus to cross through separate synchronizers, since only
+    /// one of them changes at a time.
+    pub fn gray_coded() -> Self {
+        Self {
+            gray_coded: true,
+            ..Default::default()
+        }
+    }
     fn hdl(&self, name: &str) -> Result<HDLDescriptor, RHDLError> {
         let module_name = name.to_owned();
         let module_ident = format_ident!("{}", module_name);
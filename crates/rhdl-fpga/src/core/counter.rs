@@ -126,6 +126,82 @@ mod tests {
         tm.run_iverilog()?;
         let tm = tb.ntl(&uut, &Default::default())?;
         tm.run_iverilog()?;
+        tb.netlist_sim(&uut, &Default::default())?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_counter_netlist_matches_sim() -> miette::Result<()> {
+        let stream = (0..100)
+            .map(|_| random::<bool>())
+            .with_reset(4)
+            .clock_pos_edge(100)
+            .collect::<Vec<_>>();
+        let uut: Counter<6> = Counter::default();
+        let expected = uut.run(stream.clone()).map(|x| x.output.typed_bits());
+        let actual = uut.run_netlist(stream)?;
+        for (expected, actual) in expected.zip(actual) {
+            assert_eq!(expected, actual?.output);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_counter_logic_depth() -> miette::Result<()> {
+        let uut: Counter<6> = Counter::default();
+        timing::max_logic_delay(&uut, &timing::UnitDelay, 4.0)?;
+        assert!(timing::max_logic_delay(&uut, &timing::UnitDelay, 0.0).is_err());
+        let narrow = timing::critical_path(&Counter::<4>::default(), &timing::WidthDelay)?.unwrap();
+        let wide = timing::critical_path(&Counter::<16>::default(), &timing::WidthDelay)?.unwrap();
+        assert!(wide.delay > narrow.delay);
+        Ok(())
+    }
+    #[test]
+    fn test_counter_design_rules() -> miette::Result<()> {
+        let uut: Counter<6> = Counter::default();
+        let report = drc::DesignRules::default().check_synchronous(&uut)?;
+        assert!(report.violations.is_empty());
+        let report = drc::DesignRules::default()
+            .with_rule(drc::CombinationalDepth { max_depth: 1 })
+            .with_severity("combinational_depth", miette::Severity::Error)
+            .check_synchronous(&uut)?;
+        assert_eq!(report.violations.len(), 1);
+        assert_eq!(report.violations[0].location, "counter");
+        assert!(report.into_result().is_err());
+        Ok(())
+    }
+    #[test]
+    fn test_counter_resources() -> miette::Result<()> {
+        let uut: Counter<6> = Counter::default();
+        let report = resources::estimate_resources(&uut, &resources::LutModel::default())?;
+        let total = report.total();
+        assert_eq!(total.flip_flops, 6);
+        assert_eq!(total.black_boxes.len(), 1);
+        assert_eq!(total.black_boxes[0].name, "top_count");
+        assert_eq!(report.scopes.len(), 2);
+        assert_eq!(report.scopes[1].name, "top_count");
+        assert_eq!(report.scopes[1].resources.flip_flops, 6);
+        assert_eq!(report.scopes[1].resources.luts, 0);
+        // Smaller LUTs need more of them
+        let small = resources::estimate_resources(&uut, &resources::LutModel::new(3))?;
+        assert!(small.total().luts > total.luts);
+        let json = report.to_json();
+        assert!(json.contains("\"flip_flops\": 6"));
+        let table = report.to_string();
+        assert!(table.contains("top_count"));
+        Ok(())
+    }
+    #[test]
+    fn test_counter_vhdl() -> miette::Result<()> {
+        let uut: Counter<6> = Counter::default();
+        let vhdl = uut.descriptor("top".into())?.vhdl()?;
+        assert_eq!(vhdl.name, "top");
+        assert_eq!(vhdl.entities[0].name, "top");
+        // The DFF black box supplies its own entity
+        assert!(vhdl.entities.len() > 1);
+        let text = vhdl.as_vhdl();
+        assert!(text.contains("entity top is"));
+        assert!(text.contains("rising_edge"));
         Ok(())
     }
 }
@@ -201,7 +201,7 @@ mod tests {
             endmodule
             module top_dffs(input wire [1:0] clock_reset, input wire [9:0] i, output wire [9:0] o);
                top_dffs_c0 c0(.clock_reset(clock_reset), .i(i[4:0]), .o(o[4:0]));
-               top_dffs_c1 c1(.clock_reset(clock_reset), .i(i[9:5]), .o(o[9:5]));
+               top_dffs_c0 c1(.clock_reset(clock_reset), .i(i[9:5]), .o(o[9:5]));
             endmodule
             module top_dffs_c0(input wire [1:0] clock_reset, input wire [4:0] i, output reg [4:0] o);
                wire  clock;
@@ -219,27 +219,77 @@ mod tests {
                   end
                end
             endmodule
-            module top_dffs_c1(input wire [1:0] clock_reset, input wire [4:0] i, output reg [4:0] o);
-               wire  clock;
-               wire  reset;
-               assign clock = clock_reset[0];
-               assign reset = clock_reset[1];
-               initial begin
-                  o = 5'b00000;
-               end
-               always @(posedge clock) begin
-                  if (reset) begin
-                     o <= 5'b00000;
-                  end else begin
-                     o <= i;
-                  end
-               end
-            endmodule
         "#]];
         expect.assert_eq(&hdl);
         Ok(())
     }
 
+    #[test]
+    fn test_identical_dffs_share_a_module() -> miette::Result<()> {
+        let uut = Delay::<Option<Bits<8>>, 4>::default();
+        let desc = uut.descriptor("top".into())?;
+        let hdl = desc.hdl()?;
+        // The delay, the array of flip flops, and a single flip flop
+        assert_eq!(hdl.modules.modules.len(), 3);
+        let instances = hdl.instances();
+        assert_eq!(instances.len(), 6);
+        assert_eq!(instances[1], ("top.c0".into(), "top_dffs".into()));
+        assert!(instances[2..]
+            .iter()
+            .all(|(_, module)| module == "top_dffs_c0"));
+        assert_eq!(instances[5].0, "top.c0.c3");
+        Ok(())
+    }
+
+    // A circuit holding a delay line, so the flip flops are
+    // three levels below the top
+    #[derive(Clone, Debug, Synchronous, SynchronousDQ)]
+    pub struct Holder {
+        delay: Delay<b8, 3>,
+    }
+
+    impl SynchronousIO for Holder {
+        type I = b8;
+        type O = b8;
+        type Kernel = holder;
+    }
+
+    #[kernel]
+    pub fn holder(_cr: ClockReset, i: b8, q: HolderQ) -> (b8, HolderD) {
+        let mut d = HolderD::dont_care();
+        d.delay = i;
+        (q.delay, d)
+    }
+
+    #[test]
+    fn test_resources_of_nested_delay() -> miette::Result<()> {
+        let uut = Holder {
+            delay: Delay::default(),
+        };
+        let report = resources::estimate_resources(&uut, &resources::LutModel::default())?;
+        let names = report
+            .scopes
+            .iter()
+            .map(|scope| scope.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                "top",
+                "top_delay",
+                "top_delay_dffs",
+                "top_delay_dffs_c0",
+                "top_delay_dffs_c1",
+                "top_delay_dffs_c2"
+            ]
+        );
+        assert_eq!(report.total().flip_flops, 24);
+        assert!(report.scopes[3..]
+            .iter()
+            .all(|scope| scope.resources.flip_flops == 8));
+        Ok(())
+    }
+
     #[test]
     fn test_delay_hdl_works() -> miette::Result<()> {
         let uut = Delay::<Option<Bits<8>>, 4>::default();
@@ -5,7 +5,10 @@
 //! and can store a custom value for the reset value to take.
 //! The [DFF] is positive edge triggered, with an active
 //! high reset.  It is a [Synchronous] component, and meant
-//! to be used in synchronous circuits.  
+//! to be used in synchronous circuits.  The reset can be made
+//! asynchronous or active low (or both) by wrapping the design
+//! in a [ResetStyled] circuit.  Inside a [ClockEnable], the [DFF]
+//! only loads its input on the enabled clock edges.
 //!
 //! Here is the schematic symbol
 //!
@@ -59,9 +62,17 @@ output+-------+-------+-------+-------+
 //!
 //! The trace shows the FSM working.
 #![doc = include_str!("../../doc/dff.md")]
-use quote::format_ident;
+use quote::{format_ident, quote};
 use rhdl::{
-    core::{circuit::descriptor::SyncKind, ScopedName},
+    core::{
+        circuit::{descriptor::SyncKind, resources::BlackBoxResources},
+        hdl::vhdl::{self, Direction},
+        ntl::{
+            smt::{smt_bit, BlackBoxSmt},
+            vm::BlackBoxSim,
+        },
+        ScopedName,
+    },
     prelude::*,
 };
 use syn::parse_quote;
@@ -124,17 +135,28 @@ impl<T: Digital> Synchronous for DFF<T> {
         trace("input", &input);
         let clock = clock_reset.clock;
         let reset = clock_reset.reset;
+        let asynchronous = reset_style().asynchronous;
         if !clock.raw() {
-            state.next = input;
+            // When the clock is not enabled, the flip flop holds its value
+            state.next = if clock_enable().unwrap_or(true) {
+                input
+            } else {
+                state.current
+            };
             state.reset = reset;
         }
         if clock.raw() && !state.cr.clock.raw() {
-            if state.reset.raw() {
+            // An asynchronous reset is not sampled along with the data
+            let reset = if asynchronous { reset } else { state.reset };
+            if reset.raw() {
                 state.current = self.reset;
             } else {
                 state.current = state.next;
             }
         }
+        if asynchronous && reset.raw() {
+            state.current = self.reset;
+        }
         state.cr = clock_reset;
         trace("output", &state.current);
         trace_pop_path();
@@ -154,11 +176,139 @@ impl<T: Digital> Synchronous for DFF<T> {
             netlist: None,
             _phantom: std::marker::PhantomData,
         }
-        .with_netlist_black_box()
+        .with_netlist_black_box_model(self.model()?)?
+        .with_black_box_vhdl(self.vhdl(&name)?)?
+        .with_black_box_smt(self.model()?)?
+        .with_black_box_resources(BlackBoxResources {
+            flip_flops: T::BITS,
+            memory_bits: 0,
+        })
+    }
+}
+
+/// Bit level model of the [DFF] used when simulating netlists.
+///
+/// The state is laid out as `[clock, reset, current.., next..]`,
+/// mirroring the simulation state of the [DFF] itself.  The reset
+/// in the state is the asserted (active high) value.  With a clock
+/// enable, the enable is the third argument.
+struct DFFModel {
+    reset: Box<[BitX]>,
+    clock_index: usize,
+    reset_index: usize,
+    style: ResetStyle,
+    clock_enable: bool,
+}
+
+impl DFFModel {
+    fn asserted(&self, line: BitX) -> BitX {
+        if self.style.active_low {
+            !line
+        } else {
+            line
+        }
+    }
+}
+
+impl BlackBoxSim for DFFModel {
+    fn init(&self) -> Vec<BitX> {
+        // Like the generated Verilog, the output starts at the reset value
+        [BitX::Zero, BitX::Zero]
+            .into_iter()
+            .chain(self.reset.iter().copied())
+            .chain(self.reset.iter().copied())
+            .collect()
+    }
+
+    fn sim(&self, args: &[Vec<BitX>], state: &mut Vec<BitX>) -> Vec<BitX> {
+        let width = self.reset.len();
+        let current = 2..2 + width;
+        let next = 2 + width..2 + 2 * width;
+        let clock = args[0][self.clock_index];
+        let reset = self.asserted(args[0][self.reset_index]);
+        if clock == BitX::Zero {
+            if self.clock_enable && args[2][0] != BitX::One {
+                state.copy_within(current.clone(), next.start);
+            } else {
+                state[next.clone()].copy_from_slice(&args[1]);
+            }
+            state[1] = reset;
+        }
+        if clock == BitX::One && state[0] != BitX::One {
+            let reset = if self.style.asynchronous {
+                reset
+            } else {
+                state[1]
+            };
+            if reset == BitX::One {
+                state[current.clone()].copy_from_slice(&self.reset);
+            } else {
+                state.copy_within(next, current.start);
+            }
+        }
+        if self.style.asynchronous && reset == BitX::One {
+            state[current.clone()].copy_from_slice(&self.reset);
+        }
+        state[0] = clock;
+        state[current].to_vec()
+    }
+}
+
+// The formal model only needs the current value, since the clock is
+// held low within a cycle, and the flip flop updates between cycles.
+// An asynchronous reset also forces the output within the cycle.
+impl BlackBoxSmt for DFFModel {
+    fn init(&self) -> Vec<BitX> {
+        self.reset.to_vec()
+    }
+
+    fn step(&self, state: &[String], args: &[Vec<String>]) -> (Vec<String>, Vec<String>) {
+        let line = &args[0][self.reset_index];
+        let reset = if self.style.active_low {
+            format!("(not {line})")
+        } else {
+            line.clone()
+        };
+        let next = self
+            .reset
+            .iter()
+            .zip(&args[1])
+            .zip(state)
+            .map(|((&init, d), q)| {
+                let d = if self.clock_enable {
+                    format!("(ite {} {d} {q})", args[2][0])
+                } else {
+                    d.clone()
+                };
+                format!("(ite {reset} {} {d})", smt_bit(init == BitX::One))
+            })
+            .collect();
+        let output = if self.style.asynchronous {
+            self.reset
+                .iter()
+                .zip(state)
+                .map(|(&init, q)| format!("(ite {reset} {} {q})", smt_bit(init == BitX::One)))
+                .collect()
+        } else {
+            state.to_vec()
+        };
+        (output, next)
     }
 }
 
 impl<T: Digital> DFF<T> {
+    fn model(&self) -> Result<DFFModel, RHDLError> {
+        let cr = ClockReset::dont_care();
+        let (clock_range, _) = bit_range(ClockReset::static_kind(), &path!(cr.clock))?;
+        let (reset_range, _) = bit_range(ClockReset::static_kind(), &path!(cr.reset))?;
+        Ok(DFFModel {
+            reset: self.reset.bin(),
+            clock_index: clock_range.start,
+            reset_index: reset_range.start,
+            style: reset_style(),
+            clock_enable: clock_enable().is_some(),
+        })
+    }
     fn hdl(&self, name: &str) -> Result<HDLDescriptor, RHDLError> {
         let module_name = format_ident!("{}", name);
         let init: vlog::LitVerilog = self.reset.typed_bits().into();
@@ -168,11 +318,31 @@ impl<T: Digital> DFF<T> {
         let reset_index = syn::Index::from(reset_index.0.start);
         let clock_index = bit_range(ClockReset::static_kind(), &path!(cr.clock))?;
         let clock_index = syn::Index::from(clock_index.0.start);
+        let style = reset_style();
+        let events = match (style.asynchronous, style.active_low) {
+            (false, _) => quote! { posedge clock },
+            (true, false) => quote! { posedge clock, posedge reset },
+            (true, true) => quote! { posedge clock, negedge reset },
+        };
+        let asserted = if style.active_low {
+            quote! { !reset }
+        } else {
+            quote! { reset }
+        };
+        let (ce_port, update) = if clock_enable().is_some() {
+            (
+                quote! { , input wire ce },
+                quote! { if (ce) begin o <= i; end },
+            )
+        } else {
+            (quote! {}, quote! { o <= i; })
+        };
         let module: vlog::ModuleDef = parse_quote! {
             module #module_name(
                 input wire [1:0] clock_reset,
                 input wire [#data_width] i,
                 output reg [#data_width] o
+                #ce_port
             );
                 wire clock;
                 wire reset;
@@ -181,11 +351,11 @@ impl<T: Digital> DFF<T> {
                 initial begin
                     o = #init;
                 end
-                always @(posedge clock) begin
-                    if (reset) begin
+                always @(#events) begin
+                    if (#asserted) begin
                         o <= #init;
                     end else begin
-                        o <= i;
+                        #update
                     end
                 end
             endmodule
@@ -195,6 +365,71 @@ impl<T: Digital> DFF<T> {
             modules: module.into(),
         })
     }
+    fn vhdl(&self, name: &str) -> Result<VHDLDescriptor, RHDLError> {
+        let width = T::static_kind().bits();
+        let init = vhdl::vector_literal(&self.reset.bin());
+        let cr = ClockReset::dont_care();
+        let (reset_index, _) = bit_range(ClockReset::static_kind(), &path!(cr.reset))?;
+        let (clock_index, _) = bit_range(ClockReset::static_kind(), &path!(cr.clock))?;
+        let ports = [
+            vhdl::port("clock_reset", Direction::In, 2),
+            vhdl::port("i", Direction::In, width),
+            vhdl::port("o", Direction::Out, width),
+            clock_enable()
+                .is_some()
+                .then(|| vhdl::port("ce", Direction::In, 1))
+                .flatten(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+        let declarations = [format!("signal q : {} := {init};", vhdl::slv(width))];
+        let style = reset_style();
+        let level = if style.active_low { '0' } else { '1' };
+        let update = |indent: &str| {
+            if clock_enable().is_some() {
+                format!("if ce(0) = '1' then\n{indent}    q <= i;\n{indent}end if;")
+            } else {
+                "q <= i;".to_string()
+            }
+        };
+        let process = if style.asynchronous {
+            format!(
+                "process (clock_reset({clock}), clock_reset({reset})) is
+begin
+    if clock_reset({reset}) = '{level}' then
+        q <= {init};
+    elsif rising_edge(clock_reset({clock})) then
+        {update}
+    end if;
+end process;",
+                clock = clock_index.start,
+                reset = reset_index.start,
+                update = update("        "),
+            )
+        } else {
+            format!(
+                "process (clock_reset({clock})) is
+begin
+    if rising_edge(clock_reset({clock})) then
+        if clock_reset({reset}) = '{level}' then
+            q <= {init};
+        else
+            {update}
+        end if;
+    end if;
+end process;",
+                clock = clock_index.start,
+                reset = reset_index.start,
+                update = update("            "),
+            )
+        };
+        let body = ["o <= q;".to_string(), process];
+        Ok(VHDLDescriptor::new(
+            name,
+            vhdl::entity(name, &ports, &declarations, &body),
+        ))
+    }
 }
 
 #[cfg(test)]
@@ -225,4 +460,174 @@ mod tests {
         expect.assert_eq(&hdl);
         Ok(())
     }
+
+    #[test]
+    fn test_vhdl_output() -> miette::Result<()> {
+        let expect = expect_test::expect![[r#"
+            library ieee;
+            use ieee.std_logic_1164.all;
+            use ieee.numeric_std.all;
+
+            entity top is
+                port (
+                    clock_reset : in std_logic_vector(1 downto 0);
+                    i : in std_logic_vector(3 downto 0);
+                    o : out std_logic_vector(3 downto 0)
+                );
+            end entity top;
+
+            architecture rtl of top is
+                signal q : std_logic_vector(3 downto 0) := "1010";
+            begin
+                o <= q;
+                process (clock_reset(0)) is
+                begin
+                    if rising_edge(clock_reset(0)) then
+                        if clock_reset(1) = '1' then
+                            q <= "1010";
+                        else
+                            q <= i;
+                        end if;
+                    end if;
+                end process;
+            end architecture rtl;
+        "#]];
+        let uut: DFF<b4> = DFF::new(bits(0b1010));
+        let vhdl = uut.vhdl("top")?.as_vhdl();
+        expect.assert_eq(&vhdl);
+        Ok(())
+    }
+
+    #[test]
+    fn test_async_active_low_hdl_output() -> miette::Result<()> {
+        let expect = expect_test::expect![[r#"
+            module top(input wire [1:0] clock_reset, input wire [3:0] i, output reg [3:0] o);
+               wire  clock;
+               wire  reset;
+               assign clock = clock_reset[0];
+               assign reset = clock_reset[1];
+               initial begin
+                  o = 4'b1010;
+               end
+               always @(posedge clock, negedge reset) begin
+                  if (!reset) begin
+                     o <= 4'b1010;
+                  end else begin
+                     o <= i;
+                  end
+               end
+            endmodule
+        "#]];
+        let uut = ResetStyled::new(
+            DFF::<b4>::new(bits(0b1010)),
+            ResetStyle::asynchronous().active_low(),
+        );
+        let hdl = uut.descriptor("top".into())?.hdl()?.modules.pretty();
+        expect.assert_eq(&hdl);
+        Ok(())
+    }
+
+    // A stream of data with a reset pulse that is asserted while the
+    // clock is high, and released before the next rising edge.  The
+    // index of the sample that asserts the pulse is returned too.
+    fn mid_cycle_reset(
+        style: ResetStyle,
+    ) -> (
+        usize,
+        impl Iterator<Item = TimedSample<(ClockReset, b8)>> + Clone,
+    ) {
+        let mut samples = (1..=20)
+            .map(bits)
+            .with_reset(1)
+            .clock_pos_edge(100)
+            .collect::<Vec<_>>();
+        let pulse = (1..samples.len())
+            .filter(|&ndx| samples[ndx].value.0.clock.raw() && samples[ndx - 1].value.0.clock.raw())
+            .nth(10)
+            .unwrap();
+        samples[pulse].value.0.reset = reset(true);
+        for sample in &mut samples {
+            let line = sample.value.0.reset.raw() != style.active_low;
+            sample.value.0.reset = reset(line);
+        }
+        (pulse, samples.into_iter())
+    }
+
+    #[test]
+    fn test_reset_styles_between_clock_edges() -> miette::Result<()> {
+        let styles = [
+            ResetStyle::synchronous(),
+            ResetStyle::synchronous().active_low(),
+            ResetStyle::asynchronous(),
+            ResetStyle::asynchronous().active_low(),
+        ];
+        for style in styles {
+            let uut = ResetStyled::new(DFF::<b8>::new(bits(0)), style);
+            let (pulse, input) = mid_cycle_reset(style);
+            let output = uut.run(input).collect::<Vec<_>>();
+            // Only an asynchronous reset clears the flip flop before the next edge
+            assert_eq!(output[pulse].output == bits(0), style.asynchronous);
+            assert_ne!(output[pulse - 1].output, bits(0));
+            let test_bench = output.into_iter().collect::<SynchronousTestBench<_, _>>();
+            test_bench.netlist_sim(&uut, &Default::default())?;
+            test_bench.rtl(&uut, &Default::default())?.run_iverilog()?;
+            test_bench.ntl(&uut, &Default::default())?.run_iverilog()?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_clock_enable_hdl_output() -> miette::Result<()> {
+        let expect = expect_test::expect![[r#"
+            module top(input wire [1:0] clock_reset, input wire [4:0] i, output wire [3:0] o);
+               top_inner inner(.clock_reset(clock_reset), .i(i[4:1]), .o(o), .ce(i[0]));
+            endmodule
+            module top_inner(input wire [1:0] clock_reset, input wire [3:0] i, output reg [3:0] o, input wire  ce);
+               wire  clock;
+               wire  reset;
+               assign clock = clock_reset[0];
+               assign reset = clock_reset[1];
+               initial begin
+                  o = 4'b1010;
+               end
+               always @(posedge clock) begin
+                  if (reset) begin
+                     o <= 4'b1010;
+                  end else begin
+                     if (ce) begin
+                        o <= i;
+                     end
+                  end
+               end
+            endmodule
+        "#]];
+        let uut = ClockEnable::new(DFF::<b4>::new(bits(0b1010)));
+        let hdl = uut.descriptor("top".into())?.hdl()?.modules.pretty();
+        expect.assert_eq(&hdl);
+        Ok(())
+    }
+
+    #[test]
+    fn test_clock_enable_holds_state() -> miette::Result<()> {
+        let uut = ClockEnable::new(DFF::<b8>::new(bits(0)));
+        let input = (1..=30)
+            .map(|n| (n % 3 == 0, bits(n)))
+            .with_reset(1)
+            .clock_pos_edge(100);
+        let samples = uut
+            .run(input.clone())
+            .synchronous_sample()
+            .skip(2)
+            .collect::<Vec<_>>();
+        for pair in samples.windows(2) {
+            let (enable, data) = pair[0].input.1;
+            let expected = if enable { data } else { pair[0].output };
+            assert_eq!(pair[1].output, expected);
+        }
+        let test_bench = uut.run(input).collect::<SynchronousTestBench<_, _>>();
+        test_bench.netlist_sim(&uut, &Default::default())?;
+        test_bench.rtl(&uut, &Default::default())?.run_iverilog()?;
+        test_bench.ntl(&uut, &Default::default())?.run_iverilog()?;
+        Ok(())
+    }
 }
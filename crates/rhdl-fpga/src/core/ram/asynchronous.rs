@@ -96,9 +96,15 @@ use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
 
 use quote::{format_ident, quote};
 use rhdl::{
-    core::{circuit::descriptor::AsyncKind, ScopedName},
+    core::{
+        circuit::{cdc::Synchronizer, descriptor::AsyncKind, resources::BlackBoxResources},
+        hdl::vhdl,
+        ScopedName,
+    },
     prelude::*,
 };
+
+use super::{bram_vhdl, BRAMSignals};
 use syn::parse_quote;
 
 #[derive(PartialEq, Debug, Clone, Default)]
@@ -274,7 +280,13 @@ where
             netlist: None,
             _phantom: std::marker::PhantomData,
         }
-        .with_netlist_black_box()
+        .with_netlist_black_box()?
+        .with_black_box_vhdl(self.vhdl(&name)?)?
+        .with_black_box_resources(BlackBoxResources {
+            flip_flops: 0,
+            memory_bits: (1 << N) * T::BITS,
+        })?
+        .with_black_box_synchronizer(Synchronizer::DualClockMemory)
     }
 }
 
@@ -390,6 +402,29 @@ where
             modules: module.into(),
         })
     }
+
+    fn vhdl(&self, name: &str) -> Result<VHDLDescriptor, RHDLError> {
+        let i_kind = <<Self as CircuitIO>::I as Digital>::static_kind();
+        let i = <Self as CircuitIO>::I::dont_care();
+        let slice = |range: std::ops::Range<usize>| format!("i({})", vhdl::range(&range));
+        let bit = |range: std::ops::Range<usize>| format!("i({})", range.start);
+        let signals = BRAMSignals {
+            read_clock: bit(bit_range(i_kind, &path!(i.read.val().clock))?.0),
+            read_addr: slice(bit_range(i_kind, &path!(i.read.val().addr))?.0),
+            write_clock: bit(bit_range(i_kind, &path!(i.write.val().clock))?.0),
+            write_addr: slice(bit_range(i_kind, &path!(i.write.val().addr))?.0),
+            write_data: slice(bit_range(i_kind, &path!(i.write.val().data))?.0),
+            write_enable: bit(bit_range(i_kind, &path!(i.write.val().enable))?.0),
+            clock_enable: None,
+        };
+        Ok(bram_vhdl(
+            name,
+            i_kind.bits(),
+            false,
+            &self.initial,
+            signals,
+        ))
+    }
 }
 
 #[cfg(test)]
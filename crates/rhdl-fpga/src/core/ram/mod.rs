@@ -5,3 +5,108 @@ pub mod option_async;
 pub mod option_sync;
 pub mod pipe_sync;
 pub mod synchronous;
+
+use rhdl::{
+    core::hdl::vhdl::{self, Direction},
+    prelude::*,
+};
+use std::collections::BTreeMap;
+
+/// Where the signals of a block RAM are found in the ports of its entity,
+/// as VHDL expressions.
+pub(crate) struct BRAMSignals {
+    pub(crate) read_clock: String,
+    pub(crate) read_addr: String,
+    pub(crate) write_clock: String,
+    pub(crate) write_addr: String,
+    pub(crate) write_data: String,
+    pub(crate) write_enable: String,
+    /// The clock enable of both ports (a `ce` port), if there is one
+    pub(crate) clock_enable: Option<String>,
+}
+
+/// Build the VHDL entity for a block RAM with a registered read port
+/// and a write port.  Both the synchronous and asynchronous RAMs share
+/// this structure, and differ only in where the signals come from.
+pub(crate) fn bram_vhdl<T: Digital, const N: usize>(
+    name: &str,
+    input_bits: usize,
+    has_clock_reset: bool,
+    initial: &BTreeMap<Bits<N>, T>,
+    signals: BRAMSignals,
+) -> VHDLDescriptor
+where
+    rhdl::bits::W<N>: BitWidth,
+{
+    let data_width = T::BITS;
+    let ports = [
+        has_clock_reset
+            .then(|| vhdl::port("clock_reset", Direction::In, 2))
+            .flatten(),
+        vhdl::port("i", Direction::In, input_bits),
+        vhdl::port("o", Direction::Out, data_width),
+        signals
+            .clock_enable
+            .is_some()
+            .then(|| vhdl::port("ce", Direction::In, 1))
+            .flatten(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+    let mut contents = initial
+        .iter()
+        .map(|(addr, val)| format!("{} => {}", addr.raw(), vhdl::vector_literal(&val.bin())))
+        .collect::<Vec<_>>();
+    contents.push("others => (others => 'X')".into());
+    let declarations = [
+        format!(
+            "type mem_t is array (0 to {}) of {};",
+            (1 << N) - 1,
+            vhdl::slv(data_width)
+        ),
+        format!("signal mem : mem_t := ({});", contents.join(", ")),
+    ];
+    let BRAMSignals {
+        read_clock,
+        read_addr,
+        write_clock,
+        write_addr,
+        write_data,
+        write_enable,
+        clock_enable,
+    } = signals;
+    let read = format!("o <= mem(to_integer(unsigned({read_addr})));");
+    let (read, write_enable) = match clock_enable {
+        Some(ce) => (
+            format!(
+                "if {ce} = '1' then
+            {read}
+        end if;"
+            ),
+            format!("{write_enable} = '1' and {ce} = '1'"),
+        ),
+        None => (read, format!("{write_enable} = '1'")),
+    };
+    let body = [
+        format!(
+            "process ({read_clock}) is
+begin
+    if rising_edge({read_clock}) then
+        {read}
+    end if;
+end process;"
+        ),
+        format!(
+            "process ({write_clock}) is
+begin
+    if rising_edge({write_clock}) then
+        if {write_enable} then
+            mem(to_integer(unsigned({write_addr}))) <= {write_data};
+        end if;
+    end if;
+end process;"
+        ),
+    ];
+    VHDLDescriptor::new(name, vhdl::entity(name, &ports, &declarations, &body))
+}
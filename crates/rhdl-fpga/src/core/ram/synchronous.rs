@@ -54,6 +54,9 @@ bool  |                     |
 +------------------------------------------------------------------+
 "#)]
 //!
+//! Inside a [ClockEnable], both the read and the write ports are
+//! only active on the enabled clock edges.
+//!
 //! In general, I don't recommend using a [SyncBRAM].  It's easier
 //! and more idiomatic to use either [OptionSyncBRAM](super::option_sync::OptionSyncBRAM)
 //! or [PipeSyncBRAM](super::pipe_sync::PipeSyncBRAM),
@@ -70,9 +73,11 @@ bool  |                     |
 
 use quote::{format_ident, quote};
 use rhdl::{
-    core::{ScopedName, SyncKind},
+    core::{circuit::resources::BlackBoxResources, hdl::vhdl, ScopedName, SyncKind},
     prelude::*,
 };
+
+use super::{bram_vhdl, BRAMSignals};
 use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
 use syn::parse_quote;
 
@@ -168,6 +173,7 @@ where
     output_current: T,
     output_next: T,
     write_prev: Write<T, N>,
+    enable: bool,
 }
 
 impl<T: Digital, const N: usize> Synchronous for SyncBRAM<T, N>
@@ -183,6 +189,7 @@ where
             output_current: T::dont_care(),
             output_next: T::dont_care(),
             write_prev: Write::dont_care(),
+            enable: false,
         }))
     }
 
@@ -198,8 +205,9 @@ where
                 .copied()
                 .unwrap_or(T::dont_care());
             state.write_prev = input.write;
+            state.enable = clock_enable().unwrap_or(true);
         }
-        if clock.raw() && !state.clock.raw() {
+        if clock.raw() && !state.clock.raw() && state.enable {
             if state.write_prev.enable {
                 let addr = state.write_prev.addr;
                 let data = state.write_prev.value;
@@ -226,7 +234,12 @@ where
             hdl: Some(self.hdl(&name)?),
             _phantom: std::marker::PhantomData,
         }
-        .with_netlist_black_box()
+        .with_netlist_black_box()?
+        .with_black_box_vhdl(self.vhdl(&name)?)?
+        .with_black_box_resources(BlackBoxResources {
+            flip_flops: 0,
+            memory_bits: (1 << N) * T::BITS,
+        })
     }
 }
 
@@ -258,11 +271,25 @@ where
         let clock_index: vlog::BitRange = bit_range(ClockReset::static_kind(), &path!(cr.clock))?
             .0
             .into();
+        let (ce_port, read, write_condition) = if clock_enable().is_some() {
+            (
+                quote! { , input wire ce },
+                quote! { if (ce) begin o <= mem[read_addr]; end },
+                quote! { write_enable & ce },
+            )
+        } else {
+            (
+                quote! {},
+                quote! { o <= mem[read_addr]; },
+                quote! { write_enable },
+            )
+        };
         let module: vlog::ModuleDef = parse_quote! {
             module #module(
                 input wire [1:0] clock_reset,
                 input wire [#input_bits] i,
                 output reg [#data_bits] o
+                #ce_port
             );
                 wire [#address_bits] read_addr;
                 wire [#address_bits] write_addr;
@@ -279,10 +306,10 @@ where
                 assign write_enable = i[#write_enable_index];
                 assign clock = clock_reset[#clock_index];
                 always @(posedge clock) begin
-                    o <= mem[read_addr];
+                    #read
                 end
                 always @(posedge clock) begin
-                    if (write_enable)
+                    if (#write_condition)
                     begin
                         mem[write_addr] <= write_value;
                     end
@@ -294,6 +321,29 @@ where
             modules: module.into(),
         })
     }
+    fn vhdl(&self, name: &str) -> Result<VHDLDescriptor, RHDLError> {
+        let i_kind = <<Self as SynchronousIO>::I as Digital>::static_kind();
+        let i = <Self as SynchronousIO>::I::dont_care();
+        let slice = |range: std::ops::Range<usize>| format!("i({})", vhdl::range(&range));
+        let bit = |range: std::ops::Range<usize>| format!("i({})", range.start);
+        let cr: ClockReset = ClockReset::dont_care();
+        let clock = format!(
+            "clock_reset({})",
+            bit_range(ClockReset::static_kind(), &path!(cr.clock))?
+                .0
+                .start
+        );
+        let signals = BRAMSignals {
+            read_clock: clock.clone(),
+            read_addr: slice(bit_range(i_kind, &path!(i.read_addr))?.0),
+            write_clock: clock,
+            write_addr: slice(bit_range(i_kind, &path!(i.write.addr))?.0),
+            write_data: slice(bit_range(i_kind, &path!(i.write.value))?.0),
+            write_enable: bit(bit_range(i_kind, &path!(i.write.enable))?.0),
+            clock_enable: clock_enable().map(|_| "ce(0)".into()),
+        };
+        Ok(bram_vhdl(name, i_kind.bits(), true, &self.initial, signals))
+    }
 }
 
 #[cfg(test)]
@@ -227,4 +227,12 @@ mod tests {
         tm.run_iverilog()?;
         Ok(())
     }
+
+    #[test]
+    fn test_clock_domain_crossings() -> miette::Result<()> {
+        let uut = AsyncFIFO::<Bits<8>, Red, Blue, 5>::default();
+        let report = drc::DesignRules::default().check_circuit(&uut)?;
+        assert!(report.violations.is_empty());
+        Ok(())
+    }
 }
@@ -1,4 +1,5 @@
 //! FPGA Support for RHDL
+pub mod axi4;
 pub mod axi4lite;
 pub mod cdc;
 pub mod core;
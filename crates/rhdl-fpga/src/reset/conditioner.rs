@@ -88,7 +88,10 @@ reset (out)  +-----+                  +---------+
 
 use quote::format_ident;
 use rhdl::{
-    core::{circuit::descriptor::AsyncKind, ScopedName},
+    core::{
+        circuit::{cdc::Synchronizer, descriptor::AsyncKind, resources::BlackBoxResources},
+        ScopedName,
+    },
     prelude::*,
 };
 use syn::parse_quote;
@@ -191,7 +194,12 @@ impl<W: Domain, R: Domain> Circuit for ResetConditioner<W, R> {
             _phantom: std::marker::PhantomData,
             netlist: None,
         }
-        .with_netlist_black_box()
+        .with_netlist_black_box()?
+        .with_black_box_resources(BlackBoxResources {
+            flip_flops: 2,
+            memory_bits: 0,
+        })?
+        .with_black_box_synchronizer(Synchronizer::SingleBit)
     }
 }
 
@@ -0,0 +1,161 @@
+use rhdl::prelude::*;
+use rhdl_fpga::core::{delay::Delay, dff::DFF};
+
+// A checksum of a sequence of words, that pass through a delay
+// line, and are accumulated whenever the input is high.
+#[derive(Clone, Debug, Synchronous, SynchronousDQ)]
+#[rhdl(dq_no_prefix)]
+pub struct U {
+    delay: Delay<b16, 8>,
+    count: DFF<b16>,
+    sum: DFF<b16>,
+}
+
+impl Default for U {
+    fn default() -> Self {
+        Self {
+            delay: Delay::default(),
+            count: DFF::new(bits(0)),
+            sum: DFF::new(bits(0)),
+        }
+    }
+}
+
+impl SynchronousIO for U {
+    type I = bool;
+    type O = b16;
+    type Kernel = checksum;
+}
+
+#[kernel]
+pub fn checksum(_cr: ClockReset, i: bool, q: Q) -> (b16, D) {
+    let mut d = D::dont_care();
+    d.delay = q.count + bits(0x3a5);
+    d.count = q.count + 1;
+    d.sum = q.sum;
+    if i {
+        d.sum = (q.sum + q.sum) ^ q.delay;
+    }
+    (q.sum, d)
+}
+
+fn stream(cycles: usize) -> impl Iterator<Item = TimedSample<(ClockReset, bool)>> + Clone {
+    (0..cycles)
+        .map(|n| n % 3 != 0)
+        .with_reset(1)
+        .clock_pos_edge(100)
+}
+
+#[test]
+fn test_compiled_matches_sim() -> miette::Result<()> {
+    let uut = U::default();
+    let expected = uut.run(stream(200)).collect::<Vec<_>>();
+    let actual = uut
+        .run_compiled(stream(200), Decode::decode)?
+        .collect::<Vec<_>>();
+    assert_eq!(expected.len(), actual.len());
+    for (expected, actual) in expected.iter().zip(&actual) {
+        assert_eq!(expected.time, actual.time);
+        assert_eq!(expected.output, actual.output);
+    }
+    // The checksum changes, so the test is not trivial
+    assert!(actual.last().unwrap().output != bits(0));
+    let tb = actual.into_iter().collect::<SynchronousTestBench<_, _>>();
+    tb.netlist_sim(&uut, &Default::default())?;
+    Ok(())
+}
+
+// A register with an output that is wider than 64 bits, and
+// has signed and compound parts, to exercise the decoder.
+type Wide = (b128, s100, [b4; 3]);
+
+#[derive(Clone, Debug, Synchronous, SynchronousDQ)]
+pub struct V {
+    reg: DFF<Wide>,
+}
+
+impl Default for V {
+    fn default() -> Self {
+        Self {
+            reg: DFF::new((bits(0), signed(0), [bits(0); 3])),
+        }
+    }
+}
+
+impl SynchronousIO for V {
+    type I = Wide;
+    type O = Wide;
+    type Kernel = pass;
+}
+
+#[kernel]
+pub fn pass(_cr: ClockReset, i: Wide, q: VQ) -> (Wide, VD) {
+    let mut d = VD::dont_care();
+    d.reg = i;
+    (q.reg, d)
+}
+
+#[test]
+fn test_compiled_decodes_wide_outputs() -> miette::Result<()> {
+    let uut = V::default();
+    let inputs = (0..50_u128)
+        .map(|n| {
+            (
+                bits(n << 100 | n),
+                signed(-((n as i128) << 90)),
+                [bits(n % 16), bits(15 - n % 16), bits(n % 7)],
+            )
+        })
+        .with_reset(1)
+        .clock_pos_edge(100);
+    let expected = uut.run(inputs.clone()).collect::<Vec<_>>();
+    let actual = uut
+        .run_compiled(inputs, Decode::decode)?
+        .collect::<Vec<_>>();
+    assert_eq!(expected.len(), actual.len());
+    for (expected, actual) in expected.iter().zip(&actual) {
+        assert_eq!(expected.output, actual.output);
+    }
+    assert!(actual.last().unwrap().output.1 < signed(0));
+    Ok(())
+}
+
+// Time a run of the simulation, and return its last output
+fn bench<T>(name: &str, cycles: usize, run: impl FnOnce() -> T) -> T {
+    let tic = std::time::Instant::now();
+    let result = run();
+    let elapsed = tic.elapsed();
+    println!(
+        "{name:>20}: {cycles} cycles in {elapsed:?} ({:.0} cycles/s)",
+        cycles as f64 / elapsed.as_secs_f64()
+    );
+    result
+}
+
+// A benchmark of the compiled netlist simulator against the other two
+// ways of running the same design.  Timings from a debug build are not
+// meaningful, so run it with
+//
+//   cargo test --release -p rhdl-fpga --test compiled_sim -- --ignored --nocapture
+#[test]
+#[ignore]
+fn bench_compiled_sim_speed() -> miette::Result<()> {
+    let uut = U::default();
+    let cycles = 1_000_000;
+    let expected = bench("Simulation", cycles, || {
+        uut.run(stream(cycles)).last().unwrap().output
+    });
+    let interpreted = bench("Netlist interpreter", cycles, || {
+        uut.run_netlist(stream(cycles))?
+            .last()
+            .unwrap()
+            .map(|sample| sample.output)
+    })?;
+    let compiled = bench("Compiled netlist", cycles, || {
+        uut.run_compiled(stream(cycles), Decode::decode)
+            .map(|run| run.last().unwrap().output)
+    })?;
+    assert_eq!(interpreted, expected.typed_bits());
+    assert_eq!(compiled, expected);
+    Ok(())
+}
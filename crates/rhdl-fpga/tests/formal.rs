@@ -0,0 +1,126 @@
+use rhdl::core::circuit::formal::{
+    check_kernel_equivalence, check_synchronous_equivalence, check_synchronous_property,
+};
+use rhdl::core::ntl::smt::Solver;
+use rhdl::core::TypedBits;
+use rhdl::prelude::*;
+use rhdl_fpga::core::{counter::Counter, dff};
+
+// A counter that adds the enable rather than selecting the next count
+#[derive(Clone, Debug, Synchronous, SynchronousDQ)]
+#[rhdl(dq_no_prefix)]
+pub struct AltCounter {
+    count: dff::DFF<b4>,
+}
+
+impl SynchronousIO for AltCounter {
+    type I = bool;
+    type O = b4;
+    type Kernel = alt_counter;
+}
+
+#[kernel]
+pub fn alt_counter(cr: ClockReset, enable: bool, q: Q) -> (b4, D) {
+    let step = if enable { bits(1) } else { bits(0) };
+    let mut d = D::dont_care();
+    d.count = q.count + step;
+    if cr.reset.any() {
+        d.count = bits(0);
+    }
+    (q.count, d)
+}
+
+#[test]
+fn test_counters_are_equivalent() -> miette::Result<()> {
+    let a = Counter::<4>::default();
+    let b = AltCounter {
+        count: dff::DFF::new(bits(0)),
+    };
+    assert!(check_synchronous_equivalence(&a, &b, 20, &Solver::z3())?.is_none());
+    // A counter with a different reset value differs from the start
+    let c = AltCounter {
+        count: dff::DFF::new(bits(1)),
+    };
+    let cex = check_synchronous_equivalence(&a, &c, 4, &Solver::z3())?.unwrap();
+    assert_eq!(cex.cycle, 0);
+    Ok(())
+}
+
+#[test]
+fn test_counter_property_counterexample() -> miette::Result<()> {
+    let uut = Counter::<4>::default();
+    // The count is three
+    let is_three = |o: &[String]| {
+        format!(
+            "(not (and {} {} (not {}) (not {})))",
+            o[0], o[1], o[2], o[3]
+        )
+    };
+    // The counter cannot reach three in the first three cycles
+    assert!(check_synchronous_property(&uut, 3, &Solver::z3(), is_three)?.is_none());
+    let cex = check_synchronous_property(&uut, 5, &Solver::z3(), is_three)?.unwrap();
+    assert_eq!(cex.cycle, 3);
+    assert!(cex.reset[..3].iter().all(|reset| !reset));
+    // Replay the counterexample in simulation and in Verilog
+    let test_bench = cex.test_bench(&uut, |x: &TypedBits| x.bits()[0] == BitX::One);
+    test_bench.netlist_sim(&uut, &TestBenchOptions::default())?;
+    test_bench
+        .rtl(&uut, &TestBenchOptions::default())?
+        .run_iverilog()?;
+    Ok(())
+}
+
+#[kernel]
+pub fn xor_direct(a: b4, b: b4) -> b4 {
+    a ^ b
+}
+
+#[kernel]
+pub fn xor_expanded(a: b4, b: b4) -> b4 {
+    (a | b) & !(a & b)
+}
+
+#[kernel]
+pub fn or_only(a: b4, b: b4) -> b4 {
+    a | b
+}
+
+#[test]
+fn test_kernel_equivalence() -> miette::Result<()> {
+    let solver = Solver::z3();
+    assert!(check_kernel_equivalence::<xor_direct, xor_expanded>(
+        CompilationMode::Synchronous,
+        &solver
+    )?
+    .is_none());
+    let args =
+        check_kernel_equivalence::<xor_direct, or_only>(CompilationMode::Synchronous, &solver)?
+            .unwrap();
+    let (a, b) = (args[0].as_i64()?, args[1].as_i64()?);
+    assert_ne!(a ^ b, a | b);
+    Ok(())
+}
+
+#[kernel]
+pub fn xor_signed(a: b4, b: b4) -> s4 {
+    (a ^ b).as_signed()
+}
+
+#[kernel]
+pub fn xor_narrow(a: b4, b: b3) -> b4 {
+    a ^ b.resize::<4>()
+}
+
+#[test]
+fn test_kernel_equivalence_requires_matching_kinds() {
+    // The kinds are checked before the solver is run
+    let solver = Solver::z3();
+    assert!(matches!(
+        check_kernel_equivalence::<xor_direct, xor_signed>(CompilationMode::Synchronous, &solver),
+        Err(RHDLError::KernelSignatureError(_))
+    ));
+    assert!(matches!(
+        check_kernel_equivalence::<xor_direct, xor_narrow>(CompilationMode::Synchronous, &solver),
+        Err(RHDLError::KernelSignatureError(_))
+    ));
+}
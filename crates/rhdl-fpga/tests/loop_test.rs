@@ -4,12 +4,12 @@ use rhdl_fpga::axi4lite::{
     types::{AXI4Error, AxilAddr},
 };
 
-const ROM0_BASE: AxilAddr = bits(0x4_000_000);
-const ROM1_BASE: AxilAddr = bits(0x6_000_000);
+const ROM0_BASE: AxilAddr<32> = bits(0x4_000_000);
+const ROM1_BASE: AxilAddr<32> = bits(0x6_000_000);
 
 // The decode function
 #[kernel]
-pub fn decode_addr(_cr: ClockReset, req: AxilAddr) -> Command {
+pub fn decode_addr(_cr: ClockReset, req: AxilAddr<32>) -> Command<32> {
     let rom_0_active = req & ROM0_BASE == ROM0_BASE;
     let rom_1_active = req & ROM1_BASE == ROM1_BASE;
     match (rom_0_active, rom_1_active) {
@@ -23,6 +23,6 @@ use rhdl_fpga::axi4lite::core::switch::read::kernel;
 
 #[test]
 fn test_loop_test() -> miette::Result<()> {
-    let _ = compile_design::<kernel<2>>(CompilationMode::Synchronous)?;
+    let _ = compile_design::<kernel<32, 32, 2>>(CompilationMode::Synchronous)?;
     Ok(())
 }
@@ -0,0 +1,152 @@
+use rhdl::prelude::*;
+use rhdl_fpga::fifo::asynchronous::AsyncFIFO;
+
+// Two asynchronous FIFOs chained through a third (blue) clock domain.
+#[derive(Clone, Circuit, CircuitDQ, Default)]
+pub struct Chain {
+    first: AsyncFIFO<b8, Red, Blue, 3>,
+    second: AsyncFIFO<b8, Blue, Green, 3>,
+}
+
+#[derive(PartialEq, Debug, Digital, Copy, Timed, Clone)]
+pub struct In {
+    pub data: Signal<Option<b8>, Red>,
+    pub next: Signal<bool, Green>,
+    pub cr_red: Signal<ClockReset, Red>,
+    pub cr_blue: Signal<ClockReset, Blue>,
+    pub cr_green: Signal<ClockReset, Green>,
+}
+
+#[derive(PartialEq, Debug, Digital, Copy, Timed, Clone)]
+pub struct Out {
+    pub full: Signal<bool, Red>,
+    pub data: Signal<Option<b8>, Green>,
+}
+
+impl CircuitIO for Chain {
+    type I = In;
+    type O = Out;
+    type Kernel = chain_kernel;
+}
+
+#[kernel]
+pub fn chain_kernel(i: In, q: ChainQ) -> (Out, ChainD) {
+    let mut d = ChainD::dont_care();
+    d.first.cr_w = i.cr_red;
+    d.first.cr_r = i.cr_blue;
+    d.first.data = i.data;
+    d.second.cr_w = i.cr_blue;
+    d.second.cr_r = i.cr_green;
+    d.second.next = i.next;
+    // Move words from the first FIFO to the second in the blue domain
+    let mut moved = None;
+    let mut next = false;
+    if let Some(word) = q.first.data.val() {
+        if !q.second.full.val() {
+            moved = Some(word);
+            next = true;
+        }
+    }
+    d.second.data = signal(moved);
+    d.first.next = signal(next);
+    let mut o = Out::dont_care();
+    o.full = q.first.full;
+    o.data = q.second.data;
+    (o, d)
+}
+
+#[test]
+fn test_three_domains_with_phase_and_jitter() {
+    let uut = Chain::default();
+    let data = (0..64).map(b8).collect::<Vec<_>>();
+    let mut feed = data.iter().copied();
+    let mut received = vec![];
+    let samples = run_async(
+        &uut,
+        [
+            DomainClock::new(
+                ClockSpec::new(50).with_jitter(3, 1),
+                |cr, input: &mut In| input.cr_red = cr,
+            )
+            .on_neg_edge(|output: Out, input: &mut In| {
+                input.data = signal(None);
+                if !output.full.val() {
+                    input.data = signal(feed.next());
+                }
+            }),
+            DomainClock::new(
+                ClockSpec::new(78)
+                    .with_phase(17)
+                    .with_duty_cycle(40)
+                    .with_jitter(5, 2)
+                    .with_reset(3),
+                |cr, input: &mut In| input.cr_blue = cr,
+            ),
+            DomainClock::new(
+                ClockSpec::new(34)
+                    .with_phase(5)
+                    .with_duty_cycle(60)
+                    .with_reset(2),
+                |cr, input: &mut In| input.cr_green = cr,
+            )
+            .on_neg_edge(|output: Out, input: &mut In| {
+                input.next = signal(false);
+                if let Some(word) = output.data.val() {
+                    input.next = signal(true);
+                    received.push(word);
+                }
+            }),
+        ],
+    )
+    .take_while(|sample| sample.time < 20_000)
+    .collect::<Vec<_>>();
+    assert_eq!(received, data);
+    // Time never goes backwards, and each domain has its own clock
+    assert!(samples.windows(2).all(|w| w[0].time < w[1].time));
+    let rising = |clock: fn(&In) -> bool| {
+        samples
+            .windows(2)
+            .filter(|w| !clock(&w[0].input) && clock(&w[1].input))
+            .count()
+    };
+    assert_eq!(rising(|i| i.cr_green.val().clock.raw()), 588);
+    assert!(rising(|i| i.cr_red.val().clock.raw()).abs_diff(400) <= 1);
+    assert!(rising(|i| i.cr_blue.val().clock.raw()).abs_diff(256) <= 1);
+}
+
+#[test]
+fn test_two_domains_match_red_blue() {
+    let uut = AsyncFIFO::<b8, Red, Blue, 3>::default();
+    let feed = |output: Out2, input: &mut In2| {
+        input.data = signal(None);
+        if !output.full.val() {
+            input.data = signal(Some(b8(0x5a)));
+        }
+    };
+    let drain = |output: Out2, input: &mut In2| {
+        input.next = signal(output.data.val().is_some());
+    };
+    let expected = run_async_red_blue(&uut, feed, drain, 50, 78, |red, blue, input| {
+        input.cr_w = red;
+        input.cr_r = blue;
+    })
+    .take_while(|sample| sample.time < 5_000)
+    .map(|sample| (sample.time, sample.input, sample.output))
+    .collect::<Vec<_>>();
+    let actual = run_async(
+        &uut,
+        [
+            DomainClock::new(ClockSpec::new(50), |cr, input: &mut In2| input.cr_w = cr)
+                .on_neg_edge(feed),
+            DomainClock::new(ClockSpec::new(78), |cr, input: &mut In2| input.cr_r = cr)
+                .on_neg_edge(drain),
+        ],
+    )
+    .take_while(|sample| sample.time < 5_000)
+    .map(|sample| (sample.time, sample.input, sample.output))
+    .collect::<Vec<_>>();
+    assert_eq!(expected, actual);
+}
+
+type In2 = rhdl_fpga::fifo::asynchronous::In<b8, Red, Blue>;
+type Out2 = rhdl_fpga::fifo::asynchronous::Out<b8, Red, Blue>;
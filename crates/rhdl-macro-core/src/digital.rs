@@ -60,6 +60,16 @@ fn derive_digital_tuple_struct(decl: DeriveInput) -> syn::Result<TokenStream> {
                             )*
                             ].into()
                         )
+                    }
+                    fn static_trace_type() -> rhdl::rtt::TraceType {
+                        rhdl::rtt::make_struct(
+                            #fqdn,
+                            [
+                            #(
+                                rhdl::rtt::make_field(stringify!(#fields), <#field_types as rhdl::core::Digital>::static_trace_type()),
+                            )*
+                            ].into()
+                        )
                     }
                      fn bin(self) -> Box<[rhdl::core::BitX]> {
                         [
@@ -155,6 +165,16 @@ fn derive_digital_named_struct(decl: DeriveInput) -> syn::Result<TokenStream> {
                         ].into(),
                         )
                     }
+                    fn static_trace_type() -> rhdl::rtt::TraceType {
+                        rhdl::rtt::make_struct(
+                            #fqdn,
+                            [
+                            #(
+                                rhdl::rtt::make_field(stringify!(#fields), <#field_types as rhdl::core::Digital>::static_trace_type()),
+                            )*
+                        ].into(),
+                        )
+                    }
                     fn bin(self) -> Box<[rhdl::core::BitX]> {
                         #bin_body
                     }
@@ -191,6 +191,33 @@ fn variant_kind_mapping(enum_name: &Ident, variant: &Variant) -> TokenStream {
     }
 }
 
+fn variant_trace_type_mapping(enum_name: &Ident, variant: &Variant) -> TokenStream {
+    match &variant.fields {
+        syn::Fields::Unit => quote! {rhdl::rtt::TraceType::Empty},
+        syn::Fields::Unnamed(fields) => {
+            let field_types = fields.unnamed.iter().map(|f| &f.ty);
+            quote! {
+                rhdl::rtt::make_tuple([#(
+                    <#field_types as rhdl::core::Digital>::static_trace_type()
+                ),*].into())
+            }
+        }
+        syn::Fields::Named(fields) => {
+            let field_names = fields.named.iter().map(|f| &f.ident);
+            let field_types = fields.named.iter().map(|f| &f.ty);
+            let struct_name = format_ident!("_{}__{}", enum_name, variant.ident);
+            quote! {
+                rhdl::rtt::make_struct(
+                    stringify!(#struct_name),
+                    [#(
+                    rhdl::rtt::make_field(stringify!(#field_names), <#field_types as rhdl::core::Digital>::static_trace_type())
+                ),*].into()
+            )
+            }
+        }
+    }
+}
+
 fn variant_bits_mapping(variant: &Variant) -> TokenStream {
     match &variant.fields {
         syn::Fields::Unit => quote! {0_usize},
@@ -358,6 +385,10 @@ pub fn derive_digital_enum(decl: DeriveInput) -> syn::Result<TokenStream> {
         .iter()
         .map(|v| variant_kind_mapping(enum_name, v));
     let variant_kind_mapping = kind_mapping.clone();
+    let trace_type_mapping = e
+        .variants
+        .iter()
+        .map(|v| variant_trace_type_mapping(enum_name, v));
     let variant_bits_mapping = e.variants.iter().map(variant_bits_mapping);
     let kind = discriminant_kind(&discriminants_values);
     let width_override = parse_discriminant_width_attribute(&decl.attrs)?;
@@ -405,6 +436,21 @@ pub fn derive_digital_enum(decl: DeriveInput) -> syn::Result<TokenStream> {
                 )
             )
         }
+        fn static_trace_type() -> rhdl::rtt::TraceType {
+            rhdl::rtt::make_enum(
+                #fqdn,
+                vec![
+                    #(
+                        rhdl::rtt::make_variant(stringify!(#variant_names), #trace_type_mapping, #discriminants)
+                    ),*
+                ],
+                rhdl::core::Kind::make_discriminant_layout(
+                    #width_bits,
+                    #discriminant_alignment_expr,
+                    #discriminant_ty
+                ).into()
+            )
+        }
         fn bin(self) -> Box<[rhdl::core::BitX]> {
             let mut raw =
                 match self {
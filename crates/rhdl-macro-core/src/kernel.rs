@@ -5,13 +5,171 @@ use proc_macro2::Span;
 use quote::{ToTokens, format_ident, quote};
 use syn::{
     FnArg, Ident, Pat, PatType, Path, ReturnType, Token, parse::Parser, punctuated::Punctuated,
-    spanned::Spanned, token::Comma,
+    spanned::Spanned, token::Comma, visit_mut::VisitMut,
 };
 
 // use crate::suffix::CustomSuffix;
 type TS = proc_macro2::TokenStream;
 type Result<T> = syn::Result<T>;
-// use syn::visit_mut::VisitMut;
+
+// Methods that are built into the RHDL compiler.  Any other
+// method call in a kernel is taken to be a call to a `#[kernel]`
+// method defined in an inherent `impl` block.
+const KNOWN_METHODS: &[&str] = &[
+    "any",
+    "all",
+    "xor",
+    "as_signed",
+    "as_unsigned",
+    "val",
+    "resize",
+    "raw",
+    "xadd",
+    "xsub",
+    "xmul",
+    "xneg",
+    "xext",
+    "xshl",
+    "xshr",
+    "xsgn",
+    "dyn_bits",
+    "as_bits",
+    "as_signed_bits",
+    "as_wide_bits",
+    "as_signed_wide_bits",
+    "as_fixed",
+    "as_signed_fixed",
+    "saturating_add",
+    "saturating_sub",
+    "overflowing_add",
+    "overflowing_sub",
+    "count_ones",
+    "leading_zeros",
+    "trailing_zeros",
+    "reverse_bits",
+];
+const TURBO_METHODS: &[&str] = &["resize", "xext", "xshl", "xshr"];
+
+// The attribute used to tag user method calls with their index
+// into the list of resolved methods.
+const METHOD_TAG: &str = "rhdl_method";
+
+fn is_user_method(method: &Ident) -> bool {
+    !KNOWN_METHODS.contains(&method.to_string().as_str())
+}
+
+// The type that names a kernel method in `KernelMethod<NAME>`, which
+// spells out the name one character at a time.
+fn method_name_type(name: &Ident) -> TS {
+    let chars = name
+        .to_string()
+        .chars()
+        .map(|c| quote!(rhdl::core::digital_fn::MethodChar<#c>))
+        .collect::<Vec<_>>();
+    quote!((#(#chars,)*))
+}
+
+fn method_tag(attrs: &[syn::Attribute]) -> Option<usize> {
+    attrs.iter().find_map(|attr| {
+        if !attr.path().is_ident(METHOD_TAG) {
+            return None;
+        }
+        attr.parse_args::<syn::LitInt>()
+            .ok()
+            .and_then(|x| x.base10_parse().ok())
+    })
+}
+
+// The type of a user defined method call cannot be known when the
+// macro runs, since it depends on the type of the receiver.  So we
+// number each such call, and tag it with `#[rhdl_method(k)]`.  The
+// tags are used to build a copy of the body (the "shadow") that
+// rustc type checks (but never runs) to find the receiver types.
+#[derive(Default)]
+struct MethodCallTagger {
+    names: Vec<Ident>,
+}
+
+impl VisitMut for MethodCallTagger {
+    fn visit_expr_method_call_mut(&mut self, node: &mut syn::ExprMethodCall) {
+        syn::visit_mut::visit_expr_method_call_mut(self, node);
+        if is_user_method(&node.method) {
+            let ndx = self.names.len();
+            // Give the tag the span of the call, so that the spans of
+            // any enclosing expressions are unchanged.
+            let tag = format_ident!("{}", METHOD_TAG, span = node.span());
+            node.attrs
+                .push(syn::parse_quote_spanned!(node.span()=> #[#tag(#ndx)]));
+            self.names.push(node.method.clone());
+        }
+    }
+}
+
+// Rewrite each tagged method call `recv.name(args)` into
+//   { let __rhdl_recv = recv; __rhdl_probe_k = Some(probe_method(&__rhdl_recv)); __rhdl_recv.name(args) }
+// and replace `self` with `__rhdl_self`, since the shadow lives
+// in a closure and not in the method itself.
+struct ShadowRewriter;
+
+impl VisitMut for ShadowRewriter {
+    fn visit_expr_mut(&mut self, node: &mut syn::Expr) {
+        syn::visit_mut::visit_expr_mut(self, node);
+        if let syn::Expr::Path(path) = node {
+            if path.qself.is_none() && path.path.is_ident("self") {
+                *node = syn::parse_quote!(__rhdl_self);
+            }
+            return;
+        }
+        let syn::Expr::MethodCall(call) = node else {
+            return;
+        };
+        let Some(ndx) = method_tag(&call.attrs) else {
+            return;
+        };
+        call.attrs.retain(|attr| !attr.path().is_ident(METHOD_TAG));
+        let probe = format_ident!("__rhdl_probe_{ndx}");
+        let key = method_name_type(&call.method);
+        let receiver = &call.receiver;
+        let method = &call.method;
+        let turbofish = &call.turbofish;
+        let args = &call.args;
+        *node = syn::parse_quote! {
+            {
+                let __rhdl_recv = #receiver;
+                #probe = Some(rhdl::core::digital_fn::probe_method::<#key, _>(&__rhdl_recv));
+                __rhdl_recv.#method #turbofish (#args)
+            }
+        };
+    }
+}
+
+// Build the statement that resolves the user defined methods called
+// by a kernel into a list of signatures and kernels, bound to
+// `__rhdl_methods`.  The kernel arguments are the arguments of the
+// inner closure, which is type checked, but never called.
+fn method_resolver(inputs: &[(TS, TS)], ret: &ReturnType, block: &syn::Block, count: usize) -> TS {
+    let mut block = block.clone();
+    ShadowRewriter.visit_block_mut(&mut block);
+    let probes = (0..count)
+        .map(|ndx| format_ident!("__rhdl_probe_{ndx}"))
+        .collect::<Vec<_>>();
+    let list = probes
+        .iter()
+        .fold(quote!(()), |acc, probe| quote!((#acc, #probe)));
+    let pats = inputs.iter().map(|(pat, _)| pat);
+    let tys = inputs.iter().map(|(_, ty)| ty);
+    quote! {
+        let __rhdl_methods = rhdl::core::digital_fn::resolve_methods(|| {
+            #(
+                #[allow(unused_mut)]
+                let mut #probes = None;
+            )*
+            #[allow(unused_variables, unused_mut, unused_assignments, unreachable_code, clippy::all)]
+            let _ = |#(#pats: #tys),*| #ret #block;
+            #list
+        });
+    }
+}
 
 // We need the same kind of scope tracking that is used in `infer_types.rs`.
 // Basically, in any given scope, we need a list of the bindings that have
@@ -77,6 +235,27 @@ fn pattern_has_bindings(pat: &syn::Pat) -> bool {
     }
 }
 
+// Variant patterns like `Foo::Bar(_)` cannot be rewritten as constant
+// expressions, since `_` is not a value.  They are matched like patterns
+// with bindings instead.
+fn pattern_has_wildcards(pat: &syn::Pat) -> bool {
+    fn inner(pat: &syn::Pat) -> bool {
+        match pat {
+            Pat::Wild(_) => true,
+            Pat::Paren(pat) => inner(&pat.pat),
+            Pat::Struct(struct_) => struct_.fields.iter().any(|x| inner(&x.pat)),
+            Pat::TupleStruct(tuple) => tuple.elems.iter().any(inner),
+            Pat::Type(ty) => inner(&ty.pat),
+            _ => false,
+        }
+    }
+    match pat {
+        Pat::Struct(struct_) => struct_.fields.iter().any(|x| inner(&x.pat)),
+        Pat::TupleStruct(tuple) => tuple.elems.iter().any(inner),
+        _ => false,
+    }
+}
+
 //
 // This is a kludge.  I do not know of any way to determine if
 // an expression like j = Foo::Bar(3) is a function named Bar
@@ -386,9 +565,98 @@ impl Context {
 pub fn hdl_kernel(attrs: TS, input: TS) -> Result<TS> {
     let parser = Punctuated::<Ident, Token![,]>::parse_terminated;
     let attrs = parser.parse(attrs.into())?;
-    let input = syn::parse::<syn::ItemFn>(input.into())?;
-    let mut context = Context::default();
-    context.function(&attrs, input)
+    match syn::parse::<syn::Item>(input.into())? {
+        syn::Item::Fn(function) => {
+            if let Some(receiver) = function.sig.receiver() {
+                return Err(syn::Error::new(
+                    receiver.span(),
+                    "To use methods in rhdl kernels, apply #[kernel] to the enclosing impl block",
+                ));
+            }
+            let mut context = Context::default();
+            context.function(&attrs, function)
+        }
+        syn::Item::Impl(item) => kernel_impl(&attrs, item),
+        item => Err(syn::Error::new(
+            item.span(),
+            "#[kernel] can only be applied to functions and inherent impl blocks",
+        )),
+    }
+}
+
+// Apply #[kernel] to each method in an inherent impl block.  Each method
+// must take `self` by value, so that it can be treated as a function with
+// the receiver as the first argument.  In addition to the method itself,
+// we generate a hidden function that returns the kernel for the method, and
+// an implementation of `KernelMethod` that lets callers find it by name.
+fn kernel_impl(attrs: &Punctuated<Ident, Token![,]>, mut item: syn::ItemImpl) -> Result<TS> {
+    if let Some((_, path, _)) = &item.trait_ {
+        return Err(syn::Error::new(
+            path.span(),
+            "#[kernel] can only be applied to inherent impl blocks",
+        ));
+    }
+    let self_ty = item.self_ty.clone();
+    let (impl_generics, _, where_clause) = item.generics.split_for_impl();
+    let mut methods = vec![];
+    let mut trait_impls = vec![];
+    for impl_item in std::mem::take(&mut item.items) {
+        let syn::ImplItem::Fn(method) = impl_item else {
+            methods.push(quote!(#impl_item));
+            continue;
+        };
+        let Some(receiver) = method.sig.receiver() else {
+            return Err(syn::Error::new(
+                method.sig.span(),
+                "Methods in a #[kernel] impl block must take `self` by value",
+            ));
+        };
+        if receiver.reference.is_some() || receiver.colon_token.is_some() {
+            return Err(syn::Error::new(
+                receiver.span(),
+                "Methods in a #[kernel] impl block must take `self` by value",
+            ));
+        }
+        if !method.sig.generics.params.is_empty() {
+            return Err(syn::Error::new(
+                method.sig.generics.span(),
+                "Generic methods are not supported in rhdl kernels",
+            ));
+        }
+        let name = &method.sig.ident;
+        let key = method_name_type(name);
+        let kernel_name = format_ident!("__rhdl_kernel_{}", name);
+        let mut context = Context::default();
+        let kernel = context.method(attrs, &method)?;
+        let wrapped_method = trace_wrap_method(&method);
+        methods.push(quote! {
+            #wrapped_method
+
+            #[doc(hidden)]
+            fn #kernel_name() -> Option<rhdl::core::digital_fn::KernelFnKind> {
+                #kernel
+            }
+        });
+        trait_impls.push(quote! {
+            impl #impl_generics rhdl::core::digital_fn::KernelMethod<#key> for #self_ty #where_clause {
+                fn signature() -> rhdl::core::digital_fn::DigitalSignature {
+                    rhdl::core::digital_fn::inspect_digital(<#self_ty>::#name)
+                }
+                fn kernel_fn() -> Option<rhdl::core::digital_fn::KernelFnKind> {
+                    <#self_ty>::#kernel_name()
+                }
+            }
+        });
+    }
+    let impl_attrs = &item.attrs;
+    Ok(quote! {
+        #(#impl_attrs)*
+        impl #impl_generics #self_ty #where_clause {
+            #(#methods)*
+        }
+
+        #(#trait_impls)*
+    })
 }
 
 // Convert a pattern that would appear in a function argument into an expression.
@@ -593,6 +861,36 @@ fn trace_wrap_function(function: &syn::ItemFn) -> Result<TS> {
     })
 }
 
+// A method cannot be wrapped with an inner function as `trace_wrap_function`
+// does, since the inner function cannot take `self`.  So we wrap the body
+// in a closure instead.
+//
+//  fn my_method(self, args) -> Ret {
+//      note_push("my_method");
+//      let ret = (move || -> Ret { body })();
+//      note_pop();
+//      ret
+//  }
+//
+fn trace_wrap_method(method: &syn::ImplItemFn) -> TS {
+    let attrs = &method.attrs;
+    let vis = &method.vis;
+    let sig = &method.sig;
+    let name = &sig.ident;
+    let ret = &sig.output;
+    let body = &method.block;
+    quote! {
+        #( #attrs )*
+        #vis #sig {
+            rhdl::core::trace_push_path(stringify!(#name));
+            #[allow(clippy::redundant_closure_call)]
+            let ret = (move || #ret #body)();
+            rhdl::core::trace_pop_path();
+            ret
+        }
+    }
+}
+
 // Get the file path from a Span, if possible
 // If the filename is not UTF8, then return None
 fn span_file_path(span: Span) -> Option<String> {
@@ -612,7 +910,6 @@ impl Context {
             .map(|x| quote! {Some(#x)})
             .unwrap_or(quote! {None});
         let root_id = self.id(&function, &function.attrs);
-        let orig_name = &function.sig.ident;
         let vis = &function.vis;
         let (impl_generics, ty_generics, where_clause) = function.sig.generics.split_for_impl();
         let weak_partial_flag = attrs.iter().any(|x| x == "allow_weak_partial");
@@ -638,22 +935,80 @@ impl Context {
             })
             .collect::<Vec<_>>();
         let name = &function.sig.ident;
+        let fn_id = quote!(std::any::TypeId::of::<#name #ty_generics>());
+        let kernel =
+            self.kernel_fn_body(root_id, &function.sig, &function.block, fn_id, text, flags)?;
+        let wrapped_function = trace_wrap_function(&function)?;
+        let digital_fnk_impl = impl_digital_fnk_trait(&function)?;
+        Ok(quote! {
+            #wrapped_function
+
+            #[allow(non_camel_case_types)]
+            #[doc(hidden)]
+            #vis struct #name #impl_generics {#(#phantom_fields,)*}
+
+            #digital_fnk_impl
+
+            impl #impl_generics rhdl::core::digital_fn::DigitalFn for #name #ty_generics #where_clause {
+                fn kernel_fn() -> Option<rhdl::core::digital_fn::KernelFnKind> {
+                    #kernel
+                }
+            }
+        })
+    }
+
+    // Generate the body of the `kernel_fn` for a method in a #[kernel]
+    // impl block.  The receiver is treated as an argument named `self`.
+    fn method(
+        &mut self,
+        attrs: &Punctuated<Ident, Token![,]>,
+        method: &syn::ImplItemFn,
+    ) -> Result<TS> {
+        let text = span_file_path(method.span())
+            .map(|x| quote! {Some(#x)})
+            .unwrap_or(quote! {None});
+        let root_id = self.id(&method, &method.attrs);
+        let weak_partial_flag = attrs.iter().any(|x| x == "allow_weak_partial");
+        let flags = if !weak_partial_flag {
+            quote!(vec![])
+        } else {
+            quote!(vec![rhdl::core::ast::KernelFlags::AllowWeakPartial])
+        };
+        let key = method_name_type(&method.sig.ident);
+        let fn_id = quote!(std::any::TypeId::of::<
+            rhdl::core::digital_fn::MethodMarker<Self, #key>,
+        >());
+        self.kernel_fn_body(root_id, &method.sig, &method.block, fn_id, text, flags)
+    }
+
+    fn kernel_fn_body(
+        &mut self,
+        root_id: u32,
+        sig: &syn::Signature,
+        block: &syn::Block,
+        fn_id: TS,
+        text: TS,
+        flags: TS,
+    ) -> Result<TS> {
+        let name = &sig.ident;
         // Put the function arguments into the current scope
-        for arg in function.sig.inputs.iter() {
+        for arg in sig.inputs.iter() {
             match arg {
-                syn::FnArg::Receiver(_) => {
-                    return Err(syn::Error::new(
-                        arg.span(),
-                        "Unsupported receiver in rhdl kernel function",
-                    ));
+                syn::FnArg::Receiver(receiver) => {
+                    self.scopes[self.active_scope.0]
+                        .bindings
+                        .insert(Ident::from(receiver.self_token));
                 }
                 syn::FnArg::Typed(pat) => {
                     self.add_scoped_binding(&pat.pat)?;
                 }
             }
         }
-        let block = self.block_inner(&function.block)?;
-        let ret = match &function.sig.output {
+        let mut tagged = block.clone();
+        let mut tagger = MethodCallTagger::default();
+        tagger.visit_block_mut(&mut tagged);
+        let block_ts = self.block_inner(&tagged)?;
+        let ret = match &sig.output {
             syn::ReturnType::Default => quote! {rhdl::core::Kind::Empty},
             syn::ReturnType::Type(_, ty) => {
                 quote! {
@@ -661,15 +1016,22 @@ impl Context {
                 }
             }
         };
-        let args = &function
-            .sig
+        let args = &sig
             .inputs
             .iter()
             .map(|arg| match arg {
-                syn::FnArg::Receiver(_) => Err(syn::Error::new(
-                    arg.span(),
-                    "Unsupported receiver in rhdl kernel function",
-                )),
+                syn::FnArg::Receiver(receiver) => {
+                    let mutability = receiver.mutability.is_some();
+                    let pat_id = self.id(&receiver.self_token, &[]);
+                    let id = self.id(&arg, &[]);
+                    Ok(quote! {
+                        type_pat(
+                            #id.into(),
+                            ident_pat(#pat_id.into(), "self", #mutability),
+                            <Self as rhdl::core::Digital>::static_kind()
+                        )
+                    })
+                }
                 syn::FnArg::Typed(pat) => {
                     let ty = &pat.ty;
                     let pat = self.pat(&pat.pat)?;
@@ -679,34 +1041,47 @@ impl Context {
                 }
             })
             .collect::<Result<Vec<_>>>()?;
-        let wrapped_function = trace_wrap_function(&function)?;
-        let digital_fnk_impl = impl_digital_fnk_trait(&function)?;
         let json_data = serde_json::to_string(&self.meta_db).unwrap();
-        Ok(quote! {
-            #wrapped_function
-
-            #[allow(non_camel_case_types)]
-            #[doc(hidden)]
-            #vis struct #name #impl_generics {#(#phantom_fields,)*}
-
-            #digital_fnk_impl
-
-            impl #impl_generics rhdl::core::digital_fn::DigitalFn for #name #ty_generics #where_clause {
-                fn kernel_fn() -> Option<rhdl::core::digital_fn::KernelFnKind> {
-                    use rhdl::core::ast::builder::*;
-                    const META_DATA: &'static str = #json_data;
-                    Some(kernel_fn(
-                        #root_id.into(),
-                        stringify!(#orig_name),
-                        vec!{#(#args),*},
-                        #ret,
-                        #block,
-                        std::any::TypeId::of::<#name #ty_generics>(),
-                        #text,
-                        rhdl::serde_json::from_str(META_DATA).unwrap(),
-                        #flags
-                    ))
+        let kernel = quote! {
+            use rhdl::core::ast::builder::*;
+            const META_DATA: &'static str = #json_data;
+            Some(kernel_fn(
+                #root_id.into(),
+                stringify!(#name),
+                vec!{#(#args),*},
+                #ret,
+                #block_ts,
+                #fn_id,
+                #text,
+                rhdl::serde_json::from_str(META_DATA).unwrap(),
+                #flags
+            ))
+        };
+        if tagger.names.is_empty() {
+            return Ok(kernel);
+        }
+        // The builder functions are kept out of scope of the shadow
+        // copy of the body, so that they cannot shadow the names it uses.
+        let inputs = sig
+            .inputs
+            .iter()
+            .map(|arg| match arg {
+                syn::FnArg::Receiver(receiver) => {
+                    let mutability = &receiver.mutability;
+                    (quote!(#mutability __rhdl_self), quote!(Self))
+                }
+                syn::FnArg::Typed(pat) => {
+                    let ty = &pat.ty;
+                    let pat = &pat.pat;
+                    (quote!(#pat), quote!(#ty))
                 }
+            })
+            .collect::<Vec<_>>();
+        let resolver = method_resolver(&inputs, &sig.output, &tagged, tagger.names.len());
+        Ok(quote! {
+            #resolver
+            {
+                #kernel
             }
         })
     }
@@ -937,30 +1312,10 @@ impl Context {
     }
 
     fn method_call(&mut self, expr: &syn::ExprMethodCall) -> Result<TS> {
+        let tag = method_tag(&expr.attrs);
+        let mut expr = expr.clone();
+        expr.attrs.retain(|attr| !attr.path().is_ident(METHOD_TAG));
         let id = self.id(&expr, &expr.attrs);
-        const KNOWN_METHODS: &[&str] = &[
-            "any",
-            "all",
-            "xor",
-            "as_signed",
-            "as_unsigned",
-            "val",
-            "resize",
-            "raw",
-            "xadd",
-            "xsub",
-            "xmul",
-            "xneg",
-            "xext",
-            "xshl",
-            "xshr",
-            "xsgn",
-            "dyn_bits",
-            "as_bits",
-            "as_signed_bits",
-        ];
-        const TURBO_METHODS: &[&str] = &["resize", "xext", "xshl", "xshr"];
-
         let receiver = self.expr(&expr.receiver)?;
         let args = expr
             .args
@@ -968,6 +1323,26 @@ impl Context {
             .map(|x| self.expr(x))
             .collect::<Result<Vec<_>>>()?;
         let method = &expr.method;
+        if let Some(ndx) = tag {
+            // A user defined method is called like a function with the
+            // receiver as the first argument.  The signature and kernel
+            // come from the resolved methods (see `method_resolver`).
+            if let Some(turbofish) = &expr.turbofish {
+                return Err(syn::Error::new(
+                    turbofish.span(),
+                    "Unsupported turbofish on a #[kernel] method call",
+                ));
+            }
+            return Ok(quote! {
+                call_expr(
+                    #id.into(),
+                    path(vec![path_segment(stringify!(#method), path_arguments_none())],),
+                    vec![#receiver, #(#args),*],
+                    __rhdl_methods[#ndx].0.clone(),
+                    __rhdl_methods[#ndx].1.clone()
+                )
+            });
+        }
         if !KNOWN_METHODS.contains(&method.to_string().as_str()) {
             return Err(syn::Error::new(
                 method.span(),
@@ -1298,29 +1673,25 @@ impl Context {
 
     fn match_ex(&mut self, expr: &syn::ExprMatch) -> Result<TS> {
         let id = self.id(&expr, &expr.attrs);
-        for arm in &expr.arms {
-            if let Some(guard) = &arm.guard {
-                return Err(syn::Error::new(
-                    guard.1.span(),
-                    "Guards are not supported in rhdl kernel function match expressions",
-                ));
-            }
-        }
         let arms = expr
             .arms
             .iter()
-            .map(|x| self.arm(&x.pat, &x.body))
+            .map(|x| self.arm(&x.pat, x.guard.as_ref().map(|g| g.1.as_ref()), &x.body))
             .collect::<Result<Vec<_>>>()?;
         let expr = self.expr(&expr.expr)?;
         Ok(quote! {
             match_expr(#id.into(), #expr, vec![#(#arms),*])
         })
     }
-    fn arm(&mut self, pat: &syn::Pat, body: &syn::Expr) -> Result<TS> {
+    fn arm(&mut self, pat: &syn::Pat, guard: Option<&syn::Expr>, body: &syn::Expr) -> Result<TS> {
         let id = self.id(&pat, &[]);
         let option_or_result_discriminant = get_pattern_option_or_result_discriminant(pat);
         self.new_scope();
-        let arm = if !pattern_has_bindings(pat) && option_or_result_discriminant.is_none() {
+        let (kind, guard, body) = if !pattern_has_bindings(pat)
+            && !pattern_has_wildcards(pat)
+            && option_or_result_discriminant.is_none()
+        {
+            let guard = guard.map(|x| self.expr(x)).transpose()?;
             let body = self.expr(body)?;
             let kind = if let syn::Pat::Wild(_) = &pat {
                 quote! {arm_kind_wild()}
@@ -1330,9 +1701,11 @@ impl Context {
                 let pat = self.rewrite_pattern_as_typed_bits(pat)?;
                 quote! {arm_kind_constant(#pat)}
             };
-            quote! {arm(#id.into(), #kind, #body)}
+            (kind, guard, body)
         } else {
             self.add_scoped_binding(pat)?;
+            // The guard can refer to the bindings introduced by the pattern
+            let guard = guard.map(|x| self.expr(x)).transpose()?;
             let body = self.expr(body)?;
             let mut discriminant = option_or_result_discriminant.map(|x| quote!(#x.typed_bits()));
             if discriminant.is_none() {
@@ -1347,7 +1720,11 @@ impl Context {
             }
             let inner = self.pat(pat)?;
             let kind = quote! {arm_kind_enum(#inner, #discriminant)};
-            quote! {arm(#id.into(), #kind, #body)}
+            (kind, guard, body)
+        };
+        let arm = match guard {
+            Some(guard) => quote! {guarded_arm(#id.into(), #kind, #guard, #body)},
+            None => quote! {arm(#id.into(), #kind, #body)},
         };
         self.end_scope();
         Ok(arm)
@@ -1365,6 +1742,7 @@ impl Context {
         let option_or_result_discriminant = get_pattern_option_or_result_discriminant(pat);
         self.new_scope();
         let (kind, body) = if !pattern_has_bindings(&let_expr.pat)
+            && !pattern_has_wildcards(&let_expr.pat)
             && option_or_result_discriminant.is_none()
         {
             let body = self.block_inner(then_branch)?;
@@ -1640,6 +2018,8 @@ impl Context {
             syn::BinOp::Add(_) => quote!(rhdl::core::ast::builder::BinOp::Add),
             syn::BinOp::Sub(_) => quote!(rhdl::core::ast::builder::BinOp::Sub),
             syn::BinOp::Mul(_) => quote!(rhdl::core::ast::builder::BinOp::Mul),
+            syn::BinOp::Div(_) => quote!(rhdl::core::ast::builder::BinOp::Div),
+            syn::BinOp::Rem(_) => quote!(rhdl::core::ast::builder::BinOp::Rem),
             syn::BinOp::And(_) => quote!(rhdl::core::ast::builder::BinOp::And),
             syn::BinOp::Or(_) => quote!(rhdl::core::ast::builder::BinOp::Or),
             syn::BinOp::BitXor(_) => quote!(rhdl::core::ast::builder::BinOp::BitXor),
@@ -1656,6 +2036,8 @@ impl Context {
             syn::BinOp::AddAssign(_) => quote!(rhdl::core::ast::builder::BinOp::AddAssign),
             syn::BinOp::SubAssign(_) => quote!(rhdl::core::ast::builder::BinOp::SubAssign),
             syn::BinOp::MulAssign(_) => quote!(rhdl::core::ast::builder::BinOp::MulAssign),
+            syn::BinOp::DivAssign(_) => quote!(rhdl::core::ast::builder::BinOp::DivAssign),
+            syn::BinOp::RemAssign(_) => quote!(rhdl::core::ast::builder::BinOp::RemAssign),
             syn::BinOp::BitXorAssign(_) => quote!(rhdl::core::ast::builder::BinOp::BitXorAssign),
             syn::BinOp::BitAndAssign(_) => quote!(rhdl::core::ast::builder::BinOp::BitAndAssign),
             syn::BinOp::BitOrAssign(_) => quote!(rhdl::core::ast::builder::BinOp::BitOrAssign),
@@ -17,6 +17,17 @@ fn define_children_fn(field_set: &FieldSet) -> TokenStream {
                 #(Synchronous::descriptor(&self.#component_name, parent_scope.with(stringify!(#component_name)))),*
             ].into_iter()
         }
+        fn descendants(&self, parent_scope: &rhdl::core::ScopedName) -> impl Iterator<Item = Result<rhdl::core::Descriptor<rhdl::core::SyncKind>, rhdl::core::RHDLError>> {
+            let descendants: Vec<Vec<Result<rhdl::core::Descriptor<rhdl::core::SyncKind>, rhdl::core::RHDLError>>> = vec![
+                #({
+                    let scope = parent_scope.with(stringify!(#component_name));
+                    std::iter::once(Synchronous::descriptor(&self.#component_name, scope.clone()))
+                        .chain(Synchronous::descendants(&self.#component_name, &scope))
+                        .collect()
+                }),*
+            ];
+            descendants.into_iter().flatten()
+        }
     }
 }
 
@@ -40,6 +40,20 @@ pub fn translate_raw(
                 subfields: vec![],
             })
         }
+        TraceType::Fixed(int, frac) | TraceType::SignedFixed(int, frac) => {
+            let n = int + frac;
+            let signed = matches!(ty, TraceType::SignedFixed(_, _));
+            Ok(TranslationResult {
+                kind,
+                val: fixed_value(&bits[0..n], *frac, signed).unwrap_or_else(|| {
+                    ValueRepr::Bits(
+                        n as u64,
+                        bits.iter().take(n).copied().rev().map(bit_char).collect(),
+                    )
+                }),
+                subfields: vec![],
+            })
+        }
         TraceType::Array(inner) => {
             let mut subfields = vec![];
             let mut bits = bits;
@@ -155,6 +169,23 @@ pub fn translate_raw(
     }
 }
 
+// Decode a fixed point value with `frac` fractional bits into its real value.
+// Returns `None` if any of the bits are not a plain 0 or 1.
+fn fixed_value(bits: &[TraceBit], frac: usize, signed: bool) -> Option<ValueRepr> {
+    let mut val = 0.0_f64;
+    for (ndx, bit) in bits.iter().enumerate() {
+        match bit {
+            TraceBit::One => val += 2.0_f64.powi(ndx as i32 - frac as i32),
+            TraceBit::Zero => {}
+            _ => return None,
+        }
+    }
+    if signed && bits.last() == Some(&TraceBit::One) {
+        val -= 2.0_f64.powi(bits.len() as i32 - frac as i32);
+    }
+    Some(ValueRepr::String(format!("{val}")))
+}
+
 #[cfg(test)]
 mod tests {
     use rhdl_trace_type::RTT;
@@ -9,6 +9,7 @@ pub fn trace_type_width_in_bits(ty: &TraceType) -> usize {
     match ty {
         TraceType::Empty => 0,
         TraceType::Signed(x) | TraceType::Bits(x) => *x,
+        TraceType::Fixed(int, frac) | TraceType::SignedFixed(int, frac) => int + frac,
         TraceType::Clock | TraceType::Reset => 1,
         TraceType::Array(inner) => inner.size * trace_type_width_in_bits(&inner.base),
         TraceType::Struct(inner) => inner
@@ -50,7 +51,11 @@ pub fn color_for_binary_representation(s: &str) -> ValueKind {
 
 pub fn not_present(ty: &TraceType) -> TranslationResult {
     match ty {
-        TraceType::Empty | TraceType::Bits(_) | TraceType::Signed(_) => TranslationResult {
+        TraceType::Empty
+        | TraceType::Bits(_)
+        | TraceType::Signed(_)
+        | TraceType::Fixed(_, _)
+        | TraceType::SignedFixed(_, _) => TranslationResult {
             val: ValueRepr::NotPresent,
             subfields: vec![],
             kind: ValueKind::Normal,
@@ -157,7 +162,11 @@ pub fn discriminant<'a>(
 pub fn trace_type_to_variable_info(ty: &TraceType) -> VariableInfo {
     match ty {
         TraceType::Signed(1) | TraceType::Bits(1) => VariableInfo::Bool,
-        TraceType::Empty | TraceType::Signed(_) | TraceType::Bits(_) => VariableInfo::Bits,
+        TraceType::Empty
+        | TraceType::Signed(_)
+        | TraceType::Bits(_)
+        | TraceType::Fixed(_, _)
+        | TraceType::SignedFixed(_, _) => VariableInfo::Bits,
         TraceType::Clock => VariableInfo::Clock,
         TraceType::Reset => VariableInfo::Bool,
         TraceType::Tuple(inner) => VariableInfo::Compound {
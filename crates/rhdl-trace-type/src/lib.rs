@@ -28,6 +28,11 @@ pub enum TraceType {
     Bits(usize),
     /// Signed bit vector of given width
     Signed(usize),
+    /// Unsigned fixed point value with the given integer and fractional widths
+    Fixed(usize, usize),
+    /// Signed fixed point value with the given integer (including the sign bit)
+    /// and fractional widths
+    SignedFixed(usize, usize),
     /// Signal carrying data of type T with given color
     Signal(Box<TraceType>, Color),
     /// Clock signal
@@ -243,6 +243,8 @@ pub enum BinaryOp {
     Mod,
     /// `*` operator
     Mul,
+    /// `/` operator
+    Div,
 }
 
 impl Parse for BinaryOp {
@@ -322,6 +324,9 @@ impl Parse for BinaryOp {
         } else if lookahead.peek(Token![*]) {
             let _: Token![*] = input.parse()?;
             Ok(BinaryOp::Mul)
+        } else if lookahead.peek(Token![/]) {
+            let _: Token![/] = input.parse()?;
+            Ok(BinaryOp::Div)
         } else {
             Err(input.error("expected binary operator"))
         }
@@ -351,6 +356,7 @@ impl Pretty for BinaryOp {
             BinaryOp::Xor => formatter.write("^"),
             BinaryOp::Mod => formatter.write("%"),
             BinaryOp::Mul => formatter.write("*"),
+            BinaryOp::Div => formatter.write("/"),
         }
     }
 }
@@ -421,6 +427,9 @@ impl ToTokens for BinaryOp {
             BinaryOp::Mul => {
                 tokens.extend(quote! { * });
             }
+            BinaryOp::Div => {
+                tokens.extend(quote! { / });
+            }
         }
     }
 }
@@ -431,7 +440,7 @@ impl BinaryOp {
     /// and the second element is the right binding power.
     pub fn binding_power(&self) -> (u8, u8) {
         match self {
-            BinaryOp::Mod | BinaryOp::Mul => (20, 21),
+            BinaryOp::Mod | BinaryOp::Mul | BinaryOp::Div => (20, 21),
             BinaryOp::Plus | BinaryOp::Minus => (18, 19),
             BinaryOp::Shl | BinaryOp::Shr | BinaryOp::SignedRightShift => (16, 17),
             BinaryOp::Ge | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Lt => (14, 15),
@@ -607,6 +607,7 @@ fn test_binary_expressions() -> miette::Result<()> {
               b = a ^ b;
               b = a % b;
               b = a * b;
+              b = a / b;
            end
         endmodule
     "#]];
@@ -634,6 +635,7 @@ fn test_binary_expressions() -> miette::Result<()> {
                 b = a ^ b;
                 b = a % b;
                 b = a * b;
+                b = a / b;
             end
             endmodule
     ",
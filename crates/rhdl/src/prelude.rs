@@ -1,21 +1,33 @@
 pub use rhdl_bits::BitWidth;
 pub use rhdl_bits::Bits;
 pub use rhdl_bits::SignedBits;
+pub use rhdl_bits::SignedWideBits;
+pub use rhdl_bits::WideBitWidth;
+pub use rhdl_bits::WideBits;
 pub use rhdl_bits::alias::*;
 pub use rhdl_bits::bits;
+pub use rhdl_bits::fixed::{
+    Convergent, Fixed, FixedPoint, HalfUp, Saturate, SignedFixed, Truncate, Wrap, quantize,
+};
 pub use rhdl_bits::signed;
+pub use rhdl_bits::signed_wide;
+pub use rhdl_bits::wide_bits;
 pub use rhdl_core::CircuitDQ;
 pub use rhdl_core::ClockReset;
 pub use rhdl_core::CompilationMode;
 pub use rhdl_core::TraceKey;
 pub use rhdl_core::circuit::adapter::Adapter;
 pub use rhdl_core::circuit::circuit_impl::Circuit;
+pub use rhdl_core::circuit::clock_enable::{ClockEnable, clock_enable};
 pub use rhdl_core::circuit::circuit_impl::CircuitIO;
+pub use rhdl_core::circuit::function::pipelined::Pipelined;
 pub use rhdl_core::circuit::function::synchronous::Func;
 pub use rhdl_core::circuit::hdl_descriptor::HDLDescriptor;
+pub use rhdl_core::circuit::reset_style::{ResetStyle, ResetStyled, reset_style};
 pub use rhdl_core::circuit::synchronous::Synchronous;
 pub use rhdl_core::circuit::synchronous::SynchronousDQ;
 pub use rhdl_core::circuit::synchronous::SynchronousIO;
+pub use rhdl_core::circuit::vhdl_descriptor::VHDLDescriptor;
 pub use rhdl_core::compile_design;
 pub use rhdl_core::compiler::driver::compile_design_stage1;
 pub use rhdl_core::error::RHDLError;
@@ -74,8 +86,11 @@ pub use rhdl_core::bitx::bitx_parse;
 pub use rhdl_core::bitx::bitx_string;
 pub use rhdl_core::bitx_vec;
 pub use rhdl_core::circuit::drc;
+pub use rhdl_core::circuit::timing;
+pub use rhdl_core::circuit::resources;
 pub use rhdl_core::circuit::fixture::Driver;
 pub use rhdl_core::circuit::fixture::ExportError;
+pub use rhdl_core::circuit::foreign::ForeignBuilder;
 pub use rhdl_core::circuit::fixture::Fixture;
 pub use rhdl_core::circuit::fixture::MountPoint;
 pub use rhdl_core::circuit::fixture::passthrough_input_driver;
@@ -94,8 +109,14 @@ pub use rhdl_core::sim::iter::uniform::uniform;
 pub use rhdl_core::sim::probe::context_around::AroundEventExt;
 pub use rhdl_core::sim::probe::ext::ProbeExt;
 pub use rhdl_core::sim::probe::ext::SynchronousProbeExt;
+pub use rhdl_core::sim::run::async_fn::ClockSpec;
+pub use rhdl_core::sim::run::async_fn::DomainClock;
+pub use rhdl_core::sim::run::async_fn::run_async;
 pub use rhdl_core::sim::run::async_fn::run_async_red_blue;
 pub use rhdl_core::sim::run::asynchronous::RunExt;
+pub use rhdl_core::sim::run::compiled::{Decode, RunCompiledExt};
+pub use rhdl_core::sim::run::netlist::RunNetListExt;
+pub use rhdl_core::sim::run::netlist::RunSynchronousNetListExt;
 pub use rhdl_core::sim::run::sync_fn::RunSynchronousFeedbackExt;
 pub use rhdl_core::sim::run::synchronous::RunSynchronousExt;
 pub use rhdl_core::sim::testbench::TestBenchOptions;
@@ -0,0 +1,125 @@
+use rand::prelude::*;
+use rhdl::core::ntl::{compiled::CompiledNetList, vm::NetListVM};
+use rhdl::core::sim::run::compiled::run_compiled;
+use rhdl::prelude::*;
+
+type Input = (s8, s8, b3, b8, b8);
+type Output = (
+    s8,
+    s8,
+    s8,
+    s8,
+    bool,
+    bool,
+    bool,
+    b8,
+    b8,
+    b8,
+    (bool, bool, bool),
+);
+
+#[kernel]
+fn alu(cr: ClockReset, i: Input) -> Output {
+    let (a, b, n, c, d) = i;
+    let quotient = if d == bits(0) { bits(0) } else { c / d };
+    let reduced = (c.any(), c.all(), (c ^ d).xor());
+    let wrapped = if cr.reset.any() { bits(0) } else { c << n };
+    (
+        a + b,
+        a * b,
+        a >> n,
+        -a,
+        a < b,
+        a >= b,
+        c == d,
+        c - d,
+        wrapped,
+        quotient,
+        reduced,
+    )
+}
+
+fn random_inputs() -> impl Iterator<Item = TimedSample<(ClockReset, Input)>> + Clone {
+    let mut rng = StdRng::seed_from_u64(0x5eed);
+    (0..500)
+        .map(|_| {
+            (
+                signed(rng.random_range(-128..128)),
+                signed(rng.random_range(-128..128)),
+                bits(rng.random_range(0..8)),
+                bits(rng.random_range(0..256)),
+                bits(rng.random_range(0..4)),
+            )
+        })
+        .collect::<Vec<_>>()
+        .with_reset(1)
+        .clock_pos_edge(100)
+}
+
+#[test]
+fn test_compiled_kernel_matches_sim() -> miette::Result<()> {
+    let uut = Func::try_new::<alu>()?;
+    let mut compiled = run_compiled(
+        &uut,
+        std::iter::empty::<TimedSample<(ClockReset, Input)>>(),
+        |_| Output::dont_care(),
+    )?;
+    for sample in uut.run(random_inputs()) {
+        let actual = compiled.eval(sample.input)?;
+        assert_eq!(actual, sample.output.typed_bits());
+    }
+    Ok(())
+}
+
+// Unknown bits are propagated through these operations, rather than
+// being an error (as they are for multiplication, division and shifts)
+#[kernel]
+fn select(
+    _cr: ClockReset,
+    i: (s8, s8, b8, b2),
+) -> (s8, s8, bool, bool, s8, (bool, bool, bool), b8) {
+    let (a, b, c, d) = i;
+    let picked = if c.any() { a } else { b };
+    let mapped = match d.raw() {
+        0 => c,
+        1 => !c,
+        _ => c ^ bits(0x55),
+    };
+    (
+        a + b,
+        a - b,
+        a < b,
+        a == b,
+        picked,
+        (c.any(), c.all(), c.xor()),
+        mapped,
+    )
+}
+
+#[test]
+fn test_compiled_matches_interpreter_with_unknowns() -> miette::Result<()> {
+    let uut = Func::try_new::<select>()?;
+    let netlist = uut.descriptor("top".into())?.netlist()?.clone();
+    let mut vm = NetListVM::new(&netlist)?;
+    let mut compiled = CompiledNetList::new(&netlist)?;
+    let mut rng = StdRng::seed_from_u64(0xdead);
+    let mut random = |len: usize| {
+        (0..len)
+            .map(|_| match rng.random_range(0..10) {
+                0 => BitX::X,
+                n if n % 2 == 0 => BitX::Zero,
+                _ => BitX::One,
+            })
+            .collect::<Vec<_>>()
+    };
+    for _ in 0..500 {
+        let args = netlist
+            .inputs
+            .iter()
+            .map(|input| random(input.len()))
+            .collect::<Vec<_>>();
+        let args = args.iter().map(|arg| &arg[..]).collect::<Vec<_>>();
+        assert_eq!(vm.step(&args)?, compiled.step(&args)?);
+    }
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use rhdl::core::circuit::foreign::ForeignSynchronous;
+use rhdl::core::sim::cosim::Cosim;
+use rhdl::core::sim::testbench::synchronous::SynchronousTestBench;
+use rhdl::prelude::*;
+
+#[cfg(test)]
+mod common;
+#[cfg(test)]
+use common::*;
+
+mod splitter {
+    use super::*;
+
+    #[derive(Clone, Debug, Synchronous, SynchronousDQ, Default)]
+    #[rhdl(dq_no_prefix)]
+    pub struct U {}
+
+    impl SynchronousIO for U {
+        type I = b4;
+        type O = (b4, bool);
+        type Kernel = splitter;
+    }
+
+    #[kernel]
+    pub fn splitter(_cr: ClockReset, i: b4, _q: Q) -> ((b4, bool), D) {
+        ((i + 1, i.any()), D {})
+    }
+}
+
+#[test]
+fn test_cosim_matches_rust() -> miette::Result<()> {
+    let uut = splitter::U::default();
+    let inputs = exhaustive::<4>().with_reset(4).clock_pos_edge(100);
+    let test_bench = uut.run(inputs).collect::<SynchronousTestBench<_, _>>();
+    test_bench.cosim(&uut, &TestBenchOptions::default())?;
+    Ok(())
+}
+
+#[test]
+fn test_cosim_reports_first_mismatch() -> miette::Result<()> {
+    let uut = splitter::U::default();
+    let inputs = exhaustive::<4>().with_reset(4).clock_pos_edge(100);
+    // Corrupt the recorded output of one of the samples
+    let test_bench = uut
+        .run(inputs)
+        .map(|s| s.to_timed_sample().map(|((cr, i), o)| (cr, i, o)))
+        .map(|mut s| {
+            let (cr, i, o) = s.value;
+            if i == bits(7) {
+                s.value = (cr, i, (o.0, !o.1));
+            }
+            s
+        })
+        .collect::<SynchronousTestBench<_, _>>();
+    let Err(RHDLError::CosimMismatch { diff, .. }) =
+        test_bench.cosim(&uut, &TestBenchOptions::default())
+    else {
+        panic!("Expected a co-simulation mismatch");
+    };
+    assert!(diff.contains(".1"));
+    assert!(!diff.contains(".0"));
+    Ok(())
+}
+
+mod parent {
+    use super::*;
+
+    // A 4 bit register that only exists in Verilog
+    pub fn reg4() -> miette::Result<Cosim<ForeignSynchronous<b4, b4>>> {
+        let module: vlog::ModuleDef = parse_quote_miette! {
+            module reg4(input wire clk, input wire rst, input wire [3:0] d, output reg [3:0] q);
+                initial begin
+                    q = 4'b0000;
+                end
+                always @(posedge clk) begin
+                    if (rst) begin
+                        q <= 4'b0000;
+                    end else begin
+                        q <= d;
+                    end
+                end
+            endmodule
+        }?;
+        let reg = ForeignBuilder::<b4, b4>::new(module)
+            .clock("clk")
+            .reset("rst")
+            .input("d", Path::default())
+            .output("q", Path::default())
+            .synchronous()?;
+        Ok(Cosim::new(reg, Decode::decode))
+    }
+
+    #[derive(Clone, Synchronous, SynchronousDQ)]
+    #[rhdl(dq_no_prefix)]
+    pub struct Parent {
+        pub reg: Cosim<ForeignSynchronous<b4, b4>>,
+    }
+
+    impl SynchronousIO for Parent {
+        type I = b4;
+        type O = b4;
+        type Kernel = parent;
+    }
+
+    #[kernel]
+    pub fn parent(_cr: ClockReset, i: b4, q: Q) -> (b4, D) {
+        (q.reg + 1, D { reg: i })
+    }
+}
+
+#[test]
+fn test_cosim_verilog_only_child() -> miette::Result<()> {
+    let uut = parent::Parent {
+        reg: parent::reg4()?,
+    };
+    let inputs = exhaustive::<4>().with_reset(4).clock_pos_edge(100);
+    let samples = uut
+        .run(inputs.clone())
+        .synchronous_sample()
+        .collect::<Vec<_>>();
+    // The register delays the input by one clock
+    for window in samples.windows(2) {
+        let ((cr, i), next) = (window[0].input, window[1].output);
+        if !cr.reset.any() {
+            assert_eq!(next, i + 1);
+        }
+    }
+    let test_bench = uut.run(inputs).collect::<SynchronousTestBench<_, _>>();
+    test_bench.cosim(&uut, &TestBenchOptions::default())?;
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use rhdl::prelude::*;
+
+#[cfg(test)]
+mod common;
+
+#[cfg(test)]
+use common::*;
+use rhdl::core::sim::testbench::kernel::test_kernel_vm_and_verilog;
+
+#[test]
+fn test_div_rem_unsigned() -> miette::Result<()> {
+    #[kernel]
+    fn do_stuff(a: Signal<b4, Red>, b: Signal<b4, Red>) -> Signal<(b4, b4), Red> {
+        let a = a.val();
+        let b = b.val();
+        signal((a / b, a % b))
+    }
+
+    let args = tuple_pair_b4_red();
+    test_kernel_vm_and_verilog::<do_stuff, _, _, _>(do_stuff, args)?;
+    Ok(())
+}
+
+#[test]
+fn test_div_rem_signed() -> miette::Result<()> {
+    #[kernel]
+    fn do_stuff(a: Signal<s4, Red>, b: Signal<s4, Red>) -> Signal<(s4, s4), Red> {
+        let a = a.val();
+        let b = b.val();
+        signal((a / b, a % b))
+    }
+
+    let args = tuple_pair_sn_red::<4>();
+    test_kernel_vm_and_verilog::<do_stuff, _, _, _>(do_stuff, args)?;
+    Ok(())
+}
+
+#[test]
+fn test_div_rem_assign() -> miette::Result<()> {
+    #[kernel]
+    fn do_stuff(a: Signal<b4, Red>, b: Signal<b4, Red>) -> Signal<(b4, b4), Red> {
+        let mut q = a.val();
+        let mut r = a.val();
+        q /= b.val();
+        r %= b.val();
+        signal((q, r))
+    }
+
+    let args = tuple_pair_b4_red();
+    test_kernel_vm_and_verilog::<do_stuff, _, _, _>(do_stuff, args)?;
+    Ok(())
+}
+
+#[test]
+#[allow(clippy::modulo_one)]
+fn test_div_rem_unsigned_by_power_of_two() -> miette::Result<()> {
+    #[kernel]
+    fn do_stuff(a: Signal<b8, Red>) -> Signal<(b8, b8, b8, b8), Red> {
+        let a = a.val();
+        signal((a / 4, a % 8, a / 1, a % 1))
+    }
+
+    test_kernel_vm_and_verilog::<do_stuff, _, _, _>(do_stuff, tuple_b8())?;
+    Ok(())
+}
+
+#[test]
+fn test_div_rem_signed_by_power_of_two() -> miette::Result<()> {
+    #[kernel]
+    fn do_stuff(a: Signal<s8, Red>) -> Signal<(s8, s8, s8, s8), Red> {
+        let a = a.val();
+        signal((a / 4, a % 4, a / 64, a % 64))
+    }
+
+    test_kernel_vm_and_verilog::<do_stuff, _, _, _>(do_stuff, s8_red())?;
+    Ok(())
+}
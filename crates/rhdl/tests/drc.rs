@@ -0,0 +1,243 @@
+use rhdl::{core::circuit::foreign::ForeignSynchronous, prelude::*};
+
+#[test]
+fn test_drc_unused_inputs() -> miette::Result<()> {
+    #[kernel]
+    fn first(_cr: ClockReset, a: (b4, b4)) -> b4 {
+        a.0
+    }
+    let uut = Func::try_new::<first>()?;
+    let report = drc::DesignRules::default().check_synchronous(&uut)?;
+    let unused = report.violations_of("unused_inputs").collect::<Vec<_>>();
+    assert_eq!(unused.len(), 1);
+    assert_eq!(unused[0].location, "top");
+    assert!(unused[0].message.contains("4..=7"));
+    // Unused inputs are only a warning by default
+    assert!(!report.has_errors());
+    let report = drc::DesignRules::default()
+        .allow("unused_inputs", "top")
+        .check_synchronous(&uut)?;
+    assert_eq!(report.violations_of("unused_inputs").count(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_drc_constant_outputs() -> miette::Result<()> {
+    #[kernel]
+    fn pad(_cr: ClockReset, a: b4) -> (b4, b4) {
+        (a, bits(3))
+    }
+    let uut = Func::try_new::<pad>()?;
+    let report = drc::DesignRules::empty()
+        .with_rule(drc::ConstantOutputs)
+        .check_synchronous(&uut)?;
+    assert_eq!(report.violations.len(), 1);
+    assert_eq!(report.violations[0].severity, miette::Severity::Advice);
+    assert!(report.violations[0].message.contains("4..=7"));
+    assert!(report.into_result().is_ok());
+    Ok(())
+}
+
+#[test]
+fn test_drc_wide_case() -> miette::Result<()> {
+    #[kernel]
+    fn lookup(_cr: ClockReset, a: (b3, [b4; 8])) -> b4 {
+        a.1[a.0]
+    }
+    let uut = Func::try_new::<lookup>()?;
+    let report = drc::DesignRules::default().check_synchronous(&uut)?;
+    assert_eq!(report.violations_of("wide_case").count(), 0);
+    let report = drc::DesignRules::empty()
+        .with_rule(drc::WideCase { max_arms: 4 })
+        .check_synchronous(&uut)?;
+    // The mux is repeated for each bit of the output, but only reported once
+    assert_eq!(report.violations.len(), 1);
+    assert_eq!(report.violations[0].location, "lookup");
+    Ok(())
+}
+
+#[test]
+fn test_drc_wide_multiplier() -> miette::Result<()> {
+    #[kernel]
+    fn wide(_cr: ClockReset, a: (b24, b24)) -> b24 {
+        a.0 * a.1
+    }
+    #[kernel]
+    fn narrow(_cr: ClockReset, a: (b16, b16)) -> b16 {
+        a.0 * a.1
+    }
+    let rules = drc::DesignRules::empty().with_rule(drc::WideMultiplier::default());
+    let report = rules.check_synchronous(&Func::try_new::<wide>()?)?;
+    assert_eq!(report.violations.len(), 1);
+    assert_eq!(report.violations[0].location, "wide");
+    let report = rules.check_synchronous(&Func::try_new::<narrow>()?)?;
+    assert!(report.violations.is_empty());
+    // Violations can be promoted to errors, or allowed by kernel name
+    let rules = rules.with_severity("wide_multiplier", miette::Severity::Error);
+    let report = rules.check_synchronous(&Func::try_new::<wide>()?)?;
+    assert!(report.into_result().is_err());
+    let report = rules
+        .allow("wide_multiplier", "wide")
+        .check_synchronous(&Func::try_new::<wide>()?)?;
+    assert!(report.into_result().is_ok());
+    Ok(())
+}
+
+#[test]
+fn test_drc_combinational_depth() -> miette::Result<()> {
+    #[kernel]
+    fn sum(_cr: ClockReset, a: [b4; 4]) -> b4 {
+        a[0] + a[1] + a[2] + a[3]
+    }
+    let uut = Func::try_new::<sum>()?;
+    let report = drc::DesignRules::default().check_synchronous(&uut)?;
+    assert!(report.violations.is_empty());
+    let report = drc::DesignRules::empty()
+        .with_rule(drc::CombinationalDepth { max_depth: 2 })
+        .check_synchronous(&uut)?;
+    assert_eq!(report.violations.len(), 1);
+    assert_eq!(report.violations[0].location, "sum");
+    assert!(report.violations[0].message.contains("3 levels"));
+    Ok(())
+}
+
+#[kernel]
+fn first(_cr: ClockReset, a: (b4, b4)) -> b4 {
+    a.0
+}
+
+#[derive(Clone, Synchronous, SynchronousDQ)]
+pub struct Inner {
+    first: Func<(b4, b4), b4>,
+}
+
+impl SynchronousIO for Inner {
+    type I = (b4, b4);
+    type O = b4;
+    type Kernel = inner_kernel;
+}
+
+#[kernel]
+pub fn inner_kernel(_cr: ClockReset, i: (b4, b4), q: InnerQ) -> (b4, InnerD) {
+    let mut d = InnerD::dont_care();
+    d.first = i;
+    (q.first, d)
+}
+
+#[derive(Clone, Synchronous, SynchronousDQ)]
+pub struct Outer {
+    inner: Inner,
+}
+
+impl SynchronousIO for Outer {
+    type I = (b4, b4);
+    type O = b4;
+    type Kernel = outer_kernel;
+}
+
+#[kernel]
+pub fn outer_kernel(_cr: ClockReset, i: (b4, b4), q: OuterQ) -> (b4, OuterD) {
+    let mut d = OuterD::dont_care();
+    d.inner = i;
+    (q.inner, d)
+}
+
+#[test]
+fn test_drc_checks_every_level() -> miette::Result<()> {
+    let uut = Outer {
+        inner: Inner {
+            first: Func::try_new::<first>()?,
+        },
+    };
+    let report = drc::DesignRules::default().check_synchronous(&uut)?;
+    let unused = report
+        .violations_of("unused_inputs")
+        .map(|v| v.location.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(unused, ["top", "top_inner", "top_inner_first"]);
+    Ok(())
+}
+
+// A register with its own clock and reset, for use inside an
+// asynchronous circuit.
+fn register() -> miette::Result<ForeignSynchronous<b4, b4>> {
+    let module: vlog::ModuleDef = parse_quote_miette! {
+        module reg4(input wire clk, input wire rst, input wire [3:0] d, output reg [3:0] q);
+            always @(posedge clk) begin
+                if (rst) begin
+                    q <= 4'b0000;
+                end else begin
+                    q <= d;
+                end
+            end
+        endmodule
+    }?;
+    Ok(ForeignBuilder::<b4, b4>::new(module)
+        .clock("clk")
+        .reset("rst")
+        .input("d", Path::default())
+        .output("q", Path::default())
+        .synchronous()?)
+}
+
+type RegIn = Signal<(ClockReset, b4), Red>;
+
+#[derive(Clone, Circuit, CircuitDQ)]
+pub struct ResetRegister {
+    reg: Adapter<ForeignSynchronous<b4, b4>, Red>,
+}
+
+impl CircuitIO for ResetRegister {
+    type I = RegIn;
+    type O = Signal<b4, Red>;
+    type Kernel = reset_register;
+}
+
+#[kernel]
+pub fn reset_register(i: RegIn, q: ResetRegisterQ) -> (Signal<b4, Red>, ResetRegisterD) {
+    let mut d = ResetRegisterD::dont_care();
+    let (cr, data) = i.val();
+    d.reg.clock_reset = signal(cr);
+    d.reg.input = signal(data);
+    (q.reg, d)
+}
+
+#[derive(Clone, Circuit, CircuitDQ)]
+pub struct UnresetRegister {
+    reg: Adapter<ForeignSynchronous<b4, b4>, Red>,
+}
+
+impl CircuitIO for UnresetRegister {
+    type I = RegIn;
+    type O = Signal<b4, Red>;
+    type Kernel = unreset_register;
+}
+
+const NO_RESET: Reset = reset(false);
+
+#[kernel]
+pub fn unreset_register(i: RegIn, q: UnresetRegisterQ) -> (Signal<b4, Red>, UnresetRegisterD) {
+    let mut d = UnresetRegisterD::dont_care();
+    let (mut cr, data) = i.val();
+    cr.reset = NO_RESET;
+    d.reg.clock_reset = signal(cr);
+    d.reg.input = signal(data);
+    (q.reg, d)
+}
+
+#[test]
+fn test_drc_registers_never_reset() -> miette::Result<()> {
+    let rules = drc::DesignRules::empty().with_rule(drc::RegistersNeverReset);
+    let uut = ResetRegister {
+        reg: Adapter::new(register()?),
+    };
+    let report = rules.check_circuit(&uut)?;
+    assert!(report.violations.is_empty());
+    let uut = UnresetRegister {
+        reg: Adapter::new(register()?),
+    };
+    let report = rules.check_circuit(&uut)?;
+    assert_eq!(report.violations.len(), 1);
+    assert!(report.violations[0].message.contains("tied to"));
+    Ok(())
+}
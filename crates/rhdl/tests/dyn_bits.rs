@@ -5,6 +5,7 @@ mod common;
 
 #[cfg(test)]
 use common::*;
+use rhdl::core::ntl::{from_rtl::build_ntl_from_rtl, vm::NetListVM};
 use rhdl::core::sim::testbench::kernel::test_kernel_vm_and_verilog;
 
 // A macro to deduplicate the test code for the bN x bN case for
@@ -199,11 +200,14 @@ fn test_add_via_dyn_bits_fails_compile_with_mismatched() -> miette::Result<()> {
 macro_rules! check_for_op_that_causes_overflow {
     ($op: ident) => {{
         #[kernel]
-        fn do_stuff(a1: Signal<b128, Red>, a2: Signal<b128, Red>) -> Signal<b128, Red> {
+        fn do_stuff(
+            a1: Signal<WideBits<512>, Red>,
+            a2: Signal<WideBits<512>, Red>,
+        ) -> Signal<WideBits<512>, Red> {
             let a1 = a1.val().dyn_bits();
             let a2 = a2.val().dyn_bits();
             let c = a1.$op(a2);
-            let c: b128 = c.as_bits();
+            let c: WideBits<512> = c.as_wide_bits();
             signal(c)
         }
         // Should cause a TypeError with bit overflow
@@ -218,11 +222,14 @@ macro_rules! check_for_op_that_causes_overflow {
 macro_rules! check_for_signed_op_that_causes_overflow {
     ($op: ident) => {{
         #[kernel]
-        fn do_stuff(a1: Signal<s128, Red>, a2: Signal<s128, Red>) -> Signal<s128, Red> {
+        fn do_stuff(
+            a1: Signal<SignedWideBits<512>, Red>,
+            a2: Signal<SignedWideBits<512>, Red>,
+        ) -> Signal<SignedWideBits<512>, Red> {
             let a1 = a1.val().dyn_bits();
             let a2 = a2.val().dyn_bits();
             let c = a1.$op(a2);
-            let c: s128 = c.as_signed_bits();
+            let c: SignedWideBits<512> = c.as_signed_wide_bits();
             signal(c)
         }
         // Should cause a TypeError with bit overflow
@@ -246,11 +253,14 @@ fn test_xops_overflow() -> miette::Result<()> {
 #[test]
 fn test_xadd_causes_overflow_warning_at_rhdl_compile_time() -> miette::Result<()> {
     #[kernel]
-    fn do_stuff(a1: Signal<b128, Red>, a2: Signal<b128, Red>) -> Signal<b128, Red> {
+    fn do_stuff(
+        a1: Signal<WideBits<512>, Red>,
+        a2: Signal<WideBits<512>, Red>,
+    ) -> Signal<WideBits<512>, Red> {
         let a1 = a1.val().dyn_bits();
         let a2 = a2.val().dyn_bits();
         let c = a1.xadd(a2);
-        let c: b128 = c.as_bits();
+        let c: WideBits<512> = c.as_wide_bits();
         signal(c)
     }
     // Should cause a TypeError with bit overflow
@@ -279,3 +289,41 @@ fn test_xsgn_is_trapped_as_signed() -> miette::Result<()> {
     expect_test::expect_file!["expect/xsgn_is_trapped_as_signed.expect"].assert_eq(&report);
     Ok(())
 }
+
+#[test]
+fn test_wide_datapath() -> miette::Result<()> {
+    #[kernel]
+    fn do_stuff(
+        a: Signal<WideBits<256>, Red>,
+        b: Signal<WideBits<256>, Red>,
+    ) -> Signal<(WideBits<256>, WideBits<256>, bool, WideBits<257>), Red> {
+        let a = a.val();
+        let b = b.val();
+        let c: WideBits<257> = a.xadd(b).as_wide_bits();
+        signal((a + b, a << 100, a < b, c))
+    }
+
+    let wide = |hi: u128, lo: u128| (wide_bits::<256>(hi) << 128) | wide_bits(lo);
+    let vals = [
+        wide(0, 0),
+        wide(0, u128::MAX),
+        wide(u128::MAX, u128::MAX),
+        wide(1, 0),
+        wide(0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210),
+        wide(u128::MAX >> 1, 1),
+    ];
+    let args = vals
+        .into_iter()
+        .flat_map(|a| vals.into_iter().map(move |b| (red(a), red(b))));
+    test_kernel_vm_and_verilog::<do_stuff, _, _, _>(do_stuff, args.clone())?;
+    // Also check the netlist directly, so the wide logic is covered
+    // without a Verilog simulator
+    let rtl = compile_design::<do_stuff>(CompilationMode::Asynchronous)?;
+    let ntl = rhdl::core::compiler::optimize_ntl(build_ntl_from_rtl(&rtl))?;
+    let mut vm = NetListVM::new(&ntl)?;
+    for (a, b) in args {
+        let output = vm.step(&[&a.bin(), &b.bin()])?;
+        assert_eq!(output, do_stuff(a, b).bin().to_vec());
+    }
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use rhdl::prelude::*;
+
+#[cfg(test)]
+mod common;
+
+#[cfg(test)]
+use common::*;
+use rhdl::core::sim::testbench::kernel::test_kernel_vm_and_verilog;
+
+type SFx = SignedFixed<4, 4>;
+type SFxOut = SignedFixed<3, 1>;
+type UFx = Fixed<4, 4>;
+type UFxOut = Fixed<3, 2>;
+
+#[test]
+fn test_quantize_signed() -> miette::Result<()> {
+    #[kernel]
+    fn do_stuff(a: Signal<SFx, Red>) -> Signal<(SFxOut, SFxOut, SFxOut, SFxOut), Red> {
+        let a = a.val();
+        let b = quantize::<Truncate, Wrap, SFx, SFxOut>(a);
+        let c = quantize::<HalfUp, Wrap, SFx, SFxOut>(a);
+        let d = quantize::<Convergent, Saturate, SFx, SFxOut>(a);
+        let e = quantize::<Truncate, Saturate, SFx, SFxOut>(a);
+        signal((b, c, d, e))
+    }
+
+    let args = exhaustive_signed::<8>()
+        .into_iter()
+        .map(|a| (red(a.as_signed_fixed::<4, 4>()),));
+    test_kernel_vm_and_verilog::<do_stuff, _, _, _>(do_stuff, args)?;
+    Ok(())
+}
+
+#[test]
+fn test_quantize_unsigned() -> miette::Result<()> {
+    #[kernel]
+    fn do_stuff(a: Signal<UFx, Red>) -> Signal<(UFxOut, UFxOut, Fixed<3, 6>, Fixed<6, 4>), Red> {
+        let a = a.val();
+        let b = quantize::<HalfUp, Saturate, UFx, UFxOut>(a);
+        let c = quantize::<Convergent, Wrap, UFx, UFxOut>(a);
+        let d = quantize::<Truncate, Saturate, UFx, Fixed<3, 6>>(a);
+        let e = quantize::<Truncate, Saturate, UFx, Fixed<6, 4>>(a);
+        signal((b, c, d, e))
+    }
+
+    let args = exhaustive::<8>()
+        .into_iter()
+        .map(|a| (red(a.as_fixed::<4, 4>()),));
+    test_kernel_vm_and_verilog::<do_stuff, _, _, _>(do_stuff, args)?;
+    Ok(())
+}
+
+#[test]
+fn test_fixed_arithmetic() -> miette::Result<()> {
+    #[kernel]
+    fn do_stuff(
+        a: Signal<s4, Red>,
+        b: Signal<s4, Red>,
+    ) -> Signal<(SignedFixed<3, 2>, SignedFixed<4, 4>, s5), Red> {
+        let a: SignedFixed<2, 2> = a.val().as_signed_fixed();
+        let b: SignedFixed<2, 2> = b.val().as_signed_fixed();
+        let c: SignedFixed<3, 2> = a.xadd(b);
+        let d: SignedFixed<4, 4> = a.xmul(b);
+        let e: SignedFixed<3, 2> = a.xsub(b);
+        let e: s5 = e.as_signed_bits();
+        signal((c, d, e))
+    }
+
+    test_kernel_vm_and_verilog::<do_stuff, _, _, _>(do_stuff, tuple_pair_sn_red::<4>())?;
+    Ok(())
+}
@@ -29,6 +29,28 @@ fn test_simple_fixture_example() -> miette::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_simple_fixture_vhdl() -> miette::Result<()> {
+    let expect = expect_test::expect_file!["expect/fixture_adder_vhdl.expect"];
+    #[kernel]
+    fn adder(a: Signal<(b4, b4), Red>) -> Signal<b4, Red> {
+        let (a, b) = a.val();
+        signal(a + b)
+    }
+
+    let adder = AsyncFunc::new::<adder>()?;
+    let mut fixture = Fixture::new("adder_top", adder);
+    let input = Signal::<(b4, b4), Red>::dont_care();
+    let output = Signal::<b4, Red>::dont_care();
+    bind!(fixture, a -> input.val().0);
+    bind!(fixture, b -> input.val().1);
+    bind!(fixture, sum <- output.val());
+    let vhdl = fixture.vhdl()?;
+    expect.assert_eq(&vhdl.as_vhdl());
+    vhdl.checked()?;
+    Ok(())
+}
+
 #[test]
 fn test_bind_macro_with_expressions() -> miette::Result<()> {
     #[kernel]
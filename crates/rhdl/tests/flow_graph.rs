@@ -137,6 +137,7 @@ where
     tm_rtl.run_iverilog()?;
     let tm_fg = test_bench.ntl(uut, &TestBenchOptions::default())?;
     tm_fg.run_iverilog()?;
+    test_bench.netlist_sim(uut, &TestBenchOptions::default())?;
     Ok(())
 }
 
@@ -150,6 +151,7 @@ where
     tm_rtl.run_iverilog()?;
     let tm_fg = test_bench.ntl(uut, &TestBenchOptions::default())?;
     tm_fg.run_iverilog()?;
+    test_bench.netlist_sim(uut, &TestBenchOptions::default())?;
     Ok(())
 }
 
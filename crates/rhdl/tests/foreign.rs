@@ -0,0 +1,144 @@
+use rhdl::core::circuit::foreign::ForeignError;
+use rhdl::prelude::*;
+
+#[cfg(test)]
+mod common;
+#[cfg(test)]
+use common::*;
+
+fn adder_module() -> miette::Result<vlog::ModuleDef> {
+    Ok(parse_quote_miette! {
+        module adder(input wire [3:0] a, input wire [3:0] b, output wire [3:0] y);
+            assign y = a + b;
+        endmodule
+    }?)
+}
+
+#[test]
+fn test_foreign_adder_circuit() -> miette::Result<()> {
+    type I = Signal<(b4, b4), Red>;
+    type O = Signal<b4, Red>;
+    let i = I::dont_care();
+    let o = O::dont_care();
+    let uut = ForeignBuilder::<I, O>::new(adder_module()?)
+        .input("a", path!(i.val().0))
+        .input("b", path!(i.val().1))
+        .output("y", path!(o.val()))
+        .circuit()?
+        .with_model((), |i: I, _| signal(i.val().0 + i.val().1));
+    let inputs = exhaustive::<4>()
+        .into_iter()
+        .flat_map(|x| exhaustive::<4>().into_iter().map(move |y| (x, y)))
+        .map(signal::<_, Red>)
+        .enumerate()
+        .map(|(ndx, val)| timed_sample((ndx * 100) as u64, val));
+    let test_bench = uut.run(inputs).collect::<TestBench<_, _>>();
+    test_bench
+        .rtl(&uut, &TestBenchOptions::default())?
+        .run_iverilog()?;
+    test_bench
+        .ntl(&uut, &TestBenchOptions::default())?
+        .run_iverilog()?;
+    Ok(())
+}
+
+#[test]
+fn test_foreign_register_synchronous() -> miette::Result<()> {
+    let module: vlog::ModuleDef = parse_quote_miette! {
+        module reg4(input wire clk, input wire rst, input wire [3:0] d, output reg [3:0] q);
+            initial begin
+                q = 4'b0000;
+            end
+            always @(posedge clk) begin
+                if (rst) begin
+                    q <= 4'b0000;
+                end else begin
+                    q <= d;
+                end
+            end
+        endmodule
+    }?;
+    let uut = ForeignBuilder::<b4, b4>::new(module)
+        .clock("clk")
+        .reset("rst")
+        .input("d", Path::default())
+        .output("q", Path::default())
+        .synchronous()?
+        .with_model(
+            (false, false, b4::default(), b4::default()),
+            |cr: ClockReset, d: b4, (clock, reset, next, current)| {
+                if !cr.clock.raw() {
+                    *next = d;
+                    *reset = cr.reset.raw();
+                }
+                if cr.clock.raw() && !*clock {
+                    *current = if *reset { bits(0) } else { *next };
+                }
+                *clock = cr.clock.raw();
+                *current
+            },
+        );
+    let inputs = exhaustive::<4>().with_reset(4).clock_pos_edge(100);
+    let test_bench = uut.run(inputs).collect::<SynchronousTestBench<_, _>>();
+    test_bench
+        .rtl(&uut, &TestBenchOptions::default())?
+        .run_iverilog()?;
+    test_bench
+        .ntl(&uut, &TestBenchOptions::default())?
+        .run_iverilog()?;
+    Ok(())
+}
+
+#[test]
+fn test_foreign_port_width_is_checked() -> miette::Result<()> {
+    let i = <(b4, b8)>::dont_care();
+    let res = ForeignBuilder::<(b4, b8), b4>::new(adder_module()?)
+        .input("a", path!(i.0))
+        .input("b", path!(i.1))
+        .output("y", Path::default())
+        .synchronous();
+    assert!(matches!(
+        res.err(),
+        Some(RHDLError::ForeignError(ForeignError::WidthMismatch {
+            port_width: 4,
+            field_width: 8,
+            ..
+        }))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_foreign_port_map_is_checked() -> miette::Result<()> {
+    let i = <(b4, b4)>::dont_care();
+    // Input b is never driven
+    let res = ForeignBuilder::<(b4, b4), b4>::new(adder_module()?)
+        .input("a", path!(i.0))
+        .output("y", Path::default())
+        .synchronous();
+    assert!(matches!(
+        res.err(),
+        Some(RHDLError::ForeignError(ForeignError::UnboundInput { .. }))
+    ));
+    // Outputs must be fully driven
+    let res = ForeignBuilder::<(b4, b4), (b4, b4)>::new(adder_module()?)
+        .input("a", path!(i.0))
+        .input("b", path!(i.1))
+        .output("y", Path::default().tuple_index(0))
+        .synchronous();
+    assert!(matches!(
+        res.err(),
+        Some(RHDLError::ForeignError(ForeignError::OutputsNotCovered(_)))
+    ));
+    // Circuits have no clock
+    let res = ForeignBuilder::<Signal<(b4, b4), Red>, Signal<b4, Red>>::new(adder_module()?)
+        .clock("a")
+        .circuit();
+    assert!(matches!(
+        res.err(),
+        Some(RHDLError::ForeignError(
+            ForeignError::ClockResetNotAvailable { .. }
+        ))
+    ));
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use rhdl::core::sim::testbench::kernel::test_kernel_vm_and_verilog;
+use rhdl::prelude::*;
+
+#[cfg(test)]
+mod common;
+#[cfg(test)]
+use common::*;
+
+#[test]
+fn test_match_guard_on_constant_arms() -> miette::Result<()> {
+    #[kernel]
+    fn do_stuff(a: Signal<b4, Red>, b: Signal<b4, Red>) -> Signal<b4, Red> {
+        let a = a.val();
+        let b = b.val();
+        signal(match a.raw() {
+            0 if b > 4 => b,
+            0 => bits(1),
+            3 if b == a => bits(2),
+            _ if b == 0 => bits(3),
+            _ => a + b,
+        })
+    }
+
+    test_kernel_vm_and_verilog::<do_stuff, _, _, _>(do_stuff, tuple_pair_b4_red())?;
+    Ok(())
+}
+
+#[test]
+fn test_match_guard_uses_pattern_bindings() -> miette::Result<()> {
+    #[derive(PartialEq, Debug, Digital, Copy, Default, Clone)]
+    pub enum SimpleEnum {
+        #[default]
+        Init,
+        Run(b8),
+        Point {
+            x: b4,
+            y: b8,
+        },
+        Boom,
+    }
+
+    #[kernel]
+    fn do_stuff<C: Domain>(state: Signal<SimpleEnum, C>) -> Signal<b8, C> {
+        let x = state.val();
+        signal(match x {
+            SimpleEnum::Init => bits(1),
+            SimpleEnum::Run(x) if x > 4 => x,
+            SimpleEnum::Run(_) => bits(2),
+            SimpleEnum::Point { x, y } if x == 1 => y,
+            SimpleEnum::Point { x: _, y } => y + 1,
+            SimpleEnum::Boom => bits(7),
+        })
+    }
+
+    let samples = vec![
+        SimpleEnum::Init,
+        SimpleEnum::Run(bits(1)),
+        SimpleEnum::Run(bits(5)),
+        SimpleEnum::Run(bits(9)),
+        SimpleEnum::Point {
+            x: bits(1),
+            y: bits(2),
+        },
+        SimpleEnum::Point {
+            x: bits(3),
+            y: bits(9),
+        },
+        SimpleEnum::Boom,
+    ];
+    test_kernel_vm_and_verilog::<do_stuff<Red>, _, _, _>(
+        do_stuff,
+        samples.into_iter().map(red).map(|x| (x,)),
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_match_guard_on_option() -> miette::Result<()> {
+    #[kernel]
+    fn do_stuff(a: Signal<b4, Red>, b: Signal<b4, Red>) -> Signal<b4, Red> {
+        let a = a.val();
+        let b = b.val();
+        let c = if a.any() { Some(a) } else { None };
+        signal(match c {
+            Some(x) if x > b => x - b,
+            Some(x) => x,
+            None => b,
+        })
+    }
+
+    test_kernel_vm_and_verilog::<do_stuff, _, _, _>(do_stuff, tuple_pair_b4_red())?;
+    Ok(())
+}
+
+#[test]
+fn test_match_guard_with_local_rebinding() -> miette::Result<()> {
+    #[kernel]
+    fn do_stuff(a: Signal<b4, Red>, b: Signal<b4, Red>) -> Signal<(b4, b4), Red> {
+        let a = a.val();
+        let b = b.val();
+        let mut c = bits(0);
+        let mut d = b;
+        match a.raw() {
+            1 if b.any() => {
+                c = b;
+            }
+            1 => {
+                d = bits(3);
+            }
+            2 if b.all() => {
+                c = a;
+                d = a;
+            }
+            _ => {}
+        }
+        signal((c, d))
+    }
+
+    test_kernel_vm_and_verilog::<do_stuff, _, _, _>(do_stuff, tuple_pair_b4_red())?;
+    Ok(())
+}
+
+#[test]
+fn test_match_guard_lowers_to_select_chain() -> miette::Result<()> {
+    #[kernel]
+    fn foo(a: b4, b: b4) -> b4 {
+        match a.raw() {
+            0 if b.any() => b,
+            1 => a,
+            _ => bits(2),
+        }
+    }
+
+    let rtl = compile_design::<foo>(CompilationMode::Synchronous)?;
+    assert!(
+        !rtl.ops
+            .iter()
+            .any(|op| matches!(op.op, rhdl::core::rtl::spec::OpCode::Case(_)))
+    );
+    Ok(())
+}
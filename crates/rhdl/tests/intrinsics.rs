@@ -0,0 +1,98 @@
+use rhdl::prelude::*;
+
+#[cfg(test)]
+mod common;
+
+#[cfg(test)]
+use common::*;
+use rhdl::core::sim::testbench::kernel::test_kernel_vm_and_verilog;
+
+type Overflowing<T> = ((T, bool), (T, bool));
+
+#[test]
+fn test_saturating_unsigned() -> miette::Result<()> {
+    #[kernel]
+    fn do_stuff(a: Signal<b8, Red>, b: Signal<b8, Red>) -> Signal<(b8, b8), Red> {
+        let a = a.val();
+        let b = b.val();
+        signal((a.saturating_add(b), a.saturating_sub(b)))
+    }
+
+    test_kernel_vm_and_verilog::<do_stuff, _, _, _>(do_stuff, tuple_pair_b8_red())?;
+    Ok(())
+}
+
+#[test]
+fn test_saturating_signed() -> miette::Result<()> {
+    #[kernel]
+    fn do_stuff(a: Signal<s8, Red>, b: Signal<s8, Red>) -> Signal<(s8, s8), Red> {
+        let a = a.val();
+        let b = b.val();
+        signal((a.saturating_add(b), a.saturating_sub(b)))
+    }
+
+    test_kernel_vm_and_verilog::<do_stuff, _, _, _>(do_stuff, tuple_pair_s8_red())?;
+    Ok(())
+}
+
+#[test]
+fn test_overflowing_unsigned() -> miette::Result<()> {
+    #[kernel]
+    fn do_stuff(a: Signal<b8, Red>, b: Signal<b8, Red>) -> Signal<Overflowing<b8>, Red> {
+        let a = a.val();
+        let b = b.val();
+        signal((a.overflowing_add(b), a.overflowing_sub(b)))
+    }
+
+    test_kernel_vm_and_verilog::<do_stuff, _, _, _>(do_stuff, tuple_pair_b8_red())?;
+    Ok(())
+}
+
+#[test]
+fn test_overflowing_signed() -> miette::Result<()> {
+    #[kernel]
+    fn do_stuff(a: Signal<s8, Red>, b: Signal<s8, Red>) -> Signal<Overflowing<s8>, Red> {
+        let a = a.val();
+        let b = b.val();
+        signal((a.overflowing_add(b), a.overflowing_sub(b)))
+    }
+
+    test_kernel_vm_and_verilog::<do_stuff, _, _, _>(do_stuff, tuple_pair_s8_red())?;
+    Ok(())
+}
+
+#[test]
+fn test_bit_counting_unsigned() -> miette::Result<()> {
+    #[kernel]
+    fn do_stuff(a: Signal<b7, Red>) -> Signal<(b7, b7, b7, b7), Red> {
+        let a = a.val();
+        signal((
+            a.count_ones(),
+            a.leading_zeros(),
+            a.trailing_zeros(),
+            a.reverse_bits(),
+        ))
+    }
+
+    let args = exhaustive::<7>().into_iter().map(|a| (red(a),));
+    test_kernel_vm_and_verilog::<do_stuff, _, _, _>(do_stuff, args)?;
+    Ok(())
+}
+
+#[test]
+fn test_bit_counting_signed() -> miette::Result<()> {
+    #[kernel]
+    fn do_stuff(a: Signal<s8, Red>) -> Signal<(b8, b8, b8, s8), Red> {
+        let a = a.val();
+        signal((
+            a.count_ones(),
+            a.leading_zeros(),
+            a.trailing_zeros(),
+            a.reverse_bits(),
+        ))
+    }
+
+    let args = exhaustive_signed::<8>().into_iter().map(|a| (red(a),));
+    test_kernel_vm_and_verilog::<do_stuff, _, _, _>(do_stuff, args)?;
+    Ok(())
+}
@@ -0,0 +1,161 @@
+#![allow(unused_variables)]
+#![allow(unused_assignments)]
+#![allow(unused_mut)]
+#![allow(unreachable_code)]
+#![allow(unused_must_use)]
+#![allow(dead_code)]
+
+use rhdl::prelude::*;
+
+#[cfg(test)]
+mod common;
+#[cfg(test)]
+use common::*;
+use rhdl::core::sim::testbench::kernel::test_kernel_vm_and_verilog_synchronous;
+
+#[test]
+fn test_method_call_on_struct() -> miette::Result<()> {
+    #[derive(PartialEq, Debug, Digital, Clone, Copy)]
+    pub struct Counter {
+        count: b8,
+        limit: b8,
+    }
+
+    #[kernel]
+    impl Counter {
+        pub fn is_full(self) -> bool {
+            self.count == self.limit
+        }
+        pub fn bump(mut self, amount: b8) -> Counter {
+            if !self.is_full() {
+                self.count += amount;
+            }
+            self
+        }
+    }
+
+    #[kernel]
+    fn update(a: b8, b: b8) -> (bool, b8) {
+        let c = Counter {
+            count: a,
+            limit: b8(0x80),
+        };
+        let c = c.bump(b);
+        (c.is_full(), c.count)
+    }
+
+    let samples = exhaustive::<8>()
+        .into_iter()
+        .flat_map(|a| [b8(0), b8(1), b8(0x7F)].map(|b| (a, b)));
+    test_kernel_vm_and_verilog_synchronous::<update, _, _, _>(update, samples)?;
+    Ok(())
+}
+
+#[test]
+fn test_method_call_on_generic_struct() -> miette::Result<()> {
+    #[derive(PartialEq, Debug, Digital, Clone, Copy)]
+    pub struct Pair<const N: usize>
+    where
+        rhdl::bits::W<N>: BitWidth,
+    {
+        a: Bits<N>,
+        b: Bits<N>,
+    }
+
+    #[kernel]
+    impl<const N: usize> Pair<N>
+    where
+        rhdl::bits::W<N>: BitWidth,
+    {
+        pub fn larger(self) -> Bits<N> {
+            if self.a > self.b { self.a } else { self.b }
+        }
+    }
+
+    #[kernel]
+    fn biggest<const N: usize>(a: Bits<N>, b: Bits<N>) -> Bits<N>
+    where
+        rhdl::bits::W<N>: BitWidth,
+    {
+        let p = Pair::<N> { a, b };
+        p.larger()
+    }
+
+    let samples = exhaustive::<4>()
+        .into_iter()
+        .flat_map(|a| exhaustive::<4>().into_iter().map(move |b| (a, b)));
+    test_kernel_vm_and_verilog_synchronous::<biggest<4>, _, _, _>(biggest, samples)?;
+    Ok(())
+}
+
+#[test]
+fn test_method_call_on_enum() -> miette::Result<()> {
+    #[derive(PartialEq, Debug, Digital, Clone, Copy, Default)]
+    pub enum Mode {
+        #[default]
+        Idle,
+        Run(b8),
+    }
+
+    #[kernel]
+    impl Mode {
+        pub fn payload(self) -> b8 {
+            match self {
+                Mode::Idle => b8(0),
+                Mode::Run(x) => x,
+            }
+        }
+    }
+
+    #[kernel]
+    fn run(a: b8) -> b8 {
+        let m = if a.any() { Mode::Run(a) } else { Mode::Idle };
+        m.payload() + 1
+    }
+
+    test_kernel_vm_and_verilog_synchronous::<run, _, _, _>(
+        run,
+        exhaustive::<8>().into_iter().map(|x| (x,)),
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_method_call_resolved_by_receiver_type() -> miette::Result<()> {
+    #[derive(PartialEq, Debug, Digital, Clone, Copy)]
+    pub struct Low {
+        x: b4,
+    }
+
+    #[derive(PartialEq, Debug, Digital, Clone, Copy)]
+    pub struct High {
+        x: b4,
+    }
+
+    #[kernel]
+    impl Low {
+        pub fn value(self) -> b8 {
+            self.x.resize()
+        }
+    }
+
+    #[kernel]
+    impl High {
+        pub fn value(self) -> b8 {
+            self.x.resize::<8>() << 4
+        }
+    }
+
+    #[kernel]
+    fn join(a: b4, b: b4) -> b8 {
+        let lo = Low { x: a };
+        let hi = High { x: b };
+        lo.value() | hi.value()
+    }
+
+    let samples = exhaustive::<4>()
+        .into_iter()
+        .flat_map(|a| exhaustive::<4>().into_iter().map(move |b| (a, b)));
+    test_kernel_vm_and_verilog_synchronous::<join, _, _, _>(join, samples)?;
+    Ok(())
+}
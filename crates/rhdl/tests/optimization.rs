@@ -1,5 +1,9 @@
 use rhdl::core::{
-    rtl::spec::AluBinary, rtl::spec::Binary, sim::testbench::kernel::test_kernel_vm_and_verilog,
+    compiler::{OptimizationLevel, optimize_ntl_with_level},
+    ntl::from_rtl::build_ntl_from_rtl,
+    rtl::spec::AluBinary,
+    rtl::spec::Binary,
+    sim::testbench::kernel::test_kernel_vm_and_verilog,
 };
 use rhdl::prelude::*;
 #[cfg(test)]
@@ -493,6 +497,24 @@ fn test_lower_multiplies_to_shifts() -> miette::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_lower_divides_to_shifts() -> miette::Result<()> {
+    #[kernel]
+    fn foo(a: b8, b: s8) -> (b8, b8, s8, s8) {
+        (a / 16, a % 16, b / 4, b % 4)
+    }
+
+    let rtl = compile_design::<foo>(CompilationMode::Synchronous)?;
+    assert!(rtl.ops.iter().all(|op| !matches!(
+        op.op,
+        rhdl::core::rtl::spec::OpCode::Binary(Binary {
+            op: AluBinary::Div | AluBinary::Rem,
+            ..
+        })
+    )));
+    Ok(())
+}
+
 #[test]
 #[allow(unused_variables)]
 fn test_empty_indices_dropped() -> miette::Result<()> {
@@ -549,3 +571,203 @@ fn test_empty_case_dropped() -> miette::Result<()> {
     assert!(no_empty_operands(&rtl));
     Ok(())
 }
+
+fn count_rtl_ops(rtl: &rhdl::core::rtl::Object, op: AluBinary) -> usize {
+    rtl.ops
+        .iter()
+        .filter(|lop| matches!(&lop.op, rhdl::core::rtl::spec::OpCode::Binary(binary) if binary.op == op))
+        .count()
+}
+
+#[test]
+fn test_common_subexpressions_in_branches_are_shared() -> miette::Result<()> {
+    use rhdl::core::compiler::driver::{compile_design_stage1, compile_design_stage2};
+    use rhdl::core::rhif::spec::OpCode;
+
+    #[kernel]
+    fn foo(a: b8, b: b8, c: bool) -> (b8, b8) {
+        if c { (a + b, a ^ b) } else { (b + a, a ^ b) }
+    }
+
+    let rhif = compile_design_stage1::<foo>(CompilationMode::Synchronous)?;
+    let count_rhif_ops = |op: AluBinary| {
+        rhif.ops
+            .iter()
+            .filter(|lop| matches!(&lop.op, OpCode::Binary(binary) if binary.op == op.into()))
+            .count()
+    };
+    let rtl = compile_design_stage2(&rhif)?;
+    for op in [AluBinary::Add, AluBinary::BitXor] {
+        assert_eq!(count_rhif_ops(op), 2);
+        assert_eq!(count_rtl_ops(&rtl, op), 1);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_common_subexpressions_in_netlist_are_shared() -> miette::Result<()> {
+    use rhdl::core::ntl::spec::{Binary, BinaryOp, OpCode};
+
+    // The low nibble of the two XORs is the same logic, but
+    // that is only visible once the XOR is split into bits
+    #[kernel]
+    fn foo(a: b8, b: b8) -> (b8, b4) {
+        (a ^ b, a.resize::<4>() ^ b.resize::<4>())
+    }
+
+    let count_xors = |ntl: &rhdl::core::ntl::Object| {
+        ntl.ops
+            .iter()
+            .filter(|lop| {
+                matches!(
+                    lop.op,
+                    OpCode::Binary(Binary {
+                        op: BinaryOp::Xor,
+                        ..
+                    })
+                )
+            })
+            .count()
+    };
+    let rtl = compile_design::<foo>(CompilationMode::Synchronous)?;
+    let before = build_ntl_from_rtl(&rtl);
+    assert_eq!(count_xors(&before), 12);
+    let after = rhdl::core::compiler::optimize_ntl(before)?;
+    assert_eq!(count_xors(&after), 8);
+    Ok(())
+}
+
+fn count_ntl_logic(ntl: &rhdl::core::ntl::Object) -> usize {
+    use rhdl::core::ntl::spec::OpCode;
+    ntl.ops
+        .iter()
+        .filter(|lop| {
+            matches!(
+                lop.op,
+                OpCode::Binary(_) | OpCode::Not(_) | OpCode::Select(_) | OpCode::Case(_)
+            )
+        })
+        .count()
+}
+
+#[test]
+fn test_redundant_netlist_logic_is_removed() -> miette::Result<()> {
+    #[kernel]
+    fn foo(a: b8, b: b8) -> b8 {
+        (a & b) | (a & !b)
+    }
+
+    let rtl = compile_design::<foo>(CompilationMode::Synchronous)?;
+    let ntl = optimize_ntl_with_level(build_ntl_from_rtl(&rtl), OptimizationLevel::Aggressive)?;
+    assert_eq!(count_ntl_logic(&ntl), 0);
+    Ok(())
+}
+
+#[test]
+fn test_x_propagation_is_preserved_by_default() -> miette::Result<()> {
+    use rhdl::core::ntl::vm::NetListVM;
+
+    #[kernel]
+    fn foo(a: b4, b: b4) -> b4 {
+        (a & b) | (a & !b)
+    }
+
+    let rtl = compile_design::<foo>(CompilationMode::Synchronous)?;
+    let ntl = build_ntl_from_rtl(&rtl);
+    let a = b4(0b1010).bin();
+    let b = [BitX::X; 4];
+    let before = NetListVM::new(&ntl)?.step(&[&a, &b])?;
+    assert_eq!(before, vec![BitX::Zero, BitX::X, BitX::Zero, BitX::X]);
+    let after = rhdl::core::compiler::optimize_ntl(ntl.clone())?;
+    assert_eq!(NetListVM::new(&after)?.step(&[&a, &b])?, before);
+    // The and-inverter graph resolves the X, since it simplifies the
+    // expression to `a`
+    let aggressive = optimize_ntl_with_level(ntl, OptimizationLevel::Aggressive)?;
+    assert_eq!(NetListVM::new(&aggressive)?.step(&[&a, &b])?, a.to_vec());
+    Ok(())
+}
+
+#[test]
+fn test_aig_optimized_netlist_matches_kernel() -> miette::Result<()> {
+    use rhdl::core::ntl::vm::NetListVM;
+
+    mod ops {
+        use super::*;
+        pub const SAME: b2 = b2(0);
+        pub const AND: b2 = b2(1);
+        pub const LEFT: b2 = b2(2);
+    }
+
+    #[kernel]
+    fn foo(a: b4, b: b4, c: b2) -> b4 {
+        match c {
+            ops::SAME => (a & b) | (a & !b),
+            ops::AND => !(!a | !b),
+            ops::LEFT => (a ^ b) ^ b,
+            _ => (a & b) ^ (a | b),
+        }
+    }
+
+    let rtl = compile_design::<foo>(CompilationMode::Synchronous)?;
+    let ntl = optimize_ntl_with_level(build_ntl_from_rtl(&rtl), OptimizationLevel::Aggressive)?;
+    // Each bit needs an AND and an XOR feeding the case statement
+    assert_eq!(count_ntl_logic(&ntl), 12);
+    let mut vm = NetListVM::new(&ntl)?;
+    for a in exhaustive::<4>() {
+        for b in exhaustive::<4>() {
+            for c in exhaustive::<2>() {
+                let output = vm.step(&[&a.bin(), &b.bin(), &c.bin()])?;
+                assert_eq!(output, foo(a, b, c).bin().to_vec());
+            }
+        }
+    }
+    Ok(())
+}
+
+#[kernel]
+fn pass(_cr: ClockReset, a: b8) -> b8 {
+    a
+}
+
+#[derive(Clone, Synchronous, SynchronousDQ)]
+pub struct Redundant {
+    inner: Func<b8, b8>,
+}
+
+impl SynchronousIO for Redundant {
+    type I = (b8, b8);
+    type O = b8;
+    type Kernel = redundant;
+}
+
+#[kernel]
+pub fn redundant(_cr: ClockReset, i: (b8, b8), q: RedundantQ) -> (b8, RedundantD) {
+    let mut d = RedundantD::dont_care();
+    d.inner = (i.0 & i.1) | (i.0 & !i.1);
+    (q.inner, d)
+}
+
+#[test]
+fn test_design_reports_shrink_when_aggressive() -> miette::Result<()> {
+    use rhdl::core::circuit::{resources, timing};
+    use rhdl::core::compiler::with_optimization_level;
+
+    let uut = Redundant {
+        inner: Func::try_new::<pass>()?,
+    };
+    let report = || -> miette::Result<(usize, f64)> {
+        let luts = resources::estimate_resources(&uut, &resources::LutModel::default())?
+            .total()
+            .luts;
+        let delay = timing::critical_path(&uut, &timing::UnitDelay)?.map_or(0.0, |p| p.delay);
+        Ok((luts, delay))
+    };
+    let (luts, delay) = report()?;
+    let (aggressive_luts, aggressive_delay) =
+        with_optimization_level(OptimizationLevel::Aggressive, report)?;
+    assert!(aggressive_luts < luts);
+    assert!(aggressive_delay < delay);
+    // The level is restored afterwards
+    assert_eq!(report()?, (luts, delay));
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use rhdl::prelude::*;
+
+#[kernel]
+fn dot(cr: ClockReset, a: ([b8; 4], [b8; 4])) -> b8 {
+    let sum = a.0[0] * a.1[0] + a.0[1] * a.1[1] + a.0[2] * a.1[2] + a.0[3] * a.1[3];
+    if cr.reset.any() { bits(0) } else { sum }
+}
+
+type Input = ([b8; 4], [b8; 4]);
+
+fn stream() -> impl Iterator<Item = TimedSample<(ClockReset, Input)>> + Clone {
+    (0..100_u128)
+        .map(|n| {
+            let x = [n, n * 3, n * 7, n * 11].map(|v| bits(v & 0xff));
+            let y = [255 - n, n * 5, n + 17, n * 13].map(|v| bits(v & 0xff));
+            (x, y)
+        })
+        .with_reset(2)
+        .clock_pos_edge(100)
+}
+
+#[test]
+fn test_pipelined_matches_delayed_func() -> miette::Result<()> {
+    let func = Func::try_new::<dot>()?;
+    let uut = Pipelined::<Input, b8, 3>::try_new::<dot>()?;
+    let expected = func
+        .run(stream())
+        .synchronous_sample()
+        .map(|t| t.output)
+        .collect::<Vec<_>>();
+    let actual = uut
+        .run(stream())
+        .synchronous_sample()
+        .map(|t| t.output)
+        .collect::<Vec<_>>();
+    // The reset is delayed along with the input, so the outputs match
+    // from the first cycle that has left the pipeline
+    assert_eq!(actual[3..], expected[..expected.len() - 3]);
+    Ok(())
+}
+
+#[test]
+fn test_pipelined_netlist_matches_sim() -> miette::Result<()> {
+    let uut = Pipelined::<Input, b8, 3>::try_new::<dot>()?;
+    let tb = uut.run(stream()).collect::<SynchronousTestBench<_, _>>();
+    // Each clock cycle has two samples, and the first three cycles are not meaningful
+    tb.netlist_sim(&uut, &TestBenchOptions::default().skip(6))?;
+    Ok(())
+}
+
+#[test]
+fn test_pipelined_stages_are_balanced() -> miette::Result<()> {
+    let func = timing::critical_path(&Func::try_new::<dot>()?, &timing::WidthDelay)?.unwrap();
+    let uut = Pipelined::<Input, b8, 3>::try_new::<dot>()?;
+    let piped = timing::critical_path(&uut, &timing::WidthDelay)?.unwrap();
+    assert!(piped.delay * 2.0 < func.delay);
+    // Balancing by logic depth gives a different split
+    let uut = uut.with_delay_model(timing::UnitDelay);
+    let depth = timing::critical_path(&uut, &timing::UnitDelay)?.unwrap();
+    let unpiped = timing::critical_path(&Func::try_new::<dot>()?, &timing::UnitDelay)?.unwrap();
+    assert!(depth.delay < unpiped.delay);
+    // Each stage boundary is a rank of registers
+    let descriptor = uut.descriptor("top".into())?;
+    assert_eq!(descriptor.netlist()?.black_boxes.len(), 3);
+    Ok(())
+}
+
+#[test]
+fn test_pipelined_verilog() -> miette::Result<()> {
+    let uut = Pipelined::<Input, b8, 2>::try_new::<dot>()?;
+    let tb = uut.run(stream()).collect::<SynchronousTestBench<_, _>>();
+    let options = TestBenchOptions::default().skip(4);
+    tb.rtl(&uut, &options)?.run_iverilog()?;
+    tb.ntl(&uut, &options)?.run_iverilog()?;
+    Ok(())
+}
+
+#[test]
+fn test_pipelined_holds_without_clock_enable() -> miette::Result<()> {
+    let uut = ClockEnable::new(ClockEnable::new(
+        Pipelined::<Input, b8, 2>::try_new::<dot>()?
+    ));
+    let input = stream().map(|sample| {
+        let n = sample.time / 100;
+        sample.map(|(cr, i)| (cr, (n % 8 < 6, (n % 3 != 0, i))))
+    });
+    let output = uut
+        .run(input.clone())
+        .synchronous_sample()
+        .collect::<Vec<_>>();
+    // The pipeline only advances when both enables are high
+    for pair in output.windows(2) {
+        let (outer, (inner, _)) = pair[0].input.1;
+        if !(outer && inner) {
+            assert_eq!(pair[1].output, pair[0].output);
+        }
+    }
+    let hdl = uut.descriptor("top".into())?.hdl()?.modules.pretty();
+    assert!(hdl.contains("& ce)"));
+    let tb = uut.run(input).collect::<SynchronousTestBench<_, _>>();
+    let options = TestBenchOptions::default().skip(6);
+    tb.netlist_sim(&uut, &options)?;
+    tb.rtl(&uut, &options)?.run_iverilog()?;
+    tb.ntl(&uut, &options)?.run_iverilog()?;
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use rhdl::prelude::*;
+
+#[kernel]
+fn bump(cr: ClockReset, i: b8) -> b8 {
+    if cr.reset.any() { bits(0) } else { i + 1 }
+}
+
+// The reset line is low for the first cycle
+fn active_low_stream() -> impl Iterator<Item = TimedSample<(ClockReset, b8)>> + Clone {
+    (0..20)
+        .map(bits)
+        .with_reset(1)
+        .clock_pos_edge(100)
+        .map(|mut sample| {
+            sample.value.0.reset = reset(!sample.value.0.reset.raw());
+            sample
+        })
+}
+
+#[test]
+fn test_kernels_see_an_active_high_reset() -> miette::Result<()> {
+    let uut = ResetStyled::new(
+        Func::try_new::<bump>()?,
+        ResetStyle::synchronous().active_low(),
+    );
+    let output = uut.run(active_low_stream()).collect::<Vec<_>>();
+    // The output is zero exactly when the reset line is low
+    assert!(
+        output
+            .iter()
+            .all(|t| (t.output == bits(0)) != t.input.0.reset.raw())
+    );
+    let tb = output.into_iter().collect::<SynchronousTestBench<_, _>>();
+    tb.netlist_sim(&uut, &TestBenchOptions::default())?;
+    tb.rtl(&uut, &TestBenchOptions::default())?.run_iverilog()?;
+    tb.ntl(&uut, &TestBenchOptions::default())?.run_iverilog()?;
+    Ok(())
+}
+
+#[test]
+fn test_reset_style_is_scoped_to_the_wrapper() -> miette::Result<()> {
+    let uut = ResetStyled::new(
+        Func::try_new::<bump>()?,
+        ResetStyle::asynchronous().active_low(),
+    );
+    let hdl = uut.descriptor("top".into())?.hdl()?.modules.pretty();
+    assert!(hdl.contains("kernel_bump({~clock_reset[1], clock_reset[0]}, i)"));
+    assert_eq!(reset_style(), ResetStyle::default());
+    let hdl = Func::try_new::<bump>()?
+        .descriptor("top".into())?
+        .hdl()?
+        .modules
+        .pretty();
+    assert!(hdl.contains("kernel_bump(clock_reset, i)"));
+    Ok(())
+}